@@ -0,0 +1,153 @@
+#![allow(unused_crate_dependencies, missing_docs)]
+//! Compares lazy and eager compilation of nested functions.
+//!
+//! The `Compilation` groups only time the compilation of the top level code, while the
+//! `Evaluation` groups time the compilation and evaluation of each script, which includes
+//! compiling the functions its top level code calls. Besides the criterion timings, the peak
+//! and retained memory of compiling each script is printed once per compilation mode.
+
+use boa_engine::{Context, Source, optimizer::OptimizerOptions, script::Script};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::RefCell,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+/// An allocator that keeps track of the number of live bytes and their peak.
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+static PEAK_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+fn track(delta: isize) {
+    let live = LIVE_BYTES.fetch_add(delta, Ordering::Relaxed) + delta;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+// SAFETY: all calls are forwarded to the system allocator.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout.size() as isize);
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::alloc`.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-(layout.size() as isize));
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::dealloc`.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        track(new_size as isize - layout.size() as isize);
+        // SAFETY: the caller upholds the contract of `GlobalAlloc::realloc`.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn context(lazy: bool) -> Context {
+    let mut context = Context::default();
+    context.set_optimizer_options(OptimizerOptions::empty());
+    context.set_lazy_compilation(lazy);
+    context
+}
+
+fn scripts() -> Vec<PathBuf> {
+    let scripts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/v8-benches");
+
+    let mut scripts: Vec<_> = std::fs::read_dir(&scripts_dir)
+        .unwrap()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "js"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+fn bench_compilation(c: &mut Criterion) {
+    for path in scripts() {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let name = path.file_stem().unwrap().display().to_string();
+
+        let mut group = c.benchmark_group(format!("Compilation/{name}"));
+
+        for (mode, lazy) in [("Lazy", true), ("Eager", false)] {
+            let context = &mut context(lazy);
+
+            // Measure the peak memory while parsing and compiling the script, and
+            // the memory retained afterwards, which includes the syntax trees
+            // kept around for uncompiled functions.
+            let before = LIVE_BYTES.load(Ordering::Relaxed);
+            PEAK_BYTES.store(before, Ordering::Relaxed);
+            let script = Script::parse(Source::from_bytes(&code), None, context).unwrap();
+            script.codeblock(context).unwrap();
+            let retained = LIVE_BYTES.load(Ordering::Relaxed) - before;
+            let peak = PEAK_BYTES.load(Ordering::Relaxed) - before;
+            drop(script);
+            eprintln!(
+                "{name}: {mode} compilation peaks at {:.1} KiB and retains {:.1} KiB",
+                peak as f64 / 1024.0,
+                retained as f64 / 1024.0
+            );
+
+            // Only the compilation is measured, parsing happens in the setup.
+            let context = RefCell::new(context);
+            group.bench_function(mode, |b| {
+                b.iter_batched(
+                    || {
+                        let context = &mut *context.borrow_mut();
+                        Script::parse(Source::from_bytes(&code), None, context).unwrap()
+                    },
+                    |script| script.codeblock(&mut context.borrow_mut()).unwrap(),
+                    BatchSize::SmallInput,
+                );
+            });
+        }
+
+        group.finish();
+    }
+}
+
+fn bench_evaluation(c: &mut Criterion) {
+    for path in scripts() {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let name = path.file_stem().unwrap().display().to_string();
+
+        let mut group = c.benchmark_group(format!("Evaluation/{name}"));
+
+        for (mode, lazy) in [("Lazy", true), ("Eager", false)] {
+            group.bench_function(mode, |b| {
+                b.iter_batched(
+                    || {
+                        let mut context = context(lazy);
+                        boa_runtime::register(
+                            boa_runtime::extensions::ConsoleExtension(boa_runtime::NullLogger),
+                            None,
+                            &mut context,
+                        )
+                        .expect("Runtime registration failed");
+                        let script =
+                            Script::parse(Source::from_bytes(&code), None, &mut context).unwrap();
+                        (context, script)
+                    },
+                    // The context is returned, so it is dropped outside of the measurement.
+                    |(mut context, script)| {
+                        script.evaluate(&mut context).unwrap();
+                        context
+                    },
+                    BatchSize::PerIteration,
+                );
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_compilation, bench_evaluation);
+criterion_main!(benches);
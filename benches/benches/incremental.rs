@@ -0,0 +1,75 @@
+#![allow(unused_crate_dependencies, missing_docs)]
+//! Compares incremental reparsing with parsing from scratch.
+//!
+//! For each script, `Parse` times a parse from scratch, `Reparse` times the reparse after an
+//! edit in the middle of the script, and `ScopeAnalysis` times the scope analysis of the whole
+//! script alone, which is also part of each reparse.
+
+use boa_ast::scope::Scope;
+use boa_interner::Interner;
+use boa_parser::parser::incremental::{Incremental, TextEdit};
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use std::path::Path;
+
+/// Creates an edit that inserts an empty line after the first line ending with a `;` past the
+/// middle of the source.
+fn edit(code: &str) -> TextEdit {
+    let middle = code.len() / 2;
+    let offset = code[middle..]
+        .find(";\n")
+        .map_or(middle, |index| middle + index + 2);
+    let start = code[..offset].encode_utf16().count();
+    TextEdit::new(start..start, "\n")
+}
+
+fn bench_incremental(c: &mut Criterion) {
+    let scripts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/v8-benches");
+
+    let mut scripts: Vec<_> = std::fs::read_dir(&scripts_dir)
+        .unwrap()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "js"))
+        .collect();
+    scripts.sort();
+
+    for path in scripts {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let name = path.file_stem().unwrap().display().to_string();
+        let edit = edit(&code);
+
+        let interner = &mut Interner::default();
+        let parsed = Incremental::parse_script(&code, &Scope::new_global(), interner).unwrap();
+
+        let mut group = c.benchmark_group(format!("Incremental/{name}"));
+
+        group.bench_function("Parse", |b| {
+            b.iter(|| Incremental::parse_script(&code, &Scope::new_global(), interner).unwrap());
+        });
+
+        group.bench_function("Reparse", |b| {
+            b.iter(|| {
+                parsed
+                    .reparse(&edit, &Scope::new_global(), interner)
+                    .unwrap()
+            });
+        });
+
+        group.bench_function("ScopeAnalysis", |b| {
+            b.iter_batched(
+                || parsed.ast().clone(),
+                |mut script| {
+                    script
+                        .analyze_scope(&Scope::new_global(), interner)
+                        .unwrap();
+                    script
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_incremental);
+criterion_main!(benches);
@@ -72,8 +72,13 @@ fn bench_scripts(c: &mut Criterion) {
             .unwrap_or_else(|| panic!("'main' is not a function in script: {}", path.display()))
             .clone();
 
+        // Run the jobs queued by async scripts within each iteration
         group.bench_function("Execution", |b| {
-            b.iter(|| function.call(&JsValue::undefined(), &[], context));
+            b.iter(|| {
+                let result = function.call(&JsValue::undefined(), &[], context);
+                context.run_jobs().unwrap();
+                result
+            });
         });
         group.finish();
     }
@@ -0,0 +1,73 @@
+#![allow(unused_crate_dependencies, missing_docs)]
+//! Compares creating a context and running a prelude script with restoring a snapshot taken
+//! after running it.
+//!
+//! Each v8 benchmark script is used as a prelude, since it only defines functions and objects
+//! until its `main` function is called. Restoring a snapshot still creates a context, since the
+//! intrinsics are not part of the snapshot.
+
+use boa_engine::{Context, Source};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::path::Path;
+
+#[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
+#[global_allocator]
+static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
+
+#[cfg(target_os = "macos")]
+#[global_allocator]
+static ALLOC: mimalloc_safe::MiMalloc = mimalloc_safe::MiMalloc;
+
+/// Creates a context with the `console` of the runtime, which the scripts use.
+fn new_context() -> Context {
+    let mut context = Context::default();
+    boa_runtime::register(
+        boa_runtime::extensions::ConsoleExtension(boa_runtime::NullLogger),
+        None,
+        &mut context,
+    )
+    .expect("Runtime registration failed");
+    context
+}
+
+fn bench_startup(c: &mut Criterion) {
+    c.bench_function("Startup/Context", |b| b.iter(new_context));
+
+    let scripts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scripts/v8-benches");
+    let mut scripts: Vec<_> = std::fs::read_dir(&scripts_dir)
+        .unwrap()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "js"))
+        .collect();
+    scripts.sort();
+
+    for path in scripts {
+        let code = std::fs::read_to_string(&path).unwrap();
+        let name = path.file_stem().unwrap().display().to_string();
+
+        let context = &mut new_context();
+        context.mark_snapshot_base();
+        context.eval(Source::from_bytes(&code)).unwrap();
+        let snapshot = context.snapshot().unwrap();
+
+        let mut group = c.benchmark_group(format!("Startup/{name}"));
+        group.bench_function("Evaluate", |b| {
+            b.iter(|| {
+                let context = &mut new_context();
+                context.eval(Source::from_bytes(&code)).unwrap();
+            });
+        });
+        // Reports the size of the snapshot as the throughput of restoring it.
+        group.throughput(Throughput::Bytes(snapshot.len() as u64));
+        group.bench_function("Restore", |b| {
+            b.iter(|| {
+                let context = &mut new_context();
+                context.restore_snapshot(&snapshot).unwrap();
+            });
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_startup);
+criterion_main!(benches);
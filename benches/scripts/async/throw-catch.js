@@ -0,0 +1,19 @@
+async function thrower(depth) {
+  if (depth > 0) {
+    return await thrower(depth - 1);
+  }
+  await null;
+  let caught = 0;
+  for (let i = 0; i < 10_000; i++) {
+    try {
+      throw new Error("oops");
+    } catch {
+      caught++;
+    }
+  }
+  return caught;
+}
+
+function main() {
+  thrower(30);
+}
@@ -0,0 +1,138 @@
+//! Reading and writing of Debug Adapter Protocol messages.
+//!
+//! Every message is a JSON object preceded by a `Content-Length` header, see
+//! <https://microsoft.github.io/debug-adapter-protocol/overview#base-protocol>.
+
+use serde_json::{Value, json};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener},
+    sync::{Arc, Mutex},
+};
+
+/// The receiving half of a connection to a client.
+pub(super) struct Reader {
+    input: Box<dyn BufRead + Send>,
+}
+
+impl Reader {
+    /// Reads the next message, returning `None` once the client closes the connection.
+    pub(super) fn read(&mut self) -> io::Result<Option<Value>> {
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':')
+                && name.eq_ignore_ascii_case("Content-Length")
+            {
+                content_length = Some(value.trim().parse::<usize>().map_err(io::Error::other)?);
+            }
+        }
+
+        let Some(content_length) = content_length else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing Content-Length header",
+            ));
+        };
+        let mut content = vec![0; content_length];
+        self.input.read_exact(&mut content)?;
+        serde_json::from_slice(&content)
+            .map(Some)
+            .map_err(io::Error::other)
+    }
+}
+
+struct WriterInner {
+    output: Box<dyn Write + Send>,
+    seq: u64,
+}
+
+/// The sending half of a connection to a client, which can be shared between threads.
+#[derive(Clone)]
+pub(super) struct Writer {
+    inner: Arc<Mutex<WriterInner>>,
+}
+
+impl Writer {
+    fn new(output: Box<dyn Write + Send>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(WriterInner { output, seq: 0 })),
+        }
+    }
+
+    /// Sends a message to the client.
+    ///
+    /// Errors are ignored: they mean that the client went away, which the reader notices.
+    fn send(&self, mut message: Value) {
+        let mut inner = self.inner.lock().expect("writer lock failed");
+        inner.seq += 1;
+        message["seq"] = inner.seq.into();
+
+        let content = message.to_string();
+        let output = &mut inner.output;
+        drop(
+            write!(output, "Content-Length: {}\r\n\r\n{content}", content.len())
+                .and_then(|()| output.flush()),
+        );
+    }
+
+    /// Sends a successful response to `request`.
+    pub(super) fn respond(&self, request: &Value, body: Value) {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+        });
+        response["body"] = body;
+        self.send(response);
+    }
+
+    /// Sends an error response to `request`.
+    pub(super) fn respond_error(&self, request: &Value, message: &str) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": message,
+        }));
+    }
+
+    /// Sends an event to the client.
+    pub(super) fn event(&self, event: &str, body: Value) {
+        let mut message = json!({ "type": "event", "event": event });
+        message["body"] = body;
+        self.send(message);
+    }
+}
+
+/// Connects to a client over the standard input and output.
+pub(super) fn stdio() -> (Reader, Writer) {
+    (
+        Reader {
+            input: Box::new(BufReader::new(io::stdin())),
+        },
+        Writer::new(Box::new(io::stdout())),
+    )
+}
+
+/// Waits for a client to connect to `port` on the local host.
+pub(super) fn tcp(port: u16) -> io::Result<(Reader, Writer)> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    eprintln!("Debug adapter listening on {}", listener.local_addr()?);
+    let (stream, _) = listener.accept()?;
+    Ok((
+        Reader {
+            input: Box::new(BufReader::new(stream.try_clone()?)),
+        },
+        Writer::new(Box::new(stream)),
+    ))
+}
@@ -0,0 +1,514 @@
+//! A Debug Adapter Protocol server, to debug scripts from editors like VS Code.
+//!
+//! The server is started with `--dap`, and talks to a client over the standard input and
+//! output, or over a TCP connection on the local host if a port is given. Requests are read on
+//! a separate thread and handled on the main thread, before the program runs, while it is
+//! paused, and after it finished. `pause` requests are handled right away by the reading
+//! thread, since the main thread can't handle requests while the program runs.
+//!
+//! See <https://microsoft.github.io/debug-adapter-protocol/specification>.
+
+mod connection;
+
+use crate::{Opt, evaluate_file, logger::SharedExternalPrinterLogger};
+use boa_engine::{
+    Context, JsObject, JsValue, Source,
+    debugger::{
+        Breakpoint, BreakpointId, Debugger, ExceptionPauseMode, PauseReason, PausedEvent,
+        ResumeAction, ScopeKind,
+    },
+    module::SimpleModuleLoader,
+    vm::SourcePath,
+};
+use color_eyre::{Result, eyre::WrapErr};
+use connection::Writer;
+use rustyline::ExternalPrinter;
+use serde_json::{Value, json};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::mpsc,
+    thread,
+};
+
+/// The identifier of the only thread of the program.
+const THREAD_ID: u64 = 1;
+
+/// Sends the output of the program to the client as `output` events.
+struct OutputPrinter(Writer);
+
+impl ExternalPrinter for OutputPrinter {
+    fn print(&mut self, msg: String) -> rustyline::Result<()> {
+        self.0
+            .event("output", json!({ "category": "stdout", "output": msg }));
+        Ok(())
+    }
+}
+
+/// What to do after handling a request.
+enum Flow {
+    /// Wait for the next request.
+    Wait,
+
+    /// Start running the program.
+    Start,
+
+    /// Resume the paused program.
+    Resume(ResumeAction),
+
+    /// End the session.
+    Disconnect,
+}
+
+/// The program to debug, set by a `launch` or `attach` request.
+struct Launch {
+    files: Vec<PathBuf>,
+    stop_on_entry: bool,
+}
+
+#[derive(Default)]
+struct State {
+    launch: Option<Launch>,
+
+    /// Set once the client sent its breakpoints with a `configurationDone` request.
+    configured: bool,
+
+    /// The breakpoints of each source, which are replaced by every `setBreakpoints` request.
+    breakpoints: HashMap<PathBuf, Vec<BreakpointId>>,
+
+    /// The objects the client can expand with a `variables` request, which are valid until
+    /// the program resumes. Their variables reference is their index plus one.
+    objects: Vec<JsObject>,
+
+    /// Set while a pause on the entry of the program is pending.
+    stopping_on_entry: bool,
+}
+
+struct Session {
+    writer: Writer,
+    requests: mpsc::Receiver<Value>,
+
+    /// The files to debug when the request doesn't name a program.
+    default_files: Vec<PathBuf>,
+
+    state: RefCell<State>,
+}
+
+/// Runs a debug adapter server, which debugs the files of the `launch` or `attach` request.
+///
+/// The server communicates over stdio, or over the given TCP port of the local host.
+pub(crate) fn run(
+    port: Option<u16>,
+    args: &Opt,
+    context: &mut Context,
+    loader: &SimpleModuleLoader,
+    printer: &SharedExternalPrinterLogger,
+) -> Result<()> {
+    let (mut reader, writer) = match port {
+        Some(port) => connection::tcp(port).wrap_err("could not accept a debugger client")?,
+        None => connection::stdio(),
+    };
+    printer.set(OutputPrinter(writer.clone()));
+
+    let (sender, requests) = mpsc::channel();
+    let pause_handle = context.debugger_pause_handle();
+    let pause_writer = writer.clone();
+    thread::spawn(move || {
+        while let Ok(Some(request)) = reader.read() {
+            if request["command"] == "pause" {
+                pause_handle.request_pause();
+                pause_writer.respond(&request, Value::Null);
+            } else if sender.send(request).is_err() {
+                break;
+            }
+        }
+    });
+
+    let session = Rc::new(Session {
+        writer,
+        requests,
+        default_files: args.files.clone(),
+        state: RefCell::default(),
+    });
+    context.attach_debugger(session.clone());
+
+    // Wait until the client sets the breakpoints and launches the program.
+    let launch = loop {
+        let Ok(request) = session.requests.recv() else {
+            return Ok(());
+        };
+        match session.handle(&request, None, context) {
+            Flow::Start => {
+                if let Some(launch) = session.state.borrow_mut().launch.take() {
+                    break launch;
+                }
+            }
+            Flow::Disconnect => return Ok(()),
+            Flow::Wait | Flow::Resume(_) => {}
+        }
+    };
+
+    if launch.stop_on_entry {
+        session.state.borrow_mut().stopping_on_entry = true;
+        context.debugger_pause_handle().request_pause();
+    }
+
+    let mut exit_code = 0;
+    for file in &launch.files {
+        if let Err(err) = evaluate_file(&normalize(file), args, context, loader, printer) {
+            session.writer.event(
+                "output",
+                json!({ "category": "stderr", "output": format!("{err}\n") }),
+            );
+            exit_code = 1;
+            break;
+        }
+    }
+    session
+        .writer
+        .event("exited", json!({ "exitCode": exit_code }));
+    session.writer.event("terminated", json!({}));
+
+    // Keep answering the client, which can still evaluate code, until it disconnects.
+    while let Ok(request) = session.requests.recv() {
+        if let Flow::Disconnect = session.handle(&request, None, context) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Makes `path` absolute, so that the paths of breakpoints match the paths of the sources.
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl Debugger for Session {
+    fn paused(&self, event: &PausedEvent, context: &mut Context) -> ResumeAction {
+        let stopping_on_entry = std::mem::take(&mut self.state.borrow_mut().stopping_on_entry);
+        let mut body = json!({ "threadId": THREAD_ID, "allThreadsStopped": true });
+        body["reason"] = match event.reason() {
+            PauseReason::Breakpoint(id) => {
+                body["hitBreakpointIds"] = json!([id.get()]);
+                "breakpoint".into()
+            }
+            PauseReason::DebuggerStatement => {
+                body["description"] = "Paused on debugger statement".into();
+                "pause".into()
+            }
+            PauseReason::Exception(error) => {
+                body["text"] = error.to_string().into();
+                "exception".into()
+            }
+            PauseReason::Step => "step".into(),
+            PauseReason::Requested if stopping_on_entry => "entry".into(),
+            PauseReason::Requested => "pause".into(),
+        };
+        self.writer.event("stopped", body);
+
+        loop {
+            let Ok(request) = self.requests.recv() else {
+                // The client went away, so there is no one to resume the program.
+                std::process::exit(0);
+            };
+            match self.handle(&request, Some(event), context) {
+                Flow::Wait | Flow::Start => {}
+                Flow::Resume(action) => {
+                    self.state.borrow_mut().objects.clear();
+                    return action;
+                }
+                Flow::Disconnect => std::process::exit(0),
+            }
+        }
+    }
+}
+
+impl Session {
+    /// Handles a request and responds to it.
+    fn handle(&self, request: &Value, paused: Option<&PausedEvent>, context: &mut Context) -> Flow {
+        let arguments = &request["arguments"];
+        let command = request["command"].as_str().unwrap_or_default();
+        let mut flow = Flow::Wait;
+        let result = match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsEvaluateForHovers": true,
+                "supportsTerminateRequest": true,
+                "exceptionBreakpointFilters": [
+                    { "filter": "all", "label": "All Exceptions" },
+                    { "filter": "uncaught", "label": "Uncaught Exceptions", "default": true },
+                ],
+            })),
+            "launch" | "attach" => {
+                let files = match arguments["program"].as_str() {
+                    Some(program) if command == "launch" => vec![PathBuf::from(program)],
+                    _ => self.default_files.clone(),
+                };
+                if files.is_empty() {
+                    Err("there is no program to debug".to_owned())
+                } else {
+                    let mut state = self.state.borrow_mut();
+                    state.launch = Some(Launch {
+                        files,
+                        stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or(false),
+                    });
+                    if state.configured {
+                        flow = Flow::Start;
+                    }
+                    Ok(Value::Null)
+                }
+            }
+            "configurationDone" => {
+                let mut state = self.state.borrow_mut();
+                state.configured = true;
+                if state.launch.is_some() {
+                    flow = Flow::Start;
+                }
+                Ok(Value::Null)
+            }
+            "setBreakpoints" => self.set_breakpoints(arguments, context),
+            "setExceptionBreakpoints" => {
+                let filters = arguments["filters"].as_array();
+                let has_filter = |name: &str| filters.is_some_and(|f| f.iter().any(|f| f == name));
+                context.set_exception_pause_mode(if has_filter("all") {
+                    ExceptionPauseMode::All
+                } else if has_filter("uncaught") {
+                    ExceptionPauseMode::Uncaught
+                } else {
+                    ExceptionPauseMode::None
+                });
+                Ok(Value::Null)
+            }
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => Ok(stack_trace(arguments, paused)),
+            "scopes" => self.scopes(arguments, paused, context),
+            "variables" => self.variables(arguments, context),
+            "evaluate" => self.evaluate(arguments, paused, context),
+            "continue" | "next" | "stepIn" | "stepOut" if paused.is_some() => {
+                flow = Flow::Resume(match command {
+                    "next" => ResumeAction::StepOver,
+                    "stepIn" => ResumeAction::StepIn,
+                    "stepOut" => ResumeAction::StepOut,
+                    _ => ResumeAction::Continue,
+                });
+                Ok(json!({ "allThreadsContinued": true }))
+            }
+            "continue" | "next" | "stepIn" | "stepOut" => {
+                Err("the program is not paused".to_owned())
+            }
+            "disconnect" | "terminate" => {
+                flow = Flow::Disconnect;
+                Ok(Value::Null)
+            }
+            _ => Err(format!("unsupported request `{command}`")),
+        };
+
+        match result {
+            Ok(body) => self.writer.respond(request, body),
+            Err(message) => self.writer.respond_error(request, &message),
+        }
+        if command == "initialize" {
+            self.writer.event("initialized", json!({}));
+        }
+        flow
+    }
+
+    fn set_breakpoints(&self, arguments: &Value, context: &mut Context) -> Result<Value, String> {
+        let path = arguments["source"]["path"]
+            .as_str()
+            .ok_or("the source has no path")?;
+        let path = normalize(Path::new(path));
+
+        let mut state = self.state.borrow_mut();
+        for id in state.breakpoints.remove(&path).unwrap_or_default() {
+            context.remove_breakpoint(id);
+        }
+
+        let mut ids = Vec::new();
+        let mut breakpoints = Vec::new();
+        for breakpoint in arguments["breakpoints"].as_array().into_iter().flatten() {
+            let line = to_u32(&breakpoint["line"]).ok_or("invalid breakpoint line")?;
+            let mut new = Breakpoint::new(line).with_path(&path);
+            if let Some(column) = to_u32(&breakpoint["column"]) {
+                new = new.with_column(column);
+            }
+
+            let id = context.set_breakpoint(new);
+            ids.push(id);
+            breakpoints.push(json!({ "id": id.get(), "verified": true, "line": line }));
+        }
+        state.breakpoints.insert(path, ids);
+
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn scopes(
+        &self,
+        arguments: &Value,
+        paused: Option<&PausedEvent>,
+        context: &mut Context,
+    ) -> Result<Value, String> {
+        let frame = to_u32(&arguments["frameId"])
+            .and_then(|id| paused?.frames().get(id as usize))
+            .ok_or("unknown stack frame")?;
+
+        let scopes = frame
+            .scopes(context)
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .map(|scope| {
+                let (name, hint) = match scope.kind() {
+                    ScopeKind::Local => ("Local", "locals"),
+                    ScopeKind::Block => ("Block", "locals"),
+                    ScopeKind::Closure => ("Closure", "locals"),
+                    ScopeKind::With => ("With", "locals"),
+                    ScopeKind::Module => ("Module", "locals"),
+                    ScopeKind::Script => ("Script", "globals"),
+                    ScopeKind::Global => ("Global", "globals"),
+                };
+                json!({
+                    "name": name,
+                    "presentationHint": hint,
+                    "variablesReference": self.reference(scope.object().clone()),
+                    "expensive": scope.kind() == ScopeKind::Global,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "scopes": scopes }))
+    }
+
+    fn variables(&self, arguments: &Value, context: &mut Context) -> Result<Value, String> {
+        let object = arguments["variablesReference"]
+            .as_u64()
+            .and_then(|reference| {
+                let index = usize::try_from(reference).ok()?.checked_sub(1)?;
+                self.state.borrow().objects.get(index).cloned()
+            })
+            .ok_or("unknown variables reference")?;
+
+        let keys = object
+            .own_property_keys(context)
+            .map_err(|err| err.to_string())?;
+        let mut variables = Vec::new();
+        for key in keys {
+            let mut variable = match object.get(key.clone(), context) {
+                Ok(value) => self.describe(&value),
+                Err(err) => json!({ "value": err.to_string(), "variablesReference": 0 }),
+            };
+            variable["name"] = key.to_string().into();
+            variables.push(variable);
+        }
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn evaluate(
+        &self,
+        arguments: &Value,
+        paused: Option<&PausedEvent>,
+        context: &mut Context,
+    ) -> Result<Value, String> {
+        let expression = arguments["expression"]
+            .as_str()
+            .ok_or("missing expression")?;
+
+        let result = match to_u32(&arguments["frameId"]) {
+            Some(id) => paused
+                .and_then(|event| event.frames().get(id as usize))
+                .ok_or("unknown stack frame")?
+                .evaluate(expression, context),
+            None => context.eval(Source::from_bytes(expression)),
+        };
+        let value = result.map_err(|err| err.to_string())?;
+
+        let mut body = self.describe(&value);
+        if let Some(fields) = body.as_object_mut()
+            && let Some(result) = fields.remove("value")
+        {
+            fields.insert("result".to_owned(), result);
+        }
+        Ok(body)
+    }
+
+    /// Returns the variables reference that expands `object`.
+    fn reference(&self, object: JsObject) -> usize {
+        let objects = &mut self.state.borrow_mut().objects;
+        objects.push(object);
+        objects.len()
+    }
+
+    /// Describes a value with the fields of a DAP `Variable`, without the name.
+    fn describe(&self, value: &JsValue) -> Value {
+        let Some(object) = value.as_object() else {
+            return json!({
+                "value": value.display().to_string(),
+                "type": value.type_of(),
+                "variablesReference": 0,
+            });
+        };
+
+        let description = if object.is_callable() {
+            "Function"
+        } else if object.is_array() {
+            "Array"
+        } else {
+            "Object"
+        };
+        json!({
+            "value": description,
+            "type": value.type_of(),
+            "variablesReference": self.reference(object),
+        })
+    }
+}
+
+/// Lists the frames of the paused program, with the paused frame first.
+fn stack_trace(arguments: &Value, paused: Option<&PausedEvent>) -> Value {
+    let frames = paused.map(PausedEvent::frames).unwrap_or_default();
+    let start = to_u32(&arguments["startFrame"]).unwrap_or(0) as usize;
+    let levels = match to_u32(&arguments["levels"]) {
+        Some(levels) if levels > 0 => levels as usize,
+        _ => frames.len(),
+    };
+
+    let stack_frames = frames
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(levels)
+        .map(|(id, frame)| {
+            let location = frame.location();
+            let name = if location.function_name.is_empty() {
+                "<anonymous>".to_owned()
+            } else {
+                location.function_name.to_std_string_escaped()
+            };
+            let (line, column) = location.position.map_or((0, 0), |position| {
+                (position.line_number(), position.column_number())
+            });
+            let mut stack_frame = json!({
+                "id": id,
+                "name": name,
+                "line": line,
+                "column": column,
+            });
+            if let SourcePath::Path(path) = &location.path {
+                stack_frame["source"] = json!({
+                    "name": path.file_name().map(|name| name.to_string_lossy()),
+                    "path": path.to_string_lossy(),
+                });
+            }
+            stack_frame
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "stackFrames": stack_frames, "totalFrames": frames.len() })
+}
+
+fn to_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|value| u32::try_from(value).ok())
+}
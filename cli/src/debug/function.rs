@@ -71,12 +71,11 @@ fn flowgraph(_this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResu
             format = flowgraph_parse_format_option(arguments)?;
         }
     }
-    let Some(function) = object.downcast_ref::<OrdinaryFunction>() else {
+    let Some(code) = OrdinaryFunction::compiled_codeblock(&object) else {
         return Err(JsNativeError::typ()
             .with_message("expected an ordinary function object")
             .into());
     };
-    let code = function.codeblock();
     let mut graph = Graph::new(direction);
     code.to_graph(graph.subgraph(String::default()));
     let result = match format {
@@ -98,12 +97,11 @@ fn bytecode(_: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue>
             .with_message(format!("expected object, got {}", value.type_of()))
             .into());
     };
-    let Some(function) = object.downcast_ref::<OrdinaryFunction>() else {
+    let Some(code) = OrdinaryFunction::compiled_codeblock(&object) else {
         return Err(JsNativeError::typ()
             .with_message("expected an ordinary function object")
             .into());
     };
-    let code = function.codeblock();
 
     println!("{code}");
 
@@ -111,12 +109,11 @@ fn bytecode(_: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue>
 }
 
 fn set_trace_flag_in_function_object(object: &JsObject, value: bool) -> JsResult<()> {
-    let Some(function) = object.downcast_ref::<OrdinaryFunction>() else {
+    let Some(code) = OrdinaryFunction::compiled_codeblock(object) else {
         return Err(JsNativeError::typ()
             .with_message("expected an ordinary function object")
             .into());
     };
-    let code = function.codeblock();
     code.set_traceable(value);
     Ok(())
 }
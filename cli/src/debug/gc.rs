@@ -1,5 +1,8 @@
+use std::{fs::File, io::BufWriter};
+
 use boa_engine::{
-    Context, JsObject, JsResult, JsValue, NativeFunction, js_string, object::ObjectInitializer,
+    Context, JsArgs, JsNativeError, JsObject, JsResult, JsValue, NativeFunction, js_string,
+    object::ObjectInitializer,
 };
 
 /// Trigger garbage collection.
@@ -8,6 +11,27 @@ fn collect(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
     Ok(JsValue::undefined())
 }
 
+/// Writes a heap snapshot in the `.heapsnapshot` format to the given path.
+fn snapshot(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let path = args
+        .get_or_undefined(0)
+        .to_string(context)?
+        .to_std_string_escaped();
+
+    let file = File::create(&path).map_err(|e| {
+        JsNativeError::error().with_message(format!("could not create `{path}`: {e}"))
+    })?;
+
+    context
+        .heap_snapshot()
+        .write_json(BufWriter::new(file))
+        .map_err(|e| {
+            JsNativeError::error().with_message(format!("could not write `{path}`: {e}"))
+        })?;
+
+    Ok(JsValue::undefined())
+}
+
 pub(super) fn create_object(context: &mut Context) -> JsObject {
     ObjectInitializer::new(context)
         .function(
@@ -15,5 +39,10 @@ pub(super) fn create_object(context: &mut Context) -> JsObject {
             js_string!("collect"),
             0,
         )
+        .function(
+            NativeFunction::from_fn_ptr(snapshot),
+            js_string!("snapshot"),
+            1,
+        )
         .build()
 }
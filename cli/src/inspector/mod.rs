@@ -0,0 +1,875 @@
+//! An inspector serving a subset of the Chrome DevTools Protocol, so that DevTools can debug
+//! and profile scripts through `chrome://inspect`.
+//!
+//! The inspector is started with `--inspect`, and waits for a client to connect to a WebSocket
+//! on the local host and to send `Runtime.runIfWaitingForDebugger` before running the input
+//! files. Messages are read on separate threads and handled on the main thread, before the
+//! program runs, while it is paused, and after it finished. While the program runs, the main
+//! thread is briefly paused to handle the messages, without notifying the client. The
+//! `Profiler` domain uses the sampling profiler of the engine. `Debugger.pause` is handled
+//! right away by the reading thread.
+//!
+//! See <https://chromedevtools.github.io/devtools-protocol/>.
+
+mod remote;
+mod server;
+
+use crate::{Opt, evaluate_file, logger::SharedExternalPrinterLogger};
+use boa_engine::{
+    Context, Finalize, JsNativeError, JsResult, JsValue, Source, Trace,
+    debugger::{
+        Breakpoint, BreakpointId, Debugger, ExceptionPauseMode, PauseHandle, PauseReason,
+        PausedEvent, ResumeAction, ScopeKind,
+    },
+    module::SimpleModuleLoader,
+    profiler::CpuProfile,
+    vm::SourcePath,
+};
+use boa_runtime::{ConsoleState, Logger};
+use color_eyre::{Result, eyre::WrapErr};
+use regex::Regex;
+use remote::Objects;
+use serde_json::{Value, json};
+use server::{Incoming, Target, Writer};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::{Duration, SystemTime},
+};
+use uuid::Uuid;
+
+/// The port the inspector listens on by default, like Node.js.
+pub(crate) const DEFAULT_PORT: u16 = 9229;
+
+/// The identifier of the only execution context.
+const CONTEXT_ID: u64 = 1;
+
+/// The object group of the objects of a pause, which are released when the program resumes.
+const BACKTRACE_GROUP: &str = "backtrace";
+
+/// The interval between samples of the profiler, unless the client sets one.
+const DEFAULT_SAMPLING_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The most console messages kept until the client enables the `Runtime` domain.
+const MAX_BUFFERED_MESSAGES: usize = 1000;
+
+/// The state shared with the threads reading messages.
+struct Shared {
+    pause_handle: PauseHandle,
+
+    /// Set when the client asked for a pause, to tell it apart from the pauses of the inspector.
+    client_pause: AtomicBool,
+
+    /// Set while the program runs without stepping, when it can be paused to handle messages.
+    running: AtomicBool,
+}
+
+/// What to do after handling a message.
+enum Flow {
+    /// Wait for the next message.
+    Wait,
+
+    /// Start running the program.
+    Start,
+
+    /// Resume the paused program.
+    Resume(ResumeAction),
+}
+
+/// A script that was sent to the client with a `Debugger.scriptParsed` event.
+struct Script {
+    id: String,
+    url: String,
+    path: PathBuf,
+    source: String,
+}
+
+#[derive(Default)]
+struct State {
+    runtime_enabled: bool,
+    debugger_enabled: bool,
+
+    /// The console messages sent before the `Runtime` domain was enabled.
+    console_messages: Vec<Value>,
+
+    scripts: Vec<Script>,
+
+    /// The breakpoints of the client, which can resolve to a breakpoint in several scripts.
+    breakpoints: HashMap<String, Vec<BreakpointId>>,
+    next_breakpoint: u64,
+    breakpoints_active: bool,
+
+    /// The interval between samples of the profiler, if the client set one.
+    sampling_interval: Option<Duration>,
+}
+
+/// A Chrome DevTools Protocol inspector of a context.
+pub(crate) struct Inspector {
+    shared: Arc<Shared>,
+    incoming: mpsc::Receiver<Incoming>,
+    client: RefCell<Option<Writer>>,
+
+    /// The step the program was resumed with, which the pauses of the inspector resume with.
+    step: Cell<Option<ResumeAction>>,
+
+    /// The objects the client references, which are separate from the state since describing
+    /// objects can run code that logs to the console.
+    objects: RefCell<Objects>,
+
+    state: RefCell<State>,
+}
+
+impl Inspector {
+    /// Starts the inspector server on `port` of the local host, for the given input files.
+    pub(crate) fn new(port: u16, files: &[PathBuf], context: &Context) -> Result<Rc<Self>> {
+        let shared = Arc::new(Shared {
+            pause_handle: context.debugger_pause_handle(),
+            client_pause: AtomicBool::new(false),
+            running: AtomicBool::new(false),
+        });
+
+        let url = files
+            .first()
+            .map(|file| server::file_url(&normalize(file)))
+            .unwrap_or_default();
+        let title = files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let target = Target {
+            id: Uuid::new_v4().to_string(),
+            title: if title.is_empty() {
+                "boa".to_owned()
+            } else {
+                title
+            },
+            url,
+        };
+        let id = target.id.clone();
+
+        let (sender, incoming) = mpsc::channel();
+        let forward = sender.clone();
+        let message_shared = shared.clone();
+        let address = server::listen(port, target, sender, move |message, writer| {
+            if message["method"] == "Debugger.pause" {
+                message_shared.client_pause.store(true, Ordering::Release);
+                message_shared.pause_handle.request_pause();
+                writer.send(&json!({ "id": message["id"], "result": {} }));
+                return true;
+            }
+            let sent = forward.send(Incoming::Message(message)).is_ok();
+            if message_shared.running.load(Ordering::Acquire) {
+                message_shared.pause_handle.request_pause();
+            }
+            sent
+        })
+        .wrap_err("could not start the inspector")?;
+
+        eprintln!("Debugger listening on ws://{address}/{id}");
+        eprintln!("Open chrome://inspect in a Chromium browser to attach to it.");
+
+        Ok(Rc::new(Self {
+            shared,
+            incoming,
+            client: RefCell::new(None),
+            step: Cell::new(None),
+            objects: RefCell::default(),
+            state: RefCell::new(State {
+                breakpoints_active: true,
+                ..State::default()
+            }),
+        }))
+    }
+
+    /// Returns a console logger that prints with `printer` and forwards messages to the client.
+    pub(crate) fn logger(self: &Rc<Self>, printer: SharedExternalPrinterLogger) -> ConsoleLogger {
+        ConsoleLogger {
+            printer,
+            inspector: self.clone(),
+        }
+    }
+}
+
+/// Runs the input files once a client asks for it, then serves the client until it disconnects.
+pub(crate) fn run(
+    inspector: &Rc<Inspector>,
+    args: &Opt,
+    context: &mut Context,
+    loader: &SimpleModuleLoader,
+    printer: &SharedExternalPrinterLogger,
+) -> Result<()> {
+    context.attach_debugger(inspector.clone());
+
+    let files = args
+        .files
+        .iter()
+        .map(|file| normalize(file))
+        .collect::<Vec<_>>();
+    for file in &files {
+        inspector.script_id(file);
+    }
+
+    // Wait until the client sets the breakpoints and starts the program.
+    eprintln!("Waiting for the debugger to attach...");
+    loop {
+        let Ok(incoming) = inspector.incoming.recv() else {
+            return Ok(());
+        };
+        if let Flow::Start = inspector.receive(incoming, None, context) {
+            break;
+        }
+    }
+
+    inspector.resume(ResumeAction::Continue);
+    let result = files
+        .iter()
+        .try_for_each(|file| evaluate_file(file, args, context, loader, printer));
+    inspector.shared.running.store(false, Ordering::Release);
+
+    // Keep answering the client, which can still evaluate code, until it disconnects.
+    if inspector.client.borrow().is_some() {
+        eprintln!("Waiting for the debugger to disconnect...");
+        while let Ok(incoming) = inspector.incoming.recv() {
+            let disconnected = matches!(incoming, Incoming::Disconnected);
+            inspector.receive(incoming, None, context);
+            if disconnected {
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Makes `path` absolute, so that the paths of breakpoints match the paths of the sources.
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl Debugger for Inspector {
+    fn paused(&self, event: &PausedEvent, context: &mut Context) -> ResumeAction {
+        self.shared.running.store(false, Ordering::Release);
+        let client_pause = self.shared.client_pause.swap(false, Ordering::AcqRel);
+
+        let is_silent = match event.reason() {
+            PauseReason::Requested => !client_pause,
+            PauseReason::Breakpoint(_) => !self.state.borrow().breakpoints_active,
+            _ => false,
+        };
+        if is_silent || self.client.borrow().is_none() {
+            while let Ok(incoming) = self.incoming.try_recv() {
+                self.receive(incoming, None, context);
+            }
+            return self.resume(self.step.get().unwrap_or_default());
+        }
+
+        let params = self.paused_params(event, context);
+        self.send_event("Debugger.paused", params);
+
+        loop {
+            let Ok(incoming) = self.incoming.recv() else {
+                return self.resume(ResumeAction::Continue);
+            };
+            let disconnected = matches!(incoming, Incoming::Disconnected);
+            match self.receive(incoming, Some(event), context) {
+                Flow::Resume(action) => {
+                    self.objects.borrow_mut().release_group(BACKTRACE_GROUP);
+                    self.send_event("Debugger.resumed", json!({}));
+                    return self.resume(action);
+                }
+                Flow::Wait | Flow::Start if disconnected => {
+                    return self.resume(ResumeAction::Continue);
+                }
+                Flow::Wait | Flow::Start => {}
+            }
+        }
+    }
+}
+
+impl Inspector {
+    /// Records how the program resumes, and returns `action`.
+    fn resume(&self, action: ResumeAction) -> ResumeAction {
+        let stepping = action != ResumeAction::Continue;
+        self.step.set(stepping.then_some(action));
+        self.shared.running.store(!stepping, Ordering::Release);
+        action
+    }
+
+    /// Handles a message from the server thread.
+    fn receive(
+        &self,
+        incoming: Incoming,
+        paused: Option<&PausedEvent>,
+        context: &mut Context,
+    ) -> Flow {
+        match incoming {
+            Incoming::Connected(writer) => {
+                *self.client.borrow_mut() = Some(writer);
+                Flow::Wait
+            }
+            Incoming::Message(message) => self.handle(&message, paused, context),
+            Incoming::Disconnected => {
+                self.disconnect(context);
+                Flow::Wait
+            }
+        }
+    }
+
+    /// Forgets the state of the client that went away, and removes its breakpoints.
+    fn disconnect(&self, context: &mut Context) {
+        *self.client.borrow_mut() = None;
+        self.objects.borrow_mut().clear();
+        context.stop_cpu_profile();
+
+        let mut state = self.state.borrow_mut();
+        state.runtime_enabled = false;
+        state.debugger_enabled = false;
+        state.breakpoints_active = true;
+        state.breakpoints.clear();
+        state.sampling_interval = None;
+        context.clear_breakpoints();
+        context.set_exception_pause_mode(ExceptionPauseMode::None);
+    }
+
+    fn send_event(&self, method: &str, params: Value) {
+        if let Some(client) = &*self.client.borrow() {
+            let mut message = json!({ "method": method });
+            message["params"] = params;
+            client.send(&message);
+        }
+    }
+
+    /// Handles a message and responds to it.
+    #[allow(clippy::too_many_lines)]
+    fn handle(&self, message: &Value, paused: Option<&PausedEvent>, context: &mut Context) -> Flow {
+        let params = &message["params"];
+        let method = message["method"].as_str().unwrap_or_default();
+        let mut flow = Flow::Wait;
+        let result = match method {
+            "Runtime.enable" => {
+                self.send_event(
+                    "Runtime.executionContextCreated",
+                    json!({
+                        "context": {
+                            "id": CONTEXT_ID,
+                            "origin": "",
+                            "name": "Boa",
+                            "uniqueId": CONTEXT_ID.to_string(),
+                        },
+                    }),
+                );
+                let messages = {
+                    let mut state = self.state.borrow_mut();
+                    state.runtime_enabled = true;
+                    std::mem::take(&mut state.console_messages)
+                };
+                for params in messages {
+                    self.send_event("Runtime.consoleAPICalled", params);
+                }
+                Ok(json!({}))
+            }
+            "Runtime.disable" => {
+                self.state.borrow_mut().runtime_enabled = false;
+                Ok(json!({}))
+            }
+            "Runtime.runIfWaitingForDebugger" => {
+                flow = Flow::Start;
+                Ok(json!({}))
+            }
+            "Runtime.evaluate" if params["throwOnSideEffect"] == true => {
+                Err("evaluating without side effects is not supported".to_owned())
+            }
+            "Runtime.evaluate" => {
+                let result = params["expression"]
+                    .as_str()
+                    .ok_or_else(|| "missing expression".to_owned())
+                    .map(|expression| context.eval(Source::from_bytes(expression)));
+                result.map(|result| self.evaluation_result(result, params, context))
+            }
+            "Runtime.callFunctionOn" => self.call_function_on(params, context),
+            "Runtime.getProperties" => self.get_properties(params, context),
+            "Runtime.releaseObject" => {
+                if let Some(id) = params["objectId"].as_str() {
+                    self.objects.borrow_mut().release(id);
+                }
+                Ok(json!({}))
+            }
+            "Runtime.releaseObjectGroup" => {
+                if let Some(group) = params["objectGroup"].as_str() {
+                    self.objects.borrow_mut().release_group(group);
+                }
+                Ok(json!({}))
+            }
+            "Runtime.globalLexicalScopeNames" => Ok(json!({ "names": [] })),
+            "Debugger.enable" => {
+                let scripts = {
+                    let mut state = self.state.borrow_mut();
+                    state.debugger_enabled = true;
+                    state
+                        .scripts
+                        .iter()
+                        .map(script_parsed_params)
+                        .collect::<Vec<_>>()
+                };
+                for params in scripts {
+                    self.send_event("Debugger.scriptParsed", params);
+                }
+                Ok(json!({ "debuggerId": "boa" }))
+            }
+            "Debugger.disable" => {
+                self.state.borrow_mut().debugger_enabled = false;
+                Ok(json!({}))
+            }
+            "Debugger.setBreakpointByUrl" => self.set_breakpoint_by_url(params, context),
+            "Debugger.removeBreakpoint" => {
+                let ids = params["breakpointId"]
+                    .as_str()
+                    .and_then(|id| self.state.borrow_mut().breakpoints.remove(id));
+                for id in ids.into_iter().flatten() {
+                    context.remove_breakpoint(id);
+                }
+                Ok(json!({}))
+            }
+            "Debugger.setBreakpointsActive" => {
+                self.state.borrow_mut().breakpoints_active = params["active"] != false;
+                Ok(json!({}))
+            }
+            "Debugger.setPauseOnExceptions" => {
+                context.set_exception_pause_mode(match params["state"].as_str() {
+                    Some("all" | "caught") => ExceptionPauseMode::All,
+                    Some("uncaught") => ExceptionPauseMode::Uncaught,
+                    _ => ExceptionPauseMode::None,
+                });
+                Ok(json!({}))
+            }
+            "Debugger.getScriptSource" => {
+                let state = self.state.borrow();
+                state
+                    .scripts
+                    .iter()
+                    .find(|script| params["scriptId"] == script.id)
+                    .map(|script| json!({ "scriptSource": script.source }))
+                    .ok_or_else(|| "unknown script".to_owned())
+            }
+            "Debugger.getPossibleBreakpoints" => Ok(json!({ "locations": [] })),
+            "Debugger.resume" | "Debugger.stepOver" | "Debugger.stepInto" | "Debugger.stepOut"
+                if paused.is_some() =>
+            {
+                flow = Flow::Resume(match method {
+                    "Debugger.stepOver" => ResumeAction::StepOver,
+                    "Debugger.stepInto" => ResumeAction::StepIn,
+                    "Debugger.stepOut" => ResumeAction::StepOut,
+                    _ => ResumeAction::Continue,
+                });
+                Ok(json!({}))
+            }
+            "Debugger.resume" | "Debugger.stepOver" | "Debugger.stepInto" | "Debugger.stepOut" => {
+                Err("the program is not paused".to_owned())
+            }
+            "Debugger.evaluateOnCallFrame" => {
+                let frame = params["callFrameId"]
+                    .as_str()
+                    .and_then(|id| id.parse::<usize>().ok())
+                    .and_then(|id| paused?.frames().get(id));
+                match (frame, params["expression"].as_str()) {
+                    (Some(frame), Some(expression)) => {
+                        let result = frame.evaluate(expression, context);
+                        Ok(self.evaluation_result(result, params, context))
+                    }
+                    (None, _) => Err("unknown call frame".to_owned()),
+                    (_, None) => Err("missing expression".to_owned()),
+                }
+            }
+            "Profiler.setSamplingInterval" => {
+                if let Some(interval) = params["interval"].as_u64() {
+                    self.state.borrow_mut().sampling_interval =
+                        Some(Duration::from_micros(interval.max(1)));
+                }
+                Ok(json!({}))
+            }
+            "Profiler.start" => {
+                let interval = self.state.borrow().sampling_interval;
+                context.start_cpu_profile(interval.unwrap_or(DEFAULT_SAMPLING_INTERVAL));
+                Ok(json!({}))
+            }
+            "Profiler.stop" => context
+                .stop_cpu_profile()
+                .map(|profile| json!({ "profile": self.profile(&profile) }))
+                .ok_or_else(|| "the profiler is not started".to_owned()),
+            "Console.enable"
+            | "Console.disable"
+            | "Runtime.compileScript"
+            | "Debugger.setAsyncCallStackDepth"
+            | "Debugger.setBlackboxPatterns"
+            | "Debugger.setBlackboxExecutionContexts"
+            | "Profiler.enable"
+            | "Profiler.disable" => Ok(json!({})),
+            _ => {
+                if let Some(client) = &*self.client.borrow() {
+                    client.send(&json!({
+                        "id": message["id"],
+                        "error": { "code": -32601, "message": format!("'{method}' wasn't found") },
+                    }));
+                }
+                return flow;
+            }
+        };
+
+        if let Some(client) = &*self.client.borrow() {
+            client.send(&match result {
+                Ok(result) => json!({ "id": message["id"], "result": result }),
+                Err(message_text) => json!({
+                    "id": message["id"],
+                    "error": { "code": -32000, "message": message_text },
+                }),
+            });
+        }
+        flow
+    }
+
+    /// Returns the identifier of the script at `path`, sending it to the client the first time.
+    fn script_id(&self, path: &Path) -> Option<String> {
+        let mut state = self.state.borrow_mut();
+        if let Some(script) = state.scripts.iter().find(|script| script.path == path) {
+            return Some(script.id.clone());
+        }
+
+        let source = std::fs::read_to_string(path).ok()?;
+        let script = Script {
+            id: (state.scripts.len() + 1).to_string(),
+            url: server::file_url(path),
+            path: path.to_path_buf(),
+            source,
+        };
+        let id = script.id.clone();
+        let params = state
+            .debugger_enabled
+            .then(|| script_parsed_params(&script));
+        state.scripts.push(script);
+        drop(state);
+
+        if let Some(params) = params {
+            self.send_event("Debugger.scriptParsed", params);
+        }
+        Some(id)
+    }
+
+    /// Returns the script identifier and the URL of a source.
+    fn script_location(&self, path: &SourcePath) -> (String, String) {
+        match path {
+            SourcePath::Path(path) => (
+                self.script_id(path).unwrap_or_else(|| "0".to_owned()),
+                server::file_url(path),
+            ),
+            _ => ("0".to_owned(), String::new()),
+        }
+    }
+
+    fn paused_params(&self, event: &PausedEvent, context: &mut Context) -> Value {
+        let mut params = json!({ "reason": "other", "hitBreakpoints": [] });
+        match event.reason() {
+            PauseReason::Breakpoint(hit) => {
+                let state = self.state.borrow();
+                let ids = state
+                    .breakpoints
+                    .iter()
+                    .filter(|(_, ids)| ids.contains(hit))
+                    .map(|(id, _)| id.clone())
+                    .collect::<Vec<_>>();
+                params["hitBreakpoints"] = ids.into();
+            }
+            PauseReason::Exception(error) => {
+                params["reason"] = "exception".into();
+                if let Ok(value) = error.clone().into_opaque(context) {
+                    params["data"] =
+                        self.objects
+                            .borrow_mut()
+                            .remote(&value, BACKTRACE_GROUP, context);
+                }
+            }
+            PauseReason::DebuggerStatement | PauseReason::Step | PauseReason::Requested => {}
+        }
+
+        let mut call_frames = Vec::new();
+        for (id, frame) in event.frames().iter().enumerate() {
+            let location = frame.location();
+            let (script_id, url) = self.script_location(&location.path);
+            let (line, column) = location.position.map_or((0, 0), |position| {
+                (position.line_number() - 1, position.column_number() - 1)
+            });
+
+            let mut scope_chain = Vec::new();
+            for scope in frame.scopes(context).unwrap_or_default() {
+                let kind = match scope.kind() {
+                    ScopeKind::Local => "local",
+                    ScopeKind::Block => "block",
+                    ScopeKind::Closure => "closure",
+                    ScopeKind::With => "with",
+                    ScopeKind::Module => "module",
+                    ScopeKind::Script => "script",
+                    ScopeKind::Global => "global",
+                };
+                let object = self.objects.borrow_mut().remote(
+                    &scope.object().clone().into(),
+                    BACKTRACE_GROUP,
+                    context,
+                );
+                scope_chain.push(json!({ "type": kind, "object": object }));
+            }
+            let this = frame.this(context).unwrap_or_default();
+            let this = self
+                .objects
+                .borrow_mut()
+                .remote(&this, BACKTRACE_GROUP, context);
+
+            call_frames.push(json!({
+                "callFrameId": id.to_string(),
+                "functionName": location.function_name.to_std_string_escaped(),
+                "location": { "scriptId": script_id, "lineNumber": line, "columnNumber": column },
+                "url": url,
+                "scopeChain": scope_chain,
+                "this": this,
+                "canBeRestarted": false,
+            }));
+        }
+        params["callFrames"] = call_frames.into();
+        params
+    }
+
+    /// Converts a CPU profile to a `Profile`, with the script ids sent to the client.
+    fn profile(&self, profile: &CpuProfile) -> Value {
+        let mut json: Value = serde_json::from_str(&profile.to_json_string()).unwrap_or_default();
+        if let Some(nodes) = json["nodes"].as_array_mut() {
+            for (node, json) in profile.nodes().iter().zip(nodes) {
+                let (script_id, url) = self.script_location(node.path());
+                json["callFrame"]["scriptId"] = script_id.into();
+                json["callFrame"]["url"] = url.into();
+            }
+        }
+        json
+    }
+
+    fn set_breakpoint_by_url(
+        &self,
+        params: &Value,
+        context: &mut Context,
+    ) -> Result<Value, String> {
+        let line = to_u32(&params["lineNumber"]).ok_or("invalid breakpoint line")?;
+        let column = to_u32(&params["columnNumber"]).unwrap_or(0);
+        if params["condition"]
+            .as_str()
+            .is_some_and(|condition| !condition.is_empty())
+        {
+            return Err("conditional breakpoints are not supported".to_owned());
+        }
+
+        let paths = if let Some(url) = params["url"].as_str() {
+            vec![PathBuf::from(
+                server::url_path(url).unwrap_or_else(|| url.to_owned()),
+            )]
+        } else if let Some(pattern) = params["urlRegex"].as_str() {
+            let regex = Regex::new(pattern).map_err(|err| err.to_string())?;
+            self.state
+                .borrow()
+                .scripts
+                .iter()
+                .filter(|script| regex.is_match(&script.url))
+                .map(|script| script.path.clone())
+                .collect()
+        } else {
+            return Err("the breakpoint has no url".to_owned());
+        };
+
+        let mut ids = Vec::new();
+        let mut locations = Vec::new();
+        for path in paths {
+            let mut breakpoint = Breakpoint::new(line + 1).with_path(&path);
+            // DevTools sets the breakpoints of lines on their first column.
+            if column > 0 {
+                breakpoint = breakpoint.with_column(column + 1);
+            }
+            ids.push(context.set_breakpoint(breakpoint));
+            if let Some(script_id) = self.script_id(&path) {
+                locations.push(json!({
+                    "scriptId": script_id,
+                    "lineNumber": line,
+                    "columnNumber": column,
+                }));
+            }
+        }
+
+        let mut state = self.state.borrow_mut();
+        state.next_breakpoint += 1;
+        let id = state.next_breakpoint.to_string();
+        state.breakpoints.insert(id.clone(), ids);
+        Ok(json!({ "breakpointId": id, "locations": locations }))
+    }
+
+    fn get_properties(&self, params: &Value, context: &mut Context) -> Result<Value, String> {
+        if params["accessorPropertiesOnly"] == true {
+            return Ok(json!({ "result": [] }));
+        }
+        let object = params["objectId"]
+            .as_str()
+            .and_then(|id| self.objects.borrow().get(id).cloned())
+            .ok_or("unknown object")?;
+        let group = params["objectGroup"].as_str().unwrap_or(BACKTRACE_GROUP);
+        self.objects
+            .borrow_mut()
+            .properties(&object, group, context)
+            .map_err(|err| err.to_string())
+    }
+
+    fn call_function_on(&self, params: &Value, context: &mut Context) -> Result<Value, String> {
+        let declaration = params["functionDeclaration"]
+            .as_str()
+            .ok_or("missing function declaration")?;
+        let this = match params["objectId"].as_str() {
+            Some(id) => self
+                .objects
+                .borrow()
+                .get(id)
+                .cloned()
+                .map(JsValue::from)
+                .ok_or("unknown object")?,
+            None => JsValue::undefined(),
+        };
+        let mut arguments = Vec::new();
+        for argument in params["arguments"].as_array().into_iter().flatten() {
+            let argument = self.objects.borrow().argument(argument, context);
+            arguments.push(argument.ok_or("invalid argument")?);
+        }
+
+        let result = context
+            .eval(Source::from_bytes(&format!("({declaration})")))
+            .and_then(|function| {
+                function
+                    .as_callable()
+                    .ok_or_else(|| JsNativeError::typ().with_message("not a function"))?
+                    .call(&this, &arguments, context)
+            });
+        Ok(self.evaluation_result(result, params, context))
+    }
+
+    /// Describes the result of an evaluation, with `exceptionDetails` if it threw.
+    fn evaluation_result(
+        &self,
+        result: JsResult<JsValue>,
+        params: &Value,
+        context: &mut Context,
+    ) -> Value {
+        let group = params["objectGroup"].as_str().unwrap_or("console");
+        match result {
+            Ok(value) if params["returnByValue"] == true => {
+                let mut remote = json!({ "type": value.type_of() });
+                remote["value"] = value.to_json(context).ok().flatten().unwrap_or_default();
+                json!({ "result": remote })
+            }
+            Ok(value) => json!({
+                "result": self.objects.borrow_mut().remote(&value, group, context),
+            }),
+            Err(error) => {
+                let text = error.to_string();
+                let exception = error.into_opaque(context).unwrap_or_default();
+                let exception = self.objects.borrow_mut().remote(&exception, group, context);
+                json!({
+                    "result": exception,
+                    "exceptionDetails": {
+                        "exceptionId": 1,
+                        "text": format!("Uncaught {text}"),
+                        "lineNumber": 0,
+                        "columnNumber": 0,
+                        "exception": exception,
+                    },
+                })
+            }
+        }
+    }
+
+    /// Sends a console message to the client, or keeps it until the `Runtime` domain is enabled.
+    fn console_api_called(&self, kind: &str, message: &str) {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0;
+        let params = json!({
+            "type": kind,
+            "args": [{ "type": "string", "value": message }],
+            "executionContextId": CONTEXT_ID,
+            "timestamp": timestamp,
+        });
+
+        let mut state = self.state.borrow_mut();
+        if state.runtime_enabled {
+            drop(state);
+            self.send_event("Runtime.consoleAPICalled", params);
+        } else if state.console_messages.len() < MAX_BUFFERED_MESSAGES {
+            state.console_messages.push(params);
+        }
+    }
+}
+
+fn script_parsed_params(script: &Script) -> Value {
+    let lines = script.source.lines().count();
+    json!({
+        "scriptId": script.id,
+        "url": script.url,
+        "startLine": 0,
+        "startColumn": 0,
+        "endLine": lines,
+        "endColumn": 0,
+        "executionContextId": CONTEXT_ID,
+        "hash": "",
+        "length": script.source.len(),
+    })
+}
+
+fn to_u32(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|value| u32::try_from(value).ok())
+}
+
+/// A console logger that prints messages and forwards them to the inspector client.
+#[derive(Trace, Finalize)]
+pub(crate) struct ConsoleLogger {
+    printer: SharedExternalPrinterLogger,
+    #[unsafe_ignore_trace]
+    inspector: Rc<Inspector>,
+}
+
+impl Debug for ConsoleLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConsoleLogger").finish_non_exhaustive()
+    }
+}
+
+impl Logger for ConsoleLogger {
+    fn log(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()> {
+        self.inspector.console_api_called("log", &msg);
+        self.printer.log(msg, state, context)
+    }
+
+    fn info(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()> {
+        self.inspector.console_api_called("info", &msg);
+        self.printer.info(msg, state, context)
+    }
+
+    fn warn(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()> {
+        self.inspector.console_api_called("warning", &msg);
+        self.printer.warn(msg, state, context)
+    }
+
+    fn error(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()> {
+        self.inspector.console_api_called("error", &msg);
+        self.printer.error(msg, state, context)
+    }
+}
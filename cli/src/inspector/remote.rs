@@ -0,0 +1,220 @@
+//! Conversion of JavaScript values to the `RemoteObject`s of the `Runtime` domain.
+//!
+//! See <https://chromedevtools.github.io/devtools-protocol/tot/Runtime/#type-RemoteObject>.
+
+use boa_engine::{
+    Context, JsNativeError, JsObject, JsResult, JsValue, builtins::error::Error, js_string,
+    property::PropertyKey,
+};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// The objects that the client references by their `objectId`.
+#[derive(Default)]
+pub(super) struct Objects {
+    next_id: u64,
+
+    /// The objects with their group, which the client releases at once.
+    objects: HashMap<String, (JsObject, String)>,
+}
+
+impl Objects {
+    /// Returns the `objectId` that references `object`.
+    pub(super) fn add(&mut self, object: JsObject, group: &str) -> String {
+        self.next_id += 1;
+        let id = self.next_id.to_string();
+        self.objects.insert(id.clone(), (object, group.to_owned()));
+        id
+    }
+
+    pub(super) fn get(&self, id: &str) -> Option<&JsObject> {
+        self.objects.get(id).map(|(object, _)| object)
+    }
+
+    pub(super) fn release(&mut self, id: &str) {
+        self.objects.remove(id);
+    }
+
+    pub(super) fn release_group(&mut self, group: &str) {
+        self.objects
+            .retain(|_, (_, object_group)| object_group != group);
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.objects.clear();
+    }
+
+    /// Describes `value` as a `RemoteObject`, referencing it in `group` if it is an object.
+    pub(super) fn remote(&mut self, value: &JsValue, group: &str, context: &mut Context) -> Value {
+        if let Some(number) = value.as_number() {
+            let unserializable = if number.is_nan() {
+                Some("NaN")
+            } else if number.is_infinite() {
+                Some(if number > 0.0 {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                })
+            } else if number == 0.0 && number.is_sign_negative() {
+                Some("-0")
+            } else {
+                None
+            };
+            return match unserializable {
+                Some(text) => json!({
+                    "type": "number",
+                    "unserializableValue": text,
+                    "description": text,
+                }),
+                None => json!({
+                    "type": "number",
+                    "value": number,
+                    "description": value.display().to_string(),
+                }),
+            };
+        }
+
+        if value.is_null() {
+            return json!({ "type": "object", "subtype": "null", "value": null });
+        }
+        if value.is_undefined() {
+            return json!({ "type": "undefined" });
+        }
+        if let Some(boolean) = value.as_boolean() {
+            return json!({ "type": "boolean", "value": boolean });
+        }
+        if let Some(string) = value.as_string() {
+            return json!({ "type": "string", "value": string.to_std_string_escaped() });
+        }
+        if value.is_bigint() {
+            let text = value.display().to_string();
+            return json!({ "type": "bigint", "unserializableValue": text, "description": text });
+        }
+        let Some(object) = value.as_object() else {
+            return json!({ "type": value.type_of(), "description": value.display().to_string() });
+        };
+
+        let mut remote = if object.is_callable() {
+            json!({
+                "type": "function",
+                "className": "Function",
+                "description": value.display().to_string(),
+            })
+        } else if object.is_array() {
+            let length = object
+                .get(js_string!("length"), context)
+                .ok()
+                .and_then(|length| length.as_number())
+                .unwrap_or_default();
+            json!({
+                "type": "object",
+                "subtype": "array",
+                "className": "Array",
+                "description": format!("Array({length})"),
+            })
+        } else if object.is::<Error>() {
+            // `stack` only lists the frames, so the message is added before it like in V8.
+            let mut description = value.display().to_string();
+            if let Some(stack) = object
+                .get(js_string!("stack"), context)
+                .ok()
+                .and_then(|stack| stack.as_string())
+            {
+                description.push('\n');
+                description.push_str(stack.to_std_string_escaped().trim_end());
+            }
+            json!({
+                "type": "object",
+                "subtype": "error",
+                "className": "Error",
+                "description": description,
+            })
+        } else {
+            json!({ "type": "object", "className": "Object", "description": "Object" })
+        };
+        remote["objectId"] = self.add(object, group).into();
+        remote
+    }
+
+    /// Lists the own properties of an object as `PropertyDescriptor`s, without calling getters.
+    pub(super) fn properties(
+        &mut self,
+        object: &JsObject,
+        group: &str,
+        context: &mut Context,
+    ) -> JsResult<Value> {
+        let get_own_property_descriptor = context
+            .intrinsics()
+            .constructors()
+            .object()
+            .constructor()
+            .get(js_string!("getOwnPropertyDescriptor"), context)?
+            .as_callable()
+            .ok_or_else(|| JsNativeError::typ().with_message("missing getOwnPropertyDescriptor"))?;
+
+        let mut result = Vec::new();
+        for key in object.own_property_keys(context)? {
+            let name = match &key {
+                PropertyKey::Symbol(symbol) => symbol.descriptive_string().to_std_string_escaped(),
+                key => key.to_string(),
+            };
+            let descriptor = get_own_property_descriptor.call(
+                &JsValue::undefined(),
+                &[object.clone().into(), key.into()],
+                context,
+            )?;
+            let Some(descriptor) = descriptor.as_object() else {
+                continue;
+            };
+
+            let mut property = json!({ "name": name, "isOwn": true });
+            for flag in ["writable", "enumerable", "configurable"] {
+                let value = descriptor.get(js_string!(flag), context)?;
+                if !value.is_undefined() {
+                    property[flag] = value.to_boolean().into();
+                }
+            }
+            for field in ["get", "set"] {
+                let value = descriptor.get(js_string!(field), context)?;
+                if !value.is_undefined() {
+                    property[field] = self.remote(&value, group, context);
+                }
+            }
+            if descriptor.has_own_property(js_string!("value"), context)? {
+                let value = descriptor.get(js_string!("value"), context)?;
+                property["value"] = self.remote(&value, group, context);
+            }
+            result.push(property);
+        }
+
+        let mut internal = Vec::new();
+        if let Some(prototype) = object.prototype() {
+            internal.push(json!({
+                "name": "[[Prototype]]",
+                "value": self.remote(&prototype.into(), group, context),
+            }));
+        }
+
+        Ok(json!({ "result": result, "internalProperties": internal }))
+    }
+
+    /// Resolves a `CallArgument`, which is a value, an unserializable value or an object.
+    pub(super) fn argument(&self, argument: &Value, context: &mut Context) -> Option<JsValue> {
+        if let Some(id) = argument["objectId"].as_str() {
+            return self.get(id).cloned().map(JsValue::from);
+        }
+        if let Some(text) = argument["unserializableValue"].as_str() {
+            return match text {
+                "NaN" => Some(f64::NAN.into()),
+                "Infinity" => Some(f64::INFINITY.into()),
+                "-Infinity" => Some(f64::NEG_INFINITY.into()),
+                "-0" => Some((-0.0).into()),
+                _ => None,
+            };
+        }
+        match argument.get("value") {
+            Some(value) => JsValue::from_json(value, context).ok(),
+            None => Some(JsValue::undefined()),
+        }
+    }
+}
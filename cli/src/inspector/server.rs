@@ -0,0 +1,349 @@
+//! The HTTP and WebSocket server of the inspector.
+//!
+//! DevTools finds debugging targets through the `/json/list` and `/json/version` HTTP endpoints,
+//! then connects to the `webSocketDebuggerUrl` of a target, where every WebSocket text message
+//! is a JSON encoded protocol message. See
+//! <https://chromedevtools.github.io/devtools-protocol/#endpoints>.
+//!
+//! Only requests for the local host are served, and WebSocket connections are only accepted
+//! from the DevTools frontend and pages of the local host, so that web pages can't find or
+//! drive the inspector.
+
+use serde_json::{Value, json};
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    path::Path,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+    },
+    thread,
+};
+use tungstenite::{Message, WebSocket, handshake::derive_accept_key, protocol::Role};
+
+/// The hosts that requests can be sent to, and that pages connecting to the inspector can be
+/// served from.
+///
+/// Requests for other hosts come from pages that rebound their domain name to the local host.
+const ALLOWED_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+
+/// A message from the server thread to the main thread.
+pub(super) enum Incoming {
+    /// A client connected, and can be sent messages with the given writer.
+    Connected(Writer),
+
+    /// The client sent a message.
+    Message(Value),
+
+    /// The client disconnected.
+    Disconnected,
+}
+
+/// The sending half of a WebSocket connection, which can be shared between threads.
+#[derive(Clone)]
+pub(super) struct Writer {
+    socket: Arc<Mutex<WebSocket<TcpStream>>>,
+}
+
+impl Writer {
+    /// Sends a JSON message to the client.
+    ///
+    /// Errors are ignored: they mean that the client went away, which the reader notices.
+    pub(super) fn send(&self, message: &Value) {
+        drop(self.send_message(Message::text(message.to_string())));
+    }
+
+    fn send_message(&self, message: Message) -> tungstenite::Result<()> {
+        let mut socket = self.socket.lock().expect("writer lock failed");
+        socket.send(message)
+    }
+}
+
+/// The receiving half of a WebSocket connection.
+///
+/// Writes are dropped: the replies to pings and closes that the WebSocket of the reader queues
+/// are sent by the [`Writer`] instead, so that they can't interleave with its messages.
+struct ReadHalf(TcpStream);
+
+impl Read for ReadHalf {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for ReadHalf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Describes the debugging target in the `/json/list` endpoint.
+pub(super) struct Target {
+    pub(super) id: String,
+    pub(super) title: String,
+    pub(super) url: String,
+}
+
+/// Listens on `port` of the local host, passing the messages of the client to `on_message`.
+///
+/// `on_message` returns `false` once messages can't be handled anymore. Connections and
+/// disconnections are sent to `sender`. Only one client can be connected at a time.
+pub(super) fn listen(
+    port: u16,
+    target: Target,
+    sender: Sender<Incoming>,
+    on_message: impl Fn(Value, &Writer) -> bool + Send + Sync + 'static,
+) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    let address = listener.local_addr()?;
+    let target = Arc::new(target);
+    let on_message = Arc::new(on_message);
+    let connected = Arc::new(AtomicBool::new(false));
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let target = target.clone();
+            let sender = sender.clone();
+            let on_message = on_message.clone();
+            let connected = connected.clone();
+            thread::spawn(move || {
+                // Errors only affect this connection, which gets closed.
+                drop(serve(
+                    stream,
+                    address,
+                    &target,
+                    &sender,
+                    &*on_message,
+                    &connected,
+                ));
+            });
+        }
+    });
+
+    Ok(address)
+}
+
+/// Serves a HTTP request, which is either a request to an endpoint or a WebSocket upgrade.
+fn serve(
+    stream: TcpStream,
+    address: SocketAddr,
+    target: &Target,
+    sender: &Sender<Incoming>,
+    on_message: &dyn Fn(Value, &Writer) -> bool,
+    connected: &AtomicBool,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let mut websocket_key = None;
+    let mut host = None;
+    let mut origin = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = Some(value.trim().to_owned());
+            if name.eq_ignore_ascii_case("Sec-WebSocket-Key") {
+                websocket_key = value;
+            } else if name.eq_ignore_ascii_case("Host") {
+                host = value;
+            } else if name.eq_ignore_ascii_case("Origin") {
+                origin = value;
+            }
+        }
+    }
+
+    if !host.as_deref().is_some_and(is_allowed_host)
+        || !origin.as_deref().is_none_or(is_allowed_origin)
+    {
+        return write!(
+            stream,
+            "HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\nThe host or origin of the request is not allowed"
+        );
+    }
+
+    let websocket_url = format!("{address}/{}", target.id);
+    match (path, websocket_key) {
+        ("/json" | "/json/list", _) => write_json(
+            &mut stream,
+            &json!([{
+                "description": "Boa instance",
+                "devtoolsFrontendUrl": format!(
+                    "devtools://devtools/bundled/js_app.html?experiments=true&v8only=true&ws={websocket_url}"
+                ),
+                "id": target.id,
+                "title": target.title,
+                "type": "node",
+                "url": target.url,
+                "webSocketDebuggerUrl": format!("ws://{websocket_url}"),
+            }]),
+        ),
+        ("/json/version", _) => write_json(
+            &mut stream,
+            &json!({
+                "Browser": concat!("Boa/", env!("CARGO_PKG_VERSION")),
+                "Protocol-Version": "1.3",
+            }),
+        ),
+        (path, Some(key)) if path.strip_prefix('/') == Some(&target.id) => {
+            if connected.swap(true, Ordering::AcqRel) {
+                return write!(
+                    stream,
+                    "HTTP/1.1 409 Conflict\r\nConnection: close\r\n\r\nA client is already connected"
+                );
+            }
+
+            let result = write!(
+                stream,
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                derive_accept_key(key.as_bytes())
+            )
+            .and_then(|()| {
+                let writer = Writer {
+                    socket: Arc::new(Mutex::new(WebSocket::from_raw_socket(
+                        stream,
+                        Role::Server,
+                        None,
+                    ))),
+                };
+                // The main thread only goes away when the program exits.
+                drop(sender.send(Incoming::Connected(writer.clone())));
+                let buffered = reader.buffer().to_vec();
+                let reader = WebSocket::from_partially_read(
+                    ReadHalf(reader.into_inner()),
+                    buffered,
+                    Role::Server,
+                    None,
+                );
+                read_messages(reader, &writer, on_message)
+            });
+
+            drop(sender.send(Incoming::Disconnected));
+            connected.store(false, Ordering::Release);
+            result
+        }
+        _ => write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\nConnection: close\r\n\r\nUnknown endpoint"
+        ),
+    }
+}
+
+/// Returns `true` if the `Host` header of a request names the local host.
+fn is_allowed_host(host: &str) -> bool {
+    ALLOWED_HOSTS.contains(&strip_port(host))
+}
+
+/// Returns `true` if a WebSocket connection can be opened from `origin`.
+fn is_allowed_origin(origin: &str) -> bool {
+    if origin == "devtools://devtools" {
+        return true;
+    }
+    ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| origin.strip_prefix(scheme))
+        .any(|host| ALLOWED_HOSTS.contains(&strip_port(host)))
+}
+
+/// Removes the port from a `host[:port]` string.
+fn strip_port(host: &str) -> &str {
+    match host.rsplit_once(':') {
+        Some((name, port))
+            if !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()) =>
+        {
+            name
+        }
+        _ => host,
+    }
+}
+
+fn write_json(stream: &mut TcpStream, body: &Value) -> io::Result<()> {
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json; charset=UTF-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Reads the messages of a WebSocket connection until the client closes it.
+fn read_messages(
+    mut reader: WebSocket<ReadHalf>,
+    writer: &Writer,
+    on_message: &dyn Fn(Value, &Writer) -> bool,
+) -> io::Result<()> {
+    loop {
+        let message = reader.read().map_err(io::Error::other)?;
+        let value = match message {
+            Message::Text(text) => serde_json::from_str(&text),
+            Message::Binary(data) => serde_json::from_slice(&data),
+            Message::Ping(data) => {
+                writer
+                    .send_message(Message::Pong(data))
+                    .map_err(io::Error::other)?;
+                continue;
+            }
+            Message::Close(frame) => {
+                return writer
+                    .send_message(Message::Close(frame))
+                    .map_err(io::Error::other);
+            }
+            Message::Pong(_) | Message::Frame(_) => continue,
+        }
+        .map_err(io::Error::other)?;
+        if !on_message(value, writer) {
+            return Ok(());
+        }
+    }
+}
+
+/// Returns the `file://` URL of an absolute path.
+pub(super) fn file_url(path: &Path) -> String {
+    let mut url = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            url.push(char::from(byte));
+        } else {
+            let _ = write!(url, "%{byte:02X}");
+        }
+    }
+    url
+}
+
+/// Returns the path of a `file://` URL.
+pub(super) fn url_path(url: &str) -> Option<String> {
+    let encoded = url.strip_prefix("file://")?.as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%'
+            && let Some(byte) = encoded
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            bytes.push(byte);
+            i += 3;
+        } else {
+            bytes.push(encoded[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
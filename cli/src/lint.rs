@@ -0,0 +1,189 @@
+//! The `boa lint` subcommand, which checks JavaScript files for problems without running them.
+//!
+//! Every file is parsed and linted on its own with the rules of [`boa_lint`], and the problems
+//! are printed as code frames. Fixes are only written back to the files with `--fix`.
+
+use boa_engine::{
+    Source,
+    ast::scope::Scope,
+    interner::Interner,
+    parser::{Parser, diagnostic::Diagnostic},
+};
+use boa_lint::{Level, LintDiagnostic, Linter, apply_fixes};
+use clap::ValueHint;
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use colored::{Colorize, control::SHOULD_COLORIZE};
+use std::path::{Path, PathBuf};
+
+/// Check JavaScript files for problems, without running them.
+#[derive(Debug, clap::Args)]
+pub(crate) struct LintArgs {
+    /// The JavaScript file(s) to be linted.
+    #[arg(
+        name = "FILE",
+        value_hint = ValueHint::FilePath,
+        required_unless_present = "list_rules"
+    )]
+    files: Vec<PathBuf>,
+
+    /// Treats the input files as modules.
+    #[arg(long, short = 'm')]
+    module: bool,
+
+    /// Sets the level of a rule, as `RULE=LEVEL`, where the level is `off`, `warn` or `error`.
+    #[arg(long = "rule", value_name = "RULE=LEVEL")]
+    rules: Vec<String>,
+
+    /// Sets an option of a rule, as `RULE.OPTION=VALUE`.
+    #[arg(long = "rule-option", value_name = "RULE.OPTION=VALUE")]
+    rule_options: Vec<String>,
+
+    /// Applies the fixes of the problems that have one, and writes them back to the files.
+    #[arg(long)]
+    fix: bool,
+
+    /// Lists the available rules and their default levels.
+    #[arg(long, exclusive = true)]
+    list_rules: bool,
+}
+
+/// Runs the `lint` subcommand, returning an error if any file has a syntax error or a problem
+/// with the `error` level.
+pub(crate) fn run(args: &LintArgs) -> Result<()> {
+    let linter = linter(args)?;
+
+    if args.list_rules {
+        for (rule, level) in linter.rules() {
+            println!(
+                "{:<24}{:<8}{}",
+                rule.name(),
+                level.as_str(),
+                rule.description()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut fixed = 0;
+    for file in &args.files {
+        let is_module = args.module
+            || file
+                .extension()
+                .is_some_and(|ext| ext == "mjs" || ext == "mts");
+        let mut text = std::fs::read_to_string(file)
+            .wrap_err_with(|| format!("could not read file `{}`", file.display()))?;
+
+        let mut diagnostics = match lint(&linter, file, &text, is_module) {
+            Ok(diagnostics) => diagnostics,
+            Err(frame) => {
+                println!("{frame}");
+                errors += 1;
+                continue;
+            }
+        };
+        if args.fix {
+            let (fixed_text, count) = apply_fixes(&text, &diagnostics);
+            if count > 0 {
+                std::fs::write(file, &fixed_text)
+                    .wrap_err_with(|| format!("could not write file `{}`", file.display()))?;
+                text = fixed_text;
+                fixed += count;
+                diagnostics = lint(&linter, file, &text, is_module).map_err(|frame| {
+                    eyre!(
+                        "the fixes of `{}` caused a syntax error:\n{frame}",
+                        file.display()
+                    )
+                })?;
+            }
+        }
+
+        for diagnostic in &diagnostics {
+            match diagnostic.level() {
+                Level::Error => errors += 1,
+                Level::Warn => warnings += 1,
+                Level::Off => {}
+            }
+            print!(
+                "{}",
+                Diagnostic::from(diagnostic)
+                    .with_path(file)
+                    .render(&text)
+                    .colored(SHOULD_COLORIZE.should_colorize())
+            );
+            if let Some(fix) = diagnostic.fix() {
+                println!("  {} {}", "= fix:".bold(), fix.message());
+            }
+            println!();
+        }
+    }
+
+    if fixed > 0 {
+        println!("{}", format!("fixed {fixed} problem(s)").green().bold());
+    }
+    let summary = format!("found {errors} error(s) and {warnings} warning(s)");
+    if errors > 0 {
+        return Err(eyre!(summary));
+    }
+    if warnings > 0 {
+        println!("{}", summary.bold());
+    }
+    Ok(())
+}
+
+/// Creates the linter with the rule levels and options of the arguments.
+fn linter(args: &LintArgs) -> Result<Linter> {
+    let mut linter = Linter::default();
+    for rule in &args.rules {
+        let (name, level) = rule
+            .split_once('=')
+            .ok_or_else(|| eyre!("invalid rule `{rule}`, expected `RULE=LEVEL`"))?;
+        linter.set_level(name, level.parse()?)?;
+    }
+    for option in &args.rule_options {
+        let (name, value) = option.split_once('=').unwrap_or((option, ""));
+        let (rule, name) = name
+            .split_once('.')
+            .ok_or_else(|| eyre!("invalid rule option `{option}`, expected `RULE.OPTION=VALUE`"))?;
+        linter.configure(rule, name, value)?;
+    }
+    Ok(linter)
+}
+
+/// Lints the text of a file, returning the code frame of the syntax error if it fails to parse.
+fn lint(
+    linter: &Linter,
+    file: &Path,
+    text: &str,
+    is_module: bool,
+) -> std::result::Result<Vec<LintDiagnostic>, String> {
+    let typescript = file
+        .extension()
+        .is_some_and(|ext| ext == "ts" || ext == "mts");
+    let source = Source::from_bytes(text)
+        .with_path(file)
+        .with_typescript(typescript);
+    let interner = &mut Interner::default();
+    let scope = Scope::new_global();
+    let mut parser = Parser::new(source);
+    let result = if is_module {
+        parser
+            .parse_module(&scope, interner)
+            .map(|module| linter.lint_module(&module, interner))
+    } else {
+        parser
+            .parse_script(&scope, interner)
+            .map(|script| linter.lint_script(&script, &scope, interner))
+    };
+    result.map_err(|error| {
+        Diagnostic::from(&error)
+            .with_path(file)
+            .render(text)
+            .colored(SHOULD_COLORIZE.should_colorize())
+            .to_string()
+    })
+}
@@ -7,9 +7,12 @@
 #![cfg_attr(not(test), deny(clippy::unwrap_used))]
 #![allow(clippy::print_stdout, clippy::print_stderr)]
 
+mod dap;
 mod debug;
 mod executor;
 mod helper;
+mod inspector;
+mod lint;
 mod logger;
 
 use crate::executor::Executor;
@@ -20,6 +23,7 @@ use boa_engine::error::JsErasedError;
 use boa_engine::job::NativeAsyncJob;
 use boa_engine::{
     Context, JsError, Source,
+    ast::Position,
     builtins::promise::PromiseState,
     context::ContextBuilder,
     module::{Module, SimpleModuleLoader},
@@ -27,15 +31,17 @@ use boa_engine::{
     script::Script,
     vm::flowgraph::{Direction, Graph},
 };
-use boa_parser::source::ReadChar;
-use clap::{Parser, ValueEnum, ValueHint};
+use boa_parser::{diagnostic::Diagnostic, source::ReadChar};
+use boa_runtime::Logger;
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
 use color_eyre::{
     Result, Section,
     eyre::{WrapErr, eyre},
 };
-use colored::Colorize;
+use colored::{Colorize, control::SHOULD_COLORIZE};
 use debug::init_boa_debug_object;
 use rustyline::{EditMode, Editor, config::Config, error::ReadlineError};
+use std::fmt::Debug;
 use std::time::{Duration, Instant};
 use std::{
     fs::OpenOptions,
@@ -44,6 +50,7 @@ use std::{
     rc::Rc,
     thread,
 };
+use tracing_subscriber::{Layer, filter::LevelFilter, layer::SubscriberExt};
 
 // ----
 
@@ -87,9 +94,18 @@ static CLI_HISTORY: &str = ".boa_history";
 // is an optional argument that optionally takes a value ([--opt=[val]]).
 // https://docs.rs/structopt/0.3.11/structopt/#type-magic
 #[derive(Debug, Parser)]
-#[command(author, version, about, name = "boa")]
+#[command(
+    author,
+    version,
+    about,
+    name = "boa",
+    args_conflicts_with_subcommands = true
+)]
 #[allow(clippy::struct_excessive_bools)] // NOTE: Allow having more than 3 bools in struct
 struct Opt {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The JavaScript file(s) to be evaluated.
     #[arg(name = "FILE", value_hint = ValueHint::FilePath)]
     files: Vec<PathBuf>,
@@ -161,6 +177,11 @@ struct Opt {
     #[arg(long)]
     no_can_block: bool,
 
+    /// Show the original positions of generated code in stack traces, using the source maps
+    /// referenced by its `//# sourceMappingURL=` comments.
+    #[arg(long)]
+    enable_source_maps: bool,
+
     /// Treats the input files as modules.
     #[arg(long, short = 'm', group = "mod")]
     module: bool,
@@ -177,6 +198,93 @@ struct Opt {
     /// Suppress the welcome banner when starting the REPL.
     #[arg(long, short = 'q')]
     quiet: bool,
+
+    /// Start a Debug Adapter Protocol server to debug the input files, talking over stdio
+    /// or over the given TCP port of the local host.
+    #[arg(
+        long,
+        value_name = "PORT",
+        conflicts_with_all = ["dump_ast", "trace", "graph", "expression"]
+    )]
+    #[allow(clippy::option_option)]
+    dap: Option<Option<u16>>,
+
+    /// Start a Chrome DevTools Protocol inspector on the given port of the local host
+    /// (9229 by default), and wait for a client to attach before running the input files.
+    #[arg(
+        long,
+        value_name = "PORT",
+        requires = "FILE",
+        conflicts_with_all = ["dump_ast", "trace", "graph", "expression", "dap"]
+    )]
+    #[allow(clippy::option_option)]
+    inspect: Option<Option<u16>>,
+
+    /// Record a CPU profile of the execution, and write it to the given file when the program
+    /// ends (`boa.cpuprofile` or `boa.collapsed` by default, depending on the format).
+    #[arg(
+        long,
+        value_name = "FILE",
+        require_equals = true,
+        conflicts_with_all = ["dap", "inspect"]
+    )]
+    #[allow(clippy::option_option)]
+    cpu_prof: Option<Option<PathBuf>>,
+
+    /// Specifies the format of the CPU profile. Default is cpuprofile.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        ignore_case = true,
+        value_enum,
+        default_value_t,
+        requires = "cpu_prof"
+    )]
+    cpu_prof_format: CpuProfileFormat,
+
+    /// The interval between the samples of the CPU profile, in microseconds.
+    #[arg(
+        long,
+        value_name = "MICROSECONDS",
+        default_value_t = 1000,
+        requires = "cpu_prof"
+    )]
+    cpu_prof_interval: u64,
+
+    /// Collect the code coverage of the execution, and write it to the given directory when
+    /// the program ends, as V8 coverage JSON and as an LCOV `lcov.info` tracefile.
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        conflicts_with_all = ["dap", "inspect"]
+    )]
+    coverage: Option<PathBuf>,
+
+    /// Collect execution statistics of the VM, and print them to the standard error when the
+    /// program ends, as tables or as JSON. Default is table.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        ignore_case = true,
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "table",
+        conflicts_with_all = ["dap", "inspect"]
+    )]
+    vm_stats: Option<VmStatsFormat>,
+
+    /// Record the parsing, compilation, evaluation, jobs and garbage collections of the engine,
+    /// and write them to the given file in the Chrome trace event format, which
+    /// `chrome://tracing` and Perfetto can open (`boa.trace.json` by default).
+    #[arg(long, value_name = "FILE", require_equals = true)]
+    #[allow(clippy::option_option)]
+    trace_events: Option<Option<PathBuf>>,
+
+    /// Also record every call of a JavaScript function in the trace events.
+    #[arg(long, requires = "trace_events")]
+    trace_event_calls: bool,
 }
 
 impl Opt {
@@ -186,6 +294,12 @@ impl Opt {
     }
 }
 
+/// The subcommands of the CLI, which don't evaluate any code.
+#[derive(Debug, Subcommand)]
+enum Command {
+    Lint(lint::LintArgs),
+}
+
 /// The different types of format available for dumping.
 #[derive(Debug, Copy, Clone, Default, ValueEnum)]
 enum DumpFormat {
@@ -215,6 +329,25 @@ enum FlowgraphFormat {
     Mermaid,
 }
 
+/// Represents the format of the CPU profile.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum CpuProfileFormat {
+    /// Generates a `.cpuprofile` JSON file, which the Chrome developer tools can open.
+    #[default]
+    Cpuprofile,
+    /// Generates collapsed stacks, which flamegraph tools can render.
+    Collapsed,
+}
+
+/// Represents the format of the VM statistics.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum VmStatsFormat {
+    /// Prints tables, for humans.
+    Table,
+    /// Prints JSON, for other tools.
+    Json,
+}
+
 /// Represents the direction of the instruction flowgraph.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum FlowgraphDirection {
@@ -383,6 +516,44 @@ fn uncaught_error(error: &JsError) -> String {
     )
 }
 
+/// Formats an uncaught error thrown by `code`, the code of `file`, with a code frame of the
+/// place where it was thrown if it is known.
+#[must_use]
+fn uncaught_file_error(error: &JsError, file: &Path, code: &[u8]) -> String {
+    let path = file.display().to_string();
+    let location = error.stack_frames().and_then(|frames| {
+        let frame = frames
+            .iter()
+            .find(|frame| frame.path() == Some(path.as_str()))?;
+        Some(Position::new(frame.line()?, frame.column()?))
+    });
+    let Some(location) = location else {
+        return uncaught_error(error);
+    };
+
+    Diagnostic::new(format!("Uncaught {error}"))
+        .with_span(location.into())
+        .with_path(file)
+        .render(&String::from_utf8_lossy(code))
+        .colored(SHOULD_COLORIZE.should_colorize())
+        .to_string()
+}
+
+/// Renders the syntax error found by the parser in `code`, the code of `file`, as a code frame.
+///
+/// Returns `None` if the error wasn't created from a parser error.
+fn syntax_error_frame(error: &JsError, file: &Path, code: &[u8]) -> Option<String> {
+    let diagnostic = error.as_native()?.diagnostic()?;
+    Some(
+        diagnostic
+            .clone()
+            .with_path(file)
+            .render(&String::from_utf8_lossy(code))
+            .colored(SHOULD_COLORIZE.should_colorize())
+            .to_string(),
+    )
+}
+
 #[must_use]
 fn uncaught_job_error(error: &JsError) -> String {
     format!(
@@ -453,17 +624,31 @@ fn evaluate_file(
     loader: &SimpleModuleLoader,
     printer: &SharedExternalPrinterLogger,
 ) -> Result<()> {
-    // Treat files with .mjs extension automatically as modules.
-    let is_module = args.module || file.extension().is_some_and(|ext| ext == "mjs");
+    // Treat files with .mjs and .mts extensions automatically as modules.
+    let is_module = args.module
+        || file
+            .extension()
+            .is_some_and(|ext| ext == "mjs" || ext == "mts");
+    // Strip the types of TypeScript files.
+    let typescript = file
+        .extension()
+        .is_some_and(|ext| ext == "ts" || ext == "mts");
+    // The code is read once, so that code frames of errors show the code that was parsed.
+    let code = std::fs::read(file)?;
+    let read_source = || {
+        Source::from_bytes(&code)
+            .with_path(file)
+            .with_typescript(typescript)
+    };
 
     if args.has_dump_flag() {
-        return dump(Source::from_filepath(file)?, args, is_module, context);
+        return dump(read_source(), args, is_module, context);
     }
 
     if let Some(flowgraph) = args.flowgraph {
         let flowgraph = generate_flowgraph(
             context,
-            Source::from_filepath(file)?,
+            read_source(),
             flowgraph.unwrap_or(FlowgraphFormat::Graphviz),
             args.flowgraph_direction,
         )?;
@@ -474,13 +659,22 @@ fn evaluate_file(
     }
 
     if is_module {
-        let source = Source::from_filepath(file)?;
+        let source = read_source();
         let mut counters = Counters::new(args.time);
         let module = {
             let _timer = counters.new_timer("Parsing");
             Module::parse(source, None, context)
         };
-        let module = module.map_err(|e| e.into_erased(context))?;
+        let module = match module {
+            Ok(module) => module,
+            Err(err) => {
+                if let Some(frame) = syntax_error_frame(&err, file, &code) {
+                    printer.print(frame);
+                    return Err(eyre!("parsing failed"));
+                }
+                return Err(err.into_erased(context).into());
+            }
+        };
 
         loader.insert(
             file.canonicalize()
@@ -505,13 +699,22 @@ fn evaluate_file(
         };
     }
 
-    let source = Source::from_filepath(file)?;
+    let source = read_source();
     let mut counters = Counters::new(args.time);
     let script = {
         let _timer = counters.new_timer("Parsing");
         Script::parse(source, None, context)
     };
-    let script = script.map_err(|e| e.into_erased(context))?;
+    let script = match script {
+        Ok(script) => script,
+        Err(err) => {
+            if let Some(frame) = syntax_error_frame(&err, file, &code) {
+                printer.print(frame);
+                return Err(eyre!("parsing failed"));
+            }
+            return Err(err.into_erased(context).into());
+        }
+    };
 
     let result = {
         let _timer = counters.new_timer("Execution");
@@ -523,11 +726,11 @@ fn evaluate_file(
     match result {
         Ok(v) => {
             if !v.is_undefined() {
-                println!("{}", v.display());
+                printer.print(format!("{}\n", v.display()));
             }
         }
         Err(v) => {
-            printer.print(uncaught_error(&v));
+            printer.print(uncaught_file_error(&v, file, &code));
             return Err(eyre!("execution failed"));
         }
     }
@@ -563,6 +766,20 @@ fn main() -> Result<()> {
 
     let args = Opt::parse();
 
+    if let Some(Command::Lint(lint_args)) = &args.command {
+        return lint::run(lint_args);
+    }
+
+    // Writes the end of the trace file when dropped, at the end of `main`.
+    let _trace_events = args
+        .trace_events
+        .as_ref()
+        .map(|path| {
+            let path = path.as_deref().unwrap_or(Path::new("boa.trace.json"));
+            start_trace_events(path, args.trace_event_calls)
+        })
+        .transpose()?;
+
     // A channel of expressions to run.
     let (sender, receiver) = async_channel::unbounded();
     let printer = SharedExternalPrinterLogger::new();
@@ -579,12 +796,33 @@ fn main() -> Result<()> {
     // Strict mode
     context.strict(args.strict);
 
-    // Add `console`.
-    add_runtime(printer.clone(), context);
+    let inspector = args
+        .inspect
+        .map(|port| {
+            inspector::Inspector::new(
+                port.unwrap_or(inspector::DEFAULT_PORT),
+                &args.files,
+                context,
+            )
+        })
+        .transpose()?;
+
+    // Add `console`, forwarding the messages to the inspector if there is one.
+    match &inspector {
+        Some(inspector) => add_runtime(inspector.logger(printer.clone()), context),
+        None => add_runtime(printer.clone(), context),
+    }
 
     // Trace Output
     context.set_trace(args.trace);
 
+    context.set_source_map_loading(args.enable_source_maps);
+
+    // The flowgraph and the coverage should include the code of all nested functions.
+    if args.flowgraph.is_some() || args.coverage.is_some() {
+        context.set_lazy_compilation(false);
+    }
+
     if args.debug_object {
         init_boa_debug_object(context);
     }
@@ -609,27 +847,20 @@ fn main() -> Result<()> {
     optimizer_options.set(OptimizerOptions::OPTIMIZE_ALL, args.optimize);
     context.set_optimizer_options(optimizer_options);
 
-    if !args.files.is_empty() {
-        evaluate_files(&args, context, &loader, &printer)?;
+    if let Some(port) = args.dap {
+        return dap::run(port, &args, context, &loader, &printer);
+    }
 
-        if let Some(ref expr) = args.expression {
-            evaluate_expr(expr, &args, context, &printer)?;
-        }
+    if let Some(inspector) = &inspector {
+        return inspector::run(inspector, &args, context, &loader, &printer);
+    }
 
-        return Ok(());
-    } else if let Some(ref expr) = args.expression {
-        evaluate_expr(expr, &args, context, &printer)?;
-        return Ok(());
-    } else if !io::stdin().is_terminal() {
-        let mut input = String::new();
-        io::stdin()
-            .read_to_string(&mut input)
-            .wrap_err("failed to read stdin")?;
-        return if input.is_empty() {
-            Ok(())
-        } else {
-            evaluate_expr(&input, &args, context, &printer)
-        };
+    let reports = Reports::start(&args, context);
+
+    if !args.files.is_empty() || args.expression.is_some() || !io::stdin().is_terminal() {
+        let result = evaluate_input(&args, context, &loader, &printer);
+        reports.write(context)?;
+        return result;
     }
 
     // Print the welcome banner unless --quiet is passed.
@@ -713,10 +944,155 @@ fn main() -> Result<()> {
     let result = context.run_jobs().map_err(|e| e.into_erased(context));
 
     handle.join().expect("failed to join thread");
+    reports.write(context)?;
 
     Ok(result?)
 }
 
+/// Evaluates the input files and expression, or the standard input if there are none.
+fn evaluate_input(
+    args: &Opt,
+    context: &mut Context,
+    loader: &SimpleModuleLoader,
+    printer: &SharedExternalPrinterLogger,
+) -> Result<()> {
+    if !args.files.is_empty() {
+        evaluate_files(args, context, loader, printer)?;
+
+        if let Some(ref expr) = args.expression {
+            evaluate_expr(expr, args, context, printer)?;
+        }
+
+        Ok(())
+    } else if let Some(ref expr) = args.expression {
+        evaluate_expr(expr, args, context, printer)
+    } else {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("failed to read stdin")?;
+        if input.is_empty() {
+            Ok(())
+        } else {
+            evaluate_expr(&input, args, context, printer)
+        }
+    }
+}
+
+/// The reports that are recorded while the program runs, and written when it ends.
+struct Reports {
+    cpu_profile: Option<(PathBuf, CpuProfileFormat)>,
+    coverage: Option<PathBuf>,
+    vm_stats: Option<VmStatsFormat>,
+}
+
+impl Reports {
+    /// Starts recording the reports requested by `args`.
+    fn start(args: &Opt, context: &mut Context) -> Self {
+        let cpu_profile = args.cpu_prof.as_ref().map(|path| {
+            let path = path.clone().unwrap_or_else(|| {
+                PathBuf::from(match args.cpu_prof_format {
+                    CpuProfileFormat::Cpuprofile => "boa.cpuprofile",
+                    CpuProfileFormat::Collapsed => "boa.collapsed",
+                })
+            });
+            (path, args.cpu_prof_format)
+        });
+        if cpu_profile.is_some() {
+            context.start_cpu_profile(Duration::from_micros(args.cpu_prof_interval.max(1)));
+        }
+        if args.coverage.is_some() {
+            context.start_coverage();
+        }
+        if args.vm_stats.is_some() {
+            context.start_vm_stats();
+        }
+
+        Self {
+            cpu_profile,
+            coverage: args.coverage.clone(),
+            vm_stats: args.vm_stats,
+        }
+    }
+
+    /// Stops recording the reports, and writes them.
+    fn write(&self, context: &mut Context) -> Result<()> {
+        if let (Some((path, format)), Some(profile)) =
+            (&self.cpu_profile, context.stop_cpu_profile())
+        {
+            write_report(path, |writer| match format {
+                CpuProfileFormat::Cpuprofile => profile.write_json(writer),
+                CpuProfileFormat::Collapsed => profile.write_collapsed(writer),
+            })?;
+        }
+
+        if let (Some(directory), Some(coverage)) = (&self.coverage, context.stop_coverage()) {
+            std::fs::create_dir_all(directory).wrap_err_with(|| {
+                format!("could not create the directory `{}`", directory.display())
+            })?;
+
+            // Named like the files Node.js writes to `NODE_V8_COVERAGE`, so that the tools
+            // reading that directory find it.
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let name = format!("coverage-{}-{timestamp}-0.json", std::process::id());
+            write_report(&directory.join(name), |writer| coverage.write_json(writer))?;
+            write_report(&directory.join("lcov.info"), |writer| {
+                coverage.write_lcov(writer)
+            })?;
+        }
+
+        if let (Some(format), Some(stats)) = (self.vm_stats, context.stop_vm_stats()) {
+            let stderr = io::stderr().lock();
+            match format {
+                VmStatsFormat::Table => stats.write_table(stderr),
+                VmStatsFormat::Json => stats.write_json(stderr),
+            }
+            .wrap_err("could not write the VM statistics")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Starts writing the `tracing` spans of the engine to the file at `path`, in the Chrome trace
+/// event format, until the returned guard is dropped.
+fn start_trace_events(path: &Path, calls: bool) -> Result<tracing_chrome::FlushGuard> {
+    let file = std::fs::File::create(path)
+        .wrap_err_with(|| format!("could not create `{}`", path.display()))?;
+    let (layer, guard) = tracing_chrome::ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build();
+
+    // Function calls are `TRACE` spans, and the rest of the spans are `INFO` spans.
+    let level = if calls {
+        LevelFilter::TRACE
+    } else {
+        LevelFilter::INFO
+    };
+    tracing::subscriber::set_global_default(
+        tracing_subscriber::registry().with(layer.with_filter(level)),
+    )
+    .wrap_err("could not record the trace events")?;
+    Ok(guard)
+}
+
+/// Creates the file at `path`, and writes a report to it.
+fn write_report(
+    path: &Path,
+    write: impl FnOnce(&mut io::BufWriter<std::fs::File>) -> io::Result<()>,
+) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .wrap_err_with(|| format!("could not create `{}`", path.display()))?;
+    let mut writer = io::BufWriter::new(file);
+    write(&mut writer)
+        .and_then(|()| writer.flush())
+        .wrap_err_with(|| format!("could not write `{}`", path.display()))
+}
+
 fn readline_thread_main(
     sender: &Sender<String>,
     printer_out: &SharedExternalPrinterLogger,
@@ -827,10 +1203,10 @@ fn start_readline_thread(
 }
 
 /// Adds the CLI runtime to the context with default options.
-fn add_runtime(printer: SharedExternalPrinterLogger, context: &mut Context) {
+fn add_runtime<L: Logger + Debug + 'static>(logger: L, context: &mut Context) {
     boa_runtime::register(
         (
-            boa_runtime::extensions::ConsoleExtension(printer),
+            boa_runtime::extensions::ConsoleExtension(logger),
             #[cfg(feature = "fetch")]
             boa_runtime::extensions::FetchExtension(
                 boa_runtime::fetch::BlockingReqwestFetcher::default(),
@@ -0,0 +1,735 @@
+//! Generation of expressions.
+
+use super::{Precedence, Printer, Start, escape_template};
+use crate::{
+    Expression, Spanned,
+    expression::{
+        Call, Identifier, ImportCall, ImportPhase, Optional, OptionalOperationKind, RegExpLiteral,
+        TaggedTemplate,
+        access::{PropertyAccess, PropertyAccessField},
+        jsx::{JsxAttribute, JsxAttributeValue, JsxChild, JsxElement, JsxElementName},
+        literal::{
+            ArrayLiteral, LiteralKind, ObjectLiteral, PropertyDefinition, TemplateElement,
+            TemplateLiteral,
+        },
+        operator::{
+            Assign, Binary, Update,
+            assign::AssignTarget,
+            binary::{ArithmeticOp, BinaryOp, BitwiseOp, LogicalOp, RelationalOp},
+            update::{UpdateOp, UpdateTarget},
+        },
+    },
+    pattern::Pattern,
+    property::PropertyName,
+};
+use boa_interner::Sym;
+
+impl Printer<'_> {
+    /// Writes an expression, in parentheses if its precedence is lower than `min` or if it can't
+    /// appear at the current position.
+    pub(super) fn expression(&mut self, expression: &Expression, min: Precedence) {
+        let parenthesized = precedence(expression) < min
+            || (self.no_in && is_in(expression))
+            || self.needs_start_parentheses(expression);
+        if parenthesized {
+            self.parenthesized(|printer| printer.expression_unparenthesized(expression));
+        } else {
+            self.expression_unparenthesized(expression);
+        }
+    }
+
+    /// Checks if `expression` can't appear at the position marked as the start of some code.
+    fn needs_start_parentheses(&self, expression: &Expression) -> bool {
+        let Some((_, start)) = self.start else {
+            return false;
+        };
+        if !self.at_start(start) {
+            return false;
+        }
+
+        let is_function = matches!(
+            expression,
+            Expression::FunctionExpression(_)
+                | Expression::GeneratorExpression(_)
+                | Expression::AsyncFunctionExpression(_)
+                | Expression::AsyncGeneratorExpression(_)
+                | Expression::ClassExpression(_)
+        );
+        let is_object = matches!(expression, Expression::ObjectLiteral(_))
+            || matches!(
+                expression,
+                Expression::Assign(assign)
+                    if matches!(assign.lhs(), AssignTarget::Pattern(Pattern::Object(_)))
+            );
+        let is_identifier =
+            |sym| matches!(expression, Expression::Identifier(ident) if ident.sym() == sym);
+
+        match start {
+            Start::Statement => is_function || is_object || is_identifier(Sym::LET),
+            Start::ArrowBody => is_object,
+            Start::ExportDefault => is_function,
+            Start::ForOf => is_identifier(Sym::LET) || is_identifier(Sym::ASYNC),
+            Start::ForIn => is_identifier(Sym::LET),
+        }
+    }
+
+    fn expression_unparenthesized(&mut self, expression: &Expression) {
+        if !matches!(
+            expression,
+            Expression::Identifier(_) | Expression::Parenthesized(_)
+        ) {
+            self.map(expression.span().start(), None);
+        }
+
+        match expression {
+            Expression::This(_) => self.token("this"),
+            Expression::Identifier(ident) => self.identifier(*ident),
+            Expression::Literal(literal) => self.literal(literal.kind()),
+            Expression::RegExpLiteral(regexp) => self.regexp(regexp),
+            Expression::ArrayLiteral(array) => self.array_literal(array),
+            Expression::ObjectLiteral(object) => self.object_literal(object),
+            Expression::Spread(spread) => {
+                self.token("...");
+                self.expression(spread.target(), Precedence::Assignment);
+            }
+            Expression::FunctionExpression(f) => self.function(
+                "function",
+                f.has_binding_identifier().then(|| f.name()).flatten(),
+                f.parameters(),
+                f.body(),
+            ),
+            Expression::GeneratorExpression(f) => self.function(
+                "function*",
+                f.has_binding_identifier().then(|| f.name()).flatten(),
+                f.parameters(),
+                f.body(),
+            ),
+            Expression::AsyncFunctionExpression(f) => self.function(
+                "async function",
+                f.has_binding_identifier().then(|| f.name()).flatten(),
+                f.parameters(),
+                f.body(),
+            ),
+            Expression::AsyncGeneratorExpression(f) => self.function(
+                "async function*",
+                f.has_binding_identifier().then(|| f.name()).flatten(),
+                f.parameters(),
+                f.body(),
+            ),
+            Expression::ArrowFunction(f) => self.arrow_function(false, f.parameters(), f.body()),
+            Expression::AsyncArrowFunction(f) => {
+                self.arrow_function(true, f.parameters(), f.body());
+            }
+            Expression::ClassExpression(class) => self.class(
+                class.name_scope().and(class.name()),
+                class.super_ref(),
+                class.constructor(),
+                class.elements(),
+            ),
+            Expression::TemplateLiteral(template) => self.template_literal(template),
+            Expression::PropertyAccess(access) => self.property_access(access),
+            Expression::New(new) => {
+                self.token("new");
+                let constructor = new.constructor();
+                if precedence(constructor) < Precedence::Call || contains_call(constructor) {
+                    self.parenthesized(|printer| {
+                        printer.expression(constructor, Precedence::Sequence);
+                    });
+                } else {
+                    self.expression(constructor, Precedence::Call);
+                }
+                self.arguments(new.arguments());
+            }
+            Expression::Call(call) => self.call(call),
+            Expression::SuperCall(call) => {
+                self.token("super");
+                self.arguments(call.arguments());
+            }
+            Expression::ImportCall(call) => self.import_call(call),
+            Expression::Optional(optional) => self.optional(optional),
+            Expression::TaggedTemplate(template) => self.tagged_template(template),
+            Expression::NewTarget(_) => {
+                self.token("new");
+                self.token(".");
+                self.token("target");
+            }
+            Expression::ImportMeta(_) => {
+                self.token("import");
+                self.token(".");
+                self.token("meta");
+            }
+            Expression::Assign(assign) => self.assign(assign),
+            Expression::Unary(unary) => {
+                self.token(unary.op().as_str());
+                self.expression(unary.target(), Precedence::Unary);
+            }
+            Expression::Update(update) => self.update(update),
+            Expression::Binary(binary) => self.binary(binary),
+            Expression::BinaryInPrivate(binary) => {
+                let name = format!(
+                    "#{}",
+                    self.interner.resolve_expect(binary.lhs().description())
+                );
+                self.token(&name);
+                self.space();
+                self.token("in");
+                self.space();
+                self.expression(binary.rhs(), Precedence::Shift);
+            }
+            Expression::Conditional(conditional) => {
+                self.expression(conditional.condition(), Precedence::Coalesce);
+                self.space();
+                self.token("?");
+                self.space();
+                self.delimited(|printer| {
+                    printer.expression(conditional.if_true(), Precedence::Assignment);
+                });
+                self.space();
+                self.token(":");
+                self.space();
+                self.expression(conditional.if_false(), Precedence::Assignment);
+            }
+            Expression::Await(r#await) => {
+                self.token("await");
+                self.space();
+                self.expression(r#await.target(), Precedence::Unary);
+            }
+            Expression::Yield(r#yield) => {
+                self.token("yield");
+                if r#yield.delegate() {
+                    self.token("*");
+                }
+                if let Some(target) = r#yield.target() {
+                    self.space();
+                    self.expression(target, Precedence::Assignment);
+                }
+            }
+            Expression::Parenthesized(parenthesized) => {
+                self.map(parenthesized.span().start(), None);
+                self.parenthesized(|printer| {
+                    printer.expression(parenthesized.expression(), Precedence::Sequence);
+                });
+            }
+            Expression::Jsx(jsx) => self.jsx_element(jsx.element()),
+        }
+    }
+
+    /// Writes an identifier reference, mapped to its name in the original source.
+    pub(super) fn identifier(&mut self, ident: Identifier) {
+        self.map(ident.span().start(), Some(ident.sym()));
+        self.sym(ident.sym());
+    }
+
+    fn literal(&mut self, literal: &LiteralKind) {
+        match literal {
+            LiteralKind::String(sym) => self.string(*sym),
+            LiteralKind::Num(number) => self.token(&format_number(*number)),
+            LiteralKind::Int(number) => self.token(&number.to_string()),
+            LiteralKind::BigInt(bigint) => self.token(&format!("{bigint}n")),
+            LiteralKind::Bool(true) => self.token("true"),
+            LiteralKind::Bool(false) => self.token("false"),
+            LiteralKind::Null => self.token("null"),
+            LiteralKind::Undefined => {
+                self.token("void");
+                self.token("0");
+            }
+        }
+    }
+
+    fn regexp(&mut self, regexp: &RegExpLiteral) {
+        let regexp = format!(
+            "/{}/{}",
+            self.interner.resolve_expect(regexp.pattern()),
+            self.interner.resolve_expect(regexp.flags())
+        );
+        self.token(&regexp);
+    }
+
+    fn array_literal(&mut self, array: &ArrayLiteral) {
+        let elements = array.as_ref();
+        self.token("[");
+        self.delimited(|printer| {
+            for (index, element) in elements.iter().enumerate() {
+                if index > 0 {
+                    printer.comma();
+                }
+                if let Some(element) = element {
+                    printer.expression(element, Precedence::Assignment);
+                }
+            }
+            // A trailing hole needs its own comma.
+            if matches!(elements.last(), Some(None)) {
+                printer.token(",");
+            }
+        });
+        self.token("]");
+    }
+
+    fn object_literal(&mut self, object: &ObjectLiteral) {
+        self.list_block(object.properties(), |printer, property| match property {
+            PropertyDefinition::IdentifierReference(ident) => printer.identifier(*ident),
+            PropertyDefinition::Property(name, value) => {
+                printer.property_name(name);
+                printer.token(":");
+                printer.space();
+                printer.expression(value, Precedence::Assignment);
+            }
+            PropertyDefinition::MethodDefinition(method) => printer.method(
+                false,
+                method.kind(),
+                |printer| printer.property_name(method.name()),
+                method.parameters(),
+                method.body(),
+            ),
+            PropertyDefinition::SpreadObject(target) => {
+                printer.token("...");
+                printer.expression(target, Precedence::Assignment);
+            }
+            PropertyDefinition::CoverInitializedName(ident, init) => {
+                printer.identifier(*ident);
+                printer.initializer(init);
+            }
+        });
+    }
+
+    /// Writes the ` = init` initializer of a binding or parameter.
+    pub(super) fn initializer(&mut self, init: &Expression) {
+        self.space();
+        self.token("=");
+        self.space();
+        self.expression(init, Precedence::Assignment);
+    }
+
+    /// Writes the name of a property, method or field.
+    pub(super) fn property_name(&mut self, name: &PropertyName) {
+        match name {
+            PropertyName::Literal(ident) => {
+                self.map(ident.span().start(), Some(ident.sym()));
+                self.name(ident.sym());
+            }
+            PropertyName::Computed(expression) => {
+                self.token("[");
+                self.delimited(|printer| printer.expression(expression, Precedence::Assignment));
+                self.token("]");
+            }
+        }
+    }
+
+    fn template_literal(&mut self, template: &TemplateLiteral) {
+        self.token("`");
+        for element in template.elements() {
+            match element {
+                TemplateElement::String(cooked) => {
+                    let escaped = escape_template(self.interner.resolve_expect(*cooked).utf16());
+                    self.raw(&escaped);
+                }
+                TemplateElement::Expr(expression) => self.substitution(expression),
+            }
+        }
+        self.raw("`");
+    }
+
+    fn tagged_template(&mut self, template: &TaggedTemplate) {
+        self.callee(template.tag());
+        self.token("`");
+        for (index, raw) in template.raws().iter().enumerate() {
+            if index > 0
+                && let Some(expression) = template.exprs().get(index - 1)
+            {
+                self.substitution(expression);
+            }
+            let raw = self.interner.resolve_expect(*raw).to_string();
+            self.raw(&raw);
+        }
+        self.raw("`");
+    }
+
+    /// Writes a `${expression}` substitution of a template.
+    fn substitution(&mut self, expression: &Expression) {
+        self.raw("${");
+        self.delimited(|printer| printer.expression(expression, Precedence::Sequence));
+        self.raw("}");
+    }
+
+    /// Writes a property access, also used as a simple assignment target.
+    pub(super) fn property_access(&mut self, access: &PropertyAccess) {
+        match access {
+            PropertyAccess::Simple(access) => {
+                self.member_target(access.target());
+                self.field(access.field());
+            }
+            PropertyAccess::Private(access) => {
+                self.member_target(access.target());
+                self.token(".");
+                let name = format!(
+                    "#{}",
+                    self.interner.resolve_expect(access.field().description())
+                );
+                self.token(&name);
+            }
+            PropertyAccess::Super(access) => {
+                self.token("super");
+                self.field(access.field());
+            }
+        }
+    }
+
+    fn field(&mut self, field: &PropertyAccessField) {
+        match field {
+            PropertyAccessField::Const(ident) => {
+                self.token(".");
+                self.identifier(*ident);
+            }
+            PropertyAccessField::Expr(expression) => {
+                self.token("[");
+                self.delimited(|printer| printer.expression(expression, Precedence::Sequence));
+                self.token("]");
+            }
+        }
+    }
+
+    /// Writes the target of a member access, where a number would take the dot as its decimal
+    /// point.
+    fn member_target(&mut self, target: &Expression) {
+        if matches!(
+            target,
+            Expression::Literal(literal)
+                if matches!(literal.kind(), LiteralKind::Num(_) | LiteralKind::Int(_))
+        ) {
+            self.parenthesized(|printer| printer.expression(target, Precedence::Sequence));
+        } else {
+            self.callee(target);
+        }
+    }
+
+    /// Writes the target of a call or member access, where an optional chain would change the
+    /// short-circuiting of the chain.
+    fn callee(&mut self, callee: &Expression) {
+        if matches!(callee, Expression::Optional(_)) {
+            self.parenthesized(|printer| printer.expression(callee, Precedence::Sequence));
+        } else {
+            self.expression(callee, Precedence::Call);
+        }
+    }
+
+    fn call(&mut self, call: &Call) {
+        self.callee(call.function());
+        self.arguments(call.args());
+    }
+
+    fn arguments(&mut self, arguments: &[Expression]) {
+        self.parenthesized(|printer| {
+            for (index, argument) in arguments.iter().enumerate() {
+                if index > 0 {
+                    printer.comma();
+                }
+                printer.expression(argument, Precedence::Assignment);
+            }
+        });
+    }
+
+    fn import_call(&mut self, call: &ImportCall) {
+        self.token("import");
+        match call.phase() {
+            ImportPhase::Evaluation => {}
+            ImportPhase::Defer => {
+                self.token(".");
+                self.token("defer");
+            }
+            ImportPhase::Source => {
+                self.token(".");
+                self.token("source");
+            }
+        }
+        self.parenthesized(|printer| {
+            printer.expression(call.specifier(), Precedence::Assignment);
+            if let Some(options) = call.options() {
+                printer.comma();
+                printer.expression(options, Precedence::Assignment);
+            }
+        });
+    }
+
+    fn optional(&mut self, optional: &Optional) {
+        self.callee(optional.target());
+        for operation in optional.chain() {
+            self.map(operation.span().start(), None);
+            if operation.shorted() {
+                self.token("?.");
+            }
+            match operation.kind() {
+                OptionalOperationKind::SimplePropertyAccess { field } => match field {
+                    PropertyAccessField::Const(ident) => {
+                        if !operation.shorted() {
+                            self.token(".");
+                        }
+                        self.identifier(*ident);
+                    }
+                    PropertyAccessField::Expr(expression) => {
+                        self.token("[");
+                        self.delimited(|printer| {
+                            printer.expression(expression, Precedence::Sequence);
+                        });
+                        self.token("]");
+                    }
+                },
+                OptionalOperationKind::PrivatePropertyAccess { field } => {
+                    if !operation.shorted() {
+                        self.token(".");
+                    }
+                    let name = format!("#{}", self.interner.resolve_expect(field.description()));
+                    self.token(&name);
+                }
+                OptionalOperationKind::Call { args } => self.arguments(args),
+            }
+        }
+    }
+
+    fn assign(&mut self, assign: &Assign) {
+        match assign.lhs() {
+            AssignTarget::Identifier(ident) => self.identifier(*ident),
+            AssignTarget::Access(access) => self.property_access(access),
+            AssignTarget::Pattern(pattern) => self.pattern(pattern),
+        }
+        self.space();
+        self.token(assign.op().as_str());
+        self.space();
+        self.expression(assign.rhs(), Precedence::Assignment);
+    }
+
+    fn update(&mut self, update: &Update) {
+        let prefix = matches!(update.op(), UpdateOp::IncrementPre | UpdateOp::DecrementPre);
+        if prefix {
+            self.token(update.op().as_str());
+        }
+        match update.target() {
+            UpdateTarget::Identifier(ident) => self.identifier(*ident),
+            UpdateTarget::PropertyAccess(access) => self.property_access(access),
+        }
+        if !prefix {
+            self.token(update.op().as_str());
+        }
+    }
+
+    fn binary(&mut self, binary: &Binary) {
+        let op = binary.op();
+        let precedence = binary_precedence(op);
+        let (lhs, rhs) = match op {
+            // Exponentiation is right-associative, and its base can't be a unary expression.
+            BinaryOp::Arithmetic(ArithmeticOp::Exp) => {
+                (Precedence::Postfix, Precedence::Exponentiation)
+            }
+            // Nullish coalescing can't be mixed with other logical operators without parentheses.
+            BinaryOp::Logical(LogicalOp::Coalesce) => {
+                let lhs = if matches!(
+                    binary.lhs(),
+                    Expression::Binary(lhs) if lhs.op() == BinaryOp::Logical(LogicalOp::Coalesce)
+                ) {
+                    Precedence::Coalesce
+                } else {
+                    Precedence::BitwiseOr
+                };
+                (lhs, Precedence::BitwiseOr)
+            }
+            _ => (precedence, precedence.next()),
+        };
+
+        self.expression(binary.lhs(), lhs);
+        if op != BinaryOp::Comma {
+            self.space();
+        }
+        self.token(op.as_str());
+        self.space();
+        self.expression(binary.rhs(), rhs);
+    }
+
+    fn jsx_element(&mut self, element: &JsxElement) {
+        self.map(element.span().start(), None);
+        self.token("<");
+        if let Some(name) = element.name() {
+            self.jsx_element_name(name);
+        }
+        for attribute in element.attributes() {
+            self.raw(" ");
+            match attribute {
+                JsxAttribute::Named { name, value } => {
+                    self.sym(*name);
+                    if let Some(value) = value {
+                        self.token("=");
+                        match value {
+                            JsxAttributeValue::String(text) => {
+                                let raw = self.interner.resolve_expect(text.raw()).to_string();
+                                let quote = if raw.contains('"') { '\'' } else { '"' };
+                                self.token(&format!("{quote}{raw}{quote}"));
+                            }
+                            JsxAttributeValue::Expression(expression) => {
+                                self.jsx_expression_container(false, expression);
+                            }
+                            JsxAttributeValue::Element(element) => self.jsx_element(element),
+                        }
+                    }
+                }
+                JsxAttribute::Spread(expression) => {
+                    self.jsx_expression_container(true, expression);
+                }
+            }
+        }
+
+        if element.name().is_some() && element.children().is_empty() {
+            self.space();
+            self.raw("/>");
+            return;
+        }
+
+        self.raw(">");
+        for child in element.children() {
+            match child {
+                JsxChild::Text(text) => {
+                    let raw = self.interner.resolve_expect(text.raw()).to_string();
+                    self.raw(&raw);
+                }
+                JsxChild::Element(element) => self.jsx_element(element),
+                JsxChild::Expression(expression) => {
+                    self.jsx_expression_container(false, expression);
+                }
+                JsxChild::Spread(expression) => self.jsx_expression_container(true, expression),
+            }
+        }
+        self.raw("</");
+        if let Some(name) = element.name() {
+            self.jsx_element_name(name);
+        }
+        self.raw(">");
+    }
+
+    fn jsx_element_name(&mut self, name: &JsxElementName) {
+        match name {
+            JsxElementName::Intrinsic(name) => {
+                let name = self.interner.resolve_expect(*name).to_string();
+                self.raw(&name);
+            }
+            JsxElementName::Component(expression) => {
+                self.expression(expression, Precedence::Call);
+            }
+        }
+    }
+
+    fn jsx_expression_container(&mut self, spread: bool, expression: &Expression) {
+        self.raw("{");
+        if spread {
+            self.raw("...");
+        }
+        self.delimited(|printer| printer.expression(expression, Precedence::Assignment));
+        self.raw("}");
+    }
+}
+
+/// Gets the precedence of an expression, as it will be written.
+fn precedence(expression: &Expression) -> Precedence {
+    match expression {
+        Expression::Binary(binary) => binary_precedence(binary.op()),
+        Expression::BinaryInPrivate(_) => Precedence::Relational,
+        Expression::Assign(_)
+        | Expression::Yield(_)
+        | Expression::ArrowFunction(_)
+        | Expression::AsyncArrowFunction(_)
+        | Expression::Spread(_) => Precedence::Assignment,
+        Expression::Conditional(_) => Precedence::Conditional,
+        Expression::Unary(_) | Expression::Await(_) => Precedence::Unary,
+        Expression::Update(update) => match update.op() {
+            UpdateOp::IncrementPre | UpdateOp::DecrementPre => Precedence::Unary,
+            UpdateOp::IncrementPost | UpdateOp::DecrementPost => Precedence::Postfix,
+        },
+        Expression::Literal(literal) => match literal.kind() {
+            LiteralKind::Num(number) if !number.is_finite() => Precedence::Multiplicative,
+            LiteralKind::Num(number) if number.is_sign_negative() => Precedence::Unary,
+            LiteralKind::Int(number) if *number < 0 => Precedence::Unary,
+            LiteralKind::BigInt(bigint) if bigint.sign() == num_bigint::Sign::Minus => {
+                Precedence::Unary
+            }
+            LiteralKind::Undefined => Precedence::Unary,
+            _ => Precedence::Primary,
+        },
+        Expression::New(_)
+        | Expression::Call(_)
+        | Expression::SuperCall(_)
+        | Expression::ImportCall(_)
+        | Expression::Optional(_)
+        | Expression::PropertyAccess(_)
+        | Expression::TaggedTemplate(_) => Precedence::Call,
+        _ => Precedence::Primary,
+    }
+}
+
+const fn binary_precedence(op: BinaryOp) -> Precedence {
+    match op {
+        BinaryOp::Comma => Precedence::Sequence,
+        BinaryOp::Logical(LogicalOp::Coalesce) => Precedence::Coalesce,
+        BinaryOp::Logical(LogicalOp::Or) => Precedence::LogicalOr,
+        BinaryOp::Logical(LogicalOp::And) => Precedence::LogicalAnd,
+        BinaryOp::Bitwise(BitwiseOp::Or) => Precedence::BitwiseOr,
+        BinaryOp::Bitwise(BitwiseOp::Xor) => Precedence::BitwiseXor,
+        BinaryOp::Bitwise(BitwiseOp::And) => Precedence::BitwiseAnd,
+        BinaryOp::Bitwise(BitwiseOp::Shl | BitwiseOp::Shr | BitwiseOp::UShr) => Precedence::Shift,
+        BinaryOp::Relational(
+            RelationalOp::Equal
+            | RelationalOp::NotEqual
+            | RelationalOp::StrictEqual
+            | RelationalOp::StrictNotEqual,
+        ) => Precedence::Equality,
+        BinaryOp::Relational(_) => Precedence::Relational,
+        BinaryOp::Arithmetic(ArithmeticOp::Add | ArithmeticOp::Sub) => Precedence::Additive,
+        BinaryOp::Arithmetic(ArithmeticOp::Mul | ArithmeticOp::Div | ArithmeticOp::Mod) => {
+            Precedence::Multiplicative
+        }
+        BinaryOp::Arithmetic(ArithmeticOp::Exp) => Precedence::Exponentiation,
+    }
+}
+
+/// Checks if `expression` is written with an `in` operator at its top level.
+fn is_in(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Binary(binary) if binary.op() == BinaryOp::Relational(RelationalOp::In)
+    ) || matches!(expression, Expression::BinaryInPrivate(_))
+}
+
+/// Checks if the constructor of a `new` expression contains a call, which would otherwise take the
+/// arguments of the `new` expression.
+fn contains_call(expression: &Expression) -> bool {
+    match expression {
+        Expression::Call(_)
+        | Expression::SuperCall(_)
+        | Expression::ImportCall(_)
+        | Expression::Optional(_) => true,
+        Expression::PropertyAccess(PropertyAccess::Simple(access)) => {
+            contains_call(access.target())
+        }
+        Expression::PropertyAccess(PropertyAccess::Private(access)) => {
+            contains_call(access.target())
+        }
+        Expression::TaggedTemplate(template) => contains_call(template.tag()),
+        _ => false,
+    }
+}
+
+/// Formats a number with the shortest representation that parses back to the same value.
+fn format_number(number: f64) -> String {
+    if number.is_nan() {
+        return "0/0".to_owned();
+    }
+    if number.is_infinite() {
+        return if number > 0.0 { "1/0" } else { "-1/0" }.to_owned();
+    }
+    if number == 0.0 {
+        return if number.is_sign_negative() { "-0" } else { "0" }.to_owned();
+    }
+
+    let decimal = format!("{number}");
+    let exponential = format!("{number:e}");
+    if exponential.len() < decimal.len() {
+        exponential
+    } else {
+        decimal
+    }
+}
@@ -0,0 +1,223 @@
+//! Generation of functions and classes.
+
+use super::{Precedence, Printer, Start};
+use crate::{
+    Expression, Statement, StatementListItem,
+    expression::Identifier,
+    function::{
+        ClassElement, ClassElementName, FormalParameterList, FunctionBody, FunctionExpression,
+        PrivateName,
+    },
+    property::MethodDefinitionKind,
+};
+use boa_interner::Sym;
+
+impl Printer<'_> {
+    /// Writes a function, introduced by the words of the `keyword`, like `async function*`.
+    pub(super) fn function(
+        &mut self,
+        keyword: &str,
+        name: Option<Identifier>,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+    ) {
+        for word in keyword.split(' ') {
+            self.token(word);
+        }
+        if let Some(name) = name {
+            self.space();
+            self.identifier(name);
+        }
+        self.parameters(parameters);
+        self.space();
+        self.function_body(body);
+    }
+
+    pub(super) fn arrow_function(
+        &mut self,
+        r#async: bool,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+    ) {
+        if r#async {
+            self.token("async");
+            self.space();
+        }
+
+        match parameters.as_ref() {
+            [parameter]
+                if self.minify
+                    && parameter.is_identifier()
+                    && parameter.init().is_none()
+                    && !parameter.is_rest_param() =>
+            {
+                self.binding(parameter.variable().binding());
+            }
+            _ => self.parameters(parameters),
+        }
+        self.space();
+        self.token("=>");
+        self.space();
+
+        // A single `return` is written as a concise body.
+        if let [StatementListItem::Statement(statement)] = body.statements()
+            && let Statement::Return(r#return) = statement.as_ref()
+            && let Some(target) = r#return.target()
+        {
+            self.set_start(Start::ArrowBody);
+            self.expression(target, Precedence::Assignment);
+        } else {
+            self.function_body(body);
+        }
+    }
+
+    fn parameters(&mut self, parameters: &FormalParameterList) {
+        self.parenthesized(|printer| {
+            for (index, parameter) in parameters.as_ref().iter().enumerate() {
+                if index > 0 {
+                    printer.comma();
+                }
+                if parameter.is_rest_param() {
+                    printer.token("...");
+                }
+                printer.variable(parameter.variable());
+            }
+        });
+    }
+
+    fn function_body(&mut self, body: &FunctionBody) {
+        self.statement_block(body.statements(), body.strict());
+    }
+
+    /// Writes a method of an object literal or a class, where `name` writes its name.
+    pub(super) fn method(
+        &mut self,
+        r#static: bool,
+        kind: MethodDefinitionKind,
+        name: impl FnOnce(&mut Self),
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+    ) {
+        if r#static {
+            self.token("static");
+            self.space();
+        }
+        match kind {
+            MethodDefinitionKind::Get => {
+                self.token("get");
+                self.space();
+            }
+            MethodDefinitionKind::Set => {
+                self.token("set");
+                self.space();
+            }
+            MethodDefinitionKind::Ordinary => {}
+            MethodDefinitionKind::Generator => self.token("*"),
+            MethodDefinitionKind::Async => {
+                self.token("async");
+                self.space();
+            }
+            MethodDefinitionKind::AsyncGenerator => {
+                self.token("async");
+                self.space();
+                self.token("*");
+            }
+        }
+        name(self);
+        self.parameters(parameters);
+        self.space();
+        self.function_body(body);
+    }
+
+    /// Writes a class declaration or expression.
+    pub(super) fn class(
+        &mut self,
+        name: Option<Identifier>,
+        super_ref: Option<&Expression>,
+        constructor: Option<&FunctionExpression>,
+        elements: &[ClassElement],
+    ) {
+        self.token("class");
+        if let Some(name) = name {
+            self.space();
+            self.identifier(name);
+        }
+        if let Some(super_ref) = super_ref {
+            self.space();
+            self.token("extends");
+            self.space();
+            self.expression(super_ref, Precedence::Call);
+        }
+        self.space();
+
+        let members: Vec<_> = constructor
+            .map(Member::Constructor)
+            .into_iter()
+            .chain(elements.iter().map(Member::Element))
+            .collect();
+        self.block(&members, |printer, member| match member {
+            Member::Constructor(constructor) => printer.method(
+                false,
+                MethodDefinitionKind::Ordinary,
+                |printer| printer.sym(Sym::CONSTRUCTOR),
+                constructor.parameters(),
+                constructor.body(),
+            ),
+            Member::Element(element) => printer.class_element(element),
+        });
+    }
+
+    fn class_element(&mut self, element: &ClassElement) {
+        match element {
+            ClassElement::MethodDefinition(method) => self.method(
+                method.is_static(),
+                method.kind(),
+                |printer| match method.name() {
+                    ClassElementName::PropertyName(name) => printer.property_name(name),
+                    ClassElementName::PrivateName(name) => printer.private_name(*name),
+                },
+                method.parameters(),
+                method.body(),
+            ),
+            ClassElement::FieldDefinition(field) | ClassElement::StaticFieldDefinition(field) => {
+                if matches!(element, ClassElement::StaticFieldDefinition(_)) {
+                    self.token("static");
+                    self.space();
+                }
+                self.property_name(field.name());
+                if let Some(init) = field.initializer() {
+                    self.initializer(init);
+                }
+                self.token(";");
+            }
+            ClassElement::PrivateFieldDefinition(field)
+            | ClassElement::PrivateStaticFieldDefinition(field) => {
+                if matches!(element, ClassElement::PrivateStaticFieldDefinition(_)) {
+                    self.token("static");
+                    self.space();
+                }
+                self.private_name(*field.name());
+                if let Some(init) = field.initializer() {
+                    self.initializer(init);
+                }
+                self.token(";");
+            }
+            ClassElement::StaticBlock(block) => {
+                self.token("static");
+                self.space();
+                self.function_body(block.statements());
+            }
+        }
+    }
+
+    fn private_name(&mut self, name: PrivateName) {
+        let name = format!("#{}", self.interner.resolve_expect(name.description()));
+        self.token(&name);
+    }
+}
+
+/// A member of a class body.
+enum Member<'a> {
+    Constructor(&'a FunctionExpression),
+    Element(&'a ClassElement),
+}
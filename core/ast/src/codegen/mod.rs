@@ -0,0 +1,538 @@
+//! JavaScript code generation from the AST.
+//!
+//! The [`ToInternedString`][boa_interner::ToInternedString] implementations of the AST nodes are
+//! meant for debugging, while the [`Generator`] produces code that can be parsed back into an
+//! equivalent AST. It adds the parentheses required by the precedence of operators and by the
+//! restrictions at the start of statements, always terminates statements with semicolons instead
+//! of relying on automatic semicolon insertion, and escapes strings and templates.
+//!
+//! The code is either pretty-printed, or minified by removing all optional whitespace. A
+//! [Source Map v3][spec] relating the generated code to the [`Span`][crate::Span]s of the
+//! original source can be emitted along with the code.
+//!
+//! Comments and the original formatting of the code are not preserved, and statements that were
+//! replaced by [`Statement::Error`][crate::Statement::Error] while recovering from syntax errors
+//! are generated as empty statements.
+//!
+//! [spec]: https://tc39.es/ecma426/
+
+mod expression;
+mod function;
+mod pattern;
+mod source_map;
+mod statement;
+
+#[cfg(test)]
+mod tests;
+
+pub use source_map::SourceMap;
+
+use crate::{Expression, Module, Position, Script};
+use boa_interner::{Interner, Sym};
+use source_map::SourceMapBuilder;
+
+/// Generates JavaScript code from the AST.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_ast::{
+/// #     Expression, Span,
+/// #     codegen::Generator,
+/// #     expression::{Identifier, literal::Literal, operator::{Binary, binary::{ArithmeticOp, BinaryOp}}},
+/// # };
+/// # use boa_interner::Interner;
+/// let mut interner = Interner::default();
+/// let span = Span::new((1, 1), (1, 2));
+/// let a = Identifier::new(interner.get_or_intern("a"), span);
+/// let sum = Binary::new(ArithmeticOp::Add.into(), a.into(), Literal::new(1, span).into());
+/// let product = Binary::new(ArithmeticOp::Mul.into(), Expression::from(sum), a.into());
+///
+/// let generated = Generator::new(&interner).expression(&product.into());
+/// assert_eq!(generated.code(), "(a + 1) * a");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Generator<'a> {
+    interner: &'a Interner,
+    minify: bool,
+    indent: String,
+    source_map: Option<SourceMapOptions>,
+}
+
+#[derive(Debug, Clone)]
+struct SourceMapOptions {
+    source: String,
+    file: Option<String>,
+    source_content: Option<String>,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates a new generator of pretty-printed code, indented with four spaces and without a
+    /// source map.
+    #[must_use]
+    pub fn new(interner: &'a Interner) -> Self {
+        Self {
+            interner,
+            minify: false,
+            indent: "    ".to_owned(),
+            source_map: None,
+        }
+    }
+
+    /// Sets whether the code is minified, removing all optional whitespace.
+    #[must_use]
+    pub const fn minify(mut self, minify: bool) -> Self {
+        self.minify = minify;
+        self
+    }
+
+    /// Sets the indentation of pretty-printed code.
+    #[must_use]
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// Enables the source map, mapping the generated code to the original `source` file.
+    #[must_use]
+    pub fn source_map(mut self, source: impl Into<String>) -> Self {
+        self.source_map = Some(SourceMapOptions {
+            source: source.into(),
+            file: None,
+            source_content: None,
+        });
+        self
+    }
+
+    /// Sets the name of the generated file in the source map.
+    ///
+    /// Has no effect if the source map is not enabled.
+    #[must_use]
+    pub fn source_map_file(mut self, file: impl Into<String>) -> Self {
+        if let Some(options) = &mut self.source_map {
+            options.file = Some(file.into());
+        }
+        self
+    }
+
+    /// Embeds the content of the original source in the source map.
+    ///
+    /// Has no effect if the source map is not enabled.
+    #[must_use]
+    pub fn source_content(mut self, content: impl Into<String>) -> Self {
+        if let Some(options) = &mut self.source_map {
+            options.source_content = Some(content.into());
+        }
+        self
+    }
+
+    /// Generates the code of a script.
+    #[must_use]
+    pub fn script(&self, script: &Script) -> Generated {
+        let mut printer = self.printer();
+        printer.statement_list(script.statements().statements(), script.strict());
+        printer.finish()
+    }
+
+    /// Generates the code of a module.
+    #[must_use]
+    pub fn module(&self, module: &Module) -> Generated {
+        let mut printer = self.printer();
+        printer.module_items(module.items().items());
+        printer.finish()
+    }
+
+    /// Generates the code of an expression.
+    #[must_use]
+    pub fn expression(&self, expression: &Expression) -> Generated {
+        let mut printer = self.printer();
+        printer.expression(expression, Precedence::Sequence);
+        printer.finish()
+    }
+
+    fn printer(&self) -> Printer<'_> {
+        Printer {
+            interner: self.interner,
+            minify: self.minify,
+            indent: &self.indent,
+            output: String::new(),
+            level: 0,
+            line: 0,
+            column: 0,
+            start: None,
+            no_in: false,
+            pending: None,
+            source_map: self.source_map.clone().map(|options| {
+                SourceMapBuilder::new(options.file, options.source, options.source_content)
+            }),
+        }
+    }
+}
+
+/// The code produced by a [`Generator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    code: String,
+    source_map: Option<SourceMap>,
+}
+
+impl Generated {
+    /// Gets the generated code.
+    #[inline]
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Gets the source map of the generated code, if it was enabled.
+    #[inline]
+    #[must_use]
+    pub const fn source_map(&self) -> Option<&SourceMap> {
+        self.source_map.as_ref()
+    }
+
+    /// Splits the generated code from its source map.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (String, Option<SourceMap>) {
+        (self.code, self.source_map)
+    }
+}
+
+/// The precedence of expressions, from the loosest to the tightest binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Sequence,
+    Assignment,
+    Conditional,
+    Coalesce,
+    LogicalOr,
+    LogicalAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseAnd,
+    Equality,
+    Relational,
+    Shift,
+    Additive,
+    Multiplicative,
+    Exponentiation,
+    Unary,
+    Postfix,
+    Call,
+    Primary,
+}
+
+impl Precedence {
+    /// Gets the next tighter precedence.
+    const fn next(self) -> Self {
+        match self {
+            Self::Sequence => Self::Assignment,
+            Self::Assignment => Self::Conditional,
+            Self::Conditional => Self::Coalesce,
+            Self::Coalesce => Self::LogicalOr,
+            Self::LogicalOr => Self::LogicalAnd,
+            Self::LogicalAnd => Self::BitwiseOr,
+            Self::BitwiseOr => Self::BitwiseXor,
+            Self::BitwiseXor => Self::BitwiseAnd,
+            Self::BitwiseAnd => Self::Equality,
+            Self::Equality => Self::Relational,
+            Self::Relational => Self::Shift,
+            Self::Shift => Self::Additive,
+            Self::Additive => Self::Multiplicative,
+            Self::Multiplicative => Self::Exponentiation,
+            Self::Exponentiation => Self::Unary,
+            Self::Unary => Self::Postfix,
+            Self::Postfix => Self::Call,
+            Self::Call | Self::Primary => Self::Primary,
+        }
+    }
+}
+
+/// A position where some expressions can't appear without parentheses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Start {
+    /// The start of an expression statement, which can't start with `{`, `function`, `class` or
+    /// `let [`.
+    Statement,
+    /// The start of the concise body of an arrow function, which can't start with `{`.
+    ArrowBody,
+    /// The start of a default export, which can't start with `function` or `class`.
+    ExportDefault,
+    /// The left-hand side of a `for-of` loop, which can't start with `let` or `async of`.
+    ForOf,
+    /// The left-hand side of a `for-in` loop, which can't start with `let`.
+    ForIn,
+}
+
+/// Writes the generated code, keeping track of the position for the source map.
+struct Printer<'a> {
+    interner: &'a Interner,
+    minify: bool,
+    indent: &'a str,
+    output: String,
+    level: usize,
+
+    /// The zero-based line of the end of the output.
+    line: u32,
+    /// The zero-based column of the end of the output, in UTF-16 code units.
+    column: u32,
+
+    /// The length of the output at a position where some expressions need parentheses.
+    start: Option<(usize, Start)>,
+    /// Whether the `in` operator must be parenthesized, in the head of `for` loops.
+    no_in: bool,
+
+    /// The mapping of the next token.
+    pending: Option<(Position, Option<Sym>)>,
+    source_map: Option<SourceMapBuilder>,
+}
+
+impl Printer<'_> {
+    fn finish(self) -> Generated {
+        Generated {
+            code: self.output,
+            source_map: self.source_map.map(SourceMapBuilder::build),
+        }
+    }
+
+    /// Maps the next token to the `position` in the original source, where an identifier called
+    /// `name` starts.
+    fn map(&mut self, position: Position, name: Option<Sym>) {
+        if self.source_map.is_some() {
+            self.pending = Some((position, name));
+        }
+    }
+
+    /// Writes a token, separating it from the previous one if they would otherwise be merged.
+    fn token(&mut self, token: &str) {
+        if let (Some(last), Some(first)) = (self.output.chars().next_back(), token.chars().next())
+            && needs_separator(&self.output, last, first, token)
+        {
+            self.raw(" ");
+        }
+
+        if let Some((position, name)) = self.pending.take()
+            && let Some(source_map) = &mut self.source_map
+        {
+            let name = name.map(|name| self.interner.resolve_expect(name).to_string());
+            source_map.add(self.line, self.column, position, name.as_deref());
+        }
+        self.raw(token);
+    }
+
+    /// Writes text without any separation, updating the position.
+    fn raw(&mut self, text: &str) {
+        let count = |count: usize| u32::try_from(count).unwrap_or(u32::MAX);
+        match text.rfind('\n') {
+            Some(index) => {
+                self.line += count(text.matches('\n').count());
+                self.column = count(text[index + 1..].encode_utf16().count());
+            }
+            None => self.column += count(text.encode_utf16().count()),
+        }
+        self.output.push_str(text);
+    }
+
+    /// Writes a space if the code is pretty-printed.
+    fn space(&mut self) {
+        if !self.minify {
+            self.raw(" ");
+        }
+    }
+
+    /// Starts a new indented line if the code is pretty-printed.
+    fn newline(&mut self) {
+        if !self.minify {
+            self.raw("\n");
+            for _ in 0..self.level {
+                self.raw(self.indent);
+            }
+        }
+    }
+
+    /// Writes `, ` between items of a list.
+    fn comma(&mut self) {
+        self.token(",");
+        self.space();
+    }
+
+    /// Writes the name of a symbol.
+    fn sym(&mut self, sym: Sym) {
+        let name = self.interner.resolve_expect(sym).to_string();
+        self.token(&name);
+    }
+
+    /// Writes a string literal.
+    fn string(&mut self, sym: Sym) {
+        let string = quote(self.interner.resolve_expect(sym).utf16());
+        self.token(&string);
+    }
+
+    /// Writes a property key or module export name, which is quoted if it isn't a valid
+    /// identifier name.
+    fn name(&mut self, sym: Sym) {
+        let name = self.interner.resolve_expect(sym);
+        if name.utf8().is_some_and(is_identifier_name) {
+            self.sym(sym);
+        } else {
+            self.string(sym);
+        }
+    }
+
+    /// Marks the current position as a position where some expressions need parentheses.
+    fn set_start(&mut self, start: Start) {
+        self.start = Some((self.output.len(), start));
+    }
+
+    /// Checks if nothing was written since the position marked as `start`.
+    fn at_start(&self, start: Start) -> bool {
+        self.start == Some((self.output.len(), start))
+    }
+
+    /// Writes `f` in parentheses, where the restrictions of the surrounding code don't apply.
+    fn parenthesized(&mut self, f: impl FnOnce(&mut Self)) {
+        self.token("(");
+        self.delimited(f);
+        self.token(")");
+    }
+
+    /// Writes `f` inside delimiters, where the `in` operator doesn't need parentheses.
+    fn delimited(&mut self, f: impl FnOnce(&mut Self)) {
+        let no_in = std::mem::replace(&mut self.no_in, false);
+        f(self);
+        self.no_in = no_in;
+    }
+
+    /// Writes a block of items, each on its own line if the code is pretty-printed.
+    fn block<T>(&mut self, items: &[T], f: impl FnMut(&mut Self, &T)) {
+        self.items_block(items, false, f);
+    }
+
+    /// Writes a block of comma-separated items, like the properties of an object literal.
+    fn list_block<T>(&mut self, items: &[T], f: impl FnMut(&mut Self, &T)) {
+        self.items_block(items, true, f);
+    }
+
+    fn items_block<T>(&mut self, items: &[T], commas: bool, mut f: impl FnMut(&mut Self, &T)) {
+        self.token("{");
+        if !items.is_empty() {
+            self.level += 1;
+            self.delimited(|printer| {
+                for (index, item) in items.iter().enumerate() {
+                    if commas && index > 0 {
+                        printer.token(",");
+                    }
+                    printer.newline();
+                    f(printer, item);
+                }
+            });
+            self.level -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+}
+
+/// Checks if two tokens must be separated by a space, when `last` is the last character of
+/// `output` and `first` is the first character of the next `token`.
+fn needs_separator(output: &str, last: char, first: char, token: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || matches!(c, '$' | '_' | '\\') || !c.is_ascii();
+    (is_word(last) && is_word(first))
+        || (last == '+' && first == '+')
+        || (last == '-' && first == '-')
+        || (last == '/' && matches!(first, '/' | '*'))
+        // A JSX element after a `<` operator.
+        || (last == '<' && first == '<')
+        // HTML-like comments, `<!--` and `-->`.
+        || (output.ends_with("<!") && token.starts_with("--"))
+        || (last == '<' && token.starts_with("!--"))
+        || (output.ends_with("--") && first == '>')
+}
+
+/// Checks if `name` is an identifier name, which can be used as a property key without quotes.
+///
+/// Only ASCII names are recognized, because other names can be quoted without changing their
+/// meaning.
+fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '$' | '_'))
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '$' | '_'))
+}
+
+/// Quotes a string literal, with the quote that needs the fewest escapes.
+fn quote(string: &[u16]) -> String {
+    let double = string.iter().filter(|&&c| c == u16::from(b'"')).count();
+    let single = string.iter().filter(|&&c| c == u16::from(b'\'')).count();
+    let quote = if double > single { '\'' } else { '"' };
+
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push(quote);
+    for c in char::decode_utf16(string.iter().copied()) {
+        match c {
+            Ok(c) if c == quote => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            Ok(c) => escape_char(&mut quoted, c),
+            Err(error) => push_unicode_escape(&mut quoted, error.unpaired_surrogate()),
+        }
+    }
+    quoted.push(quote);
+    quoted
+}
+
+/// Escapes the cooked string of a template literal.
+fn escape_template(string: &[u16]) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    let mut chars = char::decode_utf16(string.iter().copied()).peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            Ok('`') => escaped.push_str("\\`"),
+            Ok('$') if matches!(chars.peek(), Some(Ok('{'))) => escaped.push_str("\\$"),
+            Ok(c) => escape_char(&mut escaped, c),
+            Err(error) => push_unicode_escape(&mut escaped, error.unpaired_surrogate()),
+        }
+    }
+    escaped
+}
+
+/// Escapes a character of a string or template literal.
+fn escape_char(output: &mut String, c: char) {
+    match c {
+        '\\' => output.push_str("\\\\"),
+        '\n' => output.push_str("\\n"),
+        '\r' => output.push_str("\\r"),
+        '\t' => output.push_str("\\t"),
+        '\u{2028}' | '\u{2029}' => push_unicode_escape(output, c as u16),
+        c if c.is_ascii_control() => {
+            use std::fmt::Write;
+            let _ = write!(output, "\\x{:02x}", c as u32);
+        }
+        c => output.push(c),
+    }
+}
+
+fn push_unicode_escape(output: &mut String, code_unit: u16) {
+    use std::fmt::Write;
+    let _ = write!(output, "\\u{code_unit:04x}");
+}
+
+/// Escapes a string for a JSON string literal.
+fn escape_json(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if u32::from(c) < 0x20 => push_unicode_escape(&mut escaped, c as u16),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,151 @@
+//! Generation of destructuring patterns and bindings.
+
+use super::Printer;
+use crate::{
+    declaration::{Binding, Variable},
+    pattern::{ArrayPatternElement, ObjectPatternElement, Pattern},
+    property::PropertyName,
+};
+
+impl Printer<'_> {
+    /// Writes a destructuring pattern.
+    pub(super) fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Object(pattern) => self.list_block(pattern.bindings(), |printer, element| {
+                printer.object_pattern_element(element);
+            }),
+            Pattern::Array(pattern) => {
+                let elements = pattern.bindings();
+                self.token("[");
+                self.delimited(|printer| {
+                    for (index, element) in elements.iter().enumerate() {
+                        if index > 0 {
+                            printer.comma();
+                        }
+                        printer.array_pattern_element(element);
+                    }
+                    // A trailing hole needs its own comma.
+                    if matches!(elements.last(), Some(ArrayPatternElement::Elision)) {
+                        printer.token(",");
+                    }
+                });
+                self.token("]");
+            }
+        }
+    }
+
+    fn object_pattern_element(&mut self, element: &ObjectPatternElement) {
+        match element {
+            ObjectPatternElement::SingleName {
+                name,
+                ident,
+                default_init,
+            } => {
+                if !matches!(name, PropertyName::Literal(name) if name.sym() == ident.sym()) {
+                    self.property_name(name);
+                    self.token(":");
+                    self.space();
+                }
+                self.identifier(*ident);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+            ObjectPatternElement::RestProperty { ident } => {
+                self.token("...");
+                self.identifier(*ident);
+            }
+            ObjectPatternElement::AssignmentPropertyAccess {
+                name,
+                access,
+                default_init,
+            } => {
+                self.property_name(name);
+                self.token(":");
+                self.space();
+                self.property_access(access);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+            ObjectPatternElement::AssignmentRestPropertyAccess { access } => {
+                self.token("...");
+                self.property_access(access);
+            }
+            ObjectPatternElement::Pattern {
+                name,
+                pattern,
+                default_init,
+            } => {
+                self.property_name(name);
+                self.token(":");
+                self.space();
+                self.pattern(pattern);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+        }
+    }
+
+    fn array_pattern_element(&mut self, element: &ArrayPatternElement) {
+        match element {
+            ArrayPatternElement::Elision => {}
+            ArrayPatternElement::SingleName {
+                ident,
+                default_init,
+            } => {
+                self.identifier(*ident);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+            ArrayPatternElement::PropertyAccess {
+                access,
+                default_init,
+            } => {
+                self.property_access(access);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+            ArrayPatternElement::Pattern {
+                pattern,
+                default_init,
+            } => {
+                self.pattern(pattern);
+                if let Some(init) = default_init {
+                    self.initializer(init);
+                }
+            }
+            ArrayPatternElement::SingleNameRest { ident } => {
+                self.token("...");
+                self.identifier(*ident);
+            }
+            ArrayPatternElement::PropertyAccessRest { access } => {
+                self.token("...");
+                self.property_access(access);
+            }
+            ArrayPatternElement::PatternRest { pattern } => {
+                self.token("...");
+                self.pattern(pattern);
+            }
+        }
+    }
+
+    /// Writes the target of a binding.
+    pub(super) fn binding(&mut self, binding: &Binding) {
+        match binding {
+            Binding::Identifier(ident) => self.identifier(*ident),
+            Binding::Pattern(pattern) => self.pattern(pattern),
+        }
+    }
+
+    /// Writes a declared variable, with its initializer.
+    pub(super) fn variable(&mut self, variable: &Variable) {
+        self.binding(variable.binding());
+        if let Some(init) = variable.init() {
+            self.initializer(init);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Source maps of generated code.
+
+use super::escape_json;
+use crate::Position;
+use std::fmt::Write;
+
+/// A [Source Map v3][spec] relating generated code to the positions of the original source.
+///
+/// [spec]: https://tc39.es/ecma426/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    file: Option<String>,
+    source: String,
+    source_content: Option<String>,
+    names: Vec<String>,
+    mappings: String,
+}
+
+impl SourceMap {
+    /// Gets the name of the generated file, if it was set.
+    #[inline]
+    #[must_use]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Gets the name of the original source.
+    #[inline]
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Gets the content of the original source, if it was set.
+    #[inline]
+    #[must_use]
+    pub fn source_content(&self) -> Option<&str> {
+        self.source_content.as_deref()
+    }
+
+    /// Gets the names of the identifiers referenced by the mappings.
+    #[inline]
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Gets the encoded mappings.
+    #[inline]
+    #[must_use]
+    pub fn mappings(&self) -> &str {
+        &self.mappings
+    }
+
+    /// Serializes the source map to JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"version\":3,");
+        if let Some(file) = &self.file {
+            let _ = write!(json, "\"file\":\"{}\",", escape_json(file));
+        }
+        let _ = write!(json, "\"sources\":[\"{}\"],", escape_json(&self.source));
+        if let Some(content) = &self.source_content {
+            let _ = write!(json, "\"sourcesContent\":[\"{}\"],", escape_json(content));
+        }
+        json.push_str("\"names\":[");
+        for (index, name) in self.names.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "\"{}\"", escape_json(name));
+        }
+        let _ = write!(json, "],\"mappings\":\"{}\"}}", self.mappings);
+        json
+    }
+}
+
+/// Collects the mappings of generated code while it is being written.
+#[derive(Debug)]
+pub(super) struct SourceMapBuilder {
+    file: Option<String>,
+    source: String,
+    source_content: Option<String>,
+    names: Vec<String>,
+    mappings: String,
+
+    /// The generated line of the last mapping.
+    line: u32,
+    /// The values of the last segment, which the next segment is encoded relative to.
+    previous: Segment,
+}
+
+/// A mapping, with zero-based lines and columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Segment {
+    generated_column: u32,
+    original_line: u32,
+    original_column: u32,
+    name: Option<u32>,
+}
+
+impl SourceMapBuilder {
+    pub(super) fn new(
+        file: Option<String>,
+        source: String,
+        source_content: Option<String>,
+    ) -> Self {
+        Self {
+            file,
+            source,
+            source_content,
+            names: Vec::new(),
+            mappings: String::new(),
+            line: 0,
+            previous: Segment::default(),
+        }
+    }
+
+    /// Maps the zero-based `line` and `column` of the generated code to the `original` position,
+    /// optionally with the `name` of the identifier at that position.
+    pub(super) fn add(&mut self, line: u32, column: u32, original: Position, name: Option<&str>) {
+        let name = name.map(|name| self.name_index(name));
+        let segment = Segment {
+            generated_column: column,
+            original_line: original.line_number().saturating_sub(1),
+            original_column: original.column_number().saturating_sub(1),
+            name,
+        };
+
+        if line > self.line {
+            for _ in self.line..line {
+                self.mappings.push(';');
+            }
+            self.line = line;
+            self.previous.generated_column = 0;
+        } else if !self.mappings.is_empty() && !self.mappings.ends_with(';') {
+            if self.previous.generated_column == column {
+                return;
+            }
+            self.mappings.push(',');
+        }
+
+        encode_vlq(
+            &mut self.mappings,
+            i64::from(column) - i64::from(self.previous.generated_column),
+        );
+        // All mappings refer to the single source, at index 0.
+        encode_vlq(&mut self.mappings, 0);
+        encode_vlq(
+            &mut self.mappings,
+            i64::from(segment.original_line) - i64::from(self.previous.original_line),
+        );
+        encode_vlq(
+            &mut self.mappings,
+            i64::from(segment.original_column) - i64::from(self.previous.original_column),
+        );
+        if let Some(name) = name {
+            let previous = self.previous.name.unwrap_or_default();
+            encode_vlq(&mut self.mappings, i64::from(name) - i64::from(previous));
+        }
+
+        self.previous = Segment {
+            name: name.or(self.previous.name),
+            ..segment
+        };
+    }
+
+    fn name_index(&mut self, name: &str) -> u32 {
+        let index = self
+            .names
+            .iter()
+            .position(|existing| existing == name)
+            .unwrap_or_else(|| {
+                self.names.push(name.to_owned());
+                self.names.len() - 1
+            });
+        u32::try_from(index).unwrap_or(u32::MAX)
+    }
+
+    pub(super) fn build(self) -> SourceMap {
+        SourceMap {
+            file: self.file,
+            source: self.source,
+            source_content: self.source_content,
+            names: self.names,
+            mappings: self.mappings,
+        }
+    }
+}
+
+/// Appends `value` encoded as a base64 VLQ to `output`.
+pub(super) fn encode_vlq(output: &mut String, value: i64) {
+    const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut value = if value < 0 {
+        (value.unsigned_abs() << 1) | 1
+    } else {
+        value.unsigned_abs() << 1
+    };
+    loop {
+        let mut digit = (value & 0b1_1111) as usize;
+        value >>= 5;
+        if value > 0 {
+            digit |= 0b10_0000;
+        }
+        output.push(char::from(BASE64[digit]));
+        if value == 0 {
+            break;
+        }
+    }
+}
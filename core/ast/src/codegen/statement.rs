@@ -0,0 +1,606 @@
+//! Generation of statements, declarations and module items.
+
+use super::{Precedence, Printer, Start};
+use crate::{
+    Declaration, Expression, ModuleItem, Spanned, Statement, StatementListItem,
+    declaration::{
+        ExportDeclaration, ExportSpecifier, ImportAttribute, ImportDeclaration, ImportKind,
+        LexicalDeclaration, ModuleSpecifier, ReExportKind, VariableList,
+    },
+    expression::Identifier,
+    statement::{
+        Labelled, LabelledItem,
+        iteration::{ForLoopInitializer, IterableLoopInitializer},
+    },
+};
+use boa_interner::Sym;
+
+impl Printer<'_> {
+    /// Writes a list of statements that can start with a directive prologue, each on its own
+    /// line if the code is pretty-printed.
+    ///
+    /// If the code isn't `strict`, an escaped `"use strict"` string in the prologue is wrapped in
+    /// parentheses, so it doesn't become a directive.
+    pub(super) fn statement_list(&mut self, items: &[StatementListItem], strict: bool) {
+        let mut prologue = true;
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.newline();
+            }
+
+            let directive = match item {
+                StatementListItem::Statement(statement) => match statement.as_ref() {
+                    Statement::Expression(Expression::Literal(literal)) => {
+                        literal.as_string().map(|sym| (literal, sym))
+                    }
+                    _ => None,
+                },
+                StatementListItem::Declaration(_) => None,
+            };
+            prologue &= directive.is_some();
+
+            match directive {
+                Some((literal, sym))
+                    if prologue
+                        && !strict
+                        && self.interner.resolve_expect(sym).utf8() == Some("use strict") =>
+                {
+                    self.map(literal.span().start(), None);
+                    self.parenthesized(|printer| printer.string(sym));
+                    self.token(";");
+                }
+                _ => self.statement_list_item(item),
+            }
+        }
+    }
+
+    /// Writes a list of statements in braces.
+    pub(super) fn statement_block(&mut self, items: &[StatementListItem], strict: bool) {
+        self.token("{");
+        if !items.is_empty() {
+            self.level += 1;
+            self.delimited(|printer| {
+                printer.newline();
+                printer.statement_list(items, strict);
+            });
+            self.level -= 1;
+            self.newline();
+        }
+        self.token("}");
+    }
+
+    /// Writes the items of a module.
+    pub(super) fn module_items(&mut self, items: &[ModuleItem]) {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.newline();
+            }
+            match item {
+                ModuleItem::ImportDeclaration(import) => self.import_declaration(import),
+                ModuleItem::ExportDeclaration(export) => self.export_declaration(export),
+                ModuleItem::StatementListItem(item) => self.statement_list_item(item),
+            }
+        }
+    }
+
+    fn statement_list_item(&mut self, item: &StatementListItem) {
+        match item {
+            StatementListItem::Statement(statement) => self.statement(statement),
+            StatementListItem::Declaration(declaration) => self.declaration(declaration),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block(block) => {
+                // Blocks can't start with a directive prologue.
+                self.statement_block(block.statement_list().statements(), true);
+            }
+            Statement::Var(var) => {
+                self.token("var");
+                self.variable_list(&var.0);
+                self.token(";");
+            }
+            Statement::Empty | Statement::Error(_) => self.token(";"),
+            Statement::Expression(expression) => {
+                self.set_start(Start::Statement);
+                self.expression(expression, Precedence::Sequence);
+                self.token(";");
+            }
+            Statement::If(r#if) => {
+                self.token("if");
+                self.condition(r#if.cond());
+                match r#if.else_node() {
+                    Some(r#else) => {
+                        // An `else` would otherwise belong to an `if` at the end of the body.
+                        if ends_with_if(r#if.body()) {
+                            self.space();
+                            self.token("{");
+                            self.level += 1;
+                            self.newline();
+                            self.statement(r#if.body());
+                            self.level -= 1;
+                            self.newline();
+                            self.token("}");
+                        } else {
+                            self.substatement(r#if.body());
+                        }
+                        self.space();
+                        self.token("else");
+                        self.substatement(r#else);
+                    }
+                    None => self.substatement(r#if.body()),
+                }
+            }
+            Statement::DoWhileLoop(r#loop) => {
+                self.token("do");
+                self.substatement(r#loop.body());
+                self.space();
+                self.token("while");
+                self.condition(r#loop.cond());
+                self.token(";");
+            }
+            Statement::WhileLoop(r#loop) => {
+                self.token("while");
+                self.condition(r#loop.condition());
+                self.substatement(r#loop.body());
+            }
+            Statement::ForLoop(r#loop) => {
+                self.token("for");
+                self.space();
+                self.parenthesized(|printer| {
+                    printer.no_in = true;
+                    match r#loop.init() {
+                        Some(ForLoopInitializer::Expression(expression)) => {
+                            printer.set_start(Start::ForIn);
+                            printer.expression(expression, Precedence::Sequence);
+                        }
+                        Some(ForLoopInitializer::Var(var)) => {
+                            printer.token("var");
+                            printer.variable_list(&var.0);
+                        }
+                        Some(ForLoopInitializer::Lexical(lexical)) => {
+                            printer.lexical_declaration(lexical.declaration());
+                        }
+                        None => {}
+                    }
+                    printer.no_in = false;
+                    printer.token(";");
+                    if let Some(condition) = r#loop.condition() {
+                        printer.space();
+                        printer.expression(condition, Precedence::Sequence);
+                    }
+                    printer.token(";");
+                    if let Some(final_expr) = r#loop.final_expr() {
+                        printer.space();
+                        printer.expression(final_expr, Precedence::Sequence);
+                    }
+                });
+                self.substatement(r#loop.body());
+            }
+            Statement::ForInLoop(r#loop) => {
+                self.token("for");
+                self.space();
+                self.parenthesized(|printer| {
+                    printer.iterable_loop_initializer(r#loop.initializer(), Start::ForIn);
+                    printer.token("in");
+                    printer.space();
+                    printer.expression(r#loop.target(), Precedence::Sequence);
+                });
+                self.substatement(r#loop.body());
+            }
+            Statement::ForOfLoop(r#loop) => {
+                self.token("for");
+                if r#loop.r#await() {
+                    self.token("await");
+                }
+                self.space();
+                self.parenthesized(|printer| {
+                    printer.iterable_loop_initializer(r#loop.initializer(), Start::ForOf);
+                    printer.token("of");
+                    printer.space();
+                    printer.expression(r#loop.iterable(), Precedence::Assignment);
+                });
+                self.substatement(r#loop.body());
+            }
+            Statement::Switch(switch) => {
+                self.token("switch");
+                self.condition(switch.val());
+                self.space();
+                self.block(switch.cases(), |printer, case| {
+                    match case.condition() {
+                        Some(condition) => {
+                            printer.token("case");
+                            printer.space();
+                            printer.expression(condition, Precedence::Sequence);
+                        }
+                        None => printer.token("default"),
+                    }
+                    printer.token(":");
+                    printer.level += 1;
+                    for item in case.body().statements() {
+                        printer.newline();
+                        printer.statement_list_item(item);
+                    }
+                    printer.level -= 1;
+                });
+            }
+            Statement::Continue(r#continue) => self.jump("continue", r#continue.label()),
+            Statement::Break(r#break) => self.jump("break", r#break.label()),
+            Statement::Return(r#return) => {
+                self.token("return");
+                if let Some(target) = r#return.target() {
+                    self.space();
+                    self.expression(target, Precedence::Sequence);
+                }
+                self.token(";");
+            }
+            Statement::Labelled(labelled) => self.labelled(labelled),
+            Statement::Throw(throw) => {
+                self.token("throw");
+                self.space();
+                self.expression(throw.target(), Precedence::Sequence);
+                self.token(";");
+            }
+            Statement::Try(r#try) => {
+                self.token("try");
+                self.space();
+                self.statement_block(r#try.block().statement_list().statements(), true);
+                if let Some(catch) = r#try.catch() {
+                    self.space();
+                    self.token("catch");
+                    self.space();
+                    if let Some(parameter) = catch.parameter() {
+                        self.parenthesized(|printer| printer.binding(parameter));
+                        self.space();
+                    }
+                    self.statement_block(catch.block().statement_list().statements(), true);
+                }
+                if let Some(finally) = r#try.finally() {
+                    self.space();
+                    self.token("finally");
+                    self.space();
+                    self.statement_block(finally.block().statement_list().statements(), true);
+                }
+            }
+            Statement::With(with) => {
+                self.token("with");
+                self.condition(with.expression());
+                self.substatement(with.statement());
+            }
+            Statement::Debugger(_) => {
+                self.token("debugger");
+                self.token(";");
+            }
+        }
+    }
+
+    /// Writes the ` (condition)` of a statement.
+    fn condition(&mut self, condition: &Expression) {
+        self.space();
+        self.parenthesized(|printer| printer.expression(condition, Precedence::Sequence));
+    }
+
+    /// Writes the body of a compound statement.
+    fn substatement(&mut self, statement: &Statement) {
+        self.space();
+        self.statement(statement);
+    }
+
+    fn jump(&mut self, keyword: &str, label: Option<Sym>) {
+        self.token(keyword);
+        if let Some(label) = label {
+            self.space();
+            self.sym(label);
+        }
+        self.token(";");
+    }
+
+    fn labelled(&mut self, labelled: &Labelled) {
+        self.sym(labelled.label());
+        self.token(":");
+        self.space();
+        match labelled.item() {
+            LabelledItem::FunctionDeclaration(f) => {
+                self.function("function", Some(f.name()), f.parameters(), f.body());
+            }
+            LabelledItem::Statement(statement) => self.statement(statement),
+        }
+    }
+
+    /// Writes the left-hand side of a `for-in` or `for-of` loop, followed by a space.
+    fn iterable_loop_initializer(&mut self, initializer: &IterableLoopInitializer, start: Start) {
+        match initializer {
+            IterableLoopInitializer::Identifier(ident) => {
+                self.set_start(start);
+                self.expression(&Expression::Identifier(*ident), Precedence::Call);
+            }
+            IterableLoopInitializer::Access(access) => {
+                self.set_start(start);
+                self.property_access(access);
+            }
+            IterableLoopInitializer::Var(variable) => {
+                self.token("var");
+                self.space();
+                self.no_in = true;
+                self.variable(variable);
+                self.no_in = false;
+            }
+            IterableLoopInitializer::Let(binding) => {
+                self.token("let");
+                self.space();
+                self.binding(binding);
+            }
+            IterableLoopInitializer::Const(binding) => {
+                self.token("const");
+                self.space();
+                self.binding(binding);
+            }
+            IterableLoopInitializer::Pattern(pattern) => self.pattern(pattern),
+        }
+        // Keeps `in` and `of` apart from the initializer when minified.
+        self.raw(" ");
+    }
+
+    fn declaration(&mut self, declaration: &Declaration) {
+        match declaration {
+            Declaration::FunctionDeclaration(f) => {
+                self.function("function", Some(f.name()), f.parameters(), f.body());
+            }
+            Declaration::GeneratorDeclaration(f) => {
+                self.function("function*", Some(f.name()), f.parameters(), f.body());
+            }
+            Declaration::AsyncFunctionDeclaration(f) => {
+                self.function("async function", Some(f.name()), f.parameters(), f.body());
+            }
+            Declaration::AsyncGeneratorDeclaration(f) => {
+                self.function("async function*", Some(f.name()), f.parameters(), f.body());
+            }
+            Declaration::ClassDeclaration(class) => self.class(
+                Some(class.name()),
+                class.super_ref(),
+                class.constructor(),
+                class.elements(),
+            ),
+            Declaration::Lexical(lexical) => {
+                self.lexical_declaration(lexical);
+                self.token(";");
+            }
+        }
+    }
+
+    fn lexical_declaration(&mut self, lexical: &LexicalDeclaration) {
+        match lexical {
+            LexicalDeclaration::Const(_) => self.token("const"),
+            LexicalDeclaration::Let(_) => self.token("let"),
+            LexicalDeclaration::Using(_) => self.token("using"),
+            LexicalDeclaration::AwaitUsing(_) => {
+                self.token("await");
+                self.token("using");
+            }
+        }
+        self.variable_list(lexical.variable_list());
+    }
+
+    fn variable_list(&mut self, list: &VariableList) {
+        self.space();
+        for (index, variable) in list.as_ref().iter().enumerate() {
+            if index > 0 {
+                self.comma();
+            }
+            self.variable(variable);
+        }
+    }
+
+    fn import_declaration(&mut self, import: &ImportDeclaration) {
+        self.token("import");
+        self.space();
+
+        let mut has_bindings = false;
+        if let Some(default) = import.default() {
+            self.identifier(default);
+            has_bindings = true;
+        }
+        match import.kind() {
+            ImportKind::DefaultOrUnnamed => {}
+            ImportKind::Namespaced { binding } => {
+                if has_bindings {
+                    self.comma();
+                }
+                self.token("*");
+                self.space();
+                self.token("as");
+                self.space();
+                self.identifier(*binding);
+                has_bindings = true;
+            }
+            ImportKind::Named { names } => {
+                if has_bindings {
+                    self.comma();
+                }
+                self.specifier_list(names, |printer, specifier| {
+                    let binding = specifier.binding();
+                    if specifier.export_name() != binding.sym() {
+                        printer.name(specifier.export_name());
+                        printer.space();
+                        printer.token("as");
+                        printer.space();
+                    }
+                    printer.identifier(binding);
+                });
+                has_bindings = true;
+            }
+        }
+
+        if has_bindings {
+            self.space();
+            self.token("from");
+            self.space();
+        }
+        self.module_specifier(import.specifier(), import.attributes());
+    }
+
+    fn export_declaration(&mut self, export: &ExportDeclaration) {
+        self.token("export");
+        self.space();
+        match export {
+            ExportDeclaration::ReExport {
+                kind,
+                specifier,
+                attributes,
+            } => {
+                match kind {
+                    ReExportKind::Namespaced { name } => {
+                        self.token("*");
+                        if let Some(name) = name {
+                            self.space();
+                            self.token("as");
+                            self.space();
+                            self.name(*name);
+                        }
+                    }
+                    ReExportKind::Named { names } => self.export_specifiers(names),
+                }
+                self.space();
+                self.token("from");
+                self.space();
+                self.module_specifier(*specifier, attributes);
+            }
+            ExportDeclaration::List(names) => {
+                self.export_specifiers(names);
+                self.token(";");
+            }
+            ExportDeclaration::VarStatement(var) => {
+                self.token("var");
+                self.variable_list(&var.0);
+                self.token(";");
+            }
+            ExportDeclaration::Declaration(declaration) => self.declaration(declaration),
+            ExportDeclaration::DefaultFunctionDeclaration(f) => {
+                self.token("default");
+                self.space();
+                self.function("function", default_name(f.name()), f.parameters(), f.body());
+            }
+            ExportDeclaration::DefaultGeneratorDeclaration(f) => {
+                self.token("default");
+                self.space();
+                self.function(
+                    "function*",
+                    default_name(f.name()),
+                    f.parameters(),
+                    f.body(),
+                );
+            }
+            ExportDeclaration::DefaultAsyncFunctionDeclaration(f) => {
+                self.token("default");
+                self.space();
+                self.function(
+                    "async function",
+                    default_name(f.name()),
+                    f.parameters(),
+                    f.body(),
+                );
+            }
+            ExportDeclaration::DefaultAsyncGeneratorDeclaration(f) => {
+                self.token("default");
+                self.space();
+                self.function(
+                    "async function*",
+                    default_name(f.name()),
+                    f.parameters(),
+                    f.body(),
+                );
+            }
+            ExportDeclaration::DefaultClassDeclaration(class) => {
+                self.token("default");
+                self.space();
+                self.class(
+                    default_name(class.name()),
+                    class.super_ref(),
+                    class.constructor(),
+                    class.elements(),
+                );
+            }
+            ExportDeclaration::DefaultAssignmentExpression(expression) => {
+                self.token("default");
+                self.space();
+                self.set_start(Start::ExportDefault);
+                self.expression(expression, Precedence::Assignment);
+                self.token(";");
+            }
+        }
+    }
+
+    fn export_specifiers(&mut self, names: &[ExportSpecifier]) {
+        self.specifier_list(names, |printer, specifier| {
+            if specifier.string_literal() {
+                printer.string(specifier.private_name());
+            } else {
+                printer.sym(specifier.private_name());
+            }
+            if specifier.string_literal() || specifier.alias() != specifier.private_name() {
+                printer.space();
+                printer.token("as");
+                printer.space();
+                printer.name(specifier.alias());
+            }
+        });
+    }
+
+    /// Writes a `{ a, b as c }` list of import or export specifiers.
+    fn specifier_list<T: Copy>(&mut self, specifiers: &[T], mut f: impl FnMut(&mut Self, T)) {
+        self.token("{");
+        if !specifiers.is_empty() {
+            self.space();
+            for (index, specifier) in specifiers.iter().enumerate() {
+                if index > 0 {
+                    self.comma();
+                }
+                f(self, *specifier);
+            }
+            self.space();
+        }
+        self.token("}");
+    }
+
+    /// Writes the `"module" with { type: "json" };` end of an import or re-export.
+    fn module_specifier(&mut self, specifier: ModuleSpecifier, attributes: &[ImportAttribute]) {
+        self.string(specifier.sym());
+        if !attributes.is_empty() {
+            self.space();
+            self.token("with");
+            self.space();
+            self.specifier_list(attributes, |printer, attribute| {
+                printer.name(attribute.key());
+                printer.token(":");
+                printer.space();
+                printer.string(attribute.value());
+            });
+        }
+        self.token(";");
+    }
+}
+
+/// Checks if a statement ends with an `if` statement without an `else`, which would take the
+/// `else` of an enclosing `if` statement.
+fn ends_with_if(statement: &Statement) -> bool {
+    match statement {
+        Statement::If(r#if) => r#if.else_node().is_none_or(ends_with_if),
+        Statement::WhileLoop(r#loop) => ends_with_if(r#loop.body()),
+        Statement::ForLoop(r#loop) => ends_with_if(r#loop.body()),
+        Statement::ForInLoop(r#loop) => ends_with_if(r#loop.body()),
+        Statement::ForOfLoop(r#loop) => ends_with_if(r#loop.body()),
+        Statement::With(with) => ends_with_if(with.statement()),
+        Statement::Labelled(labelled) => match labelled.item() {
+            LabelledItem::Statement(statement) => ends_with_if(statement),
+            LabelledItem::FunctionDeclaration(_) => false,
+        },
+        _ => false,
+    }
+}
+
+/// Gets the name of a default export declaration, which is anonymous if it has the `default`
+/// name given by the parser.
+fn default_name(name: Identifier) -> Option<Identifier> {
+    (name.sym() != Sym::DEFAULT).then_some(name)
+}
@@ -0,0 +1,106 @@
+use super::{
+    escape_json, escape_template, is_identifier_name, needs_separator, quote,
+    source_map::{SourceMapBuilder, encode_vlq},
+};
+use crate::Position;
+
+fn utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+#[test]
+fn vlq() {
+    for (value, expected) in [
+        (0, "A"),
+        (1, "C"),
+        (-1, "D"),
+        (15, "e"),
+        (16, "gB"),
+        (-16, "hB"),
+        (123, "2H"),
+        (1024, "ggC"),
+    ] {
+        let mut encoded = String::new();
+        encode_vlq(&mut encoded, value);
+        assert_eq!(encoded, expected, "encoding {value}");
+    }
+}
+
+#[test]
+fn source_map_segments() {
+    let mut builder = SourceMapBuilder::new(
+        Some("out.js".to_owned()),
+        "in.js".to_owned(),
+        Some("let a\n  = \"b\";".to_owned()),
+    );
+    builder.add(0, 0, Position::new(1, 1), None);
+    builder.add(0, 4, Position::new(1, 5), Some("a"));
+    // A second mapping at the same column is ignored.
+    builder.add(0, 4, Position::new(2, 3), None);
+    builder.add(2, 2, Position::new(2, 5), None);
+    let map = builder.build();
+
+    assert_eq!(map.names(), ["a"]);
+    assert_eq!(map.mappings(), "AAAA,IAAIA;;EACA");
+    assert_eq!(
+        map.to_json(),
+        concat!(
+            r#"{"version":3,"file":"out.js","sources":["in.js"],"#,
+            r#""sourcesContent":["let a\n  = \"b\";"],"names":["a"],"#,
+            r#""mappings":"AAAA,IAAIA;;EACA"}"#
+        )
+    );
+}
+
+#[test]
+fn quoting() {
+    assert_eq!(quote(&utf16("abc")), r#""abc""#);
+    assert_eq!(quote(&utf16(r#"a"b"#)), r#"'a"b'"#);
+    assert_eq!(quote(&utf16(r#"a'"b"#)), r#""a'\"b""#);
+    assert_eq!(quote(&utf16("\\\n\r\t\0")), r#""\\\n\r\t\x00""#);
+    assert_eq!(quote(&utf16("\u{2028}é😀")), "\"\\u2028é😀\"");
+    assert_eq!(quote(&[0xD800, u16::from(b'a')]), r#""\ud800a""#);
+}
+
+#[test]
+fn template_escaping() {
+    assert_eq!(escape_template(&utf16("a`b${c}$d\\")), r"a\`b\${c}$d\\");
+}
+
+#[test]
+fn json_escaping() {
+    assert_eq!(escape_json("a\"b\\c\n\u{1}"), r#"a\"b\\c\n\u0001"#);
+}
+
+#[test]
+fn identifier_names() {
+    assert!(is_identifier_name("a"));
+    assert!(is_identifier_name("$_0"));
+    assert!(is_identifier_name("default"));
+    assert!(!is_identifier_name(""));
+    assert!(!is_identifier_name("0a"));
+    assert!(!is_identifier_name("a-b"));
+    assert!(!is_identifier_name("é"));
+}
+
+#[test]
+fn separators() {
+    let separated = |output: &str, token: &str| {
+        needs_separator(
+            output,
+            output.chars().next_back().unwrap(),
+            token.chars().next().unwrap(),
+            token,
+        )
+    };
+    assert!(separated("return", "a"));
+    assert!(separated("a+", "+"));
+    assert!(separated("a-", "--"));
+    assert!(separated("a/", "/b/"));
+    assert!(separated("a<", "!--"));
+    assert!(separated("a--", ">"));
+    assert!(separated("a<", "<b/>"));
+    assert!(!separated("a", "+"));
+    assert!(!separated("return", "\"a\""));
+    assert!(!separated("a+", "-"));
+}
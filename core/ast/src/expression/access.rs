@@ -249,7 +249,7 @@ impl VisitWith for SimplePropertyAccess {
 pub struct PrivatePropertyAccess {
     target: Box<Expression>,
     field: PrivateName,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl PrivatePropertyAccess {
@@ -334,7 +334,7 @@ impl VisitWith for PrivatePropertyAccess {
 #[derive(Clone, Debug, PartialEq)]
 pub struct SuperPropertyAccess {
     field: PropertyAccessField,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl SuperPropertyAccess {
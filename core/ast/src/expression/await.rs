@@ -23,7 +23,7 @@ use boa_interner::{Interner, ToIndentedString, ToInternedString};
 #[derive(Clone, Debug, PartialEq)]
 pub struct Await {
     target: Box<Expression>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Await {
@@ -25,7 +25,7 @@ use super::Expression;
 pub struct Call {
     function: Box<Expression>,
     args: Box<[Expression]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Call {
@@ -117,7 +117,7 @@ impl VisitWith for Call {
 #[derive(Clone, Debug, PartialEq)]
 pub struct SuperCall {
     args: Box<[Expression]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl SuperCall {
@@ -225,7 +225,7 @@ pub struct ImportCall {
     specifier: Box<Expression>,
     options: Option<Box<Expression>>,
     phase: ImportPhase,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl ImportCall {
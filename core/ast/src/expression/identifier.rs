@@ -43,7 +43,7 @@ pub const RESERVED_IDENTIFIERS_STRICT: [Sym; 9] = [
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Identifier {
     ident: Sym,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl PartialEq<Sym> for Identifier {
@@ -0,0 +1,547 @@
+//! JSX expression nodes.
+//!
+//! JSX is a syntax extension that embeds XML-like elements in expressions. It isn't part of the
+//! ECMAScript specification, and it is only parsed when enabled on the source. JSX elements are
+//! lowered into calls of a factory function when compiled, as configured by their
+//! [`JsxRuntime`].
+//!
+//! More information:
+//!  - [JSX specification][spec]
+//!
+//! [spec]: https://facebook.github.io/jsx/
+
+use super::{Expression, Identifier};
+use crate::{
+    Span, Spanned,
+    visitor::{VisitWith, Visitor, VisitorMut},
+};
+use boa_interner::{Interner, Sym, ToInternedString};
+use core::{fmt::Write as _, ops::ControlFlow};
+
+/// A JSX element used as an expression, with the runtime that creates it.
+///
+/// Only the outermost element of a JSX tree is a [`Jsx`] expression; nested elements are
+/// children or attribute values of their parent [`JsxElement`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jsx {
+    element: Box<JsxElement>,
+    runtime: JsxRuntime,
+}
+
+impl Jsx {
+    /// Creates a new JSX expression from its outermost element and its runtime.
+    #[inline]
+    #[must_use]
+    pub fn new(element: JsxElement, runtime: JsxRuntime) -> Self {
+        Self {
+            element: Box::new(element),
+            runtime,
+        }
+    }
+
+    /// Gets the outermost element of the expression.
+    #[inline]
+    #[must_use]
+    pub const fn element(&self) -> &JsxElement {
+        &self.element
+    }
+
+    /// Gets the runtime that creates the elements of the expression.
+    #[inline]
+    #[must_use]
+    pub const fn runtime(&self) -> &JsxRuntime {
+        &self.runtime
+    }
+}
+
+impl Spanned for Jsx {
+    #[inline]
+    fn span(&self) -> Span {
+        self.element.span()
+    }
+}
+
+impl ToInternedString for Jsx {
+    #[inline]
+    fn to_interned_string(&self, interner: &Interner) -> String {
+        self.element.to_interned_string(interner)
+    }
+}
+
+impl From<Jsx> for Expression {
+    #[inline]
+    fn from(jsx: Jsx) -> Self {
+        Self::Jsx(jsx)
+    }
+}
+
+impl VisitWith for Jsx {
+    fn visit_with<'a, V>(&'a self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        match &self.runtime {
+            JsxRuntime::Classic { factory, fragment } => {
+                visitor.visit_expression(factory)?;
+                visitor.visit_expression(fragment)?;
+            }
+            JsxRuntime::Automatic {
+                jsx,
+                jsxs,
+                fragment,
+                module,
+                exports,
+            } => {
+                visitor.visit_identifier(jsx)?;
+                visitor.visit_identifier(jsxs)?;
+                visitor.visit_identifier(fragment)?;
+                visitor.visit_sym(module)?;
+                for export in exports {
+                    visitor.visit_sym(export)?;
+                }
+            }
+        }
+        visitor.visit_jsx_element(&self.element)
+    }
+
+    fn visit_with_mut<'a, V>(&'a mut self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: VisitorMut<'a>,
+    {
+        match &mut self.runtime {
+            JsxRuntime::Classic { factory, fragment } => {
+                visitor.visit_expression_mut(factory)?;
+                visitor.visit_expression_mut(fragment)?;
+            }
+            JsxRuntime::Automatic {
+                jsx,
+                jsxs,
+                fragment,
+                module,
+                exports,
+            } => {
+                visitor.visit_identifier_mut(jsx)?;
+                visitor.visit_identifier_mut(jsxs)?;
+                visitor.visit_identifier_mut(fragment)?;
+                visitor.visit_sym_mut(module)?;
+                for export in exports {
+                    visitor.visit_sym_mut(export)?;
+                }
+            }
+        }
+        visitor.visit_jsx_element_mut(&mut self.element)
+    }
+}
+
+/// The runtime that creates the elements of a [`Jsx`] expression.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxRuntime {
+    /// Elements are created with `factory(type, props, ...children)`, like
+    /// `React.createElement`, and fragments use `fragment` as their type.
+    Classic {
+        /// The function that creates an element.
+        factory: Box<Expression>,
+        /// The type of fragments.
+        fragment: Box<Expression>,
+    },
+    /// Elements are created with `jsx(type, props, key)`, or with `jsxs` if they have multiple
+    /// static children, where the children are part of the props.
+    ///
+    /// The identifiers are bound by an import of the `jsx-runtime` module of the JSX library.
+    /// The import isn't part of the source, so it is added to the imports of the module when the
+    /// module is compiled (see [`ModuleItemList::jsx_runtime_import_entries`]).
+    ///
+    /// [`ModuleItemList::jsx_runtime_import_entries`]: crate::ModuleItemList::jsx_runtime_import_entries
+    Automatic {
+        /// The function that creates an element with zero or one child.
+        jsx: Identifier,
+        /// The function that creates an element with multiple static children.
+        jsxs: Identifier,
+        /// The type of fragments.
+        fragment: Identifier,
+        /// The module that exports the runtime, like `react/jsx-runtime`.
+        module: Sym,
+        /// The names that the module exports `jsx`, `jsxs` and `fragment` as.
+        exports: [Sym; 3],
+    },
+}
+
+/// A JSX element, like `<div id="a">text</div>`, or a fragment, like `<>text</>`.
+///
+/// More information:
+///  - [JSX specification][spec]
+///
+/// [spec]: https://facebook.github.io/jsx/#prod-JSXElement
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsxElement {
+    name: Option<JsxElementName>,
+    attributes: Box<[JsxAttribute]>,
+    children: Box<[JsxChild]>,
+    pub(crate) span: Span,
+}
+
+impl JsxElement {
+    /// Creates a new JSX element, or a fragment if `name` is `None`.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        name: Option<JsxElementName>,
+        attributes: Box<[JsxAttribute]>,
+        children: Box<[JsxChild]>,
+        span: Span,
+    ) -> Self {
+        Self {
+            name,
+            attributes,
+            children,
+            span,
+        }
+    }
+
+    /// Gets the name of the element, or `None` if it is a fragment.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> Option<&JsxElementName> {
+        self.name.as_ref()
+    }
+
+    /// Gets the attributes of the element.
+    #[inline]
+    #[must_use]
+    pub const fn attributes(&self) -> &[JsxAttribute] {
+        &self.attributes
+    }
+
+    /// Gets the children of the element.
+    #[inline]
+    #[must_use]
+    pub const fn children(&self) -> &[JsxChild] {
+        &self.children
+    }
+}
+
+impl Spanned for JsxElement {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl ToInternedString for JsxElement {
+    fn to_interned_string(&self, interner: &Interner) -> String {
+        let name = self
+            .name
+            .as_ref()
+            .map(|name| name.to_interned_string(interner))
+            .unwrap_or_default();
+        let mut buf = format!("<{name}");
+        for attribute in &*self.attributes {
+            let _ = write!(buf, " {}", attribute.to_interned_string(interner));
+        }
+        if self.children.is_empty() && self.name.is_some() {
+            buf.push_str(" />");
+            return buf;
+        }
+        buf.push('>');
+        for child in &*self.children {
+            buf.push_str(&child.to_interned_string(interner));
+        }
+        let _ = write!(buf, "</{name}>");
+        buf
+    }
+}
+
+impl VisitWith for JsxElement {
+    fn visit_with<'a, V>(&'a self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        if let Some(JsxElementName::Component(expression)) = &self.name {
+            visitor.visit_expression(expression)?;
+        }
+        for attribute in &*self.attributes {
+            visitor.visit_jsx_attribute(attribute)?;
+        }
+        for child in &*self.children {
+            visitor.visit_jsx_child(child)?;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_with_mut<'a, V>(&'a mut self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: VisitorMut<'a>,
+    {
+        if let Some(JsxElementName::Component(expression)) = &mut self.name {
+            visitor.visit_expression_mut(expression)?;
+        }
+        for attribute in &mut *self.attributes {
+            visitor.visit_jsx_attribute_mut(attribute)?;
+        }
+        for child in &mut *self.children {
+            visitor.visit_jsx_child_mut(child)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// The name of a [`JsxElement`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxElementName {
+    /// An intrinsic element, whose type is its name as a string, like `div`, `my-element` or
+    /// `svg:rect`.
+    Intrinsic(Sym),
+    /// A component, whose type is the value of an identifier or a member expression, like
+    /// `Button` or `this.components.Button`.
+    Component(Expression),
+}
+
+impl ToInternedString for JsxElementName {
+    #[inline]
+    fn to_interned_string(&self, interner: &Interner) -> String {
+        match self {
+            Self::Intrinsic(name) => interner.resolve_expect(*name).to_string(),
+            Self::Component(expression) => expression.to_interned_string(interner),
+        }
+    }
+}
+
+/// An attribute of a [`JsxElement`], like `id="a"` or `{...props}`.
+///
+/// More information:
+///  - [JSX specification][spec]
+///
+/// [spec]: https://facebook.github.io/jsx/#prod-JSXAttributes
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxAttribute {
+    /// A named attribute. An attribute without a value is `true`.
+    Named {
+        /// The name of the attribute, which can be namespaced like `xlink:href`.
+        name: Sym,
+        /// The value of the attribute.
+        value: Option<JsxAttributeValue>,
+    },
+    /// A spread attribute, like `{...props}`.
+    Spread(Expression),
+}
+
+impl ToInternedString for JsxAttribute {
+    fn to_interned_string(&self, interner: &Interner) -> String {
+        match self {
+            Self::Named { name, value: None } => interner.resolve_expect(*name).to_string(),
+            Self::Named {
+                name,
+                value: Some(value),
+            } => {
+                let name = interner.resolve_expect(*name);
+                match value {
+                    JsxAttributeValue::String(text) => {
+                        let raw = interner.resolve_expect(text.raw()).to_string();
+                        let quote = if raw.contains('"') { '\'' } else { '"' };
+                        format!("{name}={quote}{raw}{quote}")
+                    }
+                    JsxAttributeValue::Expression(expression) => {
+                        format!("{name}={{{}}}", expression.to_interned_string(interner))
+                    }
+                    JsxAttributeValue::Element(element) => {
+                        format!("{name}={}", element.to_interned_string(interner))
+                    }
+                }
+            }
+            Self::Spread(expression) => {
+                format!("{{...{}}}", expression.to_interned_string(interner))
+            }
+        }
+    }
+}
+
+impl VisitWith for JsxAttribute {
+    fn visit_with<'a, V>(&'a self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        match self {
+            Self::Named { name, value } => {
+                visitor.visit_sym(name)?;
+                match value {
+                    Some(JsxAttributeValue::String(text)) => visitor.visit_jsx_text(text),
+                    Some(JsxAttributeValue::Expression(expression)) => {
+                        visitor.visit_expression(expression)
+                    }
+                    Some(JsxAttributeValue::Element(element)) => visitor.visit_jsx_element(element),
+                    None => ControlFlow::Continue(()),
+                }
+            }
+            Self::Spread(expression) => visitor.visit_expression(expression),
+        }
+    }
+
+    fn visit_with_mut<'a, V>(&'a mut self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: VisitorMut<'a>,
+    {
+        match self {
+            Self::Named { name, value } => {
+                visitor.visit_sym_mut(name)?;
+                match value {
+                    Some(JsxAttributeValue::String(text)) => visitor.visit_jsx_text_mut(text),
+                    Some(JsxAttributeValue::Expression(expression)) => {
+                        visitor.visit_expression_mut(expression)
+                    }
+                    Some(JsxAttributeValue::Element(element)) => {
+                        visitor.visit_jsx_element_mut(element)
+                    }
+                    None => ControlFlow::Continue(()),
+                }
+            }
+            Self::Spread(expression) => visitor.visit_expression_mut(expression),
+        }
+    }
+}
+
+/// The value of a named [`JsxAttribute`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxAttributeValue {
+    /// A string, like `"a"`.
+    String(JsxText),
+    /// An expression container, like `{a}`.
+    Expression(Expression),
+    /// An element, like `<a />`.
+    Element(JsxElement),
+}
+
+/// A child of a [`JsxElement`].
+///
+/// More information:
+///  - [JSX specification][spec]
+///
+/// [spec]: https://facebook.github.io/jsx/#prod-JSXChildren
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsxChild {
+    /// Text between elements.
+    Text(JsxText),
+    /// A nested element.
+    Element(JsxElement),
+    /// An expression container, like `{a}`.
+    Expression(Expression),
+    /// A spread child, like `{...a}`.
+    Spread(Expression),
+}
+
+impl ToInternedString for JsxChild {
+    fn to_interned_string(&self, interner: &Interner) -> String {
+        match self {
+            Self::Text(text) => interner.resolve_expect(text.raw()).to_string(),
+            Self::Element(element) => element.to_interned_string(interner),
+            Self::Expression(expression) => {
+                format!("{{{}}}", expression.to_interned_string(interner))
+            }
+            Self::Spread(expression) => {
+                format!("{{...{}}}", expression.to_interned_string(interner))
+            }
+        }
+    }
+}
+
+impl VisitWith for JsxChild {
+    fn visit_with<'a, V>(&'a self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        match self {
+            Self::Text(text) => visitor.visit_jsx_text(text),
+            Self::Element(element) => visitor.visit_jsx_element(element),
+            Self::Expression(expression) | Self::Spread(expression) => {
+                visitor.visit_expression(expression)
+            }
+        }
+    }
+
+    fn visit_with_mut<'a, V>(&'a mut self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: VisitorMut<'a>,
+    {
+        match self {
+            Self::Text(text) => visitor.visit_jsx_text_mut(text),
+            Self::Element(element) => visitor.visit_jsx_element_mut(element),
+            Self::Expression(expression) | Self::Spread(expression) => {
+                visitor.visit_expression_mut(expression)
+            }
+        }
+    }
+}
+
+/// JSX text, either between elements or as a string attribute value.
+///
+/// The value of the text has its HTML character references, like `&amp;`, decoded. Whitespace
+/// is kept as written, and it is only trimmed when the element is created.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JsxText {
+    raw: Sym,
+    value: Sym,
+    pub(crate) span: Span,
+}
+
+impl JsxText {
+    /// Creates a new JSX text from its raw source and its decoded value.
+    #[inline]
+    #[must_use]
+    pub const fn new(raw: Sym, value: Sym, span: Span) -> Self {
+        Self { raw, value, span }
+    }
+
+    /// Gets the text as written in the source.
+    #[inline]
+    #[must_use]
+    pub const fn raw(&self) -> Sym {
+        self.raw
+    }
+
+    /// Gets the text with its character references decoded.
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> Sym {
+        self.value
+    }
+}
+
+impl Spanned for JsxText {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl VisitWith for JsxText {
+    fn visit_with<'a, V>(&'a self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        visitor.visit_sym(&self.raw)?;
+        visitor.visit_sym(&self.value)
+    }
+
+    fn visit_with_mut<'a, V>(&'a mut self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: VisitorMut<'a>,
+    {
+        visitor.visit_sym_mut(&mut self.raw)?;
+        visitor.visit_sym_mut(&mut self.value)
+    }
+}
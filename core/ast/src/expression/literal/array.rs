@@ -30,7 +30,7 @@ use core::ops::ControlFlow;
 pub struct ArrayLiteral {
     arr: Box<[Option<Expression>]>,
     has_trailing_comma_spread: bool,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl ArrayLiteral {
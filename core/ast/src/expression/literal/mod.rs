@@ -40,8 +40,8 @@ use super::Expression;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Literal {
     kind: LiteralKind,
-    span: Span,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) span: Span,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl Literal {
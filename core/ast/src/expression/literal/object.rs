@@ -41,7 +41,7 @@ use core::{fmt::Write as _, ops::ControlFlow};
 #[derive(Clone, Debug, PartialEq)]
 pub struct ObjectLiteral {
     properties: Box<[PropertyDefinition]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl ObjectLiteral {
@@ -424,7 +424,7 @@ pub struct ObjectMethodDefinition {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl ObjectMethodDefinition {
@@ -20,7 +20,7 @@ use core::{fmt::Write as _, ops::ControlFlow};
 #[derive(Clone, Debug, PartialEq)]
 pub struct TemplateLiteral {
     elements: Box<[TemplateElement]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 /// Manual implementation, because string and expression in the element list must always appear in order.
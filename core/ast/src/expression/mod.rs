@@ -11,6 +11,7 @@
 
 use self::{
     access::PropertyAccess,
+    jsx::Jsx,
     literal::{ArrayLiteral, ObjectLiteral, TemplateLiteral},
     operator::{Assign, Binary, BinaryInPrivate, Conditional, Unary, Update},
 };
@@ -59,6 +60,7 @@ pub use this::This;
 pub use r#yield::Yield;
 
 pub mod access;
+pub mod jsx;
 pub mod literal;
 pub mod operator;
 
@@ -178,6 +180,9 @@ pub enum Expression {
 
     /// See [`Parenthesized`].
     Parenthesized(Parenthesized),
+
+    /// See [`Jsx`].
+    Jsx(Jsx),
 }
 
 impl Expression {
@@ -222,6 +227,7 @@ impl Expression {
             Self::Yield(yi) => yi.to_interned_string(interner),
             Self::Parenthesized(expr) => expr.to_interned_string(interner),
             Self::RegExpLiteral(regexp) => regexp.to_interned_string(interner),
+            Self::Jsx(jsx) => jsx.to_interned_string(interner),
         }
     }
 
@@ -314,6 +320,7 @@ impl Spanned for Expression {
             Self::Yield(yi) => yi.span(),
             Self::Parenthesized(expr) => expr.span(),
             Self::RegExpLiteral(regexp) => regexp.span(),
+            Self::Jsx(jsx) => jsx.span(),
         }
     }
 }
@@ -371,6 +378,7 @@ impl VisitWith for Expression {
             Self::Parenthesized(e) => visitor.visit_parenthesized(e),
             Self::NewTarget(new_target) => visitor.visit_new_target(new_target),
             Self::ImportMeta(import_meta) => visitor.visit_import_meta(import_meta),
+            Self::Jsx(jsx) => visitor.visit_jsx(jsx),
         }
     }
 
@@ -412,6 +420,7 @@ impl VisitWith for Expression {
             Self::Parenthesized(e) => visitor.visit_parenthesized_mut(e),
             Self::NewTarget(new_target) => visitor.visit_new_target_mut(new_target),
             Self::ImportMeta(import_meta) => visitor.visit_import_meta_mut(import_meta),
+            Self::Jsx(jsx) => visitor.visit_jsx_mut(jsx),
         }
     }
 }
@@ -215,7 +215,7 @@ pub enum AssignOp {
 
 impl AssignOp {
     /// Retrieves the operation as a static string.
-    const fn as_str(self) -> &'static str {
+    pub(crate) const fn as_str(self) -> &'static str {
         match self {
             Self::Assign => "=",
             Self::Add => "+=",
@@ -35,7 +35,7 @@ pub use op::*;
 pub struct Unary {
     op: UnaryOp,
     target: Box<Expression>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Unary {
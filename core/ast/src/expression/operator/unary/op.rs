@@ -137,7 +137,7 @@ pub enum UnaryOp {
 
 impl UnaryOp {
     /// Retrieves the operation as a static string.
-    const fn as_str(self) -> &'static str {
+    pub(crate) const fn as_str(self) -> &'static str {
         match self {
             Self::Plus => "+",
             Self::Minus => "-",
@@ -33,7 +33,7 @@ pub use op::*;
 pub struct Update {
     op: UpdateOp,
     target: Box<UpdateTarget>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Update {
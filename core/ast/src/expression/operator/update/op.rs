@@ -69,7 +69,7 @@ pub enum UpdateOp {
 
 impl UpdateOp {
     /// Retrieves the operation as a static string.
-    const fn as_str(self) -> &'static str {
+    pub(crate) const fn as_str(self) -> &'static str {
         match self {
             Self::IncrementPost | Self::IncrementPre => "++",
             Self::DecrementPost | Self::DecrementPre => "--",
@@ -76,7 +76,7 @@ impl VisitWith for OptionalOperationKind {
 pub struct OptionalOperation {
     kind: OptionalOperationKind,
     shorted: bool,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl OptionalOperation {
@@ -195,7 +195,7 @@ impl VisitWith for OptionalOperation {
 pub struct Optional {
     target: Box<Expression>,
     chain: Box<[OptionalOperation]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Optional {
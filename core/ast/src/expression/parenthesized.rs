@@ -19,7 +19,7 @@ use core::ops::ControlFlow;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Parenthesized {
     pub(crate) expression: Box<Expression>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Parenthesized {
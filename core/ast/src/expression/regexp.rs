@@ -32,7 +32,7 @@ use super::Expression;
 pub struct RegExpLiteral {
     pattern: Sym,
     flags: Sym,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl RegExpLiteral {
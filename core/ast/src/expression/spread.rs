@@ -29,7 +29,7 @@ use super::Expression;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Spread {
     target: Box<Expression>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Spread {
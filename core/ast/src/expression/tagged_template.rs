@@ -21,8 +21,8 @@ pub struct TaggedTemplate {
     raws: Box<[Sym]>,
     cookeds: Box<[Option<Sym>]>,
     exprs: Box<[Expression]>,
-    identifier: u64,
-    span: Span,
+    pub(crate) identifier: u64,
+    pub(crate) span: Span,
 }
 
 impl TaggedTemplate {
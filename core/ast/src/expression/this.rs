@@ -14,7 +14,7 @@ use super::Expression;
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct This {
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl This {
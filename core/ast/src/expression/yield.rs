@@ -22,7 +22,7 @@ use super::Expression;
 pub struct Yield {
     target: Option<Box<Expression>>,
     delegate: bool,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl Yield {
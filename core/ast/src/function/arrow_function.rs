@@ -30,8 +30,8 @@ pub struct ArrowFunction {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
-    span: Span,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
+    pub(crate) span: Span,
 }
 
 impl ArrowFunction {
@@ -30,9 +30,9 @@ pub struct AsyncArrowFunction {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl AsyncArrowFunction {
@@ -31,7 +31,7 @@ pub struct AsyncFunctionDeclaration {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl AsyncFunctionDeclaration {
@@ -160,9 +160,9 @@ pub struct AsyncFunctionExpression {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl AsyncFunctionExpression {
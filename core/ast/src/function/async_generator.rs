@@ -32,7 +32,7 @@ pub struct AsyncGeneratorDeclaration {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl AsyncGeneratorDeclaration {
@@ -161,9 +161,9 @@ pub struct AsyncGeneratorExpression {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl AsyncGeneratorExpression {
@@ -175,7 +175,7 @@ pub struct ClassExpression {
     pub(crate) constructor: Option<FunctionExpression>,
     pub(crate) elements: Box<[ClassElement]>,
 
-    span: Span,
+    pub(crate) span: Span,
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) name_scope: Option<Scope>,
@@ -696,7 +696,7 @@ pub struct ClassMethodDefinition {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl ClassMethodDefinition {
@@ -860,7 +860,7 @@ impl ToInternedString for ClassElementName {
 pub struct PrivateName {
     /// The `[[Description]]` internal slot of the private name.
     description: Sym,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl PrivateName {
@@ -66,7 +66,7 @@ use crate::{
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionBody {
     pub(crate) statements: StatementList,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl FunctionBody {
@@ -78,9 +78,11 @@ impl FunctionBody {
     }
 
     /// Gets the list of statements.
+    ///
+    /// This is not a `const fn`, since the statements are reference counted.
     #[inline]
     #[must_use]
-    pub const fn statements(&self) -> &[StatementListItem] {
+    pub fn statements(&self) -> &[StatementListItem] {
         self.statements.statements()
     }
 
@@ -134,7 +136,7 @@ impl VisitWith for FunctionBody {
     where
         V: VisitorMut<'a>,
     {
-        for statement in &mut *self.statements.statements {
+        for statement in std::rc::Rc::make_mut(&mut self.statements.statements) {
             visitor.visit_statement_list_item_mut(statement)?;
         }
         ControlFlow::Continue(())
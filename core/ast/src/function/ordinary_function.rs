@@ -32,7 +32,7 @@ pub struct FunctionDeclaration {
 
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
-    linear_span: LinearSpanIgnoreEq,
+    pub(crate) linear_span: LinearSpanIgnoreEq,
 }
 
 impl FunctionDeclaration {
@@ -162,9 +162,9 @@ pub struct FunctionExpression {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) scopes: FunctionScopes,
 
-    span: Span,
+    pub(crate) span: Span,
 
-    linear_span: Option<LinearSpan>,
+    pub(crate) linear_span: Option<LinearSpan>,
 }
 
 impl PartialEq for FunctionExpression {
@@ -31,6 +31,7 @@ mod source;
 mod source_text;
 mod statement_list;
 
+pub mod codegen;
 pub mod declaration;
 pub mod expression;
 pub mod function;
@@ -40,6 +41,7 @@ pub mod pattern;
 pub mod property;
 pub mod scope;
 pub mod scope_analyzer;
+pub mod semantic;
 pub mod statement;
 pub mod visitor;
 
@@ -53,7 +55,8 @@ pub use self::{
     keyword::Keyword,
     module_item_list::{ModuleItem, ModuleItemList},
     position::{
-        LinearPosition, LinearSpan, LinearSpanIgnoreEq, Position, PositionGroup, Span, Spanned,
+        LinearPosition, LinearSpan, LinearSpanIgnoreEq, Position, PositionGroup, Relocation, Span,
+        Spanned,
     },
     punctuator::Punctuator,
     source::{Module, Script},
@@ -12,6 +12,7 @@ use crate::{
         ImportEntry, ImportKind, ImportName, IndirectExportEntry, LocalExportEntry,
         ModuleSpecifier, ReExportImportName, ReExportKind,
     },
+    expression::jsx::{Jsx, JsxRuntime},
     operations::{BoundNamesVisitor, bound_names},
     visitor::{VisitWith, Visitor, VisitorMut},
 };
@@ -302,6 +303,49 @@ impl ModuleItemList {
         entries
     }
 
+    /// Gets the import entries of the automatic JSX runtime used by the elements of this module.
+    ///
+    /// The import of the runtime isn't part of the source, so these entries are not part of the
+    /// [`import_entries`][Self::import_entries] of the module, but they have to be imported in
+    /// the same way. All the elements of a module use the same runtime.
+    #[must_use]
+    pub fn jsx_runtime_import_entries(&self) -> Vec<ImportEntry> {
+        #[derive(Debug)]
+        struct JsxRuntimeVisitor<'vec>(&'vec mut Vec<ImportEntry>);
+
+        impl<'ast> Visitor<'ast> for JsxRuntimeVisitor<'_> {
+            type BreakTy = ();
+
+            fn visit_jsx(&mut self, node: &'ast Jsx) -> ControlFlow<Self::BreakTy> {
+                let JsxRuntime::Automatic {
+                    jsx,
+                    jsxs,
+                    fragment,
+                    module,
+                    exports,
+                } = node.runtime()
+                else {
+                    return ControlFlow::Continue(());
+                };
+                for (local_name, export) in [jsx, jsxs, fragment].into_iter().zip(exports) {
+                    self.0.push(ImportEntry::new(
+                        *module,
+                        ImportName::Name(*export),
+                        *local_name,
+                        Box::default(),
+                    ));
+                }
+                ControlFlow::Break(())
+            }
+        }
+
+        let mut entries = Vec::default();
+
+        let _ = JsxRuntimeVisitor(&mut entries).visit_module_item_list(self);
+
+        entries
+    }
+
     /// Operation [`ExportEntries`][spec].
     ///
     /// Gets the list of export entries of this module.
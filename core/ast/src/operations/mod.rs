@@ -9,28 +9,37 @@ use boa_interner::{Interner, Sym};
 use rustc_hash::FxHashSet;
 
 use crate::{
-    Declaration, Expression, LinearSpan, ModuleItem, Script, Statement, StatementList,
-    StatementListItem,
+    Declaration, Expression, LinearSpan, ModuleItem, Position, Relocation, Script, Spanned,
+    Statement, StatementList, StatementListItem,
     declaration::{
         Binding, ExportDeclaration, ImportDeclaration, LexicalDeclaration, VarDeclaration, Variable,
     },
     expression::{
-        Await, Call, Identifier, NewTarget, OptionalOperationKind, SuperCall, This, Yield,
-        access::{PrivatePropertyAccess, SuperPropertyAccess},
-        literal::PropertyDefinition,
-        operator::BinaryInPrivate,
+        Await, Call, Identifier, ImportCall, ImportMeta, NewTarget, Optional, OptionalOperation,
+        OptionalOperationKind, Parenthesized, RegExpLiteral, Spread, SuperCall, TaggedTemplate,
+        This, Yield,
+        access::{PrivatePropertyAccess, PropertyAccessField, SuperPropertyAccess},
+        jsx::{JsxElement, JsxText},
+        literal::{
+            ArrayLiteral, Literal, ObjectLiteral, ObjectMethodDefinition, PropertyDefinition,
+            TemplateLiteral,
+        },
+        operator::{BinaryInPrivate, Unary, Update},
     },
     function::{
         ArrowFunction, AsyncArrowFunction, AsyncFunctionDeclaration, AsyncFunctionExpression,
         AsyncGeneratorDeclaration, AsyncGeneratorExpression, ClassDeclaration, ClassElement,
         ClassElementName, ClassExpression, FormalParameterList, FunctionBody, FunctionDeclaration,
         FunctionExpression, GeneratorDeclaration, GeneratorExpression, PrivateFieldDefinition,
+        PrivateName,
     },
+    pattern::{ArrayPattern, ObjectPattern},
+    property::PropertyName,
     statement::{
         LabelledItem, With,
         iteration::{ForLoopInitializer, IterableLoopInitializer},
     },
-    visitor::{NodeRef, VisitWith, Visitor},
+    visitor::{NodeRef, NodeRefMut, VisitWith, Visitor, VisitorMut},
 };
 
 #[cfg(test)]
@@ -465,24 +474,36 @@ pub fn has_direct_super_new(params: &FormalParameterList, body: &FunctionBody) -
 
 /// A container that [`BoundNamesVisitor`] can use to push the found identifiers.
 pub(crate) trait IdentList {
-    fn add(&mut self, value: Sym, function: bool);
+    fn add(&mut self, value: Identifier, function: bool);
 }
 
 impl IdentList for Vec<Sym> {
-    fn add(&mut self, value: Sym, _function: bool) {
-        self.push(value);
+    fn add(&mut self, value: Identifier, _function: bool) {
+        self.push(value.sym());
     }
 }
 
 impl IdentList for Vec<(Sym, bool)> {
-    fn add(&mut self, value: Sym, function: bool) {
-        self.push((value, function));
+    fn add(&mut self, value: Identifier, function: bool) {
+        self.push((value.sym(), function));
     }
 }
 
 impl IdentList for FxHashSet<Sym> {
-    fn add(&mut self, value: Sym, _function: bool) {
-        self.insert(value);
+    fn add(&mut self, value: Identifier, _function: bool) {
+        self.insert(value.sym());
+    }
+}
+
+impl IdentList for Vec<Identifier> {
+    fn add(&mut self, value: Identifier, _function: bool) {
+        self.push(value);
+    }
+}
+
+impl IdentList for Vec<(Identifier, bool)> {
+    fn add(&mut self, value: Identifier, function: bool) {
+        self.push((value, function));
     }
 }
 
@@ -494,7 +515,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
     type BreakTy = Infallible;
 
     fn visit_identifier(&mut self, node: &'ast Identifier) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.sym(), false);
+        self.0.add(*node, false);
         ControlFlow::Continue(())
     }
 
@@ -507,7 +528,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         node: &'ast FunctionExpression,
     ) -> ControlFlow<Self::BreakTy> {
         if let Some(ident) = node.name() {
-            self.0.add(ident.sym(), true);
+            self.0.add(ident, true);
         }
         ControlFlow::Continue(())
     }
@@ -516,7 +537,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         &mut self,
         node: &'ast FunctionDeclaration,
     ) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.name().sym(), true);
+        self.0.add(node.name(), true);
         ControlFlow::Continue(())
     }
 
@@ -525,7 +546,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         node: &'ast GeneratorExpression,
     ) -> ControlFlow<Self::BreakTy> {
         if let Some(ident) = node.name() {
-            self.0.add(ident.sym(), false);
+            self.0.add(ident, false);
         }
         ControlFlow::Continue(())
     }
@@ -534,7 +555,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         &mut self,
         node: &'ast GeneratorDeclaration,
     ) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.name().sym(), false);
+        self.0.add(node.name(), false);
         ControlFlow::Continue(())
     }
 
@@ -543,7 +564,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         node: &'ast AsyncFunctionExpression,
     ) -> ControlFlow<Self::BreakTy> {
         if let Some(ident) = node.name() {
-            self.0.add(ident.sym(), false);
+            self.0.add(ident, false);
         }
         ControlFlow::Continue(())
     }
@@ -552,7 +573,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         &mut self,
         node: &'ast AsyncFunctionDeclaration,
     ) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.name().sym(), false);
+        self.0.add(node.name(), false);
         ControlFlow::Continue(())
     }
 
@@ -561,7 +582,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         node: &'ast AsyncGeneratorExpression,
     ) -> ControlFlow<Self::BreakTy> {
         if let Some(ident) = node.name() {
-            self.0.add(ident.sym(), false);
+            self.0.add(ident, false);
         }
         ControlFlow::Continue(())
     }
@@ -570,7 +591,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         &mut self,
         node: &'ast AsyncGeneratorDeclaration,
     ) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.name().sym(), false);
+        self.0.add(node.name(), false);
         ControlFlow::Continue(())
     }
 
@@ -579,7 +600,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         node: &'ast ClassExpression,
     ) -> ControlFlow<Self::BreakTy> {
         if let Some(ident) = node.name() {
-            self.0.add(ident.sym(), false);
+            self.0.add(ident, false);
         }
         ControlFlow::Continue(())
     }
@@ -588,7 +609,7 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
         &mut self,
         node: &'ast ClassDeclaration,
     ) -> ControlFlow<Self::BreakTy> {
-        self.0.add(node.name().sym(), false);
+        self.0.add(node.name(), false);
         ControlFlow::Continue(())
     }
 
@@ -600,22 +621,23 @@ impl<'ast, T: IdentList> Visitor<'ast> for BoundNamesVisitor<'_, T> {
             ExportDeclaration::VarStatement(var) => self.visit_var_declaration(var)?,
             ExportDeclaration::Declaration(decl) => self.visit_declaration(decl)?,
             ExportDeclaration::DefaultFunctionDeclaration(f) => {
-                self.0.add(f.name().sym(), true);
+                self.0.add(f.name(), true);
             }
             ExportDeclaration::DefaultGeneratorDeclaration(g) => {
-                self.0.add(g.name().sym(), false);
+                self.0.add(g.name(), false);
             }
             ExportDeclaration::DefaultAsyncFunctionDeclaration(af) => {
-                self.0.add(af.name().sym(), false);
+                self.0.add(af.name(), false);
             }
             ExportDeclaration::DefaultAsyncGeneratorDeclaration(ag) => {
-                self.0.add(ag.name().sym(), false);
+                self.0.add(ag.name(), false);
             }
             ExportDeclaration::DefaultClassDeclaration(cl) => {
-                self.0.add(cl.name().sym(), false);
+                self.0.add(cl.name(), false);
             }
-            ExportDeclaration::DefaultAssignmentExpression(_) => {
-                self.0.add(Sym::DEFAULT_EXPORT, false);
+            ExportDeclaration::DefaultAssignmentExpression(expr) => {
+                self.0
+                    .add(Identifier::new(Sym::DEFAULT_EXPORT, expr.span()), false);
             }
             ExportDeclaration::ReExport { .. } | ExportDeclaration::List(_) => {}
         }
@@ -640,6 +662,19 @@ where
     names
 }
 
+/// Returns a list with the bound names of an AST node together with their spans, which may
+/// contain duplicates.
+#[must_use]
+pub fn bound_identifiers<'a, N>(node: &'a N) -> Vec<Identifier>
+where
+    &'a N: Into<NodeRef<'a>>,
+{
+    let mut names = Vec::new();
+    let _ = BoundNamesVisitor(&mut names).visit(node.into());
+
+    names
+}
+
 /// The [`Visitor`] used to obtain the lexically declared names of a node.
 #[derive(Debug)]
 struct LexicallyDeclaredNamesVisitor<'a, T: IdentList>(&'a mut T);
@@ -832,11 +867,26 @@ where
     names
 }
 
+/// Returns a list with the lexical bindings of a node together with their spans, which may
+/// contain duplicates.
+///
+/// Like [`lexically_declared_names_legacy`], names that originate from a function declaration
+/// are flagged as `true` in the returned list.
+#[must_use]
+pub fn lexically_declared_identifiers<'a, N>(node: &'a N) -> Vec<(Identifier, bool)>
+where
+    &'a N: Into<NodeRef<'a>>,
+{
+    let mut names = Vec::new();
+    let _ = LexicallyDeclaredNamesVisitor(&mut names).visit(node.into());
+    names
+}
+
 /// The [`Visitor`] used to obtain the var declared names of a node.
 #[derive(Debug)]
-struct VarDeclaredNamesVisitor<'a>(&'a mut FxHashSet<Sym>);
+struct VarDeclaredNamesVisitor<'a, T: IdentList>(&'a mut T);
 
-impl<'ast> Visitor<'ast> for VarDeclaredNamesVisitor<'_> {
+impl<'ast, T: IdentList> Visitor<'ast> for VarDeclaredNamesVisitor<'_, T> {
     type BreakTy = Infallible;
 
     fn visit_script(&mut self, node: &'ast Script) -> ControlFlow<Self::BreakTy> {
@@ -875,7 +925,8 @@ impl<'ast> Visitor<'ast> for VarDeclaredNamesVisitor<'_> {
     fn visit_statement(&mut self, node: &'ast Statement) -> ControlFlow<Self::BreakTy> {
         match node {
             Statement::Empty
-            | Statement::Debugger
+            | Statement::Debugger(_)
+            | Statement::Error(_)
             | Statement::Expression(_)
             | Statement::Continue(_)
             | Statement::Break(_)
@@ -1090,6 +1141,20 @@ where
     names
 }
 
+/// Returns a list with the var declared names of a node together with their spans, which may
+/// contain duplicates.
+///
+/// This is the same as [`var_declared_names`], but keeps every declaration of a name.
+#[must_use]
+pub fn var_declared_identifiers<'a, N>(node: &'a N) -> Vec<Identifier>
+where
+    &'a N: Into<NodeRef<'a>>,
+{
+    let mut names = Vec::new();
+    let _ = VarDeclaredNamesVisitor(&mut names).visit(node.into());
+    names
+}
+
 /// Utility function that collects the top level lexicals of a statement list into `names`.
 ///
 /// This is equivalent to the [`TopLevelLexicallyDeclaredNames`][spec] syntax operation in the spec.
@@ -1122,7 +1187,7 @@ fn top_level_lexicals<T: IdentList>(stmts: &StatementList, names: &mut T) {
 /// This is equivalent to the [`TopLevelVarDeclaredNames`][spec] syntax operation in the spec.
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-static-semantics-toplevelvardeclarednames
-fn top_level_vars(stmts: &StatementList, names: &mut FxHashSet<Sym>) {
+fn top_level_vars<T: IdentList>(stmts: &StatementList, names: &mut T) {
     for stmt in stmts.statements() {
         match stmt {
             StatementListItem::Declaration(decl) => {
@@ -1452,7 +1517,8 @@ where
                 Statement::Block(node) => self.visit_block(node),
                 Statement::Var(_)
                 | Statement::Empty
-                | Statement::Debugger
+                | Statement::Debugger(_)
+                | Statement::Error(_)
                 | Statement::Expression(_)
                 | Statement::Return(_)
                 | Statement::Throw(_) => ControlFlow::Continue(()),
@@ -1734,7 +1800,7 @@ where
 
         fn visit_object_literal(
             &mut self,
-            node: &'ast crate::expression::literal::ObjectLiteral,
+            node: &'ast ObjectLiteral,
         ) -> ControlFlow<Self::BreakTy> {
             for pd in node.properties() {
                 if let PropertyDefinition::CoverInitializedName(..) = pd {
@@ -2093,7 +2159,8 @@ impl<'ast> Visitor<'ast> for VarScopedDeclarationsVisitor<'_> {
             Statement::Try(s) => self.visit(s),
             Statement::With(s) => self.visit(s),
             Statement::Empty
-            | Statement::Debugger
+            | Statement::Debugger(_)
+            | Statement::Error(_)
             | Statement::Expression(_)
             | Statement::Continue(_)
             | Statement::Break(_)
@@ -2564,3 +2631,251 @@ impl<'ast> Visitor<'ast> for ReturnsValueVisitor {
         ControlFlow::Continue(())
     }
 }
+
+/// Moves the positions of a node that was parsed before an edit of the source code to the
+/// positions it has after the edit.
+///
+/// This updates the spans and linear spans of all the nodes contained in `node` with
+/// [`Relocation`], so it should only be used with nodes that don't contain any of the edited
+/// text.
+pub fn relocate<'a, N>(node: N, relocation: Relocation)
+where
+    N: Into<NodeRefMut<'a>>,
+{
+    /// Relocates the `span` field of the visited nodes before visiting their children.
+    macro_rules! relocate_span {
+        ($($fn_name:ident: $type_name:ty),* $(,)?) => {
+            $(
+                fn $fn_name(&mut self, node: &'ast mut $type_name) -> ControlFlow<Self::BreakTy> {
+                    node.span = self.0.span(node.span);
+                    node.visit_with_mut(self)
+                }
+            )*
+        };
+    }
+
+    /// Relocates the `linear_span` field of the visited functions before visiting them.
+    macro_rules! relocate_function {
+        ($($fn_name:ident: $type_name:ty),* $(,)?) => {
+            $(
+                fn $fn_name(&mut self, node: &'ast mut $type_name) -> ControlFlow<Self::BreakTy> {
+                    node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+                    node.visit_with_mut(self)
+                }
+            )*
+        };
+    }
+
+    struct RelocateVisitor(Relocation);
+
+    impl<'ast> VisitorMut<'ast> for RelocateVisitor {
+        type BreakTy = Infallible;
+
+        relocate_span!(
+            visit_identifier_mut: Identifier,
+            visit_this_mut: This,
+            visit_reg_exp_literal_mut: RegExpLiteral,
+            visit_array_literal_mut: ArrayLiteral,
+            visit_object_literal_mut: ObjectLiteral,
+            visit_template_literal_mut: TemplateLiteral,
+            visit_spread_mut: Spread,
+            visit_call_mut: Call,
+            visit_super_call_mut: SuperCall,
+            visit_import_call_mut: ImportCall,
+            visit_optional_mut: Optional,
+            visit_optional_operation_mut: OptionalOperation,
+            visit_tagged_template_mut: TaggedTemplate,
+            visit_unary_mut: Unary,
+            visit_update_mut: Update,
+            visit_await_mut: Await,
+            visit_yield_mut: Yield,
+            visit_parenthesized_mut: Parenthesized,
+            visit_new_target_mut: NewTarget,
+            visit_import_meta_mut: ImportMeta,
+            visit_private_property_access_mut: PrivatePropertyAccess,
+            visit_super_property_access_mut: SuperPropertyAccess,
+            visit_jsx_element_mut: JsxElement,
+            visit_jsx_text_mut: JsxText,
+            visit_object_pattern_mut: ObjectPattern,
+            visit_array_pattern_mut: ArrayPattern,
+            visit_class_expression_mut: ClassExpression,
+        );
+
+        relocate_function!(
+            visit_function_declaration_mut: FunctionDeclaration,
+            visit_generator_declaration_mut: GeneratorDeclaration,
+            visit_async_function_declaration_mut: AsyncFunctionDeclaration,
+            visit_async_generator_declaration_mut: AsyncGeneratorDeclaration,
+            visit_object_method_definition_mut: ObjectMethodDefinition,
+        );
+
+        fn visit_generator_expression_mut(
+            &mut self,
+            node: &'ast mut GeneratorExpression,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_async_function_expression_mut(
+            &mut self,
+            node: &'ast mut AsyncFunctionExpression,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_async_generator_expression_mut(
+            &mut self,
+            node: &'ast mut AsyncGeneratorExpression,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_arrow_function_mut(
+            &mut self,
+            node: &'ast mut ArrowFunction,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_async_arrow_function_mut(
+            &mut self,
+            node: &'ast mut AsyncArrowFunction,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_function_expression_mut(
+            &mut self,
+            node: &'ast mut FunctionExpression,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span = node.linear_span.map(|span| self.0.linear_span(span));
+            node.visit_with_mut(self)
+        }
+
+        fn visit_literal_mut(&mut self, node: &'ast mut Literal) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            node.linear_span.0 = self.0.linear_span(node.linear_span.0);
+            ControlFlow::Continue(())
+        }
+
+        fn visit_private_name_mut(
+            &mut self,
+            node: &'ast mut PrivateName,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            ControlFlow::Continue(())
+        }
+
+        fn visit_property_name_mut(
+            &mut self,
+            node: &'ast mut PropertyName,
+        ) -> ControlFlow<Self::BreakTy> {
+            match node {
+                PropertyName::Literal(ident) => self.visit_identifier_mut(ident),
+                PropertyName::Computed(expr) => self.visit_expression_mut(expr),
+            }
+        }
+
+        fn visit_property_access_field_mut(
+            &mut self,
+            node: &'ast mut PropertyAccessField,
+        ) -> ControlFlow<Self::BreakTy> {
+            match node {
+                PropertyAccessField::Const(ident) => self.visit_identifier_mut(ident),
+                PropertyAccessField::Expr(expr) => self.visit_expression_mut(&mut *expr),
+            }
+        }
+
+        fn visit_class_element_mut(
+            &mut self,
+            node: &'ast mut ClassElement,
+        ) -> ControlFlow<Self::BreakTy> {
+            if let ClassElement::MethodDefinition(method) = node {
+                method.linear_span.0 = self.0.linear_span(method.linear_span.0);
+            }
+            node.visit_with_mut(self)
+        }
+
+        fn visit_function_body_mut(
+            &mut self,
+            node: &'ast mut FunctionBody,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.span = self.0.span(node.span);
+            self.visit_statement_list_mut(&mut node.statements)
+        }
+
+        fn visit_statement_list_mut(
+            &mut self,
+            node: &'ast mut StatementList,
+        ) -> ControlFlow<Self::BreakTy> {
+            node.linear_pos_end = self.0.linear_position(node.linear_pos_end);
+            node.visit_with_mut(self)
+        }
+
+        fn visit_statement_mut(&mut self, node: &'ast mut Statement) -> ControlFlow<Self::BreakTy> {
+            if let Statement::Debugger(span) | Statement::Error(span) = node {
+                *span = self.0.span(*span);
+            }
+            node.visit_with_mut(self)
+        }
+    }
+
+    let _ = RelocateVisitor(relocation).visit(node);
+}
+
+/// Numbers the tagged templates of `node` in the order they end in the source code.
+///
+/// The parser gives each tagged template a unique identifier, which counts the tagged templates
+/// that were parsed up to it. Since the inner tagged templates of a tagged template are parsed
+/// before it, this is the order of the ends of the tagged templates. The lower 32 bits of the
+/// identifiers, which identify the parser, are kept.
+pub fn number_tagged_templates<N>(node: &mut N)
+where
+    N: VisitWith,
+{
+    struct TemplateEnds(Vec<Position>);
+
+    impl<'ast> Visitor<'ast> for TemplateEnds {
+        type BreakTy = Infallible;
+
+        fn visit_tagged_template(
+            &mut self,
+            node: &'ast TaggedTemplate,
+        ) -> ControlFlow<Self::BreakTy> {
+            self.0.push(node.span.end());
+            node.visit_with(self)
+        }
+    }
+
+    struct NumberTemplates(Vec<Position>);
+
+    impl<'ast> VisitorMut<'ast> for NumberTemplates {
+        type BreakTy = Infallible;
+
+        fn visit_tagged_template_mut(
+            &mut self,
+            node: &'ast mut TaggedTemplate,
+        ) -> ControlFlow<Self::BreakTy> {
+            let index = self.0.partition_point(|end| *end < node.span.end()) as u64;
+            node.identifier = ((index + 1) << 32) | (node.identifier & u64::from(u32::MAX));
+            node.visit_with_mut(self)
+        }
+    }
+
+    let mut ends = TemplateEnds(Vec::new());
+    let _ = node.visit_with(&mut ends);
+    let mut ends = ends.0;
+    ends.sort_unstable();
+    let _ = node.visit_with_mut(&mut NumberTemplates(ends));
+}
@@ -111,7 +111,7 @@ impl VisitWith for Pattern {
 #[derive(Clone, Debug, PartialEq)]
 pub struct ObjectPattern {
     elements: Box<[ObjectPatternElement]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl ToInternedString for ObjectPattern {
@@ -204,7 +204,7 @@ impl VisitWith for ObjectPattern {
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArrayPattern {
     bindings: Box<[ArrayPatternElement]>,
-    span: Span,
+    pub(crate) span: Span,
 }
 
 impl ToInternedString for ArrayPattern {
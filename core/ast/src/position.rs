@@ -196,6 +196,17 @@ impl Span {
         let other = other.into();
         self.start <= other.start && self.end >= other.end
     }
+
+    /// Gets the smallest span that contains both this span and another span or position.
+    #[inline]
+    #[must_use]
+    pub fn union(self, other: impl Into<Self>) -> Self {
+        let other: Self = other.into();
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
 }
 
 impl From<Position> for Span {
@@ -413,11 +424,96 @@ impl PositionGroup {
     }
 }
 
+/// Moves the positions that come after an edit of the ECMAScript source code to where they are
+/// after the edit.
+///
+/// The relocation maps a position of the source text from before the edit, which is at or after
+/// the end of the edited text, to the same position of the source text after the edit. Positions
+/// that come before the `from` position are left untouched, so the relocation must only be used
+/// with positions that are not inside of the edited text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relocation {
+    from: PositionGroup,
+    to: PositionGroup,
+}
+
+impl Relocation {
+    /// Creates a new `Relocation` that moves the source text starting at `from` to `to`.
+    #[inline]
+    #[must_use]
+    pub const fn new(from: PositionGroup, to: PositionGroup) -> Self {
+        Self { from, to }
+    }
+
+    /// Gets the position where the moved source text started before the edit.
+    #[inline]
+    #[must_use]
+    pub const fn from(&self) -> PositionGroup {
+        self.from
+    }
+
+    /// Gets the position where the moved source text starts after the edit.
+    #[inline]
+    #[must_use]
+    pub const fn to(&self) -> PositionGroup {
+        self.to
+    }
+
+    /// Relocates a `Position`.
+    ///
+    /// Only the columns of the positions on the first line of the moved text change, since the
+    /// lines after it keep the same contents.
+    #[must_use]
+    pub fn position(&self, position: Position) -> Position {
+        let from = self.from.position();
+        let to = self.to.position();
+        if position < from {
+            position
+        } else if position.line_number() == from.line_number() {
+            Position::new(
+                to.line_number(),
+                position.column_number() - from.column_number() + to.column_number(),
+            )
+        } else {
+            Position::new(
+                position.line_number() - from.line_number() + to.line_number(),
+                position.column_number(),
+            )
+        }
+    }
+
+    /// Relocates a `LinearPosition`.
+    #[must_use]
+    pub fn linear_position(&self, position: LinearPosition) -> LinearPosition {
+        let from = self.from.linear_position().pos();
+        if position.pos() < from {
+            position
+        } else {
+            LinearPosition::new(position.pos() - from + self.to.linear_position().pos())
+        }
+    }
+
+    /// Relocates a `Span`.
+    #[must_use]
+    pub fn span(&self, span: Span) -> Span {
+        Span::new(self.position(span.start()), self.position(span.end()))
+    }
+
+    /// Relocates a `LinearSpan`.
+    #[must_use]
+    pub fn linear_span(&self, span: LinearSpan) -> LinearSpan {
+        LinearSpan::new(
+            self.linear_position(span.start()),
+            self.linear_position(span.end()),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::similar_names)]
     #![allow(unused_must_use)]
-    use super::{LinearPosition, LinearSpan, Position, Span};
+    use super::{LinearPosition, LinearSpan, Position, PositionGroup, Relocation, Span};
 
     /// Checks that we cannot create a position with 0 as the column.
     #[test]
@@ -686,6 +782,31 @@ mod tests {
         assert_eq!(span_ac.partial_cmp(&span_bd), None);
     }
 
+    /// Checks that the union of spans contains both of them.
+    #[test]
+    fn union() {
+        let a = Position::new(1, 5);
+        let b = Position::new(1, 8);
+        let c = Position::new(3, 1);
+        let d = Position::new(3, 4);
+
+        let span_ab = Span::new(a, b);
+        let span_ad = Span::new(a, d);
+        let span_bc = Span::new(b, c);
+        let span_cd = Span::new(c, d);
+        let span_ac = Span::new(a, c);
+        let span_bd = Span::new(b, d);
+
+        assert_eq!(span_bd.union(a), span_ad);
+        assert_eq!(span_ab.union(a), span_ab);
+        assert_eq!(span_bd.union(span_ac), span_ad);
+        assert_eq!(span_ac.union(span_bd), span_ad);
+        assert_eq!(span_ac.union(b), span_ac);
+        assert_eq!(span_bc.union(span_ab), span_ac);
+        assert_eq!(span_cd.union(a), span_ad);
+        assert_eq!(span_cd.union(span_bc), span_bd);
+    }
+
     /// Checks that the ordering of linear spans is correct.
     #[test]
     fn linear_union() {
@@ -713,6 +834,54 @@ mod tests {
         assert_eq!(span_cd.union(a), span_ad);
         assert_eq!(span_cd.union(span_bc), span_bd);
     }
+
+    /// Checks that the positions after an edit are moved to their new place.
+    #[test]
+    fn relocation() {
+        // `a;\nb; c;` edited to `a;\nxx\nyyy b; c;`.
+        let relocation = Relocation::new(
+            PositionGroup::new(Position::new(2, 1), LinearPosition::new(3)),
+            PositionGroup::new(Position::new(3, 5), LinearPosition::new(10)),
+        );
+
+        assert_eq!(
+            relocation.position(Position::new(1, 2)),
+            Position::new(1, 2)
+        );
+        assert_eq!(
+            relocation.position(Position::new(2, 1)),
+            Position::new(3, 5)
+        );
+        assert_eq!(
+            relocation.position(Position::new(2, 4)),
+            Position::new(3, 8)
+        );
+        assert_eq!(
+            relocation.position(Position::new(4, 2)),
+            Position::new(5, 2)
+        );
+
+        assert_eq!(
+            relocation.linear_position(LinearPosition::new(2)),
+            LinearPosition::new(2)
+        );
+        assert_eq!(
+            relocation.linear_position(LinearPosition::new(6)),
+            LinearPosition::new(13)
+        );
+
+        assert_eq!(
+            relocation.span(Span::new((1, 1), (2, 2))),
+            Span::new((1, 1), (3, 6))
+        );
+        assert_eq!(
+            relocation.linear_span(LinearSpan::new(
+                LinearPosition::new(3),
+                LinearPosition::new(5)
+            )),
+            LinearSpan::new(LinearPosition::new(10), LinearPosition::new(12))
+        );
+    }
 }
 
 // TODO: union Span & LinearSpan into `SpanBase<T>` and then:
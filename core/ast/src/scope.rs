@@ -64,6 +64,74 @@ impl Binding {
     }
 }
 
+/// The state of a single binding of a [`Scope`].
+///
+/// This is returned by [`Scope::bindings`] and consumed by [`Scope::from_bindings`], to allow
+/// storing scopes outside of the AST, like in a bytecode cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeBinding {
+    name: JsString,
+    index: u32,
+    flags: u8,
+}
+
+impl ScopeBinding {
+    /// Creates a new `ScopeBinding` from its parts.
+    ///
+    /// `flags` must be a value previously returned by [`ScopeBinding::flags`]; unknown flags are
+    /// ignored.
+    #[must_use]
+    pub const fn new(name: JsString, index: u32, flags: u8) -> Self {
+        Self { name, index, flags }
+    }
+
+    /// Returns the name of the binding.
+    #[must_use]
+    pub const fn name(&self) -> &JsString {
+        &self.name
+    }
+
+    /// Returns the index of the binding in its environment.
+    #[must_use]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the opaque flags of the binding.
+    #[must_use]
+    pub const fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns `true` if the binding is mutable.
+    #[must_use]
+    pub fn is_mutable(&self) -> bool {
+        BindingFlags::from_bits_truncate(self.flags).is_mutable()
+    }
+
+    /// Returns `true` if the binding is a strict immutable binding.
+    #[must_use]
+    pub fn is_strict(&self) -> bool {
+        BindingFlags::from_bits_truncate(self.flags).is_strict()
+    }
+
+    /// Returns `true` if the binding is stored in a runtime environment instead of a register.
+    #[must_use]
+    pub fn escapes(&self) -> bool {
+        BindingFlags::from_bits_truncate(self.flags).escapes()
+    }
+}
+
+impl From<&Binding> for ScopeBinding {
+    fn from(binding: &Binding) -> Self {
+        Self {
+            name: binding.name.clone(),
+            index: binding.index,
+            flags: binding.flags.bits(),
+        }
+    }
+}
+
 /// A scope maps bound identifiers to their binding positions.
 ///
 /// It can be either a global scope or a function scope or a declarative scope.
@@ -142,6 +210,39 @@ impl Scope {
         }
     }
 
+    /// Creates a new scope with the state of a scope that was previously analyzed.
+    ///
+    /// The state can be obtained with [`Scope::scope_index`], [`Scope::is_function`],
+    /// [`Scope::escaped_this`] and [`Scope::bindings`].
+    #[must_use]
+    pub fn from_bindings(
+        parent: Self,
+        function: bool,
+        index: u32,
+        this_escaped: bool,
+        bindings: Vec<ScopeBinding>,
+    ) -> Self {
+        let bindings = bindings
+            .into_iter()
+            .map(|binding| Binding {
+                name: binding.name,
+                index: binding.index,
+                flags: BindingFlags::from_bits_truncate(binding.flags),
+            })
+            .collect();
+        Self {
+            inner: Rc::new(Inner {
+                unique_id: parent.inner.context.next_unique_id(),
+                index: Cell::new(index),
+                bindings: RefCell::new(bindings),
+                function,
+                this_escaped: Cell::new(this_escaped),
+                context: parent.inner.context.clone(),
+                outer: Some(parent),
+            }),
+        }
+    }
+
     /// Checks if the scope has only local bindings.
     #[must_use]
     pub fn all_bindings_local(&self) -> bool {
@@ -408,7 +509,7 @@ impl Scope {
 
     /// Crate an immutable binding.
     #[allow(clippy::cast_possible_truncation)]
-    pub(crate) fn create_immutable_binding(&self, name: JsString, strict: bool) {
+    pub fn create_immutable_binding(&self, name: JsString, strict: bool) {
         let mut bindings = self.inner.bindings.borrow_mut();
         if bindings.iter().any(|b| b.name == name) {
             return;
@@ -525,6 +626,17 @@ impl Scope {
     pub fn unique_id(&self) -> u32 {
         self.inner.unique_id
     }
+
+    /// Returns the bindings of this scope, in declaration order.
+    #[must_use]
+    pub fn bindings(&self) -> Vec<ScopeBinding> {
+        self.inner
+            .bindings
+            .borrow()
+            .iter()
+            .map(ScopeBinding::from)
+            .collect()
+    }
 }
 
 /// Additional state that all Scopes of a single AST share for bookkeeping.
@@ -625,6 +737,27 @@ impl BindingLocator {
         }
     }
 
+    /// Creates a binding locator from its parts.
+    ///
+    /// The parts can be obtained with [`BindingLocator::name`], [`BindingLocator::scope`],
+    /// [`BindingLocator::binding_index`] and [`BindingLocator::unique_scope_id`].
+    #[must_use]
+    pub fn from_parts(
+        name: JsString,
+        scope: BindingLocatorScope,
+        binding_index: u32,
+        unique_scope_id: u32,
+    ) -> Self {
+        let mut locator = Self {
+            name,
+            scope: 0,
+            binding_index,
+            unique_scope_id,
+        };
+        locator.set_scope(scope);
+        locator
+    }
+
     /// Creates a binding locator that indicates that the binding is on the global object.
     pub(super) const fn global(name: JsString) -> Self {
         Self {
@@ -2202,7 +2202,11 @@ fn function_declaration_instantiation(
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-source-text-module-record-initialize-environment
 fn module_instantiation(module: &Module, env: &Scope, interner: &Interner) {
-    for entry in module.items().import_entries() {
+    let import_entries = module.items().jsx_runtime_import_entries();
+    for entry in import_entries
+        .into_iter()
+        .chain(module.items().import_entries())
+    {
         let local_name = entry.local_name().to_js_string(interner);
         env.create_immutable_binding(local_name, true);
     }
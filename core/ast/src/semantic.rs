@@ -0,0 +1,1013 @@
+//! A read-only semantic model of a script or module, built from the output of the scope analysis.
+//!
+//! The [`SemanticModel`] links every identifier of an analyzed AST to the binding it declares or
+//! refers to, which answers questions like "where is this binding declared", "what are all the
+//! references to this binding", "which bindings does this closure capture" or "is this
+//! identifier global". Identifiers are looked up by their [`Span`], and closures by the span of
+//! their [`FunctionBody`].
+//!
+//! The model must be built from an AST whose scopes were already analyzed, like the ones returned
+//! by the parser.
+
+use crate::{
+    Expression, Module, Script, Span, Spanned, ToJsString,
+    declaration::{Binding, ImportDeclaration, ImportKind, LexicalDeclaration, VarDeclaration},
+    expression::{
+        Identifier,
+        literal::{ObjectMethodDefinition, PropertyDefinition},
+        operator::{
+            Assign, Update,
+            assign::{AssignOp, AssignTarget},
+            update::UpdateTarget,
+        },
+    },
+    function::{
+        ArrowFunction, AsyncArrowFunction, AsyncFunctionDeclaration, AsyncFunctionExpression,
+        AsyncGeneratorDeclaration, AsyncGeneratorExpression, ClassDeclaration, ClassElement,
+        ClassElementName, ClassExpression, FormalParameterList, FunctionBody, FunctionDeclaration,
+        FunctionExpression, GeneratorDeclaration, GeneratorExpression,
+    },
+    operations::{ContainsSymbol, contains},
+    pattern::{ArrayPatternElement, ObjectPatternElement},
+    scope::{FunctionScopes, Scope},
+    statement::{
+        Block, Catch, ForInLoop, ForLoop, ForOfLoop, Switch, With,
+        iteration::{ForLoopInitializer, IterableLoopInitializer},
+    },
+    visitor::{VisitWith, Visitor},
+};
+use boa_interner::{Interner, Sym};
+use rustc_hash::FxHashMap;
+use std::ops::ControlFlow;
+
+/// The identifier of a binding in a [`SemanticModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BindingId(usize);
+
+/// The kind of declaration that creates a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    /// A `var` declaration.
+    Var,
+
+    /// A `let` declaration.
+    Let,
+
+    /// A `const` declaration.
+    Const,
+
+    /// A `using` or `await using` declaration.
+    Using,
+
+    /// A function or generator declaration, or the name of a function expression.
+    Function,
+
+    /// A class declaration, or the name of a class expression.
+    Class,
+
+    /// A parameter of a function.
+    Parameter,
+
+    /// The parameter of a `catch` clause.
+    CatchParameter,
+
+    /// An imported binding.
+    Import,
+}
+
+/// A declaration of a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BindingDeclaration {
+    span: Span,
+    kind: BindingKind,
+}
+
+impl BindingDeclaration {
+    /// Gets the kind of the declaration.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> BindingKind {
+        self.kind
+    }
+}
+
+impl Spanned for BindingDeclaration {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A binding of a [`SemanticModel`], with its declarations and references.
+///
+/// Undeclared names, like the properties of the global object, also get a binding without
+/// declarations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingInfo {
+    name: Sym,
+    global: bool,
+    declarations: Vec<BindingDeclaration>,
+    references: Vec<usize>,
+}
+
+impl BindingInfo {
+    /// Gets the name of the binding.
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> Sym {
+        self.name
+    }
+
+    /// Returns `true` if the binding is in the global scope or a property of the global object.
+    #[inline]
+    #[must_use]
+    pub const fn is_global(&self) -> bool {
+        self.global
+    }
+
+    /// Returns `true` if the binding is declared in the source.
+    #[inline]
+    #[must_use]
+    pub fn is_declared(&self) -> bool {
+        !self.declarations.is_empty()
+    }
+
+    /// Gets the declarations of the binding, in source order.
+    #[inline]
+    #[must_use]
+    pub fn declarations(&self) -> &[BindingDeclaration] {
+        &self.declarations
+    }
+}
+
+/// How a [`Reference`] accesses its binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// The binding is read, like in `a + 1`.
+    Read,
+
+    /// The binding is assigned, like in `a = 1`.
+    Write,
+
+    /// The binding is read and assigned, like in `a += 1` or `a++`.
+    ReadWrite,
+}
+
+/// A reference to a binding, by an identifier outside of a declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reference {
+    span: Span,
+    binding: BindingId,
+    kind: ReferenceKind,
+    dynamic: bool,
+}
+
+impl Reference {
+    /// Gets the binding the reference resolves to.
+    #[inline]
+    #[must_use]
+    pub const fn binding(&self) -> BindingId {
+        self.binding
+    }
+
+    /// Gets how the reference accesses its binding.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> ReferenceKind {
+        self.kind
+    }
+
+    /// Returns `true` if the reference is inside a `with` statement or a scope with a direct
+    /// `eval`, where it could resolve to another binding at runtime.
+    #[inline]
+    #[must_use]
+    pub const fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+}
+
+impl Spanned for Reference {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A function, method, arrow function or class static block, with the bindings it captures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Closure {
+    span: Span,
+    captures: Vec<BindingId>,
+}
+
+impl Closure {
+    /// Gets the bindings of the enclosing functions that the closure references, including
+    /// through nested closures.
+    ///
+    /// Global bindings are not captured.
+    #[inline]
+    #[must_use]
+    pub fn captures(&self) -> &[BindingId] {
+        &self.captures
+    }
+}
+
+impl Spanned for Closure {
+    /// Gets the span of the body of the closure.
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A read-only semantic model of a script or module.
+///
+/// See the [module level documentation][self] for more information.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SemanticModel {
+    bindings: Vec<BindingInfo>,
+    references: Vec<Reference>,
+    closures: Vec<Closure>,
+    identifiers: FxHashMap<Span, BindingId>,
+    bodies: FxHashMap<Span, usize>,
+}
+
+impl SemanticModel {
+    /// Builds the semantic model of a script that was analyzed in the given global scope.
+    #[must_use]
+    pub fn script(script: &Script, scope: &Scope, interner: &Interner) -> Self {
+        let mut builder = Builder::new(scope.clone(), interner);
+        let _ = builder.visit_script(script);
+        builder.model
+    }
+
+    /// Builds the semantic model of an analyzed module.
+    #[must_use]
+    pub fn module(module: &Module, interner: &Interner) -> Self {
+        let mut builder = Builder::new(module.scope.clone(), interner);
+        let _ = builder.visit_module_item_list(module.items());
+        builder.model
+    }
+
+    /// Gets all the bindings of the model.
+    #[inline]
+    #[must_use]
+    pub fn bindings(&self) -> &[BindingInfo] {
+        &self.bindings
+    }
+
+    /// Gets the binding with the given identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the identifier is from another model.
+    #[inline]
+    #[must_use]
+    pub fn binding(&self, id: BindingId) -> &BindingInfo {
+        &self.bindings[id.0]
+    }
+
+    /// Gets the binding declared or referenced by the identifier at the given span.
+    #[must_use]
+    pub fn binding_at(&self, span: Span) -> Option<BindingId> {
+        self.identifiers.get(&span).copied()
+    }
+
+    /// Gets the binding declared or referenced by an identifier of the AST.
+    #[must_use]
+    pub fn binding_of(&self, identifier: &Identifier) -> Option<BindingId> {
+        self.binding_at(identifier.span())
+    }
+
+    /// Gets all the references of the model.
+    #[inline]
+    #[must_use]
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Gets the references to a binding.
+    pub fn references_to(&self, id: BindingId) -> impl Iterator<Item = &Reference> {
+        self.binding(id)
+            .references
+            .iter()
+            .map(|index| &self.references[*index])
+    }
+
+    /// Returns `true` if the identifier at the given span declares or references a global
+    /// binding.
+    #[must_use]
+    pub fn is_global(&self, span: Span) -> bool {
+        self.binding_at(span)
+            .is_some_and(|id| self.binding(id).is_global())
+    }
+
+    /// Gets all the closures of the model, outer closures first.
+    #[inline]
+    #[must_use]
+    pub fn closures(&self) -> &[Closure] {
+        &self.closures
+    }
+
+    /// Gets the closure with the given body.
+    #[must_use]
+    pub fn closure(&self, body: &FunctionBody) -> Option<&Closure> {
+        self.bodies
+            .get(&body.span())
+            .map(|index| &self.closures[*index])
+    }
+}
+
+/// A binding, identified by the unique identifier of its scope, or `None` for the properties of
+/// the global object.
+type Key = (Option<u32>, Sym);
+
+/// Builds a [`SemanticModel`], following the scopes of the AST like the scope analysis.
+struct Builder<'interner> {
+    model: SemanticModel,
+    interner: &'interner Interner,
+    scope: Scope,
+    keys: FxHashMap<Key, BindingId>,
+    binding_keys: Vec<Key>,
+    /// Bindings of the scopes of class names, which are the same as the bindings of the
+    /// enclosing scope for the model.
+    aliases: FxHashMap<Key, Key>,
+    /// The open closures, with the unique identifiers of their function scopes.
+    closures: Vec<(usize, u32)>,
+    /// The kind of binding that identifiers in patterns declare, if any.
+    declaring: Option<BindingKind>,
+    dynamic: bool,
+}
+
+impl<'interner> Builder<'interner> {
+    fn new(scope: Scope, interner: &'interner Interner) -> Self {
+        Self {
+            model: SemanticModel::default(),
+            interner,
+            scope,
+            keys: FxHashMap::default(),
+            binding_keys: Vec::new(),
+            aliases: FxHashMap::default(),
+            closures: Vec::new(),
+            declaring: None,
+            dynamic: false,
+        }
+    }
+
+    /// Resolves a name from the current scope, returning its binding and the unique identifiers
+    /// of the scopes that were searched before finding it.
+    fn resolve(&mut self, name: Sym) -> (BindingId, Vec<u32>) {
+        let js_name = name.to_js_string(self.interner);
+        let mut searched = Vec::new();
+        let mut current = Some(&self.scope);
+        let found = loop {
+            match current {
+                Some(scope) if scope.has_binding(&js_name) => break Some(scope),
+                Some(scope) => {
+                    searched.push(scope.unique_id());
+                    current = scope.outer();
+                }
+                None => break None,
+            }
+        };
+        let global = found.is_none_or(Scope::is_global);
+        let mut key = (found.map(Scope::unique_id), name);
+        if let Some(alias) = self.aliases.get(&key) {
+            key = *alias;
+        }
+
+        let id = *self.keys.entry(key).or_insert_with(|| {
+            self.model.bindings.push(BindingInfo {
+                name,
+                global,
+                declarations: Vec::new(),
+                references: Vec::new(),
+            });
+            self.binding_keys.push(key);
+            BindingId(self.model.bindings.len() - 1)
+        });
+        (id, searched)
+    }
+
+    fn declare(&mut self, identifier: Identifier, kind: BindingKind) -> BindingId {
+        let (id, _) = self.resolve(identifier.sym());
+        let span = identifier.span();
+        self.model.bindings[id.0]
+            .declarations
+            .push(BindingDeclaration { span, kind });
+        self.model.identifiers.insert(span, id);
+        id
+    }
+
+    fn reference(&mut self, identifier: Identifier, kind: ReferenceKind) {
+        let (id, searched) = self.resolve(identifier.sym());
+        let span = identifier.span();
+        self.model.bindings[id.0]
+            .references
+            .push(self.model.references.len());
+        self.model.references.push(Reference {
+            span,
+            binding: id,
+            kind,
+            dynamic: self.dynamic,
+        });
+        self.model.identifiers.insert(span, id);
+
+        if !self.model.bindings[id.0].global {
+            for (closure, function_scope) in &self.closures {
+                let captures = &mut self.model.closures[*closure].captures;
+                if searched.contains(function_scope) && !captures.contains(&id) {
+                    captures.push(id);
+                }
+            }
+        }
+    }
+
+    /// Declares the identifier of a pattern, or assigns it if the pattern is not a declaration.
+    fn target(&mut self, identifier: Identifier) {
+        if let Some(kind) = self.declaring {
+            self.declare(identifier, kind);
+        } else {
+            self.reference(identifier, ReferenceKind::Write);
+        }
+    }
+
+    fn declaring<F>(&mut self, kind: Option<BindingKind>, f: F) -> ControlFlow<()>
+    where
+        F: FnOnce(&mut Self) -> ControlFlow<()>,
+    {
+        let declaring = std::mem::replace(&mut self.declaring, kind);
+        let flow = f(self);
+        self.declaring = declaring;
+        flow
+    }
+
+    fn in_scope<F>(&mut self, scope: Option<&Scope>, direct_eval: bool, f: F) -> ControlFlow<()>
+    where
+        F: FnOnce(&mut Self) -> ControlFlow<()>,
+    {
+        let dynamic = self.dynamic;
+        self.dynamic |= direct_eval;
+        let outer = scope.map(|scope| std::mem::replace(&mut self.scope, scope.clone()));
+        let flow = f(self);
+        if let Some(outer) = outer {
+            self.scope = outer;
+        }
+        self.dynamic = dynamic;
+        flow
+    }
+
+    /// Visits a named function expression, whose name is declared in its own scope.
+    fn function_expression(
+        &mut self,
+        name: Option<Identifier>,
+        name_scope: Option<&Scope>,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+        scopes: &FunctionScopes,
+        contains_direct_eval: bool,
+    ) -> ControlFlow<()> {
+        self.in_scope(name_scope, false, |builder| {
+            if let (Some(name), Some(_)) = (name, name_scope) {
+                builder.declare(name, BindingKind::Function);
+            }
+            builder.function_like(parameters, body, scopes, contains_direct_eval)
+        })
+    }
+
+    fn function_like(
+        &mut self,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+        scopes: &FunctionScopes,
+        contains_direct_eval: bool,
+    ) -> ControlFlow<()> {
+        self.model.closures.push(Closure {
+            span: body.span(),
+            captures: Vec::new(),
+        });
+        let closure = self.model.closures.len() - 1;
+        self.model.bodies.insert(body.span(), closure);
+        self.closures
+            .push((closure, scopes.function_scope().unique_id()));
+
+        let parameter_scope = scopes.parameter_scope();
+        self.in_scope(Some(&parameter_scope), contains_direct_eval, |builder| {
+            builder.declaring(Some(BindingKind::Parameter), |builder| {
+                builder.visit_formal_parameter_list(parameters)
+            })
+        })?;
+        let body_scope = scopes.body_scope();
+        self.in_scope(Some(&body_scope), contains_direct_eval, |builder| {
+            builder.visit_function_body(body)
+        })?;
+
+        self.closures.pop();
+        ControlFlow::Continue(())
+    }
+
+    fn class(
+        &mut self,
+        name_scope: Option<&Scope>,
+        super_ref: Option<&Expression>,
+        constructor: Option<&FunctionExpression>,
+        elements: &[ClassElement],
+    ) -> ControlFlow<()> {
+        self.in_scope(name_scope, false, |builder| {
+            if let Some(super_ref) = super_ref {
+                builder.visit_expression(super_ref)?;
+            }
+            if let Some(constructor) = constructor {
+                builder.visit_function_expression(constructor)?;
+            }
+            for element in elements {
+                builder.visit_class_element(element)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    /// Declares the name of a class in the enclosing scope, which is also the binding of the
+    /// name in the class scope.
+    fn class_name(&mut self, name: Identifier, name_scope: &Scope) {
+        let id = self.declare(name, BindingKind::Class);
+        self.aliases.insert(
+            (Some(name_scope.unique_id()), name.sym()),
+            self.binding_keys[id.0],
+        );
+    }
+}
+
+impl<'ast> Visitor<'ast> for Builder<'_> {
+    type BreakTy = ();
+
+    // Identifiers are only bindings in the positions handled below, and are otherwise labels or
+    // property names.
+    fn visit_identifier(&mut self, _node: &'ast Identifier) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expression(&mut self, node: &'ast Expression) -> ControlFlow<Self::BreakTy> {
+        self.declaring(None, |builder| {
+            if let Expression::Identifier(identifier) = node {
+                builder.reference(*identifier, ReferenceKind::Read);
+                ControlFlow::Continue(())
+            } else {
+                node.visit_with(builder)
+            }
+        })
+    }
+
+    fn visit_assign(&mut self, node: &'ast Assign) -> ControlFlow<Self::BreakTy> {
+        match node.lhs() {
+            AssignTarget::Identifier(identifier) => {
+                let kind = if node.op() == AssignOp::Assign {
+                    ReferenceKind::Write
+                } else {
+                    ReferenceKind::ReadWrite
+                };
+                self.reference(*identifier, kind);
+            }
+            AssignTarget::Access(access) => self.visit_property_access(access)?,
+            AssignTarget::Pattern(pattern) => {
+                self.declaring(None, |builder| builder.visit_pattern(pattern))?;
+            }
+        }
+        self.visit_expression(node.rhs())
+    }
+
+    fn visit_update(&mut self, node: &'ast Update) -> ControlFlow<Self::BreakTy> {
+        match node.target() {
+            UpdateTarget::Identifier(identifier) => {
+                self.reference(*identifier, ReferenceKind::ReadWrite);
+                ControlFlow::Continue(())
+            }
+            UpdateTarget::PropertyAccess(access) => self.visit_property_access(access),
+        }
+    }
+
+    fn visit_property_definition(
+        &mut self,
+        node: &'ast PropertyDefinition,
+    ) -> ControlFlow<Self::BreakTy> {
+        if let PropertyDefinition::IdentifierReference(identifier) = node {
+            self.reference(*identifier, ReferenceKind::Read);
+            return ControlFlow::Continue(());
+        }
+        node.visit_with(self)
+    }
+
+    fn visit_binding(&mut self, node: &'ast Binding) -> ControlFlow<Self::BreakTy> {
+        match node {
+            Binding::Identifier(identifier) => {
+                self.target(*identifier);
+                ControlFlow::Continue(())
+            }
+            Binding::Pattern(pattern) => self.visit_pattern(pattern),
+        }
+    }
+
+    fn visit_object_pattern_element(
+        &mut self,
+        node: &'ast ObjectPatternElement,
+    ) -> ControlFlow<Self::BreakTy> {
+        match node {
+            ObjectPatternElement::SingleName {
+                name,
+                ident,
+                default_init,
+            } => {
+                self.visit_property_name(name)?;
+                self.target(*ident);
+                if let Some(init) = default_init {
+                    self.visit_expression(init)?;
+                }
+                ControlFlow::Continue(())
+            }
+            ObjectPatternElement::RestProperty { ident } => {
+                self.target(*ident);
+                ControlFlow::Continue(())
+            }
+            _ => node.visit_with(self),
+        }
+    }
+
+    fn visit_array_pattern_element(
+        &mut self,
+        node: &'ast ArrayPatternElement,
+    ) -> ControlFlow<Self::BreakTy> {
+        match node {
+            ArrayPatternElement::SingleName {
+                ident,
+                default_init,
+            } => {
+                self.target(*ident);
+                if let Some(init) = default_init {
+                    self.visit_expression(init)?;
+                }
+                ControlFlow::Continue(())
+            }
+            ArrayPatternElement::SingleNameRest { ident } => {
+                self.target(*ident);
+                ControlFlow::Continue(())
+            }
+            _ => node.visit_with(self),
+        }
+    }
+
+    fn visit_var_declaration(&mut self, node: &'ast VarDeclaration) -> ControlFlow<Self::BreakTy> {
+        self.declaring(Some(BindingKind::Var), |builder| node.visit_with(builder))
+    }
+
+    fn visit_lexical_declaration(
+        &mut self,
+        node: &'ast LexicalDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        let kind = match node {
+            LexicalDeclaration::Const(_) => BindingKind::Const,
+            LexicalDeclaration::Let(_) => BindingKind::Let,
+            LexicalDeclaration::Using(_) | LexicalDeclaration::AwaitUsing(_) => BindingKind::Using,
+        };
+        self.declaring(Some(kind), |builder| node.visit_with(builder))
+    }
+
+    fn visit_import_declaration(
+        &mut self,
+        node: &'ast ImportDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        if let Some(default) = node.default() {
+            self.declare(default, BindingKind::Import);
+        }
+        match node.kind() {
+            ImportKind::DefaultOrUnnamed => {}
+            ImportKind::Namespaced { binding } => {
+                self.declare(*binding, BindingKind::Import);
+            }
+            ImportKind::Named { names } => {
+                for name in names {
+                    self.declare(name.binding(), BindingKind::Import);
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_block(&mut self, node: &'ast Block) -> ControlFlow<Self::BreakTy> {
+        self.in_scope(node.scope.as_ref(), node.contains_direct_eval, |builder| {
+            builder.visit_statement_list(&node.statements)
+        })
+    }
+
+    fn visit_switch(&mut self, node: &'ast Switch) -> ControlFlow<Self::BreakTy> {
+        self.visit_expression(&node.val)?;
+        self.in_scope(node.scope.as_ref(), node.contains_direct_eval, |builder| {
+            for case in &*node.cases {
+                builder.visit_case(case)?;
+            }
+            ControlFlow::Continue(())
+        })
+    }
+
+    fn visit_with(&mut self, node: &'ast With) -> ControlFlow<Self::BreakTy> {
+        self.visit_expression(&node.expression)?;
+        self.in_scope(Some(&node.scope), true, |builder| {
+            builder.visit_statement(&node.statement)
+        })
+    }
+
+    fn visit_catch(&mut self, node: &'ast Catch) -> ControlFlow<Self::BreakTy> {
+        self.in_scope(Some(&node.scope), node.contains_direct_eval, |builder| {
+            if let Some(parameter) = &node.parameter {
+                builder.declaring(Some(BindingKind::CatchParameter), |builder| {
+                    builder.visit_binding(parameter)
+                })?;
+            }
+            builder.visit_block(&node.block)
+        })
+    }
+
+    fn visit_for_loop(&mut self, node: &'ast ForLoop) -> ControlFlow<Self::BreakTy> {
+        let scope = match &node.inner.init {
+            Some(ForLoopInitializer::Lexical(declaration)) => Some(&declaration.scope),
+            _ => None,
+        };
+        let node = &node.inner;
+        self.in_scope(scope, node.contains_direct_eval, |builder| {
+            if let Some(init) = &node.init {
+                builder.visit_for_loop_initializer(init)?;
+            }
+            if let Some(condition) = &node.condition {
+                builder.visit_expression(condition)?;
+            }
+            if let Some(final_expr) = &node.final_expr {
+                builder.visit_expression(final_expr)?;
+            }
+            builder.visit_statement(&node.body)
+        })
+    }
+
+    fn visit_for_in_loop(&mut self, node: &'ast ForInLoop) -> ControlFlow<Self::BreakTy> {
+        self.in_scope(
+            node.target_scope.as_ref(),
+            node.target_contains_direct_eval,
+            |builder| builder.visit_expression(&node.target),
+        )?;
+        self.in_scope(node.scope.as_ref(), node.contains_direct_eval, |builder| {
+            builder.visit_iterable_loop_initializer(&node.initializer)?;
+            builder.visit_statement(&node.body)
+        })
+    }
+
+    fn visit_for_of_loop(&mut self, node: &'ast ForOfLoop) -> ControlFlow<Self::BreakTy> {
+        self.in_scope(
+            node.iterable_scope.as_ref(),
+            node.iterable_contains_direct_eval,
+            |builder| builder.visit_expression(&node.iterable),
+        )?;
+        self.in_scope(node.scope.as_ref(), node.contains_direct_eval, |builder| {
+            builder.visit_iterable_loop_initializer(&node.init)?;
+            builder.visit_statement(&node.body)
+        })
+    }
+
+    fn visit_iterable_loop_initializer(
+        &mut self,
+        node: &'ast IterableLoopInitializer,
+    ) -> ControlFlow<Self::BreakTy> {
+        let kind = match node {
+            IterableLoopInitializer::Identifier(identifier) => {
+                self.reference(*identifier, ReferenceKind::Write);
+                return ControlFlow::Continue(());
+            }
+            IterableLoopInitializer::Access(_) | IterableLoopInitializer::Pattern(_) => None,
+            IterableLoopInitializer::Var(_) => Some(BindingKind::Var),
+            IterableLoopInitializer::Let(_) => Some(BindingKind::Let),
+            IterableLoopInitializer::Const(_) => Some(BindingKind::Const),
+        };
+        self.declaring(kind, |builder| node.visit_with(builder))
+    }
+
+    fn visit_function_declaration(
+        &mut self,
+        node: &'ast FunctionDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        if node.name().sym() != Sym::DEFAULT {
+            self.declare(node.name(), BindingKind::Function);
+        }
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_generator_declaration(
+        &mut self,
+        node: &'ast GeneratorDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        if node.name().sym() != Sym::DEFAULT {
+            self.declare(node.name(), BindingKind::Function);
+        }
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_async_function_declaration(
+        &mut self,
+        node: &'ast AsyncFunctionDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        if node.name().sym() != Sym::DEFAULT {
+            self.declare(node.name(), BindingKind::Function);
+        }
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_async_generator_declaration(
+        &mut self,
+        node: &'ast AsyncGeneratorDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        if node.name().sym() != Sym::DEFAULT {
+            self.declare(node.name(), BindingKind::Function);
+        }
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_function_expression(
+        &mut self,
+        node: &'ast FunctionExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.function_expression(
+            node.name.filter(|_| node.has_binding_identifier),
+            node.name_scope.as_ref(),
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_generator_expression(
+        &mut self,
+        node: &'ast GeneratorExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.function_expression(
+            node.name.filter(|_| node.has_binding_identifier),
+            node.name_scope.as_ref(),
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_async_function_expression(
+        &mut self,
+        node: &'ast AsyncFunctionExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.function_expression(
+            node.name.filter(|_| node.has_binding_identifier),
+            node.name_scope.as_ref(),
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_async_generator_expression(
+        &mut self,
+        node: &'ast AsyncGeneratorExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.function_expression(
+            node.name.filter(|_| node.has_binding_identifier),
+            node.name_scope.as_ref(),
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_arrow_function(&mut self, node: &'ast ArrowFunction) -> ControlFlow<Self::BreakTy> {
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_async_arrow_function(
+        &mut self,
+        node: &'ast AsyncArrowFunction,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+
+    fn visit_class_declaration(
+        &mut self,
+        node: &'ast ClassDeclaration,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.class_name(node.name(), &node.name_scope);
+        self.class(
+            Some(&node.name_scope),
+            node.super_ref.as_ref(),
+            node.constructor.as_ref(),
+            &node.elements,
+        )
+    }
+
+    fn visit_class_expression(
+        &mut self,
+        node: &'ast ClassExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.in_scope(node.name_scope.as_ref(), false, |builder| {
+            if let (Some(name), Some(_)) = (node.name, &node.name_scope) {
+                builder.declare(name, BindingKind::Class);
+            }
+            ControlFlow::Continue(())
+        })?;
+        self.class(
+            node.name_scope.as_ref(),
+            node.super_ref.as_ref(),
+            node.constructor.as_ref(),
+            &node.elements,
+        )
+    }
+
+    fn visit_class_element(&mut self, node: &'ast ClassElement) -> ControlFlow<Self::BreakTy> {
+        match node {
+            ClassElement::MethodDefinition(node) => {
+                if let ClassElementName::PropertyName(name) = node.name() {
+                    self.visit_property_name(name)?;
+                }
+                self.function_like(
+                    &node.parameters,
+                    &node.body,
+                    &node.scopes,
+                    node.contains_direct_eval,
+                )
+            }
+            ClassElement::FieldDefinition(field) | ClassElement::StaticFieldDefinition(field) => {
+                self.visit_property_name(&field.name)?;
+                if let Some(initializer) = &field.initializer {
+                    self.in_scope(Some(&field.scope), false, |builder| {
+                        builder.visit_expression(initializer)
+                    })?;
+                }
+                ControlFlow::Continue(())
+            }
+            ClassElement::PrivateFieldDefinition(field)
+            | ClassElement::PrivateStaticFieldDefinition(field) => {
+                if let Some(initializer) = &field.initializer {
+                    self.in_scope(Some(&field.scope), false, |builder| {
+                        builder.visit_expression(initializer)
+                    })?;
+                }
+                ControlFlow::Continue(())
+            }
+            ClassElement::StaticBlock(node) => {
+                let contains_direct_eval = contains(node.statements(), ContainsSymbol::DirectEval);
+                self.function_like(
+                    &FormalParameterList::default(),
+                    &node.body,
+                    &node.scopes,
+                    contains_direct_eval,
+                )
+            }
+        }
+    }
+
+    fn visit_object_method_definition(
+        &mut self,
+        node: &'ast ObjectMethodDefinition,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.visit_property_name(&node.name)?;
+        self.function_like(
+            &node.parameters,
+            &node.body,
+            &node.scopes,
+            node.contains_direct_eval,
+        )
+    }
+}
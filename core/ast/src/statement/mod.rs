@@ -34,14 +34,21 @@ use core::ops::ControlFlow;
 use crate::visitor::{VisitWith, Visitor, VisitorMut};
 use boa_interner::{Interner, ToIndentedString, ToInternedString};
 
-use super::{declaration::VarDeclaration, expression::Expression};
+use super::{Span, declaration::VarDeclaration, expression::Expression};
 
 /// The `Statement` Parse Node.
 ///
 /// See the [module level documentation][self] for more information.
+///
+/// Statements don't have spans of their own, so the variants that need one to be located, like
+/// [`Statement::Debugger`] and [`Statement::Error`], carry it themselves.
+///
+/// This enum is `#[non_exhaustive]`: new statements and tooling nodes can be added to it without
+/// a breaking change, so matches outside of this crate need a wildcard arm.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Statement {
     /// See [`Block`].
     Block(Block),
@@ -108,7 +115,9 @@ pub enum Statement {
 
     /// A `debugger` statement.
     ///
-    /// The debugger statement invokes any available debugging functionality.
+    /// The debugger statement invokes any available debugging functionality. Since statements
+    /// don't have spans of their own, it keeps the span of the `debugger` keyword, which is where
+    /// a debugger pauses.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -116,7 +125,13 @@ pub enum Statement {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-debugger-statement
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/debugger
-    Debugger,
+    Debugger(Span),
+
+    /// A piece of source code that could not be parsed.
+    ///
+    /// Only the parser in its error-recovering mode produces this node, in place of the statements
+    /// and declarations that contained a syntax error. The span covers the skipped source code.
+    Error(Span),
 }
 
 impl Statement {
@@ -146,7 +161,8 @@ impl Statement {
             Self::Throw(throw) => throw.to_interned_string(interner),
             Self::Try(try_catch) => return try_catch.to_indented_string(interner, indentation),
             Self::With(with) => return with.to_interned_string(interner),
-            Self::Debugger => "debugger".to_owned(),
+            Self::Debugger(_) => "debugger".to_owned(),
+            Self::Error(_) => "/* error */".to_owned(),
         };
         s.push(';');
         s
@@ -208,7 +224,7 @@ impl VisitWith for Statement {
         match self {
             Self::Block(b) => visitor.visit_block(b),
             Self::Var(v) => visitor.visit_var_declaration(v),
-            Self::Empty | Self::Debugger => {
+            Self::Empty | Self::Debugger(_) | Self::Error(_) => {
                 // do nothing; there is nothing to visit here
                 ControlFlow::Continue(())
             }
@@ -237,7 +253,7 @@ impl VisitWith for Statement {
         match self {
             Self::Block(b) => visitor.visit_block_mut(b),
             Self::Var(v) => visitor.visit_var_declaration_mut(v),
-            Self::Empty | Self::Debugger => {
+            Self::Empty | Self::Debugger(_) | Self::Error(_) => {
                 // do nothing; there is nothing to visit here
                 ControlFlow::Continue(())
             }
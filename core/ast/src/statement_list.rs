@@ -8,7 +8,7 @@ use crate::{
 };
 use boa_interner::{Interner, ToIndentedString};
 use core::ops::ControlFlow;
-use std::ops::Deref;
+use std::{ops::Deref, rc::Rc};
 
 /// An item inside a [`StatementList`] Parse Node, as defined by the [spec].
 ///
@@ -91,6 +91,9 @@ impl VisitWith for StatementListItem {
 
 /// List of statements.
 ///
+/// The statements are reference counted, so that cloning a list (for example, to keep the body
+/// of a function around until it is compiled) doesn't clone the statements themselves.
+///
 /// More information:
 ///  - [ECMAScript reference][spec]
 ///
@@ -98,8 +101,8 @@ impl VisitWith for StatementListItem {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, Default)]
 pub struct StatementList {
-    pub(crate) statements: Box<[StatementListItem]>,
-    linear_pos_end: LinearPosition,
+    pub(crate) statements: Rc<[StatementListItem]>,
+    pub(crate) linear_pos_end: LinearPosition,
     strict: bool,
 }
 
@@ -117,16 +120,18 @@ impl StatementList {
         S: Into<Box<[StatementListItem]>>,
     {
         Self {
-            statements: statements.into(),
+            statements: statements.into().into(),
             linear_pos_end,
             strict,
         }
     }
 
     /// Gets the list of statements.
+    ///
+    /// This is not a `const fn`, since the statements are reference counted.
     #[inline]
     #[must_use]
-    pub const fn statements(&self) -> &[StatementListItem] {
+    pub fn statements(&self) -> &[StatementListItem] {
         &self.statements
     }
 
@@ -149,7 +154,7 @@ impl From<(Box<[StatementListItem]>, LinearPosition)> for StatementList {
     #[inline]
     fn from(value: (Box<[StatementListItem]>, LinearPosition)) -> Self {
         Self {
-            statements: value.0,
+            statements: value.0.into(),
             linear_pos_end: value.1,
             strict: false,
         }
@@ -204,7 +209,8 @@ impl VisitWith for StatementList {
     where
         V: VisitorMut<'a>,
     {
-        for statement in &mut *self.statements {
+        // The statements are only cloned if they are shared.
+        for statement in Rc::make_mut(&mut self.statements) {
             visitor.visit_statement_list_item_mut(statement)?;
         }
         ControlFlow::Continue(())
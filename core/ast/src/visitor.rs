@@ -20,6 +20,7 @@ use crate::{
             PrivatePropertyAccess, PropertyAccess, PropertyAccessField, SimplePropertyAccess,
             SuperPropertyAccess,
         },
+        jsx::{Jsx, JsxAttribute, JsxChild, JsxElement, JsxText},
         literal::{
             ArrayLiteral, Literal, ObjectLiteral, ObjectMethodDefinition, PropertyDefinition,
             TemplateElement, TemplateLiteral,
@@ -216,6 +217,11 @@ node_ref! {
     ReExportKind,
     ExportDeclaration,
     ExportSpecifier,
+    Jsx,
+    JsxElement,
+    JsxAttribute,
+    JsxChild,
+    JsxText,
 }
 
 /// Represents an AST visitor.
@@ -329,6 +335,11 @@ pub trait Visitor<'ast>: Sized {
     define_visit!(visit_re_export_kind, ReExportKind);
     define_visit!(visit_export_declaration, ExportDeclaration);
     define_visit!(visit_export_specifier, ExportSpecifier);
+    define_visit!(visit_jsx, Jsx);
+    define_visit!(visit_jsx_element, JsxElement);
+    define_visit!(visit_jsx_attribute, JsxAttribute);
+    define_visit!(visit_jsx_child, JsxChild);
+    define_visit!(visit_jsx_text, JsxText);
 
     /// Generic entry point for a node that is visitable by a `Visitor`.
     ///
@@ -439,6 +450,11 @@ pub trait Visitor<'ast>: Sized {
             NodeRef::ReExportKind(n) => self.visit_re_export_kind(n),
             NodeRef::ExportDeclaration(n) => self.visit_export_declaration(n),
             NodeRef::ExportSpecifier(n) => self.visit_export_specifier(n),
+            NodeRef::Jsx(n) => self.visit_jsx(n),
+            NodeRef::JsxElement(n) => self.visit_jsx_element(n),
+            NodeRef::JsxAttribute(n) => self.visit_jsx_attribute(n),
+            NodeRef::JsxChild(n) => self.visit_jsx_child(n),
+            NodeRef::JsxText(n) => self.visit_jsx_text(n),
         }
     }
 }
@@ -563,6 +579,11 @@ pub trait VisitorMut<'ast>: Sized {
     define_visit_mut!(visit_re_export_kind_mut, ReExportKind);
     define_visit_mut!(visit_export_declaration_mut, ExportDeclaration);
     define_visit_mut!(visit_export_specifier_mut, ExportSpecifier);
+    define_visit_mut!(visit_jsx_mut, Jsx);
+    define_visit_mut!(visit_jsx_element_mut, JsxElement);
+    define_visit_mut!(visit_jsx_attribute_mut, JsxAttribute);
+    define_visit_mut!(visit_jsx_child_mut, JsxChild);
+    define_visit_mut!(visit_jsx_text_mut, JsxText);
 
     /// Generic entry point for a node that is visitable by a `VisitorMut`.
     ///
@@ -675,6 +696,11 @@ pub trait VisitorMut<'ast>: Sized {
             NodeRefMut::ReExportKind(n) => self.visit_re_export_kind_mut(n),
             NodeRefMut::ExportDeclaration(n) => self.visit_export_declaration_mut(n),
             NodeRefMut::ExportSpecifier(n) => self.visit_export_specifier_mut(n),
+            NodeRefMut::Jsx(n) => self.visit_jsx_mut(n),
+            NodeRefMut::JsxElement(n) => self.visit_jsx_element_mut(n),
+            NodeRefMut::JsxAttribute(n) => self.visit_jsx_attribute_mut(n),
+            NodeRefMut::JsxChild(n) => self.visit_jsx_child_mut(n),
+            NodeRefMut::JsxText(n) => self.visit_jsx_text_mut(n),
         }
     }
 }
@@ -291,6 +291,51 @@ impl BuiltInConstructor for Array {
 }
 
 impl Array {
+    /// Returns the length of `o` if its elements can be accessed directly through its dense
+    /// indexed storage, bypassing the generic property machinery.
+    ///
+    /// This is the case if `o` is an extensible array with the shape of the array template of the
+    /// current realm (a writable `length` and no other properties), all of its elements are stored
+    /// densely, and neither `Array.prototype` nor `Object.prototype` have indexed properties that
+    /// could intercept an element access.
+    pub(crate) fn fast_array_length(o: &JsObject, context: &Context) -> Option<u32> {
+        if !o.is_array() {
+            return None;
+        }
+
+        let templates = context.intrinsics().templates();
+        let len = {
+            let object = o.borrow();
+            if !object.extensible
+                || object.properties().shape.to_addr_usize()
+                    != templates.array().shape().to_addr_usize()
+            {
+                return None;
+            }
+
+            // NOTE: The "length" property is the first element.
+            let len = object.properties().storage[0].as_number()? as u32;
+            if object.properties().indexed_properties.dense_len()? != len as usize {
+                return None;
+            }
+            len
+        };
+
+        let constructors = context.intrinsics().constructors();
+        let array_prototype = constructors.array().prototype();
+        let object_prototype = constructors.object().prototype();
+        let has_no_elements =
+            |o: &JsObject| o.borrow().properties().indexed_properties.dense_len() == Some(0);
+        let inherits_object_prototype = array_prototype
+            .prototype()
+            .is_some_and(|prototype| JsObject::equals(&prototype, &object_prototype));
+
+        (inherits_object_prototype
+            && has_no_elements(&array_prototype)
+            && has_no_elements(&object_prototype))
+        .then_some(len)
+    }
+
     /// Optimized helper function, that sets the length of the array.
     fn set_length(o: &JsObject, len: u64, context: &mut Context) -> JsResult<()> {
         if o.is_array() && len < (2u64.pow(32) - 1) {
@@ -875,6 +920,20 @@ impl Array {
                 )
                 .into());
         }
+
+        // Fast path for dense arrays, which appends the elements directly to the indexed storage.
+        if Self::fast_array_length(&o, context).is_some() && u32::try_from(len + arg_count).is_ok()
+        {
+            let mut o = o.borrow_mut();
+            let properties = o.properties_mut();
+            for element in args {
+                properties.indexed_properties.push_dense(element);
+            }
+            let len = len + arg_count;
+            properties.storage[0] = JsValue::new(len);
+            return Ok(len.into());
+        }
+
         // 5. For each element E of items, do
         for element in args.iter().cloned() {
             // a. Perform ? Set(O, ! ToString(𝔽(len)), E, true).
@@ -916,6 +975,19 @@ impl Array {
             // a. Assert: len > 0.
             // b. Let newLen be 𝔽(len - 1).
             let new_len = len - 1;
+
+            // Fast path for dense arrays, which removes the element directly from the indexed storage.
+            if Self::fast_array_length(&o, context).is_some() {
+                let mut o = o.borrow_mut();
+                let properties = o.properties_mut();
+                let element = properties
+                    .indexed_properties
+                    .pop_dense()
+                    .unwrap_or_default();
+                properties.storage[0] = JsValue::new(new_len);
+                return Ok(element);
+            }
+
             // c. Let index be ! ToString(newLen).
             let index = new_len;
             // d. Let element be ? Get(O, index).
@@ -1487,6 +1559,16 @@ impl Array {
 
         let search_element = args.get_or_undefined(0);
 
+        // Fast path for dense arrays, which compares the elements without any property lookups.
+        if Self::fast_array_length(&o, context).is_some_and(|length| i64::from(length) == len) {
+            let position = o.borrow().properties().indexed_properties.dense_position(
+                search_element,
+                false,
+                k as usize..len as usize,
+            );
+            return Ok(position.map_or(JsValue::new(-1), JsValue::new));
+        }
+
         // 10. Repeat, while k < len,
         while k < len {
             // a. Let kPresent be ? HasProperty(O, ! ToString(𝔽(k))).
@@ -1561,6 +1643,18 @@ impl Array {
 
         let search_element = args.get_or_undefined(0);
 
+        // Fast path for dense arrays, which compares the elements without any property lookups.
+        if k >= 0
+            && Self::fast_array_length(&o, context).is_some_and(|length| i64::from(length) == len)
+        {
+            let position = o.borrow().properties().indexed_properties.dense_position(
+                search_element,
+                false,
+                (0..=k as usize).rev(),
+            );
+            return Ok(position.map_or(JsValue::new(-1), JsValue::new));
+        }
+
         // 8. Repeat, while k ≥ 0,
         while k >= 0 {
             // a. Let kPresent be ? HasProperty(O, ! ToString(𝔽(k))).
@@ -1990,6 +2084,17 @@ impl Array {
 
         let value = args.get_or_undefined(0);
 
+        // Fast path for dense arrays, which stores the value directly into the indexed storage.
+        if Self::fast_array_length(&o, context).is_some_and(|length| u64::from(length) == len) {
+            let mut o_borrow = o.borrow_mut();
+            let properties = o_borrow.properties_mut();
+            for index in k..final_ {
+                properties.set_dense_property(index as u32, value);
+            }
+            drop(o_borrow);
+            return Ok(o.into());
+        }
+
         // 11. Repeat, while k < final,
         while k < final_ {
             // a. Let Pk be ! ToString(𝔽(k)).
@@ -2061,6 +2166,16 @@ impl Array {
 
         let search_element = args.get_or_undefined(0);
 
+        // Fast path for dense arrays, which compares the elements without any property lookups.
+        if Self::fast_array_length(&o, context).is_some_and(|length| i64::from(length) == len) {
+            let position = o.borrow().properties().indexed_properties.dense_position(
+                search_element,
+                true,
+                k as usize..len as usize,
+            );
+            return Ok(position.is_some().into());
+        }
+
         // 10. Repeat, while k < len,
         while k < len {
             // a. Let elementK be ? Get(O, ! ToString(𝔽(k))).
@@ -985,3 +985,94 @@ fn array_prototype_find_edge_cases() {
         "#}),
     ]);
 }
+
+#[test]
+fn dense_element_kinds() {
+    use crate::{Source, object::IndexedProperties};
+
+    let context = &mut Context::default();
+    let mut kind = |source: &str| {
+        let array = context.eval(Source::from_bytes(source)).unwrap();
+        let array = array.as_object().unwrap();
+        match array.borrow().properties().indexed_properties {
+            IndexedProperties::DenseI32(_) => "i32",
+            IndexedProperties::DenseF64(_) => "f64",
+            IndexedProperties::DenseElement(_) => "element",
+            IndexedProperties::SparseElement(_) | IndexedProperties::SparseProperty(_) => "sparse",
+        }
+    };
+
+    assert_eq!(kind("[1, 2, 3]"), "i32");
+    assert_eq!(kind("Array.of(1, 2, 3)"), "i32");
+    assert_eq!(kind("[1, 2, -0]"), "f64");
+    assert_eq!(kind("var a = [1, 2]; a.push(0.5); a"), "f64");
+    assert_eq!(kind("var a = [1, 2]; a[a.length] = 0.5; a"), "f64");
+    assert_eq!(kind("var a = [1.5]; a.fill(2); a"), "f64");
+    assert_eq!(kind("var a = [1.5]; a.push('x'); a"), "element");
+    assert_eq!(kind("var a = [1]; a[2] = 3; a"), "sparse");
+}
+
+#[test]
+fn dense_array_fast_paths() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            var ints = [1, 2, 3];
+            var doubles = [0.5, NaN, -0];
+            var values = ["a", 1, undefined];
+        "#}),
+        TestAction::assert_eq("ints.push(4, 5.5, 'six')", 6),
+        TestAction::assert_eq("ints.join()", js_string!("1,2,3,4,5.5,six")),
+        TestAction::assert_eq("ints.pop()", js_string!("six")),
+        TestAction::assert_eq("ints.length", 5),
+        TestAction::assert_eq("ints.indexOf(5.5)", 4),
+        TestAction::assert_eq("ints.indexOf('1')", -1),
+        TestAction::assert_eq("ints.lastIndexOf(1, -5)", 0),
+        TestAction::assert_eq("ints.lastIndexOf(1, -6)", -1),
+        TestAction::assert_eq("doubles.indexOf(NaN)", -1),
+        TestAction::assert("doubles.includes(NaN)"),
+        TestAction::assert_eq("doubles.indexOf(0)", 2),
+        TestAction::assert("!doubles.includes(0.5, 1)"),
+        TestAction::assert_eq("values.indexOf(undefined)", 2),
+        TestAction::assert("values.includes(1)"),
+        TestAction::assert_eq(
+            "[1, 2, 3, 4].fill(0.5, 1, 3).join()",
+            js_string!("1,0.5,0.5,4"),
+        ),
+        TestAction::assert_eq("[1, 2, 3, 4].fill(0, -1).join()", js_string!("1,2,3,0")),
+    ]);
+}
+
+#[test]
+fn dense_array_fast_paths_respect_prototype_elements() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            var log = [];
+            Object.defineProperty(Array.prototype, "3", {
+                set(value) { log.push(value); },
+                get() { return "inherited"; },
+                configurable: true,
+            });
+            var a = [1, 2, 3];
+        "#}),
+        TestAction::assert_eq("a.push(4)", 4),
+        TestAction::assert("!a.hasOwnProperty(3)"),
+        TestAction::assert_eq("a[3]", js_string!("inherited")),
+        TestAction::assert_eq("a.indexOf('inherited')", 3),
+        TestAction::assert_eq("a.pop()", js_string!("inherited")),
+        TestAction::run("a[3] = 5;"),
+        TestAction::assert_eq("log.join()", js_string!("4,5")),
+        TestAction::run("delete Array.prototype[3];"),
+        TestAction::run(indoc! {r#"
+            var calls = 0;
+            Object.defineProperty(Object.prototype, "2", {
+                set(value) { calls += value; },
+                configurable: true,
+            });
+            var b = [0, 1];
+        "#}),
+        TestAction::run("b.push(2); b[2] = 3;"),
+        TestAction::assert_eq("calls", 5),
+        TestAction::assert_eq("b.length", 3),
+        TestAction::run("delete Object.prototype[2];"),
+    ]);
+}
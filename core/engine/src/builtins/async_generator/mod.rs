@@ -107,6 +107,13 @@ impl IntrinsicObject for AsyncGenerator {
 impl AsyncGenerator {
     const NAME: JsString = StaticJsStrings::ASYNC_GENERATOR;
 
+    /// Returns the promise of the request the generator is currently completing, if any.
+    pub(crate) fn current_promise(&self) -> Option<&JsObject> {
+        self.queue
+            .front()
+            .map(|request| &request.capability.promise)
+    }
+
     /// `AsyncGenerator.prototype.next ( value )`
     ///
     /// More information:
@@ -53,6 +53,11 @@ impl Date {
         Self(dt)
     }
 
+    /// Returns the time value of the `Date`.
+    pub(crate) const fn time_value(self) -> f64 {
+        self.0
+    }
+
     /// Creates a new `Date` from the current UTC time of the host.
     pub(crate) fn utc_now(context: &mut Context) -> Self {
         Self(context.clock().system_time_millis() as f64)
@@ -140,7 +140,6 @@ pub struct Error {
     pub(crate) tag: ErrorKind,
 
     // The position of where the Error was created does not affect equality check.
-    #[unsafe_ignore_trace]
     pub(crate) stack: IgnoreEq<ErrorStack>,
 }
 
@@ -170,7 +169,11 @@ impl Error {
     /// Get the position from the last called function.
     pub(crate) fn with_caller_position(tag: ErrorKind, context: &Context) -> Self {
         let limit = context.runtime_limits().backtrace_limit();
-        let backtrace = context.vm.shadow_stack.caller_position(limit);
+        let backtrace = context
+            .vm
+            .shadow_stack
+            .caller_position(limit)
+            .with_async_stack(context.vm.async_stack(limit));
         Self {
             tag,
             stack: IgnoreEq(ErrorStack::Backtrace(backtrace)),
@@ -317,13 +320,17 @@ impl Error {
         // 5. Let stackString be an implementation-defined String value representing the call stack.
         // 6. Return stackString.
         if let Some(backtrace) = error_data.stack.0.backtrace() {
-            let stack_string = backtrace
-                .iter()
-                .rev()
-                .fold(String::new(), |mut output, entry| {
-                    let _ = writeln!(&mut output, "    at {}", entry.display(true));
-                    output
-                });
+            let mut stack_string =
+                backtrace
+                    .iter()
+                    .rev()
+                    .fold(String::new(), |mut output, entry| {
+                        let _ = writeln!(&mut output, "    at {}", entry.display(true));
+                        output
+                    });
+            for entry in backtrace.async_iter() {
+                let _ = writeln!(&mut stack_string, "    at async {}", entry.display(true));
+            }
             return Ok(js_string!(stack_string).into());
         }
 
@@ -227,11 +227,38 @@ impl OrdinaryFunction {
     }
 
     /// Returns the codeblock of the function.
+    ///
+    /// If the function was compiled lazily and was not called yet, this is a placeholder without
+    /// bytecode. [`OrdinaryFunction::compiled_codeblock`] compiles the function first.
     #[must_use]
     pub fn codeblock(&self) -> &CodeBlock {
         &self.code
     }
 
+    /// Returns the codeblock of the function object `function`, compiling the function if it
+    /// was compiled lazily and was not called yet.
+    ///
+    /// Returns `None` if `function` is not an ordinary function.
+    #[must_use]
+    pub fn compiled_codeblock(function: &JsObject) -> Option<Gc<CodeBlock>> {
+        let code = function.downcast_ref::<Self>()?.code.clone();
+        Some(Self::compile(function, code))
+    }
+
+    /// Compiles the function object `function` if `code`, its code block, is the placeholder of
+    /// a function that was not compiled yet, and returns the compiled code block.
+    fn compile(function: &JsObject, code: Gc<CodeBlock>) -> Gc<CodeBlock> {
+        if code.uncompiled.is_none() {
+            return code;
+        }
+
+        let code = CodeBlock::compiled(&code);
+        if let Some(mut function) = function.downcast_mut::<Self>() {
+            function.code = code.clone();
+        }
+        code
+    }
+
     /// Push a private environment to the function.
     pub(crate) fn push_private_environment(&mut self, environment: Gc<PrivateEnvironment>) {
         self.environments.push_private(environment);
@@ -662,6 +689,7 @@ impl BuiltInFunctionObject {
             .r#async(r#async)
             .in_with(in_with)
             .force_function_scope(true)
+            .lazy(context.lazy_compilation())
             .compile(
                 function.parameters(),
                 function.body(),
@@ -1012,6 +1040,8 @@ pub(crate) fn function_call(
 
     drop(function);
 
+    let code = OrdinaryFunction::compile(function_object, code);
+
     let env_fp = environments.len() as u32;
 
     let frame = CallFrame::new(code, script_or_module, environments, realm)
@@ -1077,6 +1107,10 @@ pub(crate) fn function_call(
             function_object.clone().into(),
             global,
         );
+        #[cfg(feature = "debugger")]
+        frame
+            .environments
+            .set_lexical_scope(&frame.code_block().constant_scope(0));
         last_env += 1;
     }
 
@@ -1122,6 +1156,8 @@ fn function_construct(
     let script_or_module = function.script_or_module.clone();
     drop(function);
 
+    let code = OrdinaryFunction::compile(this_function_object, code);
+
     let env_fp = environments.len() as u32;
 
     let new_target = context.vm.stack.pop();
@@ -1185,6 +1221,10 @@ fn function_construct(
             this_function_object.clone().into(),
             global,
         );
+        #[cfg(feature = "debugger")]
+        frame
+            .environments
+            .set_lexical_scope(&frame.code_block().constant_scope(0));
         last_env += 1;
     }
 
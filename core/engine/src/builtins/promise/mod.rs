@@ -28,6 +28,7 @@ use crate::{
     string::StaticJsStrings,
     symbol::JsSymbol,
     value::JsValue,
+    vm::shadow_stack::Awaiter,
 };
 use boa_gc::{Finalize, Gc, GcRefCell, Trace, custom_trace};
 use boa_macros::JsData;
@@ -86,6 +87,12 @@ pub struct Promise {
     fulfill_reactions: Vec<ReactionRecord>,
     reject_reactions: Vec<ReactionRecord>,
     handled: bool,
+
+    /// The first async function or generator that awaited the promise while it was pending.
+    ///
+    /// This is kept after the promise settles, so that the async stack traces of errors can be
+    /// read after the errors were caught.
+    awaiter: Option<Box<Awaiter>>,
 }
 
 /// The operation type of the [`HostPromiseRejectionTracker`][fn] abstract operation.
@@ -466,6 +473,7 @@ impl Promise {
             fulfill_reactions: Vec::default(),
             reject_reactions: Vec::default(),
             handled: false,
+            awaiter: None,
         }
     }
 
@@ -474,6 +482,23 @@ impl Promise {
         &self.state
     }
 
+    /// Records the async function or generator that awaits this promise.
+    ///
+    /// This is only used to show the awaiting functions in async stack traces, so it does nothing
+    /// if the promise is already settled or already awaited.
+    pub(crate) fn set_awaiter(&mut self, awaiter: Awaiter) {
+        if let PromiseState::Pending = self.state
+            && self.awaiter.is_none()
+        {
+            self.awaiter = Some(Box::new(awaiter));
+        }
+    }
+
+    /// Returns the first async function or generator that awaited this promise, if any.
+    pub(crate) fn awaiter(&self) -> Option<&Awaiter> {
+        self.awaiter.as_deref()
+    }
+
     /// [`Promise.try ( callbackfn, ...args )`][spec]
     ///
     /// Calls the given function and returns a new promise that is resolved if the function
@@ -319,7 +319,7 @@ impl RegExp {
     /// Equivalent to the beginning of [`RegExpInitialize ( obj, pattern, flags )`][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-regexpinitialize
-    fn compile_native_regexp(
+    pub(crate) fn compile_native_regexp(
         pattern: &JsValue,
         flags: &JsValue,
         context: &mut Context,
@@ -88,6 +88,17 @@ impl GlobalSymbolRegistry {
     }
 }
 
+/// Returns the key of `symbol` in the global symbol registry, if it was created by
+/// `Symbol.for`.
+pub(crate) fn registered_key(symbol: &JsSymbol) -> Option<JsString> {
+    GLOBAL_SYMBOL_REGISTRY.get_key(symbol)
+}
+
+/// Returns the symbol of the global symbol registry with the given key, creating it if needed.
+pub(crate) fn registered_symbol(key: &JsString) -> JsResult<JsSymbol> {
+    GLOBAL_SYMBOL_REGISTRY.get_or_create_symbol(key)
+}
+
 /// The internal representation of a `Symbol` object.
 #[derive(Debug, Clone, Copy)]
 pub struct Symbol;
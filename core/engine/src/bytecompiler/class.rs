@@ -107,6 +107,8 @@ impl ByteCompiler<'_> {
             spanned_source_text,
             self.source_path.clone(),
         );
+        compiler.lazy_functions = self.lazy_functions;
+        compiler.interner_snapshot = self.interner_snapshot.clone();
 
         compiler.code_block_flags |= CodeBlockFlags::IS_CLASS_CONSTRUCTOR;
 
@@ -418,6 +420,8 @@ impl ByteCompiler<'_> {
                         self.spanned_source_text.clone_only_source(),
                         self.source_path.clone(),
                     );
+                    field_compiler.lazy_functions = self.lazy_functions;
+                    field_compiler.interner_snapshot = self.interner_snapshot.clone();
 
                     // Function environment
                     field_compiler.code_block_flags |= CodeBlockFlags::HAS_FUNCTION_SCOPE;
@@ -469,6 +473,8 @@ impl ByteCompiler<'_> {
                         self.spanned_source_text.clone_only_source(),
                         self.source_path.clone(),
                     );
+                    field_compiler.lazy_functions = self.lazy_functions;
+                    field_compiler.interner_snapshot = self.interner_snapshot.clone();
                     field_compiler.code_block_flags |= CodeBlockFlags::HAS_FUNCTION_SCOPE;
                     let _ = field_compiler.push_scope(field.scope());
                     let value = field_compiler.register_allocator.alloc();
@@ -521,6 +527,8 @@ impl ByteCompiler<'_> {
                         self.spanned_source_text.clone_only_source(),
                         self.source_path.clone(),
                     );
+                    field_compiler.lazy_functions = self.lazy_functions;
+                    field_compiler.interner_snapshot = self.interner_snapshot.clone();
                     field_compiler.code_block_flags |= CodeBlockFlags::HAS_FUNCTION_SCOPE;
                     let _ = field_compiler.push_scope(field.scope());
                     let value = field_compiler.register_allocator.alloc();
@@ -565,6 +573,8 @@ impl ByteCompiler<'_> {
                         self.spanned_source_text.clone_only_source(),
                         self.source_path.clone(),
                     );
+                    field_compiler.lazy_functions = self.lazy_functions;
+                    field_compiler.interner_snapshot = self.interner_snapshot.clone();
                     field_compiler.code_block_flags |= CodeBlockFlags::HAS_FUNCTION_SCOPE;
                     let _ = field_compiler.push_scope(field.scope());
                     let value = field_compiler.register_allocator.alloc();
@@ -608,6 +618,8 @@ impl ByteCompiler<'_> {
                         self.spanned_source_text.clone_only_source(),
                         self.source_path.clone(),
                     );
+                    compiler.lazy_functions = self.lazy_functions;
+                    compiler.interner_snapshot = self.interner_snapshot.clone();
                     compiler.code_block_flags |= CodeBlockFlags::HAS_FUNCTION_SCOPE;
                     let _ = compiler.push_scope(block.scopes().function_scope());
 
@@ -524,26 +524,23 @@ impl ByteCompiler<'_> {
             let func_span = function.linear_span();
             let spanned_source_text = SpannedSourceText::new(self.source_text(), func_span);
 
-            let code = FunctionCompiler::new(spanned_source_text)
+            let compiler = FunctionCompiler::new(spanned_source_text)
                 .name(name.sym().to_js_string(self.interner()))
                 .generator(generator)
                 .r#async(r#async)
                 .strict(self.strict())
                 .in_with(self.in_with)
-                .source_path(self.source_path.clone())
-                .compile(
-                    parameters,
-                    body,
-                    self.variable_scope.clone(),
-                    self.lexical_scope.clone(),
-                    &scopes,
-                    contains_direct_eval,
-                    self.interner,
-                );
+                .source_path(self.source_path.clone());
 
             // Ensures global functions are printed when generating the global flowgraph.
             let name_index = self.get_or_insert_name(name.sym());
-            let function_index = self.push_function_to_constants(code);
+            let function_index = self.push_nested_function(
+                compiler,
+                parameters,
+                body,
+                &scopes,
+                contains_direct_eval,
+            );
 
             // b. Let fo be InstantiateFunctionObject of f with arguments env and privateEnv.
             // c. Perform ? env.CreateGlobalFunctionBinding(fn, fo, false).
@@ -802,25 +799,22 @@ impl ByteCompiler<'_> {
             let func_span = function.linear_span();
             let spanned_source_text = SpannedSourceText::new(self.source_text(), func_span);
 
-            let code = FunctionCompiler::new(spanned_source_text)
+            let compiler = FunctionCompiler::new(spanned_source_text)
                 .name(name.sym().to_js_string(self.interner()))
                 .generator(generator)
                 .r#async(r#async)
                 .strict(self.strict())
                 .in_with(self.in_with)
-                .name_scope(None)
-                .compile(
-                    parameters,
-                    body,
-                    self.variable_scope.clone(),
-                    self.lexical_scope.clone(),
-                    &scopes,
-                    contains_direct_eval,
-                    self.interner,
-                );
+                .name_scope(None);
 
             // b. Let fo be InstantiateFunctionObject of f with arguments lexEnv and privateEnv.
-            let index = self.push_function_to_constants(code);
+            let index = self.push_nested_function(
+                compiler,
+                parameters,
+                body,
+                &scopes,
+                contains_direct_eval,
+            );
 
             // c. If varEnv is a Global Environment Record, then
             if var_env.is_global() {
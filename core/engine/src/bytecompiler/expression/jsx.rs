@@ -0,0 +1,361 @@
+//! Lowering for JSX expressions.
+//!
+//! JSX elements are compiled to calls of the runtime selected in the [`Source`][boa_parser::Source]:
+//!
+//! - The classic runtime calls the factory as `factory(type, props, ...children)`, where `props`
+//!   is `null` if the element has no attributes.
+//! - The automatic runtime calls `_jsx(type, props, key)`, or `_jsxs` if the element has multiple
+//!   children, with the children in the `children` prop and the `key` attribute passed separately.
+//!
+//! The text of children is cleaned up like other JSX implementations do: lines are trimmed, and
+//! lines with content are joined with a single space.
+
+use crate::{
+    JsString,
+    bytecompiler::{Access, ByteCompiler, Literal, Register, ToJsString},
+    js_string,
+    vm::CallFrame,
+};
+use boa_ast::expression::jsx::{
+    Jsx, JsxAttribute, JsxAttributeValue, JsxChild, JsxElement, JsxElementName, JsxRuntime,
+};
+use boa_ast::{Expression, expression::Identifier};
+use cow_utils::CowUtils;
+use thin_vec::ThinVec;
+
+/// A child of an element after the text cleanup.
+enum Child<'a> {
+    Text(String),
+    Element(&'a JsxElement),
+    Expression(&'a Expression),
+    Spread(&'a Expression),
+}
+
+impl ByteCompiler<'_> {
+    pub(crate) fn compile_jsx(&mut self, jsx: &Jsx, dst: &Register) {
+        self.compile_jsx_element(jsx.element(), jsx.runtime(), dst);
+    }
+
+    fn compile_jsx_element(&mut self, element: &JsxElement, runtime: &JsxRuntime, dst: &Register) {
+        let children = self.jsx_children(element.children());
+        match runtime {
+            JsxRuntime::Classic { factory, fragment } => {
+                self.compile_jsx_classic(element, &children, factory, fragment, runtime, dst);
+            }
+            JsxRuntime::Automatic {
+                jsx,
+                jsxs,
+                fragment,
+                ..
+            } => {
+                let multiple = children.len() > 1
+                    || children
+                        .iter()
+                        .any(|child| matches!(child, Child::Spread(_)));
+                let function = if multiple { jsxs } else { jsx };
+                self.compile_jsx_automatic(element, &children, *function, *fragment, runtime, dst);
+            }
+        }
+    }
+
+    fn compile_jsx_classic(
+        &mut self,
+        element: &JsxElement,
+        children: &[Child<'_>],
+        factory: &Expression,
+        fragment: &Expression,
+        runtime: &JsxRuntime,
+        dst: &Register,
+    ) {
+        if let Expression::PropertyAccess(access) = factory {
+            let this = self.register_allocator.alloc();
+            let function = self.register_allocator.alloc();
+            self.compile_access_preserve_this(access, &this, &function);
+            self.push_from_register(&this);
+            self.push_from_register(&function);
+            self.register_allocator.dealloc(this);
+            self.register_allocator.dealloc(function);
+        } else {
+            let function = self.register_allocator.alloc();
+            self.compile_expr(factory, &function);
+            self.push_from_register(&CallFrame::undefined_register());
+            self.push_from_register(&function);
+            self.register_allocator.dealloc(function);
+        }
+
+        let mut compiler = self.position_guard(element);
+
+        let contains_spread = children
+            .iter()
+            .any(|child| matches!(child, Child::Spread(_)));
+        let array = contains_spread.then(|| compiler.register_allocator.alloc());
+        if let Some(array) = &array {
+            compiler.bytecode.emit_store_new_array(array.variable());
+        }
+
+        let value = compiler.register_allocator.alloc();
+        match element.name() {
+            Some(name) => compiler.compile_jsx_element_name(name, &value),
+            None => compiler.compile_expr(fragment, &value),
+        }
+        compiler.push_jsx_argument(&value, array.as_ref());
+
+        if element.attributes().is_empty() {
+            compiler.bytecode.emit_store_null(value.variable());
+        } else {
+            compiler.compile_jsx_props(element.attributes(), None, runtime, &value);
+        }
+        compiler.push_jsx_argument(&value, array.as_ref());
+
+        for child in children {
+            if let Child::Spread(expression) = child {
+                compiler.compile_expr(expression, &value);
+                compiler.bytecode.emit_get_iterator(value.variable());
+                if let Some(array) = &array {
+                    compiler
+                        .bytecode
+                        .emit_push_iterator_to_array(array.variable());
+                }
+            } else {
+                compiler.compile_jsx_child(child, runtime, &value);
+                compiler.push_jsx_argument(&value, array.as_ref());
+            }
+        }
+        compiler.register_allocator.dealloc(value);
+
+        if let Some(array) = array {
+            compiler.push_from_register(&array);
+            compiler.register_allocator.dealloc(array);
+            compiler.bytecode.emit_call_spread();
+        } else {
+            compiler
+                .bytecode
+                .emit_call(((children.len() + 2) as u32).into());
+        }
+        compiler.pop_into_register(dst);
+    }
+
+    fn compile_jsx_automatic(
+        &mut self,
+        element: &JsxElement,
+        children: &[Child<'_>],
+        function: Identifier,
+        fragment: Identifier,
+        runtime: &JsxRuntime,
+        dst: &Register,
+    ) {
+        let value = self.register_allocator.alloc();
+        self.access_get(Access::Variable { name: function }, &value);
+        self.push_from_register(&CallFrame::undefined_register());
+        self.push_from_register(&value);
+
+        let mut compiler = self.position_guard(element);
+
+        match element.name() {
+            Some(name) => compiler.compile_jsx_element_name(name, &value),
+            None => compiler.access_get(Access::Variable { name: fragment }, &value),
+        }
+        compiler.push_from_register(&value);
+
+        // The key can only be passed separately if it is before all spread attributes, because a
+        // spread could otherwise override it.
+        let key = element
+            .attributes()
+            .iter()
+            .take_while(|attribute| !matches!(attribute, JsxAttribute::Spread(_)))
+            .position(|attribute| {
+                matches!(attribute, JsxAttribute::Named { name, .. }
+                    if compiler.interner().resolve_expect(*name).utf8() == Some("key"))
+            });
+        compiler.compile_jsx_props(element.attributes(), key, runtime, &value);
+
+        match children {
+            [] => {}
+            [child @ (Child::Text(_) | Child::Element(_) | Child::Expression(_))] => {
+                let child_value = compiler.register_allocator.alloc();
+                compiler.compile_jsx_child(child, runtime, &child_value);
+                compiler.define_jsx_prop(&value, js_string!("children"), &child_value);
+                compiler.register_allocator.dealloc(child_value);
+            }
+            children => {
+                let array = compiler.register_allocator.alloc();
+                let child_value = compiler.register_allocator.alloc();
+                compiler.bytecode.emit_store_new_array(array.variable());
+                for child in children {
+                    if let Child::Spread(expression) = child {
+                        compiler.compile_expr(expression, &child_value);
+                        compiler.bytecode.emit_get_iterator(child_value.variable());
+                        compiler
+                            .bytecode
+                            .emit_push_iterator_to_array(array.variable());
+                    } else {
+                        compiler.compile_jsx_child(child, runtime, &child_value);
+                        compiler
+                            .bytecode
+                            .emit_push_value_to_array(child_value.variable(), array.variable());
+                    }
+                }
+                compiler.define_jsx_prop(&value, js_string!("children"), &array);
+                compiler.register_allocator.dealloc(child_value);
+                compiler.register_allocator.dealloc(array);
+            }
+        }
+        compiler.push_from_register(&value);
+
+        let argument_count: u32 = if let Some(JsxAttribute::Named { value: key, .. }) =
+            key.map(|index| &element.attributes()[index])
+        {
+            compiler.compile_jsx_attribute_value(key.as_ref(), runtime, &value);
+            compiler.push_from_register(&value);
+            3
+        } else {
+            2
+        };
+        compiler.register_allocator.dealloc(value);
+
+        compiler.bytecode.emit_call(argument_count.into());
+        compiler.pop_into_register(dst);
+    }
+
+    /// Compiles the props object of the `attributes` into `dst`, skipping the attribute at the
+    /// index `skip`.
+    fn compile_jsx_props(
+        &mut self,
+        attributes: &[JsxAttribute],
+        skip: Option<usize>,
+        runtime: &JsxRuntime,
+        dst: &Register,
+    ) {
+        self.bytecode.emit_store_empty_object(dst.variable());
+        let value = self.register_allocator.alloc();
+        for (index, attribute) in attributes.iter().enumerate() {
+            if Some(index) == skip {
+                continue;
+            }
+            match attribute {
+                JsxAttribute::Named { name, value: item } => {
+                    self.compile_jsx_attribute_value(item.as_ref(), runtime, &value);
+                    let name = name.to_js_string(self.interner());
+                    self.define_jsx_prop(dst, name, &value);
+                }
+                JsxAttribute::Spread(expression) => {
+                    self.compile_expr(expression, &value);
+                    self.bytecode.emit_copy_data_properties(
+                        dst.variable(),
+                        value.variable(),
+                        ThinVec::new(),
+                    );
+                }
+            }
+        }
+        self.register_allocator.dealloc(value);
+    }
+
+    /// Compiles the value of an attribute into `dst`, where attributes without a value are `true`.
+    fn compile_jsx_attribute_value(
+        &mut self,
+        value: Option<&JsxAttributeValue>,
+        runtime: &JsxRuntime,
+        dst: &Register,
+    ) {
+        match value {
+            None => self.bytecode.emit_store_true(dst.variable()),
+            Some(JsxAttributeValue::String(text)) => {
+                let value = text.value().to_js_string(self.interner());
+                self.emit_store_literal(Literal::String(value), dst);
+            }
+            Some(JsxAttributeValue::Expression(expression)) => self.compile_expr(expression, dst),
+            Some(JsxAttributeValue::Element(element)) => {
+                self.compile_jsx_element(element, runtime, dst);
+            }
+        }
+    }
+
+    fn compile_jsx_element_name(&mut self, name: &JsxElementName, dst: &Register) {
+        match name {
+            JsxElementName::Intrinsic(name) => {
+                let name = name.to_js_string(self.interner());
+                self.emit_store_literal(Literal::String(name), dst);
+            }
+            JsxElementName::Component(expression) => self.compile_expr(expression, dst),
+        }
+    }
+
+    /// Compiles a child that is not a spread into `dst`.
+    fn compile_jsx_child(&mut self, child: &Child<'_>, runtime: &JsxRuntime, dst: &Register) {
+        match child {
+            Child::Text(text) => {
+                self.emit_store_literal(Literal::String(js_string!(text.as_str())), dst);
+            }
+            Child::Element(element) => self.compile_jsx_element(element, runtime, dst),
+            Child::Expression(expression) | Child::Spread(expression) => {
+                self.compile_expr(expression, dst);
+            }
+        }
+    }
+
+    fn define_jsx_prop(&mut self, object: &Register, name: JsString, value: &Register) {
+        let index = self.get_or_insert_string(name);
+        self.bytecode.emit_define_own_property_by_name(
+            object.variable(),
+            value.variable(),
+            index.into(),
+        );
+    }
+
+    /// Pushes `value` to the `array` of a spread call, or to the stack if there is no array.
+    fn push_jsx_argument(&mut self, value: &Register, array: Option<&Register>) {
+        if let Some(array) = array {
+            self.bytecode
+                .emit_push_value_to_array(value.variable(), array.variable());
+        } else {
+            self.push_from_register(value);
+        }
+    }
+
+    /// Removes the text children that only contain formatting whitespace, and cleans up the
+    /// whitespace of the remaining ones.
+    fn jsx_children<'a>(&self, children: &'a [JsxChild]) -> Vec<Child<'a>> {
+        children
+            .iter()
+            .filter_map(|child| match child {
+                JsxChild::Text(text) => {
+                    let text = self.interner().resolve_expect(text.value()).to_string();
+                    clean_jsx_text(&text).map(Child::Text)
+                }
+                JsxChild::Element(element) => Some(Child::Element(element)),
+                JsxChild::Expression(expression) => Some(Child::Expression(expression)),
+                JsxChild::Spread(expression) => Some(Child::Spread(expression)),
+            })
+            .collect()
+    }
+}
+
+/// Trims the lines of JSX text and joins the lines with content with a single space, returning
+/// `None` if nothing is left.
+///
+/// The first line keeps its leading whitespace and the last line its trailing whitespace, so
+/// that the text can be followed or preceded by expressions on the same line.
+fn clean_jsx_text(text: &str) -> Option<String> {
+    let text = text.cow_replace('\t', " ");
+    let lines = text.split(['\n', '\r']).collect::<Vec<_>>();
+    let last = lines.len() - 1;
+
+    let mut cleaned = Vec::with_capacity(lines.len());
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = if index == 0 {
+            line
+        } else {
+            line.trim_start_matches(' ')
+        };
+        let line = if index == last {
+            line
+        } else {
+            line.trim_end_matches(' ')
+        };
+        if !line.is_empty() {
+            cleaned.push(line);
+        }
+    }
+    (!cleaned.is_empty()).then(|| cleaned.join(" "))
+}
@@ -9,6 +9,7 @@
 
 mod assign;
 mod binary;
+mod jsx;
 mod object_literal;
 mod unary;
 mod update;
@@ -21,7 +22,7 @@ use crate::{
     vm::{CallFrame, GeneratorResumeKind},
 };
 use boa_ast::{
-    Expression,
+    Expression, Spanned,
     expression::{
         ImportPhase,
         access::{PropertyAccess, PropertyAccessField},
@@ -163,7 +164,11 @@ impl ByteCompiler<'_> {
             }
             Expression::Await(expr) => {
                 self.compile_expr(expr.target(), dst);
+
+                // NOTE: The position of the `await` is shown in async stack traces.
+                self.push_source_position(expr.span().start());
                 self.bytecode.emit_await(dst.variable());
+                self.pop_source_position();
                 let resume_kind = self.register_allocator.alloc();
                 self.pop_into_register(&resume_kind);
                 self.pop_into_register(dst);
@@ -423,6 +428,7 @@ impl ByteCompiler<'_> {
             Expression::Parenthesized(parenthesized) => {
                 self.compile_expr(parenthesized.expression(), dst);
             }
+            Expression::Jsx(jsx) => self.compile_jsx(jsx, dst),
         }
     }
 }
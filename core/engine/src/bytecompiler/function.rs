@@ -3,14 +3,21 @@ use crate::{
     builtins::function::ThisMode,
     bytecompiler::ByteCompiler,
     js_string,
-    vm::{CodeBlock, CodeBlockFlags, source_info::SourcePath},
+    vm::{
+        CodeBlock, CodeBlockFlags,
+        source_info::{SourceInfo, SourceMap, SourcePath},
+    },
 };
 use boa_ast::{
     function::{FormalParameterList, FunctionBody},
     scope::{FunctionScopes, Scope},
 };
-use boa_gc::Gc;
+use boa_gc::{Finalize, Gc, Trace};
 use boa_interner::Interner;
+use std::{
+    cell::{Cell, OnceCell},
+    rc::Rc,
+};
 
 /// `FunctionCompiler` is used to compile AST functions to bytecode.
 #[derive(Debug, Clone)]
@@ -24,6 +31,8 @@ pub(crate) struct FunctionCompiler {
     method: bool,
     in_with: bool,
     force_function_scope: bool,
+    lazy: bool,
+    interner_snapshot: InternerSnapshot,
     name_scope: Option<Scope>,
     spanned_source_text: SpannedSourceText,
     source_path: SourcePath,
@@ -41,6 +50,8 @@ impl FunctionCompiler {
             method: false,
             in_with: false,
             force_function_scope: false,
+            lazy: false,
+            interner_snapshot: InternerSnapshot::default(),
             name_scope: None,
             spanned_source_text,
             source_path: SourcePath::None,
@@ -105,6 +116,18 @@ impl FunctionCompiler {
         self
     }
 
+    /// Indicate if the nested functions of the function should be compiled lazily.
+    pub(crate) const fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Provide the copy of the interner that is shared by the lazily compiled functions.
+    pub(crate) fn interner_snapshot(mut self, interner_snapshot: InternerSnapshot) -> Self {
+        self.interner_snapshot = interner_snapshot;
+        self
+    }
+
     /// Set source map file path.
     pub(crate) fn source_path(mut self, source_path: SourcePath) -> Self {
         self.source_path = source_path;
@@ -121,11 +144,12 @@ impl FunctionCompiler {
         lexical_environment: Scope,
         scopes: &FunctionScopes,
         contains_direct_eval: bool,
-        interner: &mut Interner,
+        interner: &Interner,
     ) -> Gc<CodeBlock> {
         self.strict = self.strict || body.strict();
 
         let length = parameters.length();
+        let has_prototype_property = self.has_prototype_property();
 
         let mut compiler = ByteCompiler::new(
             self.name,
@@ -142,9 +166,16 @@ impl FunctionCompiler {
         );
 
         compiler.length = length;
+        compiler.lazy_functions = self.lazy;
+        compiler.interner_snapshot = self.interner_snapshot;
+        compiler.body_start = Some(boa_ast::Spanned::span(body).start());
+        #[cfg(feature = "debugger")]
+        {
+            compiler.body_span = Some(boa_ast::Spanned::span(body));
+        }
         compiler.code_block_flags.set(
             CodeBlockFlags::HAS_PROTOTYPE_PROPERTY,
-            !self.arrow && !self.method && !self.r#async && !self.generator,
+            has_prototype_property,
         );
 
         if self.arrow {
@@ -229,4 +260,151 @@ impl FunctionCompiler {
 
         Gc::new(code)
     }
+
+    /// Returns `true` if the function objects of the function have a `"prototype"` property.
+    const fn has_prototype_property(&self) -> bool {
+        !self.arrow && !self.method && !self.r#async && !self.generator
+    }
+
+    /// Defers the compilation of a function until it is first called.
+    ///
+    /// Returns a placeholder code block, which has everything needed to create function objects
+    /// but no bytecode. The scope analysis of the function was already done by the parser, so
+    /// the function is compiled exactly as [`FunctionCompiler::compile`] would compile it now,
+    /// with a copy of `interner`.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn compile_lazily(
+        mut self,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+        variable_environment: Scope,
+        lexical_environment: Scope,
+        scopes: &FunctionScopes,
+        contains_direct_eval: bool,
+        interner: &Interner,
+    ) -> Gc<CodeBlock> {
+        self.strict = self.strict || body.strict();
+        self.interner_snapshot.take(interner);
+
+        let mut code = CodeBlock::new(self.name.clone(), parameters.length(), self.strict);
+        let mut flags = code.flags.get();
+        flags.set(CodeBlockFlags::IS_ASYNC, self.r#async);
+        flags.set(CodeBlockFlags::IS_GENERATOR, self.generator);
+        flags.set(
+            CodeBlockFlags::HAS_PROTOTYPE_PROPERTY,
+            self.has_prototype_property(),
+        );
+        code.flags.set(flags);
+        if self.arrow {
+            code.this_mode = ThisMode::Lexical;
+        }
+        code.source_info = SourceInfo::new(
+            SourceMap::new(
+                Box::default(),
+                self.source_path.clone(),
+                Some(boa_ast::Spanned::span(body).start()),
+            ),
+            self.name.clone(),
+            self.spanned_source_text.clone(),
+        );
+        code.uncompiled = Some(UncompiledFunction {
+            code: Gc::new(OnceCell::new()),
+            source: Rc::new(Cell::new(Some(Box::new(FunctionSource {
+                compiler: self,
+                // Cloning the body only clones a reference to its statements.
+                parameters: parameters.clone(),
+                body: body.clone(),
+                variable_environment,
+                lexical_environment,
+                scopes: scopes.clone(),
+                contains_direct_eval,
+            })))),
+        });
+
+        Gc::new(code)
+    }
+}
+
+/// A copy of the interner of a script, which its lazily compiled functions are compiled with.
+///
+/// A function object can be called from a context other than the one it was created in, whose
+/// interner can't resolve the symbols of its AST. The copy is only taken when the first lazily
+/// compiled function of the script is found, and is shared by all of them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InternerSnapshot(Rc<OnceCell<Interner>>);
+
+impl InternerSnapshot {
+    /// Takes a copy of `interner`, if it was not taken yet.
+    fn take(&self, interner: &Interner) {
+        self.0.get_or_init(|| interner.clone());
+    }
+
+    /// Gets the copy of the interner.
+    fn get(&self) -> &Interner {
+        self.0
+            .get()
+            .expect("the interner must be copied before a function is compiled lazily")
+    }
+}
+
+/// The AST of a function and the state of the compiler when the function was found.
+#[derive(Debug)]
+struct FunctionSource {
+    compiler: FunctionCompiler,
+    parameters: FormalParameterList,
+    body: FunctionBody,
+    variable_environment: Scope,
+    lexical_environment: Scope,
+    scopes: FunctionScopes,
+    contains_direct_eval: bool,
+}
+
+/// The AST of a function that is only compiled when it is first called.
+///
+/// It is stored in the placeholder [`CodeBlock`] of the function, which function objects use
+/// until they are first called.
+#[derive(Clone, Trace, Finalize)]
+pub(crate) struct UncompiledFunction {
+    // Shared between clones, so a function is compiled at most once.
+    code: Gc<OnceCell<Gc<CodeBlock>>>,
+    // SAFETY: The AST of a function doesn't contain any traceable types.
+    #[unsafe_ignore_trace]
+    source: Rc<Cell<Option<Box<FunctionSource>>>>,
+}
+
+impl std::fmt::Debug for UncompiledFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UncompiledFunction")
+            .field("code", &self.code)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UncompiledFunction {
+    /// Returns the code block, compiling it if this is the first call.
+    pub(crate) fn get(&self) -> Gc<CodeBlock> {
+        self.code
+            .get_or_init(|| {
+                let source = self
+                    .source
+                    .take()
+                    .expect("the source of a function must exist until it is compiled");
+                let interner_snapshot = source.compiler.interner_snapshot.clone();
+                source.compiler.compile(
+                    &source.parameters,
+                    &source.body,
+                    source.variable_environment,
+                    source.lexical_environment,
+                    &source.scopes,
+                    source.contains_direct_eval,
+                    interner_snapshot.get(),
+                )
+            })
+            .clone()
+    }
+
+    /// Returns the code block if it was already compiled.
+    pub(crate) fn get_if_compiled(&self) -> Option<&Gc<CodeBlock>> {
+        self.code.get()
+    }
 }
@@ -86,7 +86,7 @@ use thin_vec::ThinVec;
 pub(crate) use declarations::{
     global_declaration_instantiation_context, prepare_eval_declaration_instantiation,
 };
-pub(crate) use function::FunctionCompiler;
+pub(crate) use function::{FunctionCompiler, InternerSnapshot, UncompiledFunction};
 pub(crate) use jump_control::JumpControlInfo;
 pub(crate) use register::*;
 
@@ -523,6 +523,10 @@ pub struct ByteCompiler<'ctx> {
 
     pub(crate) local_binding_registers: FxHashMap<IdentifierReference, u32>,
 
+    /// The registers of the immutable bindings in [`Self::local_binding_registers`].
+    #[cfg(feature = "debugger")]
+    immutable_local_registers: Vec<u32>,
+
     /// The current variable scope.
     pub(crate) variable_scope: Scope,
 
@@ -552,10 +556,28 @@ pub struct ByteCompiler<'ctx> {
     /// Whether the function is in a `with` statement.
     pub(crate) in_with: bool,
 
+    /// Whether nested functions are compiled when they are first called, instead of together
+    /// with this function.
+    pub(crate) lazy_functions: bool,
+
+    /// The copy of the interner that the lazily compiled functions are compiled with.
+    pub(crate) interner_snapshot: InternerSnapshot,
+
+    /// The position of the start of the body of the function, if the code is a function.
+    pub(crate) body_start: Option<Position>,
+
+    /// The span of the body of the function, which contains the code breakpoints resolve to.
+    #[cfg(feature = "debugger")]
+    pub(crate) body_span: Option<boa_ast::Span>,
+
+    /// The spans of the bodies of the functions nested in this one.
+    #[cfg(feature = "debugger")]
+    nested_function_spans: Vec<boa_ast::Span>,
+
     /// Used to determine if a we emitted a `CreateUnmappedArgumentsObject` opcode
     pub(crate) emitted_mapped_arguments_object_opcode: bool,
 
-    pub(crate) interner: &'ctx mut Interner,
+    pub(crate) interner: &'ctx Interner,
     spanned_source_text: SpannedSourceText,
 
     pub(crate) global_lexs: Vec<u32>,
@@ -602,7 +624,7 @@ impl<'ctx> ByteCompiler<'ctx> {
         lexical_scope: Scope,
         is_async: bool,
         is_generator: bool,
-        interner: &'ctx mut Interner,
+        interner: &'ctx Interner,
         in_with: bool,
         spanned_source_text: SpannedSourceText,
         source_path: SourcePath,
@@ -654,6 +676,8 @@ impl<'ctx> ByteCompiler<'ctx> {
             constants: ThinVec::default(),
             bindings: Vec::default(),
             local_binding_registers: FxHashMap::default(),
+            #[cfg(feature = "debugger")]
+            immutable_local_registers: Vec::new(),
             this_mode: ThisMode::Global,
             params: FormalParameterList::default(),
             parameter_scope: Scope::default(),
@@ -680,6 +704,13 @@ impl<'ctx> ByteCompiler<'ctx> {
             #[cfg(feature = "annex-b")]
             annex_b_function_names: Vec::new(),
             in_with,
+            lazy_functions: false,
+            interner_snapshot: InternerSnapshot::default(),
+            body_start: None,
+            #[cfg(feature = "debugger")]
+            body_span: None,
+            #[cfg(feature = "debugger")]
+            nested_function_spans: Vec::new(),
             emitted_mapped_arguments_object_opcode: false,
 
             global_lexs: Vec::new(),
@@ -778,6 +809,19 @@ impl<'ctx> ByteCompiler<'ctx> {
         BindingKind::Stack(index)
     }
 
+    /// Stores a binding that doesn't escape in `register`.
+    pub(crate) fn insert_local_binding(&mut self, binding: IdentifierReference, register: u32) {
+        #[cfg(feature = "debugger")]
+        if self
+            .lexical_scope
+            .is_binding_mutable(binding.locator().name())
+            == Some(false)
+        {
+            self.immutable_local_registers.push(register);
+        }
+        self.local_binding_registers.insert(binding, register);
+    }
+
     #[inline]
     pub(crate) fn insert_binding(&mut self, binding: IdentifierReference) -> BindingKind {
         if binding.is_global_object() {
@@ -792,12 +836,12 @@ impl<'ctx> ByteCompiler<'ctx> {
         }
 
         if binding.local() {
-            return BindingKind::Local(Some(
-                *self
-                    .local_binding_registers
-                    .entry(binding)
-                    .or_insert_with(|| self.register_allocator.alloc_persistent().index()),
-            ));
+            if let Some(register) = self.local_binding_registers.get(&binding) {
+                return BindingKind::Local(Some(*register));
+            }
+            let register = self.register_allocator.alloc_persistent().index();
+            self.insert_local_binding(binding, register);
+            return BindingKind::Local(Some(register));
         }
 
         if let Some(index) = self.bindings_map.get(&binding.locator()) {
@@ -818,6 +862,52 @@ impl<'ctx> ByteCompiler<'ctx> {
         index
     }
 
+    /// Compiles a nested function with `compiler` in the current scope, returning its index
+    /// into the constants.
+    ///
+    /// If [`Self::lazy_functions`] is set, the function is only compiled when it is first
+    /// called.
+    pub(crate) fn push_nested_function(
+        &mut self,
+        compiler: FunctionCompiler,
+        parameters: &FormalParameterList,
+        body: &FunctionBody,
+        scopes: &FunctionScopes,
+        contains_direct_eval: bool,
+    ) -> u32 {
+        #[cfg(feature = "debugger")]
+        self.nested_function_spans.push(body.span());
+
+        let compiler = compiler
+            .lazy(self.lazy_functions)
+            .interner_snapshot(self.interner_snapshot.clone());
+        if !self.lazy_functions {
+            let code = compiler.compile(
+                parameters,
+                body,
+                self.variable_scope.clone(),
+                self.lexical_scope.clone(),
+                scopes,
+                contains_direct_eval,
+                self.interner,
+            );
+            return self.push_function_to_constants(code);
+        }
+
+        let function = compiler.compile_lazily(
+            parameters,
+            body,
+            self.variable_scope.clone(),
+            self.lexical_scope.clone(),
+            scopes,
+            contains_direct_eval,
+            self.interner,
+        );
+        let index = self.constants.len() as u32;
+        self.constants.push(Constant::UncompiledFunction(function));
+        index
+    }
+
     fn emit_binding(&mut self, opcode: BindingOpcode, name: JsString, value: &Register) {
         match opcode {
             BindingOpcode::Var => {
@@ -2211,7 +2301,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                                 } else {
                                     self.bytecode.emit_store_undefined(reg.variable());
                                 }
-                                self.local_binding_registers.insert(binding, reg.index());
+                                self.insert_local_binding(binding, reg.index());
                             } else {
                                 let value = self.register_allocator.alloc();
                                 if let Some(init) = variable.init() {
@@ -2258,7 +2348,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                             if binding.local() {
                                 let reg = self.register_allocator.alloc_persistent();
                                 self.compile_expr(init, &reg);
-                                self.local_binding_registers.insert(binding, reg.index());
+                                self.insert_local_binding(binding, reg.index());
                             } else {
                                 let value = self.register_allocator.alloc();
                                 self.compile_expr(init, &value);
@@ -2389,7 +2479,17 @@ impl<'ctx> ByteCompiler<'ctx> {
             StatementListItem::Statement(stmt) => {
                 self.compile_stmt(stmt, use_expr, false);
             }
-            StatementListItem::Declaration(decl) => self.compile_decl(decl, block),
+            StatementListItem::Declaration(decl) => {
+                #[cfg(any(feature = "debugger", feature = "coverage"))]
+                if let Declaration::Lexical(lexical) = &**decl {
+                    let position = statement::variable_list_position(lexical.variable_list());
+                    self.push_source_position(position);
+                    self.compile_decl(decl, block);
+                    self.pop_source_position();
+                    return;
+                }
+                self.compile_decl(decl, block);
+            }
         }
     }
 
@@ -2454,7 +2554,7 @@ impl<'ctx> ByteCompiler<'ctx> {
 
         let spanned_source_text = SpannedSourceText::new(self.source_text(), linear_span);
 
-        let code = FunctionCompiler::new(spanned_source_text)
+        let compiler = FunctionCompiler::new(spanned_source_text)
             .name(name)
             .generator(generator)
             .r#async(r#async)
@@ -2462,18 +2562,15 @@ impl<'ctx> ByteCompiler<'ctx> {
             .arrow(arrow)
             .in_with(self.in_with)
             .name_scope(name_scope.cloned())
-            .source_path(self.source_path.clone())
-            .compile(
-                parameters,
-                body,
-                self.variable_scope.clone(),
-                self.lexical_scope.clone(),
-                scopes,
-                function.contains_direct_eval,
-                self.interner,
-            );
+            .source_path(self.source_path.clone());
 
-        self.push_function_to_constants(code)
+        self.push_nested_function(
+            compiler,
+            parameters,
+            body,
+            scopes,
+            function.contains_direct_eval,
+        )
     }
 
     /// Compiles a function AST Node into bytecode, setting its corresponding binding or
@@ -2534,7 +2631,7 @@ impl<'ctx> ByteCompiler<'ctx> {
 
         let spanned_source_text = SpannedSourceText::new(self.source_text(), linear_span);
 
-        let code = FunctionCompiler::new(spanned_source_text)
+        let compiler = FunctionCompiler::new(spanned_source_text)
             .name(name)
             .generator(generator)
             .r#async(r#async)
@@ -2543,18 +2640,15 @@ impl<'ctx> ByteCompiler<'ctx> {
             .method(true)
             .in_with(self.in_with)
             .name_scope(name_scope.cloned())
-            .source_path(self.source_path.clone())
-            .compile(
-                parameters,
-                body,
-                self.variable_scope.clone(),
-                self.lexical_scope.clone(),
-                scopes,
-                function.contains_direct_eval,
-                self.interner,
-            );
+            .source_path(self.source_path.clone());
 
-        let index = self.push_function_to_constants(code);
+        let index = self.push_nested_function(
+            compiler,
+            parameters,
+            body,
+            scopes,
+            function.contains_direct_eval,
+        );
         let dst = self.register_allocator.alloc();
         self.emit_get_function(&dst, index);
         dst
@@ -2584,7 +2678,7 @@ impl<'ctx> ByteCompiler<'ctx> {
 
         let spanned_source_text = SpannedSourceText::new(self.source_text(), linear_span);
 
-        let code = FunctionCompiler::new(spanned_source_text)
+        let compiler = FunctionCompiler::new(spanned_source_text)
             .name(name)
             .generator(generator)
             .r#async(r#async)
@@ -2593,18 +2687,15 @@ impl<'ctx> ByteCompiler<'ctx> {
             .method(true)
             .in_with(self.in_with)
             .name_scope(function.name_scope.cloned())
-            .source_path(self.source_path.clone())
-            .compile(
-                parameters,
-                body,
-                self.variable_scope.clone(),
-                self.lexical_scope.clone(),
-                scopes,
-                function.contains_direct_eval,
-                self.interner,
-            );
+            .source_path(self.source_path.clone());
 
-        let index = self.push_function_to_constants(code);
+        let index = self.push_nested_function(
+            compiler,
+            parameters,
+            body,
+            scopes,
+            function.contains_direct_eval,
+        );
         let dst = self.register_allocator.alloc();
         self.emit_get_function(&dst, index);
         dst
@@ -2770,6 +2861,21 @@ impl<'ctx> ByteCompiler<'ctx> {
 
         let source_map_entries = self.source_map_builder.build(final_bytecode_len.as_u32());
 
+        #[cfg(feature = "debugger")]
+        let local_bindings = {
+            let mut bindings = self
+                .local_binding_registers
+                .iter()
+                .map(|(binding, register)| crate::debugger::LocalBinding {
+                    name: binding.locator().name().clone(),
+                    register: *register,
+                    mutable: !self.immutable_local_registers.contains(register),
+                })
+                .collect::<Vec<_>>();
+            bindings.sort_by_key(|binding| binding.register);
+            bindings.into_boxed_slice()
+        };
+
         CodeBlock {
             length: self.length,
             register_count,
@@ -2783,7 +2889,7 @@ impl<'ctx> ByteCompiler<'ctx> {
             flags: Cell::new(self.code_block_flags),
             ic: self.ic.into_boxed_slice(),
             source_info: SourceInfo::new(
-                SourceMap::new(source_map_entries, self.source_path),
+                SourceMap::new(source_map_entries, self.source_path, self.body_start),
                 self.function_name,
                 self.spanned_source_text,
             ),
@@ -2793,6 +2899,13 @@ impl<'ctx> ByteCompiler<'ctx> {
             debug_id: CodeBlock::get_next_codeblock_id(),
             #[cfg(feature = "trace")]
             traced: Cell::new(false),
+            #[cfg(feature = "debugger")]
+            debug_info: crate::debugger::CodeBlockDebugInfo::new(
+                local_bindings,
+                self.body_span,
+                self.nested_function_spans.into_boxed_slice(),
+            ),
+            uncompiled: None,
         }
     }
 
@@ -1,4 +1,5 @@
 use boa_ast::{
+    Spanned,
     declaration::Binding,
     operations::bound_names,
     scope::BindingLocatorError,
@@ -203,7 +204,7 @@ impl ByteCompiler<'_> {
             && binding.local()
         {
             let reg = self.register_allocator.alloc_persistent();
-            self.local_binding_registers.insert(binding, reg.index());
+            self.insert_local_binding(binding, reg.index());
             self.bytecode.emit_iterator_value(reg.variable());
         } else {
             let value = self.register_allocator.alloc();
@@ -291,7 +292,11 @@ impl ByteCompiler<'_> {
         if for_of_loop.r#await() {
             let value = self.register_allocator.alloc();
             self.bytecode.emit_iterator_result(value.variable());
+
+            // NOTE: The position of the awaited iterable is shown in async stack traces.
+            self.push_source_position(for_of_loop.iterable().span().start());
             self.bytecode.emit_await(value.variable());
+            self.pop_source_position();
             let resume_kind = self.register_allocator.alloc();
             self.pop_into_register(&resume_kind);
             self.pop_into_register(&value);
@@ -320,7 +325,7 @@ impl ByteCompiler<'_> {
             && ident.local()
         {
             let reg = self.register_allocator.alloc_persistent();
-            self.local_binding_registers.insert(ident, reg.index());
+            self.insert_local_binding(ident, reg.index());
             self.bytecode.emit_iterator_value(reg.variable());
 
             self.push_handler()
@@ -1,6 +1,8 @@
 use super::jump_control::{JumpRecord, JumpRecordAction, JumpRecordKind};
 use crate::{bytecompiler::ByteCompiler, vm::CallFrame};
 use boa_ast::Statement;
+#[cfg(any(feature = "debugger", feature = "coverage"))]
+use boa_ast::{Position, Spanned, declaration::Binding, declaration::VariableList};
 
 mod block;
 mod r#break;
@@ -15,6 +17,13 @@ mod with;
 impl ByteCompiler<'_> {
     /// Compiles a [`Statement`] `boa_ast` node.
     pub fn compile_stmt(&mut self, node: &Statement, use_expr: bool, root_statement: bool) {
+        #[cfg(any(feature = "debugger", feature = "coverage"))]
+        let position = statement_position(node);
+        #[cfg(any(feature = "debugger", feature = "coverage"))]
+        if position.is_some() {
+            self.push_source_position(position);
+        }
+
         match node {
             Statement::Var(var) => self.compile_var_decl(var),
             Statement::If(node) => self.compile_if(node, use_expr),
@@ -98,7 +107,15 @@ impl ByteCompiler<'_> {
                 }
             }
             Statement::With(with) => self.compile_with(with, use_expr),
-            Statement::Empty | Statement::Debugger => {}
+            Statement::Debugger(_) => self.bytecode.emit_debugger(),
+            Statement::Empty | Statement::Error(_) => {}
+            // NOTE: `Statement` is non-exhaustive, but every statement it has must be compiled above.
+            _ => unreachable!("the statement should be compiled: {node:?}"),
+        }
+
+        #[cfg(any(feature = "debugger", feature = "coverage"))]
+        if position.is_some() {
+            self.pop_source_position();
         }
     }
 
@@ -142,3 +159,36 @@ impl ByteCompiler<'_> {
         actions
     }
 }
+
+/// Returns the position that a debugger and code coverage map to the start of `statement`.
+///
+/// Statements don't have spans of their own, so this is the start of the expression or
+/// binding that the statement evaluates first.
+#[cfg(any(feature = "debugger", feature = "coverage"))]
+fn statement_position(statement: &Statement) -> Option<Position> {
+    let expression = match statement {
+        Statement::Var(var) => return variable_list_position(&var.0),
+        Statement::Expression(expression) => expression,
+        Statement::If(node) => node.cond(),
+        Statement::ForLoop(node) => node.condition()?,
+        Statement::ForInLoop(node) => node.target(),
+        Statement::ForOfLoop(node) => node.iterable(),
+        Statement::WhileLoop(node) => node.condition(),
+        Statement::Switch(node) => node.val(),
+        Statement::Return(node) => node.target()?,
+        Statement::With(node) => node.expression(),
+        Statement::Debugger(span) => return Some(span.start()),
+        _ => return None,
+    };
+    Some(expression.span().start())
+}
+
+/// Returns the position of the first binding declared by a `var`, `let` or `const` declaration.
+#[cfg(any(feature = "debugger", feature = "coverage"))]
+pub(crate) fn variable_list_position(list: &VariableList) -> Option<Position> {
+    let variable = list.as_ref().first()?;
+    Some(match variable.binding() {
+        Binding::Identifier(identifier) => identifier.span().start(),
+        Binding::Pattern(pattern) => pattern.span().start(),
+    })
+}
@@ -18,10 +18,11 @@ use timezone_provider::experimental_tzif::ZeroCompiledTzdbProvider;
 use crate::job::Job;
 use crate::js_error;
 use crate::module::DynModuleLoader;
-use crate::vm::{CodeBlock, RuntimeLimits, create_function_object_fast};
+use crate::vm::{CodeBlock, RuntimeLimits, StackFrame, create_function_object_fast};
 use crate::{
     HostDefined, JsNativeError, JsResult, JsString, JsValue, Source, builtins,
     class::{Class, ClassBuilder},
+    heap_snapshot::HeapSnapshot,
     job::{JobExecutor, SimpleJobExecutor},
     js_string,
     module::{IdleModuleLoader, ModuleLoader, SimpleModuleLoader},
@@ -31,6 +32,8 @@ use crate::{
     property::{Attribute, PropertyDescriptor, PropertyKey},
     realm::Realm,
     script::Script,
+    snapshot::{SnapshotBase, SnapshotError},
+    source_map::{SourceMap, SourceMaps},
     vm::{ActiveRunnable, CallFrame, Vm},
 };
 
@@ -125,11 +128,18 @@ pub struct Context {
     module_loader: Rc<dyn DynModuleLoader>,
 
     optimizer_options: OptimizerOptions,
+    lazy_compilation: bool,
     root_shape: RootShape,
 
+    /// The source maps of the generated code run by the context.
+    pub(crate) source_maps: SourceMaps,
+
     /// Unique identifier for each parser instance used during the context lifetime.
     parser_identifier: u32,
 
+    /// The objects marked by [`Context::mark_snapshot_base`].
+    snapshot_base: Option<Box<SnapshotBase>>,
+
     data: HostDefined<dyn Any>,
 }
 
@@ -146,7 +156,8 @@ impl std::fmt::Debug for Context {
             .field("hooks", &"HostHooks")
             .field("clock", &"Clock")
             .field("module_loader", &"ModuleLoader")
-            .field("optimizer_options", &self.optimizer_options);
+            .field("optimizer_options", &self.optimizer_options)
+            .field("lazy_compilation", &self.lazy_compilation);
 
         #[cfg(feature = "intl")]
         debug.field("intl_provider", &self.intl_provider);
@@ -470,6 +481,135 @@ impl Context {
         self.vm.trace = trace;
     }
 
+    /// Attaches a [`Debugger`][crate::debugger::Debugger] to the context, replacing the
+    /// previous one.
+    ///
+    /// The debugger is called every time execution pauses. See the [`debugger`][crate::debugger]
+    /// module for when that happens.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn attach_debugger<D: crate::debugger::Debugger + 'static>(&mut self, debugger: Rc<D>) {
+        self.vm.debugger.attach(debugger);
+    }
+
+    /// Detaches the attached [`Debugger`][crate::debugger::Debugger], returning it.
+    ///
+    /// Breakpoints are kept, but execution no longer pauses until another debugger is attached.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn detach_debugger(&mut self) -> Option<Rc<dyn crate::debugger::Debugger>> {
+        self.vm.debugger.detach()
+    }
+
+    /// Sets a breakpoint, returning its identifier.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn set_breakpoint(
+        &mut self,
+        breakpoint: crate::debugger::Breakpoint,
+    ) -> crate::debugger::BreakpointId {
+        self.vm.debugger.set_breakpoint(breakpoint)
+    }
+
+    /// Removes a breakpoint, returning `true` if it existed.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn remove_breakpoint(&mut self, id: crate::debugger::BreakpointId) -> bool {
+        self.vm.debugger.remove_breakpoint(id)
+    }
+
+    /// Removes all the breakpoints.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn clear_breakpoints(&mut self) {
+        self.vm.debugger.clear_breakpoints();
+    }
+
+    /// Sets when execution pauses on thrown exceptions.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    pub fn set_exception_pause_mode(&mut self, mode: crate::debugger::ExceptionPauseMode) {
+        self.vm.debugger.set_exception_pause_mode(mode);
+    }
+
+    /// Returns a handle that can request a pause of the running code, from any thread.
+    #[cfg(feature = "debugger")]
+    #[inline]
+    #[must_use]
+    pub fn debugger_pause_handle(&self) -> crate::debugger::PauseHandle {
+        self.vm.debugger.pause_handle()
+    }
+
+    /// Starts recording a CPU profile, sampling the stack of the running code every `interval`.
+    ///
+    /// If a profile is already being recorded, it is discarded. See the
+    /// [`profiler`][crate::profiler] module for more information.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub fn start_cpu_profile(&mut self, interval: std::time::Duration) {
+        self.vm.profiler.start(interval);
+    }
+
+    /// Stops recording the CPU profile started with [`Context::start_cpu_profile`], and
+    /// returns it, or `None` if no profile was being recorded.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub fn stop_cpu_profile(&mut self) -> Option<crate::profiler::CpuProfile> {
+        self.vm.profiler.stop()
+    }
+
+    /// Starts collecting the code coverage of the code that runs.
+    ///
+    /// If coverage is already being collected, its counts are discarded. See the
+    /// [`coverage`][crate::coverage] module for more information.
+    ///
+    /// This disables the [lazy compilation][Context::set_lazy_compilation] of nested functions,
+    /// since functions that were never compiled can't be reported as never run.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn start_coverage(&mut self) {
+        self.lazy_compilation = false;
+        self.vm.coverage.start();
+    }
+
+    /// Returns the code coverage collected since [`Context::start_coverage`] or since the
+    /// previous call to this method, and keeps collecting it.
+    ///
+    /// Returns `None` if coverage is not being collected.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn take_coverage(&mut self) -> Option<crate::coverage::Coverage> {
+        self.vm.coverage.take()
+    }
+
+    /// Stops collecting the code coverage, and returns the coverage collected since
+    /// [`Context::start_coverage`] or since the last call to [`Context::take_coverage`].
+    ///
+    /// Returns `None` if coverage was not being collected.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn stop_coverage(&mut self) -> Option<crate::coverage::Coverage> {
+        self.vm.coverage.stop()
+    }
+
+    /// Starts collecting execution statistics of the VM.
+    ///
+    /// If statistics are already being collected, they are discarded. See the
+    /// [`vm_stats`][crate::vm_stats] module for more information.
+    #[cfg(feature = "vm-stats")]
+    #[inline]
+    pub fn start_vm_stats(&mut self) {
+        self.vm.stats.start();
+    }
+
+    /// Stops collecting the statistics started with [`Context::start_vm_stats`], and returns
+    /// them, or `None` if no statistics were being collected.
+    #[cfg(feature = "vm-stats")]
+    #[inline]
+    pub fn stop_vm_stats(&mut self) -> Option<crate::vm_stats::VmStats> {
+        self.vm.stats.stop()
+    }
+
     /// Get optimizer options.
     #[inline]
     #[must_use]
@@ -482,6 +622,56 @@ impl Context {
         self.optimizer_options = optimizer_options;
     }
 
+    /// Returns `true` if nested functions are compiled lazily.
+    #[inline]
+    #[must_use]
+    pub const fn lazy_compilation(&self) -> bool {
+        self.lazy_compilation
+    }
+
+    /// Enable or disable the lazy compilation of nested functions.
+    ///
+    /// When enabled, which is the default, only the top level code of a script, module or `eval`
+    /// is compiled upfront. The functions it contains are compiled from their AST when they are
+    /// first called, so functions that are never called are never compiled. Until then, the
+    /// functions of a script keep a copy of the [`Interner`] they were parsed with, so they can be
+    /// called from another context. Disabling it compiles every function upfront, which is useful
+    /// to inspect the bytecode of a whole script.
+    #[inline]
+    pub fn set_lazy_compilation(&mut self, lazy_compilation: bool) {
+        self.lazy_compilation = lazy_compilation;
+    }
+
+    /// Returns `true` if the source maps referenced by `//# sourceMappingURL=` comments are
+    /// loaded.
+    #[inline]
+    #[must_use]
+    pub const fn source_map_loading(&self) -> bool {
+        self.source_maps.load_from_comments
+    }
+
+    /// Enable or disable the loading of the source maps referenced by the
+    /// `//# sourceMappingURL=` comments of scripts and modules, when they are parsed.
+    ///
+    /// Disabled by default. See the [`source_map`][crate::source_map] module for the supported
+    /// URLs.
+    #[inline]
+    pub fn set_source_map_loading(&mut self, load: bool) {
+        self.source_maps.load_from_comments = load;
+    }
+
+    /// Registers the source map of the generated code at `path`.
+    ///
+    /// The scripts and modules parsed afterwards from `path` use it to show their original
+    /// positions in stack traces, instead of the map referenced by their
+    /// `//# sourceMappingURL=` comment.
+    #[inline]
+    pub fn register_source_map(&mut self, path: &Path, source_map: SourceMap) {
+        self.source_maps
+            .registered
+            .insert(path.to_path_buf(), Rc::new(source_map));
+    }
+
     /// Changes the strictness mode of the context.
     #[inline]
     pub fn strict(&mut self, strict: bool) {
@@ -513,16 +703,84 @@ impl Context {
         self.kept_alive.clear();
     }
 
-    /// Retrieves the current stack trace of the context.
+    /// Retrieves the call frames of the JavaScript code that is running.
     ///
-    /// The stack trace is returned ordered with the most recent frames first.
+    /// The call frames are returned ordered with the most recent frames first.
     #[inline]
-    pub fn stack_trace(&self) -> impl Iterator<Item = &CallFrame> {
+    pub fn call_frames(&self) -> impl Iterator<Item = &CallFrame> {
         // The first frame is always a dummy frame (see `Vm` implementation for more details),
         // so skip the dummy frame and return the reversed list so that the most recent frames are first.
         self.vm.frames.iter().skip(1).rev()
     }
 
+    /// Retrieves the current stack trace of the context as [`StackFrame`]s, including the
+    /// native functions being called.
+    ///
+    /// The stack trace is returned ordered with the most recent frames first, followed by the async
+    /// functions and generators awaiting the completion of the current job. Both are limited to
+    /// the [backtrace limit][RuntimeLimits::backtrace_limit] of the context.
+    #[must_use]
+    pub fn stack_trace(&self) -> Vec<StackFrame> {
+        let limit = self.vm.runtime_limits.backtrace_limit();
+        self.vm
+            .shadow_stack
+            .take(limit, self.vm.frame().pc)
+            .with_async_stack(self.vm.async_stack(limit))
+            .stack_frames()
+    }
+
+    /// Takes a snapshot of all the values managed by the garbage collector.
+    ///
+    /// This runs a garbage collection before walking the heap. See [`HeapSnapshot`] for
+    /// more information.
+    #[must_use]
+    pub fn heap_snapshot(&self) -> HeapSnapshot {
+        HeapSnapshot::new(self)
+    }
+
+    /// Marks the objects of the current realm as the base of future snapshots.
+    ///
+    /// Every object reachable from the global object at this point is expected to exist in any
+    /// context a snapshot is restored into, and is not serialized by [`Context::snapshot`]. This
+    /// should be called once the native extensions of the host are registered, before running
+    /// the scripts whose state should be captured.
+    ///
+    /// See the [`snapshot`][crate::snapshot] module for more information.
+    pub fn mark_snapshot_base(&mut self) {
+        self.snapshot_base = Some(Box::new(SnapshotBase::new(self, true)));
+    }
+
+    /// Serializes the state of the current realm into a snapshot.
+    ///
+    /// The snapshot only stores the difference between the realm and its base, marked by
+    /// [`Context::mark_snapshot_base`], and can be restored with [`Context::restore_snapshot`]
+    /// into a context with the same base. The intrinsics are not part of the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot base was not marked, or if an object reachable from the
+    /// global object cannot be serialized.
+    pub fn snapshot(&mut self) -> Result<Vec<u8>, SnapshotError> {
+        let base = self
+            .snapshot_base
+            .as_deref()
+            .ok_or(SnapshotError::MissingBase)?;
+        crate::snapshot::encode(self, base)
+    }
+
+    /// Restores the state of the current realm from a snapshot taken by [`Context::snapshot`].
+    ///
+    /// The context must be set up in the same way as the context the snapshot was taken from
+    /// when its base was marked, and should not have run any code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot is invalid, or if it was taken from a context that was
+    /// set up differently. The realm may be partially restored in that case.
+    pub fn restore_snapshot(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        crate::snapshot::restore(self, snapshot)
+    }
+
     /// Replaces the currently active realm with `realm`, and returns the old realm.
     #[inline]
     pub fn enter_realm(&mut self, realm: Realm) -> Realm {
@@ -1257,8 +1515,11 @@ impl ContextBuilder {
             job_executor,
             module_loader,
             optimizer_options: OptimizerOptions::OPTIMIZE_ALL,
+            lazy_compilation: true,
             root_shape,
+            source_maps: SourceMaps::default(),
             parser_identifier: 0,
+            snapshot_base: None,
             can_block: self.can_block,
             data: HostDefined::default(),
         };
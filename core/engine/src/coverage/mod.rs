@@ -0,0 +1,586 @@
+//! Code coverage of JavaScript code.
+//!
+//! While coverage is collected with [`Context::start_coverage`], the VM counts how many times
+//! every instruction of every function runs. When the coverage is taken, the counts are mapped
+//! back to the source through the positions that the compiler records for every statement, which
+//! gives the number of times each function, block and line ran:
+//!
+//! - Every function has a range that covers its source, with the number of times it was called.
+//! - The blocks of a function that ran a different number of times than the function, like the
+//!   branches of an `if` or the body of a loop, have ranges nested in the range of the function.
+//! - Every line that starts a statement has the number of times its statements ran.
+//!
+//! The [`Coverage`] returned by [`Context::take_coverage`] can be written as the JSON format of
+//! the [`Profiler.takePreciseCoverage`][precise] method of V8, which tools like [c8] turn into
+//! reports, or as an [LCOV] tracefile.
+//!
+//! Only functions that were compiled are reported. Starting the coverage disables the
+//! [lazy compilation][Context::set_lazy_compilation] of nested functions, so the functions
+//! nested in a function that never ran are reported with a count of 0, as long as their code was
+//! compiled after the coverage started.
+//!
+//! ```
+//! # use boa_engine::{Context, Source};
+//! let mut context = Context::default();
+//! context.start_coverage();
+//! context
+//!     .eval(Source::from_bytes(
+//!         "function sign(x) { if (x < 0) { return -1; } return 1; } sign(5);",
+//!     ))
+//!     .unwrap();
+//! let coverage = context.stop_coverage().unwrap();
+//!
+//! let mut precise_coverage = Vec::new();
+//! coverage.write_json(&mut precise_coverage).unwrap();
+//! let lcov = coverage.to_lcov_string();
+//! ```
+//!
+//! [precise]: https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#method-takePreciseCoverage
+//! [c8]: https://github.com/bcoe/c8
+//! [LCOV]: https://github.com/linux-test-project/lcov
+
+use crate::{
+    Context, JsString,
+    spanned_source_text::SourceText,
+    vm::{CodeBlock, Constant, SourcePath},
+};
+use boa_gc::Gc;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Write as _, io};
+
+#[cfg(test)]
+mod tests;
+
+/// A range of source code, with the number of times it ran.
+///
+/// The offsets are in UTF-16 code units from the start of the source, like the offsets of V8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverageRange {
+    start: usize,
+    end: usize,
+    count: u64,
+}
+
+impl CoverageRange {
+    /// Returns the offset of the start of the range.
+    #[must_use]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the offset of the end of the range, which is not part of the range.
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the number of times the code of the range ran.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// The coverage of a function.
+#[derive(Debug, Clone)]
+pub struct FunctionCoverage {
+    function_name: JsString,
+    is_top_level: bool,
+    line: u32,
+    ranges: Vec<CoverageRange>,
+}
+
+impl FunctionCoverage {
+    /// Returns the name of the function, which is empty for anonymous functions and for the top
+    /// level code of a script.
+    #[must_use]
+    pub const fn function_name(&self) -> &JsString {
+        &self.function_name
+    }
+
+    /// Returns `true` if this is the top level code of a script, rather than a function.
+    #[must_use]
+    pub const fn is_top_level(&self) -> bool {
+        self.is_top_level
+    }
+
+    /// Returns the line number the function starts on.
+    #[must_use]
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the number of times the function was called.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.ranges[0].count
+    }
+
+    /// Returns the ranges of the function, sorted by their start.
+    ///
+    /// The first range covers the whole function, and the following ones cover the blocks that
+    /// ran a different number of times than the function.
+    #[must_use]
+    pub fn ranges(&self) -> &[CoverageRange] {
+        &self.ranges
+    }
+}
+
+/// The coverage of a script, a module or the code of an `eval`.
+#[derive(Debug, Clone)]
+pub struct ScriptCoverage {
+    path: SourcePath,
+    functions: Vec<FunctionCoverage>,
+    lines: BTreeMap<u32, u64>,
+}
+
+impl ScriptCoverage {
+    /// Returns the source of the script.
+    #[must_use]
+    pub const fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// Returns the coverage of the functions of the script, with its top level code first.
+    #[must_use]
+    pub fn functions(&self) -> &[FunctionCoverage] {
+        &self.functions
+    }
+
+    /// Returns the number of times the statements of each line ran, by line number.
+    ///
+    /// Only the lines that start a statement are listed.
+    pub fn lines(&self) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.lines.iter().map(|(&line, &count)| (line, count))
+    }
+}
+
+/// The code coverage taken with [`Context::take_coverage`] or [`Context::stop_coverage`].
+///
+/// See the [module level documentation][self] for more information.
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    scripts: Vec<ScriptCoverage>,
+}
+
+impl Coverage {
+    /// Returns the coverage of every script that ran.
+    #[must_use]
+    pub fn scripts(&self) -> &[ScriptCoverage] {
+        &self.scripts
+    }
+
+    /// Writes the coverage as the JSON result of the `Profiler.takePreciseCoverage` method of
+    /// V8 to `writer`.
+    pub fn write_json<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.to_serializable()).map_err(io::Error::from)
+    }
+
+    /// Returns the coverage as a JSON string, see [`Coverage::write_json`].
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(&self.to_serializable()).expect("coverage can always be serialized")
+    }
+
+    /// Writes the coverage of the scripts loaded from files as an LCOV tracefile to `writer`.
+    pub fn write_lcov<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_lcov_string().as_bytes())
+    }
+
+    /// Returns the coverage as an LCOV tracefile, see [`Coverage::write_lcov`].
+    #[must_use]
+    pub fn to_lcov_string(&self) -> String {
+        let mut output = String::new();
+        for script in &self.scripts {
+            let SourcePath::Path(path) = &script.path else {
+                continue;
+            };
+            let _ = writeln!(output, "TN:");
+            let _ = writeln!(output, "SF:{}", path.display());
+
+            // The top level code is not a function, and function names must be unique.
+            let mut names = FxHashSet::default();
+            let functions = script
+                .functions
+                .iter()
+                .filter(|function| !function.is_top_level)
+                .enumerate()
+                .map(|(index, function)| {
+                    let mut name = function.function_name.to_std_string_escaped();
+                    if name.is_empty() {
+                        name = format!("(anonymous_{index})");
+                    } else if names.contains(&name) {
+                        name = format!("{name}_{index}");
+                    }
+                    names.insert(name.clone());
+                    (name, function)
+                })
+                .collect::<Vec<_>>();
+            for (name, function) in &functions {
+                let _ = writeln!(output, "FN:{},{name}", function.line);
+            }
+            for (name, function) in &functions {
+                let _ = writeln!(output, "FNDA:{},{name}", function.count());
+            }
+            let hit = functions.iter().filter(|(_, f)| f.count() > 0).count();
+            let _ = writeln!(output, "FNF:{}", functions.len());
+            let _ = writeln!(output, "FNH:{hit}");
+
+            for (line, count) in script.lines() {
+                let _ = writeln!(output, "DA:{line},{count}");
+            }
+            let hit = script.lines.values().filter(|&&count| count > 0).count();
+            let _ = writeln!(output, "LF:{}", script.lines.len());
+            let _ = writeln!(output, "LH:{hit}");
+            let _ = writeln!(output, "end_of_record");
+        }
+        output
+    }
+
+    fn to_serializable(&self) -> SerializableCoverage {
+        let result = self
+            .scripts
+            .iter()
+            .enumerate()
+            .map(|(index, script)| SerializableScript {
+                script_id: (index + 1).to_string(),
+                url: match &script.path {
+                    SourcePath::Path(path) => format!("file://{}", path.display()),
+                    _ => String::new(),
+                },
+                functions: script
+                    .functions
+                    .iter()
+                    .map(|function| SerializableFunction {
+                        function_name: function.function_name.to_std_string_escaped(),
+                        ranges: function
+                            .ranges
+                            .iter()
+                            .map(|range| SerializableRange {
+                                start_offset: range.start,
+                                end_offset: range.end,
+                                count: range.count,
+                            })
+                            .collect(),
+                        is_block_coverage: true,
+                    })
+                    .collect(),
+            })
+            .collect();
+        SerializableCoverage { result }
+    }
+}
+
+#[derive(Serialize)]
+struct SerializableCoverage {
+    result: Vec<SerializableScript>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableScript {
+    script_id: String,
+    url: String,
+    functions: Vec<SerializableFunction>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableFunction {
+    function_name: String,
+    ranges: Vec<SerializableRange>,
+    is_block_coverage: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableRange {
+    start_offset: usize,
+    end_offset: usize,
+    count: u64,
+}
+
+/// The counters of the functions that ran since coverage collection started.
+#[derive(Debug, Default)]
+struct Counters {
+    /// The functions with the number of times each of their instructions ran, by `pc`.
+    functions: Vec<(Gc<CodeBlock>, Box<[u64]>)>,
+    indices: FxHashMap<usize, usize>,
+
+    /// The address and index of the last counted function, which is usually the next one.
+    last: Option<(usize, usize)>,
+}
+
+impl Counters {
+    fn count(&mut self, code_block: &Gc<CodeBlock>, pc: u32) {
+        let address = Gc::addr(code_block);
+        let index = match self.last {
+            Some((last, index)) if last == address => index,
+            _ => {
+                let index = *self.indices.entry(address).or_insert_with(|| {
+                    let counts = vec![0; code_block.bytecode.bytes.len()];
+                    self.functions
+                        .push((code_block.clone(), counts.into_boxed_slice()));
+                    self.functions.len() - 1
+                });
+                self.last = Some((address, index));
+                index
+            }
+        };
+        if let Some(count) = self.functions[index].1.get_mut(pc as usize) {
+            *count += 1;
+        }
+    }
+
+    /// Returns the coverage of the counted functions, and resets the counters.
+    fn take(&mut self) -> Coverage {
+        // The functions nested in the counted ones are reported even if they never ran.
+        let mut functions = Vec::new();
+        let mut visited = FxHashSet::default();
+        let mut nested = FxHashSet::default();
+        let mut pending = self
+            .functions
+            .iter()
+            .map(|(code_block, _)| code_block.clone())
+            .collect::<Vec<_>>();
+        while let Some(code_block) = pending.pop() {
+            if !visited.insert(Gc::addr(&code_block)) {
+                continue;
+            }
+            for constant in &code_block.constants {
+                let function = match constant {
+                    Constant::Function(function) => Some(function),
+                    Constant::LazyFunction(function) => function.get_if_decoded(),
+                    Constant::UncompiledFunction(function) => function.get_if_compiled(),
+                    _ => None,
+                };
+                if let Some(function) = function {
+                    nested.insert(Gc::addr(function));
+                    pending.push(function.clone());
+                }
+            }
+            functions.push(code_block);
+        }
+        functions.sort_by_key(|code_block| code_block.debug_id);
+
+        let mut scripts = Vec::<ScriptBuilder>::new();
+        let mut script_indices = FxHashMap::default();
+        for code_block in functions {
+            let source_text = code_block.source_info().text_spanned().source_text();
+            if source_text.is_empty() {
+                continue;
+            }
+            let index = *script_indices.entry(source_text.addr()).or_insert_with(|| {
+                scripts.push(ScriptBuilder::new(
+                    &source_text,
+                    code_block.source_info().map().path().clone(),
+                ));
+                scripts.len() - 1
+            });
+            let counts = self
+                .indices
+                .get(&Gc::addr(&code_block))
+                .map(|&index| &*self.functions[index].1);
+            let is_root = !nested.contains(&Gc::addr(&code_block));
+            scripts[index].add(&code_block, counts, is_root);
+        }
+
+        for (_, counts) in &mut self.functions {
+            counts.fill(0);
+        }
+
+        Coverage {
+            scripts: scripts.into_iter().map(ScriptBuilder::finish).collect(),
+        }
+    }
+}
+
+/// Builds the coverage of a script from the counts of its functions.
+struct ScriptBuilder {
+    path: SourcePath,
+    length: usize,
+
+    /// The offset of the start of every line.
+    line_starts: Vec<usize>,
+
+    functions: Vec<FunctionCoverage>,
+    lines: BTreeMap<u32, u64>,
+}
+
+impl ScriptBuilder {
+    fn new(source_text: &SourceText, path: SourcePath) -> Self {
+        let code_units = source_text.code_units();
+        let mut line_starts = vec![0];
+        let mut offset = 0;
+        while let Some(&unit) = code_units.get(offset) {
+            offset += 1;
+            match unit {
+                // `\r\n` is a single line terminator.
+                0x000D => {
+                    if code_units.get(offset) == Some(&0x000A) {
+                        offset += 1;
+                    }
+                    line_starts.push(offset);
+                }
+                0x000A | 0x2028 | 0x2029 => line_starts.push(offset),
+                _ => {}
+            }
+        }
+
+        Self {
+            path,
+            length: code_units.len(),
+            line_starts,
+            functions: Vec::new(),
+            lines: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the offset of a one-based line and column number.
+    fn offset(&self, line: u32, column: u32) -> usize {
+        let start = self
+            .line_starts
+            .get(line.saturating_sub(1) as usize)
+            .copied()
+            .unwrap_or(self.length);
+        (start + column.saturating_sub(1) as usize).min(self.length)
+    }
+
+    /// Returns the one-based line number of an offset.
+    fn line(&self, offset: usize) -> u32 {
+        let index = self.line_starts.partition_point(|&start| start <= offset);
+        u32::try_from(index).unwrap_or(u32::MAX)
+    }
+
+    /// Adds a function, with the number of times each of its instructions ran if it ran.
+    fn add(&mut self, code_block: &CodeBlock, counts: Option<&[u64]>, is_root: bool) {
+        let count_at = |pc: usize| counts.and_then(|counts| counts.get(pc)).copied();
+        let map = code_block.source_info().map();
+        let length = code_block.bytecode.bytes.len();
+
+        // The count of the statements that start at each offset.
+        let mut statements = BTreeMap::<usize, u64>::new();
+        for (index, entry) in map.entries().iter().enumerate() {
+            let Some(position) = entry.position() else {
+                continue;
+            };
+            let end = map
+                .entries()
+                .get(index + 1)
+                .map_or(length, |next| next.pc() as usize);
+            let start = entry.pc() as usize;
+            if start >= end {
+                continue;
+            }
+            let count = (start..end.min(length))
+                .filter_map(count_at)
+                .max()
+                .unwrap_or_default();
+
+            let offset = self.offset(position.line_number(), position.column_number());
+            let statement = statements.entry(offset).or_default();
+            *statement = (*statement).max(count);
+            let line = self.lines.entry(position.line_number()).or_default();
+            *line = (*line).max(count);
+        }
+
+        let (start, end) = match code_block.source_info().text_spanned().span() {
+            Some(span) => (span.start().pos(), span.end().pos()),
+            // The top level code covers the whole source.
+            None if is_root => (0, self.length),
+            // Code without a source of its own, like class field initializers, only counts
+            // towards the lines.
+            None => return,
+        };
+        let count = count_at(0).unwrap_or_default();
+        let mut ranges = vec![CoverageRange { start, end, count }];
+
+        // Every statement runs as many times as the one before it, unless the code branched.
+        // The ranges of the blocks run from a statement to the next statement with another
+        // count, and are only needed if their count differs from the one of the function.
+        let statements = statements
+            .into_iter()
+            .filter(|&(offset, _)| (start..end).contains(&offset));
+        let mut block = CoverageRange { start, end, count };
+        for (offset, statement_count) in statements {
+            if statement_count == block.count {
+                continue;
+            }
+            block.end = offset;
+            if block.count != count && block.start < block.end {
+                ranges.push(block);
+            }
+            block = CoverageRange {
+                start: offset,
+                end,
+                count: statement_count,
+            };
+        }
+        if block.count != count && block.start < block.end {
+            ranges.push(block);
+        }
+
+        let is_top_level = code_block.source_info().text_spanned().span().is_none();
+        let function_name = if is_top_level {
+            JsString::default()
+        } else {
+            code_block.name().clone()
+        };
+        self.functions.push(FunctionCoverage {
+            function_name,
+            is_top_level,
+            line: self.line(start),
+            ranges,
+        });
+    }
+
+    fn finish(mut self) -> ScriptCoverage {
+        // The top level code comes first, followed by the functions in source order.
+        self.functions.sort_by_key(|function| {
+            let range = function.ranges[0];
+            (range.start, usize::MAX - range.end)
+        });
+        ScriptCoverage {
+            path: self.path,
+            functions: self.functions,
+            lines: self.lines,
+        }
+    }
+}
+
+/// The coverage state of a [`Context`].
+#[derive(Debug, Default)]
+pub(crate) struct CoverageState {
+    counters: Option<Counters>,
+}
+
+impl CoverageState {
+    /// Returns `true` if the instructions that run are counted.
+    #[inline]
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.counters.is_some()
+    }
+
+    pub(crate) fn start(&mut self) {
+        self.counters = Some(Counters::default());
+    }
+
+    pub(crate) fn take(&mut self) -> Option<Coverage> {
+        self.counters.as_mut().map(Counters::take)
+    }
+
+    pub(crate) fn stop(&mut self) -> Option<Coverage> {
+        self.counters.take().as_mut().map(Counters::take)
+    }
+}
+
+impl Context {
+    /// Counts the instruction that is about to run.
+    pub(crate) fn coverage_count(&mut self) {
+        let vm = &mut self.vm;
+        if let (Some(counters), Some(frame)) = (&mut vm.coverage.counters, vm.frames.last()) {
+            counters.count(&frame.code_block, frame.pc);
+        }
+    }
+}
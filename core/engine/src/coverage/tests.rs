@@ -0,0 +1,202 @@
+use std::path::Path;
+
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::{Coverage, CoverageRange, FunctionCoverage, ScriptCoverage};
+use crate::{Context, js_string, vm::SourcePath};
+
+const CODE: &str = indoc! {r#"
+    function sign(x) {
+        if (x < 0) {
+            return -1;
+        }
+        return 1;
+    }
+    function unused() {
+        return 0;
+    }
+    for (let i = 0; i < 3; i++) {
+        sign(i);
+    }
+"#};
+
+/// Collects the coverage of `code`, run as a script from `path`.
+fn coverage(code: &str) -> Coverage {
+    let mut context = Context::default();
+    context.start_coverage();
+    context
+        .eval(Source::from_bytes(code).with_path(Path::new("/test.js")))
+        .expect("the code should run");
+    context
+        .stop_coverage()
+        .expect("coverage should be collected")
+}
+
+fn function<'a>(coverage: &'a Coverage, name: &str) -> &'a FunctionCoverage {
+    coverage.scripts()[0]
+        .functions()
+        .iter()
+        .find(|function| function.function_name() == &js_string!(name))
+        .expect("the function should be covered")
+}
+
+/// Returns the source text of a range.
+fn text(range: &CoverageRange) -> &'static str {
+    &CODE[range.start()..range.end()]
+}
+
+#[test]
+fn counts_functions() {
+    let coverage = coverage(CODE);
+    assert_eq!(coverage.scripts().len(), 1);
+    let script = &coverage.scripts()[0];
+    assert_eq!(
+        script.path(),
+        &SourcePath::Path(Path::new("/test.js").into())
+    );
+
+    let top_level = &script.functions()[0];
+    assert!(top_level.is_top_level());
+    assert!(top_level.function_name().is_empty());
+    assert_eq!(top_level.count(), 1);
+    assert_eq!(top_level.ranges()[0].start(), 0);
+    assert_eq!(top_level.ranges()[0].end(), CODE.len());
+
+    let sign = function(&coverage, "sign");
+    assert_eq!(sign.count(), 3);
+    assert_eq!(sign.line(), 1);
+    assert!(text(&sign.ranges()[0]).starts_with("function sign(x) {"));
+    assert!(text(&sign.ranges()[0]).ends_with('}'));
+
+    let unused = function(&coverage, "unused");
+    assert_eq!(unused.count(), 0);
+    assert_eq!(unused.line(), 7);
+    assert_eq!(unused.ranges().len(), 1);
+}
+
+#[test]
+fn counts_blocks() {
+    let coverage = coverage(CODE);
+
+    // The `return -1` branch never runs, and the rest of `sign` runs every time.
+    let sign = function(&coverage, "sign");
+    let blocks = &sign.ranges()[1..];
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].count(), 0);
+    assert!(text(&blocks[0]).starts_with("-1;"));
+    assert!(text(&blocks[0]).contains('}'));
+
+    // The update and the body of the loop run three times, and its condition four times.
+    let top_level = &coverage.scripts()[0].functions()[0];
+    assert!(
+        top_level
+            .ranges()
+            .iter()
+            .any(|range| range.count() == 3 && text(range).starts_with("i++) {\n    sign(i);"))
+    );
+    assert!(
+        top_level
+            .ranges()
+            .iter()
+            .any(|range| range.count() == 4 && text(range).starts_with("i < 3"))
+    );
+}
+
+#[test]
+fn counts_lines() {
+    let coverage = coverage(CODE);
+    let lines = coverage.scripts()[0].lines().collect::<Vec<_>>();
+    assert!(!lines.iter().any(|&(line, _)| line == 1));
+    assert!(lines.contains(&(2, 3)));
+    assert!(lines.contains(&(3, 0)));
+    assert!(lines.contains(&(5, 3)));
+    assert!(lines.contains(&(8, 0)));
+    assert!(lines.contains(&(11, 3)));
+}
+
+#[test]
+fn reports_functions_nested_in_uncalled_functions() {
+    let coverage = coverage(indoc! {r#"
+        function outer() {
+            function inner() {
+                return 1;
+            }
+            return inner();
+        }
+    "#});
+    assert_eq!(function(&coverage, "outer").count(), 0);
+    let inner = function(&coverage, "inner");
+    assert_eq!(inner.count(), 0);
+    assert_eq!(inner.line(), 2);
+
+    let lines = coverage.scripts()[0].lines().collect::<Vec<_>>();
+    assert!(lines.contains(&(3, 0)));
+    assert!(lines.contains(&(5, 0)));
+}
+
+#[test]
+fn take_resets_counters() {
+    let mut context = Context::default();
+    assert!(context.take_coverage().is_none());
+
+    context.start_coverage();
+    context
+        .eval(Source::from_bytes("function f() {} f(); f();"))
+        .expect("the code should run");
+    let first = context
+        .take_coverage()
+        .expect("coverage should be collected");
+    let f = first.scripts()[0]
+        .functions()
+        .iter()
+        .find(|function| function.function_name() == &js_string!("f"))
+        .expect("`f` should be covered");
+    assert_eq!(f.count(), 2);
+
+    let second = context
+        .stop_coverage()
+        .expect("coverage should be collected");
+    assert!(
+        second
+            .scripts()
+            .iter()
+            .flat_map(ScriptCoverage::functions)
+            .all(|function| function.count() == 0)
+    );
+    assert!(context.stop_coverage().is_none());
+}
+
+#[test]
+fn precise_coverage_json() {
+    let coverage = coverage(CODE);
+    let json: serde_json::Value =
+        serde_json::from_str(&coverage.to_json_string()).expect("the coverage should be JSON");
+
+    let script = &json["result"][0];
+    assert_eq!(script["url"], "file:///test.js");
+    let sign = script["functions"]
+        .as_array()
+        .expect("functions should be an array")
+        .iter()
+        .find(|function| function["functionName"] == "sign")
+        .expect("`sign` should be covered");
+    assert_eq!(sign["isBlockCoverage"], true);
+    assert_eq!(sign["ranges"][0]["count"], 3);
+    assert_eq!(sign["ranges"][1]["count"], 0);
+}
+
+#[test]
+fn lcov() {
+    let lcov = coverage(CODE).to_lcov_string();
+    let lines = lcov.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "TN:");
+    assert_eq!(lines[1], "SF:/test.js");
+    assert!(lines.contains(&"FN:1,sign"));
+    assert!(lines.contains(&"FNDA:3,sign"));
+    assert!(lines.contains(&"FNDA:0,unused"));
+    assert!(lines.contains(&"FNF:2"));
+    assert!(lines.contains(&"FNH:1"));
+    assert!(lines.contains(&"DA:3,0"));
+    assert_eq!(lines.last(), Some(&"end_of_record"));
+}
@@ -0,0 +1,455 @@
+use boa_ast::scope::Scope;
+use boa_gc::{Finalize, Gc, Trace};
+
+use crate::{
+    Context, JsNativeError, JsObject, JsResult, JsString, JsValue,
+    builtins::eval::Eval,
+    environments::{DeclarativeEnvironment, DeclarativeEnvironmentKind, Environment},
+    native_function::NativeFunction,
+    object::FunctionObjectBuilder,
+    property::PropertyDescriptor,
+    vm::{CallFrame, CallFrameLocation},
+};
+
+/// A frame of the call stack of a paused [`Context`].
+///
+/// A stack frame is only valid while the context stays paused on the event it was created for.
+/// Once its function returns, the methods of the frame return empty values.
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    /// The index of the frame in the frames of the VM.
+    index: usize,
+    location: CallFrameLocation,
+}
+
+/// The kind of a [`FrameScope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// The parameters and variables of the function of the frame.
+    Local,
+
+    /// The lexical bindings of a block in the function of the frame.
+    Block,
+
+    /// The bindings of an enclosing function that are captured by the function of the frame.
+    Closure,
+
+    /// The object of a `with` statement.
+    With,
+
+    /// The bindings of a module.
+    Module,
+
+    /// The global lexical bindings, declared with `let`, `const` or `class` in scripts.
+    Script,
+
+    /// The global object.
+    Global,
+}
+
+/// A scope visible from a [`StackFrame`].
+#[derive(Debug, Clone)]
+pub struct FrameScope {
+    kind: ScopeKind,
+    object: JsObject,
+}
+
+impl FrameScope {
+    /// Returns the kind of the scope.
+    #[must_use]
+    pub const fn kind(&self) -> ScopeKind {
+        self.kind
+    }
+
+    /// Returns an object with the bindings of the scope as properties.
+    ///
+    /// For [`ScopeKind::With`] and [`ScopeKind::Global`] scopes this is the binding object
+    /// itself. For other scopes it is a new object with a data property for every initialized
+    /// binding, so changing it doesn't change the bindings.
+    #[must_use]
+    pub const fn object(&self) -> &JsObject {
+        &self.object
+    }
+}
+
+/// Where the value of a binding is stored.
+#[derive(Debug, Clone, Trace, Finalize)]
+enum Slot {
+    Register(u32),
+    Environment(Gc<DeclarativeEnvironment>, u32),
+}
+
+#[derive(Debug)]
+struct Binding {
+    name: JsString,
+    slot: Slot,
+    mutable: bool,
+}
+
+#[derive(Debug)]
+enum RawScope {
+    Declarative(ScopeKind, Vec<Binding>),
+    Object(ScopeKind, JsObject),
+}
+
+impl StackFrame {
+    /// Collects the frames of the call stack, with the current frame first.
+    pub(super) fn collect(context: &Context, pc: u32) -> Vec<Self> {
+        let frames = &context.vm.frames;
+        let current = frames.len() - 1;
+
+        // Skip the dummy frame.
+        (1..frames.len())
+            .rev()
+            .map(|index| {
+                let frame = &frames[index];
+
+                // Note: -1 because the `pc` of the callers points to the instruction after the call.
+                let pc = if index == current {
+                    pc
+                } else {
+                    frame.pc.saturating_sub(1)
+                };
+                let source_info = &frame.code_block.source_info;
+                Self {
+                    index,
+                    location: CallFrameLocation {
+                        function_name: source_info.function_name().clone(),
+                        path: source_info.map().path().clone(),
+                        position: source_info.map().find(pc),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn frame<'a>(&self, context: &'a Context) -> Option<&'a CallFrame> {
+        context.vm.frames.get(self.index)
+    }
+
+    /// Returns the function name, path and position of the paused instruction of the frame.
+    #[must_use]
+    pub const fn location(&self) -> &CallFrameLocation {
+        &self.location
+    }
+
+    /// Returns the function object of the frame, or `None` for scripts and modules.
+    #[must_use]
+    pub fn function(&self, context: &Context) -> Option<JsObject> {
+        let frame = self.frame(context)?;
+        context.vm.stack.get_function(frame)
+    }
+
+    /// Returns the `this` value of the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is a derived constructor that didn't call `super` yet.
+    pub fn this(&self, context: &Context) -> JsResult<JsValue> {
+        let Some(frame) = self.frame(context) else {
+            return Ok(JsValue::undefined());
+        };
+
+        if frame.has_this_value_cached() {
+            return Ok(context.vm.stack.get_this(frame));
+        }
+
+        Ok(frame
+            .environments
+            .get_this_binding()?
+            .unwrap_or_else(|| frame.realm.global_this().clone().into()))
+    }
+
+    /// Returns the arguments passed to the function of the frame.
+    #[must_use]
+    pub fn arguments(&self, context: &Context) -> Vec<JsValue> {
+        self.frame(context)
+            .map(|frame| context.vm.stack.get_arguments(frame).to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Returns the values of the registers of the frame.
+    #[must_use]
+    pub fn registers(&self, context: &Context) -> Vec<JsValue> {
+        self.frame(context)
+            .map(|frame| context.vm.stack.get_registers(frame).to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Returns the scopes visible from the paused instruction of the frame, innermost first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if creating the objects of the scopes fails.
+    pub fn scopes(&self, context: &mut Context) -> JsResult<Vec<FrameScope>> {
+        let mut scopes = Vec::new();
+        for scope in self.raw_scopes(context) {
+            let (kind, object) = match scope {
+                RawScope::Object(kind, object) => (kind, object),
+                RawScope::Declarative(kind, bindings) => {
+                    let object = JsObject::with_null_proto();
+                    for binding in bindings {
+                        if let Some(value) = self.get(&binding.slot, context) {
+                            object.create_data_property_or_throw(binding.name, value, context)?;
+                        }
+                    }
+                    (kind, object)
+                }
+            };
+            scopes.push(FrameScope { kind, object });
+        }
+        Ok(scopes)
+    }
+
+    /// Evaluates `code` in the scope of the paused instruction of the frame.
+    ///
+    /// The code runs like a direct `eval` with all the bindings of the frame in scope, which can
+    /// be read and assigned. `this`, `new.target` and `super` refer to the values of the global
+    /// scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame is no longer active, or if the evaluation throws.
+    pub fn evaluate(&self, code: &str, context: &mut Context) -> JsResult<JsValue> {
+        if self.frame(context).is_none() {
+            return Err(JsNativeError::error()
+                .with_message("the stack frame is no longer active")
+                .into());
+        }
+
+        // The bindings are exposed as object environments, outermost first. The script and
+        // global scopes are already reachable through the global environment.
+        let mut objects = Vec::new();
+        for scope in self.raw_scopes(context).into_iter().rev() {
+            match scope {
+                RawScope::Object(ScopeKind::Global, _)
+                | RawScope::Declarative(ScopeKind::Script, _) => {}
+                RawScope::Object(_, object) => objects.push(object),
+                RawScope::Declarative(_, bindings) => {
+                    objects.push(self.binding_accessors(bindings, context)?);
+                }
+            }
+        }
+
+        let environments = &mut context.vm.frame_mut().environments;
+        let saved = environments.pop_to_global();
+        for object in objects {
+            environments.push_object(object);
+        }
+
+        let result = Eval::perform_eval(&JsString::from(code).into(), true, None, false, context);
+
+        let environments = &mut context.vm.frame_mut().environments;
+        environments.truncate(0);
+        environments.restore_from_saved(saved);
+
+        result
+    }
+
+    /// Collects the bindings of the scopes of the frame, innermost first.
+    fn raw_scopes(&self, context: &Context) -> Vec<RawScope> {
+        let Some(frame) = self.frame(context) else {
+            return Vec::new();
+        };
+
+        let mut scopes = Vec::new();
+        let mut local = frame
+            .code_block
+            .debug_info
+            .local_bindings
+            .iter()
+            .map(|binding| Binding {
+                name: binding.name.clone(),
+                slot: Slot::Register(binding.register),
+                mutable: binding.mutable,
+            })
+            .collect::<Vec<_>>();
+
+        // The environments below `env_fp` are the ones captured by the function of the frame.
+        let env_fp = frame.env_fp as usize;
+        let mut local_pushed = false;
+        for index in (0..frame.environments.len()).rev() {
+            let own = index >= env_fp;
+            if !own && !local_pushed {
+                local_pushed = true;
+                if !local.is_empty() {
+                    scopes.push(RawScope::Declarative(
+                        ScopeKind::Local,
+                        std::mem::take(&mut local),
+                    ));
+                }
+            }
+
+            let env = match frame.environments.get(index) {
+                Some(Environment::Declarative(env)) => env,
+                Some(Environment::Object(object)) => {
+                    scopes.push(RawScope::Object(ScopeKind::With, object.clone()));
+                    continue;
+                }
+                None => continue,
+            };
+
+            let (kind, scope) = match env.kind() {
+                DeclarativeEnvironmentKind::Function(function) => (
+                    if own {
+                        ScopeKind::Local
+                    } else {
+                        ScopeKind::Closure
+                    },
+                    Some(function.compile()),
+                ),
+                DeclarativeEnvironmentKind::Lexical(lexical) => (
+                    if own {
+                        ScopeKind::Block
+                    } else {
+                        ScopeKind::Closure
+                    },
+                    lexical.scope(),
+                ),
+                DeclarativeEnvironmentKind::Module(module) => {
+                    (ScopeKind::Module, Some(module.compile()))
+                }
+                DeclarativeEnvironmentKind::Global(_) => continue,
+            };
+            let Some(scope) = scope else {
+                continue;
+            };
+
+            let bindings = environment_bindings(env, scope, true);
+            if kind == ScopeKind::Local {
+                local.extend(bindings);
+            } else {
+                scopes.push(RawScope::Declarative(kind, bindings));
+            }
+        }
+        if !local.is_empty() {
+            scopes.push(RawScope::Declarative(ScopeKind::Local, local));
+        }
+
+        let realm = &frame.realm;
+        scopes.push(RawScope::Declarative(
+            ScopeKind::Script,
+            environment_bindings(realm.environment(), realm.scope(), false),
+        ));
+        scopes.push(RawScope::Object(
+            ScopeKind::Global,
+            realm.global_object().clone(),
+        ));
+
+        scopes
+    }
+
+    /// Gets the value of a binding, or `None` if it is not initialized.
+    fn get(&self, slot: &Slot, context: &Context) -> Option<JsValue> {
+        get_slot(self.index, slot, context)
+    }
+
+    /// Creates an object with an accessor property for each binding, that reads and writes
+    /// the binding.
+    fn binding_accessors(
+        &self,
+        bindings: Vec<Binding>,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        let object = JsObject::with_null_proto();
+        for binding in bindings {
+            let captures = (
+                self.index,
+                binding.slot,
+                binding.mutable,
+                binding.name.clone(),
+            );
+            let get = NativeFunction::from_copy_closure_with_captures(
+                |_, _, (index, slot, _, name), context| {
+                    get_slot(*index, slot, context).ok_or_else(|| {
+                        JsNativeError::reference()
+                            .with_message(format!(
+                                "Cannot access '{}' before initialization",
+                                name.to_std_string_escaped()
+                            ))
+                            .into()
+                    })
+                },
+                captures.clone(),
+            );
+            let set = NativeFunction::from_copy_closure_with_captures(
+                |_, args, (index, slot, mutable, name), context| {
+                    if !mutable {
+                        return Err(JsNativeError::typ()
+                            .with_message(format!(
+                                "cannot assign to constant '{}'",
+                                name.to_std_string_escaped()
+                            ))
+                            .into());
+                    }
+                    let value = args.first().cloned().unwrap_or_default();
+                    set_slot(*index, slot, value, context);
+                    Ok(JsValue::undefined())
+                },
+                captures,
+            );
+
+            let realm = context.realm().clone();
+            object.define_property_or_throw(
+                binding.name,
+                PropertyDescriptor::builder()
+                    .get(FunctionObjectBuilder::new(&realm, get).build())
+                    .set(FunctionObjectBuilder::new(&realm, set).build())
+                    .enumerable(true)
+                    .configurable(true),
+                context,
+            )?;
+        }
+        Ok(object)
+    }
+}
+
+/// Returns the bindings of `scope` that are stored in `env`.
+///
+/// Bindings that don't escape are stored in registers, except for the global scope.
+fn environment_bindings(
+    env: &Gc<DeclarativeEnvironment>,
+    scope: &Scope,
+    skip_local: bool,
+) -> Vec<Binding> {
+    let len = env.kind().binding_values().len();
+    scope
+        .bindings()
+        .into_iter()
+        .filter(|binding| !skip_local || binding.escapes())
+        .filter(|binding| (binding.index() as usize) < len)
+        .map(|binding| Binding {
+            name: binding.name().clone(),
+            slot: Slot::Environment(env.clone(), binding.index()),
+            mutable: binding.is_mutable(),
+        })
+        .collect()
+}
+
+fn get_slot(frame_index: usize, slot: &Slot, context: &Context) -> Option<JsValue> {
+    match slot {
+        Slot::Register(register) => {
+            let frame = context.vm.frames.get(frame_index)?;
+            context
+                .vm
+                .stack
+                .get_register(frame, *register as usize)
+                .cloned()
+        }
+        Slot::Environment(env, index) => env.get(*index),
+    }
+}
+
+fn set_slot(frame_index: usize, slot: &Slot, value: JsValue, context: &mut Context) {
+    match slot {
+        Slot::Register(register) => {
+            if let Some(frame) = context.vm.frames.get(frame_index) {
+                context
+                    .vm
+                    .stack
+                    .set_register(frame, *register as usize, value);
+            }
+        }
+        Slot::Environment(env, index) => env.set(*index, value),
+    }
+}
@@ -0,0 +1,576 @@
+//! Debugging of running scripts.
+//!
+//! A [`Debugger`] attached to a [`Context`] with [`Context::attach_debugger`] is called every
+//! time execution pauses, and decides how execution resumes. While paused, the call stack can
+//! be inspected through the [`StackFrame`]s of the [`PausedEvent`]: their location, `this` value,
+//! arguments, registers and the bindings of their scopes, and code can be evaluated in them.
+//!
+//! Execution pauses:
+//!  - On the breakpoints set with [`Context::set_breakpoint`].
+//!  - On `debugger` statements.
+//!  - On thrown exceptions, depending on the [`ExceptionPauseMode`].
+//!  - After a step requested by the [`ResumeAction`] returned from the previous pause.
+//!  - When a pause is requested through a [`PauseHandle`], which can be done from another thread.
+//!
+//! Breakpoints are resolved against the source map of each function, so they only pause on
+//! lines that contain code. To map every statement, the bytecompiler records the position of
+//! statements when the `debugger` feature is enabled, in addition to the positions of the
+//! expressions that can throw.
+//!
+//! ```
+//! # use boa_engine::{Context, Source, debugger::{Debugger, PausedEvent, ResumeAction}};
+//! # use std::{cell::RefCell, rc::Rc};
+//! #[derive(Default)]
+//! struct Logger(RefCell<Vec<u32>>);
+//!
+//! impl Debugger for Logger {
+//!     fn paused(&self, event: &PausedEvent, _: &mut Context) -> ResumeAction {
+//!         let position = event.frames()[0].location().position.unwrap();
+//!         self.0.borrow_mut().push(position.line_number());
+//!         ResumeAction::StepOver
+//!     }
+//! }
+//!
+//! let logger = Rc::new(Logger::default());
+//! let mut context = Context::default();
+//! context.attach_debugger(logger.clone());
+//! context
+//!     .eval(Source::from_bytes("debugger;\nlet a = 1;\nlet b = a + 1;"))
+//!     .unwrap();
+//! assert_eq!(*logger.0.borrow(), [1, 2, 3]);
+//! ```
+
+use std::{
+    cell::RefCell,
+    fmt,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use boa_ast::{Position, Span};
+
+use crate::{
+    Context, JsError, JsString,
+    vm::{CodeBlock, Opcode, SourcePath},
+};
+
+mod frame;
+
+#[cfg(test)]
+mod tests;
+
+pub use frame::{FrameScope, ScopeKind, StackFrame};
+
+/// A debugger that is called when the execution of a [`Context`] pauses.
+///
+/// The debugger can inspect the paused context, and returns how execution should resume. Any
+/// code that runs while paused, like the getters called to inspect an object or the code
+/// evaluated with [`StackFrame::evaluate`], doesn't pause again.
+///
+/// Like [`HostHooks`][crate::context::HostHooks], the methods take `&self`, so a debugger
+/// that keeps state must use interior mutability.
+pub trait Debugger {
+    /// Called when execution pauses, with the reason of the pause and the current call stack.
+    ///
+    /// Execution resumes when this returns.
+    fn paused(&self, event: &PausedEvent, context: &mut Context) -> ResumeAction;
+}
+
+/// How execution continues after a pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResumeAction {
+    /// Run until the next breakpoint, `debugger` statement or exception.
+    #[default]
+    Continue,
+
+    /// Pause on the next line, entering the functions that are called.
+    StepIn,
+
+    /// Pause on the next line of the current function, or in its caller when it returns.
+    StepOver,
+
+    /// Pause once the current function returns to its caller.
+    StepOut,
+}
+
+/// When execution pauses on thrown exceptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExceptionPauseMode {
+    /// Never pause on exceptions.
+    #[default]
+    None,
+
+    /// Pause on exceptions that are not caught by a `try` statement of a function in the call
+    /// stack.
+    Uncaught,
+
+    /// Pause on all exceptions.
+    All,
+}
+
+/// The identifier of a breakpoint, returned by [`Context::set_breakpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BreakpointId(u32);
+
+impl BreakpointId {
+    /// Returns the numeric value of the identifier.
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// A source location to pause on.
+///
+/// A breakpoint pauses on the first instruction of its line in every function that has code on
+/// that line. If a column is given, it only pauses in the innermost function whose body contains
+/// the column, on the first instruction at or after it, which allows breaking inside of a
+/// function that is defined on the same line as other code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoint {
+    path: Option<PathBuf>,
+    line: u32,
+    column: Option<u32>,
+}
+
+impl Breakpoint {
+    /// Creates a breakpoint on a line of the code that was not loaded from a path.
+    ///
+    /// Lines and columns start at 1.
+    #[must_use]
+    pub const fn new(line: u32) -> Self {
+        Self {
+            path: None,
+            line,
+            column: None,
+        }
+    }
+
+    /// Sets the path of the [`Source`][crate::Source] the breakpoint is in.
+    ///
+    /// This is compared with the path the source was created with, like in
+    /// [`Source::from_filepath`][crate::Source::from_filepath].
+    #[must_use]
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+
+    /// Sets the column of the breakpoint.
+    #[must_use]
+    pub const fn with_column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Returns the path of the breakpoint, if it has one.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns the line of the breakpoint.
+    #[must_use]
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column of the breakpoint, if it has one.
+    #[must_use]
+    pub const fn column(&self) -> Option<u32> {
+        self.column
+    }
+
+    fn matches_path(&self, path: &SourcePath) -> bool {
+        match (path, &self.path) {
+            (SourcePath::Path(path), Some(expected)) => **path == **expected,
+            (SourcePath::None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Finds the first instruction of `code_block` the breakpoint pauses on.
+    fn resolve(&self, code_block: &CodeBlock) -> Option<u32> {
+        let map = code_block.source_info.map();
+        if !self.matches_path(map.path()) {
+            return None;
+        }
+
+        // A breakpoint with a column only pauses in the innermost function that contains it.
+        if let Some(column) = self.column {
+            let position = Position::new(self.line, column);
+            let debug_info = &code_block.debug_info;
+            if debug_info.span.is_some_and(|span| !span.contains(position))
+                || debug_info
+                    .nested_function_spans
+                    .iter()
+                    .any(|span| span.contains(position))
+            {
+                return None;
+            }
+        }
+
+        map.entries()
+            .iter()
+            .filter_map(|entry| Some((entry.pc(), entry.position()?)))
+            .filter(|(_, position)| {
+                position.line_number() == self.line
+                    && self
+                        .column
+                        .is_none_or(|column| position.column_number() >= column)
+            })
+            .min_by_key(|(pc, position)| {
+                let column = self.column.map_or(0, |_| position.column_number());
+                (column, *pc)
+            })
+            .map(|(pc, _)| pc)
+    }
+}
+
+/// The reason execution paused.
+#[derive(Debug, Clone)]
+pub enum PauseReason {
+    /// A breakpoint was hit.
+    Breakpoint(BreakpointId),
+
+    /// A `debugger` statement was executed.
+    DebuggerStatement,
+
+    /// An exception was thrown.
+    Exception(JsError),
+
+    /// A step requested with a [`ResumeAction`] completed.
+    Step,
+
+    /// A pause was requested through a [`PauseHandle`].
+    Requested,
+}
+
+/// The state of a paused context that is passed to [`Debugger::paused`].
+#[derive(Debug)]
+pub struct PausedEvent {
+    reason: PauseReason,
+    frames: Vec<StackFrame>,
+}
+
+impl PausedEvent {
+    /// Returns the reason execution paused.
+    #[must_use]
+    pub const fn reason(&self) -> &PauseReason {
+        &self.reason
+    }
+
+    /// Returns the frames of the call stack, with the paused frame first.
+    #[must_use]
+    pub fn frames(&self) -> &[StackFrame] {
+        &self.frames
+    }
+}
+
+/// A handle to request a pause of a running [`Context`].
+///
+/// The handle can be sent to another thread, for example to pause the execution when a remote
+/// debugging client asks for it. Execution pauses on the next instruction that has a source
+/// position.
+#[derive(Debug, Clone, Default)]
+pub struct PauseHandle(Arc<AtomicBool>);
+
+impl PauseHandle {
+    /// Requests a pause.
+    pub fn request_pause(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    fn is_requested(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn clear(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// The breakpoints resolved for a [`CodeBlock`], which are cached in the code block.
+#[derive(Debug, Default, Clone)]
+struct ResolvedBreakpoints {
+    /// The generation of the breakpoints this was resolved from, or `0` if never resolved.
+    generation: u64,
+
+    /// The `pc`s of the breakpoints, sorted.
+    breakpoints: Box<[(u32, BreakpointId)]>,
+}
+
+/// A binding that is stored in a register.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalBinding {
+    pub(crate) name: JsString,
+    pub(crate) register: u32,
+    pub(crate) mutable: bool,
+}
+
+/// Debugger information of a [`CodeBlock`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CodeBlockDebugInfo {
+    /// The bindings that are stored in registers, sorted by register.
+    local_bindings: Box<[LocalBinding]>,
+
+    /// The span of the body of the function, or `None` for scripts, modules and `eval`.
+    span: Option<Span>,
+
+    /// The spans of the bodies of the functions nested in this one.
+    nested_function_spans: Box<[Span]>,
+
+    resolved: RefCell<ResolvedBreakpoints>,
+}
+
+impl CodeBlockDebugInfo {
+    pub(crate) fn new(
+        local_bindings: Box<[LocalBinding]>,
+        span: Option<Span>,
+        nested_function_spans: Box<[Span]>,
+    ) -> Self {
+        Self {
+            local_bindings,
+            span,
+            nested_function_spans,
+            resolved: RefCell::default(),
+        }
+    }
+}
+
+/// The breakpoints of all the contexts share a generation counter, so that code blocks shared
+/// between contexts never use breakpoints resolved for another context.
+static BREAKPOINT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+fn next_generation() -> u64 {
+    BREAKPOINT_GENERATION.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+/// A step that is in progress.
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    action: ResumeAction,
+
+    /// The number of frames when the step started.
+    depth: usize,
+
+    /// The line execution paused on when the step started.
+    line: Option<u32>,
+}
+
+impl Step {
+    fn should_pause(&self, depth: usize, position: Position) -> bool {
+        let new_line = Some(position.line_number()) != self.line;
+        match self.action {
+            ResumeAction::Continue => false,
+            ResumeAction::StepIn => depth != self.depth || new_line,
+            ResumeAction::StepOver => depth < self.depth || (depth == self.depth && new_line),
+            ResumeAction::StepOut => depth < self.depth,
+        }
+    }
+}
+
+/// The debugger state of a [`Context`].
+#[derive(Default)]
+pub(crate) struct DebuggerState {
+    debugger: Option<Rc<dyn Debugger>>,
+    breakpoints: Vec<(BreakpointId, Breakpoint)>,
+    next_breakpoint_id: u32,
+    generation: u64,
+    exception_pause_mode: ExceptionPauseMode,
+    pause_handle: PauseHandle,
+    step: Option<Step>,
+
+    /// Set while the debugger is handling a pause, to avoid pausing on the code it runs.
+    paused: bool,
+}
+
+impl fmt::Debug for DebuggerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebuggerState")
+            .field("debugger", &self.debugger.as_ref().map(|_| "Debugger"))
+            .field("breakpoints", &self.breakpoints)
+            .field("exception_pause_mode", &self.exception_pause_mode)
+            .field("step", &self.step)
+            .field("paused", &self.paused)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DebuggerState {
+    pub(crate) fn attach(&mut self, debugger: Rc<dyn Debugger>) {
+        self.debugger = Some(debugger);
+        self.step = None;
+    }
+
+    pub(crate) fn detach(&mut self) -> Option<Rc<dyn Debugger>> {
+        self.step = None;
+        self.debugger.take()
+    }
+
+    pub(crate) fn set_breakpoint(&mut self, breakpoint: Breakpoint) -> BreakpointId {
+        let id = BreakpointId(self.next_breakpoint_id);
+        self.next_breakpoint_id += 1;
+        self.breakpoints.push((id, breakpoint));
+        self.generation = next_generation();
+        id
+    }
+
+    pub(crate) fn remove_breakpoint(&mut self, id: BreakpointId) -> bool {
+        let len = self.breakpoints.len();
+        self.breakpoints.retain(|(breakpoint, _)| *breakpoint != id);
+        self.generation = next_generation();
+        self.breakpoints.len() != len
+    }
+
+    pub(crate) fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+        self.generation = next_generation();
+    }
+
+    pub(crate) fn set_exception_pause_mode(&mut self, mode: ExceptionPauseMode) {
+        self.exception_pause_mode = mode;
+    }
+
+    pub(crate) fn pause_handle(&self) -> PauseHandle {
+        self.pause_handle.clone()
+    }
+
+    /// Returns the breakpoint that pauses on the instruction at `pc` of `code_block`.
+    fn breakpoint_at(&self, code_block: &CodeBlock, pc: u32) -> Option<BreakpointId> {
+        if self.breakpoints.is_empty() {
+            return None;
+        }
+
+        let mut resolved = code_block.debug_info.resolved.borrow_mut();
+        if resolved.generation != self.generation {
+            let mut breakpoints = self
+                .breakpoints
+                .iter()
+                .filter_map(|(id, breakpoint)| Some((breakpoint.resolve(code_block)?, *id)))
+                .collect::<Vec<_>>();
+            breakpoints.sort_unstable();
+            *resolved = ResolvedBreakpoints {
+                generation: self.generation,
+                breakpoints: breakpoints.into_boxed_slice(),
+            };
+        }
+
+        let index = resolved
+            .breakpoints
+            .binary_search_by_key(&pc, |(pc, _)| *pc)
+            .ok()?;
+        Some(resolved.breakpoints[index].1)
+    }
+}
+
+impl Context {
+    /// Pauses before the instruction at the current `pc` if the debugger requires it.
+    #[inline]
+    pub(crate) fn debugger_before_instruction(&mut self, opcode: Opcode) {
+        let state = &self.vm.debugger;
+        if state.debugger.is_none() || state.paused {
+            return;
+        }
+
+        let frame = self.vm.frame();
+        let pc = frame.pc;
+        let reason = if opcode == Opcode::Debugger {
+            Some(PauseReason::DebuggerStatement)
+        } else if let Some(id) = state.breakpoint_at(frame.code_block(), pc) {
+            Some(PauseReason::Breakpoint(id))
+        } else if state.step.is_some() || state.pause_handle.is_requested() {
+            // Steps and requested pauses only stop on code that has a source position.
+            frame
+                .code_block()
+                .source_info
+                .map()
+                .find(pc)
+                .and_then(|position| {
+                    if state.pause_handle.is_requested() {
+                        Some(PauseReason::Requested)
+                    } else {
+                        state
+                            .step
+                            .filter(|step| step.should_pause(self.vm.frames.len(), position))
+                            .map(|_| PauseReason::Step)
+                    }
+                })
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            self.debugger_pause(reason, pc);
+        }
+    }
+
+    /// Pauses on a thrown exception if the debugger requires it.
+    pub(crate) fn debugger_exception(&mut self, error: &JsError) {
+        let state = &self.vm.debugger;
+        if state.debugger.is_none() || state.paused || !error.is_catchable() {
+            return;
+        }
+
+        match state.exception_pause_mode {
+            ExceptionPauseMode::None => return,
+            ExceptionPauseMode::Uncaught if self.exception_is_caught() => return,
+            ExceptionPauseMode::Uncaught | ExceptionPauseMode::All => {}
+        }
+
+        // Note: -1 because we increment after fetching the opcode.
+        let pc = self.vm.frame().pc.saturating_sub(1);
+        self.debugger_pause(PauseReason::Exception(error.clone()), pc);
+    }
+
+    /// Returns `true` if an exception thrown by the current instruction is caught by a
+    /// handler of a frame in the call stack.
+    fn exception_is_caught(&self) -> bool {
+        // Skip the dummy frame.
+        let mut frames = self.vm.frames.iter().skip(1).rev();
+        let Some(current) = frames.next() else {
+            return false;
+        };
+
+        current
+            .code_block()
+            .find_handler(current.pc.saturating_sub(1))
+            .is_some()
+            || frames.any(|frame| frame.code_block().find_handler(frame.pc).is_some())
+    }
+
+    /// Calls the debugger with the current call stack, `pc` being the paused instruction of
+    /// the current frame.
+    fn debugger_pause(&mut self, reason: PauseReason, pc: u32) {
+        let Some(debugger) = self.vm.debugger.debugger.clone() else {
+            return;
+        };
+
+        let frames = StackFrame::collect(self, pc);
+        let depth = self.vm.frames.len();
+        let line = frames
+            .first()
+            .and_then(|frame| frame.location().position)
+            .map(Position::line_number);
+        let event = PausedEvent { reason, frames };
+
+        let state = &mut self.vm.debugger;
+        state.paused = true;
+        state.step = None;
+        state.pause_handle.clear();
+
+        let action = debugger.paused(&event, self);
+
+        let state = &mut self.vm.debugger;
+        state.paused = false;
+        state.step = (action != ResumeAction::Continue).then_some(Step {
+            action,
+            depth,
+            line,
+        });
+    }
+}
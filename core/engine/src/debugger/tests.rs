@@ -0,0 +1,379 @@
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::{
+    Breakpoint, Debugger, ExceptionPauseMode, FrameScope, PauseReason, PausedEvent, ResumeAction,
+    ScopeKind,
+};
+use crate::{Context, JsResult, JsValue, js_string};
+
+type Handler = Box<dyn Fn(&PausedEvent, &mut Context) -> ResumeAction>;
+
+/// A debugger that records where execution paused.
+struct Recorder {
+    pauses: RefCell<Vec<Pause>>,
+    handler: Handler,
+}
+
+#[derive(Debug)]
+struct Pause {
+    reason: PauseReason,
+    function: String,
+    line: u32,
+    column: u32,
+    depth: usize,
+}
+
+impl Debugger for Recorder {
+    fn paused(&self, event: &PausedEvent, context: &mut Context) -> ResumeAction {
+        let location = event.frames()[0].location();
+        let position = location.position.expect("paused without a position");
+        self.pauses.borrow_mut().push(Pause {
+            reason: event.reason().clone(),
+            function: location.function_name.to_std_string_escaped(),
+            line: position.line_number(),
+            column: position.column_number(),
+            depth: event.frames().len(),
+        });
+        (self.handler)(event, context)
+    }
+}
+
+fn run(
+    code: &str,
+    setup: impl FnOnce(&mut Context),
+    handler: impl Fn(&PausedEvent, &mut Context) -> ResumeAction + 'static,
+) -> (Vec<Pause>, JsResult<JsValue>) {
+    let recorder = Rc::new(Recorder {
+        pauses: RefCell::default(),
+        handler: Box::new(handler),
+    });
+    let context = &mut Context::default();
+    context.attach_debugger(recorder.clone());
+    setup(context);
+    let result = context.eval(Source::from_bytes(code));
+    context.detach_debugger();
+    (recorder.pauses.take(), result)
+}
+
+fn lines(pauses: &[Pause]) -> Vec<u32> {
+    pauses.iter().map(|pause| pause.line).collect()
+}
+
+#[test]
+fn debugger_statement() {
+    let (pauses, result) = run(
+        indoc! {r#"
+            let a = 1;
+            debugger;
+            a + 1
+        "#},
+        |_| {},
+        |_, _| ResumeAction::Continue,
+    );
+
+    assert_eq!(result.unwrap(), JsValue::new(2));
+    assert_eq!(pauses.len(), 1);
+    assert!(matches!(pauses[0].reason, PauseReason::DebuggerStatement));
+    assert_eq!((pauses[0].line, pauses[0].column), (2, 1));
+}
+
+#[test]
+fn debugger_statement_without_debugger() {
+    let context = &mut Context::default();
+    let result = context.eval(Source::from_bytes("debugger; 1"));
+    assert_eq!(result.unwrap(), JsValue::new(1));
+}
+
+#[test]
+fn line_breakpoints() {
+    let (pauses, _) = run(
+        indoc! {r#"
+            function f(x) {
+                return x * 2;
+            }
+            let a = f(1);
+
+            let b = f(a);
+        "#},
+        |context| {
+            context.set_breakpoint(Breakpoint::new(2));
+            context.set_breakpoint(Breakpoint::new(5));
+            context.set_breakpoint(Breakpoint::new(6));
+        },
+        |_, _| ResumeAction::Continue,
+    );
+
+    // Line 5 is empty, so its breakpoint never pauses.
+    assert_eq!(lines(&pauses), [2, 6, 2]);
+    assert_eq!(pauses[0].function, "f");
+    assert_eq!(pauses[1].function, "<main>");
+    assert!(matches!(pauses[0].reason, PauseReason::Breakpoint(_)));
+}
+
+#[test]
+fn column_breakpoints() {
+    let (pauses, _) = run(
+        "let f = () => 1; let g = () => 2; f(); g();",
+        |context| {
+            context.set_breakpoint(Breakpoint::new(1).with_column(32));
+        },
+        |_, _| ResumeAction::Continue,
+    );
+
+    assert_eq!(pauses.len(), 1);
+    assert_eq!(pauses[0].function, "g");
+}
+
+#[test]
+fn breakpoint_paths() {
+    let recorder = Rc::new(Recorder {
+        pauses: RefCell::default(),
+        handler: Box::new(|_, _| ResumeAction::Continue),
+    });
+    let context = &mut Context::default();
+    context.attach_debugger(recorder.clone());
+    context.set_breakpoint(Breakpoint::new(1).with_path(Path::new("a.js")));
+
+    let source = Source::from_bytes("1;");
+    context.eval(source.with_path(Path::new("b.js"))).unwrap();
+    context.eval(Source::from_bytes("1;")).unwrap();
+    assert!(recorder.pauses.borrow().is_empty());
+
+    let source = Source::from_bytes("1;");
+    context.eval(source.with_path(Path::new("a.js"))).unwrap();
+    assert_eq!(recorder.pauses.borrow().len(), 1);
+}
+
+#[test]
+fn remove_breakpoint() {
+    let recorder = Rc::new(Recorder {
+        pauses: RefCell::default(),
+        handler: Box::new(|_, _| ResumeAction::Continue),
+    });
+    let context = &mut Context::default();
+    context.attach_debugger(recorder.clone());
+    context
+        .eval(Source::from_bytes("function f() {\n  return 1;\n}"))
+        .unwrap();
+
+    let id = context.set_breakpoint(Breakpoint::new(2));
+    context.eval(Source::from_bytes("f()")).unwrap();
+    assert_eq!(recorder.pauses.borrow().len(), 1);
+
+    assert!(context.remove_breakpoint(id));
+    assert!(!context.remove_breakpoint(id));
+    context.eval(Source::from_bytes("f()")).unwrap();
+    assert_eq!(recorder.pauses.borrow().len(), 1);
+}
+
+const STEPPING: &str = indoc! {r#"
+    function f(x) {
+        let y = x + 1;
+        return y;
+    }
+    debugger;
+    let a = f(1);
+    let b = a;
+"#};
+
+#[test]
+fn step_over() {
+    let (pauses, _) = run(STEPPING, |_| {}, |_, _| ResumeAction::StepOver);
+    assert_eq!(lines(&pauses), [5, 6, 7]);
+    assert!(matches!(pauses[1].reason, PauseReason::Step));
+}
+
+#[test]
+fn step_in() {
+    let (pauses, _) = run(STEPPING, |_| {}, |_, _| ResumeAction::StepIn);
+    assert_eq!(lines(&pauses), [5, 6, 2, 3, 6, 7]);
+    assert_eq!(pauses[2].function, "f");
+    assert_eq!(pauses[2].depth, 2);
+}
+
+#[test]
+fn step_out() {
+    let (pauses, _) = run(
+        STEPPING,
+        |context| {
+            context.set_breakpoint(Breakpoint::new(2));
+        },
+        |event, _| match event.reason() {
+            PauseReason::Breakpoint(_) => ResumeAction::StepOut,
+            _ => ResumeAction::Continue,
+        },
+    );
+
+    // Returns to the rest of the statement that called `f`.
+    assert_eq!(lines(&pauses), [5, 2, 6]);
+    assert_eq!(pauses[2].function, "<main>");
+}
+
+#[test]
+fn exceptions() {
+    let code = indoc! {r#"
+        try {
+            throw new Error("caught");
+        } catch {}
+        null.x;
+    "#};
+
+    let (pauses, result) = run(code, |_| {}, |_, _| ResumeAction::Continue);
+    assert!(result.is_err());
+    assert!(pauses.is_empty());
+
+    let (pauses, _) = run(
+        code,
+        |context| context.set_exception_pause_mode(ExceptionPauseMode::Uncaught),
+        |_, _| ResumeAction::Continue,
+    );
+    assert_eq!(lines(&pauses), [4]);
+
+    let (pauses, _) = run(
+        code,
+        |context| context.set_exception_pause_mode(ExceptionPauseMode::All),
+        |_, _| ResumeAction::Continue,
+    );
+    assert_eq!(lines(&pauses), [2, 4]);
+    assert!(matches!(pauses[0].reason, PauseReason::Exception(_)));
+}
+
+#[test]
+fn exceptions_caught_by_callers() {
+    let (pauses, _) = run(
+        indoc! {r#"
+            function f() {
+                throw 1;
+            }
+            try {
+                f();
+            } catch {}
+        "#},
+        |context| context.set_exception_pause_mode(ExceptionPauseMode::Uncaught),
+        |_, _| ResumeAction::Continue,
+    );
+    assert!(pauses.is_empty());
+}
+
+#[test]
+fn inspect_frames() {
+    let (pauses, result) = run(
+        indoc! {r#"
+            let global = "g";
+            function outer(a) {
+                let captured = a + 1;
+                return function inner(b) {
+                    "use strict";
+                    let local = b * 2;
+                    {
+                        // Captured, so it is stored in the environment of the block.
+                        let block = 3;
+                        let read = () => block;
+                        debugger;
+                    }
+                    return captured + local;
+                };
+            }
+            outer(1).call("this", 5);
+        "#},
+        |_| {},
+        |event, context| {
+            let frame = &event.frames()[0];
+            assert_eq!(frame.this(context).unwrap(), js_string!("this").into());
+            assert_eq!(frame.arguments(context), [JsValue::new(5)]);
+            assert!(frame.function(context).is_some());
+            assert!(!frame.registers(context).is_empty());
+
+            let scopes = frame.scopes(context).unwrap();
+            let kinds = scopes.iter().map(FrameScope::kind).collect::<Vec<_>>();
+            assert_eq!(
+                kinds,
+                [
+                    ScopeKind::Block,
+                    ScopeKind::Local,
+                    ScopeKind::Closure,
+                    ScopeKind::Script,
+                    ScopeKind::Global
+                ]
+            );
+            let get = |index: usize, name: &str, context: &mut Context| {
+                scopes[index]
+                    .object()
+                    .get(js_string!(name), context)
+                    .unwrap()
+            };
+            assert_eq!(get(0, "block", context), JsValue::new(3));
+            assert_eq!(get(1, "b", context), JsValue::new(5));
+            assert_eq!(get(1, "local", context), JsValue::new(10));
+            assert_eq!(get(2, "captured", context), JsValue::new(2));
+            assert_eq!(get(3, "global", context), js_string!("g").into());
+
+            assert_eq!(event.frames().len(), 2);
+            assert_eq!(
+                event.frames()[1].location().function_name,
+                js_string!("<main>")
+            );
+            ResumeAction::Continue
+        },
+    );
+
+    assert_eq!(pauses.len(), 1);
+    assert_eq!(result.unwrap(), JsValue::new(12));
+}
+
+#[test]
+fn evaluate_in_frame() {
+    let (pauses, result) = run(
+        indoc! {r#"
+            function f(a) {
+                let b = 2;
+                const c = 3;
+                debugger;
+                return a + b + c;
+            }
+            f(1);
+        "#},
+        |_| {},
+        |event, context| {
+            let frame = &event.frames()[0];
+            assert_eq!(
+                frame.evaluate("a + b + c", context).unwrap(),
+                JsValue::new(6)
+            );
+
+            frame.evaluate("a = 10; b = 20", context).unwrap();
+            assert!(frame.evaluate("c = 30", context).is_err());
+            assert!(frame.evaluate("missing", context).is_err());
+
+            // Code evaluated in the frame doesn't pause.
+            assert_eq!(
+                frame.evaluate("debugger; 1", context).unwrap(),
+                JsValue::new(1)
+            );
+            ResumeAction::Continue
+        },
+    );
+
+    assert_eq!(pauses.len(), 1);
+    assert_eq!(result.unwrap(), JsValue::new(33));
+}
+
+#[test]
+fn pause_handle() {
+    let recorder = Rc::new(Recorder {
+        pauses: RefCell::default(),
+        handler: Box::new(|_, _| ResumeAction::Continue),
+    });
+    let context = &mut Context::default();
+    context.attach_debugger(recorder.clone());
+
+    context.debugger_pause_handle().request_pause();
+    context.eval(Source::from_bytes("1;\n2;")).unwrap();
+
+    let pauses = recorder.pauses.borrow();
+    assert_eq!(lines(&pauses), [1]);
+    assert!(matches!(pauses[0].reason, PauseReason::Requested));
+}
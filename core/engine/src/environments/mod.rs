@@ -27,7 +27,8 @@
 mod runtime;
 
 pub(crate) use runtime::{
-    DeclarativeEnvironment, Environment, EnvironmentStack, FunctionSlots, PrivateEnvironment,
+    DeclarativeEnvironment, DeclarativeEnvironmentKind, Environment, EnvironmentNode,
+    EnvironmentStack, FunctionEnvironment, FunctionSlots, LexicalEnvironment, PrivateEnvironment,
     SavedEnvironments, ThisBindingStatus,
 };
 
@@ -35,6 +35,11 @@ impl FunctionEnvironment {
         &self.slots
     }
 
+    /// Gets the bindings of this function environment.
+    pub(crate) const fn bindings(&self) -> &GcRefCell<Vec<Option<JsValue>>> {
+        &self.bindings
+    }
+
     /// Gets the compile time environment of this function environment.
     pub(crate) const fn compile(&self) -> &Scope {
         &self.scope
@@ -272,6 +277,11 @@ impl FunctionSlots {
         }
     }
 
+    /// Returns the values of the `[[ThisValue]]` and `[[ThisBindingStatus]]` internal slots.
+    pub(crate) fn this_binding_status(&self) -> ThisBindingStatus {
+        self.this.borrow().clone()
+    }
+
     /// Returns the value of the `[[FunctionObject]]` internal slot.
     pub(crate) const fn function_object(&self) -> &JsObject {
         &self.function_object
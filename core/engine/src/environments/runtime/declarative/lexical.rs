@@ -5,6 +5,11 @@ use crate::JsValue;
 #[derive(Debug, Trace, Finalize)]
 pub(crate) struct LexicalEnvironment {
     bindings: GcRefCell<Vec<Option<JsValue>>>,
+
+    /// The compile time scope of the environment, used to name its bindings in the debugger.
+    #[cfg(feature = "debugger")]
+    #[unsafe_ignore_trace]
+    scope: std::cell::OnceCell<boa_ast::scope::Scope>,
 }
 
 impl LexicalEnvironment {
@@ -12,9 +17,23 @@ impl LexicalEnvironment {
     pub(crate) fn new(bindings: u32) -> Self {
         Self {
             bindings: GcRefCell::new(vec![None; bindings as usize]),
+            #[cfg(feature = "debugger")]
+            scope: std::cell::OnceCell::new(),
         }
     }
 
+    /// Gets the compile time scope of this environment, if it was recorded.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn scope(&self) -> Option<&boa_ast::scope::Scope> {
+        self.scope.get()
+    }
+
+    /// Records the compile time scope of this environment.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn set_scope(&self, scope: boa_ast::scope::Scope) {
+        self.scope.get_or_init(|| scope);
+    }
+
     /// Gets the binding value from the environment by it's index.
     ///
     /// # Panics
@@ -36,7 +55,6 @@ impl LexicalEnvironment {
     }
 
     /// Gets the bindings of this poisonable environment.
-    #[expect(dead_code)]
     pub(crate) const fn bindings(&self) -> &GcRefCell<Vec<Option<JsValue>>> {
         &self.bindings
     }
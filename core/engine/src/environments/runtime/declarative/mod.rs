@@ -208,6 +208,18 @@ impl DeclarativeEnvironmentKind {
         }
     }
 
+    /// Gets the values of all the bindings of the environment, in binding index order.
+    ///
+    /// Indirect bindings of module environments are returned as `None`.
+    pub(crate) fn binding_values(&self) -> Vec<Option<JsValue>> {
+        match self {
+            Self::Lexical(inner) => inner.bindings().borrow().clone(),
+            Self::Global(inner) => inner.bindings().borrow().clone(),
+            Self::Function(inner) => inner.bindings().borrow().clone(),
+            Self::Module(inner) => inner.direct_binding_values(),
+        }
+    }
+
     /// Sets the binding value from the environment by index.
     ///
     /// # Panics
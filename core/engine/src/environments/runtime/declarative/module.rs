@@ -57,6 +57,21 @@ impl ModuleEnvironment {
         &self.compile
     }
 
+    /// Gets the values of the direct bindings of this module environment.
+    ///
+    /// Indirect bindings are returned as `None`, since their values live in the environment
+    /// of another module.
+    pub(crate) fn direct_binding_values(&self) -> Vec<Option<JsValue>> {
+        self.bindings
+            .borrow()
+            .iter()
+            .map(|binding| match binding {
+                BindingType::Direct(value) => value.clone(),
+                BindingType::Indirect(_) => None,
+            })
+            .collect()
+    }
+
     /// Get the binding value from the environment by it's index.
     ///
     /// # Panics
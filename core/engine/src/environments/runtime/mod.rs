@@ -145,6 +145,11 @@ impl EnvironmentStack {
         }
     }
 
+    /// Get the address of the tip node of the chain, which identifies it in heap snapshots.
+    pub(crate) fn tip_addr(&self) -> Option<usize> {
+        self.tip.as_ref().map(Gc::addr)
+    }
+
     /// Get the tip (most recently pushed) environment.
     #[inline]
     fn last(&self) -> Option<&Environment> {
@@ -228,6 +233,16 @@ impl EnvironmentStack {
         index
     }
 
+    /// Records the compile time scope of the last pushed lexical environment.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn set_lexical_scope(&self, scope: &Scope) {
+        if let Some(Environment::Declarative(env)) = self.last()
+            && let DeclarativeEnvironmentKind::Lexical(lexical) = env.kind()
+        {
+            lexical.set_scope(scope.clone());
+        }
+    }
+
     /// Push a function environment on the environments stack.
     pub(crate) fn push_function(
         &mut self,
@@ -401,6 +416,32 @@ impl EnvironmentStack {
         self.clone()
     }
 
+    /// Creates an environment stack from its environments, starting from the root.
+    pub(crate) fn from_environments(environments: impl IntoIterator<Item = Environment>) -> Self {
+        let mut stack = Self::new();
+        for env in environments {
+            stack.push_env(env);
+        }
+        stack
+    }
+
+    /// Returns the environments of the stack, starting from the root.
+    pub(crate) fn environments(&self) -> Vec<Environment> {
+        let mut environments = self
+            .iter_from_tip()
+            .map(|(env, _)| env.clone())
+            .collect::<Vec<_>>();
+        environments.reverse();
+        environments
+    }
+
+    /// Returns `true` if the private environments of the stack declare any private name.
+    pub(crate) fn has_private_names(&self) -> bool {
+        self.private_stack
+            .iter()
+            .any(|environment| !environment.descriptions().is_empty())
+    }
+
     // ---- Private helpers ----
 
     /// Push an environment onto the chain.
@@ -14,11 +14,12 @@ use crate::{
     property::PropertyDescriptor,
     realm::Realm,
     vm::{
-        NativeSourceInfo,
+        NativeSourceInfo, StackFrame,
         shadow_stack::{Backtrace, ErrorStack, ShadowEntry},
     },
 };
 use boa_gc::{Finalize, Trace, custom_trace};
+use boa_parser::diagnostic::Diagnostic;
 use std::{borrow::Cow, error, fmt};
 use thiserror::Error;
 
@@ -649,6 +650,7 @@ impl JsError {
                     cause: cause.map(|v| Box::new(Self::from_opaque(v))),
                     realm: Some(realm),
                     stack: location,
+                    diagnostic: IgnoreEq(None),
                 })
             }
         }
@@ -801,6 +803,33 @@ impl JsError {
         }
     }
 
+    /// Returns the backtrace of the error as [`StackFrame`]s, ordered with the most recent frames
+    /// first, followed by the async functions and generators that were awaiting the code that
+    /// threw the error.
+    ///
+    /// Returns `None` if the error has no backtrace, which is the case of errors that were not
+    /// thrown while running JavaScript.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use boa_engine::{Context, Source};
+    /// let context = &mut Context::default();
+    /// let error = context
+    ///     .eval(Source::from_bytes("function f() {\n  throw new Error('oops');\n}\nf();"))
+    ///     .unwrap_err();
+    ///
+    /// let frames = error.stack_frames().unwrap();
+    /// assert_eq!(frames[0].function_name(), Some("f"));
+    /// assert_eq!(frames[0].line(), Some(2));
+    /// assert_eq!(frames[1].function_name(), Some("<main>"));
+    /// assert_eq!(frames[1].line(), Some(4));
+    /// ```
+    #[must_use]
+    pub fn stack_frames(&self) -> Option<Vec<StackFrame>> {
+        self.backtrace.as_ref().map(Backtrace::stack_frames)
+    }
+
     /// Injects a realm on the `realm` field of a native error.
     ///
     /// This is a no-op if the error is not native or if the `realm` field of the error is already
@@ -865,13 +894,16 @@ impl fmt::Display for JsError {
             for entry in shadow_stack.iter().rev() {
                 write!(f, "\n    at {}", entry.display(true))?;
             }
+            for entry in shadow_stack.async_iter() {
+                write!(f, "\n    at async {}", entry.display(true))?;
+            }
         }
         Ok(())
     }
 }
 
 /// Helper struct that ignores equality operator.
-#[derive(Debug, Clone, Finalize)]
+#[derive(Debug, Clone, Trace, Finalize)]
 pub(crate) struct IgnoreEq<T>(pub(crate) T);
 
 impl<T> Eq for IgnoreEq<T> {}
@@ -924,6 +956,7 @@ pub struct JsNativeError {
     cause: Option<Box<JsError>>,
     realm: Option<Realm>,
     pub(crate) stack: IgnoreEq<ErrorStack>,
+    diagnostic: IgnoreEq<Option<Box<Diagnostic>>>,
 }
 
 impl fmt::Display for JsNativeError {
@@ -949,6 +982,7 @@ unsafe impl Trace for JsNativeError {
         mark(&this.kind);
         mark(&this.cause);
         mark(&this.realm);
+        mark(&this.stack);
     });
 }
 
@@ -991,6 +1025,7 @@ impl JsNativeError {
                 function_name: None,
                 source_info: NativeSourceInfo::caller(),
             })),
+            diagnostic: IgnoreEq(None),
         }
     }
 
@@ -1292,6 +1327,16 @@ impl JsNativeError {
         self.cause.as_deref()
     }
 
+    /// Gets the diagnostic of the parser error this error was created from, if any.
+    ///
+    /// The diagnostic has the location of the error in the parsed source code, so it can be
+    /// rendered as a code frame of that source code.
+    #[must_use]
+    #[inline]
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.0.as_deref()
+    }
+
     /// Converts this native error to its opaque representation as a [`JsObject`].
     ///
     /// # Examples
@@ -1318,6 +1363,7 @@ impl JsNativeError {
             cause,
             realm,
             stack,
+            diagnostic: _,
         } = self;
         let constructors = realm.as_ref().map_or_else(
             || context.intrinsics().constructors(),
@@ -1398,7 +1444,9 @@ impl JsNativeError {
 impl From<boa_parser::Error> for JsNativeError {
     #[cfg_attr(feature = "native-backtrace", track_caller)]
     fn from(err: boa_parser::Error) -> Self {
-        Self::syntax().with_message(err.to_string())
+        let mut error = Self::syntax().with_message(err.to_string());
+        error.diagnostic = IgnoreEq(Some(Box::new(Diagnostic::from(&err))));
+        error
     }
 }
 
@@ -1,11 +1,13 @@
 use std::path::Path;
 
 use crate::{
-    Context, JsError, Source,
+    Context, JsError, JsNativeError, Source,
     builtins::promise::PromiseState,
     module::Module,
+    script::Script,
     vm::{shadow_stack::ShadowEntry, source_info::SourcePath},
 };
+use boa_ast::Position;
 use indoc::indoc;
 
 /// Helper to extract backtrace entries from a rejected module promise.
@@ -176,3 +178,26 @@ fn eval_error_has_backtrace() {
         }
     }
 }
+
+#[test]
+fn syntax_error_has_diagnostic() {
+    let context = &mut Context::default();
+    let code = indoc! {br#"
+        let a = 1;
+        let a = 2;
+    "#};
+    let error = Script::parse(Source::from_bytes(code), None, context)
+        .expect_err("the script should not parse");
+
+    let diagnostic = error
+        .as_native()
+        .and_then(JsNativeError::diagnostic)
+        .expect("syntax errors should have a diagnostic");
+    let span = diagnostic
+        .span()
+        .expect("the diagnostic should have a span");
+    assert_eq!(span.start(), Position::new(2, 5));
+    assert_eq!(diagnostic.labels().len(), 1);
+
+    assert!(JsNativeError::syntax().diagnostic().is_none());
+}
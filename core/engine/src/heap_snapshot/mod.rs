@@ -0,0 +1,578 @@
+//! Heap snapshots in the V8 `.heapsnapshot` format.
+//!
+//! A [`HeapSnapshot`] captures every value managed by the garbage collector, together with the
+//! references between them, and can be written as the JSON format used by the Chrome developer tools
+//! memory panel. This makes it possible to inspect what is keeping objects alive using standard
+//! tooling.
+//!
+//! The snapshot is built from [`boa_gc::heap::walk_heap`], enriched with engine knowledge:
+//!  - Objects are named after their constructor, and functions after their [`CodeBlock`].
+//!  - References from objects are named after the property key or element index they are
+//!    stored in.
+//!  - References from environments are named after the index of the binding slot.
+//!
+//! Allocations the engine doesn't know about are still included, named after their Rust type.
+//!
+//! More information:
+//!  - [Chrome developer tools heap snapshot format][format]
+//!
+//! [format]: https://developer.chrome.com/docs/devtools/memory-problems/heap-snapshots
+
+use crate::{
+    Context, JsObject, JsValue,
+    builtins::{function::OrdinaryFunction, regexp::RegExp},
+    environments::{DeclarativeEnvironment, EnvironmentNode},
+    js_string,
+    object::{ErasedVTableObject, JsPrototype},
+    property::PropertyKey,
+    vm::CodeBlock,
+};
+use boa_gc::{
+    Gc, GcErased,
+    heap::{HeapNode, HeapNodeKind, walk_heap},
+};
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::io;
+
+#[cfg(test)]
+mod tests;
+
+/// The fields of each node in the flat `nodes` array.
+const NODE_FIELDS: &[&str] = &[
+    "type",
+    "name",
+    "id",
+    "self_size",
+    "edge_count",
+    "trace_node_id",
+    "detachedness",
+];
+
+/// The fields of each edge in the flat `edges` array.
+const EDGE_FIELDS: &[&str] = &["type", "name_or_index", "to_node"];
+
+/// The node types, in the order expected by the Chrome developer tools.
+const NODE_TYPES: &[&str] = &[
+    "hidden",
+    "array",
+    "string",
+    "object",
+    "code",
+    "closure",
+    "regexp",
+    "number",
+    "native",
+    "synthetic",
+    "concatenated string",
+    "sliced string",
+    "symbol",
+    "bigint",
+    "object shape",
+];
+
+/// The edge types, in the order expected by the Chrome developer tools.
+const EDGE_TYPES: &[&str] = &[
+    "context", "element", "property", "internal", "hidden", "shortcut", "weak",
+];
+
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+enum NodeType {
+    Hidden = 0,
+    Object = 3,
+    Code = 4,
+    Closure = 5,
+    RegExp = 6,
+    Synthetic = 9,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum EdgeType {
+    Context = 0,
+    Element = 1,
+    Property = 2,
+    Internal = 3,
+    Hidden = 4,
+    Shortcut = 5,
+    Weak = 6,
+}
+
+/// The name of an edge, which is either an index into the strings table or an element index,
+/// depending on the [`EdgeType`].
+#[derive(Debug, Clone)]
+enum EdgeName {
+    String(String),
+    Index(u64),
+}
+
+#[derive(Debug)]
+struct Node {
+    ty: NodeType,
+    name: String,
+    size: usize,
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug)]
+struct Edge {
+    ty: EdgeType,
+    name: EdgeName,
+    target: usize,
+}
+
+/// A snapshot of the garbage collected heap, in the V8 `.heapsnapshot` format.
+///
+/// The snapshot contains all the values allocated by the garbage collector in the current
+/// thread, which includes the values of every [`Context`] living in the same thread.
+///
+/// Use [`Context::heap_snapshot`] to take a snapshot, and [`HeapSnapshot::write_json`] to
+/// export it to a file that can be loaded in the memory panel of the Chrome developer tools.
+#[derive(Debug)]
+pub struct HeapSnapshot {
+    nodes: Vec<u64>,
+    edges: Vec<u64>,
+    strings: Vec<String>,
+}
+
+impl HeapSnapshot {
+    /// Takes a new heap snapshot.
+    ///
+    /// This runs a garbage collection first, so that only reachable values are captured.
+    pub(crate) fn new(context: &Context) -> Self {
+        boa_gc::force_collect();
+
+        let heap = walk_heap();
+        let global = context.global_object();
+
+        let mut builder = Builder::default();
+
+        // DevTools expects the first node to be the synthetic root of the graph.
+        let root = builder.push(NodeType::Synthetic, String::new(), 0);
+        let gc_roots = builder.push(NodeType::Synthetic, "(GC roots)".to_string(), 0);
+
+        let indices = heap
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id(), i + 2))
+            .collect::<FxHashMap<_, _>>();
+
+        for node in &heap {
+            let (ty, name, edges) = describe(node);
+            let index = builder.push(ty, name, node.size());
+
+            let mut unnamed = node.edges().to_vec();
+            for (ty, name, target) in edges {
+                let Some(&target) = indices.get(&target) else {
+                    continue;
+                };
+
+                // The named reference replaces the raw reference found while tracing.
+                if let Some(position) = unnamed
+                    .iter()
+                    .position(|edge| indices.get(&edge.target()) == Some(&target))
+                {
+                    unnamed.swap_remove(position);
+                }
+
+                builder.edge(index, ty, name, target);
+            }
+
+            for (i, edge) in unnamed.iter().enumerate() {
+                let Some(&target) = indices.get(&edge.target()) else {
+                    continue;
+                };
+                let ty = if edge.is_weak() {
+                    EdgeType::Weak
+                } else {
+                    EdgeType::Hidden
+                };
+                builder.edge(index, ty, EdgeName::Index(i as u64), target);
+            }
+
+            if node.is_root() {
+                let position = builder.nodes[gc_roots].edges.len() as u64;
+                builder.edge(
+                    gc_roots,
+                    EdgeType::Element,
+                    EdgeName::Index(position),
+                    index,
+                );
+            }
+        }
+
+        builder.edge(root, EdgeType::Element, EdgeName::Index(1), gc_roots);
+        if let Some(&global) = indices.get(&Gc::addr(global.inner())) {
+            builder.edge(
+                root,
+                EdgeType::Shortcut,
+                EdgeName::String("globalThis".to_string()),
+                global,
+            );
+        }
+
+        builder.finish()
+    }
+
+    /// Returns the number of nodes in the snapshot, including the synthetic root nodes.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() / NODE_FIELDS.len()
+    }
+
+    /// Returns the number of edges in the snapshot.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len() / EDGE_FIELDS.len()
+    }
+
+    /// Writes the snapshot as `.heapsnapshot` JSON to `writer`.
+    pub fn write_json<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.to_serializable()).map_err(io::Error::from)
+    }
+
+    /// Returns the snapshot as a `.heapsnapshot` JSON string.
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(&self.to_serializable())
+            .expect("heap snapshots can always be serialized")
+    }
+
+    fn to_serializable(&self) -> SerializableSnapshot<'_> {
+        SerializableSnapshot {
+            snapshot: SnapshotInfo {
+                meta: SnapshotMeta {
+                    node_fields: NODE_FIELDS,
+                    node_types: (NODE_TYPES, "string", "number", "number", "number", "number"),
+                    edge_fields: EDGE_FIELDS,
+                    edge_types: (EDGE_TYPES, "string_or_number", "node"),
+                    trace_function_info_fields: &[],
+                    trace_node_fields: &[],
+                    sample_fields: &[],
+                    location_fields: &[],
+                },
+                node_count: self.node_count(),
+                edge_count: self.edge_count(),
+                trace_function_count: 0,
+            },
+            nodes: &self.nodes,
+            edges: &self.edges,
+            trace_function_infos: &[],
+            trace_tree: &[],
+            samples: &[],
+            locations: &[],
+            strings: &self.strings,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SerializableSnapshot<'a> {
+    snapshot: SnapshotInfo,
+    nodes: &'a [u64],
+    edges: &'a [u64],
+    trace_function_infos: &'a [u64],
+    trace_tree: &'a [u64],
+    samples: &'a [u64],
+    locations: &'a [u64],
+    strings: &'a [String],
+}
+
+#[derive(Serialize)]
+struct SnapshotInfo {
+    meta: SnapshotMeta,
+    node_count: usize,
+    edge_count: usize,
+    trace_function_count: usize,
+}
+
+#[derive(Serialize)]
+struct SnapshotMeta {
+    node_fields: &'static [&'static str],
+    node_types: (
+        &'static [&'static str],
+        &'static str,
+        &'static str,
+        &'static str,
+        &'static str,
+        &'static str,
+    ),
+    edge_fields: &'static [&'static str],
+    edge_types: (&'static [&'static str], &'static str, &'static str),
+    trace_function_info_fields: &'static [&'static str],
+    trace_node_fields: &'static [&'static str],
+    sample_fields: &'static [&'static str],
+    location_fields: &'static [&'static str],
+}
+
+/// Collects the nodes and edges of the snapshot before flattening them.
+#[derive(Default)]
+struct Builder {
+    nodes: Vec<Node>,
+}
+
+impl Builder {
+    fn push(&mut self, ty: NodeType, name: String, size: usize) -> usize {
+        self.nodes.push(Node {
+            ty,
+            name,
+            size,
+            edges: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, ty: EdgeType, name: EdgeName, target: usize) {
+        self.nodes[from].edges.push(Edge { ty, name, target });
+    }
+
+    fn finish(self) -> HeapSnapshot {
+        let mut strings = Vec::new();
+        let mut string_indices = FxHashMap::default();
+        let mut intern = |string: String| -> u64 {
+            *string_indices.entry(string).or_insert_with_key(|string| {
+                strings.push(string.clone());
+                strings.len() as u64 - 1
+            })
+        };
+
+        let mut nodes = Vec::with_capacity(self.nodes.len() * NODE_FIELDS.len());
+        let mut edges = Vec::new();
+
+        for (index, node) in self.nodes.into_iter().enumerate() {
+            nodes.extend([
+                node.ty as u64,
+                intern(node.name),
+                // Node ids must be unique and fit in 32 bits, so they cannot be addresses.
+                index as u64 * 2 + 1,
+                node.size as u64,
+                node.edges.len() as u64,
+                0,
+                0,
+            ]);
+
+            for edge in node.edges {
+                let name = match edge.name {
+                    EdgeName::String(name) => intern(name),
+                    EdgeName::Index(index) => index,
+                };
+                edges.extend([
+                    edge.ty as u64,
+                    name,
+                    (edge.target * NODE_FIELDS.len()) as u64,
+                ]);
+            }
+        }
+
+        HeapSnapshot {
+            nodes,
+            edges,
+            strings,
+        }
+    }
+}
+
+/// Returns the type, name and named references of a heap node.
+fn describe(node: &HeapNode) -> (NodeType, String, Vec<(EdgeType, EdgeName, usize)>) {
+    let Some(value) = node.value() else {
+        debug_assert_eq!(node.kind(), HeapNodeKind::Ephemeron);
+        return (NodeType::Hidden, "system / WeakRef".to_string(), Vec::new());
+    };
+
+    if let Some(object) = as_object(node, value) {
+        return describe_object(&object);
+    }
+
+    if let Some(code) = value.clone().downcast::<CodeBlock>() {
+        return (
+            NodeType::Code,
+            code.name().to_std_string_escaped(),
+            Vec::new(),
+        );
+    }
+
+    if let Some(env) = value.clone().downcast::<DeclarativeEnvironment>() {
+        let edges = env
+            .kind()
+            .binding_values()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                let object = value?.as_object()?;
+                Some((
+                    EdgeType::Context,
+                    EdgeName::String(i.to_string()),
+                    Gc::addr(object.inner()),
+                ))
+            })
+            .collect();
+        return (NodeType::Object, "system / Context".to_string(), edges);
+    }
+
+    if value.is::<EnvironmentNode>() {
+        return (
+            NodeType::Hidden,
+            "system / EnvironmentNode".to_string(),
+            Vec::new(),
+        );
+    }
+
+    (
+        NodeType::Hidden,
+        short_type_name(node.type_name()),
+        Vec::new(),
+    )
+}
+
+/// Returns the node as an object, if it's the allocation of a [`JsObject`].
+fn as_object(node: &HeapNode, value: &GcErased) -> Option<JsObject> {
+    // Objects are allocated with their concrete data type, so there is no single `TypeId` for
+    // all of them. However, all the instantiations of `VTableObject` share the same path.
+    let erased = std::any::type_name::<ErasedVTableObject>();
+    let path = &erased[..erased.find('<').unwrap_or(erased.len())];
+
+    if !node
+        .type_name()
+        .strip_prefix(path)
+        .is_some_and(|rest| rest.starts_with('<'))
+    {
+        return None;
+    }
+
+    // SAFETY: All `VTableObject`s have the same layout as `ErasedVTableObject`.
+    let inner = unsafe { value.clone().downcast_unchecked::<ErasedVTableObject>() };
+    Some(JsObject::from_inner(inner))
+}
+
+fn describe_object(object: &JsObject) -> (NodeType, String, Vec<(EdgeType, EdgeName, usize)>) {
+    let Ok(borrowed) = object.try_borrow() else {
+        return (NodeType::Object, "Object".to_string(), Vec::new());
+    };
+
+    let mut edges = Vec::new();
+    let mut push_value = |ty, name: EdgeName, value: Option<&JsValue>| {
+        if let Some(object) = value.and_then(JsValue::as_object) {
+            edges.push((ty, name, Gc::addr(object.inner())));
+        }
+    };
+
+    let properties = borrowed.properties();
+    for key in properties.shape.keys() {
+        let Some(property) = properties.get(&key) else {
+            continue;
+        };
+        let name = key.to_string();
+        push_value(
+            EdgeType::Property,
+            EdgeName::String(name.clone()),
+            property.value(),
+        );
+        push_value(
+            EdgeType::Property,
+            EdgeName::String(format!("get {name}")),
+            property.get(),
+        );
+        push_value(
+            EdgeType::Property,
+            EdgeName::String(format!("set {name}")),
+            property.set(),
+        );
+    }
+    for (index, property) in properties.index_properties() {
+        push_value(
+            EdgeType::Element,
+            EdgeName::Index(u64::from(index)),
+            property.value(),
+        );
+    }
+    if let Some(prototype) = borrowed.prototype() {
+        edges.push((
+            EdgeType::Property,
+            EdgeName::String("__proto__".to_string()),
+            Gc::addr(prototype.inner()),
+        ));
+    }
+
+    let (ty, name) = if let Some(function) = object.downcast_ref::<OrdinaryFunction>() {
+        edges.push((
+            EdgeType::Internal,
+            EdgeName::String("code".to_string()),
+            Gc::addr(&function.code),
+        ));
+        if let Some(context) = function.environments.tip_addr() {
+            edges.push((
+                EdgeType::Internal,
+                EdgeName::String("context".to_string()),
+                context,
+            ));
+        }
+        (
+            NodeType::Closure,
+            function.code.name().to_std_string_escaped(),
+        )
+    } else if object.is_callable() {
+        let name = own_string_property(object, &js_string!("name").into());
+        (NodeType::Closure, name.unwrap_or_default())
+    } else if object.is::<RegExp>() {
+        (NodeType::RegExp, "RegExp".to_string())
+    } else {
+        let name = constructor_name(borrowed.prototype());
+        (
+            NodeType::Object,
+            name.unwrap_or_else(|| "Object".to_string()),
+        )
+    };
+
+    (ty, name, edges)
+}
+
+/// Returns the name of the constructor of the given prototype, without running any user code.
+pub(crate) fn constructor_name(prototype: JsPrototype) -> Option<String> {
+    let constructor = prototype?
+        .try_borrow()
+        .ok()?
+        .properties()
+        .get(&js_string!("constructor").into())?
+        .value()?
+        .as_object()?;
+
+    own_string_property(&constructor, &js_string!("name").into())
+}
+
+/// Returns the value of an own data property of `object` if it's a string.
+fn own_string_property(object: &JsObject, key: &PropertyKey) -> Option<String> {
+    object
+        .try_borrow()
+        .ok()?
+        .properties()
+        .get(key)?
+        .value()?
+        .as_string()
+        .map(|name| name.to_std_string_escaped())
+}
+
+/// Removes the module paths from a Rust type name.
+///
+/// For example, `boa_gc::cell::GcRefCell<alloc::vec::Vec<u8>>` becomes `GcRefCell<Vec<u8>>`.
+fn short_type_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut segment_start = 0;
+
+    for (i, c) in name.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            continue;
+        }
+        if c == ':' {
+            // Skips the path segment that ends here, including both colons.
+            segment_start = i + 1;
+            continue;
+        }
+        result.push_str(&name[segment_start..i]);
+        result.push(c);
+        segment_start = i + c.len_utf8();
+    }
+    result.push_str(&name[segment_start..]);
+
+    result
+}
@@ -0,0 +1,196 @@
+use super::short_type_name;
+use crate::{Context, Source, TestAction, run_test_actions};
+use indoc::indoc;
+use serde_json::Value;
+
+/// A parsed `.heapsnapshot`, to navigate the graph in tests.
+struct Graph {
+    json: Value,
+}
+
+impl Graph {
+    fn new(context: &Context) -> Self {
+        let json = serde_json::from_str(&context.heap_snapshot().to_json_string())
+            .expect("snapshot must be valid JSON");
+        Self { json }
+    }
+
+    fn array(&self, name: &str) -> &[Value] {
+        self.json[name].as_array().expect("must be an array")
+    }
+
+    fn string(&self, index: &Value) -> &str {
+        let index = index.as_u64().expect("must be an index") as usize;
+        self.array("strings")[index]
+            .as_str()
+            .expect("must be a string")
+    }
+
+    /// Returns the node index of the first node with the given type and name.
+    fn find(&self, ty: &str, name: &str) -> Option<usize> {
+        let nodes = self.array("nodes");
+        (0..nodes.len() / super::NODE_FIELDS.len()).find(|&node| {
+            let fields = &nodes[node * super::NODE_FIELDS.len()..];
+            let node_type = super::NODE_TYPES[fields[0].as_u64().unwrap() as usize];
+            node_type == ty && self.string(&fields[1]) == name
+        })
+    }
+
+    fn name(&self, node: usize) -> &str {
+        self.string(&self.array("nodes")[node * super::NODE_FIELDS.len() + 1])
+    }
+
+    /// Returns the `(type, name, target node)` triples of the edges of a node.
+    fn edges(&self, node: usize) -> Vec<(&'static str, String, usize)> {
+        let nodes = self.array("nodes");
+        let edges = self.array("edges");
+        let node_fields = super::NODE_FIELDS.len();
+        let edge_fields = super::EDGE_FIELDS.len();
+
+        let first_edge = (0..node)
+            .map(|i| nodes[i * node_fields + 4].as_u64().unwrap() as usize)
+            .sum::<usize>();
+        let edge_count = nodes[node * node_fields + 4].as_u64().unwrap() as usize;
+
+        (first_edge..first_edge + edge_count)
+            .map(|edge| {
+                let fields = &edges[edge * edge_fields..];
+                let ty = super::EDGE_TYPES[fields[0].as_u64().unwrap() as usize];
+                let name = match ty {
+                    "element" | "hidden" | "weak" => fields[1].to_string(),
+                    _ => self.string(&fields[1]).to_string(),
+                };
+                let target = fields[2].as_u64().unwrap() as usize / node_fields;
+                (ty, name, target)
+            })
+            .collect()
+    }
+
+    fn edge(&self, node: usize, ty: &str, name: &str) -> Option<usize> {
+        self.edges(node)
+            .into_iter()
+            .find(|(edge_type, edge_name, _)| *edge_type == ty && edge_name == name)
+            .map(|(_, _, target)| target)
+    }
+}
+
+#[test]
+fn heap_snapshot_metadata() {
+    run_test_actions([TestAction::inspect_context(|context| {
+        let snapshot = context.heap_snapshot();
+        let graph = Graph::new(context);
+
+        let info = &graph.json["snapshot"];
+        assert_eq!(
+            info["node_count"].as_u64(),
+            Some(graph.array("nodes").len() as u64 / 7)
+        );
+        assert_eq!(
+            info["edge_count"].as_u64(),
+            Some(graph.array("edges").len() as u64 / 3)
+        );
+        assert!(snapshot.node_count() > 2);
+        assert!(snapshot.edge_count() > 0);
+
+        // The first node is the synthetic root, which references the GC roots.
+        assert_eq!(graph.name(0), "");
+        let gc_roots = graph
+            .edge(0, "element", "1")
+            .expect("root must have GC roots");
+        assert_eq!(graph.name(gc_roots), "(GC roots)");
+        assert!(!graph.edges(gc_roots).is_empty());
+    })]);
+}
+
+#[test]
+fn heap_snapshot_names_properties_and_closures() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            class Leaky {}
+            var leak = { payload: [new Leaky()] };
+
+            function outer() {
+                let captured = {};
+                return function inner() { return captured; };
+            }
+            var closure = outer();
+        "#}),
+        TestAction::inspect_context(|context| {
+            let graph = Graph::new(context);
+
+            let global = graph
+                .edge(0, "shortcut", "globalThis")
+                .expect("root must reference the global object");
+
+            let leak = graph
+                .edge(global, "property", "leak")
+                .expect("global must reference `leak`");
+            assert_eq!(graph.name(leak), "Object");
+
+            let payload = graph
+                .edge(leak, "property", "payload")
+                .expect("`leak` must reference `payload`");
+            assert_eq!(graph.name(payload), "Array");
+
+            let element = graph
+                .edge(payload, "element", "0")
+                .expect("`payload` must reference its element");
+            assert_eq!(graph.name(element), "Leaky");
+
+            let inner = graph
+                .find("closure", "inner")
+                .expect("snapshot must contain the `inner` closure");
+            let context = graph
+                .edge(inner, "internal", "context")
+                .expect("closure must reference its environment");
+            assert_eq!(graph.name(context), "system / EnvironmentNode");
+
+            let code = graph
+                .edge(inner, "internal", "code")
+                .expect("closure must reference its code");
+            assert_eq!(graph.name(code), "inner");
+        }),
+    ]);
+}
+
+#[test]
+fn heap_snapshot_environment_slots() {
+    let context = &mut Context::default();
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            function outer() {
+                let captured = {};
+                return () => captured;
+            }
+            var closure = outer();
+        "#}))
+        .unwrap();
+
+    let graph = Graph::new(context);
+    let nodes = graph.array("nodes").len() / super::NODE_FIELDS.len();
+
+    let captured = (0..nodes).any(|node| {
+        graph.name(node) == "system / Context"
+            && graph
+                .edges(node)
+                .iter()
+                .any(|(ty, _, target)| *ty == "context" && graph.name(*target) == "Object")
+    });
+    assert!(
+        captured,
+        "an environment must reference the captured object"
+    );
+}
+
+#[test]
+fn short_type_names() {
+    assert_eq!(short_type_name("u8"), "u8");
+    assert_eq!(
+        short_type_name("boa_gc::cell::GcRefCell<alloc::vec::Vec<u8>>"),
+        "GcRefCell<Vec<u8>>"
+    );
+    assert_eq!(
+        short_type_name("core::option::Option<(alloc::string::String, u32)>"),
+        "Option<(String, u32)>"
+    );
+}
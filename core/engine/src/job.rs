@@ -319,6 +319,9 @@ impl TimeoutJob {
     /// If the native job has an execution realm defined, this sets the running execution
     /// context to the realm's before calling the inner closure, and resets it after execution.
     pub fn call(mut self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", kind = "timeout").entered();
+
         let result = self
             .job
             .take()
@@ -409,6 +412,9 @@ impl IntervalJob {
     /// If the interval job has an execution realm defined, this sets the running execution
     /// context to the realm's before calling the inner closure, and resets it after execution.
     pub fn call(&self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", kind = "interval").entered();
+
         self.job.call(context)
     }
 
@@ -469,6 +475,9 @@ impl GenericJob {
     /// Calls the `GenericJob` with the specified [`Context`], setting the execution
     /// context to the job's realm before calling the inner closure, and resets it after execution.
     pub fn call(self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", kind = "generic").entered();
+
         self.0.call(context)
     }
 }
@@ -540,6 +549,11 @@ impl NativeAsyncJob {
         // code at the time of job's invocation.
         let realm = self.realm;
 
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!("job", kind = "async");
+        #[cfg(feature = "tracing")]
+        let guard = span.enter();
+
         let mut future = if let Some(realm) = &realm {
             let old_realm = context.borrow_mut().enter_realm(realm.clone());
 
@@ -555,7 +569,14 @@ impl NativeAsyncJob {
             (self.f)(context)
         };
 
+        #[cfg(feature = "tracing")]
+        drop(guard);
+
         std::future::poll_fn(move |cx| {
+            // The job only runs while it is polled, so its span is entered on every poll.
+            #[cfg(feature = "tracing")]
+            let _span = span.clone().entered();
+
             // We need to do the same dance again since the inner code could assume we're still
             // on the same realm.
             if let Some(realm) = &realm {
@@ -631,6 +652,9 @@ impl PromiseJob {
     /// If the job has an execution realm defined, this sets the running execution
     /// context to the realm's before calling the inner closure, and resets it after execution.
     pub fn call(self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", kind = "promise").entered();
+
         self.0.call(context)
     }
 }
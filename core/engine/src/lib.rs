@@ -40,7 +40,11 @@
 //! # Crate Features
 //!
 //!  - **serde** - Enables serialization and deserialization of the AST (Abstract Syntax Tree).
-//!  - **profiler** - Enables profiling with measureme (this is mostly internal).
+//!  - **profiler** - Enables the sampling CPU profiler of JavaScript code, see [`profiler`].
+//!  - **coverage** - Enables the collection of the code coverage of JavaScript code, see [`coverage`].
+//!  - **vm-stats** - Enables the collection of execution statistics of the VM, see [`vm_stats`].
+//!  - **typescript** - Strips TypeScript types from sources marked with `Source::with_typescript`, and from `.ts` and `.mts` modules loaded by [`SimpleModuleLoader`](module::SimpleModuleLoader).
+//!  - **tracing** - Emits [`tracing`](https://docs.rs/tracing) spans for the work of the engine, like parsing and jobs.
 //!  - **intl** - Enables `boa`'s [ECMA-402 Internationalization API][ecma-402] (`Intl` object)
 //!
 //! [ecma-402]: https://tc39.es/ecma402
@@ -87,8 +91,13 @@ pub mod builtins;
 pub mod bytecompiler;
 pub mod class;
 pub mod context;
+#[cfg(feature = "coverage")]
+pub mod coverage;
+#[cfg(feature = "debugger")]
+pub mod debugger;
 pub mod environments;
 pub mod error;
+pub mod heap_snapshot;
 pub mod interop;
 pub mod job;
 pub mod module;
@@ -96,13 +105,19 @@ pub mod native_function;
 pub mod object;
 /// ECMAScript Abstract Syntax Tree (AST) optimizer.
 pub mod optimizer;
+#[cfg(feature = "profiler")]
+pub mod profiler;
 pub mod property;
 pub mod realm;
 pub mod script;
+pub mod snapshot;
+pub mod source_map;
 pub mod string;
 pub mod symbol;
 pub mod value;
 pub mod vm;
+#[cfg(feature = "vm-stats")]
+pub mod vm_stats;
 
 mod host_defined;
 mod sys;
@@ -455,6 +455,11 @@ impl ModuleLoader for SimpleModuleLoader {
                         .with_message(format!("could not open file `{short_path}`"))
                         .with_cause(JsError::from_rust(err))
                 })?;
+                #[cfg(feature = "typescript")]
+                let source = source.with_typescript(
+                    path.extension()
+                        .is_some_and(|ext| ext == "ts" || ext == "mts"),
+                );
                 Module::parse(source, None, &mut context.borrow_mut()).map_err(|err| {
                     JsNativeError::syntax()
                         .with_message(format!("could not parse module `{short_path}`"))
@@ -36,7 +36,7 @@ use boa_engine::value::TryFromJs;
 use boa_gc::{Finalize, Gc, GcRefCell, Trace};
 use boa_interner::Interner;
 use boa_parser::source::ReadChar;
-use boa_parser::{Parser, Source};
+use boa_parser::{Parser, Source, lexer::Error as LexError};
 
 pub use loader::*;
 pub use namespace::ModuleNamespace;
@@ -275,6 +275,15 @@ impl Module {
         context: &mut Context,
     ) -> JsResult<Self> {
         let path = src.path().map(Path::to_path_buf);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "parse",
+            kind = "module",
+            path = path
+                .as_deref()
+                .map(|path| tracing::field::display(path.display())),
+        )
+        .entered();
         let realm = realm.unwrap_or_else(|| context.realm().clone());
 
         let mut parser = Parser::new(src);
@@ -283,6 +292,7 @@ impl Module {
             parser.parse_module_with_source(realm.scope(), context.interner_mut())?;
 
         let source_text = SourceText::new(source);
+        context.source_maps.attach(&source_text, path.as_deref());
         let src = SourceTextModule::new(module, context.interner(), source_text, path.clone());
 
         Ok(Self {
@@ -296,6 +306,81 @@ impl Module {
         })
     }
 
+    /// Parses the provided `src` as an ECMAScript module, using the compiled code stored in `cache`
+    /// if possible.
+    ///
+    /// `cache` must be the result of [`Module::to_code_cache`]. It is only used if it was produced
+    /// by the same version of Boa, from the same source text and with the same options of the
+    /// context. If it cannot be used, the source is parsed as in [`Module::parse`].
+    ///
+    /// The functions of the module are decoded from the cache the first time they are used.
+    pub fn parse_with_code_cache<R: ReadChar>(
+        src: Source<'_, R>,
+        cache: &[u8],
+        realm: Option<Realm>,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let path = src.path();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "parse",
+            kind = "module",
+            path = path.map(|path| tracing::field::display(path.display())),
+            code_cache = true,
+        )
+        .entered();
+        let realm = realm.unwrap_or_else(|| context.realm().clone());
+        let source_text = src
+            .read_source_text()
+            .map_err(|err| boa_parser::Error::from(LexError::from(err)))?;
+        let source_text = SourceText::new(source_text);
+        context.source_maps.attach(&source_text, path);
+
+        let module = SourceTextModule::from_code_cache(
+            cache,
+            source_text.clone(),
+            path.map(Path::to_path_buf),
+            realm.scope(),
+            context,
+        );
+
+        let Some(module) = module else {
+            let src = Source::from_utf16(source_text.code_units());
+            return match path {
+                Some(path) => Self::parse(src.with_path(path), Some(realm), context),
+                None => Self::parse(src, Some(realm), context),
+            };
+        };
+
+        Ok(Self {
+            inner: Gc::new(ModuleRepr {
+                realm,
+                namespace: GcRefCell::default(),
+                kind: ModuleKind::SourceText(Box::new(module)),
+                host_defined: HostDefined::default(),
+                path: path.map(Path::to_path_buf),
+            }),
+        })
+    }
+
+    /// Serializes the compiled code of this module into a code cache, compiling it first if
+    /// needed.
+    ///
+    /// The cache can be passed to [`Module::parse_with_code_cache`] to load the module again
+    /// without parsing and compiling it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is a synthetic module, or if the module was already linked.
+    pub fn to_code_cache(&self, context: &mut Context) -> JsResult<Vec<u8>> {
+        match self.kind() {
+            ModuleKind::SourceText(src) => src.to_code_cache(context),
+            ModuleKind::Synthetic(_) => Err(JsNativeError::typ()
+                .with_message("cannot create the code cache of a synthetic module")
+                .into()),
+        }
+    }
+
     /// Abstract operation [`CreateSyntheticModule ( exportNames, evaluationSteps, realm )`][spec].
     ///
     /// Creates a new Synthetic Module from its list of exported names, its evaluation steps and
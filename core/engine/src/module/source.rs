@@ -8,18 +8,20 @@ use std::{
 };
 
 use boa_ast::{
+    Position, Span,
     declaration::{
-        ExportEntry, ImportEntry, ImportName, IndirectExportEntry, LocalExportEntry,
-        ReExportImportName,
+        ExportEntry, ImportAttribute as AstImportAttribute, ImportEntry, ImportName,
+        IndirectExportEntry, LocalExportEntry, ReExportImportName,
     },
+    expression::Identifier,
     operations::{
         ContainsSymbol, LexicallyScopedDeclaration, bound_names, contains,
         lexically_scoped_declarations, var_scoped_declarations,
     },
-    scope::BindingLocator,
+    scope::{BindingLocator, Scope},
 };
 use boa_gc::{Finalize, Gc, GcRefCell, Trace};
-use boa_interner::Interner;
+use boa_interner::{Interner, Sym};
 use boa_macros::js_str;
 use dynify::Dynify;
 use indexmap::IndexSet;
@@ -38,13 +40,14 @@ use crate::{
     realm::Realm,
     vm::{
         ActiveRunnable, CallFrame, CallFrameFlags, CodeBlock, CompletionRecord,
+        code_cache::{self, Reader, Writer},
         create_function_object_fast,
     },
 };
 
 use super::{
-    BindingName, GraphLoadingState, Module, Referrer, ResolveExportError, ResolvedBinding,
-    SourceText,
+    BindingName, GraphLoadingState, ImportAttribute, Module, Referrer, ResolveExportError,
+    ResolvedBinding, SourceText,
 };
 
 /// Current status of a [`SourceTextModule`].
@@ -57,13 +60,11 @@ use super::{
 #[boa_gc(unsafe_no_drop)]
 enum ModuleStatus {
     Unlinked {
-        #[unsafe_ignore_trace]
-        source: boa_ast::Module,
+        source: ModuleSource,
         source_text: SourceText,
     },
     Linking {
-        #[unsafe_ignore_trace]
-        source: boa_ast::Module,
+        source: ModuleSource,
         source_text: SourceText,
         ancestor_index: usize,
     },
@@ -111,7 +112,7 @@ impl ModuleStatus {
         *self = f(std::mem::replace(
             self,
             ModuleStatus::Unlinked {
-                source: boa_ast::Module::default(),
+                source: ModuleSource::Ast(boa_ast::Module::default()),
                 source_text: SourceText::default(),
             },
         ));
@@ -208,7 +209,7 @@ impl ModuleStatus {
     }
 
     /// If this module is in the unlinked or linking states, gets its source.
-    fn source(&self) -> Option<(&boa_ast::Module, &SourceText)> {
+    fn source_mut(&mut self) -> Option<(&mut ModuleSource, &SourceText)> {
         match self {
             ModuleStatus::Unlinked {
                 source,
@@ -224,6 +225,29 @@ impl ModuleStatus {
     }
 }
 
+/// The code of a [`SourceTextModule`] that is not linked yet.
+#[derive(Debug, Trace, Finalize)]
+#[boa_gc(unsafe_no_drop)]
+enum ModuleSource {
+    /// The parsed module, which is compiled when the module is linked.
+    Ast(#[unsafe_ignore_trace] boa_ast::Module),
+    /// The compiled module, either compiled ahead of linking or loaded from a code cache.
+    Compiled(CompiledModule),
+}
+
+/// The compiled code of a [`SourceTextModule`].
+#[derive(Debug, Clone, Trace, Finalize)]
+#[boa_gc(unsafe_no_drop)]
+struct CompiledModule {
+    #[unsafe_ignore_trace]
+    scope: Scope,
+    codeblock: Gc<CodeBlock>,
+    /// The function declarations of the module, as the index of their function constant and
+    /// the binding they are assigned to.
+    #[unsafe_ignore_trace]
+    functions: Vec<(u32, BindingLocator)>,
+}
+
 /// The execution context of a [`SourceTextModule`].
 ///
 /// Stores the required context data that needs to be in place before executing the
@@ -281,6 +305,128 @@ struct ModuleCode {
     star_export_entries: Vec<super::ModuleRequest>,
 }
 
+impl ModuleCode {
+    /// Writes the module records to a code cache.
+    fn encode(&self, writer: &mut Writer, interner: &Interner) {
+        let sym = |writer: &mut Writer, sym: Sym| writer.string(&sym.to_js_string(interner));
+        let attributes = |writer: &mut Writer, attributes: &[AstImportAttribute]| {
+            writer.seq(attributes.iter(), |writer, attribute| {
+                sym(writer, attribute.key());
+                sym(writer, attribute.value());
+            });
+        };
+        let request = |writer: &mut Writer, request: &super::ModuleRequest| {
+            writer.string(request.specifier());
+            writer.seq(request.attributes().iter(), |writer, attribute| {
+                writer.string(attribute.key());
+                writer.string(attribute.value());
+            });
+        };
+
+        writer.bool(self.has_tla);
+        writer.seq(self.requested_modules.iter(), request);
+        writer.seq(self.import_entries.iter(), |writer, entry| {
+            sym(writer, entry.module_request());
+            let name = match entry.import_name() {
+                ImportName::Namespace => None,
+                ImportName::Name(name) => Some(name),
+            };
+            writer.option(name, sym);
+            sym(writer, entry.local_name().sym());
+            attributes(writer, entry.attributes());
+        });
+        writer.seq(self.local_export_entries.iter(), |writer, entry| {
+            sym(writer, entry.local_name());
+            sym(writer, entry.export_name());
+        });
+        writer.seq(self.indirect_export_entries.iter(), |writer, entry| {
+            sym(writer, entry.module_request());
+            let name = match entry.import_name() {
+                ReExportImportName::Star => None,
+                ReExportImportName::Name(name) => Some(name),
+            };
+            writer.option(name, sym);
+            sym(writer, entry.export_name());
+            attributes(writer, entry.attributes());
+        });
+        writer.seq(self.star_export_entries.iter(), request);
+    }
+
+    /// Reads the module records written by [`ModuleCode::encode`].
+    fn decode(
+        reader: &mut Reader<'_>,
+        path: Option<PathBuf>,
+        interner: &mut Interner,
+    ) -> Option<Self> {
+        fn sym(reader: &mut Reader<'_>, interner: &mut Interner) -> Option<Sym> {
+            let string = reader.string()?;
+            Some(interner.get_or_intern(string.to_vec().as_slice()))
+        }
+        fn attributes(
+            reader: &mut Reader<'_>,
+            interner: &mut Interner,
+        ) -> Option<Box<[AstImportAttribute]>> {
+            let attributes = reader.seq(|reader| {
+                Some(AstImportAttribute::new(
+                    sym(reader, interner)?,
+                    sym(reader, interner)?,
+                ))
+            })?;
+            Some(attributes.into_boxed_slice())
+        }
+        fn request(reader: &mut Reader<'_>) -> Option<super::ModuleRequest> {
+            let specifier = reader.string()?;
+            let attributes = reader
+                .seq(|reader| Some(ImportAttribute::new(reader.string()?, reader.string()?)))?;
+            Some(super::ModuleRequest::new(
+                specifier,
+                attributes.into_boxed_slice(),
+            ))
+        }
+
+        // Identifiers only need a span to report early errors, which were already reported.
+        let span = Span::from(Position::new(1, 1));
+
+        Some(Self {
+            has_tla: reader.bool()?,
+            requested_modules: reader.seq(request)?.into_iter().collect(),
+            path,
+            import_entries: reader.seq(|reader| {
+                let module_request = sym(reader, interner)?;
+                let import_name = reader
+                    .option(|reader| sym(reader, interner))?
+                    .map_or(ImportName::Namespace, ImportName::Name);
+                let local_name = Identifier::new(sym(reader, interner)?, span);
+                Some(ImportEntry::new(
+                    module_request,
+                    import_name,
+                    local_name,
+                    attributes(reader, interner)?,
+                ))
+            })?,
+            local_export_entries: reader.seq(|reader| {
+                Some(LocalExportEntry::new(
+                    sym(reader, interner)?,
+                    sym(reader, interner)?,
+                ))
+            })?,
+            indirect_export_entries: reader.seq(|reader| {
+                let module_request = sym(reader, interner)?;
+                let import_name = reader
+                    .option(|reader| sym(reader, interner))?
+                    .map_or(ReExportImportName::Star, ReExportImportName::Name);
+                Some(IndirectExportEntry::new(
+                    module_request,
+                    import_name,
+                    sym(reader, interner)?,
+                    attributes(reader, interner)?,
+                ))
+            })?,
+            star_export_entries: reader.seq(request)?,
+        })
+    }
+}
+
 struct ModuleRequestsVisitor<'a> {
     interner: &'a Interner,
     requests: IndexSet<super::ModuleRequest, BuildHasherDefault<FxHasher>>,
@@ -342,19 +488,33 @@ impl SourceTextModule {
         source_text: SourceText,
         path: Option<PathBuf>,
     ) -> Self {
+        // The automatic JSX runtime is imported like the first import of the module.
+        let jsx_runtime_entries = source.items().jsx_runtime_import_entries();
+
         // 3. Let requestedModules be the ModuleRequests of body.
         let requested_modules = {
             use boa_ast::visitor::Visitor;
 
             let mut visitor = ModuleRequestsVisitor {
                 interner,
-                requests: IndexSet::default(),
+                requests: jsx_runtime_entries
+                    .first()
+                    .map(|entry| {
+                        super::ModuleRequest::from_ast(
+                            entry.module_request().to_js_string(interner),
+                            entry.attributes(),
+                            interner,
+                        )
+                    })
+                    .into_iter()
+                    .collect(),
             };
             let _ = visitor.visit_module(&source);
             visitor.requests
         };
         // 4. Let importEntries be ImportEntries of body.
-        let import_entries = source.items().import_entries();
+        let mut import_entries = jsx_runtime_entries;
+        import_entries.extend(source.items().import_entries());
 
         // 5. Let importedBoundNames be ImportedLocalNames(importEntries).
         // Can be ignored because this is just a simple `Iter::map`
@@ -469,7 +629,7 @@ impl SourceTextModule {
         // Most of this can be ignored, since `Status` takes care of the remaining state.
         Self {
             status: GcRefCell::new(ModuleStatus::Unlinked {
-                source,
+                source: ModuleSource::Ast(source),
                 source_text,
             }),
             loaded_modules: GcRefCell::default(),
@@ -487,6 +647,67 @@ impl SourceTextModule {
         }
     }
 
+    /// Creates a new `SourceTextModule` from a code cache created by
+    /// [`SourceTextModule::to_code_cache`].
+    ///
+    /// Returns `None` if the cache cannot be used for this source.
+    pub(super) fn from_code_cache(
+        cache: &[u8],
+        source_text: SourceText,
+        path: Option<PathBuf>,
+        global_scope: &Scope,
+        context: &mut Context,
+    ) -> Option<Self> {
+        let module = code_cache::decode_module(
+            cache,
+            &source_text,
+            path.clone().into(),
+            global_scope,
+            context,
+            |reader, interner| ModuleCode::decode(reader, path, interner),
+        )?;
+
+        Some(Self {
+            status: GcRefCell::new(ModuleStatus::Unlinked {
+                source: ModuleSource::Compiled(CompiledModule {
+                    scope: module.scope,
+                    codeblock: module.codeblock,
+                    functions: module.functions,
+                }),
+                source_text,
+            }),
+            loaded_modules: GcRefCell::default(),
+            async_parent_modules: GcRefCell::default(),
+            import_meta: GcRefCell::default(),
+            code: module.records,
+        })
+    }
+
+    /// Serializes the compiled code of this module into a code cache, compiling it first if
+    /// needed.
+    pub(super) fn to_code_cache(&self, context: &mut Context) -> JsResult<Vec<u8>> {
+        if !matches!(&*self.status.borrow(), ModuleStatus::Unlinked { .. }) {
+            return Err(JsNativeError::typ()
+                .with_message("cannot create the code cache of a linked module")
+                .into());
+        }
+
+        let compiled = self.compile(context)?;
+        let status = self.status.borrow();
+        let ModuleStatus::Unlinked { source_text, .. } = &*status else {
+            unreachable!("compiling a module must not link it");
+        };
+
+        Ok(code_cache::encode_module(
+            &compiled.codeblock,
+            &compiled.scope,
+            &compiled.functions,
+            source_text,
+            context,
+            |writer| self.code.encode(writer, context.interner()),
+        ))
+    }
+
     /// Abstract operation [`InnerModuleLoading`][spec].
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-InnerModuleLoading
@@ -1585,6 +1806,142 @@ impl SourceTextModule {
         // 2. Return unused.
     }
 
+    /// Compiles the code of this module, if it wasn't compiled yet.
+    ///
+    /// The compiled code doesn't depend on the imported modules, so this can be done before the
+    /// module is linked.
+    fn compile(&self, context: &mut Context) -> JsResult<CompiledModule> {
+        let mut status = self.status.borrow_mut();
+        let (source, source_text) = status
+            .source_mut()
+            .js_expect("module can only be compiled before it is linked")?;
+
+        let ast = match &*source {
+            ModuleSource::Ast(ast) => ast,
+            ModuleSource::Compiled(compiled) => return Ok(compiled.clone()),
+        };
+        let env = ast.scope().clone();
+
+        let spanned_source_text = SpannedSourceText::new_source_only(source_text.clone());
+        let lazy_functions = context.lazy_compilation();
+        let mut compiler = ByteCompiler::new(
+            js_string!("<main>"),
+            true,
+            false,
+            env.clone(),
+            env.clone(),
+            self.code.has_tla,
+            false,
+            context.interner_mut(),
+            false,
+            spanned_source_text,
+            self.code.path.clone().into(),
+        );
+        compiler.lazy_functions = lazy_functions;
+
+        compiler.async_handler = self.code.has_tla.then(|| compiler.push_handler());
+
+        // 18. Let code be module.[[ECMAScriptCode]].
+        // 19. Let varDeclarations be the VarScopedDeclarations of code.
+        let var_declarations = var_scoped_declarations(ast);
+        // 20. Let declaredVarNames be a new empty List.
+        let mut declared_var_names = Vec::new();
+        // 21. For each element d of varDeclarations, do
+        for var in var_declarations {
+            // a. For each element dn of the BoundNames of d, do
+            for name in var.bound_names() {
+                let name = name.to_js_string(compiler.interner());
+
+                // i. If declaredVarNames does not contain dn, then
+                if !declared_var_names.contains(&name) {
+                    // 1. Perform ! env.CreateMutableBinding(dn, false).
+                    // 2. Perform ! env.InitializeBinding(dn, undefined).
+                    let binding = env
+                        .get_binding_reference(&name)
+                        .js_expect("binding must exist")?;
+                    let index = compiler.insert_binding(binding);
+                    compiler.emit_binding_access(
+                        BindingAccessOpcode::DefInitVar,
+                        &index,
+                        &CallFrame::undefined_register(),
+                    );
+
+                    // 3. Append dn to declaredVarNames.
+                    declared_var_names.push(name);
+                }
+            }
+        }
+
+        // 22. Let lexDeclarations be the LexicallyScopedDeclarations of code.
+        // 23. Let privateEnv be null.
+        let lex_declarations = lexically_scoped_declarations(ast);
+        let mut functions = Vec::new();
+        // 24. For each element d of lexDeclarations, do
+        for declaration in lex_declarations {
+            // ii. Else,
+            // a. For each element dn of the BoundNames of d, do
+            // 1. Perform ! env.CreateMutableBinding(dn, false).
+            //
+            // iii. If d is either a FunctionDeclaration, a GeneratorDeclaration, an
+            //      AsyncFunctionDeclaration, or an AsyncGeneratorDeclaration, then
+            // 1. Let fo be InstantiateFunctionObject of d with arguments env and privateEnv.
+            // 2. Perform ! env.InitializeBinding(dn, fo).
+            //
+            // deferred to below.
+            let (spec, locator): (FunctionSpec<'_>, _) = match declaration {
+                LexicallyScopedDeclaration::FunctionDeclaration(f) => {
+                    let name = bound_names(f)[0].to_js_string(compiler.interner());
+                    let locator = env.get_binding(&name).js_expect("binding must exist")?;
+
+                    (f.into(), locator)
+                }
+                LexicallyScopedDeclaration::GeneratorDeclaration(g) => {
+                    let name = bound_names(g)[0].to_js_string(compiler.interner());
+                    let locator = env.get_binding(&name).js_expect("binding must exist")?;
+
+                    (g.into(), locator)
+                }
+                LexicallyScopedDeclaration::AsyncFunctionDeclaration(af) => {
+                    let name = bound_names(af)[0].to_js_string(compiler.interner());
+                    let locator = env.get_binding(&name).js_expect("binding must exist")?;
+
+                    (af.into(), locator)
+                }
+                LexicallyScopedDeclaration::AsyncGeneratorDeclaration(ag) => {
+                    let name = bound_names(ag)[0].to_js_string(compiler.interner());
+                    let locator = env.get_binding(&name).js_expect("binding must exist")?;
+
+                    (ag.into(), locator)
+                }
+                LexicallyScopedDeclaration::ClassDeclaration(_)
+                | LexicallyScopedDeclaration::LexicalDeclaration(_)
+                | LexicallyScopedDeclaration::AssignmentExpression(_) => {
+                    continue;
+                }
+            };
+
+            functions.push((spec, locator));
+        }
+
+        // Should compile after initializing bindings first to ensure inner calls
+        // are correctly resolved to the outer functions instead of as global bindings.
+        let functions = functions
+            .into_iter()
+            .map(|(spec, locator)| (compiler.function(spec), locator))
+            .collect::<Vec<_>>();
+
+        compiler.compile_module_item_list(ast.items());
+
+        let module = CompiledModule {
+            scope: env,
+            codeblock: Gc::new(compiler.finish()),
+            functions,
+        };
+        *source = ModuleSource::Compiled(module.clone());
+
+        Ok(module)
+    }
+
     /// Abstract operation [`InitializeEnvironment ( )`][spec].
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-source-text-module-record-initialize-environment
@@ -1601,6 +1958,16 @@ impl SourceTextModule {
             },
         }
 
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "compile",
+            kind = "module",
+            path = module_self
+                .path()
+                .map(|path| tracing::field::display(path.display())),
+        )
+        .entered();
+
         {
             // 1. For each ExportEntry Record e of module.[[IndirectExportEntries]], do
             for e in &self.code.indirect_export_entries {
@@ -1635,201 +2002,87 @@ impl SourceTextModule {
         // 4. Assert: realm is not undefined.
         let realm = module_self.realm().clone();
 
-        let status = self.status.borrow();
-        let (source, source_text) = status
-            .source()
-            .js_expect("module can only initialize its environment in the linking phase")?;
-
         // 5. Let env be NewModuleEnvironment(realm.[[GlobalEnv]]).
         // 6. Set module.[[Environment]] to env.
-        let env = source.scope().clone();
-
-        let spanned_source_text = SpannedSourceText::new_source_only(source_text.clone());
-        let mut compiler = ByteCompiler::new(
-            js_string!("<main>"),
-            true,
-            false,
-            source.scope().clone(),
-            source.scope().clone(),
-            self.code.has_tla,
-            false,
-            context.interner_mut(),
-            false,
-            spanned_source_text,
-            self.code.path.clone().into(),
-        );
-
-        compiler.async_handler = self.code.has_tla.then(|| compiler.push_handler());
+        let CompiledModule {
+            scope: env,
+            codeblock,
+            functions,
+        } = self.compile(context)?;
 
         let mut imports = Vec::new();
 
-        let (codeblock, functions) = {
-            // 7. For each ImportEntry Record in of module.[[ImportEntries]], do
-            for entry in &self.code.import_entries {
-                // a. Let importedModule be GetImportedModule(module, in.[[ModuleRequest]]).
-                let module_request = super::ModuleRequest::from_ast(
-                    entry.module_request().to_js_string(compiler.interner()),
-                    entry.attributes(),
-                    compiler.interner(),
-                );
-                let imported_module = self.loaded_modules.borrow()[&module_request].clone();
-
-                if let ImportName::Name(name) = entry.import_name() {
-                    let name = name.to_js_string(compiler.interner());
-                    // c. Else,
-                    //    i. Let resolution be importedModule.ResolveExport(in.[[ImportName]]).
-                    let resolution = imported_module
-                        .resolve_export(&name, &mut HashSet::default(), compiler.interner())
-                        // ii. If resolution is either null or ambiguous, throw a SyntaxError exception.
-                        .map_err(|err| match err {
-                            ResolveExportError::NotFound => JsNativeError::syntax().with_message(
-                                format!("could not find export `{}`", name.to_std_string_escaped()),
-                            ),
-                            ResolveExportError::Ambiguous => {
-                                JsNativeError::syntax().with_message(format!(
-                                    "could not resolve ambiguous export `{}`",
-                                    name.to_std_string_escaped()
-                                ))
-                            }
-                        })?;
+        // 7. For each ImportEntry Record in of module.[[ImportEntries]], do
+        for entry in &self.code.import_entries {
+            // a. Let importedModule be GetImportedModule(module, in.[[ModuleRequest]]).
+            let module_request = super::ModuleRequest::from_ast(
+                entry.module_request().to_js_string(context.interner()),
+                entry.attributes(),
+                context.interner(),
+            );
+            let imported_module = self.loaded_modules.borrow()[&module_request].clone();
 
-                    // 2. Perform ! env.CreateImmutableBinding(in.[[LocalName]], true).
-                    // 3. Perform ! env.InitializeBinding(in.[[LocalName]], namespace).
-                    let local_name = entry.local_name().to_js_string(compiler.interner());
-                    let locator = env
-                        .get_binding(&local_name)
-                        .js_expect("binding must exist")?;
+            if let ImportName::Name(name) = entry.import_name() {
+                let name = name.to_js_string(context.interner());
+                // c. Else,
+                //    i. Let resolution be importedModule.ResolveExport(in.[[ImportName]]).
+                let resolution = imported_module
+                    .resolve_export(&name, &mut HashSet::default(), context.interner())
+                    // ii. If resolution is either null or ambiguous, throw a SyntaxError exception.
+                    .map_err(|err| match err {
+                        ResolveExportError::NotFound => JsNativeError::syntax().with_message(
+                            format!("could not find export `{}`", name.to_std_string_escaped()),
+                        ),
+                        ResolveExportError::Ambiguous => {
+                            JsNativeError::syntax().with_message(format!(
+                                "could not resolve ambiguous export `{}`",
+                                name.to_std_string_escaped()
+                            ))
+                        }
+                    })?;
 
-                    if let BindingName::Name(_) = resolution.binding_name() {
-                        // 1. Perform env.CreateImportBinding(in.[[LocalName]], resolution.[[Module]],
-                        //    resolution.[[BindingName]]).
-                        //    deferred to initialization below
-                        imports.push(ImportBinding::Single {
-                            locator,
-                            export_locator: resolution,
-                        });
-                    } else {
-                        // 1. Let namespace be GetModuleNamespace(resolution.[[Module]]).
-                        // deferred to initialization below
-                        imports.push(ImportBinding::Namespace {
-                            locator,
-                            module: resolution.into_module(),
-                        });
-                    }
+                // 2. Perform ! env.CreateImmutableBinding(in.[[LocalName]], true).
+                // 3. Perform ! env.InitializeBinding(in.[[LocalName]], namespace).
+                let local_name = entry.local_name().to_js_string(context.interner());
+                let locator = env
+                    .get_binding(&local_name)
+                    .js_expect("binding must exist")?;
+
+                if let BindingName::Name(_) = resolution.binding_name() {
+                    // 1. Perform env.CreateImportBinding(in.[[LocalName]], resolution.[[Module]],
+                    //    resolution.[[BindingName]]).
+                    //    deferred to initialization below
+                    imports.push(ImportBinding::Single {
+                        locator,
+                        export_locator: resolution,
+                    });
                 } else {
-                    // b. If in.[[ImportName]] is namespace-object, then
-                    //    ii. Perform ! env.CreateImmutableBinding(in.[[LocalName]], true).
-                    //    iii. Perform ! env.InitializeBinding(in.[[LocalName]], namespace).
-                    let name = entry.local_name().to_js_string(compiler.interner());
-                    let locator = env.get_binding(&name).js_expect("binding must exist")?;
-
-                    //    i. Let namespace be GetModuleNamespace(importedModule).
-                    //       deferred to initialization below
+                    // 1. Let namespace be GetModuleNamespace(resolution.[[Module]]).
+                    // deferred to initialization below
                     imports.push(ImportBinding::Namespace {
                         locator,
-                        module: imported_module.clone(),
+                        module: resolution.into_module(),
                     });
                 }
+            } else {
+                // b. If in.[[ImportName]] is namespace-object, then
+                //    ii. Perform ! env.CreateImmutableBinding(in.[[LocalName]], true).
+                //    iii. Perform ! env.InitializeBinding(in.[[LocalName]], namespace).
+                let name = entry.local_name().to_js_string(context.interner());
+                let locator = env.get_binding(&name).js_expect("binding must exist")?;
+
+                //    i. Let namespace be GetModuleNamespace(importedModule).
+                //       deferred to initialization below
+                imports.push(ImportBinding::Namespace {
+                    locator,
+                    module: imported_module.clone(),
+                });
             }
-
-            // 18. Let code be module.[[ECMAScriptCode]].
-            // 19. Let varDeclarations be the VarScopedDeclarations of code.
-            let var_declarations = var_scoped_declarations(source);
-            // 20. Let declaredVarNames be a new empty List.
-            let mut declared_var_names = Vec::new();
-            // 21. For each element d of varDeclarations, do
-            for var in var_declarations {
-                // a. For each element dn of the BoundNames of d, do
-                for name in var.bound_names() {
-                    let name = name.to_js_string(compiler.interner());
-
-                    // i. If declaredVarNames does not contain dn, then
-                    if !declared_var_names.contains(&name) {
-                        // 1. Perform ! env.CreateMutableBinding(dn, false).
-                        // 2. Perform ! env.InitializeBinding(dn, undefined).
-                        let binding = env
-                            .get_binding_reference(&name)
-                            .js_expect("binding must exist")?;
-                        let index = compiler.insert_binding(binding);
-                        compiler.emit_binding_access(
-                            BindingAccessOpcode::DefInitVar,
-                            &index,
-                            &CallFrame::undefined_register(),
-                        );
-
-                        // 3. Append dn to declaredVarNames.
-                        declared_var_names.push(name);
-                    }
-                }
-            }
-
-            // 22. Let lexDeclarations be the LexicallyScopedDeclarations of code.
-            // 23. Let privateEnv be null.
-            let lex_declarations = lexically_scoped_declarations(source);
-            let mut functions = Vec::new();
-            // 24. For each element d of lexDeclarations, do
-            for declaration in lex_declarations {
-                // ii. Else,
-                // a. For each element dn of the BoundNames of d, do
-                // 1. Perform ! env.CreateMutableBinding(dn, false).
-                //
-                // iii. If d is either a FunctionDeclaration, a GeneratorDeclaration, an
-                //      AsyncFunctionDeclaration, or an AsyncGeneratorDeclaration, then
-                // 1. Let fo be InstantiateFunctionObject of d with arguments env and privateEnv.
-                // 2. Perform ! env.InitializeBinding(dn, fo).
-                //
-                // deferred to below.
-                let (spec, locator): (FunctionSpec<'_>, _) = match declaration {
-                    LexicallyScopedDeclaration::FunctionDeclaration(f) => {
-                        let name = bound_names(f)[0].to_js_string(compiler.interner());
-                        let locator = env.get_binding(&name).js_expect("binding must exist")?;
-
-                        (f.into(), locator)
-                    }
-                    LexicallyScopedDeclaration::GeneratorDeclaration(g) => {
-                        let name = bound_names(g)[0].to_js_string(compiler.interner());
-                        let locator = env.get_binding(&name).js_expect("binding must exist")?;
-
-                        (g.into(), locator)
-                    }
-                    LexicallyScopedDeclaration::AsyncFunctionDeclaration(af) => {
-                        let name = bound_names(af)[0].to_js_string(compiler.interner());
-                        let locator = env.get_binding(&name).js_expect("binding must exist")?;
-
-                        (af.into(), locator)
-                    }
-                    LexicallyScopedDeclaration::AsyncGeneratorDeclaration(ag) => {
-                        let name = bound_names(ag)[0].to_js_string(compiler.interner());
-                        let locator = env.get_binding(&name).js_expect("binding must exist")?;
-
-                        (ag.into(), locator)
-                    }
-                    LexicallyScopedDeclaration::ClassDeclaration(_)
-                    | LexicallyScopedDeclaration::LexicalDeclaration(_)
-                    | LexicallyScopedDeclaration::AssignmentExpression(_) => {
-                        continue;
-                    }
-                };
-
-                functions.push((spec, locator));
-            }
-
-            // Should compile after initializing bindings first to ensure inner calls
-            // are correctly resolved to the outer functions instead of as global bindings.
-            let functions = functions
-                .into_iter()
-                .map(|(spec, locator)| (compiler.function(spec), locator))
-                .collect::<Vec<_>>();
-
-            compiler.compile_module_item_list(source.items());
-
-            (Gc::new(compiler.finish()), functions)
-        };
+        }
 
         // 8. Let moduleContext be a new ECMAScript code execution context.
         let mut envs = EnvironmentStack::new();
-        envs.push_module(source.scope().clone());
-        drop(status);
+        envs.push_module(env);
 
         // 9. Set the Function of moduleContext to null.
         // 10. Assert: module.[[Realm]] is not undefined.
@@ -1960,6 +2213,16 @@ impl SourceTextModule {
         capability: Option<PromiseCapability>,
         context: &mut Context,
     ) -> JsResult<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "evaluate",
+            kind = "module",
+            path = module_self
+                .path()
+                .map(|path| tracing::field::display(path.display())),
+        )
+        .entered();
+
         // 1. Let moduleContext be a new ECMAScript code execution context.
         let SourceTextContext {
             codeblock,
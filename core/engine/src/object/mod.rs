@@ -329,6 +329,11 @@ impl<T: ?Sized> Object<T> {
 
         self.private_elements.push((name, element));
     }
+
+    /// Returns `true` if the object has any private element.
+    pub(crate) fn has_private_elements(&self) -> bool {
+        !self.private_elements.is_empty()
+    }
 }
 
 /// The functions binding.
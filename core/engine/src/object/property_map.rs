@@ -26,6 +26,12 @@ use thin_vec::ThinVec;
 /// Since we know the properties of the property descriptors (and they are all the same) we can omit it and just store only
 /// the value field and construct the data property descriptor on demand.
 ///
+/// Dense storage additionally tracks the kind of its elements, similar to the elements kinds of V8:
+/// arrays that only contain integers are stored as [`i32`]s, arrays that only contain numbers are
+/// stored as [`f64`]s, and all other arrays are stored as [`JsValue`]s. Storing a value that does not
+/// fit the current kind transitions the storage to a more general kind (`DenseI32` → `DenseF64` →
+/// `DenseElement`), but never back to a more specific one.
+///
 /// ## Sparse Storage
 ///
 /// This storage is used as a backup if the element keys are not continuous or the property descriptors
@@ -59,13 +65,77 @@ impl Default for IndexedProperties {
 }
 
 impl IndexedProperties {
+    /// Creates dense storage with the most specific element kind that can hold `elements`.
     pub(crate) fn from_dense_js_value(elements: ThinVec<JsValue>) -> Self {
-        if elements.is_empty() {
-            return Self::default();
+        if elements.iter().all(|value| value.as_i32().is_some()) {
+            return Self::DenseI32(elements.iter().filter_map(JsValue::as_i32).collect());
+        }
+        if elements.iter().all(JsValue::is_number) {
+            return Self::DenseF64(elements.iter().filter_map(JsValue::as_number).collect());
         }
         Self::DenseElement(elements)
     }
 
+    /// Returns the number of elements if the storage is dense.
+    pub(crate) fn dense_len(&self) -> Option<usize> {
+        match self {
+            Self::DenseI32(vec) => Some(vec.len()),
+            Self::DenseF64(vec) => Some(vec.len()),
+            Self::DenseElement(vec) => Some(vec.len()),
+            Self::SparseElement(_) | Self::SparseProperty(_) => None,
+        }
+    }
+
+    /// Removes the last element of the dense indexed properties and returns it.
+    ///
+    /// Returns `None` if the storage is sparse or empty.
+    pub(crate) fn pop_dense(&mut self) -> Option<JsValue> {
+        match self {
+            Self::DenseI32(vec) => vec.pop().map(JsValue::from),
+            Self::DenseF64(vec) => vec.pop().map(JsValue::from),
+            Self::DenseElement(vec) => vec.pop(),
+            Self::SparseElement(_) | Self::SparseProperty(_) => None,
+        }
+    }
+
+    /// Returns the first index yielded by `indices` whose dense element is equal to `search`.
+    ///
+    /// Elements are compared with [`JsValue::same_value_zero`] if `same_value_zero` is `true`, and
+    /// with [`JsValue::strict_equals`] otherwise. Specialized element kinds compare the raw numbers
+    /// directly, and can be skipped entirely if `search` is not a number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage is sparse or if an index is out of bounds.
+    #[allow(clippy::float_cmp)]
+    pub(crate) fn dense_position(
+        &self,
+        search: &JsValue,
+        same_value_zero: bool,
+        mut indices: impl Iterator<Item = usize>,
+    ) -> Option<usize> {
+        match self {
+            Self::DenseI32(vec) => {
+                let search = search.as_number()?;
+                indices.find(|&index| f64::from(vec[index]) == search)
+            }
+            Self::DenseF64(vec) => {
+                let search = search.as_number()?;
+                if same_value_zero && search.is_nan() {
+                    return indices.find(|&index| vec[index].is_nan());
+                }
+                indices.find(|&index| vec[index] == search)
+            }
+            Self::DenseElement(vec) if same_value_zero => {
+                indices.find(|&index| JsValue::same_value_zero(search, &vec[index]))
+            }
+            Self::DenseElement(vec) => indices.find(|&index| search.strict_equals(&vec[index])),
+            Self::SparseElement(_) | Self::SparseProperty(_) => {
+                panic!("dense_position called on sparse storage")
+            }
+        }
+    }
+
     #[inline]
     fn property_simple_value(property: &PropertyDescriptor) -> Option<&JsValue> {
         if property.writable().unwrap_or(false)
@@ -714,7 +784,7 @@ impl PropertyMap {
 
     /// Overrides all the indexed properties, setting it to dense storage.
     pub(crate) fn override_indexed_properties(&mut self, properties: ThinVec<JsValue>) {
-        self.indexed_properties = IndexedProperties::DenseElement(properties);
+        self.indexed_properties = IndexedProperties::from_dense_js_value(properties);
     }
 
     pub(crate) fn get_dense_property(&self, index: u32) -> Option<JsValue> {
@@ -107,6 +107,9 @@ impl Shape {
     ///
     /// NOTE: This assumes that there is no property with the given key!
     pub(crate) fn insert_property_transition(&self, key: TransitionKey) -> Self {
+        #[cfg(feature = "vm-stats")]
+        crate::vm_stats::count_shape_transition(crate::vm_stats::ShapeTransition::InsertProperty);
+
         match &self.inner {
             Inner::Shared(shape) => {
                 let shape = shape.insert_property_transition(key);
@@ -127,6 +130,9 @@ impl Shape {
         &self,
         key: TransitionKey,
     ) -> ChangeTransition<Self> {
+        #[cfg(feature = "vm-stats")]
+        crate::vm_stats::count_shape_transition(crate::vm_stats::ShapeTransition::ChangeAttributes);
+
         match &self.inner {
             Inner::Shared(shape) => {
                 let change_transition = shape.change_attributes_transition(key);
@@ -149,6 +155,9 @@ impl Shape {
     ///
     /// NOTE: This assumes that there already is a property with the given key!
     pub(crate) fn remove_property_transition(&self, key: &PropertyKey) -> Self {
+        #[cfg(feature = "vm-stats")]
+        crate::vm_stats::count_shape_transition(crate::vm_stats::ShapeTransition::RemoveProperty);
+
         match &self.inner {
             Inner::Shared(shape) => {
                 let shape = shape.remove_property_transition(key);
@@ -163,6 +172,9 @@ impl Shape {
 
     /// Create a prototype transitions returning the new transitioned [`Shape`].
     pub(crate) fn change_prototype_transition(&self, prototype: JsPrototype) -> Self {
+        #[cfg(feature = "vm-stats")]
+        crate::vm_stats::count_shape_transition(crate::vm_stats::ShapeTransition::ChangePrototype);
+
         match &self.inner {
             Inner::Shared(shape) => {
                 let shape = shape.change_prototype_transition(prototype);
@@ -0,0 +1,518 @@
+//! Sampling CPU profiler of JavaScript code.
+//!
+//! While a profile is recorded with [`Context::start_cpu_profile`], a timer thread asks the VM
+//! to sample its stack at a fixed interval. The VM records the stack before executing its next
+//! instruction, from the same shadow stack that backs error stack traces, so both bytecode and
+//! native functions show up in the profile. Since samples are only taken while instructions
+//! run, the time spent outside of JavaScript, like waiting for jobs, is attributed to a
+//! `(program)` node.
+//!
+//! The [`CpuProfile`] returned by [`Context::stop_cpu_profile`] can be written as the
+//! `.cpuprofile` JSON format of the Chrome developer tools performance panel, or as collapsed
+//! stacks for flamegraph tools like [inferno] or [`flamegraph.pl`][flamegraph].
+//!
+//! ```
+//! # use boa_engine::{Context, Source};
+//! # use std::time::Duration;
+//! let mut context = Context::default();
+//! context.start_cpu_profile(Duration::from_micros(100));
+//! context
+//!     .eval(Source::from_bytes(
+//!         "function work() { let x = 0; for (let i = 0; i < 1e5; i++) x += i; return x; } work();",
+//!     ))
+//!     .unwrap();
+//! let profile = context.stop_cpu_profile().unwrap();
+//!
+//! let mut cpuprofile = Vec::new();
+//! profile.write_json(&mut cpuprofile).unwrap();
+//! let flamegraph = profile.to_collapsed_string();
+//! ```
+//!
+//! More information:
+//!  - [Chrome developer tools `Profile` format][format]
+//!
+//! [inferno]: https://github.com/jonhoo/inferno
+//! [flamegraph]: https://github.com/brendangregg/FlameGraph
+//! [format]: https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#type-Profile
+
+use crate::{
+    Context, JsString, js_string,
+    vm::{SourcePath, shadow_stack::ShadowEntry, source_info::SourceInfo},
+};
+use boa_ast::Position;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A node of the call tree of a [`CpuProfile`].
+///
+/// Every node is a function called from the function of its parent node, and counts the
+/// samples taken while the function was at the top of the stack.
+#[derive(Debug, Clone)]
+pub struct ProfileNode {
+    function_name: JsString,
+    path: SourcePath,
+    position: Option<Position>,
+    hit_count: u64,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    line_ticks: BTreeMap<u32, u64>,
+}
+
+impl ProfileNode {
+    /// Returns the name of the function, which is empty for anonymous functions.
+    ///
+    /// The synthetic nodes that don't correspond to a function are named in parentheses, like
+    /// the `(root)` node.
+    #[must_use]
+    pub const fn function_name(&self) -> &JsString {
+        &self.function_name
+    }
+
+    /// Returns the source of the function.
+    #[must_use]
+    pub const fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// Returns the position of the first instruction of the function that has a position, or
+    /// `None` for native functions.
+    #[must_use]
+    pub const fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Returns the number of samples taken while this function was at the top of the stack.
+    #[must_use]
+    pub const fn hit_count(&self) -> u64 {
+        self.hit_count
+    }
+
+    /// Returns the index of the parent node, or `None` for the root node.
+    #[must_use]
+    pub const fn parent(&self) -> Option<usize> {
+        self.parent
+    }
+
+    /// Returns the indices of the child nodes.
+    #[must_use]
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    /// Returns the number of samples taken on each line of the function, by line number.
+    pub fn line_ticks(&self) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.line_ticks.iter().map(|(&line, &ticks)| (line, ticks))
+    }
+
+    /// Returns the label of the node in collapsed stacks, with the location of its function.
+    fn label(&self) -> String {
+        let name = if self.function_name.is_empty() {
+            "(anonymous)".to_owned()
+        } else {
+            self.function_name.to_std_string_escaped()
+        };
+        match (&self.path, self.position) {
+            (SourcePath::Path(path), Some(position)) => {
+                format!("{name} ({}:{})", path.display(), position.line_number())
+            }
+            (SourcePath::Path(path), None) => format!("{name} ({})", path.display()),
+            _ => name,
+        }
+    }
+}
+
+/// A CPU profile recorded with [`Context::start_cpu_profile`].
+///
+/// See the [module level documentation][self] for more information.
+#[derive(Debug, Clone)]
+pub struct CpuProfile {
+    /// The nodes of the call tree, with the `(root)` node first.
+    nodes: Vec<ProfileNode>,
+
+    /// The node of each sample, with the time it was taken since the start of the profile.
+    samples: Vec<(usize, Duration)>,
+
+    duration: Duration,
+}
+
+impl CpuProfile {
+    /// Returns the nodes of the call tree, with the `(root)` node first.
+    #[must_use]
+    pub fn nodes(&self) -> &[ProfileNode] {
+        &self.nodes
+    }
+
+    /// Returns the node index of each sample, with the time it was taken since the start of
+    /// the profile.
+    #[must_use]
+    pub fn samples(&self) -> &[(usize, Duration)] {
+        &self.samples
+    }
+
+    /// Returns how long the profile was recorded.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Writes the profile as `.cpuprofile` JSON to `writer`.
+    pub fn write_json<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.to_serializable()).map_err(io::Error::from)
+    }
+
+    /// Returns the profile as a `.cpuprofile` JSON string.
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(&self.to_serializable())
+            .expect("CPU profiles can always be serialized")
+    }
+
+    /// Writes the profile as collapsed stacks to `writer`.
+    ///
+    /// Every line is a stack of function names separated by `;`, from the outermost function
+    /// to the innermost one, followed by the number of samples of that stack.
+    pub fn write_collapsed<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_collapsed_string().as_bytes())
+    }
+
+    /// Returns the profile as collapsed stacks, see [`CpuProfile::write_collapsed`].
+    #[must_use]
+    pub fn to_collapsed_string(&self) -> String {
+        let mut output = String::new();
+        let mut stack = Vec::new();
+        for node in &self.nodes {
+            if node.hit_count == 0 {
+                continue;
+            }
+
+            stack.clear();
+            let mut current = Some(node);
+            while let Some(node) = current.filter(|node| node.parent.is_some()) {
+                stack.push(node.label());
+                current = node.parent.map(|parent| &self.nodes[parent]);
+            }
+            stack.reverse();
+            let _ = writeln!(output, "{} {}", stack.join(";"), node.hit_count);
+        }
+        output
+    }
+
+    fn to_serializable(&self) -> SerializableProfile {
+        let mut script_ids = FxHashMap::default();
+        let nodes = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let url = match &node.path {
+                    SourcePath::Path(path) => format!("file://{}", path.display()),
+                    _ => String::new(),
+                };
+                let next_id = script_ids.len() + 1;
+                let script_id = if url.is_empty() {
+                    0
+                } else {
+                    *script_ids.entry(url.clone()).or_insert(next_id)
+                };
+                let (line_number, column_number) = node.position.map_or((-1, -1), |position| {
+                    (
+                        i64::from(position.line_number()) - 1,
+                        i64::from(position.column_number()) - 1,
+                    )
+                });
+
+                SerializableNode {
+                    id: index + 1,
+                    call_frame: SerializableCallFrame {
+                        function_name: node.function_name.to_std_string_escaped(),
+                        script_id: script_id.to_string(),
+                        url,
+                        line_number,
+                        column_number,
+                    },
+                    hit_count: node.hit_count,
+                    children: node.children.iter().map(|child| child + 1).collect(),
+                    position_ticks: node
+                        .line_ticks()
+                        .map(|(line, ticks)| SerializablePositionTick { line, ticks })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let mut last = Duration::ZERO;
+        let time_deltas = self
+            .samples
+            .iter()
+            .map(|&(_, time)| {
+                let delta = time.saturating_sub(last);
+                last = time;
+                delta.as_micros()
+            })
+            .collect();
+
+        SerializableProfile {
+            nodes,
+            start_time: 0,
+            end_time: self.duration.as_micros(),
+            samples: self.samples.iter().map(|&(node, _)| node + 1).collect(),
+            time_deltas,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableProfile {
+    nodes: Vec<SerializableNode>,
+    start_time: u128,
+    end_time: u128,
+    samples: Vec<usize>,
+    time_deltas: Vec<u128>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableNode {
+    id: usize,
+    call_frame: SerializableCallFrame,
+    hit_count: u64,
+    children: Vec<usize>,
+    position_ticks: Vec<SerializablePositionTick>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableCallFrame {
+    function_name: String,
+    script_id: String,
+    url: String,
+    line_number: i64,
+    column_number: i64,
+}
+
+#[derive(Serialize)]
+struct SerializablePositionTick {
+    line: u32,
+    ticks: u64,
+}
+
+/// Identifies the function of a stack entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FunctionKey {
+    /// A bytecode function, identified by its source information, which is shared by all the
+    /// instances of a function.
+    Bytecode(*const ()),
+    Native(JsString),
+}
+
+/// A profile that is being recorded.
+#[derive(Debug)]
+struct Recording {
+    interval: Duration,
+    start: Instant,
+    last_sample: Instant,
+
+    /// Tells the timer thread to stop.
+    stop: Arc<AtomicBool>,
+
+    nodes: Vec<ProfileNode>,
+    children: FxHashMap<(usize, FunctionKey), usize>,
+    samples: Vec<(usize, Duration)>,
+
+    /// The node of the time spent outside of JavaScript, created on the first such sample.
+    program_node: Option<usize>,
+
+    /// Keeps the source information of the sampled functions alive, so that their keys aren't
+    /// reused by other functions.
+    sources: Vec<SourceInfo>,
+}
+
+impl Recording {
+    fn node(
+        &mut self,
+        parent: usize,
+        key: FunctionKey,
+        create: impl FnOnce() -> ProfileNode,
+    ) -> usize {
+        if let Some(&node) = self.children.get(&(parent, key.clone())) {
+            return node;
+        }
+        let node = self.nodes.len();
+        self.nodes.push(ProfileNode {
+            parent: Some(parent),
+            ..create()
+        });
+        self.nodes[parent].children.push(node);
+        self.children.insert((parent, key), node);
+        node
+    }
+
+    /// Records a sample of `stack`, given from the outermost entry to the innermost one.
+    fn sample(&mut self, stack: &[ShadowEntry], pc: u32) {
+        let now = Instant::now();
+
+        // No sample is taken while the VM doesn't execute instructions, so a late sample
+        // means that the time since the previous one was spent outside of JavaScript.
+        if now.duration_since(self.last_sample) > self.interval * 2 {
+            let program = *self.program_node.get_or_insert_with(|| {
+                let node = self.nodes.len();
+                self.nodes.push(ProfileNode {
+                    parent: Some(0),
+                    ..ProfileNode::new(js_string!("(program)"))
+                });
+                self.nodes[0].children.push(node);
+                node
+            });
+            self.nodes[program].hit_count += 1;
+            let time = self.last_sample + self.interval;
+            self.samples
+                .push((program, time.duration_since(self.start)));
+        }
+
+        let mut node = 0;
+        let mut line = None;
+        for (i, entry) in stack.iter().enumerate() {
+            match entry {
+                ShadowEntry::Bytecode {
+                    pc: entry_pc,
+                    source_info,
+                } => {
+                    let key = FunctionKey::Bytecode(source_info.as_ptr());
+                    let is_new = !self.children.contains_key(&(node, key.clone()));
+                    node = self.node(node, key, || ProfileNode {
+                        function_name: source_info.function_name().clone(),
+                        path: source_info.map().path().clone(),
+                        position: source_info.map().function_position(),
+                        ..ProfileNode::new(JsString::default())
+                    });
+                    if is_new {
+                        self.sources.push(source_info.clone());
+                    }
+
+                    // The last entry is the running function, whose `pc` isn't updated in the
+                    // shadow stack.
+                    let pc = if i + 1 == stack.len() { pc } else { *entry_pc };
+                    line = source_info.map().find(pc).map(Position::line_number);
+                }
+                ShadowEntry::Native { function_name, .. } => {
+                    let name = function_name.clone().unwrap_or_default();
+                    node = self.node(node, FunctionKey::Native(name.clone()), || ProfileNode {
+                        ..ProfileNode::new(name)
+                    });
+                    line = None;
+                }
+            }
+        }
+
+        let node_data = &mut self.nodes[node];
+        node_data.hit_count += 1;
+        if let Some(line) = line {
+            *node_data.line_ticks.entry(line).or_default() += 1;
+        }
+        self.samples.push((node, now.duration_since(self.start)));
+        self.last_sample = now;
+    }
+
+    fn finish(self) -> CpuProfile {
+        self.stop.store(true, Ordering::Relaxed);
+        CpuProfile {
+            nodes: self.nodes,
+            samples: self.samples,
+            duration: self.start.elapsed(),
+        }
+    }
+}
+
+impl ProfileNode {
+    fn new(function_name: JsString) -> Self {
+        Self {
+            function_name,
+            path: SourcePath::None,
+            position: None,
+            hit_count: 0,
+            parent: None,
+            children: Vec::new(),
+            line_ticks: BTreeMap::new(),
+        }
+    }
+}
+
+/// The profiler state of a [`Context`].
+#[derive(Debug, Default)]
+pub(crate) struct ProfilerState {
+    /// Set by the timer thread when a sample should be taken.
+    tick: Arc<AtomicBool>,
+
+    recording: Option<Recording>,
+}
+
+impl ProfilerState {
+    /// Returns `true` if a sample should be taken before the next instruction.
+    #[inline]
+    pub(crate) fn should_sample(&self) -> bool {
+        self.tick.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn start(&mut self, interval: Duration) {
+        if let Some(recording) = self.recording.take() {
+            recording.finish();
+        }
+
+        let interval = interval.max(Duration::from_micros(1));
+        let stop = Arc::new(AtomicBool::new(false));
+        let tick = self.tick.clone();
+        let thread_stop = stop.clone();
+        thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(interval);
+                tick.store(true, Ordering::Relaxed);
+            }
+        });
+
+        let now = Instant::now();
+        self.recording = Some(Recording {
+            interval,
+            start: now,
+            last_sample: now,
+            stop,
+            nodes: vec![ProfileNode::new(js_string!("(root)"))],
+            children: FxHashMap::default(),
+            samples: Vec::new(),
+            program_node: None,
+            sources: Vec::new(),
+        });
+    }
+
+    pub(crate) fn stop(&mut self) -> Option<CpuProfile> {
+        let profile = self.recording.take().map(Recording::finish);
+        self.tick.store(false, Ordering::Relaxed);
+        profile
+    }
+}
+
+impl Context {
+    /// Records a sample of the stack of the running code.
+    pub(crate) fn profiler_sample(&mut self) {
+        let vm = &mut self.vm;
+        vm.profiler.tick.store(false, Ordering::Relaxed);
+        let pc = vm.frame().pc;
+        if let Some(recording) = &mut vm.profiler.recording {
+            recording.sample(vm.shadow_stack.entries(), pc);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use boa_ast::Position;
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::{CpuProfile, ProfileNode};
+use crate::{Context, js_string};
+
+/// Profiles `code` with a short sampling interval.
+fn profile(code: &str) -> CpuProfile {
+    let mut context = Context::default();
+    context.start_cpu_profile(Duration::from_micros(10));
+    context
+        .eval(Source::from_bytes(code))
+        .expect("the code should run");
+    context
+        .stop_cpu_profile()
+        .expect("a profile should be recorded")
+}
+
+/// Returns the function names from the root to the node.
+fn stack(profile: &CpuProfile, mut node: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    while let Some(parent) = profile.nodes()[node].parent() {
+        names.push(
+            profile.nodes()[node]
+                .function_name()
+                .to_std_string_escaped(),
+        );
+        node = parent;
+    }
+    names.reverse();
+    names
+}
+
+const BUSY: &str = indoc! {r#"
+    function work() {
+        let x = 0;
+        for (let i = 0; i < 100000; i++) {
+            x += i % 7;
+        }
+        return x;
+    }
+    work();
+"#};
+
+#[test]
+fn samples_functions() {
+    let profile = profile(BUSY);
+
+    assert_eq!(profile.nodes()[0].function_name(), &js_string!("(root)"));
+    assert!(!profile.samples().is_empty());
+
+    let (index, work) = profile
+        .nodes()
+        .iter()
+        .enumerate()
+        .find(|(_, node)| node.function_name() == &js_string!("work"))
+        .expect("`work` should be sampled");
+    assert_eq!(stack(&profile, index), ["<main>", "work"]);
+    assert!(work.hit_count() > 0);
+    assert_eq!(work.position().map(Position::line_number), Some(1));
+    assert!(work.line_ticks().all(|(line, _)| (1..=7).contains(&line)));
+
+    let hits: u64 = profile.nodes().iter().map(ProfileNode::hit_count).sum();
+    assert_eq!(hits, profile.samples().len() as u64);
+    assert!(
+        profile
+            .samples()
+            .windows(2)
+            .all(|samples| samples[0].1 <= samples[1].1)
+    );
+}
+
+#[test]
+fn samples_native_functions() {
+    let profile = profile(indoc! {r#"
+        function compare(a, b) {
+            let x = 0;
+            for (let i = 0; i < 2000; i++) {
+                x += i;
+            }
+            return a - b;
+        }
+        [5, 3, 8, 1, 9, 2, 7, 4, 6, 0].sort(compare);
+    "#});
+
+    let compare = profile
+        .nodes()
+        .iter()
+        .position(|node| node.function_name() == &js_string!("compare"))
+        .expect("`compare` should be sampled");
+    assert_eq!(stack(&profile, compare), ["<main>", "sort", "compare"]);
+}
+
+#[test]
+fn stop_without_start() {
+    let mut context = Context::default();
+    assert!(context.stop_cpu_profile().is_none());
+
+    context.start_cpu_profile(Duration::from_millis(1));
+    assert!(context.stop_cpu_profile().is_some());
+    assert!(context.stop_cpu_profile().is_none());
+}
+
+#[test]
+fn cpuprofile_json() {
+    let profile = profile(BUSY);
+    let json: serde_json::Value =
+        serde_json::from_str(&profile.to_json_string()).expect("the profile should be JSON");
+
+    let nodes = json["nodes"].as_array().expect("nodes should be an array");
+    assert_eq!(nodes.len(), profile.nodes().len());
+    assert_eq!(nodes[0]["id"], 1);
+    assert_eq!(nodes[0]["callFrame"]["functionName"], "(root)");
+
+    let work = nodes
+        .iter()
+        .find(|node| node["callFrame"]["functionName"] == "work")
+        .expect("`work` should be sampled");
+    assert_eq!(work["callFrame"]["lineNumber"], 0);
+    assert!(
+        work["positionTicks"]
+            .as_array()
+            .is_some_and(|t| !t.is_empty())
+    );
+
+    let samples = json["samples"]
+        .as_array()
+        .expect("samples should be an array");
+    let time_deltas = json["timeDeltas"]
+        .as_array()
+        .expect("deltas should be an array");
+    assert_eq!(samples.len(), profile.samples().len());
+    assert_eq!(time_deltas.len(), samples.len());
+    assert_eq!(json["startTime"], 0);
+}
+
+#[test]
+fn collapsed_stacks() {
+    let profile = profile(BUSY);
+    let collapsed = profile.to_collapsed_string();
+
+    let mut total = 0;
+    for line in collapsed.lines() {
+        let (stack, count) = line
+            .rsplit_once(' ')
+            .expect("a line should end with a count");
+        assert!(stack.starts_with("<main>") || stack == "(program)");
+        total += count.parse::<u64>().expect("the count should be a number");
+    }
+    assert_eq!(total, profile.samples().len() as u64);
+    assert!(collapsed.contains("<main>;work "));
+}
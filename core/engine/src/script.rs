@@ -13,7 +13,7 @@ use std::path::{Path, PathBuf};
 use rustc_hash::FxHashMap;
 
 use boa_gc::{Finalize, Gc, GcRefCell, Trace};
-use boa_parser::{Parser, Source, source::ReadChar};
+use boa_parser::{Parser, Source, lexer::Error as LexError, source::ReadChar};
 
 use crate::{
     Context, HostDefined, JsResult, JsString, JsValue, Module, SpannedSourceText,
@@ -22,7 +22,7 @@ use crate::{
     js_string,
     realm::Realm,
     spanned_source_text::SourceText,
-    vm::{ActiveRunnable, CallFrame, CallFrameFlags, CodeBlock},
+    vm::{ActiveRunnable, CallFrame, CallFrameFlags, CodeBlock, code_cache},
 };
 
 /// ECMAScript's [**Script Record**][spec].
@@ -90,6 +90,16 @@ impl Script {
         context: &mut Context,
     ) -> JsResult<Self> {
         let path = src.path().map(Path::to_path_buf);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "parse",
+            kind = "script",
+            path = path
+                .as_deref()
+                .map(|path| tracing::field::display(path.display())),
+        )
+        .entered();
+
         let mut parser = Parser::new(src);
         parser.set_identifier(context.next_parser_identifier());
         if context.is_strict() {
@@ -102,6 +112,7 @@ impl Script {
         }
 
         let source_text = SourceText::new(source);
+        context.source_maps.attach(&source_text, path.as_deref());
 
         Ok(Self {
             inner: Gc::new(Inner {
@@ -115,6 +126,82 @@ impl Script {
         })
     }
 
+    /// Parses the provided `src` as an ECMAScript script, using the compiled code stored in `cache`
+    /// if possible.
+    ///
+    /// `cache` must be the result of [`Script::to_code_cache`]. It is only used if it was produced
+    /// by the same version of Boa, from the same source text and with the same strictness and
+    /// optimizer options of the context. If it cannot be used, the source is parsed as in
+    /// [`Script::parse`].
+    ///
+    /// The functions of the script are decoded from the cache the first time they are used.
+    pub fn parse_with_code_cache<R: ReadChar>(
+        src: Source<'_, R>,
+        cache: &[u8],
+        realm: Option<Realm>,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let path = src.path();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "parse",
+            kind = "script",
+            path = path.map(|path| tracing::field::display(path.display())),
+            code_cache = true,
+        )
+        .entered();
+
+        let source_text = src
+            .read_source_text()
+            .map_err(|err| boa_parser::Error::from(LexError::from(err)))?;
+        let source_text = SourceText::new(source_text);
+        context.source_maps.attach(&source_text, path);
+
+        let scope = context.realm().scope().clone();
+        let codeblock = code_cache::decode_script(
+            cache,
+            &source_text,
+            path.map(Path::to_path_buf).into(),
+            &scope,
+            context,
+        );
+
+        let Some(codeblock) = codeblock else {
+            let src = Source::from_utf16(source_text.code_units());
+            return match path {
+                Some(path) => Self::parse(src.with_path(path), realm, context),
+                None => Self::parse(src, realm, context),
+            };
+        };
+
+        Ok(Self {
+            inner: Gc::new(Inner {
+                realm: realm.unwrap_or_else(|| context.realm().clone()),
+                phase: GcRefCell::new(ScriptPhase::Codeblock(codeblock)),
+                source_text,
+                loaded_modules: GcRefCell::default(),
+                host_defined: HostDefined::default(),
+                path: path.map(Path::to_path_buf),
+            }),
+        })
+    }
+
+    /// Serializes the compiled code of this script into a code cache, compiling it first if
+    /// needed.
+    ///
+    /// The cache can be passed to [`Script::parse_with_code_cache`] to load the script again
+    /// without parsing and compiling it. It should be produced before the script is evaluated,
+    /// with the same context options that will be used to load it.
+    pub fn to_code_cache(&self, context: &mut Context) -> JsResult<Vec<u8>> {
+        let codeblock = self.codeblock(context)?;
+        Ok(code_cache::encode_script(
+            &codeblock,
+            &self.inner.source_text,
+            self.inner.realm.scope(),
+            context,
+        ))
+    }
+
     /// Compiles the codeblock of this script.
     ///
     /// This is a no-op if this has been called previously.
@@ -126,6 +213,16 @@ impl Script {
                 ScriptPhase::Ast(source) => source,
             };
 
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!(
+                "compile",
+                kind = "script",
+                path = self
+                    .path()
+                    .map(|path| tracing::field::display(path.display())),
+            )
+            .entered();
+
             let mut annex_b_function_names = Vec::new();
 
             global_declaration_instantiation_context(
@@ -137,6 +234,7 @@ impl Script {
 
             let spanned_source_text = SpannedSourceText::new_source_only(self.get_source());
 
+            let lazy_functions = context.lazy_compilation();
             let mut compiler = ByteCompiler::new(
                 js_string!("<main>"),
                 source.strict(),
@@ -150,6 +248,7 @@ impl Script {
                 spanned_source_text,
                 self.path().map(Path::to_owned).into(),
             );
+            compiler.lazy_functions = lazy_functions;
 
             #[cfg(feature = "annex-b")]
             {
@@ -174,6 +273,16 @@ impl Script {
     ///
     /// [`JobExecutor::run_jobs`]: crate::job::JobExecutor::run_jobs
     pub fn evaluate(&self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!(
+            "evaluate",
+            kind = "script",
+            path = self
+                .path()
+                .map(|path| tracing::field::display(path.display())),
+        )
+        .entered();
+
         self.prepare_run(context)?;
         let record = context.run();
 
@@ -206,13 +315,29 @@ impl Script {
         context: &mut Context,
         budget: u32,
     ) -> JsResult<JsValue> {
-        self.prepare_run(context)?;
+        let evaluate = async {
+            self.prepare_run(context)?;
 
-        let record = context.run_async_with_budget(budget).await;
+            let record = context.run_async_with_budget(budget).await;
 
-        context.vm.pop_frame();
+            context.vm.pop_frame();
 
-        record.consume()
+            record.consume()
+        };
+
+        #[cfg(feature = "tracing")]
+        let evaluate = tracing::Instrument::instrument(
+            evaluate,
+            tracing::info_span!(
+                "evaluate",
+                kind = "script",
+                path = self
+                    .path()
+                    .map(|path| tracing::field::display(path.display())),
+            ),
+        );
+
+        evaluate.await
     }
 
     fn prepare_run(&self, context: &mut Context) -> JsResult<()> {
@@ -0,0 +1,765 @@
+//! Restoration of a realm from a snapshot.
+
+use std::{path::Path, rc::Rc};
+
+use boa_ast::scope::ScopeBinding;
+use boa_gc::Gc;
+use rustc_hash::FxHashMap;
+
+use super::{ObjectKind, SnapshotBase, SnapshotError, read_snapshot, tag};
+use crate::{
+    Context, JsBigInt, JsObject, JsString, JsSymbol, JsValue,
+    builtins::{
+        Array, Date, OrdinaryObject,
+        error::{Error, ErrorKind},
+        function::{BoundFunction, OrdinaryFunction},
+        map::ordered_map::OrderedMap,
+        regexp::RegExp,
+        set::ordered_set::OrderedSet,
+        symbol::registered_symbol,
+    },
+    environments::{
+        DeclarativeEnvironment, DeclarativeEnvironmentKind, Environment, EnvironmentStack,
+        FunctionEnvironment, FunctionSlots, LexicalEnvironment, ThisBindingStatus,
+    },
+    object::JsFunction,
+    property::{PropertyDescriptor, PropertyKey},
+    spanned_source_text::SourceText,
+    vm::{
+        CodeBlock,
+        code_cache::{CachedCode, CodeSource, Reader, Tables},
+        source_info::SourcePath,
+    },
+};
+
+/// Restores the realm of `context` from a snapshot produced by [`super::encode`].
+///
+/// The bindings, objects and environments of the snapshot are created first, and only then are
+/// their values and properties set, so that they can reference each other.
+pub(crate) fn restore(context: &mut Context, snapshot: &[u8]) -> Result<(), SnapshotError> {
+    let base = SnapshotBase::new(context, false);
+    let payload = read_snapshot(snapshot, base.fingerprint)?;
+    let mut reader = Reader::new(payload);
+    let records = Records::read(&mut reader).ok_or(SnapshotError::InvalidSnapshot)?;
+
+    let scope = context.realm().scope().clone();
+    if records
+        .global_bindings
+        .iter()
+        .any(|binding| scope.has_binding(binding.name()))
+    {
+        return Err(SnapshotError::BaseMismatch);
+    }
+
+    let code = if reader.bool().ok_or(SnapshotError::InvalidSnapshot)? {
+        let tables = Tables::read(payload, &mut reader).ok_or(SnapshotError::InvalidSnapshot)?;
+        let declared = records
+            .global_bindings
+            .iter()
+            .map(|binding| binding.name().clone())
+            .collect::<Vec<_>>();
+        if !tables.check_globals(&scope, &declared) {
+            return Err(SnapshotError::BaseMismatch);
+        }
+        Some(tables)
+    } else if reader.is_empty() {
+        None
+    } else {
+        return Err(SnapshotError::InvalidSnapshot);
+    };
+
+    for binding in &records.global_bindings {
+        if binding.is_mutable() {
+            drop(scope.create_mutable_binding(binding.name().clone(), false));
+        } else {
+            scope.create_immutable_binding(binding.name().clone(), binding.is_strict());
+        }
+    }
+    context.realm().resize_global_env();
+
+    let code = match code {
+        Some(tables) => {
+            let mut identifiers = FxHashMap::default();
+            let sources = records
+                .code_sources
+                .iter()
+                .map(|(first_function, source)| {
+                    let (text, path) = records.sources.get(*source as usize)?;
+                    let template_identifier = *identifiers
+                        .entry(*source)
+                        .or_insert_with(|| context.next_parser_identifier());
+                    Some(CodeSource {
+                        first_function: *first_function,
+                        text: text.clone(),
+                        path: path.clone(),
+                        template_identifier,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or(SnapshotError::InvalidSnapshot)?;
+            Some(
+                CachedCode::new(payload, tables, &scope, sources)
+                    .ok_or(SnapshotError::InvalidSnapshot)?,
+            )
+        }
+        None => None,
+    };
+
+    let mut restorer = Restorer {
+        base: &base,
+        records: &records,
+        code,
+        code_blocks: FxHashMap::default(),
+        objects: vec![State::Pending; records.objects.len()],
+        environments: vec![State::Pending; records.environments.len()],
+    };
+    restorer
+        .restore(context)
+        .ok_or(SnapshotError::InvalidSnapshot)
+}
+
+/// A value of a snapshot, which may reference an object that is not created yet.
+#[derive(Debug, Clone)]
+enum Value {
+    Primitive(JsValue),
+    Object(u32),
+}
+
+#[derive(Debug)]
+struct Property {
+    key: PropertyKey,
+    enumerable: Option<bool>,
+    configurable: Option<bool>,
+    writable: Option<bool>,
+    value: Option<Value>,
+    get: Option<Value>,
+    set: Option<Value>,
+}
+
+#[derive(Debug)]
+enum ObjectData {
+    Ordinary,
+    Array,
+    Function {
+        code: u32,
+        environments: Vec<EnvironmentRef>,
+        home_object: Option<u32>,
+        fields: Vec<(PropertyKey, u32, Option<PropertyKey>)>,
+    },
+    BoundFunction {
+        target: u32,
+        this: Value,
+        args: Vec<Value>,
+    },
+    Error(ErrorKind),
+    Boolean(bool),
+    Number(f64),
+    String(JsString),
+    Date(f64),
+    RegExp(JsString, JsString),
+    Map(Vec<(Value, Value)>),
+    Set(Vec<Value>),
+}
+
+#[derive(Debug)]
+struct ObjectRecord {
+    data: ObjectData,
+    prototype: Option<u32>,
+    extensible: bool,
+    properties: Vec<Property>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum EnvironmentRef {
+    Declarative(u32),
+    Object(u32),
+}
+
+#[derive(Debug)]
+enum ThisRecord {
+    Lexical,
+    Uninitialized,
+    Initialized(Value),
+}
+
+#[derive(Debug)]
+struct FunctionSlotsRecord {
+    scope: u32,
+    this: ThisRecord,
+    function_object: u32,
+    new_target: Option<u32>,
+}
+
+#[derive(Debug)]
+struct EnvironmentRecord {
+    poisoned: bool,
+    with: bool,
+    bindings: Vec<Option<Value>>,
+    function: Option<FunctionSlotsRecord>,
+}
+
+/// The changes to an object of the base.
+#[derive(Debug)]
+struct Diff {
+    index: u32,
+    #[allow(clippy::option_option)]
+    prototype: Option<Option<u32>>,
+    extensible: bool,
+    removed: Vec<PropertyKey>,
+    changed: Vec<Property>,
+}
+
+/// The parsed sections of a snapshot, before the code tables.
+#[derive(Debug)]
+struct Records {
+    sources: Vec<(SourceText, SourcePath)>,
+    code_sources: Vec<(u32, u32)>,
+    global_bindings: Vec<ScopeBinding>,
+    global_values: Vec<Option<Value>>,
+    diffs: Vec<Diff>,
+    objects: Vec<ObjectRecord>,
+    environments: Vec<EnvironmentRecord>,
+}
+
+impl Records {
+    fn read(reader: &mut Reader<'_>) -> Option<Self> {
+        let symbols = reader.seq(|reader| match reader.u8()? {
+            0 => JsSymbol::from_well_known_tag(reader.u8()?),
+            1 => registered_symbol(&reader.string()?).ok(),
+            2 => JsSymbol::new(reader.option(Reader::string)?),
+            _ => None,
+        })?;
+
+        let sources = reader.seq(|reader| {
+            let text = reader.option(|reader| {
+                let units = reader.seq(Reader::u16)?;
+                Some(SourceText::from_code_units(&units))
+            })?;
+            let path = match reader.u8()? {
+                0 => SourcePath::None,
+                1 => SourcePath::Eval,
+                2 => SourcePath::Json,
+                3 => SourcePath::Path(Rc::from(Path::new(reader.str()?))),
+                _ => return None,
+            };
+            Some((text.unwrap_or_else(SourceText::new_empty), path))
+        })?;
+        let code_sources = reader.seq(|reader| Some((reader.u32()?, reader.u32()?)))?;
+
+        let global_bindings =
+            reader.seq(|reader| Some(ScopeBinding::new(reader.string()?, 0, reader.u8()?)))?;
+
+        let mut records = RecordReader { symbols: &symbols };
+        let global_values = reader.seq(|reader| reader.option(|reader| records.value(reader)))?;
+        let diffs = reader.seq(|reader| records.diff(reader))?;
+        let objects = reader.seq(|reader| records.object(reader))?;
+        let environments = reader.seq(|reader| records.environment(reader))?;
+
+        Some(Self {
+            sources,
+            code_sources,
+            global_bindings,
+            global_values,
+            diffs,
+            objects,
+            environments,
+        })
+    }
+}
+
+/// Reads the records that reference symbols.
+struct RecordReader<'a> {
+    symbols: &'a [JsSymbol],
+}
+
+impl RecordReader<'_> {
+    fn value(&mut self, reader: &mut Reader<'_>) -> Option<Value> {
+        let value = match reader.u8()? {
+            tag::UNDEFINED => JsValue::undefined(),
+            tag::NULL => JsValue::null(),
+            tag::FALSE => JsValue::new(false),
+            tag::TRUE => JsValue::new(true),
+            tag::INTEGER => JsValue::new(reader.u32()? as i32),
+            tag::NUMBER => JsValue::new(f64::from_bits(reader.u64()?)),
+            tag::STRING => JsValue::new(reader.string()?),
+            tag::BIGINT => JsValue::new(JsBigInt::from_string_radix(reader.str()?, 16)?),
+            tag::SYMBOL => JsValue::new(self.symbol(reader)?),
+            tag::OBJECT => return Some(Value::Object(reader.u32()?)),
+            _ => return None,
+        };
+        Some(Value::Primitive(value))
+    }
+
+    fn symbol(&self, reader: &mut Reader<'_>) -> Option<JsSymbol> {
+        self.symbols.get(reader.u32()? as usize).cloned()
+    }
+
+    fn key(&mut self, reader: &mut Reader<'_>) -> Option<PropertyKey> {
+        Some(match reader.u8()? {
+            0 => PropertyKey::String(reader.string()?),
+            1 => PropertyKey::Symbol(self.symbol(reader)?),
+            2 => PropertyKey::from(reader.u32()?),
+            _ => return None,
+        })
+    }
+
+    fn property(&mut self, reader: &mut Reader<'_>) -> Option<Property> {
+        let key = self.key(reader)?;
+        let flags = reader.u8()?;
+        let flag = |i: u8| (flags >> (i * 2) & 0b10 != 0).then_some(flags >> (i * 2) & 1 != 0);
+        Some(Property {
+            key,
+            enumerable: flag(0),
+            configurable: flag(1),
+            writable: flag(2),
+            value: reader.option(|reader| self.value(reader))?,
+            get: reader.option(|reader| self.value(reader))?,
+            set: reader.option(|reader| self.value(reader))?,
+        })
+    }
+
+    fn diff(&mut self, reader: &mut Reader<'_>) -> Option<Diff> {
+        Some(Diff {
+            index: reader.u32()?,
+            prototype: reader.option(|reader| reader.option(Reader::u32))?,
+            extensible: reader.bool()?,
+            removed: reader.seq(|reader| self.key(reader))?,
+            changed: reader.seq(|reader| self.property(reader))?,
+        })
+    }
+
+    fn object(&mut self, reader: &mut Reader<'_>) -> Option<ObjectRecord> {
+        let data = match ObjectKind::from_u8(reader.u8()?)? {
+            ObjectKind::Ordinary => ObjectData::Ordinary,
+            ObjectKind::Array => ObjectData::Array,
+            ObjectKind::Function => ObjectData::Function {
+                code: reader.u32()?,
+                environments: reader.seq(|reader| {
+                    Some(match reader.u8()? {
+                        0 => EnvironmentRef::Declarative(reader.u32()?),
+                        1 => EnvironmentRef::Object(reader.u32()?),
+                        _ => return None,
+                    })
+                })?,
+                home_object: reader.option(Reader::u32)?,
+                fields: reader.seq(|reader| {
+                    Some((
+                        self.key(reader)?,
+                        reader.u32()?,
+                        reader.option(|reader| self.key(reader))?,
+                    ))
+                })?,
+            },
+            ObjectKind::BoundFunction => ObjectData::BoundFunction {
+                target: reader.u32()?,
+                this: self.value(reader)?,
+                args: reader.seq(|reader| self.value(reader))?,
+            },
+            ObjectKind::Error => ObjectData::Error(match reader.u8()? {
+                0 => ErrorKind::Aggregate,
+                1 => ErrorKind::Error,
+                2 => ErrorKind::Eval,
+                3 => ErrorKind::Type,
+                4 => ErrorKind::Range,
+                5 => ErrorKind::Reference,
+                6 => ErrorKind::Syntax,
+                7 => ErrorKind::Uri,
+                _ => return None,
+            }),
+            ObjectKind::Boolean => ObjectData::Boolean(reader.bool()?),
+            ObjectKind::Number => ObjectData::Number(f64::from_bits(reader.u64()?)),
+            ObjectKind::String => ObjectData::String(reader.string()?),
+            ObjectKind::Date => ObjectData::Date(f64::from_bits(reader.u64()?)),
+            ObjectKind::RegExp => ObjectData::RegExp(reader.string()?, reader.string()?),
+            ObjectKind::Map => ObjectData::Map(
+                reader.seq(|reader| Some((self.value(reader)?, self.value(reader)?)))?,
+            ),
+            ObjectKind::Set => ObjectData::Set(reader.seq(|reader| self.value(reader))?),
+        };
+
+        Some(ObjectRecord {
+            data,
+            prototype: reader.option(Reader::u32)?,
+            extensible: reader.bool()?,
+            properties: reader.seq(|reader| self.property(reader))?,
+        })
+    }
+
+    fn environment(&mut self, reader: &mut Reader<'_>) -> Option<EnvironmentRecord> {
+        let function = reader.bool()?;
+        let poisoned = reader.bool()?;
+        let with = reader.bool()?;
+        let bindings = reader.seq(|reader| reader.option(|reader| self.value(reader)))?;
+
+        let function = if function {
+            Some(FunctionSlotsRecord {
+                scope: reader.u32()?,
+                this: match reader.u8()? {
+                    0 => ThisRecord::Lexical,
+                    1 => ThisRecord::Uninitialized,
+                    2 => ThisRecord::Initialized(self.value(reader)?),
+                    _ => return None,
+                },
+                function_object: reader.u32()?,
+                new_target: reader.option(Reader::u32)?,
+            })
+        } else {
+            None
+        };
+
+        Some(EnvironmentRecord {
+            poisoned,
+            with,
+            bindings,
+            function,
+        })
+    }
+}
+
+/// The creation state of an object or environment.
+#[derive(Debug, Clone)]
+enum State<T> {
+    Pending,
+    /// The node is being created, which is only possible if the dependencies have a cycle.
+    InProgress,
+    Created(T),
+}
+
+/// Creates the objects and environments of a snapshot.
+struct Restorer<'a> {
+    base: &'a SnapshotBase,
+    records: &'a Records,
+    code: Option<Rc<CachedCode>>,
+    code_blocks: FxHashMap<u32, Gc<CodeBlock>>,
+    objects: Vec<State<JsObject>>,
+    environments: Vec<State<Gc<DeclarativeEnvironment>>>,
+}
+
+impl Restorer<'_> {
+    fn restore(&mut self, context: &mut Context) -> Option<()> {
+        for index in 0..self.records.objects.len() {
+            self.object(self.base.len() + index as u32, context)?;
+        }
+        for index in 0..self.records.environments.len() {
+            self.environment(index as u32, context)?;
+        }
+
+        let records = self.records;
+        for (index, record) in records.objects.iter().enumerate() {
+            let object = self.object(self.base.len() + index as u32, context)?;
+            let prototype = self.optional_object(record.prototype, context)?;
+            object.set_prototype(prototype);
+            for property in &record.properties {
+                let (key, property) = self.property(property, context)?;
+                object.borrow_mut().properties_mut().insert(&key, property);
+            }
+            object.borrow_mut().extensible = record.extensible;
+
+            match &record.data {
+                ObjectData::Function {
+                    home_object,
+                    fields,
+                    ..
+                } => {
+                    let home_object = self.optional_object(*home_object, context)?;
+                    let fields = fields
+                        .iter()
+                        .map(|(key, initializer, name)| {
+                            let initializer = self.object(*initializer, context)?;
+                            let initializer = JsFunction::from_object(initializer)?;
+                            Some((key.clone(), initializer, name.clone()))
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+
+                    let mut function = object.downcast_mut::<OrdinaryFunction>()?;
+                    if let Some(home_object) = home_object {
+                        function.set_home_object(home_object);
+                    }
+                    for (key, initializer, name) in fields {
+                        function.push_field(key, initializer, name);
+                    }
+                }
+                ObjectData::Map(entries) => {
+                    let entries = entries
+                        .iter()
+                        .map(|(key, value)| {
+                            Some((self.value(key, context)?, self.value(value, context)?))
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    let mut map = object.downcast_mut::<OrderedMap<JsValue>>()?;
+                    for (key, value) in entries {
+                        map.insert(key, value);
+                    }
+                }
+                ObjectData::Set(values) => {
+                    let values = values
+                        .iter()
+                        .map(|value| self.value(value, context))
+                        .collect::<Option<Vec<_>>>()?;
+                    let mut set = object.downcast_mut::<OrderedSet>()?;
+                    for value in values {
+                        set.add(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (index, record) in records.environments.iter().enumerate() {
+            let env = self.environment(index as u32, context)?;
+            let values = record
+                .bindings
+                .iter()
+                .map(|value| self.optional_value(value.as_ref(), context))
+                .collect::<Option<Vec<_>>>()?;
+            let bindings = match env.kind() {
+                DeclarativeEnvironmentKind::Lexical(env) => env.bindings(),
+                DeclarativeEnvironmentKind::Function(env) => env.bindings(),
+                _ => return None,
+            };
+            *bindings.borrow_mut() = values;
+        }
+
+        let global_values = records
+            .global_values
+            .iter()
+            .map(|value| self.optional_value(value.as_ref(), context))
+            .collect::<Option<Vec<_>>>()?;
+        let global = context.realm().environment().clone();
+        let DeclarativeEnvironmentKind::Global(global) = global.kind() else {
+            return None;
+        };
+        let mut bindings = global.bindings().borrow_mut();
+        if global_values.len() != bindings.len() {
+            return None;
+        }
+        *bindings = global_values;
+        drop(bindings);
+
+        for diff in &records.diffs {
+            let object = self.base.objects.get(diff.index as usize)?.object.clone();
+            if let Some(prototype) = diff.prototype {
+                let prototype = self.optional_object(prototype, context)?;
+                object.set_prototype(prototype);
+            }
+            for key in &diff.removed {
+                object.borrow_mut().remove(key);
+            }
+            for property in &diff.changed {
+                let (key, property) = self.property(property, context)?;
+                object.borrow_mut().properties_mut().insert(&key, property);
+            }
+            object.borrow_mut().extensible = diff.extensible;
+        }
+
+        Some(())
+    }
+
+    fn value(&mut self, value: &Value, context: &mut Context) -> Option<JsValue> {
+        match value {
+            Value::Primitive(value) => Some(value.clone()),
+            Value::Object(index) => self.object(*index, context).map(Into::into),
+        }
+    }
+
+    #[allow(clippy::option_option)]
+    fn optional_value(
+        &mut self,
+        value: Option<&Value>,
+        context: &mut Context,
+    ) -> Option<Option<JsValue>> {
+        match value {
+            Some(value) => self.value(value, context).map(Some),
+            None => Some(None),
+        }
+    }
+
+    #[allow(clippy::option_option)]
+    fn optional_object(
+        &mut self,
+        index: Option<u32>,
+        context: &mut Context,
+    ) -> Option<Option<JsObject>> {
+        match index {
+            Some(index) => self.object(index, context).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn property(
+        &mut self,
+        property: &Property,
+        context: &mut Context,
+    ) -> Option<(PropertyKey, PropertyDescriptor)> {
+        let mut value = |value: &Option<Value>| self.optional_value(value.as_ref(), context);
+        let descriptor = PropertyDescriptor::builder()
+            .maybe_enumerable(property.enumerable)
+            .maybe_configurable(property.configurable)
+            .maybe_writable(property.writable)
+            .maybe_value(value(&property.value)?)
+            .maybe_get(value(&property.get)?)
+            .maybe_set(value(&property.set)?)
+            .build();
+        Some((property.key.clone(), descriptor))
+    }
+
+    fn code(&mut self, index: u32) -> Option<Gc<CodeBlock>> {
+        if let Some(code) = self.code_blocks.get(&index) {
+            return Some(code.clone());
+        }
+        let code = Gc::new(self.code.as_ref()?.function(index)?);
+        self.code_blocks.insert(index, code.clone());
+        Some(code)
+    }
+
+    /// Returns the object at `index`, creating it if needed.
+    ///
+    /// The internal slots an object is created with are created first, which must not lead back
+    /// to the object. The properties of the object are only set once all objects exist.
+    fn object(&mut self, index: u32, context: &mut Context) -> Option<JsObject> {
+        let Some(local) = index.checked_sub(self.base.len()) else {
+            return Some(self.base.objects[index as usize].object.clone());
+        };
+        match self.objects.get(local as usize)? {
+            State::Created(object) => return Some(object.clone()),
+            State::InProgress => return None,
+            State::Pending => {}
+        }
+        self.objects[local as usize] = State::InProgress;
+
+        let root_shape = context.root_shape().clone();
+        let new = |data| JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, data);
+        let object = match &self.records.objects[local as usize].data {
+            ObjectData::Ordinary => new(OrdinaryObject).upcast(),
+            ObjectData::Array => {
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, Array).upcast()
+            }
+            ObjectData::Function {
+                code, environments, ..
+            } => {
+                let code = self.code(*code)?;
+                let environments = environments
+                    .iter()
+                    .map(|env| {
+                        Some(match *env {
+                            EnvironmentRef::Declarative(index) => {
+                                Environment::Declarative(self.environment(index, context)?)
+                            }
+                            EnvironmentRef::Object(index) => {
+                                Environment::Object(self.object(index, context)?)
+                            }
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                let function = OrdinaryFunction::new(
+                    code,
+                    EnvironmentStack::from_environments(environments),
+                    None,
+                    context.realm().clone(),
+                );
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, function)
+                    .upcast()
+            }
+            ObjectData::BoundFunction { target, this, args } => {
+                let target = self.object(*target, context)?;
+                let this = self.value(this, context)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.value(arg, context))
+                    .collect::<Option<Vec<_>>>()?;
+                BoundFunction::create(target, this, args, context).ok()?
+            }
+            ObjectData::Error(kind) => JsObject::from_proto_and_data_with_shared_shape(
+                &root_shape,
+                None,
+                Error::new(*kind),
+            )
+            .upcast(),
+            ObjectData::Boolean(boolean) => {
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, *boolean)
+                    .upcast()
+            }
+            ObjectData::Number(number) => {
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, *number).upcast()
+            }
+            ObjectData::String(string) => {
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, string.clone())
+                    .upcast()
+            }
+            ObjectData::Date(time) => {
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, Date::new(*time))
+                    .upcast()
+            }
+            ObjectData::RegExp(source, flags) => {
+                let regexp = RegExp::compile_native_regexp(
+                    &source.clone().into(),
+                    &flags.clone().into(),
+                    context,
+                )
+                .ok()?;
+                JsObject::from_proto_and_data_with_shared_shape(&root_shape, None, regexp).upcast()
+            }
+            ObjectData::Map(_) => JsObject::from_proto_and_data_with_shared_shape(
+                &root_shape,
+                None,
+                OrderedMap::<JsValue>::new(),
+            )
+            .upcast(),
+            ObjectData::Set(_) => JsObject::from_proto_and_data_with_shared_shape(
+                &root_shape,
+                None,
+                OrderedSet::new(),
+            )
+            .upcast(),
+        };
+
+        self.objects[local as usize] = State::Created(object.clone());
+        Some(object)
+    }
+
+    /// Returns the environment at `index`, creating it if needed.
+    ///
+    /// The bindings of the environment are only set once all objects exist.
+    fn environment(
+        &mut self,
+        index: u32,
+        context: &mut Context,
+    ) -> Option<Gc<DeclarativeEnvironment>> {
+        match self.environments.get(index as usize)? {
+            State::Created(env) => return Some(env.clone()),
+            State::InProgress => return None,
+            State::Pending => {}
+        }
+        self.environments[index as usize] = State::InProgress;
+
+        let record = &self.records.environments[index as usize];
+        let bindings = u32::try_from(record.bindings.len()).ok()?;
+        let kind = if let Some(function) = &record.function {
+            let scope = self.code.as_ref()?.scope(function.scope)?.clone();
+            let this = match &function.this {
+                ThisRecord::Lexical => ThisBindingStatus::Lexical,
+                ThisRecord::Uninitialized => ThisBindingStatus::Uninitialized,
+                ThisRecord::Initialized(this) => {
+                    ThisBindingStatus::Initialized(self.value(this, context)?)
+                }
+            };
+            let function_object = self.object(function.function_object, context)?;
+            let new_target = self.optional_object(function.new_target, context)?;
+            DeclarativeEnvironmentKind::Function(FunctionEnvironment::new(
+                bindings,
+                FunctionSlots::new(this, function_object, new_target),
+                scope,
+            ))
+        } else {
+            DeclarativeEnvironmentKind::Lexical(LexicalEnvironment::new(bindings))
+        };
+
+        let env = Gc::new(DeclarativeEnvironment::new(
+            kind,
+            record.poisoned,
+            record.with,
+        ));
+        self.environments[index as usize] = State::Created(env.clone());
+        Some(env)
+    }
+}
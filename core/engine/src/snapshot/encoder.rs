@@ -0,0 +1,652 @@
+//! Serialization of a realm into a snapshot.
+
+use boa_ast::scope::ScopeBinding;
+use boa_gc::Gc;
+use rustc_hash::FxHashMap;
+
+use super::{
+    ObjectKind, SnapshotBase, SnapshotError, function_name, own_properties, property_path,
+    same_property, tag, write_snapshot,
+};
+use crate::{
+    Context, JsObject, JsString, JsSymbol, JsValue, JsVariant,
+    builtins::{
+        Array, Date, OrdinaryObject,
+        error::{Error, ErrorKind},
+        function::{BoundFunction, ClassFieldDefinition, OrdinaryFunction},
+        map::ordered_map::OrderedMap,
+        regexp::RegExp,
+        set::ordered_set::OrderedSet,
+        symbol::registered_key,
+    },
+    environments::{DeclarativeEnvironment, DeclarativeEnvironmentKind, Environment},
+    heap_snapshot::constructor_name,
+    property::{PropertyDescriptor, PropertyKey},
+    spanned_source_text::SourceText,
+    vm::{
+        ActiveRunnable, CodeBlock,
+        code_cache::{Encoder, Writer},
+        source_info::SourcePath,
+    },
+};
+
+/// Serializes the current realm of `context`, storing only the changes made to the objects of
+/// `base`.
+pub(crate) fn encode(context: &Context, base: &SnapshotBase) -> Result<Vec<u8>, SnapshotError> {
+    let mut encoder = SnapshotEncoder::new(base);
+    let mut section = Writer::default();
+
+    let diffs = base
+        .objects
+        .iter()
+        .enumerate()
+        .filter_map(|(index, object)| {
+            let state = object.state.as_ref()?;
+            encoder.diff(index as u32, &object.object, &object.path, state)
+        })
+        .collect::<Vec<_>>();
+
+    let realm = context.realm();
+    let mut bindings = realm.scope().bindings();
+    bindings.sort_by_key(ScopeBinding::index);
+    let mut globals = Writer::default();
+    let new_bindings = bindings
+        .iter()
+        .filter(|binding| binding.index() >= base.global_bindings)
+        .collect::<Vec<_>>();
+    globals.seq(new_bindings.into_iter(), |writer, binding| {
+        writer.string(binding.name());
+        writer.u8(binding.flags());
+    });
+    let values = realm.environment().kind().binding_values();
+    globals.len(values.len());
+    for (index, value) in values.iter().enumerate() {
+        globals.option(value.as_ref(), |writer, value| {
+            encoder.value(writer, value, || {
+                bindings
+                    .iter()
+                    .find(|binding| binding.index() as usize == index)
+                    .map_or_else(
+                        || format!("<global binding {index}>"),
+                        |binding| binding.name().to_std_string_escaped(),
+                    )
+            });
+        });
+    }
+
+    let mut objects = Vec::new();
+    let mut environments = Vec::new();
+    loop {
+        if let Some((object, path)) = encoder.objects.get(objects.len()).cloned() {
+            objects.push(encoder.object(context, &object, &path)?);
+        } else if let Some((env, path)) = encoder.environments.get(environments.len()).cloned() {
+            environments.push(encoder.environment(&env, &path));
+        } else {
+            break;
+        }
+    }
+
+    section.seq(encoder.symbols.iter(), |writer, symbol| {
+        if let Some(tag) = symbol.well_known_tag() {
+            writer.u8(0);
+            writer.u8(tag);
+        } else if let Some(key) = registered_key(symbol) {
+            writer.u8(1);
+            writer.string(&key);
+        } else {
+            writer.u8(2);
+            writer.option(symbol.description().as_ref(), Writer::string);
+        }
+    });
+    section.seq(encoder.sources.iter(), |writer, (text, path)| {
+        writer.option(
+            (!text.is_empty()).then(|| text.code_units()),
+            |writer, units| {
+                writer.len(units.len());
+                for unit in units {
+                    writer.u16(*unit);
+                }
+            },
+        );
+        match path {
+            SourcePath::None => writer.u8(0),
+            SourcePath::Eval => writer.u8(1),
+            SourcePath::Json => writer.u8(2),
+            SourcePath::Path(path) => {
+                writer.u8(3);
+                writer.str(&path.to_string_lossy());
+            }
+        }
+    });
+    section.seq(
+        encoder.code_sources.iter(),
+        |writer, (first_function, source)| {
+            writer.u32(*first_function);
+            writer.u32(*source);
+        },
+    );
+    section.raw(&globals.into_bytes());
+    section.seq(diffs.iter(), |writer, diff| writer.raw(diff));
+    section.seq(objects.iter(), |writer, object| writer.raw(object));
+    section.seq(environments.iter(), |writer, env| writer.raw(env));
+
+    let payload = if encoder.code.function_count() == 0 {
+        section.bool(false);
+        section.into_bytes()
+    } else {
+        section.bool(true);
+        encoder.code.finish(section)
+    };
+
+    Ok(write_snapshot(base.fingerprint, &payload))
+}
+
+/// Collects the objects, environments and code reachable from the realm.
+///
+/// Objects and environments are numbered when they are first found, and serialized afterwards
+/// in the same order.
+struct SnapshotEncoder<'a> {
+    base: &'a SnapshotBase,
+
+    /// The objects that are not part of the base, with their paths. The index of an object is
+    /// offset by the number of objects of the base.
+    objects: Vec<(JsObject, String)>,
+    object_indices: FxHashMap<usize, u32>,
+
+    environments: Vec<(Gc<DeclarativeEnvironment>, String)>,
+    environment_indices: FxHashMap<usize, u32>,
+
+    symbols: Vec<JsSymbol>,
+    symbol_indices: FxHashMap<JsSymbol, u32>,
+
+    /// The source texts of the code, with their paths.
+    sources: Vec<(SourceText, SourcePath)>,
+    /// The first function of each range of the function table, with the index of its source.
+    code_sources: Vec<(u32, u32)>,
+    code: Encoder,
+}
+
+impl<'a> SnapshotEncoder<'a> {
+    fn new(base: &'a SnapshotBase) -> Self {
+        Self {
+            base,
+            objects: Vec::new(),
+            object_indices: FxHashMap::default(),
+            environments: Vec::new(),
+            environment_indices: FxHashMap::default(),
+            symbols: Vec::new(),
+            symbol_indices: FxHashMap::default(),
+            sources: Vec::new(),
+            code_sources: Vec::new(),
+            code: Encoder::default(),
+        }
+    }
+
+    /// Returns the index of an object, numbering it if it's found for the first time.
+    fn object_ref(&mut self, object: &JsObject, path: impl FnOnce() -> String) -> u32 {
+        if let Some(index) = self.base.index(object) {
+            return index;
+        }
+        let address = Gc::addr(object.inner());
+        if let Some(index) = self.object_indices.get(&address) {
+            return *index;
+        }
+        let index = self.base.len() + self.objects.len() as u32;
+        self.objects.push((object.clone(), path()));
+        self.object_indices.insert(address, index);
+        index
+    }
+
+    fn symbol_ref(&mut self, symbol: &JsSymbol) -> u32 {
+        if let Some(index) = self.symbol_indices.get(symbol) {
+            return *index;
+        }
+        let index = self.symbols.len() as u32;
+        self.symbols.push(symbol.clone());
+        self.symbol_indices.insert(symbol.clone(), index);
+        index
+    }
+
+    fn value(&mut self, writer: &mut Writer, value: &JsValue, path: impl FnOnce() -> String) {
+        match value.variant() {
+            JsVariant::Undefined => writer.u8(tag::UNDEFINED),
+            JsVariant::Null => writer.u8(tag::NULL),
+            JsVariant::Boolean(false) => writer.u8(tag::FALSE),
+            JsVariant::Boolean(true) => writer.u8(tag::TRUE),
+            JsVariant::Integer32(integer) => {
+                writer.u8(tag::INTEGER);
+                writer.u32(integer as u32);
+            }
+            JsVariant::Float64(number) => {
+                writer.u8(tag::NUMBER);
+                writer.u64(number.to_bits());
+            }
+            JsVariant::String(string) => {
+                writer.u8(tag::STRING);
+                writer.string(&string);
+            }
+            JsVariant::BigInt(bigint) => {
+                writer.u8(tag::BIGINT);
+                writer.str(&bigint.to_string_radix(16));
+            }
+            JsVariant::Symbol(symbol) => {
+                writer.u8(tag::SYMBOL);
+                let index = self.symbol_ref(&symbol);
+                writer.u32(index);
+            }
+            JsVariant::Object(object) => {
+                writer.u8(tag::OBJECT);
+                let index = self.object_ref(&object, path);
+                writer.u32(index);
+            }
+        }
+    }
+
+    fn key(&mut self, writer: &mut Writer, key: &PropertyKey) {
+        match key {
+            PropertyKey::String(name) => {
+                writer.u8(0);
+                writer.string(name);
+            }
+            PropertyKey::Symbol(symbol) => {
+                writer.u8(1);
+                let index = self.symbol_ref(symbol);
+                writer.u32(index);
+            }
+            PropertyKey::Index(index) => {
+                writer.u8(2);
+                writer.u32(index.get());
+            }
+        }
+    }
+
+    fn property(
+        &mut self,
+        writer: &mut Writer,
+        path: &str,
+        key: &PropertyKey,
+        property: &PropertyDescriptor,
+    ) {
+        self.key(writer, key);
+
+        let mut flags = 0;
+        for (i, flag) in [
+            property.enumerable(),
+            property.configurable(),
+            property.writable(),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if let Some(flag) = flag {
+                flags |= (0b10 | u8::from(flag)) << (i * 2);
+            }
+        }
+        writer.u8(flags);
+
+        for (value, suffix) in [
+            (property.value(), ""),
+            (property.get(), ".<get>"),
+            (property.set(), ".<set>"),
+        ] {
+            writer.option(value, |writer, value| {
+                self.value(writer, value, || {
+                    format!("{}{suffix}", property_path(path, key))
+                });
+            });
+        }
+    }
+
+    fn prototype(&mut self, writer: &mut Writer, path: &str, prototype: Option<&JsObject>) {
+        writer.option(prototype, |writer, prototype| {
+            let index = self.object_ref(prototype, || format!("{path}.__proto__"));
+            writer.u32(index);
+        });
+    }
+
+    /// Serializes the changes made to an object of the base since the base was marked.
+    ///
+    /// Returns `None` if the object didn't change.
+    fn diff(
+        &mut self,
+        index: u32,
+        object: &JsObject,
+        path: &str,
+        state: &super::ObjectState,
+    ) -> Option<Vec<u8>> {
+        let properties = own_properties(object);
+        let old = state
+            .properties
+            .iter()
+            .map(|(key, property)| (key, property))
+            .collect::<FxHashMap<_, _>>();
+
+        let removed = state
+            .properties
+            .iter()
+            .filter(|(key, _)| !properties.iter().any(|(current, _)| current == key))
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        let changed = properties
+            .iter()
+            .filter(|(key, property)| old.get(key).is_none_or(|old| !same_property(old, property)))
+            .collect::<Vec<_>>();
+
+        let prototype = object.prototype();
+        let prototype_changed = match (&prototype, &state.prototype) {
+            (Some(current), Some(old)) => !JsObject::equals(current, old),
+            (None, None) => false,
+            _ => true,
+        };
+        let extensible = object.borrow().extensible;
+
+        if removed.is_empty()
+            && changed.is_empty()
+            && !prototype_changed
+            && extensible == state.extensible
+        {
+            return None;
+        }
+
+        let mut writer = Writer::default();
+        writer.u32(index);
+        writer.option(prototype_changed.then_some(()), |writer, ()| {
+            self.prototype(writer, path, prototype.as_ref());
+        });
+        writer.bool(extensible);
+        writer.seq(removed.into_iter(), |writer, key| self.key(writer, key));
+        writer.seq(changed.into_iter(), |writer, (key, property)| {
+            self.property(writer, path, key, property);
+        });
+        Some(writer.into_bytes())
+    }
+
+    /// Serializes an object that is not part of the base.
+    fn object(
+        &mut self,
+        context: &Context,
+        object: &JsObject,
+        path: &str,
+    ) -> Result<Vec<u8>, SnapshotError> {
+        let unsupported = |kind: String| SnapshotError::UnsupportedObject {
+            kind,
+            path: path.to_string(),
+        };
+
+        if object.borrow().has_private_elements() {
+            return Err(unsupported("an object with private elements".to_string()));
+        }
+
+        let mut writer = Writer::default();
+
+        if let Some(function) = object.downcast_ref::<OrdinaryFunction>() {
+            let name = || function.code.name().to_std_string_escaped();
+            if function.realm() != context.realm() {
+                return Err(unsupported(format!(
+                    "the function `{}` of another realm",
+                    name()
+                )));
+            }
+            if matches!(function.script_or_module, Some(ActiveRunnable::Module(_))) {
+                return Err(unsupported(format!(
+                    "the function `{}` of a module",
+                    name()
+                )));
+            }
+            if function.environments.has_private_names()
+                || !function.get_private_methods().is_empty()
+                || function
+                    .get_fields()
+                    .iter()
+                    .any(|field| matches!(field, ClassFieldDefinition::Private(..)))
+            {
+                return Err(unsupported(format!(
+                    "the function `{}` with private names",
+                    name()
+                )));
+            }
+
+            writer.u8(ObjectKind::Function as u8);
+            let code = CodeBlock::compiled(&function.code);
+            let code = self.code_ref(&code);
+            writer.u32(code);
+
+            let environments = function.environments.environments();
+            writer.len(environments.len());
+            for env in &environments {
+                self.environment_ref(&mut writer, env, path)?;
+            }
+
+            writer.option(function.get_home_object(), |writer, home_object| {
+                let index = self.object_ref(home_object, || format!("{path}.<home object>"));
+                writer.u32(index);
+            });
+
+            let fields = function.get_fields();
+            writer.len(fields.len());
+            for field in fields {
+                if let ClassFieldDefinition::Public(key, initializer, name) = field {
+                    self.key(&mut writer, key);
+                    let index = self.object_ref(initializer, || {
+                        format!("{}.<initializer>", property_path(path, key))
+                    });
+                    writer.u32(index);
+                    writer.option(name.as_ref(), |writer, name| self.key(writer, name));
+                }
+            }
+        } else if let Some(function) = object.downcast_ref::<BoundFunction>() {
+            writer.u8(ObjectKind::BoundFunction as u8);
+            let target = self.object_ref(function.target_function(), || {
+                format!("{path}.<bound target>")
+            });
+            writer.u32(target);
+            self.value(&mut writer, function.this(), || {
+                format!("{path}.<bound this>")
+            });
+            writer.len(function.args().len());
+            for (i, arg) in function.args().iter().enumerate() {
+                self.value(&mut writer, arg, || {
+                    format!("{path}.<bound arguments>[{i}]")
+                });
+            }
+        } else if object.is::<Array>() {
+            writer.u8(ObjectKind::Array as u8);
+        } else if object.is::<OrdinaryObject>() {
+            writer.u8(ObjectKind::Ordinary as u8);
+        } else if let Some(error) = object.downcast_ref::<Error>() {
+            writer.u8(ObjectKind::Error as u8);
+            writer.u8(match error.tag {
+                ErrorKind::Aggregate => 0,
+                ErrorKind::Error => 1,
+                ErrorKind::Eval => 2,
+                ErrorKind::Type => 3,
+                ErrorKind::Range => 4,
+                ErrorKind::Reference => 5,
+                ErrorKind::Syntax => 6,
+                ErrorKind::Uri => 7,
+            });
+        } else if let Some(boolean) = object.downcast_ref::<bool>() {
+            writer.u8(ObjectKind::Boolean as u8);
+            writer.bool(*boolean);
+        } else if let Some(number) = object.downcast_ref::<f64>() {
+            writer.u8(ObjectKind::Number as u8);
+            writer.u64(number.to_bits());
+        } else if let Some(string) = object.downcast_ref::<JsString>() {
+            writer.u8(ObjectKind::String as u8);
+            writer.string(&string);
+        } else if let Some(date) = object.downcast_ref::<Date>() {
+            writer.u8(ObjectKind::Date as u8);
+            writer.u64(date.time_value().to_bits());
+        } else if let Some(regexp) = object.downcast_ref::<RegExp>() {
+            writer.u8(ObjectKind::RegExp as u8);
+            writer.string(regexp.original_source());
+            writer.string(regexp.original_flags());
+        } else if let Some(map) = object.downcast_ref::<OrderedMap<JsValue>>() {
+            writer.u8(ObjectKind::Map as u8);
+            let entries = map
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>();
+            drop(map);
+            writer.len(entries.len());
+            for (i, (key, value)) in entries.iter().enumerate() {
+                self.value(&mut writer, key, || format!("{path}.<entries>[{i}].key"));
+                self.value(&mut writer, value, || {
+                    format!("{path}.<entries>[{i}].value")
+                });
+            }
+        } else if let Some(set) = object.downcast_ref::<OrderedSet>() {
+            writer.u8(ObjectKind::Set as u8);
+            let values = set.iter().cloned().collect::<Vec<_>>();
+            drop(set);
+            writer.len(values.len());
+            for (i, value) in values.iter().enumerate() {
+                self.value(&mut writer, value, || format!("{path}.<entries>[{i}]"));
+            }
+        } else if object.is_callable() {
+            return Err(unsupported(format!(
+                "the native function `{}`",
+                function_name(object).to_std_string_escaped()
+            )));
+        } else {
+            let name = constructor_name(object.prototype()).unwrap_or_else(|| "Object".into());
+            return Err(unsupported(format!("a native `{name}` object")));
+        }
+
+        let prototype = object.prototype();
+        self.prototype(&mut writer, path, prototype.as_ref());
+        writer.bool(object.borrow().extensible);
+
+        let properties = own_properties(object);
+        writer.seq(properties.iter(), |writer, (key, property)| {
+            self.property(writer, path, key, property);
+        });
+
+        Ok(writer.into_bytes())
+    }
+
+    /// Adds a code block to the function table, returning its index.
+    fn code_ref(&mut self, code: &CodeBlock) -> u32 {
+        let first_function = self.code.function_count();
+        let index = self.code.function(code);
+        if self.code.function_count() == first_function {
+            return index;
+        }
+
+        let source_info = code.source_info();
+        let text = source_info.text_spanned().source_text();
+        let path = source_info.map().path();
+        let source = if let Some(source) = self
+            .sources
+            .iter()
+            .position(|(other, other_path)| other.addr() == text.addr() && other_path == path)
+        {
+            source
+        } else {
+            self.sources.push((text, path.clone()));
+            self.sources.len() - 1
+        };
+
+        if self
+            .code_sources
+            .last()
+            .is_none_or(|(_, last)| *last as usize != source)
+        {
+            self.code_sources.push((first_function, source as u32));
+        }
+        index
+    }
+
+    fn environment_ref(
+        &mut self,
+        writer: &mut Writer,
+        env: &Environment,
+        path: &str,
+    ) -> Result<(), SnapshotError> {
+        match env {
+            Environment::Declarative(env) => {
+                if matches!(
+                    env.kind(),
+                    DeclarativeEnvironmentKind::Module(_) | DeclarativeEnvironmentKind::Global(_)
+                ) {
+                    return Err(SnapshotError::UnsupportedObject {
+                        kind: "a closure over a module environment".to_string(),
+                        path: path.to_string(),
+                    });
+                }
+
+                let address = Gc::addr(env);
+                let index = if let Some(index) = self.environment_indices.get(&address) {
+                    *index
+                } else {
+                    let index = self.environments.len() as u32;
+                    self.environments
+                        .push((env.clone(), format!("{path}.<closure>")));
+                    self.environment_indices.insert(address, index);
+                    index
+                };
+                writer.u8(0);
+                writer.u32(index);
+            }
+            Environment::Object(object) => {
+                writer.u8(1);
+                let index = self.object_ref(object, || format!("{path}.<with>"));
+                writer.u32(index);
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes a declarative environment captured by a function.
+    fn environment(&mut self, env: &Gc<DeclarativeEnvironment>, path: &str) -> Vec<u8> {
+        let mut writer = Writer::default();
+        let kind = env.kind();
+
+        let function = kind.as_function();
+        writer.u8(u8::from(function.is_some()));
+        writer.bool(env.poisoned());
+        writer.bool(env.with());
+
+        let names = function.map(|function| function.compile().bindings());
+        let values = kind.binding_values();
+        writer.len(values.len());
+        for (i, value) in values.iter().enumerate() {
+            writer.option(value.as_ref(), |writer, value| {
+                self.value(writer, value, || {
+                    names
+                        .iter()
+                        .flatten()
+                        .find(|binding| binding.index() as usize == i)
+                        .map_or_else(
+                            || format!("{path}[{i}]"),
+                            |binding| format!("{path}.{}", binding.name().to_std_string_escaped()),
+                        )
+                });
+            });
+        }
+
+        if let Some(function) = function {
+            let scope = self.code.scope(function.compile());
+            writer.u32(scope);
+
+            let slots = function.slots();
+            match slots.this_binding_status() {
+                crate::environments::ThisBindingStatus::Lexical => writer.u8(0),
+                crate::environments::ThisBindingStatus::Uninitialized => writer.u8(1),
+                crate::environments::ThisBindingStatus::Initialized(this) => {
+                    writer.u8(2);
+                    self.value(&mut writer, &this, || format!("{path}.<this>"));
+                }
+            }
+            let function_object =
+                self.object_ref(slots.function_object(), || format!("{path}.<function>"));
+            writer.u32(function_object);
+            writer.option(slots.new_target(), |writer, new_target| {
+                let index = self.object_ref(new_target, || format!("{path}.<new target>"));
+                writer.u32(index);
+            });
+        }
+
+        writer.into_bytes()
+    }
+}
@@ -0,0 +1,435 @@
+//! Snapshots of an initialized [`Context`], to speed up its creation.
+//!
+//! Creating a context with all its intrinsics and running the scripts that prepare its global
+//! object can take a significant amount of time. A snapshot stores the state of the realm after
+//! those scripts run, so that a new context can be restored from it without running them again.
+//!
+//! This is a partial implementation of the startup snapshots of V8, which serialize the whole
+//! heap. A snapshot of Boa doesn't contain the intrinsics nor the objects of the host: restoring
+//! it still creates a context with all of them, and only applies the difference between the
+//! realm when the snapshot was taken and its *base*, which is described below. It saves the time
+//! of running the scripts, but not the time of creating a context.
+//!
+//! # Usage
+//!
+//! A snapshot is taken in three steps:
+//!
+//! 1. Create the context and register the native extensions of the host, like the ones of
+//!    `boa_runtime`.
+//! 2. Call [`Context::mark_snapshot_base`]. Every object reachable from the global object at this
+//!    point is part of the *base* of the snapshot.
+//! 3. Run the scripts that prepare the realm, then call [`Context::snapshot`].
+//!
+//! To restore it, create a context and register the native extensions in the same way, then call
+//! [`Context::restore_snapshot`] before running any code.
+//!
+//! ```
+//! # use boa_engine::{Context, Source, js_string};
+//! let mut context = Context::default();
+//! context.mark_snapshot_base();
+//! context
+//!     .eval(Source::from_bytes("function greet(name) { return `Hello, ${name}!`; }"))
+//!     .unwrap();
+//! let snapshot = context.snapshot().unwrap();
+//!
+//! let mut context = Context::default();
+//! context.restore_snapshot(&snapshot).unwrap();
+//! let greeting = context.eval(Source::from_bytes("greet('Boa')")).unwrap();
+//! assert_eq!(greeting.as_string(), Some(js_string!("Hello, Boa!")));
+//! ```
+//!
+//! # What is captured
+//!
+//! The objects of the base are not serialized. Instead, they are identified by the path used to
+//! reach them from the global object (like `globalThis.Array.prototype`) and found again in the
+//! restored context, which is why both contexts must be set up in the same way. Only the changes
+//! to their properties, prototype and extensibility are stored.
+//!
+//! All the other objects reachable from the global object and from the global lexical bindings
+//! are serialized, together with the closures and compiled code of their functions. This
+//! includes ordinary objects, arrays, functions and classes defined in JavaScript, bound
+//! functions, errors, primitive wrappers, dates, regular expressions, maps and sets.
+//!
+//! Objects whose state is owned by the host or by running code cannot be serialized, like native
+//! functions created after the base was marked, promises, generators or buffers. Taking a
+//! snapshot that contains them fails with [`SnapshotError::UnsupportedObject`], naming the path
+//! of the object.
+//!
+//! Pending jobs, loaded modules and the state of the internal data of base objects are not part
+//! of a snapshot.
+//!
+//! # Performance
+//!
+//! The intrinsics and shapes of the base are built again instead of being serialized, since
+//! building them is cheap compared to running the scripts of a prelude: their native functions
+//! and internal slots would have to be resolved one by one when restoring them anyway. The
+//! `startup` bench of `boa_benches` uses the v8 benchmark scripts as preludes. Creating a
+//! context with the `console` of `boa_runtime` takes about 0.5 ms, evaluating a prelude after
+//! that takes between 3 and 65 ms, and restoring its snapshot instead takes between 1.7 and
+//! 11 ms, which is 2 to 6 times faster.
+
+use std::fmt::Write as _;
+
+use boa_gc::Gc;
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use crate::{
+    Context, JsObject, JsString, JsValue,
+    property::{PropertyDescriptor, PropertyKey},
+    vm::code_cache::{FNV_OFFSET_BASIS, Reader, Writer, fnv1a},
+};
+
+mod decoder;
+mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use decoder::restore;
+pub(crate) use encoder::encode;
+
+/// The version of the snapshot format.
+///
+/// This must be bumped whenever the layout of a snapshot changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// The magic bytes at the start of every snapshot.
+const MAGIC: &[u8; 4] = b"BOAS";
+
+/// The error returned when a [`Context`] cannot be snapshotted or restored.
+#[derive(Debug, Clone, Error)]
+pub enum SnapshotError {
+    /// The snapshot base of the context was not marked.
+    #[error("the snapshot base was not marked, see `Context::mark_snapshot_base`")]
+    MissingBase,
+
+    /// An object reachable from the global object cannot be serialized.
+    #[error("cannot snapshot {kind} at `{path}`")]
+    UnsupportedObject {
+        /// A description of the object, like ``the native function `print` ``.
+        kind: String,
+        /// The path used to reach the object from the global object.
+        path: String,
+    },
+
+    /// The context was not set up in the same way as the context the snapshot was taken from.
+    #[error("the base of the snapshot doesn't match the context")]
+    BaseMismatch,
+
+    /// The snapshot is corrupted, or was produced by a different version of Boa.
+    #[error("the snapshot is invalid or was produced by an incompatible version")]
+    InvalidSnapshot,
+}
+
+/// An object of the base, with its state when the base was marked.
+#[derive(Debug)]
+struct BaseObject {
+    object: JsObject,
+    path: String,
+    state: Option<ObjectState>,
+}
+
+/// The own properties, prototype and extensibility of an object.
+#[derive(Debug)]
+struct ObjectState {
+    prototype: Option<JsObject>,
+    extensible: bool,
+    properties: Vec<(PropertyKey, PropertyDescriptor)>,
+}
+
+/// The objects that exist when the code of a snapshot starts running.
+///
+/// The objects are found by walking the properties and prototypes of every object, starting from
+/// the global object and the intrinsics that are not reachable from it. The walk is
+/// deterministic, so two contexts set up in the same way find the same objects in the same order.
+#[derive(Debug)]
+pub(crate) struct SnapshotBase {
+    objects: Vec<BaseObject>,
+    /// Maps the address of each object to its index in `objects`.
+    indices: FxHashMap<usize, u32>,
+    /// The number of bindings of the global scope.
+    global_bindings: u32,
+    /// A hash of the paths of the objects, to check that two bases match.
+    fingerprint: u64,
+}
+
+impl SnapshotBase {
+    /// Walks the objects of the current realm of `context`.
+    ///
+    /// If `record_state` is `true`, the current state of every object is stored, to find the
+    /// changes made to them when the snapshot is taken.
+    pub(crate) fn new(context: &Context, record_state: bool) -> Self {
+        let mut base = Self {
+            objects: Vec::new(),
+            indices: FxHashMap::default(),
+            global_bindings: context.realm().scope().num_bindings(),
+            fingerprint: FNV_OFFSET_BASIS,
+        };
+
+        for (path, object) in roots(context) {
+            base.push(object, path.to_string());
+        }
+
+        let mut next = 0;
+        while let Some(current) = base.objects.get(next) {
+            let object = current.object.clone();
+            let path = current.path.clone();
+            next += 1;
+
+            let properties = own_properties(&object);
+            for (key, property) in &properties {
+                for (value, suffix) in [
+                    (property.value(), ""),
+                    (property.get(), ".<get>"),
+                    (property.set(), ".<set>"),
+                ] {
+                    if let Some(value) = value.and_then(JsValue::as_object) {
+                        base.push(value, format!("{}{suffix}", property_path(&path, key)));
+                    }
+                }
+            }
+            let prototype = object.prototype();
+            if let Some(prototype) = prototype.clone() {
+                base.push(prototype, format!("{path}.__proto__"));
+            }
+
+            if record_state {
+                base.objects[next - 1].state = Some(ObjectState {
+                    prototype,
+                    extensible: object.borrow().extensible,
+                    properties,
+                });
+            }
+        }
+
+        base.fingerprint = fnv1a(base.fingerprint, &base.global_bindings.to_le_bytes());
+        base
+    }
+
+    fn push(&mut self, object: JsObject, path: String) {
+        let address = Gc::addr(object.inner());
+        if self.indices.contains_key(&address) {
+            return;
+        }
+        self.fingerprint = fnv1a(self.fingerprint, path.as_bytes());
+        self.indices.insert(address, self.objects.len() as u32);
+        self.objects.push(BaseObject {
+            object,
+            path,
+            state: None,
+        });
+    }
+
+    /// Returns the index of `object` if it's part of the base.
+    fn index(&self, object: &JsObject) -> Option<u32> {
+        self.indices.get(&Gc::addr(object.inner())).copied()
+    }
+
+    fn len(&self) -> u32 {
+        self.objects.len() as u32
+    }
+}
+
+/// Returns the objects the walk of a [`SnapshotBase`] starts from.
+///
+/// Apart from the global object, these are the intrinsics that can only be reached through the
+/// internal slots of other objects, or by evaluating code.
+fn roots(context: &Context) -> Vec<(&'static str, JsObject)> {
+    let intrinsics = context.intrinsics();
+    let constructors = intrinsics.constructors();
+    let objects = intrinsics.objects();
+    let iterators = objects.iterator_prototypes();
+
+    #[cfg_attr(not(feature = "intl"), allow(unused_mut))]
+    let mut roots = vec![
+        ("globalThis", context.global_object()),
+        ("%ThrowTypeError%", objects.throw_type_error().into()),
+        (
+            "%GeneratorFunction%",
+            constructors.generator_function().constructor(),
+        ),
+        (
+            "%AsyncFunction%",
+            constructors.async_function().constructor(),
+        ),
+        (
+            "%AsyncGeneratorFunction%",
+            constructors.async_generator_function().constructor(),
+        ),
+        ("%GeneratorPrototype%", objects.generator()),
+        ("%AsyncGeneratorPrototype%", objects.async_generator()),
+        ("%TypedArray%", constructors.typed_array().constructor()),
+        ("%AsyncIteratorPrototype%", iterators.async_iterator()),
+        (
+            "%AsyncFromSyncIteratorPrototype%",
+            iterators.async_from_sync_iterator(),
+        ),
+        ("%ArrayIteratorPrototype%", iterators.array()),
+        ("%SetIteratorPrototype%", iterators.set()),
+        ("%MapIteratorPrototype%", iterators.map()),
+        ("%StringIteratorPrototype%", iterators.string()),
+        ("%RegExpStringIteratorPrototype%", iterators.regexp_string()),
+        ("%IteratorHelperPrototype%", iterators.iterator_helper()),
+        (
+            "%WrapForValidIteratorPrototype%",
+            iterators.wrap_for_valid_iterator(),
+        ),
+    ];
+    #[cfg(feature = "intl")]
+    roots.push(("%SegmentIteratorPrototype%", iterators.segment()));
+    roots
+}
+
+/// Returns the own properties of an object, with the indexed properties first in ascending
+/// order, followed by the other properties in insertion order.
+fn own_properties(object: &JsObject) -> Vec<(PropertyKey, PropertyDescriptor)> {
+    let borrowed = object.borrow();
+    let properties = borrowed.properties();
+
+    let mut indices = properties.index_property_keys().collect::<Vec<_>>();
+    indices.sort_unstable();
+
+    indices
+        .into_iter()
+        .map(PropertyKey::from)
+        .chain(properties.shape.keys())
+        .filter_map(|key| {
+            let property = properties.get(&key)?;
+            Some((key, property))
+        })
+        .collect()
+}
+
+/// Returns the path of the property `key` of the object at `path`.
+fn property_path(path: &str, key: &PropertyKey) -> String {
+    let mut result = path.to_string();
+    match key {
+        PropertyKey::String(name) => {
+            let _ = write!(result, ".{}", name.to_std_string_escaped());
+        }
+        PropertyKey::Symbol(symbol) => {
+            let _ = write!(
+                result,
+                "[{}]",
+                symbol.descriptive_string().to_std_string_escaped()
+            );
+        }
+        PropertyKey::Index(index) => {
+            let _ = write!(result, "[{}]", index.get());
+        }
+    }
+    result
+}
+
+/// Returns `true` if two property descriptors are the same, comparing values with `SameValue`.
+fn same_property(a: &PropertyDescriptor, b: &PropertyDescriptor) -> bool {
+    let same_value = |a: Option<&JsValue>, b: Option<&JsValue>| match (a, b) {
+        (Some(a), Some(b)) => JsValue::same_value(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+
+    a.enumerable() == b.enumerable()
+        && a.configurable() == b.configurable()
+        && a.writable() == b.writable()
+        && same_value(a.value(), b.value())
+        && same_value(a.get(), b.get())
+        && same_value(a.set(), b.set())
+}
+
+/// Prepends the header to the payload of a snapshot.
+fn write_snapshot(fingerprint: u64, payload: &[u8]) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.raw(MAGIC);
+    writer.u32(FORMAT_VERSION);
+    writer.str(env!("CARGO_PKG_VERSION"));
+    writer.u64(fingerprint);
+    writer.u64(fnv1a(FNV_OFFSET_BASIS, payload));
+    writer.raw(payload);
+    writer.into_bytes()
+}
+
+/// Checks the header of a snapshot, returning its payload.
+fn read_snapshot(bytes: &[u8], fingerprint: u64) -> Result<&[u8], SnapshotError> {
+    let mut reader = Reader::new(bytes);
+    let valid = reader.take(MAGIC.len()) == Some(MAGIC)
+        && reader.u32() == Some(FORMAT_VERSION)
+        && reader.str() == Some(env!("CARGO_PKG_VERSION"));
+    if !valid {
+        return Err(SnapshotError::InvalidSnapshot);
+    }
+
+    if reader.u64().ok_or(SnapshotError::InvalidSnapshot)? != fingerprint {
+        return Err(SnapshotError::BaseMismatch);
+    }
+
+    let checksum = reader.u64().ok_or(SnapshotError::InvalidSnapshot)?;
+    let payload = reader.rest();
+    if fnv1a(FNV_OFFSET_BASIS, payload) != checksum {
+        return Err(SnapshotError::InvalidSnapshot);
+    }
+    Ok(payload)
+}
+
+/// The tags of the serialized values.
+mod tag {
+    pub(super) const UNDEFINED: u8 = 0;
+    pub(super) const NULL: u8 = 1;
+    pub(super) const FALSE: u8 = 2;
+    pub(super) const TRUE: u8 = 3;
+    pub(super) const INTEGER: u8 = 4;
+    pub(super) const NUMBER: u8 = 5;
+    pub(super) const STRING: u8 = 6;
+    pub(super) const BIGINT: u8 = 7;
+    pub(super) const SYMBOL: u8 = 8;
+    pub(super) const OBJECT: u8 = 9;
+}
+
+/// The kinds of serialized objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum ObjectKind {
+    Ordinary = 0,
+    Array = 1,
+    Function = 2,
+    BoundFunction = 3,
+    Error = 4,
+    Boolean = 5,
+    Number = 6,
+    String = 7,
+    Date = 8,
+    RegExp = 9,
+    Map = 10,
+    Set = 11,
+}
+
+impl ObjectKind {
+    const fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => Self::Ordinary,
+            1 => Self::Array,
+            2 => Self::Function,
+            3 => Self::BoundFunction,
+            4 => Self::Error,
+            5 => Self::Boolean,
+            6 => Self::Number,
+            7 => Self::String,
+            8 => Self::Date,
+            9 => Self::RegExp,
+            10 => Self::Map,
+            11 => Self::Set,
+            _ => return None,
+        })
+    }
+}
+
+/// Returns the name of a function object, without running any code.
+fn function_name(object: &JsObject) -> JsString {
+    object
+        .borrow()
+        .properties()
+        .get(&crate::js_string!("name").into())
+        .and_then(|property| property.value()?.as_string())
+        .unwrap_or_default()
+}
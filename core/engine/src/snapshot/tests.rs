@@ -0,0 +1,220 @@
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::SnapshotError;
+use crate::{Context, JsValue, js_string};
+
+/// Runs `source` in a new context after marking its base, and returns the snapshot.
+fn snapshot(source: &str) -> Result<Vec<u8>, SnapshotError> {
+    let context = &mut Context::default();
+    context.mark_snapshot_base();
+    context.eval(Source::from_bytes(source)).unwrap();
+    context.snapshot()
+}
+
+/// Restores `snapshot` in a new context and evaluates `source`.
+fn eval_restored(snapshot: &[u8], source: &str) -> JsValue {
+    let context = &mut Context::default();
+    context.restore_snapshot(snapshot).unwrap();
+    context.eval(Source::from_bytes(source)).unwrap()
+}
+
+#[test]
+fn functions_and_closures() {
+    let snapshot = snapshot(indoc! {r#"
+        function makeCounter(start) {
+            let count = start;
+            return { increment: () => ++count, get value() { return count; } };
+        }
+        var counter = makeCounter(10);
+        counter.increment();
+        const double = (x) => x * 2;
+        let bound = double.bind(null, 21);
+    "#})
+    .unwrap();
+
+    let result = eval_restored(
+        &snapshot,
+        "counter.increment(); [counter.value, bound(), makeCounter(1).increment()].join()",
+    );
+    assert_eq!(result.as_string(), Some(js_string!("12,42,2")));
+}
+
+#[test]
+fn classes() {
+    let snapshot = snapshot(indoc! {r#"
+        class Shape {
+            sides = 0;
+            describe() { return `${this.constructor.name} with ${this.sides} sides`; }
+        }
+        class Square extends Shape {
+            sides = 4;
+            describe() { return "a " + super.describe(); }
+        }
+    "#})
+    .unwrap();
+
+    let result = eval_restored(&snapshot, "new Square().describe()");
+    assert_eq!(
+        result.as_string(),
+        Some(js_string!("a Square with 4 sides"))
+    );
+}
+
+#[test]
+fn changes_to_base_objects() {
+    let snapshot = snapshot(indoc! {r#"
+        Array.prototype.sum = function () { return this.reduce((a, b) => a + b, 0); };
+        delete globalThis.escape;
+        Object.freeze(Math);
+        Object.setPrototypeOf(JSON, null);
+    "#})
+    .unwrap();
+
+    let result = eval_restored(
+        &snapshot,
+        indoc! {r#"
+            [
+                [1, 2, 3].sum(),
+                typeof escape,
+                Object.isFrozen(Math),
+                Object.getPrototypeOf(JSON),
+            ].join()
+        "#},
+    );
+    assert_eq!(result.as_string(), Some(js_string!("6,undefined,true,")));
+}
+
+#[test]
+fn global_lexical_bindings() {
+    let snapshot = snapshot(indoc! {r#"
+        let mutable = 1;
+        const constant = { name: "constant" };
+        function update() { mutable += 1; return mutable; }
+    "#})
+    .unwrap();
+
+    let context = &mut Context::default();
+    context.restore_snapshot(&snapshot).unwrap();
+    let result = context
+        .eval(Source::from_bytes(
+            "update(); `${mutable}:${constant.name}`",
+        ))
+        .unwrap();
+    assert_eq!(result.as_string(), Some(js_string!("2:constant")));
+
+    let error = context
+        .eval(Source::from_bytes("constant = 1"))
+        .unwrap_err();
+    assert!(error.to_string().contains("TypeError"), "{error}");
+}
+
+#[test]
+fn builtin_objects() {
+    let result = snapshot(indoc! {r#"
+        var data = {
+            map: new Map([["answer", 42], [Symbol.iterator, "iterator"]]),
+            set: new Set([1, 2n, "three"]),
+            date: new Date(0),
+            regexp: /b(o+)a/gi,
+            error: new RangeError("out of range"),
+            wrapper: Object(Symbol.for("registered")),
+            symbol: Symbol("local"),
+        };
+        data.self = data;
+    "#});
+    assert!(
+        matches!(result, Err(SnapshotError::UnsupportedObject { .. })),
+        "symbol wrappers are not supported"
+    );
+
+    let snapshot = snapshot(indoc! {r#"
+        var data = {
+            map: new Map([["answer", 42], [Symbol.iterator, "iterator"]]),
+            set: new Set([1, 2n, "three"]),
+            date: new Date(0),
+            regexp: /b(o+)a/gi,
+            error: new RangeError("out of range"),
+            symbol: Symbol.for("registered"),
+            local: Symbol("local"),
+            number: new Number(7),
+        };
+        data.self = data;
+    "#})
+    .unwrap();
+
+    let result = eval_restored(
+        &snapshot,
+        indoc! {r#"
+            [
+                data.map.get("answer"),
+                data.map.get(Symbol.iterator),
+                [...data.set].join(":"),
+                data.date.getTime(),
+                "xBOOa".replace(data.regexp, "$1"),
+                data.error instanceof RangeError,
+                data.error.message,
+                data.symbol === Symbol.for("registered"),
+                data.local.description,
+                data.number + 1,
+                data.self === data,
+            ].join()
+        "#},
+    );
+    assert_eq!(
+        result.as_string(),
+        Some(js_string!(
+            "42,iterator,1:2:three,0,xOO,true,out of range,true,local,8,true"
+        ))
+    );
+}
+
+#[test]
+fn unsupported_objects() {
+    let error = snapshot("var state = { pending: Promise.resolve(1) };").unwrap_err();
+    let SnapshotError::UnsupportedObject { kind, path } = &error else {
+        panic!("unexpected error: {error}");
+    };
+    assert_eq!(kind, "a native `Promise` object");
+    assert_eq!(path, "globalThis.state.pending");
+
+    let error = snapshot("class Secret { #value = 1; }").unwrap_err();
+    let SnapshotError::UnsupportedObject { kind, path } = &error else {
+        panic!("unexpected error: {error}");
+    };
+    assert_eq!(kind, "the function `Secret` with private names");
+    assert_eq!(path, "Secret");
+
+    let context = &mut Context::default();
+    assert!(matches!(
+        context.snapshot(),
+        Err(SnapshotError::MissingBase)
+    ));
+}
+
+#[test]
+fn invalid_snapshots() {
+    let snapshot = snapshot("var value = 1;").unwrap();
+
+    let context = &mut Context::default();
+    context
+        .eval(Source::from_bytes("globalThis.extra = {};"))
+        .unwrap();
+    assert!(matches!(
+        context.restore_snapshot(&snapshot),
+        Err(SnapshotError::BaseMismatch)
+    ));
+
+    let mut corrupted = snapshot.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    let context = &mut Context::default();
+    assert!(matches!(
+        context.restore_snapshot(&corrupted),
+        Err(SnapshotError::InvalidSnapshot)
+    ));
+    assert!(matches!(
+        context.restore_snapshot(&snapshot[..10]),
+        Err(SnapshotError::InvalidSnapshot)
+    ));
+}
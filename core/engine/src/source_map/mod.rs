@@ -0,0 +1,472 @@
+//! Input source maps, to show the original positions of generated code in stack traces.
+//!
+//! Code that was compiled from another language or bundled from several files runs with positions
+//! in the generated code. A [`SourceMap`] in the [Source Map v3][spec] format maps those positions
+//! back to the original sources, and is used by `Error.prototype.stack` and the backtraces of
+//! [`JsError`][crate::JsError] to show the original file, line, column and function name of
+//! every frame of generated code.
+//!
+//! A source map is attached to a script or module when it is parsed:
+//!  - A map registered for the path of the code with [`Context::register_source_map`] is always
+//!    used.
+//!  - Otherwise, if enabled with [`Context::set_source_map_loading`], the map referenced by the
+//!    `//# sourceMappingURL=` comment of the code is loaded. Inline `data:` URLs, `file://` URLs
+//!    and paths relative to the file of the code are supported.
+//!
+//! Index maps, made of `sections`, are not supported.
+//!
+//! [spec]: https://tc39.es/ecma426/
+//! [`Context::register_source_map`]: crate::Context::register_source_map
+//! [`Context::set_source_map_loading`]: crate::Context::set_source_map_loading
+
+use crate::spanned_source_text::SourceText;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+use thiserror::Error;
+
+#[cfg(test)]
+mod tests;
+
+/// An error produced while reading a [`SourceMap`].
+#[derive(Debug, Error)]
+pub enum SourceMapError {
+    /// The source map could not be read.
+    #[error("could not read the source map: {0}")]
+    Io(#[from] io::Error),
+
+    /// The source map is not valid JSON, or is missing required fields.
+    #[error("invalid source map: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The source map is not in the version 3 format.
+    #[error("unsupported source map version {0}")]
+    Version(u32),
+
+    /// The source map is an index map, which is not supported.
+    #[error("index source maps are not supported")]
+    IndexMap,
+
+    /// The `mappings` of the source map are invalid.
+    #[error("invalid source map mappings: {0}")]
+    Mappings(&'static str),
+}
+
+/// The JSON representation of a source map.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSourceMap {
+    version: u32,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    source_root: Option<String>,
+    #[serde(default)]
+    sources: Vec<Option<String>>,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    mappings: String,
+    #[serde(default)]
+    sections: Option<serde_json::Value>,
+}
+
+/// The original position of a segment, with 0-based indices.
+#[derive(Debug, Clone, Copy)]
+struct Original {
+    source: u32,
+    line: u32,
+    column: u32,
+    name: Option<u32>,
+}
+
+/// A segment of the mappings, starting at a 0-based column of a generated line.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    column: u32,
+    original: Option<Original>,
+}
+
+/// A decoded [Source Map v3][spec].
+///
+/// [spec]: https://tc39.es/ecma426/
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    file: Option<String>,
+    sources: Vec<String>,
+    names: Vec<String>,
+    /// The segments of every generated line, sorted by column.
+    lines: Vec<Box<[Segment]>>,
+}
+
+/// A position in an original source, returned by [`SourceMap::lookup`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginalPosition<'a> {
+    source: &'a str,
+    line: u32,
+    column: u32,
+    name: Option<&'a str>,
+}
+
+impl<'a> OriginalPosition<'a> {
+    /// Returns the original source, prefixed by the source root of the map.
+    #[must_use]
+    pub const fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the 1-based line in the original source.
+    #[must_use]
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the 1-based column in the original source.
+    #[must_use]
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Returns the original name of the identifier at this position, if any.
+    #[must_use]
+    pub const fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+}
+
+impl SourceMap {
+    /// Parses a source map from its JSON representation.
+    ///
+    /// The sources of the map are kept as written, prefixed by its `sourceRoot`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid version 3 source map.
+    pub fn from_json(json: &str) -> Result<Self, SourceMapError> {
+        let raw: RawSourceMap = serde_json::from_str(json)?;
+        if raw.version != 3 {
+            return Err(SourceMapError::Version(raw.version));
+        }
+        if raw.sections.is_some() {
+            return Err(SourceMapError::IndexMap);
+        }
+
+        let root = raw.source_root.filter(|root| !root.is_empty());
+        let sources = raw
+            .sources
+            .into_iter()
+            .map(|source| {
+                let source = source.unwrap_or_default();
+                match &root {
+                    Some(root) if root.ends_with('/') => format!("{root}{source}"),
+                    Some(root) => format!("{root}/{source}"),
+                    None => source,
+                }
+            })
+            .collect::<Vec<_>>();
+        let lines = parse_mappings(&raw.mappings, sources.len(), raw.names.len())?;
+
+        Ok(Self {
+            file: raw.file,
+            sources,
+            names: raw.names,
+            lines,
+        })
+    }
+
+    /// Reads a source map from a file.
+    ///
+    /// The relative sources of the map are resolved against the directory of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or is not a valid version 3 source map.
+    pub fn from_file(path: &Path) -> Result<Self, SourceMapError> {
+        let mut map = Self::from_json(&std::fs::read_to_string(path)?)?;
+        if let Some(directory) = path.parent() {
+            map.resolve_sources(directory);
+        }
+        Ok(map)
+    }
+
+    /// Returns the name of the generated file, if the map has one.
+    #[must_use]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the original sources of the map.
+    #[must_use]
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Returns the original position of the 1-based `line` and `column` of the generated code.
+    ///
+    /// The position is the one of the closest segment starting at or before `column` on the same
+    /// line. Returns `None` if there is no such segment, or if it has no original position.
+    #[must_use]
+    pub fn lookup(&self, line: u32, column: u32) -> Option<OriginalPosition<'_>> {
+        let segments = self.lines.get(line.checked_sub(1)? as usize)?;
+        let column = column.checked_sub(1)?;
+        let index = segments
+            .partition_point(|segment| segment.column <= column)
+            .checked_sub(1)?;
+        let original = segments[index].original?;
+        Some(OriginalPosition {
+            source: &self.sources[original.source as usize],
+            line: original.line + 1,
+            column: original.column + 1,
+            name: original.name.map(|name| self.names[name as usize].as_str()),
+        })
+    }
+
+    /// Returns the original name of the segment starting exactly at the 1-based `line` and
+    /// `column` of the generated code.
+    pub(crate) fn name_at(&self, line: u32, column: u32) -> Option<&str> {
+        let segments = self.lines.get(line.checked_sub(1)? as usize)?;
+        let column = column.checked_sub(1)?;
+        let index = segments
+            .binary_search_by_key(&column, |segment| segment.column)
+            .ok()?;
+        let name = segments[index].original?.name?;
+        Some(&self.names[name as usize])
+    }
+
+    /// Resolves the relative sources of the map against `directory`.
+    fn resolve_sources(&mut self, directory: &Path) {
+        for source in &mut self.sources {
+            if source.contains("://") || Path::new(source.as_str()).is_absolute() {
+                continue;
+            }
+            *source = normalize(&directory.join(source.as_str()))
+                .to_string_lossy()
+                .into_owned();
+        }
+    }
+}
+
+/// Removes the `.` and `..` components of a path, without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Decodes the `mappings` field of a source map into the segments of every generated line.
+fn parse_mappings(
+    mappings: &str,
+    sources: usize,
+    names: usize,
+) -> Result<Vec<Box<[Segment]>>, SourceMapError> {
+    fn index(value: i64, len: usize) -> Result<u32, SourceMapError> {
+        u32::try_from(value)
+            .ok()
+            .filter(|&index| (index as usize) < len)
+            .ok_or(SourceMapError::Mappings("index out of bounds"))
+    }
+
+    fn position(value: i64) -> Result<u32, SourceMapError> {
+        u32::try_from(value).map_err(|_| SourceMapError::Mappings("position out of bounds"))
+    }
+
+    // Every field but the generated column is relative to the previous segment of the map.
+    let (mut source, mut line, mut column, mut name) = (0, 0, 0, 0);
+    let mut lines = Vec::new();
+    for text in mappings.split(';') {
+        let mut generated = 0;
+        let mut segments = Vec::new();
+        for mut text in text.split(',').map(str::as_bytes) {
+            if text.is_empty() {
+                continue;
+            }
+            let mut fields = [0; 5];
+            let mut len = 0;
+            while !text.is_empty() {
+                let field = fields
+                    .get_mut(len)
+                    .ok_or(SourceMapError::Mappings("too many fields in a segment"))?;
+                *field = decode_vlq(&mut text)?;
+                len += 1;
+            }
+
+            generated += fields[0];
+            let original = match len {
+                1 => None,
+                4 | 5 => {
+                    source += fields[1];
+                    line += fields[2];
+                    column += fields[3];
+                    let name = if len == 5 {
+                        name += fields[4];
+                        Some(index(name, names)?)
+                    } else {
+                        None
+                    };
+                    Some(Original {
+                        source: index(source, sources)?,
+                        line: position(line)?,
+                        column: position(column)?,
+                        name,
+                    })
+                }
+                _ => {
+                    return Err(SourceMapError::Mappings(
+                        "a segment must have 1, 4 or 5 fields",
+                    ));
+                }
+            };
+            segments.push(Segment {
+                column: position(generated)?,
+                original,
+            });
+        }
+        segments.sort_by_key(|segment| segment.column);
+        lines.push(segments.into_boxed_slice());
+    }
+    Ok(lines)
+}
+
+/// Decodes a base64 VLQ value from the start of `input`, advancing it past the value.
+fn decode_vlq(input: &mut &[u8]) -> Result<i64, SourceMapError> {
+    const CONTINUATION: u8 = 0b10_0000;
+
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let (&byte, rest) = input
+            .split_first()
+            .ok_or(SourceMapError::Mappings("unterminated value"))?;
+        *input = rest;
+        let digit = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(SourceMapError::Mappings("invalid base64 digit")),
+        };
+        if shift > 32 {
+            return Err(SourceMapError::Mappings("value out of bounds"));
+        }
+        value |= u64::from(digit & !CONTINUATION) << shift;
+        if digit & CONTINUATION == 0 {
+            break;
+        }
+        shift += 5;
+    }
+
+    // The lowest bit is the sign, and the value is always small enough to fit.
+    let magnitude = (value >> 1) as i64;
+    Ok(if value & 1 == 1 {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// The source maps known to a [`Context`][crate::Context].
+#[derive(Debug, Default)]
+pub(crate) struct SourceMaps {
+    /// Whether to load the maps referenced by `//# sourceMappingURL=` comments.
+    pub(crate) load_from_comments: bool,
+
+    /// The maps registered by the host, by path of the generated code.
+    pub(crate) registered: FxHashMap<PathBuf, Rc<SourceMap>>,
+}
+
+impl SourceMaps {
+    /// Attaches the source map of the code in `source_text`, read from `path`, if it has one.
+    ///
+    /// Maps that cannot be loaded are ignored, so the stack traces of the code keep showing the
+    /// generated positions.
+    pub(crate) fn attach(&self, source_text: &SourceText, path: Option<&Path>) {
+        if let Some(map) = path.and_then(|path| self.registered.get(path)) {
+            source_text.set_source_map(map.clone());
+            return;
+        }
+        if !self.load_from_comments {
+            return;
+        }
+        let Some(url) = source_mapping_url(source_text.code_units()) else {
+            return;
+        };
+        if let Some(map) = load(&url, path) {
+            source_text.set_source_map(Rc::new(map));
+        }
+    }
+}
+
+/// Returns the URL of the last `//# sourceMappingURL=` comment of the code.
+fn source_mapping_url(code: &[u16]) -> Option<String> {
+    const PATTERN: &[u8] = b"sourceMappingURL=";
+
+    let start = code.windows(PATTERN.len()).rposition(|window| {
+        window
+            .iter()
+            .copied()
+            .eq(PATTERN.iter().copied().map(u16::from))
+    })?;
+    let prefix = code.get(start.checked_sub(4)?..start)?;
+    if !(prefix == [0x2F, 0x2F, 0x23, 0x20] || prefix == [0x2F, 0x2F, 0x40, 0x20]) {
+        // Neither `//# ` nor the deprecated `//@ `.
+        return None;
+    }
+
+    let url = &code[start + PATTERN.len()..];
+    let end = url
+        .iter()
+        .position(|&unit| char::from_u32(u32::from(unit)).is_none_or(char::is_whitespace))
+        .unwrap_or(url.len());
+    String::from_utf16(&url[..end])
+        .ok()
+        .filter(|url| !url.is_empty())
+}
+
+/// Loads the source map at `url`, referenced by the code read from `path`.
+fn load(url: &str, path: Option<&Path>) -> Option<SourceMap> {
+    let directory = path.and_then(Path::parent);
+    if let Some(data) = url.strip_prefix("data:") {
+        let (header, payload) = data.split_once(',')?;
+        if !header.starts_with("application/json") {
+            return None;
+        }
+        let json = if header.ends_with(";base64") {
+            String::from_utf8(BASE64_STANDARD.decode(payload).ok()?).ok()?
+        } else {
+            payload.to_owned()
+        };
+        let mut map = SourceMap::from_json(&json).ok()?;
+        if let Some(directory) = directory {
+            map.resolve_sources(directory);
+        }
+        return Some(map);
+    }
+
+    let file = if let Some(file) = url.strip_prefix("file://") {
+        PathBuf::from(file)
+    } else if url.contains("://") {
+        return None;
+    } else {
+        directory.unwrap_or(Path::new("")).join(url)
+    };
+    SourceMap::from_file(&file).ok()
+}
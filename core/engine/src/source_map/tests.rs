@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use boa_parser::Source;
+
+use super::{OriginalPosition, SourceMap, SourceMapError};
+use crate::{Context, JsValue};
+
+/// The generated code of `src/greet.ts`:
+///
+/// ```text
+/// function greet(name: string) {
+///     return new Error(`hi ${name}`).stack;
+/// }
+/// greet("x");
+/// ```
+const GENERATED: &str = r#"function a(b){return new Error(`hi ${b}`).stack}a("x");"#;
+
+const MAP: &str = r#"{
+    "version": 3,
+    "file": "greet.js",
+    "sourceRoot": "../src",
+    "sources": ["greet.ts"],
+    "names": ["greet", "name"],
+    "mappings": "AAAA,SAASA,EAAMC,GACX,OAAO,oBAAwB,OAEnCD"
+}"#;
+
+/// Runs `code` from `/dist/greet.js`, returning the stack of the error it creates.
+fn run(context: &mut Context, code: &str) -> String {
+    let stack = context
+        .eval(Source::from_bytes(code).with_path(Path::new("/dist/greet.js")))
+        .expect("the code should run");
+    stack
+        .as_string()
+        .expect("the stack should be a string")
+        .to_std_string_escaped()
+}
+
+#[test]
+fn lookup() {
+    let map = SourceMap::from_json(MAP).expect("the map should be valid");
+    assert_eq!(map.file(), Some("greet.js"));
+    assert_eq!(map.sources(), ["../src/greet.ts"]);
+
+    let position = |source, line, column, name| OriginalPosition {
+        source,
+        line,
+        column,
+        name,
+    };
+    assert_eq!(
+        map.lookup(1, 10),
+        Some(position("../src/greet.ts", 1, 10, Some("greet")))
+    );
+    // Columns between segments map to the closest segment before them.
+    assert_eq!(
+        map.lookup(1, 25),
+        Some(position("../src/greet.ts", 2, 12, None))
+    );
+    assert_eq!(
+        map.lookup(1, 49),
+        Some(position("../src/greet.ts", 4, 1, Some("greet")))
+    );
+    assert_eq!(map.lookup(2, 1), None);
+    assert_eq!(map.name_at(1, 12), Some("name"));
+    assert_eq!(map.name_at(1, 13), None);
+}
+
+#[test]
+fn invalid_maps() {
+    assert!(matches!(
+        SourceMap::from_json(r#"{"version": 2, "mappings": ""}"#),
+        Err(SourceMapError::Version(2))
+    ));
+    assert!(matches!(
+        SourceMap::from_json(r#"{"version": 3, "sections": []}"#),
+        Err(SourceMapError::IndexMap)
+    ));
+    assert!(matches!(
+        SourceMap::from_json(r#"{"version": 3, "sources": ["a.js"], "mappings": "AAAA,A!"}"#),
+        Err(SourceMapError::Mappings(_))
+    ));
+    assert!(matches!(
+        SourceMap::from_json(r#"{"version": 3, "sources": ["a.js"], "mappings": "ACAA"}"#),
+        Err(SourceMapError::Mappings(_))
+    ));
+    assert!(matches!(
+        SourceMap::from_json("{"),
+        Err(SourceMapError::Json(_))
+    ));
+}
+
+#[test]
+fn registered_map() {
+    let mut context = Context::default();
+    context.register_source_map(
+        Path::new("/dist/greet.js"),
+        SourceMap::from_json(MAP).expect("the map should be valid"),
+    );
+
+    let stack = run(&mut context, GENERATED);
+    let frames = stack.lines().collect::<Vec<_>>();
+    assert_eq!(
+        frames,
+        [
+            "    at greet (../src/greet.ts:2:12)",
+            "    at <main> (../src/greet.ts:4:1)"
+        ]
+    );
+}
+
+#[test]
+fn inline_map() {
+    let url = format!(
+        "data:application/json;charset=utf-8;base64,{}",
+        BASE64_STANDARD.encode(MAP)
+    );
+    let code = format!("{GENERATED}\n//# sourceMappingURL={url}\n");
+
+    // Source maps are only loaded from comments when enabled.
+    let mut context = Context::default();
+    assert!(!context.source_map_loading());
+    let stack = run(&mut context, &code);
+    assert!(stack.contains("    at a (/dist/greet.js:1:22)"));
+
+    context.set_source_map_loading(true);
+    let stack = run(&mut context, &code);
+    assert!(stack.contains("    at greet (/src/greet.ts:2:12)"));
+    assert!(stack.contains("    at <main> (/src/greet.ts:4:1)"));
+}
+
+#[test]
+fn unmapped_code() {
+    let mut context = Context::default();
+    context.register_source_map(
+        Path::new("/dist/greet.js"),
+        SourceMap::from_json(r#"{"version": 3, "sources": [], "mappings": ""}"#)
+            .expect("the map should be valid"),
+    );
+
+    // Positions without a mapping keep their generated position.
+    let stack = run(&mut context, GENERATED);
+    assert!(stack.contains("    at a (/dist/greet.js:1:22)"));
+    assert_eq!(
+        context.eval(Source::from_bytes("1 + 1")),
+        Ok(JsValue::from(2))
+    );
+}
@@ -1,14 +1,22 @@
-use std::rc::Rc;
+use std::{cell::OnceCell, rc::Rc};
 
-use boa_ast::LinearSpan;
+use boa_ast::{LinearPosition, LinearSpan};
 use boa_gc::{Finalize, Trace};
 
+use crate::source_map::SourceMap;
+
 struct Inner {
     source_text: boa_ast::SourceText,
+    source_map: OnceCell<Rc<SourceMap>>,
+    line_starts: OnceCell<Box<[usize]>>,
 }
 impl Inner {
     fn new(source_text: boa_ast::SourceText) -> Self {
-        Self { source_text }
+        Self {
+            source_text,
+            source_map: OnceCell::new(),
+            line_starts: OnceCell::new(),
+        }
     }
 }
 
@@ -26,7 +34,16 @@ impl SourceText {
         }
     }
 
-    fn new_empty() -> Self {
+    /// Creates a source text from its code units.
+    pub(crate) fn from_code_units(code_units: &[u16]) -> Self {
+        let mut source_text = boa_ast::SourceText::with_capacity(code_units.len());
+        for unit in code_units {
+            source_text.collect_code_point(u32::from(*unit));
+        }
+        Self::new(source_text)
+    }
+
+    pub(crate) fn new_empty() -> Self {
         Self { source_text: None }
     }
 
@@ -35,9 +52,62 @@ impl SourceText {
         self.source_text.as_ref().map(|x| &x.source_text)
     }
 
-    fn is_empty(&self) -> bool {
+    pub(crate) fn is_empty(&self) -> bool {
         self.source_text.is_none()
     }
+
+    /// Returns the address of the shared source text, which identifies it.
+    pub(crate) fn addr(&self) -> usize {
+        self.source_text
+            .as_ref()
+            .map_or(0, |source_text| Rc::as_ptr(source_text) as usize)
+    }
+
+    /// Returns the code units of the whole source text.
+    pub(crate) fn code_units(&self) -> &[u16] {
+        self.inner().map_or(&[], |source_text| {
+            source_text.get_code_points_from_pos(LinearPosition::new(0))
+        })
+    }
+
+    /// Attaches the source map of the generated code in this source text.
+    ///
+    /// Does nothing if the source text is empty or already has a source map.
+    pub(crate) fn set_source_map(&self, source_map: Rc<SourceMap>) {
+        if let Some(inner) = &self.source_text {
+            drop(inner.source_map.set(source_map));
+        }
+    }
+
+    /// Returns the source map of the generated code in this source text, if it has one.
+    pub(crate) fn source_map(&self) -> Option<&SourceMap> {
+        self.source_text.as_ref()?.source_map.get().map(Rc::as_ref)
+    }
+
+    /// Returns the 0-based line and column of a code unit offset.
+    pub(crate) fn line_and_column(&self, offset: usize) -> (usize, usize) {
+        let Some(inner) = &self.source_text else {
+            return (0, offset);
+        };
+        let line_starts = inner.line_starts.get_or_init(|| {
+            let code_units = self.code_units();
+            let mut line_starts = vec![0];
+            for (i, &unit) in code_units.iter().enumerate() {
+                let is_line_terminator = match unit {
+                    // `\r\n` is a single line terminator.
+                    0x0D => code_units.get(i + 1) != Some(&0x0A),
+                    0x0A | 0x2028 | 0x2029 => true,
+                    _ => false,
+                };
+                if is_line_terminator {
+                    line_starts.push(i + 1);
+                }
+            }
+            line_starts.into_boxed_slice()
+        });
+        let line = line_starts.partition_point(|&start| start <= offset) - 1;
+        (line, offset - line_starts[line])
+    }
 }
 
 /// Contains pointer to source code and span of the object.
@@ -80,6 +150,11 @@ impl SpannedSourceText {
         self.source_text.clone()
     }
 
+    /// Returns the span of the source text.
+    pub(crate) const fn span(&self) -> Option<LinearSpan> {
+        self.span
+    }
+
     /// Test if the span is empty.
     #[inline]
     #[must_use]
@@ -256,6 +256,22 @@ impl JsSymbol {
         )
     }
 
+    /// Returns the tag of this symbol if it's a well known symbol.
+    pub(crate) fn well_known_tag(&self) -> Option<u8> {
+        match self.repr.unwrap() {
+            UnwrappedTagged::Ptr(_) => None,
+            UnwrappedTagged::Tag(tag) => u8::try_from(tag).ok(),
+        }
+    }
+
+    /// Gets the well known symbol with the tag returned by [`JsSymbol::well_known_tag`].
+    pub(crate) fn from_well_known_tag(tag: u8) -> Option<Self> {
+        let well_known = WellKnown::from_tag(usize::from(tag))?;
+        Some(Self {
+            repr: Tagged::from_tag(well_known.hash() as usize),
+        })
+    }
+
     /// Consumes the [`JsSymbol`], returning a pointer to `RawJsSymbol`.
     ///
     /// To avoid a memory leak the pointer must be converted back to a `JsSymbol` using
@@ -324,3 +324,92 @@ fn function_constructor_nested_lexical_binding() {
         42,
     )]);
 }
+
+#[test]
+fn lazy_compilation_defers_nested_functions() {
+    use crate::{Context, Script, vm::Constant};
+    use boa_parser::Source;
+
+    fn uncompiled(code: &crate::vm::CodeBlock) -> Vec<bool> {
+        code.constants
+            .iter()
+            .filter_map(|constant| match constant {
+                Constant::UncompiledFunction(function) => {
+                    Some(function.get_if_compiled().is_none())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    let context = &mut Context::default();
+    let source = indoc! {r#"
+        function outer() {
+            return function inner() { return 42; };
+        }
+        function unused() {}
+    "#};
+    let script = Script::parse(Source::from_bytes(source), None, context).unwrap();
+    let code = script.codeblock(context).unwrap();
+    assert_eq!(uncompiled(&code), [true, true]);
+
+    // Creating the function objects doesn't compile the functions.
+    script.evaluate(context).unwrap();
+    assert_eq!(uncompiled(&code), [true, true]);
+
+    let result = context.eval(Source::from_bytes("outer()()")).unwrap();
+    assert_eq!(result, JsValue::new(42));
+    assert_eq!(uncompiled(&code), [false, true]);
+
+    let index = code
+        .constants
+        .iter()
+        .position(|constant| matches!(constant, Constant::UncompiledFunction(_)))
+        .unwrap();
+    let outer = code.constant_function(index);
+    assert_eq!(uncompiled(&outer), [false]);
+}
+
+#[test]
+fn lazy_and_eager_compilation_agree() {
+    use crate::Context;
+    use boa_parser::Source;
+
+    let source = indoc! {r#"
+        function counter(start = 0, step = () => 1) {
+            let count = start;
+            return { next() { count += step(); return count; } };
+        }
+        class Base {
+            static create() { return new this(); }
+            describe() { return "base"; }
+        }
+        class Derived extends Base {
+            #secret = 7;
+            describe() { return `${super.describe()}:${this.#secret}`; }
+        }
+        function* range(n) { for (let i = 0; i < n; i++) yield i; }
+        function withEval(x) { return eval("(function () { return x * 2; })")(); }
+        function withArguments() { return (() => arguments.length)(); }
+        const c = counter(10);
+        c.next();
+        [
+            c.next(),
+            Derived.create().describe(),
+            [...range(3)].join(":"),
+            withEval(21),
+            withArguments(1, 2, 3),
+            counter.toString().startsWith("function counter(start = 0"),
+        ].join()
+    "#};
+
+    let mut results = Vec::new();
+    for lazy in [true, false] {
+        let context = &mut Context::default();
+        context.set_lazy_compilation(lazy);
+        let result = context.eval(Source::from_bytes(source)).unwrap();
+        results.push(result.as_string().unwrap().to_std_string_escaped());
+    }
+    assert_eq!(results[0], results[1]);
+    assert_eq!(results[0], "12,base:7,0:1:2,42,3,true");
+}
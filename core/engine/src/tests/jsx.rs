@@ -0,0 +1,144 @@
+use crate::{
+    Context, JsValue, Module, Source, builtins::promise::PromiseState, js_string,
+    module::MapModuleLoader, parser::source::JsxOptions,
+};
+use indoc::indoc;
+use std::rc::Rc;
+
+/// A classic JSX factory that returns its arguments as an object.
+const CLASSIC_FACTORY: &str = indoc! {r#"
+    const h = (type, props, ...children) => ({ type, props, children });
+    const Fragment = "fragment";
+"#};
+
+/// Evaluates the classic JSX `code` and returns its value as JSON.
+#[track_caller]
+fn eval_classic(code: &str) -> String {
+    let context = &mut Context::default();
+    context.eval(Source::from_bytes(CLASSIC_FACTORY)).unwrap();
+    let source = format!("JSON.stringify({code})");
+    let value = context
+        .eval(Source::from_bytes(&source).with_jsx(JsxOptions::classic("h", "Fragment")))
+        .unwrap();
+    value.to_string(context).unwrap().to_std_string_escaped()
+}
+
+#[test]
+fn classic_elements() {
+    assert_eq!(
+        eval_classic(r#"<div id="a" hidden>b</div>"#),
+        r#"{"type":"div","props":{"id":"a","hidden":true},"children":["b"]}"#
+    );
+    assert_eq!(
+        eval_classic("<><br /></>"),
+        r#"{"type":"fragment","props":null,"children":[{"type":"br","props":null,"children":[]}]}"#
+    );
+}
+
+#[test]
+fn classic_components_and_expressions() {
+    assert_eq!(
+        eval_classic(indoc! {r#"
+            (() => {
+                const ui = { Button: "button-component" };
+                const props = { a: 1, b: 2 };
+                return <ui.Button {...props} b={3} c={<i />}>{props.a + 1}{...["x", "y"]}</ui.Button>;
+            })()
+        "#}),
+        concat!(
+            r#"{"type":"button-component","props":{"a":1,"b":3,"c":{"type":"i","props":null,"children":[]}},"#,
+            r#""children":[2,"x","y"]}"#
+        )
+    );
+}
+
+#[test]
+fn classic_text_cleanup() {
+    assert_eq!(
+        eval_classic(indoc! {r#"
+            <p>
+                Hello,   {"world"}
+                &amp; welcome
+                <b>  !  </b>
+            </p>
+        "#}),
+        concat!(
+            r#"{"type":"p","props":null,"children":["Hello,   ","world","& welcome","#,
+            r#"{"type":"b","props":null,"children":["  !  "]}]}"#
+        )
+    );
+}
+
+#[test]
+fn classic_member_factory_this() {
+    let context = &mut Context::default();
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            const React = {
+                name: "react",
+                createElement(type) { return this.name + ":" + type; },
+                Fragment: "fragment",
+            };
+        "#}))
+        .unwrap();
+    let value = context
+        .eval(Source::from_bytes("<><a /></>").with_jsx(JsxOptions::default()))
+        .unwrap();
+    assert_eq!(value, JsValue::from(js_string!("react:fragment")));
+}
+
+#[test]
+fn automatic_runtime() {
+    let loader = Rc::new(MapModuleLoader::new());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let runtime = Module::parse(
+        Source::from_bytes(indoc! {r#"
+            export const jsx = (type, props, key) => ({ f: "jsx", type, props, key });
+            export const jsxs = (type, props, key) => ({ f: "jsxs", type, props, key });
+            export const Fragment = "fragment";
+        "#}),
+        None,
+        context,
+    )
+    .unwrap();
+    loader.insert("preact/jsx-runtime", runtime);
+
+    let module = Module::parse(
+        Source::from_bytes(indoc! {r#"
+            const props = { id: "b" };
+            export default JSON.stringify([
+                <div key="a" {...props}>text</div>,
+                <><i /><i key={1} /></>,
+                <p {...props} key="c" />,
+            ]);
+        "#})
+        .with_jsx(JsxOptions::automatic("preact")),
+        None,
+        context,
+    )
+    .unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs().unwrap();
+    assert_eq!(
+        promise.state(),
+        PromiseState::Fulfilled(JsValue::undefined())
+    );
+
+    let value = module
+        .namespace(context)
+        .get(js_string!("default"), context)
+        .unwrap();
+    assert_eq!(
+        value.to_string(context).unwrap().to_std_string_escaped(),
+        concat!(
+            r#"[{"f":"jsx","type":"div","props":{"id":"b","children":"text"},"key":"a"},"#,
+            r#"{"f":"jsxs","type":"fragment","props":{"children":["#,
+            r#"{"f":"jsx","type":"i","props":{}},{"f":"jsx","type":"i","props":{},"key":1}]}},"#,
+            r#"{"f":"jsx","type":"p","props":{"id":"b","key":"c"}}]"#
+        )
+    );
+}
@@ -11,10 +11,15 @@ mod function;
 mod generators;
 mod iterators;
 mod job;
+mod jsx;
 mod operators;
 mod promise;
+#[cfg(feature = "tracing")]
+mod spans;
 mod spread;
 mod to_string;
+#[cfg(feature = "typescript")]
+mod typescript;
 
 use crate::{JsNativeErrorKind, JsValue, TestAction, run_test_actions};
 
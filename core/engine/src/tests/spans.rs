@@ -0,0 +1,131 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicU64, Ordering},
+};
+
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+
+use crate::{Context, Module, Source, builtins::promise::PromiseState};
+
+/// A subscriber that records the spans that are created, as `name` or `name:label`, where the
+/// label is the `kind` or `function` field of the span.
+#[derive(Default, Clone)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct Label(Option<String>);
+
+impl Visit for Label {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if matches!(field.name(), "kind" | "function") {
+            self.0 = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if matches!(field.name(), "kind" | "function") {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut label = Label(None);
+        span.record(&mut label);
+        let name = span.metadata().name();
+        self.spans
+            .lock()
+            .expect("the lock should not be poisoned")
+            .push(match label.0 {
+                Some(label) => format!("{name}:{label}"),
+                None => name.to_owned(),
+            });
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Runs `f` with a [`Recorder`], returning the recorded spans.
+fn record(f: impl FnOnce()) -> Vec<String> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    recorder
+        .spans
+        .lock()
+        .expect("the lock should not be poisoned")
+        .clone()
+}
+
+#[test]
+fn script_spans() {
+    let spans = record(|| {
+        let context = &mut Context::default();
+        context
+            .eval(Source::from_bytes(
+                "function f() { return 1; } Promise.resolve().then(f); f();",
+            ))
+            .expect("the script should run");
+        context.run_jobs().expect("the jobs should run");
+        boa_gc::force_collect();
+    });
+
+    for expected in [
+        "parse:script",
+        "compile:script",
+        "evaluate:script",
+        "call:<main>",
+        "call:f",
+        "job:promise",
+        "gc",
+    ] {
+        assert!(
+            spans.iter().any(|span| span == expected),
+            "missing `{expected}` in {spans:?}"
+        );
+    }
+    let position = |name| spans.iter().position(|span| span == name);
+    assert!(position("parse:script") < position("compile:script"));
+    assert!(position("evaluate:script") < position("job:promise"));
+}
+
+#[test]
+fn module_spans() {
+    let spans = record(|| {
+        let context = &mut Context::default();
+        let module = Module::parse(Source::from_bytes("export const x = 1;"), None, context)
+            .expect("the module should parse");
+        let promise = module.load_link_evaluate(context);
+        context.run_jobs().expect("the jobs should run");
+        assert_eq!(
+            promise.state(),
+            PromiseState::Fulfilled(crate::JsValue::undefined())
+        );
+    });
+
+    for expected in ["parse:module", "compile:module", "evaluate:module"] {
+        assert!(
+            spans.iter().any(|span| span == expected),
+            "missing `{expected}` in {spans:?}"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{Context, JsValue, Source, js_string};
+use indoc::indoc;
+
+#[test]
+fn function_to_string_shows_types() {
+    let context = &mut Context::default();
+    let code = indoc! {r#"
+        function add(a: number, b: number): number { return a + b; }
+        class C { m<T>(x: T): void {} }
+        [add.toString(), C.prototype.m.toString()].join("\n")
+    "#};
+    let value = context
+        .eval(Source::from_bytes(code).with_typescript(true))
+        .unwrap();
+    assert_eq!(
+        value,
+        JsValue::from(js_string!(
+            "function add(a: number, b: number): number { return a + b; }\nm<T>(x: T): void {}"
+        ))
+    );
+}
+
+#[test]
+fn positions_of_erased_types() {
+    let context = &mut Context::default();
+    let code = indoc! {r#"
+        function fail(message: string): never { throw new Error(message); }
+        class Point { constructor(public x: number) { fail("oops"); } }
+        new Point(1);
+    "#};
+    let error = context
+        .eval(Source::from_bytes(code).with_typescript(true))
+        .unwrap_err();
+    let frames = error.stack_frames().unwrap();
+
+    assert_eq!(frames[0].function_name(), Some("fail"));
+    assert_eq!((frames[0].line(), frames[0].column()), (Some(1), Some(47)));
+
+    // The assignment of the parameter property is inserted in the constructor, without shifting
+    // the positions that follow it: the call to `fail` is at column 51 of the source.
+    assert_eq!((frames[1].line(), frames[1].column()), (Some(2), Some(51)));
+}
+
+#[test]
+fn syntax_error_positions_after_inserted_code() {
+    let context = &mut Context::default();
+    let code = "enum Color { Red, Green } let x: Color = ;";
+    let error = context
+        .eval(Source::from_bytes(code).with_typescript(true))
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("line 1, col 42"),
+        "unexpected error: {error}"
+    );
+}
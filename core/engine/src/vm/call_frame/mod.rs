@@ -36,10 +36,16 @@ bitflags::bitflags! {
     }
 }
 
+/// The location of the instruction a [`CallFrame`] is executing.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CallFrameLocation {
+    /// The name of the function of the frame.
     pub function_name: JsString,
+
+    /// The path of the source of the frame.
     pub path: SourcePath,
+
+    /// The position of the instruction in the source, if it is known.
     pub position: Option<Position>,
 }
 
@@ -8,6 +8,7 @@ use crate::{
         OrdinaryObject,
         function::{OrdinaryFunction, ThisMode},
     },
+    bytecompiler::UncompiledFunction,
     object::JsObject,
 };
 use bitflags::bitflags;
@@ -19,6 +20,7 @@ use thin_vec::ThinVec;
 
 use super::{
     InlineCache,
+    code_cache::LazyCodeBlock,
     opcode::{Address, Bytecode, Instruction, InstructionIterator},
     source_info::{SourceInfo, SourceMap, SourcePath},
 };
@@ -101,6 +103,11 @@ pub(crate) enum Constant {
     /// Property field names and private names `[[description]]`s.
     String(JsString),
     Function(Gc<CodeBlock>),
+    /// A function loaded from a code cache, which is decoded on first use.
+    LazyFunction(LazyCodeBlock),
+    /// The placeholder code block of a function that is compiled from its AST when it is first
+    /// called.
+    UncompiledFunction(Gc<CodeBlock>),
     BigInt(#[unsafe_ignore_trace] JsBigInt),
 
     /// Declarative or function scope.
@@ -172,6 +179,14 @@ pub struct CodeBlock {
     #[cfg(feature = "trace")]
     #[unsafe_ignore_trace]
     pub(crate) traced: Cell<bool>,
+
+    #[cfg(feature = "debugger")]
+    #[unsafe_ignore_trace]
+    pub(crate) debug_info: crate::debugger::CodeBlockDebugInfo,
+
+    /// The AST of the function, if this is the placeholder of a function that was not
+    /// compiled yet.
+    pub(crate) uncompiled: Option<UncompiledFunction>,
 }
 
 /// ---- `CodeBlock` public API ----
@@ -194,7 +209,7 @@ impl CodeBlock {
             handlers: ThinVec::default(),
             ic: Box::default(),
             source_info: SourceInfo::new(
-                SourceMap::new(Box::default(), SourcePath::None),
+                SourceMap::new(Box::default(), SourcePath::None, None),
                 name,
                 SpannedSourceText::new_empty(),
             ),
@@ -204,6 +219,9 @@ impl CodeBlock {
             debug_id: CodeBlock::get_next_codeblock_id(),
             #[cfg(feature = "trace")]
             traced: Cell::new(false),
+            #[cfg(feature = "debugger")]
+            debug_info: crate::debugger::CodeBlockDebugInfo::default(),
+            uncompiled: None,
         }
     }
 
@@ -300,6 +318,21 @@ impl CodeBlock {
         self.flags.get().has_function_scope()
     }
 
+    /// Returns `code`, or the compiled code block if `code` is the placeholder of a function that
+    /// was not compiled yet, compiling the function if this is the first use.
+    pub(crate) fn compiled(code: &Gc<Self>) -> Gc<Self> {
+        match &code.uncompiled {
+            Some(function) => function.get(),
+            None => code.clone(),
+        }
+    }
+
+    /// Returns the compiled code block if this is the placeholder of a function that was
+    /// compiled.
+    pub(crate) fn get_if_compiled(&self) -> Option<&Gc<Self>> {
+        self.uncompiled.as_ref()?.get_if_compiled()
+    }
+
     /// Find exception [`Handler`] in the code block given the current program counter (`pc`).
     #[inline]
     pub(crate) fn find_handler(&self, pc: u32) -> Option<(usize, &Handler)> {
@@ -331,8 +364,13 @@ impl CodeBlock {
     /// If the type of the [`Constant`] is not [`Constant::Function`].
     /// Or `index` is greater or equal to length of `constants`.
     pub(crate) fn constant_function(&self, index: usize) -> Gc<Self> {
-        if let Some(Constant::Function(value)) = self.constants.get(index) {
-            return value.clone();
+        match self.constants.get(index) {
+            Some(Constant::Function(value)) => return value.clone(),
+            Some(Constant::LazyFunction(value)) => return value.get(),
+            Some(Constant::UncompiledFunction(value)) => {
+                return value.get_if_compiled().unwrap_or(value).clone();
+            }
+            _ => {}
         }
 
         panic!("expected function constant at index {index}")
@@ -873,6 +911,7 @@ impl CodeBlock {
             | Instruction::NewSpread
             | Instruction::SuperCallSpread
             | Instruction::PopPrivateEnvironment
+            | Instruction::Debugger
             | Instruction::Generator
             | Instruction::AsyncGenerator => String::new(),
             Instruction::Reserved1
@@ -932,8 +971,7 @@ impl CodeBlock {
             | Instruction::Reserved55
             | Instruction::Reserved56
             | Instruction::Reserved57
-            | Instruction::Reserved58
-            | Instruction::Reserved59 => unreachable!("Reserved opcodes are unreachable"),
+            | Instruction::Reserved58 => unreachable!("Reserved opcodes are unreachable"),
         }
     }
 }
@@ -1008,6 +1046,24 @@ impl Display for CodeBlock {
                         code.name().to_std_string_escaped(),
                         code.length
                     )?,
+                    Constant::LazyFunction(code) => match code.get_if_decoded() {
+                        Some(code) => writeln!(
+                            f,
+                            "[FUNCTION] name: '{}' (length: {})",
+                            code.name().to_std_string_escaped(),
+                            code.length
+                        )?,
+                        None => writeln!(f, "[FUNCTION] <not decoded>")?,
+                    },
+                    Constant::UncompiledFunction(code) => match code.get_if_compiled() {
+                        Some(code) => writeln!(
+                            f,
+                            "[FUNCTION] name: '{}' (length: {})",
+                            code.name().to_std_string_escaped(),
+                            code.length
+                        )?,
+                        None => writeln!(f, "[FUNCTION] <not compiled>")?,
+                    },
                     Constant::Scope(v) => {
                         writeln!(
                             f,
@@ -0,0 +1,420 @@
+//! Decoding of code caches into [`CodeBlock`]s.
+
+use std::{cell::Cell, cell::OnceCell, ops::Range, rc::Rc};
+
+use boa_ast::{
+    LinearPosition, LinearSpan, Position,
+    scope::{BindingLocator, BindingLocatorScope, Scope, ScopeBinding},
+};
+use boa_gc::{Finalize, Gc, Trace};
+use rustc_hash::FxHashMap;
+
+use super::Reader;
+use crate::{
+    JsBigInt, JsString, SpannedSourceText,
+    builtins::function::ThisMode,
+    spanned_source_text::SourceText,
+    vm::{
+        CodeBlock, CodeBlockFlags, Constant, GlobalFunctionBinding, Handler, InlineCache,
+        Instruction, InstructionIterator,
+        opcode::{Address, Bytecode},
+        source_info::{Entry, SourceInfo, SourceMap, SourcePath},
+    },
+};
+
+/// A scope of the scope table, before it is created.
+#[derive(Debug)]
+struct ScopeRecord {
+    parent: u32,
+    function: bool,
+    index: u32,
+    this_escaped: bool,
+    bindings: Vec<ScopeBinding>,
+}
+
+/// The tables that follow the kind specific section of a payload.
+#[derive(Debug)]
+pub(crate) struct Tables {
+    scopes: Vec<ScopeRecord>,
+    globals: Vec<(JsString, Option<u32>)>,
+    functions: Vec<Range<usize>>,
+}
+
+impl Tables {
+    /// Reads the tables of `payload`, which must be the rest of the input of `reader`.
+    pub(crate) fn read(payload: &[u8], reader: &mut Reader<'_>) -> Option<Self> {
+        let mut scope_count = 0;
+        let scopes = reader.seq(|reader| {
+            scope_count += 1;
+            let parent = reader.u32()?;
+            // Parents are always encoded before their children.
+            if parent >= scope_count {
+                return None;
+            }
+            Some(ScopeRecord {
+                parent,
+                function: reader.bool()?,
+                index: reader.u32()?,
+                this_escaped: reader.bool()?,
+                bindings: reader.seq(|reader| {
+                    Some(ScopeBinding::new(
+                        reader.string()?,
+                        reader.u32()?,
+                        reader.u8()?,
+                    ))
+                })?,
+            })
+        })?;
+
+        let globals = reader.seq(|reader| Some((reader.string()?, reader.option(Reader::u32)?)))?;
+
+        let functions = reader.seq(|reader| {
+            let len = reader.len()?;
+            let start = payload.len() - reader.rest().len();
+            reader.take(len)?;
+            Some(start..start + len)
+        })?;
+
+        if !reader.is_empty() || functions.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            scopes,
+            globals,
+            functions,
+        })
+    }
+
+    /// Checks that the global bindings referenced by the code resolve to the same kind of binding
+    /// as when the code was compiled.
+    ///
+    /// `declared` are the names of the global lexical bindings that are declared by the code
+    /// itself, which are not yet in `global_scope`.
+    pub(crate) fn check_globals(&self, global_scope: &Scope, declared: &[JsString]) -> bool {
+        self.globals.iter().all(|(name, index)| {
+            let exists = global_scope.has_binding(name) || declared.contains(name);
+            exists == index.is_some()
+        })
+    }
+}
+
+/// The source of a range of functions of the function table.
+#[derive(Debug, Clone)]
+pub(crate) struct CodeSource {
+    /// The index of the first function compiled from this source.
+    ///
+    /// The functions up to the first function of the next source were compiled from this source.
+    pub(crate) first_function: u32,
+    pub(crate) text: SourceText,
+    pub(crate) path: SourcePath,
+    /// The parser identifier assigned to the template sites of this source.
+    pub(crate) template_identifier: u32,
+}
+
+/// The decoded state of a code cache, used to materialize its [`CodeBlock`]s.
+pub(crate) struct CachedCode {
+    payload: Box<[u8]>,
+    functions: Box<[Range<usize>]>,
+
+    /// The created scopes, the first one being the global scope of the realm.
+    scopes: Box<[Scope]>,
+
+    /// Maps the indices of global declarative bindings when the code was compiled to their
+    /// current indices.
+    globals: FxHashMap<u32, u32>,
+
+    /// The sources of the functions, ordered by their first function.
+    sources: Box<[CodeSource]>,
+}
+
+impl std::fmt::Debug for CachedCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedCode")
+            .field("functions", &self.functions.len())
+            .field("scopes", &self.scopes.len())
+            .field("sources", &self.sources)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachedCode {
+    /// Creates the scopes of the code.
+    ///
+    /// This must be called after all the global bindings checked by [`Tables::check_globals`]
+    /// were declared in `global_scope`. `sources` must be sorted by their first function, the
+    /// first one starting at the function `0`.
+    pub(crate) fn new(
+        payload: &[u8],
+        tables: Tables,
+        global_scope: &Scope,
+        sources: Vec<CodeSource>,
+    ) -> Option<Rc<Self>> {
+        if sources.first()?.first_function != 0
+            || !sources
+                .windows(2)
+                .all(|pair| pair[0].first_function < pair[1].first_function)
+        {
+            return None;
+        }
+
+        let mut scopes = Vec::with_capacity(tables.scopes.len() + 1);
+        scopes.push(global_scope.clone());
+        for record in tables.scopes {
+            let parent = scopes.get(record.parent as usize)?.clone();
+            scopes.push(Scope::from_bindings(
+                parent,
+                record.function,
+                record.index,
+                record.this_escaped,
+                record.bindings,
+            ));
+        }
+
+        let mut globals = FxHashMap::default();
+        for (name, index) in tables.globals {
+            if let Some(index) = index {
+                let binding = global_scope.get_binding(&name)?;
+                globals.insert(index, binding.binding_index());
+            }
+        }
+
+        Some(Rc::new(Self {
+            payload: payload.into(),
+            functions: tables.functions.into_boxed_slice(),
+            scopes: scopes.into_boxed_slice(),
+            globals,
+            sources: sources.into_boxed_slice(),
+        }))
+    }
+
+    /// Returns the scope at `index` of the scope table.
+    pub(crate) fn scope(&self, index: u32) -> Option<&Scope> {
+        self.scopes.get(index as usize)
+    }
+
+    /// Decodes the code block at `index` of the function table.
+    ///
+    /// Nested functions are not decoded, but referenced by [`LazyCodeBlock`]s.
+    pub(crate) fn function(self: &Rc<Self>, index: u32) -> Option<CodeBlock> {
+        let range = self.functions.get(index as usize)?.clone();
+        let source = self.source(index);
+        let mut reader = Reader::new(&self.payload[range]);
+
+        let name = reader.string()?;
+        let flags = CodeBlockFlags::from_bits(reader.u16()?)?;
+        let length = reader.u32()?;
+        let parameter_length = reader.u32()?;
+        let register_count = reader.u32()?;
+        let this_mode = match reader.u8()? {
+            0 => ThisMode::Lexical,
+            1 => ThisMode::Strict,
+            2 => ThisMode::Global,
+            _ => return None,
+        };
+        let mapped_arguments_binding_indices = reader.seq(|reader| reader.option(Reader::u32))?;
+        let bytecode = Self::bytecode(reader.bytes()?, source.template_identifier);
+
+        let constants = reader.seq(|reader| {
+            Some(match reader.u8()? {
+                0 => Constant::String(reader.string()?),
+                1 => Constant::LazyFunction(LazyCodeBlock::new(self.clone(), reader.u32()?)),
+                2 => Constant::BigInt(JsBigInt::from_string_radix(reader.str()?, 16)?),
+                3 => Constant::Scope(self.scope(reader.u32()?)?.clone()),
+                _ => return None,
+            })
+        })?;
+
+        let bindings = reader.seq(|reader| self.binding(reader))?;
+        let handlers = reader.seq(|reader| {
+            Some(Handler {
+                start: Address::new(reader.u32()?),
+                end: Address::new(reader.u32()?),
+                environment_count: reader.u32()?,
+            })
+        })?;
+        let ic = reader.seq(|reader| reader.string().map(InlineCache::new))?;
+
+        let entries = reader.seq(|reader| {
+            let pc = reader.u32()?;
+            let position = reader.option(|reader| {
+                let line = reader.u32()?;
+                let column = reader.u32()?;
+                (line != 0 && column != 0).then(|| Position::new(line, column))
+            })?;
+            Some(Entry { pc, position })
+        })?;
+        let start = reader.option(|reader| {
+            let line = reader.u32()?;
+            let column = reader.u32()?;
+            (line != 0 && column != 0).then(|| Position::new(line, column))
+        })?;
+        let span = reader.option(|reader| {
+            let start = usize::try_from(reader.u64()?).ok()?;
+            let end = usize::try_from(reader.u64()?).ok()?;
+            (start <= end)
+                .then(|| LinearSpan::new(LinearPosition::new(start), LinearPosition::new(end)))
+        })?;
+
+        let global_lexs = reader.seq(Reader::u32)?;
+        let global_fns = reader.seq(|reader| {
+            Some(GlobalFunctionBinding {
+                name_index: reader.u32()?,
+                function_index: reader.u32()?,
+            })
+        })?;
+        let global_vars = reader.seq(Reader::u32)?;
+
+        if !reader.is_empty() {
+            return None;
+        }
+
+        Some(CodeBlock {
+            flags: Cell::new(flags),
+            length,
+            parameter_length,
+            register_count,
+            this_mode,
+            mapped_arguments_binding_indices: mapped_arguments_binding_indices.into(),
+            bytecode,
+            constants: constants.into(),
+            bindings: bindings.into_boxed_slice(),
+            handlers: handlers.into(),
+            ic: ic.into_boxed_slice(),
+            source_info: SourceInfo::new(
+                SourceMap::new(entries.into_boxed_slice(), source.path.clone(), start),
+                name,
+                SpannedSourceText::new(source.text.clone(), span),
+            ),
+            global_lexs: global_lexs.into_boxed_slice(),
+            global_fns: global_fns.into_boxed_slice(),
+            global_vars: global_vars.into_boxed_slice(),
+            debug_id: CodeBlock::get_next_codeblock_id(),
+            #[cfg(feature = "trace")]
+            traced: Cell::new(false),
+            // The debugger information is not cached, so the bindings stored in registers and
+            // the column breakpoints of cached code are not available to the debugger.
+            #[cfg(feature = "debugger")]
+            debug_info: crate::debugger::CodeBlockDebugInfo::default(),
+            uncompiled: None,
+        })
+    }
+
+    /// Returns the source the function at `index` of the function table was compiled from.
+    fn source(&self, index: u32) -> &CodeSource {
+        let position = self
+            .sources
+            .partition_point(|source| source.first_function <= index);
+        &self.sources[position - 1]
+    }
+
+    /// Creates the bytecode of a function, updating the tagged template sites to the identifier
+    /// of its source.
+    ///
+    /// Template sites are unique per parsed source, which would not be the case for two loads of
+    /// the same cache.
+    fn bytecode(bytes: &[u8], template_identifier: u32) -> Bytecode {
+        let mut bytecode = Bytecode {
+            bytes: bytes.into(),
+        };
+
+        let sites = InstructionIterator::new(&bytecode)
+            .filter_map(|(pc, _, instruction)| match instruction {
+                // The site follows the opcode and the jump address.
+                Instruction::TemplateLookup { site, .. } => Some((pc + 5, site)),
+                // The site directly follows the opcode.
+                Instruction::TemplateCreate { site, .. } => Some((pc + 1, site)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for (offset, site) in sites {
+            let site = (site & !u64::from(u32::MAX)) | u64::from(template_identifier);
+            bytecode.bytes[offset..offset + size_of::<u64>()].copy_from_slice(&site.to_le_bytes());
+        }
+
+        bytecode
+    }
+
+    fn binding(&self, reader: &mut Reader<'_>) -> Option<BindingLocator> {
+        let mut binding = read_binding(reader)?;
+        if binding.scope() == BindingLocatorScope::GlobalDeclarative {
+            binding.set_binding_index(*self.globals.get(&binding.binding_index())?);
+        }
+        Some(binding)
+    }
+}
+
+/// Reads a binding written by [`Encoder::binding`][super::Encoder::binding].
+///
+/// The indices of global declarative bindings are not remapped, see [`CachedCode::binding`].
+pub(crate) fn read_binding(reader: &mut Reader<'_>) -> Option<BindingLocator> {
+    let name = reader.string()?;
+    let scope = match reader.u32()? {
+        0 => BindingLocatorScope::GlobalObject,
+        1 => BindingLocatorScope::GlobalDeclarative,
+        index => BindingLocatorScope::Stack(index - 2),
+    };
+    let binding_index = reader.u32()?;
+    let unique_scope_id = reader.u32()?;
+    Some(BindingLocator::from_parts(
+        name,
+        scope,
+        binding_index,
+        unique_scope_id,
+    ))
+}
+
+/// A function constant of a [`CodeBlock`] loaded from a code cache, which is only decoded when
+/// it is first used.
+#[derive(Clone, Trace, Finalize)]
+pub(crate) struct LazyCodeBlock {
+    // Shared between clones, so a function is decoded at most once.
+    code: Gc<OnceCell<Gc<CodeBlock>>>,
+    // SAFETY: `CachedCode` doesn't contain any traceable types.
+    #[unsafe_ignore_trace]
+    cache: Rc<CachedCode>,
+    index: u32,
+}
+
+impl std::fmt::Debug for LazyCodeBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyCodeBlock")
+            .field("code", &self.code)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyCodeBlock {
+    fn new(cache: Rc<CachedCode>, index: u32) -> Self {
+        Self {
+            code: Gc::new(OnceCell::new()),
+            cache,
+            index,
+        }
+    }
+
+    /// Returns the code block, decoding it if this is the first use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code block cannot be decoded. The checksum of the cache is verified when it
+    /// is loaded, so this can only happen if the cache was produced by an incompatible encoder.
+    pub(crate) fn get(&self) -> Gc<CodeBlock> {
+        self.code
+            .get_or_init(|| {
+                let code = self
+                    .cache
+                    .function(self.index)
+                    .expect("code cache must contain a valid function");
+                Gc::new(code)
+            })
+            .clone()
+    }
+
+    /// Returns the code block if it was already decoded.
+    pub(crate) fn get_if_decoded(&self) -> Option<&Gc<CodeBlock>> {
+        self.code.get()
+    }
+}
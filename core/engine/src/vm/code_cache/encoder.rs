@@ -0,0 +1,218 @@
+//! Encoding of [`CodeBlock`]s into a code cache.
+
+use boa_ast::scope::{BindingLocator, BindingLocatorScope, Scope};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::Writer;
+use crate::{
+    JsString,
+    builtins::function::ThisMode,
+    vm::{CodeBlock, Constant},
+};
+
+/// Encodes the [`CodeBlock`]s and [`Scope`]s of a script or module into the payload of a code
+/// cache.
+#[derive(Debug, Default)]
+pub(crate) struct Encoder {
+    /// Maps the unique id of each encoded scope to its index in the scope table.
+    scope_indices: FxHashMap<u32, u32>,
+    scopes: Vec<Vec<u8>>,
+
+    /// The bindings resolved outside of the compiled code, which must resolve the same way when
+    /// the cache is loaded.
+    globals: Vec<(JsString, Option<u32>)>,
+    seen_globals: FxHashSet<(JsString, Option<u32>)>,
+
+    /// Maps the address of each encoded code block to its index in the function table.
+    function_indices: FxHashMap<usize, u32>,
+    functions: Vec<Vec<u8>>,
+}
+
+impl Encoder {
+    /// Adds a scope and its outer scopes to the scope table, returning its index.
+    ///
+    /// The index `0` is reserved for the global scope of the realm.
+    pub(crate) fn scope(&mut self, scope: &Scope) -> u32 {
+        let Some(outer) = scope.outer() else {
+            return 0;
+        };
+        if let Some(index) = self.scope_indices.get(&scope.unique_id()) {
+            return *index;
+        }
+
+        let parent = self.scope(outer);
+
+        let mut writer = Writer::default();
+        writer.u32(parent);
+        writer.bool(scope.is_function());
+        writer.u32(scope.scope_index());
+        writer.bool(scope.escaped_this());
+        writer.seq(scope.bindings().into_iter(), |writer, binding| {
+            writer.string(binding.name());
+            writer.u32(binding.index());
+            writer.u8(binding.flags());
+        });
+
+        self.scopes.push(writer.into_bytes());
+        let index = self.scopes.len() as u32;
+        self.scope_indices.insert(scope.unique_id(), index);
+        index
+    }
+
+    /// Adds a code block and all its nested functions to the function table, returning its index.
+    ///
+    /// A code block that is already in the table is not added again.
+    pub(crate) fn function(&mut self, code: &CodeBlock) -> u32 {
+        let address = std::ptr::from_ref(code) as usize;
+        if let Some(index) = self.function_indices.get(&address) {
+            return *index;
+        }
+
+        let index = self.functions.len();
+        self.functions.push(Vec::new());
+        self.function_indices.insert(address, index as u32);
+
+        let mut writer = Writer::default();
+        writer.string(code.name());
+
+        let flags = code.flags.get();
+        // Tracing is enabled by the context that compiles the code, so it isn't cached.
+        #[cfg(feature = "trace")]
+        let flags = flags.difference(crate::vm::CodeBlockFlags::TRACEABLE);
+        writer.u16(flags.bits());
+        writer.u32(code.length);
+        writer.u32(code.parameter_length);
+        writer.u32(code.register_count);
+        writer.u8(match code.this_mode {
+            ThisMode::Lexical => 0,
+            ThisMode::Strict => 1,
+            ThisMode::Global => 2,
+        });
+        writer.seq(
+            code.mapped_arguments_binding_indices.iter(),
+            |writer, index| writer.option(*index, Writer::u32),
+        );
+        writer.bytes(&code.bytecode.bytes);
+
+        writer.len(code.constants.len());
+        for constant in &code.constants {
+            match constant {
+                Constant::String(string) => {
+                    writer.u8(0);
+                    writer.string(string);
+                }
+                Constant::Function(function) => {
+                    writer.u8(1);
+                    let index = self.function(function);
+                    writer.u32(index);
+                }
+                Constant::LazyFunction(function) => {
+                    writer.u8(1);
+                    let index = self.function(&function.get());
+                    writer.u32(index);
+                }
+                Constant::UncompiledFunction(function) => {
+                    writer.u8(1);
+                    let index = self.function(&CodeBlock::compiled(function));
+                    writer.u32(index);
+                }
+                Constant::BigInt(bigint) => {
+                    writer.u8(2);
+                    writer.str(&bigint.to_string_radix(16));
+                }
+                Constant::Scope(scope) => {
+                    writer.u8(3);
+                    let index = self.scope(scope);
+                    writer.u32(index);
+                }
+            }
+        }
+
+        writer.len(code.bindings.len());
+        for binding in &code.bindings {
+            self.binding(&mut writer, binding);
+        }
+
+        writer.seq(code.handlers.iter(), |writer, handler| {
+            writer.u32(handler.start.as_u32());
+            writer.u32(handler.end.as_u32());
+            writer.u32(handler.environment_count);
+        });
+        writer.seq(code.ic.iter(), |writer, ic| writer.string(&ic.name));
+
+        let source_info = code.source_info();
+        writer.seq(source_info.map().entries().iter(), |writer, entry| {
+            writer.u32(entry.pc);
+            writer.option(entry.position, |writer, position| {
+                writer.u32(position.line_number());
+                writer.u32(position.column_number());
+            });
+        });
+        writer.option(source_info.map().start(), |writer, position| {
+            writer.u32(position.line_number());
+            writer.u32(position.column_number());
+        });
+        writer.option(source_info.text_spanned().span(), |writer, span| {
+            writer.u64(span.start().pos() as u64);
+            writer.u64(span.end().pos() as u64);
+        });
+
+        writer.seq(code.global_lexs.iter(), |writer, index| writer.u32(*index));
+        writer.seq(code.global_fns.iter(), |writer, binding| {
+            writer.u32(binding.name_index);
+            writer.u32(binding.function_index);
+        });
+        writer.seq(code.global_vars.iter(), |writer, index| writer.u32(*index));
+
+        self.functions[index] = writer.into_bytes();
+        index as u32
+    }
+
+    /// Returns the number of functions in the function table.
+    pub(crate) fn function_count(&self) -> u32 {
+        self.functions.len() as u32
+    }
+
+    /// Writes a binding, adding it to the global bindings if it isn't resolved in a scope of
+    /// the code.
+    pub(crate) fn binding(&mut self, writer: &mut Writer, binding: &BindingLocator) {
+        writer.string(binding.name());
+        let global = match binding.scope() {
+            BindingLocatorScope::GlobalObject => {
+                writer.u32(0);
+                Some(None)
+            }
+            BindingLocatorScope::GlobalDeclarative => {
+                writer.u32(1);
+                Some(Some(binding.binding_index()))
+            }
+            BindingLocatorScope::Stack(index) => {
+                writer.u32(index + 2);
+                None
+            }
+        };
+        writer.u32(binding.binding_index());
+        writer.u32(binding.unique_scope_id());
+
+        if let Some(index) = global {
+            let global = (binding.name().clone(), index);
+            if self.seen_globals.insert(global.clone()) {
+                self.globals.push(global);
+            }
+        }
+    }
+
+    /// Finishes the payload, appending the tables to the kind specific `section`.
+    pub(crate) fn finish(self, section: Writer) -> Vec<u8> {
+        let mut writer = section;
+        writer.seq(self.scopes.iter(), |writer, scope| writer.raw(scope));
+        writer.seq(self.globals.iter(), |writer, (name, index)| {
+            writer.string(name);
+            writer.option(*index, Writer::u32);
+        });
+        writer.seq(self.functions.iter(), |writer, function| {
+            writer.bytes(function);
+        });
+        writer.into_bytes()
+    }
+}
@@ -0,0 +1,523 @@
+//! Serialization of compiled [`CodeBlock`]s to a bytecode cache.
+//!
+//! Running a source requires parsing it and compiling the resulting AST with the
+//! [`ByteCompiler`][crate::bytecompiler::ByteCompiler]. A code cache stores the result of both
+//! steps, which allows loading a [`Script`][crate::Script] or a [`Module`][crate::Module] without
+//! doing either of them again.
+//!
+//! # Format
+//!
+//! All integers are stored in little endian. Strings are stored as their length followed by their
+//! UTF-16 code units, and sequences are stored as their length followed by their elements.
+//!
+//! A cache starts with a header, which must match exactly for the cache to be used:
+//!
+//! - The magic bytes `BOAC`.
+//! - The [`FORMAT_VERSION`], followed by the version of `boa_engine`.
+//! - The [`CacheKind`] of the cached code.
+//! - The compile options, see [`compile_options`].
+//! - A hash of the source text the code was compiled from.
+//! - A checksum of the payload.
+//!
+//! The payload contains a section specific to the [`CacheKind`], followed by the table of
+//! [`Scope`][boa_ast::scope::Scope]s used by the code, the global bindings referenced by the code
+//! and the table of [`CodeBlock`]s. The first code block is the top level code, the others are
+//! only decoded when the function constant referencing them is first used.
+
+use boa_ast::scope::{BindingLocator, Scope, ScopeBinding};
+use boa_gc::Gc;
+use boa_interner::Interner;
+
+use crate::{
+    Context, JsString,
+    spanned_source_text::SourceText,
+    vm::{CodeBlock, SourcePath},
+};
+
+mod decoder;
+mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use decoder::{CachedCode, CodeSource, LazyCodeBlock, Tables};
+pub(crate) use encoder::Encoder;
+
+use decoder::read_binding;
+
+/// The version of the code cache format.
+///
+/// This must be bumped whenever the layout of the cache or the encoding of the bytecode changes.
+pub(crate) const FORMAT_VERSION: u32 = 1;
+
+/// The magic bytes at the start of every code cache.
+const MAGIC: &[u8; 4] = b"BOAC";
+
+/// The kind of code stored in a code cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum CacheKind {
+    Script = 0,
+    Module = 1,
+}
+
+/// Returns the options that change the output of the parser and the compiler.
+///
+/// The code in a cache is only used if it was compiled with the same options.
+pub(crate) fn compile_options(context: &Context) -> u32 {
+    let mut options = u32::from(context.optimizer_options().bits());
+    if context.is_strict() {
+        options |= 1 << 8;
+    }
+    if cfg!(feature = "annex-b") {
+        options |= 1 << 9;
+    }
+    // The debugger and code coverage record the position of every statement.
+    if cfg!(any(feature = "debugger", feature = "coverage")) {
+        options |= 1 << 10;
+    }
+    options
+}
+
+/// Hashes a source text, to check if a cache was compiled from it.
+pub(crate) fn hash_source(code_units: &[u16]) -> u64 {
+    code_units.iter().fold(FNV_OFFSET_BASIS, |hash, unit| {
+        fnv1a(hash, &unit.to_le_bytes())
+    })
+}
+
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64 bit [FNV-1a][fnv] hash, which is stable across platforms and releases.
+///
+/// [fnv]: https://datatracker.ietf.org/doc/html/draft-eastlake-fnv
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Encodes the compiled code of a script.
+///
+/// The script specific section contains the global lexical declarations of the script, which are
+/// created in the global scope when the script is parsed, and the names of its global var
+/// declarations.
+pub(crate) fn encode_script(
+    code: &CodeBlock,
+    source_text: &SourceText,
+    global_scope: &Scope,
+    context: &Context,
+) -> Vec<u8> {
+    let mut section = Writer::default();
+
+    let bindings = global_scope.bindings();
+    section.seq(code.global_lexs.iter(), |writer, index| {
+        let name = code.constant_string(*index as usize);
+        let flags = bindings
+            .iter()
+            .find(|binding| binding.name() == &name)
+            .map_or(0, ScopeBinding::flags);
+        writer.string(&name);
+        writer.u8(flags);
+    });
+
+    let var_names = code
+        .global_vars
+        .iter()
+        .copied()
+        .chain(code.global_fns.iter().map(|function| function.name_index))
+        .map(|index| code.constant_string(index as usize))
+        .collect::<Vec<_>>();
+    section.seq(var_names.iter(), Writer::string);
+
+    let mut encoder = Encoder::default();
+    encoder.function(code);
+    let payload = encoder.finish(section);
+
+    write_cache(
+        CacheKind::Script,
+        compile_options(context),
+        hash_source(source_text.code_units()),
+        &payload,
+    )
+}
+
+/// Decodes the compiled code of a script, declaring its global lexical bindings in
+/// `global_scope`.
+///
+/// Returns `None` if the cache cannot be used for this source, in which case `global_scope` is
+/// not modified.
+pub(crate) fn decode_script(
+    cache: &[u8],
+    source_text: &SourceText,
+    path: SourcePath,
+    global_scope: &Scope,
+    context: &mut Context,
+) -> Option<Gc<CodeBlock>> {
+    let payload = read_cache(
+        cache,
+        CacheKind::Script,
+        compile_options(context),
+        hash_source(source_text.code_units()),
+    )?;
+    let mut reader = Reader::new(payload);
+
+    let lexical_bindings =
+        reader.seq(|reader| Some(ScopeBinding::new(reader.string()?, 0, reader.u8()?)))?;
+    let var_names = reader.seq(Reader::string)?;
+    let tables = Tables::read(payload, &mut reader)?;
+
+    // Same checks as the scope analysis of a script, a conflict is reported as a syntax error
+    // when parsing the source.
+    if lexical_bindings
+        .iter()
+        .any(|binding| global_scope.has_binding(binding.name()))
+        || var_names
+            .iter()
+            .any(|name| global_scope.has_lex_binding(name))
+    {
+        return None;
+    }
+
+    let declared = lexical_bindings
+        .iter()
+        .map(|binding| binding.name().clone())
+        .collect::<Vec<_>>();
+    if !tables.check_globals(global_scope, &declared) {
+        return None;
+    }
+
+    for binding in lexical_bindings {
+        if binding.is_mutable() {
+            drop(global_scope.create_mutable_binding(binding.name().clone(), false));
+        } else {
+            global_scope.create_immutable_binding(binding.name().clone(), binding.is_strict());
+        }
+    }
+
+    let code = CachedCode::new(
+        payload,
+        tables,
+        global_scope,
+        vec![CodeSource {
+            first_function: 0,
+            text: source_text.clone(),
+            path,
+            template_identifier: context.next_parser_identifier(),
+        }],
+    )?;
+    code.function(0).map(Gc::new)
+}
+
+/// Encodes the compiled code of a module.
+///
+/// The module specific section contains the scope of the module and the bindings initialized
+/// with its function declarations, followed by the records written by `records`.
+pub(crate) fn encode_module(
+    code: &CodeBlock,
+    scope: &Scope,
+    functions: &[(u32, BindingLocator)],
+    source_text: &SourceText,
+    context: &Context,
+    records: impl FnOnce(&mut Writer),
+) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    let mut section = Writer::default();
+
+    section.u32(encoder.scope(scope));
+    section.seq(functions.iter(), |writer, (index, binding)| {
+        writer.u32(*index);
+        encoder.binding(writer, binding);
+    });
+    records(&mut section);
+
+    encoder.function(code);
+    let payload = encoder.finish(section);
+
+    write_cache(
+        CacheKind::Module,
+        compile_options(context),
+        hash_source(source_text.code_units()),
+        &payload,
+    )
+}
+
+/// The compiled code of a module, decoded from a code cache.
+#[derive(Debug)]
+pub(crate) struct DecodedModule<T> {
+    pub(crate) scope: Scope,
+    pub(crate) codeblock: Gc<CodeBlock>,
+    pub(crate) functions: Vec<(u32, BindingLocator)>,
+    pub(crate) records: T,
+}
+
+/// Decodes the compiled code of a module, reading the records written to [`encode_module`] with
+/// `records`.
+///
+/// Returns `None` if the cache cannot be used for this source.
+pub(crate) fn decode_module<T>(
+    cache: &[u8],
+    source_text: &SourceText,
+    path: SourcePath,
+    global_scope: &Scope,
+    context: &mut Context,
+    records: impl FnOnce(&mut Reader<'_>, &mut Interner) -> Option<T>,
+) -> Option<DecodedModule<T>> {
+    let payload = read_cache(
+        cache,
+        CacheKind::Module,
+        compile_options(context),
+        hash_source(source_text.code_units()),
+    )?;
+    let mut reader = Reader::new(payload);
+
+    let scope = reader.u32()?;
+    let functions = reader.seq(|reader| Some((reader.u32()?, read_binding(reader)?)))?;
+    let records = records(&mut reader, context.interner_mut())?;
+    let tables = Tables::read(payload, &mut reader)?;
+
+    // The scope of a module is never the global scope.
+    if scope == 0 || !tables.check_globals(global_scope, &[]) {
+        return None;
+    }
+
+    let code = CachedCode::new(
+        payload,
+        tables,
+        global_scope,
+        vec![CodeSource {
+            first_function: 0,
+            text: source_text.clone(),
+            path,
+            template_identifier: context.next_parser_identifier(),
+        }],
+    )?;
+
+    Some(DecodedModule {
+        scope: code.scope(scope)?.clone(),
+        codeblock: Gc::new(code.function(0)?),
+        functions,
+        records,
+    })
+}
+
+/// Prepends the header to the payload of a code cache.
+pub(crate) fn write_cache(
+    kind: CacheKind,
+    options: u32,
+    source_hash: u64,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.raw(MAGIC);
+    writer.u32(FORMAT_VERSION);
+    writer.str(env!("CARGO_PKG_VERSION"));
+    writer.u8(kind as u8);
+    writer.u32(options);
+    writer.u64(source_hash);
+    writer.u64(fnv1a(FNV_OFFSET_BASIS, payload));
+    writer.raw(payload);
+    writer.into_bytes()
+}
+
+/// Checks the header of a code cache, returning its payload if the cache can be used.
+pub(crate) fn read_cache(
+    bytes: &[u8],
+    kind: CacheKind,
+    options: u32,
+    source_hash: u64,
+) -> Option<&[u8]> {
+    let mut reader = Reader::new(bytes);
+    let valid = reader.take(MAGIC.len())? == MAGIC
+        && reader.u32()? == FORMAT_VERSION
+        && reader.str()? == env!("CARGO_PKG_VERSION")
+        && reader.u8()? == kind as u8
+        && reader.u32()? == options
+        && reader.u64()? == source_hash;
+    if !valid {
+        return None;
+    }
+
+    let checksum = reader.u64()?;
+    let payload = reader.rest();
+    (fnv1a(FNV_OFFSET_BASIS, payload) == checksum).then_some(payload)
+}
+
+/// Serializes values into a code cache.
+#[derive(Debug, Default)]
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub(crate) fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub(crate) fn str(&mut self, string: &str) {
+        self.bytes(string.as_bytes());
+    }
+
+    pub(crate) fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub(crate) fn bool(&mut self, value: bool) {
+        self.u8(value.into());
+    }
+
+    pub(crate) fn u16(&mut self, value: u16) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u32(&mut self, value: u32) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    pub(crate) fn u64(&mut self, value: u64) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    pub(crate) fn len(&mut self, len: usize) {
+        self.u32(
+            u32::try_from(len).expect("code cache sequences cannot exceed `u32::MAX` elements"),
+        );
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.raw(bytes);
+    }
+
+    pub(crate) fn string(&mut self, string: &JsString) {
+        self.len(string.len());
+        for unit in string {
+            self.u16(unit);
+        }
+    }
+
+    pub(crate) fn option<T>(&mut self, value: Option<T>, f: impl FnOnce(&mut Self, T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            f(self, value);
+        }
+    }
+
+    pub(crate) fn seq<T>(
+        &mut self,
+        values: impl ExactSizeIterator<Item = T>,
+        mut f: impl FnMut(&mut Self, T),
+    ) {
+        self.len(values.len());
+        for value in values {
+            f(self, value);
+        }
+    }
+}
+
+/// Deserializes values from a code cache.
+///
+/// All the methods return `None` if the input is too short or contains an invalid value.
+#[derive(Debug, Clone)]
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub(crate) const fn rest(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let (head, tail) = self.bytes.split_at_checked(len)?;
+        self.bytes = tail;
+        Some(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    pub(crate) fn str(&mut self) -> Option<&'a str> {
+        std::str::from_utf8(self.bytes()?).ok()
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.array().map(u8::from_le_bytes)
+    }
+
+    pub(crate) fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    pub(crate) fn len(&mut self) -> Option<usize> {
+        self.u32().map(|len| len as usize)
+    }
+
+    pub(crate) fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    pub(crate) fn string(&mut self) -> Option<JsString> {
+        let len = self.len()?;
+        let bytes = self.take(len.checked_mul(2)?)?;
+        let units = bytes
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+            .collect::<Vec<_>>();
+        Some(JsString::from(units.as_slice()))
+    }
+
+    /// Reads an optional value. The outer `Option` is `None` if the input is invalid.
+    #[allow(clippy::option_option)]
+    pub(crate) fn option<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Option<T>,
+    ) -> Option<Option<T>> {
+        if self.bool()? {
+            f(self).map(Some)
+        } else {
+            Some(None)
+        }
+    }
+
+    pub(crate) fn seq<T>(&mut self, mut f: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.len()?;
+        // Every element takes at least a byte, which bounds the allocation for invalid lengths.
+        let mut values = Vec::with_capacity(len.min(self.bytes.len()));
+        for _ in 0..len {
+            values.push(f(self)?);
+        }
+        Some(values)
+    }
+}
@@ -0,0 +1,346 @@
+use std::rc::Rc;
+
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::decode_script;
+use crate::{
+    Context, JsNativeError, JsNativeErrorKind, JsValue, Module, Script,
+    builtins::promise::PromiseState,
+    js_string,
+    module::MapModuleLoader,
+    spanned_source_text::SourceText,
+    vm::{CodeBlock, Constant, source_info::SourcePath},
+};
+
+/// Creates the code cache of a script in a new context.
+fn script_cache(source: &str) -> Vec<u8> {
+    let context = &mut Context::default();
+    let script = Script::parse(Source::from_bytes(source), None, context).unwrap();
+    script.to_code_cache(context).unwrap()
+}
+
+/// Loads a script from a code cache and evaluates it.
+fn eval_cached(source: &str, cache: &[u8], context: &mut Context) -> JsValue {
+    let script =
+        Script::parse_with_code_cache(Source::from_bytes(source), cache, None, context).unwrap();
+    script.evaluate(context).unwrap()
+}
+
+/// Checks if `cache` can be used for `source` in `context`.
+fn is_usable(source: &str, cache: &[u8], context: &mut Context) -> bool {
+    let source_text = Source::from_bytes(source).read_source_text().unwrap();
+    let source_text = SourceText::new(source_text);
+    let scope = context.realm().scope().clone();
+    decode_script(cache, &source_text, SourcePath::None, &scope, context).is_some()
+}
+
+#[test]
+fn script_round_trip() {
+    let source = indoc! {r#"
+        class Counter {
+            #count = 0n;
+            increment() { return ++this.#count; }
+        }
+        function* range(n) { for (let i = 0; i < n; i++) yield i; }
+        const adders = [...range(3)].map((i) => (x) => x + i);
+        let counter = new Counter();
+        counter.increment();
+        let label;
+        try {
+            null.property;
+        } catch {
+            label = `${counter.increment()}`;
+        }
+        adders.map((add) => add(10)).join() + ":" + label + ":" + typeof range
+    "#};
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    assert!(is_usable(source, &cache, context));
+
+    let context = &mut Context::default();
+    assert_eq!(
+        eval_cached(source, &cache, context),
+        js_string!("10,11,12:2:function").into()
+    );
+}
+
+#[test]
+fn function_source_text() {
+    let source = "function add(a, b) { return a + b; } add.toString()";
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    assert_eq!(
+        eval_cached(source, &cache, context),
+        js_string!("function add(a, b) { return a + b; }").into()
+    );
+}
+
+#[test]
+fn nested_functions_are_decoded_lazily() {
+    let source = indoc! {r#"
+        function used() { return 1; }
+        function unused() { return function inner() { return 2; }; }
+        used()
+    "#};
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    let script =
+        Script::parse_with_code_cache(Source::from_bytes(source), &cache, None, context).unwrap();
+    let code = script.codeblock(context).unwrap();
+
+    let functions = |code: &CodeBlock| {
+        code.constants
+            .iter()
+            .filter_map(|constant| match constant {
+                Constant::LazyFunction(function) => Some(function.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let declarations = functions(&code);
+    assert_eq!(declarations.len(), 2);
+    assert!(
+        declarations
+            .iter()
+            .all(|function| function.get_if_decoded().is_none())
+    );
+
+    assert_eq!(script.evaluate(context).unwrap(), JsValue::new(1));
+
+    // Function declarations are instantiated before the script runs, but the functions nested in
+    // them are only decoded once they are created.
+    let unused = declarations[1].get_if_decoded().unwrap();
+    let nested = functions(unused);
+    assert_eq!(nested.len(), 1);
+    assert!(nested[0].get_if_decoded().is_none());
+}
+
+#[test]
+fn source_mismatch_falls_back_to_parsing() {
+    let cache = script_cache("1 + 1");
+
+    let context = &mut Context::default();
+    assert!(!is_usable("1 + 2", &cache, context));
+    assert_eq!(eval_cached("1 + 2", &cache, context), JsValue::new(3));
+}
+
+#[test]
+fn invalid_cache_falls_back_to_parsing() {
+    let source = "let value = 'source'; value";
+    let cache = script_cache(source);
+
+    let mut corrupted = cache.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xFF;
+    let truncated = &cache[..cache.len() / 2];
+
+    for cache in [&corrupted[..], truncated, &[]] {
+        let context = &mut Context::default();
+        assert!(!is_usable(source, cache, context));
+        assert_eq!(
+            eval_cached(source, cache, context),
+            js_string!("source").into()
+        );
+    }
+}
+
+#[test]
+fn options_mismatch_falls_back_to_parsing() {
+    let source = "(function() { return this === undefined; })()";
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    context.strict(true);
+    assert!(!is_usable(source, &cache, context));
+    assert_eq!(eval_cached(source, &cache, context), JsValue::new(true));
+}
+
+#[test]
+fn global_lexical_conflict_is_a_syntax_error() {
+    let source = "let value = 2;";
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    Script::parse(Source::from_bytes("let value = 1;"), None, context)
+        .unwrap()
+        .evaluate(context)
+        .unwrap();
+
+    assert!(!is_usable(source, &cache, context));
+    let error = Script::parse_with_code_cache(Source::from_bytes(source), &cache, None, context)
+        .unwrap_err();
+    assert_eq!(
+        error.as_native().map(JsNativeError::kind),
+        Some(&JsNativeErrorKind::Syntax)
+    );
+}
+
+#[test]
+fn global_declarative_bindings_are_remapped() {
+    let source = "typeof first + ':' + second";
+    let cache = {
+        let context = &mut Context::default();
+        Script::parse(Source::from_bytes("let second = 1;"), None, context)
+            .unwrap()
+            .evaluate(context)
+            .unwrap();
+        let script = Script::parse(Source::from_bytes(source), None, context).unwrap();
+        script.to_code_cache(context).unwrap()
+    };
+
+    // `first` is a global object binding when the cache is created.
+    let context = &mut Context::default();
+    Script::parse(
+        Source::from_bytes("let first = 1; let second = 2;"),
+        None,
+        context,
+    )
+    .unwrap()
+    .evaluate(context)
+    .unwrap();
+    assert!(!is_usable(source, &cache, context));
+
+    let context = &mut Context::default();
+    Script::parse(
+        Source::from_bytes("var first = 1; let other = 0; let second = 2;"),
+        None,
+        context,
+    )
+    .unwrap()
+    .evaluate(context)
+    .unwrap();
+    assert!(is_usable(source, &cache, context));
+    assert_eq!(
+        eval_cached(source, &cache, context),
+        js_string!("number:2").into()
+    );
+}
+
+#[test]
+fn global_lexical_declarations_are_declared() {
+    let source = "const constant = 1; let variable = 2;";
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    eval_cached(source, &cache, context);
+
+    let result = Script::parse(
+        Source::from_bytes("variable = 3; try { constant = 2 } catch (e) { e.name }"),
+        None,
+        context,
+    )
+    .unwrap()
+    .evaluate(context)
+    .unwrap();
+    assert_eq!(result, js_string!("TypeError").into());
+}
+
+#[test]
+fn template_objects_are_unique_per_load() {
+    let source = indoc! {r#"
+        var templates = globalThis.templates ?? [];
+        function tag(strings) { return strings; }
+        function get() { return tag`site`; }
+        templates.push(get(), get());
+        globalThis.templates = templates;
+        templates.length
+    "#};
+    let cache = script_cache(source);
+
+    let context = &mut Context::default();
+    eval_cached(source, &cache, context);
+    eval_cached(source, &cache, context);
+
+    let result = Script::parse(
+        Source::from_bytes(
+            "templates[0] === templates[1] && templates[2] === templates[3] && templates[0] !== templates[2]",
+        ),
+        None,
+        context,
+    )
+    .unwrap()
+    .evaluate(context)
+    .unwrap();
+    assert_eq!(result, JsValue::new(true));
+}
+
+#[test]
+fn module_round_trip() {
+    let dependency = indoc! {r#"
+        export function double(x) { return x * 2; }
+        export const name = "dependency";
+    "#};
+    let main = indoc! {r#"
+        import { double, name } from "dependency";
+        import * as namespace from "dependency";
+        export { double as twice };
+        export const result = `${double(21)}:${name}:${Object.keys(namespace)}`;
+    "#};
+
+    let (dependency_cache, main_cache) = {
+        let context = &mut Context::default();
+        let cache = |source: &str, context: &mut Context| {
+            Module::parse(Source::from_bytes(source), None, context)
+                .unwrap()
+                .to_code_cache(context)
+                .unwrap()
+        };
+        (cache(dependency, context), cache(main, context))
+    };
+
+    let loader = Rc::new(MapModuleLoader::default());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let module = Module::parse_with_code_cache(
+        Source::from_bytes(dependency),
+        &dependency_cache,
+        None,
+        context,
+    )
+    .unwrap();
+    loader.insert("dependency", module);
+
+    let module =
+        Module::parse_with_code_cache(Source::from_bytes(main), &main_cache, None, context)
+            .unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs().unwrap();
+    assert!(
+        matches!(promise.state(), PromiseState::Fulfilled(_)),
+        "{:?}",
+        promise.state()
+    );
+
+    let namespace = module.namespace(context);
+    assert_eq!(
+        namespace.get(js_string!("result"), context).unwrap(),
+        js_string!("42:dependency:double,name").into()
+    );
+    let twice = namespace.get(js_string!("twice"), context).unwrap();
+    assert_eq!(
+        twice
+            .as_callable()
+            .unwrap()
+            .call(&JsValue::undefined(), &[JsValue::new(5)], context)
+            .unwrap(),
+        JsValue::new(10)
+    );
+}
+
+#[test]
+fn linked_module_cannot_be_cached() {
+    let context = &mut Context::default();
+    let module = Module::parse(Source::from_bytes("export const a = 1;"), None, context).unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs().unwrap();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+
+    assert!(module.to_code_cache(context).is_err());
+}
@@ -362,6 +362,7 @@ impl CodeBlock {
                 | Instruction::SetNameByLocator { .. }
                 | Instruction::PushObjectEnvironment { .. }
                 | Instruction::PopPrivateEnvironment
+                | Instruction::Debugger
                 | Instruction::ImportCall { .. }
                 | Instruction::Exception { .. }
                 | Instruction::MaybeException { .. }
@@ -432,15 +433,28 @@ impl CodeBlock {
                 | Instruction::Reserved55
                 | Instruction::Reserved56
                 | Instruction::Reserved57
-                | Instruction::Reserved58
-                | Instruction::Reserved59 => unreachable!("Reserved opcodes are unreachable"),
+                | Instruction::Reserved58 => unreachable!("Reserved opcodes are unreachable"),
             }
         }
 
         for constant in &self.constants {
-            if let Constant::Function(function) = constant {
-                let subgraph = graph.subgraph(String::new());
-                function.to_graph(subgraph);
+            match constant {
+                Constant::Function(function) => {
+                    let subgraph = graph.subgraph(String::new());
+                    function.to_graph(subgraph);
+                }
+                Constant::LazyFunction(function) => {
+                    let subgraph = graph.subgraph(String::new());
+                    function.get().to_graph(subgraph);
+                }
+                // Functions that were not compiled yet have no instructions to show.
+                Constant::UncompiledFunction(function) => {
+                    if let Some(function) = function.get_if_compiled() {
+                        let subgraph = graph.subgraph(String::new());
+                        function.to_graph(subgraph);
+                    }
+                }
+                _ => {}
             }
         }
     }
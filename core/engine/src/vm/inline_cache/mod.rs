@@ -88,6 +88,10 @@ impl InlineCache {
     /// Opportunistically cleans up stale weak shape references during lookup.
     pub(crate) fn get(&self, shape: &Shape) -> Option<(Shape, Slot)> {
         if self.megamorphic.get() {
+            #[cfg(feature = "vm-stats")]
+            crate::vm_stats::count_inline_cache_lookup(
+                crate::vm_stats::InlineCacheLookup::Megamorphic,
+            );
             return None;
         }
 
@@ -109,6 +113,13 @@ impl InlineCache {
             }
         }
 
+        #[cfg(feature = "vm-stats")]
+        crate::vm_stats::count_inline_cache_lookup(if result.is_some() {
+            crate::vm_stats::InlineCacheLookup::Hit
+        } else {
+            crate::vm_stats::InlineCacheLookup::Miss
+        });
+
         result
     }
 }
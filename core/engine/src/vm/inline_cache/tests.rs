@@ -318,7 +318,7 @@ fn set_internal_method() {
 
 fn get_codeblock(value: &JsValue) -> Option<(JsObject, Gc<CodeBlock>)> {
     let object = value.as_object()?.clone();
-    let code = object.downcast_ref::<OrdinaryFunction>()?.code.clone();
+    let code = OrdinaryFunction::compiled_codeblock(&object)?;
 
     Some((object, code))
 }
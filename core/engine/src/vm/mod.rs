@@ -6,7 +6,10 @@
 
 use crate::{
     Context, JsError, JsExpect, JsNativeError, JsObject, JsResult, JsString, JsValue, Module,
-    builtins::promise::{PromiseCapability, ResolvingFunctions},
+    builtins::{
+        async_generator::AsyncGenerator,
+        promise::{PromiseCapability, ResolvingFunctions},
+    },
     environments::EnvironmentStack,
     error::RuntimeLimitError,
     object::JsFunction,
@@ -16,7 +19,7 @@ use crate::{
     vm::shadow_stack::ErrorStack,
 };
 use boa_gc::{Finalize, Gc, Trace, custom_trace};
-use shadow_stack::ShadowStack;
+use shadow_stack::{AsyncStack, ShadowStack};
 use std::{future::Future, ops::ControlFlow, path::Path, pin::Pin, task};
 
 #[cfg(feature = "trace")]
@@ -36,15 +39,17 @@ pub(crate) use {
 
 pub use runtime_limits::RuntimeLimits;
 pub use {
-    call_frame::{CallFrame, GeneratorResumeKind},
+    call_frame::{CallFrame, CallFrameLocation, GeneratorResumeKind},
     code_block::CodeBlock,
     source_info::{NativeSourceInfo, SourcePath},
+    stack_frame::StackFrame,
 };
 
 pub(crate) use code_block::GlobalFunctionBinding;
 
 mod call_frame;
 mod code_block;
+pub(crate) mod code_cache;
 mod completion_record;
 mod inline_cache;
 mod runtime_limits;
@@ -52,6 +57,7 @@ mod runtime_limits;
 pub(crate) mod opcode;
 pub(crate) mod shadow_stack;
 pub(crate) mod source_info;
+mod stack_frame;
 
 #[cfg(feature = "flowgraph")]
 pub mod flowgraph;
@@ -100,6 +106,22 @@ pub struct Vm {
     pub(crate) trace: bool,
     #[cfg(feature = "trace")]
     pub(crate) current_frame: Option<*const CallFrame>,
+
+    #[cfg(feature = "debugger")]
+    pub(crate) debugger: crate::debugger::DebuggerState,
+
+    #[cfg(feature = "profiler")]
+    pub(crate) profiler: crate::profiler::ProfilerState,
+
+    #[cfg(feature = "coverage")]
+    pub(crate) coverage: crate::coverage::CoverageState,
+
+    #[cfg(feature = "vm-stats")]
+    pub(crate) stats: crate::vm_stats::VmStatsState,
+
+    /// The `call` spans of the frames, which stay entered until their frame is popped.
+    #[cfg(feature = "tracing")]
+    pub(crate) call_spans: Vec<tracing::span::EnteredSpan>,
 }
 
 /// The stack holds the [`JsValue`]s for the calling convention and registers.
@@ -179,6 +201,14 @@ impl Stack {
         &self.stack[frame.arguments_range()]
     }
 
+    /// Get the registers of the given frame.
+    #[cfg(feature = "debugger")]
+    pub(crate) fn get_registers(&self, frame: &CallFrame) -> &[JsValue] {
+        let rp = frame.rp as usize;
+        let end = rp + frame.code_block.register_count as usize;
+        self.stack.get(rp..end).unwrap_or_default()
+    }
+
     /// Get a single function argument of the given frame by index.
     pub(crate) fn get_argument(&self, frame: &CallFrame, index: usize) -> Option<&JsValue> {
         self.get_arguments(frame).get(index)
@@ -425,6 +455,16 @@ impl Vm {
             trace: false,
             #[cfg(feature = "trace")]
             current_frame: None,
+            #[cfg(feature = "debugger")]
+            debugger: crate::debugger::DebuggerState::default(),
+            #[cfg(feature = "profiler")]
+            profiler: crate::profiler::ProfilerState::default(),
+            #[cfg(feature = "coverage")]
+            coverage: crate::coverage::CoverageState::default(),
+            #[cfg(feature = "vm-stats")]
+            stats: crate::vm_stats::VmStatsState::default(),
+            #[cfg(feature = "tracing")]
+            call_spans: Vec::new(),
         }
     }
 
@@ -561,6 +601,53 @@ impl Vm {
             .as_object()
     }
 
+    /// Returns the promise settled by the completion of the async code of `frame`, or `None` if
+    /// the code of the frame is not async.
+    ///
+    /// This is the promise of the result of an async function or module, or the promise of the
+    /// request an async generator is completing. The registers of `frame` must be on the stack.
+    pub(crate) fn async_result_promise(&self, frame: &CallFrame) -> Option<JsObject> {
+        let code_block = frame.code_block();
+        if code_block.is_async_generator() {
+            let generator = self
+                .stack
+                .get_register(frame, CallFrame::ASYNC_GENERATOR_OBJECT_REGISTER_INDEX)?
+                .as_object()?;
+            let generator = generator.downcast_ref::<AsyncGenerator>()?;
+            return generator.current_promise().cloned();
+        }
+        if !code_block.is_async() {
+            return None;
+        }
+        self.stack
+            .get_register(frame, CallFrame::PROMISE_CAPABILITY_PROMISE_REGISTER_INDEX)?
+            .as_object()
+    }
+
+    /// Returns the async functions and generators awaiting the completion of the current job, from
+    /// the innermost one, up to `limit` of them.
+    ///
+    /// The chain starts at the outermost async frame of the current job, and follows the awaiters
+    /// recorded in the promises by the `Await` opcode. Only the promise of the outermost async
+    /// frame is looked up here; the chain is followed when the stack is read.
+    pub(crate) fn async_stack(&self, limit: usize) -> AsyncStack {
+        // NOTE: Resumed generators run on their own stack, so the registers of the frames below
+        //       the first resumed frame are not on the current stack.
+        let mut outermost = None;
+        for frame in self.frames.iter().skip(1).rev() {
+            let code_block = frame.code_block();
+            if code_block.is_async() {
+                outermost = Some(frame);
+            }
+            if frame.exit_early() && (code_block.is_async() || code_block.is_generator()) {
+                break;
+            }
+        }
+
+        let promise = outermost.and_then(|frame| self.async_result_promise(frame));
+        AsyncStack::new(promise, limit)
+    }
+
     /// Retrieves the VM frame.
     ///
     /// NOTE: When you need a `&CallFrame` alongside a mutable borrow of another
@@ -602,6 +689,9 @@ impl Vm {
                 self.stack.stack.len() + register_count,
                 JsValue::undefined(),
             );
+
+            #[cfg(feature = "vm-stats")]
+            self.stats.count_call(&frame.code_block);
         }
 
         // Keep carrying the last active runnable in case the current callframe
@@ -615,6 +705,12 @@ impl Vm {
         self.shadow_stack
             .push_bytecode(current_pc, frame.code_block().source_info.clone());
 
+        #[cfg(feature = "tracing")]
+        self.call_spans.push(
+            tracing::trace_span!("call", function = %frame.code_block().name().display_escaped())
+                .entered(),
+        );
+
         self.frames.push(frame);
     }
 
@@ -636,6 +732,8 @@ impl Vm {
             return None;
         }
         self.shadow_stack.pop();
+        #[cfg(feature = "tracing")]
+        self.call_spans.pop();
         self.frames.pop()
     }
 
@@ -771,6 +869,11 @@ impl Context {
     where
         F: FnOnce(&mut Context, Opcode) -> ControlFlow<CompletionRecord>,
     {
+        #[cfg(feature = "vm-stats")]
+        if self.vm.stats.is_enabled() {
+            return self.vm_stats_execute_instruction(f, opcode);
+        }
+
         f(self, opcode)
     }
 
@@ -789,6 +892,19 @@ impl Context {
             self.instructions_remaining -= 1;
         }
 
+        #[cfg(feature = "profiler")]
+        if self.vm.profiler.should_sample() {
+            self.profiler_sample();
+        }
+
+        #[cfg(feature = "coverage")]
+        if self.vm.coverage.is_enabled() {
+            self.coverage_count();
+        }
+
+        #[cfg(feature = "debugger")]
+        self.debugger_before_instruction(opcode);
+
         #[cfg(feature = "trace")]
         if self.vm.trace || self.vm.frame().code_block.traceable() {
             self.trace_execute_instruction(f, opcode)
@@ -801,14 +917,21 @@ impl Context {
     }
 
     fn handle_error(&mut self, mut err: JsError) -> ControlFlow<CompletionRecord> {
+        // Errors that already have a backtrace were thrown by a nested run of the VM, and were
+        // already reported to the debugger there.
+        #[cfg(feature = "debugger")]
+        let thrown_here = err.backtrace.is_none();
+
         // Capture the backtrace early, before any exception handler check,
         // so that errors caught by internal handlers (e.g. async module
         // evaluation) still carry source position information.
         if err.backtrace.is_none() {
+            let limit = self.vm.runtime_limits.backtrace_limit();
             err.backtrace = Some(
                 self.vm
                     .shadow_stack
-                    .take(self.vm.runtime_limits.backtrace_limit(), self.vm.frame().pc),
+                    .take(limit, self.vm.frame().pc)
+                    .with_async_stack(self.vm.async_stack(limit)),
             );
         }
 
@@ -836,14 +959,20 @@ impl Context {
             return ControlFlow::Break(CompletionRecord::Throw(err));
         }
 
+        #[cfg(feature = "debugger")]
+        if thrown_here {
+            self.debugger_exception(&err);
+        }
+
         if let Some(native) = err.as_native_mut()
             && let ErrorStack::Position(position) = &mut native.stack.0
         {
-            let backtrace = self.vm.shadow_stack.take_and_push(
-                self.vm.runtime_limits.backtrace_limit(),
-                self.vm.frame().pc,
-                position.clone(),
-            );
+            let limit = self.vm.runtime_limits.backtrace_limit();
+            let backtrace = self
+                .vm
+                .shadow_stack
+                .take_and_push(limit, self.vm.frame().pc, position.clone())
+                .with_async_stack(self.vm.async_stack(limit));
             native.stack.0 = ErrorStack::Backtrace(backtrace);
         }
 
@@ -896,7 +1025,11 @@ impl Context {
         {
             let pc = self.vm.frames.last().expect("frame must exist").pc;
             let limit = self.vm.runtime_limits.backtrace_limit();
-            let backtrace = self.vm.shadow_stack.take(limit, pc);
+            let backtrace = self
+                .vm
+                .shadow_stack
+                .take(limit, pc)
+                .with_async_stack(self.vm.async_stack(limit));
             self.vm
                 .pending_exception
                 .as_mut()
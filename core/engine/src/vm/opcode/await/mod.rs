@@ -9,7 +9,11 @@ use crate::{
     js_string,
     native_function::NativeFunction,
     object::FunctionObjectBuilder,
-    vm::{CompletionRecord, GeneratorResumeKind, opcode::Operation},
+    vm::{
+        CompletionRecord, GeneratorResumeKind,
+        opcode::Operation,
+        shadow_stack::{Awaiter, ShadowEntry},
+    },
 };
 use boa_gc::Gc;
 use std::{cell::Cell, ops::ControlFlow};
@@ -54,6 +58,18 @@ impl Await {
             .map(|cap| JsValue::from(cap.promise))
             .unwrap_or_default();
 
+        // NOTE: The promise of the awaiting function must be read before its registers are moved
+        //       to the generator context.
+        let frame = context.vm.frame();
+        let awaiter = Awaiter {
+            entry: ShadowEntry::Bytecode {
+                // NOTE: pc points to the next opcode, so we offset by -1 to put it within range.
+                pc: frame.pc.saturating_sub(1),
+                source_info: frame.code_block().source_info.clone(),
+            },
+            promise: context.vm.async_result_promise(frame),
+        };
+
         let r#gen = GeneratorContext::from_current(context, None);
 
         let captures = Gc::new(Cell::new(Some(r#gen)));
@@ -145,6 +161,9 @@ impl Await {
             context,
         );
 
+        // Record the awaiting function in the reactions, to show it in async stack traces.
+        promise.borrow_mut().data_mut().set_awaiter(awaiter);
+
         context.vm.set_return_value(return_value);
         context.handle_yield()
     }
@@ -23,6 +23,8 @@ impl Throw {
     ) -> ControlFlow<CompletionRecord> {
         let value = context.vm.get_register(value.into());
         let error = JsError::from_opaque(value.clone());
+        #[cfg(feature = "debugger")]
+        context.debugger_exception(&error);
         context.vm.pending_exception = Some(error);
 
         // Note: -1 because we increment after fetching the opcode.
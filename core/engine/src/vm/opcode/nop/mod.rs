@@ -19,3 +19,21 @@ impl Operation for Reserved {
     const INSTRUCTION: &'static str = "INST - Reserved";
     const COST: u8 = 0;
 }
+
+/// `Debugger` implements the Opcode Operation for `Opcode::Debugger`
+///
+/// Operation:
+///  - No-op, an attached debugger pauses before this instruction is executed.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Debugger;
+
+impl Debugger {
+    #[inline(always)]
+    pub(crate) fn operation((): (), _: &mut Context) {}
+}
+
+impl Operation for Debugger {
+    const NAME: &'static str = "Debugger";
+    const INSTRUCTION: &'static str = "INST - Debugger";
+    const COST: u8 = 1;
+}
@@ -23,6 +23,8 @@ impl PushScope {
         frame
             .environments
             .push_lexical(scope.num_bindings_non_local(), global);
+        #[cfg(feature = "debugger")]
+        frame.environments.set_lexical_scope(&scope);
     }
 }
 
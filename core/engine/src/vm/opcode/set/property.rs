@@ -4,7 +4,7 @@ use crate::value::JsVariant;
 use crate::vm::opcode::{IndexOperand, RegisterOperand};
 use crate::{
     Context, JsNativeError, JsResult,
-    builtins::function::set_function_name,
+    builtins::{Array, function::set_function_name},
     object::{internal_methods::InternalMethodPropertyContext, shape::slot::SlotAttributes},
     property::{PropertyDescriptor, PropertyKey},
     vm::opcode::Operation,
@@ -180,6 +180,18 @@ impl SetPropertyByValue {
                 {
                     return Ok(());
                 }
+                drop(object_borrowed);
+
+                // Appending an element to the end of a dense array.
+                if Array::fast_array_length(&object, context).is_some_and(|len| len == index.get())
+                {
+                    let mut object_borrowed = object.borrow_mut();
+                    let properties = object_borrowed.properties_mut();
+                    properties.indexed_properties.push_dense(&value);
+                    // NOTE: The "length" property is the first element.
+                    properties.storage[0] = JsValue::new(index.get() + 1);
+                    return Ok(());
+                }
             }
         }
 
@@ -1,28 +1,129 @@
-use std::fmt::{self, Display};
+use std::{
+    cell::OnceCell,
+    fmt::{self, Display},
+};
 
 use boa_gc::{Finalize, Trace};
 use boa_string::JsString;
 use thin_vec::ThinVec;
 
-use super::source_info::{NativeSourceInfo, SourceInfo};
+use super::{
+    StackFrame,
+    source_info::{NativeSourceInfo, SourceInfo},
+};
+use crate::{
+    JsObject, builtins::promise::Promise, source_map::SourceMap, spanned_source_text::SourceText,
+};
 
 #[derive(Debug, Default, Clone, Trace, Finalize)]
 pub(crate) struct Backtrace {
     // SAFETY: Nothing in `ShadowEntry` requires trace, so this is safe.
     #[unsafe_ignore_trace]
     stack: ThinVec<ShadowEntry>,
+
+    /// The async functions and generators awaiting the completion of the stack.
+    async_stack: AsyncStack,
 }
 
 impl Backtrace {
     pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = &ShadowEntry> {
         self.stack.iter()
     }
+
+    /// Returns the async functions and generators awaiting the completion of the stack, from the
+    /// innermost one to the outermost one.
+    pub(crate) fn async_iter(&self) -> impl Iterator<Item = &ShadowEntry> {
+        self.async_stack.entries().iter()
+    }
+
+    /// Sets the async functions and generators awaiting the completion of the stack.
+    pub(crate) fn with_async_stack(mut self, async_stack: AsyncStack) -> Self {
+        self.async_stack = async_stack;
+        self
+    }
+
+    /// Returns the frames of the backtrace, with the most recent frames first and the awaiting
+    /// async functions and generators last.
+    pub(crate) fn stack_frames(&self) -> Vec<StackFrame> {
+        self.stack
+            .iter()
+            .rev()
+            .map(StackFrame::from_entry)
+            .chain(self.async_iter().map(StackFrame::from_async_entry))
+            .collect()
+    }
+}
+
+/// The async functions and generators awaiting the completion of a stack.
+///
+/// Errors are thrown and caught far more often than their stacks are read, so this only keeps
+/// the promise settled by the outermost async frame of the stack, and follows the awaiters of
+/// the promises from there the first time the entries are read.
+#[derive(Debug, Default, Clone, Trace, Finalize)]
+pub(crate) struct AsyncStack {
+    /// The promise settled by the completion of the outermost async frame of the stack.
+    promise: Option<JsObject>,
+
+    /// The maximum number of entries.
+    #[unsafe_ignore_trace]
+    limit: usize,
+
+    // SAFETY: Nothing in `ShadowEntry` requires trace, so this is safe.
+    #[unsafe_ignore_trace]
+    entries: OnceCell<ThinVec<ShadowEntry>>,
+}
+
+impl AsyncStack {
+    /// Creates the async stack awaiting the completion of `promise`, with up to `limit` entries.
+    pub(crate) fn new(promise: Option<JsObject>, limit: usize) -> Self {
+        Self {
+            promise,
+            limit,
+            entries: OnceCell::new(),
+        }
+    }
+
+    /// Returns the async functions and generators of the stack, from the innermost one to the
+    /// outermost one.
+    pub(crate) fn entries(&self) -> &[ShadowEntry] {
+        self.entries.get_or_init(|| {
+            let mut entries = ThinVec::new();
+            let mut promise = self.promise.clone();
+            while entries.len() < self.limit
+                && let Some(current) = promise.take()
+            {
+                let Some((entry, next)) = current.downcast_ref::<Promise>().and_then(|promise| {
+                    let awaiter = promise.awaiter()?;
+                    Some((awaiter.entry.clone(), awaiter.promise.clone()))
+                }) else {
+                    break;
+                };
+                entries.push(entry);
+                promise = next;
+            }
+            entries
+        })
+    }
+}
+
+/// An async function or generator awaiting a promise, recorded in the reactions of the promise
+/// to show the chain of awaiting functions in async stack traces.
+#[derive(Debug, Trace, Finalize)]
+pub(crate) struct Awaiter {
+    /// The position of the `await`.
+    // SAFETY: Nothing in `ShadowEntry` requires trace, so this is safe.
+    #[unsafe_ignore_trace]
+    pub(crate) entry: ShadowEntry,
+
+    /// The promise of the result of the awaiting function, or of the current request of the
+    /// awaiting async generator.
+    pub(crate) promise: Option<JsObject>,
 }
 
 #[derive(Debug, Clone, Trace, Finalize)]
 pub(crate) enum ErrorStack {
     Position(#[unsafe_ignore_trace] ShadowEntry),
-    Backtrace(#[unsafe_ignore_trace] Backtrace),
+    Backtrace(Backtrace),
 }
 
 impl ErrorStack {
@@ -105,9 +206,17 @@ impl Display for DisplayShadowEntry<'_> {
                 }
             }
             ShadowEntry::Bytecode { pc, source_info } => {
+                let source_text = source_info.text_spanned().source_text();
+                let source_map = source_text.source_map();
+                let position = source_info.map().find(*pc);
+
                 if self.show_function_name {
-                    let has_function_name = !source_info.function_name().is_empty();
-                    if has_function_name {
+                    let original_name = source_map.and_then(|source_map| {
+                        original_function_name(source_info, &source_text, source_map)
+                    });
+                    if let Some(name) = original_name {
+                        f.write_str(name)?;
+                    } else if !source_info.function_name().is_empty() {
                         write!(f, "{}", source_info.function_name().to_std_string_escaped())?;
                     } else {
                         f.write_str("<main>")?;
@@ -115,17 +224,30 @@ impl Display for DisplayShadowEntry<'_> {
                 }
                 f.write_str(" (")?;
 
-                source_info.map().path().fmt(f)?;
-
-                if let Some(position) = source_info.map().find(*pc) {
+                let original = source_map.zip(position).and_then(|(source_map, position)| {
+                    source_map.lookup(position.line_number(), position.column_number())
+                });
+                if let Some(original) = original {
                     write!(
                         f,
-                        ":{}:{}",
-                        position.line_number(),
-                        position.column_number()
+                        "{}:{}:{}",
+                        original.source(),
+                        original.line(),
+                        original.column()
                     )?;
                 } else {
-                    f.write_str(":?:?")?;
+                    source_info.map().path().fmt(f)?;
+
+                    if let Some(position) = position {
+                        write!(
+                            f,
+                            ":{}:{}",
+                            position.line_number(),
+                            position.column_number()
+                        )?;
+                    } else {
+                        f.write_str(":?:?")?;
+                    }
                 }
                 f.write_str(")")?;
             }
@@ -134,6 +256,39 @@ impl Display for DisplayShadowEntry<'_> {
     }
 }
 
+/// Returns the original name of the function of `source_info`, from the source map of its code.
+///
+/// Source maps only name identifiers, so this looks for a segment mapping the generated name of
+/// the function on the line where the function starts, closest to its start.
+pub(super) fn original_function_name<'a>(
+    source_info: &SourceInfo,
+    source_text: &SourceText,
+    source_map: &'a SourceMap,
+) -> Option<&'a str> {
+    let name = source_info.function_name().to_vec();
+    if name.is_empty() {
+        return None;
+    }
+    let start = source_info.text_spanned().span()?.start().pos();
+    let code_units = source_text.code_units();
+    let (line, column) = source_text.line_and_column(start);
+    let line_start = start - column;
+    let line_end = code_units[line_start..]
+        .iter()
+        .position(|unit| matches!(unit, 0x0A | 0x0D | 0x2028 | 0x2029))
+        .map_or(code_units.len(), |end| line_start + end);
+    let line = u32::try_from(line + 1).ok()?;
+
+    (line_start..line_end)
+        .filter(|&i| code_units[i..line_end].starts_with(&name))
+        .filter_map(|i| {
+            let column = u32::try_from(i - line_start + 1).ok()?;
+            Some((i.abs_diff(start), source_map.name_at(line, column)?))
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, name)| name)
+}
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct ShadowStack {
     stack: ThinVec<ShadowEntry>,
@@ -171,6 +326,12 @@ impl ShadowStack {
             .push(ShadowEntry::Bytecode { pc: 0, source_info });
     }
 
+    /// Returns the entries of the stack, from the outermost one to the innermost one.
+    #[cfg(feature = "profiler")]
+    pub(crate) fn entries(&self) -> &[ShadowEntry] {
+        &self.stack
+    }
+
     pub(crate) fn pop(&mut self) -> Option<ShadowEntry> {
         self.stack.pop()
     }
@@ -189,7 +350,10 @@ impl ShadowStack {
             // NOTE: pc points to the next opcode, so we offset by -1 to put it within range.
             *pc = last_pc.saturating_sub(1);
         }
-        Backtrace { stack }
+        Backtrace {
+            stack,
+            async_stack: AsyncStack::default(),
+        }
     }
 
     pub(crate) fn take_and_push(&self, n: usize, last_pc: u32, value: ShadowEntry) -> Backtrace {
@@ -208,7 +372,10 @@ impl ShadowStack {
             // NOTE: pc points to the next opcode, so we offset by -1 to put it within range.
             *pc = last_pc.saturating_sub(1);
         }
-        Backtrace { stack }
+        Backtrace {
+            stack,
+            async_stack: AsyncStack::default(),
+        }
     }
 
     pub(crate) fn caller_position(&self, n: usize) -> Backtrace {
@@ -223,7 +390,10 @@ impl ShadowStack {
             .cloned()
             .collect::<ThinVec<_>>();
 
-        Backtrace { stack }
+        Backtrace {
+            stack,
+            async_stack: AsyncStack::default(),
+        }
     }
 
     #[cfg(feature = "native-backtrace")]
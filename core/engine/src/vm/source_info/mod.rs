@@ -51,6 +51,13 @@ impl SourceInfo {
     pub(crate) fn text_spanned(&self) -> &SpannedSourceText {
         &self.inner.text_spanned
     }
+
+    /// Returns a pointer that identifies this source information, which is shared by the
+    /// clones of a [`SourceInfo`].
+    #[cfg(feature = "profiler")]
+    pub(crate) fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.inner).cast()
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -66,17 +73,38 @@ struct Inner {
 pub(crate) struct SourceMap {
     entries: Box<[Entry]>,
     path: SourcePath,
+    start: Option<Position>,
 }
 
 impl SourceMap {
-    pub(crate) fn new(entries: Box<[Entry]>, path: SourcePath) -> Self {
-        Self { entries, path }
+    pub(crate) fn new(entries: Box<[Entry]>, path: SourcePath, start: Option<Position>) -> Self {
+        Self {
+            entries,
+            path,
+            start,
+        }
     }
 
     pub(crate) fn entries(&self) -> &[Entry] {
         &self.entries
     }
 
+    /// Returns the position of the start of the function body, if the code is a function.
+    pub(crate) fn start(&self) -> Option<Position> {
+        self.start
+    }
+
+    /// Returns the position functions are reported at: the start of the function body, or the
+    /// first position of the code if it isn't a function.
+    ///
+    /// The first position of a function body can't be used instead, since it depends on the
+    /// positions the compiler records for the first statement.
+    #[cfg(any(feature = "vm-stats", feature = "profiler"))]
+    pub(crate) fn function_position(&self) -> Option<Position> {
+        self.start
+            .or_else(|| self.entries.iter().find_map(Entry::position))
+    }
+
     pub(crate) fn find(&self, pc: u32) -> Option<Position> {
         find_entry(self.entries(), pc)
     }
@@ -0,0 +1,147 @@
+//! Structured stack traces.
+//!
+//! A [`StackFrame`] describes a frame of the backtrace of a [`JsError`][crate::JsError] or of the
+//! [current stack][crate::Context::stack_trace] of a [`Context`][crate::Context], as plain data
+//! that can be serialized with `serde` and sent to another process.
+
+use boa_ast::Position;
+use boa_string::JsString;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    SourcePath,
+    shadow_stack::{ShadowEntry, original_function_name},
+};
+
+/// A frame of a stack trace.
+///
+/// When the code of the frame has a [source map][crate::source_map], the frame has the original
+/// function name and position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackFrame {
+    function_name: Option<String>,
+    path: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    is_native: bool,
+    is_async: bool,
+}
+
+impl StackFrame {
+    /// Returns the name of the function of the frame, `<main>` for top level code, or `None` for
+    /// anonymous functions.
+    #[must_use]
+    pub fn function_name(&self) -> Option<&str> {
+        self.function_name.as_deref()
+    }
+
+    /// Returns the path of the script or module of the frame, or `None` for code without a path,
+    /// like `eval` code.
+    ///
+    /// For native functions, this is the Rust source file of the function when the
+    /// `native-backtrace` feature is enabled.
+    #[must_use]
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Returns the 1-based line of the frame, if it is known.
+    #[must_use]
+    pub const fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the 1-based column of the frame, if it is known.
+    #[must_use]
+    pub const fn column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// Returns `true` if the frame is a call to a native function.
+    #[must_use]
+    pub const fn is_native(&self) -> bool {
+        self.is_native
+    }
+
+    /// Returns `true` if the frame is an async function or generator waiting for the frames above
+    /// it to complete, instead of a caller that is on the stack.
+    #[must_use]
+    pub const fn is_async(&self) -> bool {
+        self.is_async
+    }
+
+    /// Creates the stack frame of an async function or generator awaiting the completion of the
+    /// stack.
+    pub(crate) fn from_async_entry(entry: &ShadowEntry) -> Self {
+        Self {
+            is_async: true,
+            ..Self::from_entry(entry)
+        }
+    }
+
+    /// Creates the stack frame of an entry of the shadow stack.
+    pub(crate) fn from_entry(entry: &ShadowEntry) -> Self {
+        match entry {
+            ShadowEntry::Native {
+                function_name,
+                source_info,
+            } => {
+                let location = source_info.as_location();
+                Self {
+                    function_name: function_name.as_ref().map(JsString::to_std_string_escaped),
+                    path: location.map(|location| location.file().to_owned()),
+                    line: location.map(std::panic::Location::line),
+                    column: location.map(std::panic::Location::column),
+                    is_native: true,
+                    is_async: false,
+                }
+            }
+            ShadowEntry::Bytecode { pc, source_info } => {
+                let source_text = source_info.text_spanned().source_text();
+                let source_map = source_text.source_map();
+                let position = source_info.map().find(*pc);
+
+                let function_name = source_map
+                    .and_then(|source_map| {
+                        original_function_name(source_info, &source_text, source_map)
+                    })
+                    .map(str::to_owned)
+                    .or_else(|| {
+                        let name = source_info.function_name();
+                        (!name.is_empty()).then(|| name.to_std_string_escaped())
+                    });
+
+                let original = source_map.zip(position).and_then(|(source_map, position)| {
+                    source_map.lookup(position.line_number(), position.column_number())
+                });
+                let (path, line, column) = if let Some(original) = original {
+                    (
+                        Some(original.source().to_owned()),
+                        Some(original.line()),
+                        Some(original.column()),
+                    )
+                } else {
+                    let path = match source_info.map().path() {
+                        SourcePath::Path(path) => Some(path.display().to_string()),
+                        SourcePath::None | SourcePath::Eval | SourcePath::Json => None,
+                    };
+                    (
+                        path,
+                        position.map(Position::line_number),
+                        position.map(Position::column_number),
+                    )
+                };
+
+                Self {
+                    function_name,
+                    path,
+                    line,
+                    column,
+                    is_native: false,
+                    is_async: false,
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,16 @@
 use crate::error::RuntimeLimitError;
-use crate::vm::CallFrame;
 use crate::vm::call_frame::CallFrameLocation;
 use crate::vm::source_info::SourcePath;
+use crate::vm::{CallFrame, StackFrame};
 use crate::{
-    Context, JsNativeErrorKind, JsValue, NativeFunction, TestAction, js_string,
-    property::Attribute, run_test_actions, run_test_actions_with,
+    Context, JsError, JsNativeError, JsNativeErrorKind, JsValue, NativeFunction, TestAction,
+    js_string, property::Attribute, run_test_actions, run_test_actions_with,
 };
 use boa_ast::Position;
 use boa_macros::js_str;
 use boa_parser::Source;
 use indoc::indoc;
+use std::path::Path;
 
 #[test]
 fn typeof_string() {
@@ -53,7 +54,7 @@ fn position() {
             js_string!("check_stack"),
             2,
             NativeFunction::from_copy_closure(|_, _, context| {
-                let frame = context.stack_trace().collect::<Vec<&CallFrame>>();
+                let frame = context.call_frames().collect::<Vec<&CallFrame>>();
 
                 assert_eq!(frame.len(), 4);
                 assert_eq!(
@@ -107,6 +108,211 @@ fn position() {
     );
 }
 
+#[test]
+fn stack_trace() {
+    let context = &mut Context::default();
+    context
+        .register_global_callable(
+            js_string!("check_stack"),
+            0,
+            NativeFunction::from_copy_closure(|_, _, context| {
+                let frames = context.stack_trace();
+                assert_eq!(frames.len(), 3);
+
+                assert_eq!(frames[0].function_name(), Some("check_stack"));
+                assert!(frames[0].is_native());
+
+                assert_eq!(frames[1].function_name(), Some("myFunction"));
+                assert_eq!(frames[1].path(), Some("/test.js"));
+                assert_eq!(frames[1].line(), Some(2));
+                assert_eq!(frames[1].column(), Some(16));
+                assert!(!frames[1].is_native());
+                assert!(!frames[1].is_async());
+
+                assert_eq!(frames[2].function_name(), Some("<main>"));
+                assert_eq!(frames[2].line(), Some(4));
+                Ok(JsValue::undefined())
+            }),
+        )
+        .expect("Could not register function");
+
+    let code = indoc! {r#"
+        function myFunction() {
+            check_stack();
+        }
+        myFunction();
+    "#};
+    context
+        .eval(Source::from_bytes(code).with_path(Path::new("/test.js")))
+        .expect("the code should run");
+}
+
+#[test]
+fn error_stack_frames() {
+    let context = &mut Context::default();
+    let error = context
+        .eval(Source::from_bytes(indoc! {r#"
+            function thrower() {
+                throw new TypeError("oops");
+            }
+            [1].map(thrower);
+        "#}))
+        .expect_err("the code should throw");
+
+    let frames = error
+        .stack_frames()
+        .expect("the error should have a backtrace");
+    let names = frames
+        .iter()
+        .map(StackFrame::function_name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("thrower"), Some("map"), Some("<main>")]);
+    assert_eq!(frames[0].path(), None);
+    assert_eq!(frames[0].line(), Some(2));
+
+    let json = serde_json::to_value(&frames[0]).expect("the frame should serialize");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "functionName": "thrower",
+            "path": null,
+            "line": 2,
+            "column": 11,
+            "isNative": false,
+            "isAsync": false,
+        })
+    );
+    let frame: StackFrame = serde_json::from_value(json).expect("the frame should deserialize");
+    assert_eq!(frame, frames[0]);
+
+    assert!(
+        JsError::from_native(JsNativeError::typ())
+            .stack_frames()
+            .is_none()
+    );
+}
+
+#[test]
+fn async_stack_frames() {
+    let context = &mut Context::default();
+    let code = indoc! {r#"
+        async function inner() {
+            await null;
+            throw new Error("oops");
+        }
+        async function middle() {
+            await inner();
+        }
+        async function outer() {
+            await middle();
+        }
+        async function* generator() {
+            await null;
+            throw new Error("oops");
+        }
+        async function consumer() {
+            for await (const value of generator()) {}
+        }
+        let stack;
+        let generatorStack;
+        outer().catch((error) => { stack = error.stack; });
+        consumer().catch((error) => { generatorStack = error.stack; });
+    "#};
+    context
+        .eval(Source::from_bytes(code).with_path(Path::new("/test.js")))
+        .expect("the code should run");
+    context.run_jobs().expect("the jobs should run");
+
+    let stack = |name: &str, context: &mut Context| {
+        context
+            .eval(Source::from_bytes(name))
+            .expect("the stack should be set")
+            .to_string(context)
+            .expect("the stack should be a string")
+            .to_std_string_escaped()
+    };
+
+    let function_stack = stack("stack", context);
+    let lines = function_stack.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "    at inner (/test.js:3:11)");
+    assert_eq!(lines[lines.len() - 2], "    at async middle (/test.js:6:5)");
+    assert_eq!(lines[lines.len() - 1], "    at async outer (/test.js:9:5)");
+
+    let generator_stack = stack("generatorStack", context);
+    let lines = generator_stack.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "    at generator (/test.js:13:11)");
+    assert_eq!(
+        lines[lines.len() - 1],
+        "    at async consumer (/test.js:16:40)"
+    );
+}
+
+#[test]
+fn async_stack_frames_after_collection() {
+    let context = &mut Context::default();
+    let code = indoc! {r#"
+        async function inner() {
+            await null;
+            throw new Error("oops");
+        }
+        async function outer() {
+            await inner();
+        }
+        let caught;
+        outer().catch((error) => { caught = error; });
+    "#};
+    context
+        .eval(Source::from_bytes(code).with_path(Path::new("/test.js")))
+        .expect("the code should run");
+    context.run_jobs().expect("the jobs should run");
+
+    // The awaiting functions are only looked up when the stack is read, so the promises they
+    // awaited must survive a collection.
+    boa_gc::force_collect();
+
+    let stack = context
+        .eval(Source::from_bytes("caught.stack"))
+        .expect("the stack should be set")
+        .to_string(context)
+        .expect("the stack should be a string")
+        .to_std_string_escaped();
+    let lines = stack.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "    at inner (/test.js:3:11)");
+    assert_eq!(lines[lines.len() - 1], "    at async outer (/test.js:6:5)");
+}
+
+#[test]
+fn async_error_stack_trace() {
+    let context = &mut Context::default();
+    context
+        .register_global_callable(
+            js_string!("check_stack"),
+            0,
+            NativeFunction::from_copy_closure(|_, _, context| {
+                let frames = context.stack_trace();
+                let last = frames.last().expect("there should be frames");
+                assert_eq!(last.function_name(), Some("caller"));
+                assert!(last.is_async());
+                assert!(!frames[0].is_async());
+                Ok(JsValue::undefined())
+            }),
+        )
+        .expect("Could not register function");
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            async function checked() {
+                await null;
+                check_stack();
+            }
+            async function caller() {
+                await checked();
+            }
+            caller();
+        "#}))
+        .expect("the code should run");
+    context.run_jobs().expect("the jobs should run");
+}
+
 #[test]
 fn try_catch_finally_from_init() {
     // the initialisation of the array here emits a PopOnReturnAdd op
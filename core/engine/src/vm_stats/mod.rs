@@ -0,0 +1,619 @@
+//! Execution statistics of the VM.
+//!
+//! While statistics are collected with [`Context::start_vm_stats`], the VM records:
+//!
+//! - How many times every opcode ran, and how long it took. The time of an instruction includes
+//!   the time of the native functions it calls, and of the code that they run.
+//! - How many times every function was called. Resuming a generator or an async function is not
+//!   a new call.
+//! - How many property lookups of the bytecode hit or missed their inline cache, or were not
+//!   cached at all because their cache saw too many shapes and became megamorphic.
+//! - How many shape transitions objects went through, by kind of transition.
+//!
+//! Inline caches and shapes are shared by all the contexts of a thread, so their statistics
+//! include the lookups and transitions of every context running on the thread while the
+//! statistics are collected.
+//!
+//! The [`VmStats`] returned by [`Context::stop_vm_stats`] can be written as a table for humans,
+//! or as JSON for other tools.
+//!
+//! ```
+//! # use boa_engine::{Context, Source};
+//! let mut context = Context::default();
+//! context.start_vm_stats();
+//! context
+//!     .eval(Source::from_bytes(
+//!         "function point(x, y) { return { x, y }; } for (let i = 0; i < 10; i++) point(i, i).x;",
+//!     ))
+//!     .unwrap();
+//! let stats = context.stop_vm_stats().unwrap();
+//!
+//! let mut json = Vec::new();
+//! stats.write_json(&mut json).unwrap();
+//! let table = stats.to_table_string();
+//! ```
+
+use crate::{
+    Context, JsString,
+    vm::{CodeBlock, CompletionRecord, SourcePath, opcode::Opcode},
+};
+use boa_ast::Position;
+use boa_gc::Gc;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::{
+    cell::Cell,
+    fmt::Write as _,
+    io,
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of times an opcode ran, and the time it took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeStats {
+    name: &'static str,
+    count: u64,
+    time: Duration,
+}
+
+impl OpcodeStats {
+    /// Returns the name of the opcode.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the number of times the opcode ran.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total time the opcode took to run.
+    #[must_use]
+    pub const fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+/// The number of times a function was called.
+#[derive(Debug, Clone)]
+pub struct FunctionStats {
+    function_name: JsString,
+    path: SourcePath,
+    position: Option<Position>,
+    invocations: u64,
+}
+
+impl FunctionStats {
+    /// Returns the name of the function, which is empty for anonymous functions.
+    #[must_use]
+    pub const fn function_name(&self) -> &JsString {
+        &self.function_name
+    }
+
+    /// Returns the source of the function.
+    #[must_use]
+    pub const fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// Returns the position of the first instruction of the function that has a position.
+    #[must_use]
+    pub const fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Returns the number of times the function was called.
+    #[must_use]
+    pub const fn invocations(&self) -> u64 {
+        self.invocations
+    }
+
+    /// Returns the label of the function in the table, with its location.
+    fn label(&self) -> String {
+        let name = if self.function_name.is_empty() {
+            "(anonymous)".to_owned()
+        } else {
+            self.function_name.to_std_string_escaped()
+        };
+        match (&self.path, self.position) {
+            (SourcePath::Path(path), Some(position)) => format!(
+                "{name} ({}:{}:{})",
+                path.display(),
+                position.line_number(),
+                position.column_number()
+            ),
+            (SourcePath::Path(path), None) => format!("{name} ({})", path.display()),
+            _ => name,
+        }
+    }
+}
+
+/// The outcomes of the property lookups that use an inline cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InlineCacheStats {
+    hits: u64,
+    misses: u64,
+    megamorphic: u64,
+}
+
+impl InlineCacheStats {
+    /// Returns the number of lookups that found the shape of the object in the cache.
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of lookups that didn't find the shape of the object in the cache.
+    #[must_use]
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the number of lookups that skipped the cache, because it was megamorphic.
+    #[must_use]
+    pub const fn megamorphic(&self) -> u64 {
+        self.megamorphic
+    }
+
+    fn since(self, start: Self) -> Self {
+        Self {
+            hits: self.hits - start.hits,
+            misses: self.misses - start.misses,
+            megamorphic: self.megamorphic - start.megamorphic,
+        }
+    }
+}
+
+/// The number of shape transitions, by kind of transition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShapeTransitionStats {
+    insert_property: u64,
+    change_attributes: u64,
+    remove_property: u64,
+    change_prototype: u64,
+}
+
+impl ShapeTransitionStats {
+    /// Returns the number of transitions that added a property.
+    #[must_use]
+    pub const fn insert_property(&self) -> u64 {
+        self.insert_property
+    }
+
+    /// Returns the number of transitions that changed the attributes of a property.
+    #[must_use]
+    pub const fn change_attributes(&self) -> u64 {
+        self.change_attributes
+    }
+
+    /// Returns the number of transitions that removed a property.
+    #[must_use]
+    pub const fn remove_property(&self) -> u64 {
+        self.remove_property
+    }
+
+    /// Returns the number of transitions that changed the prototype.
+    #[must_use]
+    pub const fn change_prototype(&self) -> u64 {
+        self.change_prototype
+    }
+
+    /// Returns the number of transitions of all kinds.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.insert_property + self.change_attributes + self.remove_property + self.change_prototype
+    }
+
+    fn since(self, start: Self) -> Self {
+        Self {
+            insert_property: self.insert_property - start.insert_property,
+            change_attributes: self.change_attributes - start.change_attributes,
+            remove_property: self.remove_property - start.remove_property,
+            change_prototype: self.change_prototype - start.change_prototype,
+        }
+    }
+}
+
+/// The execution statistics of the VM.
+#[derive(Debug, Clone)]
+pub struct VmStats {
+    duration: Duration,
+    opcodes: Vec<OpcodeStats>,
+    functions: Vec<FunctionStats>,
+    inline_caches: InlineCacheStats,
+    shape_transitions: ShapeTransitionStats,
+}
+
+impl VmStats {
+    /// Returns the time during which the statistics were collected.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the opcodes that ran, from the most to the least frequent.
+    #[must_use]
+    pub fn opcodes(&self) -> &[OpcodeStats] {
+        &self.opcodes
+    }
+
+    /// Returns the functions that were called, from the most to the least called.
+    #[must_use]
+    pub fn functions(&self) -> &[FunctionStats] {
+        &self.functions
+    }
+
+    /// Returns the statistics of the inline caches.
+    #[must_use]
+    pub const fn inline_caches(&self) -> InlineCacheStats {
+        self.inline_caches
+    }
+
+    /// Returns the statistics of the shape transitions.
+    #[must_use]
+    pub const fn shape_transitions(&self) -> ShapeTransitionStats {
+        self.shape_transitions
+    }
+
+    /// Writes the statistics as tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statistics could not be written.
+    pub fn write_table<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_table_string().as_bytes())
+    }
+
+    /// Returns the statistics as tables.
+    #[must_use]
+    pub fn to_table_string(&self) -> String {
+        let mut out = String::new();
+
+        let count = self.opcodes.iter().map(|opcode| opcode.count).sum::<u64>();
+        let _ = writeln!(
+            out,
+            "{count} instructions in {:.3} ms",
+            self.duration.as_secs_f64() * 1000.0
+        );
+
+        let _ = writeln!(out, "\nOpcodes:");
+        let _ = writeln!(
+            out,
+            "  {:<32} {:>12} {:>7} {:>12} {:>10}",
+            "Opcode", "Count", "%", "Time (ms)", "Avg (ns)"
+        );
+        for opcode in &self.opcodes {
+            let _ = writeln!(
+                out,
+                "  {:<32} {:>12} {:>7.2} {:>12.3} {:>10}",
+                opcode.name,
+                opcode.count,
+                percent(opcode.count, count),
+                opcode.time.as_secs_f64() * 1000.0,
+                opcode.time.as_nanos() / u128::from(opcode.count.max(1))
+            );
+        }
+
+        let _ = writeln!(out, "\nFunctions:");
+        let _ = writeln!(out, "  {:>12}  Function", "Invocations");
+        for function in &self.functions {
+            let _ = writeln!(out, "  {:>12}  {}", function.invocations, function.label());
+        }
+
+        let ic = self.inline_caches;
+        let lookups = ic.hits + ic.misses + ic.megamorphic;
+        let _ = writeln!(out, "\nInline caches:");
+        for (name, value) in [
+            ("Hits", ic.hits),
+            ("Misses", ic.misses),
+            ("Megamorphic", ic.megamorphic),
+        ] {
+            let _ = writeln!(
+                out,
+                "  {name:<20} {value:>12} {:>7.2}",
+                percent(value, lookups)
+            );
+        }
+
+        let shapes = self.shape_transitions;
+        let _ = writeln!(out, "\nShape transitions:");
+        for (name, value) in [
+            ("Insert property", shapes.insert_property),
+            ("Change attributes", shapes.change_attributes),
+            ("Remove property", shapes.remove_property),
+            ("Change prototype", shapes.change_prototype),
+        ] {
+            let _ = writeln!(out, "  {name:<20} {value:>12}");
+        }
+
+        out
+    }
+
+    /// Writes the statistics as JSON.
+    ///
+    /// Times are in nanoseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statistics could not be written.
+    pub fn write_json<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.serializable()).map_err(io::Error::other)
+    }
+
+    /// Returns the statistics as JSON.
+    ///
+    /// Times are in nanoseconds.
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(&self.serializable())
+            .expect("serializing the statistics should not fail")
+    }
+
+    fn serializable(&self) -> SerializableVmStats<'_> {
+        SerializableVmStats {
+            duration: duration_nanos(self.duration),
+            opcodes: self
+                .opcodes
+                .iter()
+                .map(|opcode| SerializableOpcode {
+                    name: opcode.name,
+                    count: opcode.count,
+                    time: duration_nanos(opcode.time),
+                })
+                .collect(),
+            functions: self
+                .functions
+                .iter()
+                .map(|function| SerializableFunction {
+                    function_name: function.function_name.to_std_string_escaped(),
+                    url: match &function.path {
+                        SourcePath::Path(path) => path.display().to_string(),
+                        _ => String::new(),
+                    },
+                    line_number: function.position.map(Position::line_number),
+                    column_number: function.position.map(Position::column_number),
+                    invocations: function.invocations,
+                })
+                .collect(),
+            inline_caches: self.inline_caches,
+            shape_transitions: self.shape_transitions,
+        }
+    }
+}
+
+fn percent(value: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        value as f64 * 100.0 / total as f64
+    }
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableVmStats<'a> {
+    duration: u64,
+    opcodes: Vec<SerializableOpcode<'a>>,
+    functions: Vec<SerializableFunction>,
+    inline_caches: InlineCacheStats,
+    shape_transitions: ShapeTransitionStats,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableOpcode<'a> {
+    name: &'a str,
+    count: u64,
+    time: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SerializableFunction {
+    function_name: String,
+    url: String,
+    line_number: Option<u32>,
+    column_number: Option<u32>,
+    invocations: u64,
+}
+
+/// The counters of the inline caches and shapes, which are shared by all the contexts of a
+/// thread.
+#[derive(Debug, Clone, Copy, Default)]
+struct SharedCounters {
+    inline_caches: InlineCacheStats,
+    shape_transitions: ShapeTransitionStats,
+}
+
+thread_local! {
+    static SHARED_COUNTERS: Cell<SharedCounters> = Cell::new(SharedCounters::default());
+}
+
+fn count_shared(f: impl FnOnce(&mut SharedCounters)) {
+    SHARED_COUNTERS.with(|counters| {
+        let mut value = counters.get();
+        f(&mut value);
+        counters.set(value);
+    });
+}
+
+/// The outcome of a lookup in an inline cache.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum InlineCacheLookup {
+    Hit,
+    Miss,
+    Megamorphic,
+}
+
+/// Counts a lookup in an inline cache.
+pub(crate) fn count_inline_cache_lookup(lookup: InlineCacheLookup) {
+    count_shared(|counters| {
+        let stats = &mut counters.inline_caches;
+        match lookup {
+            InlineCacheLookup::Hit => stats.hits += 1,
+            InlineCacheLookup::Miss => stats.misses += 1,
+            InlineCacheLookup::Megamorphic => stats.megamorphic += 1,
+        }
+    });
+}
+
+/// The kind of a shape transition.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ShapeTransition {
+    InsertProperty,
+    ChangeAttributes,
+    RemoveProperty,
+    ChangePrototype,
+}
+
+/// Counts a shape transition.
+pub(crate) fn count_shape_transition(transition: ShapeTransition) {
+    count_shared(|counters| {
+        let stats = &mut counters.shape_transitions;
+        match transition {
+            ShapeTransition::InsertProperty => stats.insert_property += 1,
+            ShapeTransition::ChangeAttributes => stats.change_attributes += 1,
+            ShapeTransition::RemoveProperty => stats.remove_property += 1,
+            ShapeTransition::ChangePrototype => stats.change_prototype += 1,
+        }
+    });
+}
+
+/// The statistics being collected.
+#[derive(Debug)]
+struct Recording {
+    start: Instant,
+    shared_start: SharedCounters,
+
+    opcode_counts: Box<[u64; 256]>,
+    opcode_times: Box<[Duration; 256]>,
+
+    /// The called functions with their number of calls.
+    functions: Vec<(Gc<CodeBlock>, u64)>,
+    indices: FxHashMap<usize, usize>,
+}
+
+impl Recording {
+    fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            shared_start: SHARED_COUNTERS.with(Cell::get),
+            opcode_counts: Box::new([0; 256]),
+            opcode_times: Box::new([Duration::ZERO; 256]),
+            functions: Vec::new(),
+            indices: FxHashMap::default(),
+        }
+    }
+
+    fn finish(self) -> VmStats {
+        let shared = SHARED_COUNTERS.with(Cell::get);
+
+        let mut opcodes = (0..=u8::MAX)
+            .filter(|&opcode| self.opcode_counts[usize::from(opcode)] != 0)
+            .map(|opcode| OpcodeStats {
+                name: Opcode::from(opcode).as_str(),
+                count: self.opcode_counts[usize::from(opcode)],
+                time: self.opcode_times[usize::from(opcode)],
+            })
+            .collect::<Vec<_>>();
+        opcodes.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(b.name)));
+
+        let mut functions = self
+            .functions
+            .into_iter()
+            .map(|(code_block, invocations)| {
+                let source_info = code_block.source_info();
+                FunctionStats {
+                    function_name: code_block.name().clone(),
+                    path: source_info.map().path().clone(),
+                    position: source_info.map().function_position(),
+                    invocations,
+                }
+            })
+            .collect::<Vec<_>>();
+        // The sort is stable, so functions called as often stay in the order of their first call.
+        functions.sort_by_key(|function| std::cmp::Reverse(function.invocations));
+
+        VmStats {
+            duration: self.start.elapsed(),
+            opcodes,
+            functions,
+            inline_caches: shared.inline_caches.since(self.shared_start.inline_caches),
+            shape_transitions: shared
+                .shape_transitions
+                .since(self.shared_start.shape_transitions),
+        }
+    }
+}
+
+/// The VM statistics state of a [`Context`].
+#[derive(Debug, Default)]
+pub(crate) struct VmStatsState {
+    recording: Option<Recording>,
+}
+
+impl VmStatsState {
+    /// Returns `true` if statistics are collected.
+    #[inline]
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub(crate) fn start(&mut self) {
+        self.recording = Some(Recording::new());
+    }
+
+    pub(crate) fn stop(&mut self) -> Option<VmStats> {
+        self.recording.take().map(Recording::finish)
+    }
+
+    /// Counts a call of the function of `code_block`.
+    pub(crate) fn count_call(&mut self, code_block: &Gc<CodeBlock>) {
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+        let index = *recording
+            .indices
+            .entry(Gc::addr(code_block))
+            .or_insert_with(|| {
+                recording.functions.push((code_block.clone(), 0));
+                recording.functions.len() - 1
+            });
+        recording.functions[index].1 += 1;
+    }
+}
+
+impl Context {
+    /// Executes an instruction, recording its count and time.
+    pub(crate) fn vm_stats_execute_instruction<F>(
+        &mut self,
+        f: F,
+        opcode: Opcode,
+    ) -> ControlFlow<CompletionRecord>
+    where
+        F: FnOnce(&mut Context, Opcode) -> ControlFlow<CompletionRecord>,
+    {
+        let start = Instant::now();
+        let result = f(self, opcode);
+        let time = start.elapsed();
+
+        // The statistics may have been stopped by the instruction.
+        if let Some(recording) = &mut self.vm.stats.recording {
+            recording.opcode_counts[opcode as usize] += 1;
+            recording.opcode_times[opcode as usize] += time;
+        }
+        result
+    }
+}
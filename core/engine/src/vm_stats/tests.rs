@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use boa_parser::Source;
+use indoc::indoc;
+
+use super::{FunctionStats, VmStats};
+use crate::{Context, js_string, vm::SourcePath};
+
+const CODE: &str = indoc! {r#"
+    function point(x, y) {
+        return { x, y };
+    }
+    function unused() {}
+    let sum = 0;
+    for (let i = 0; i < 10; i++) {
+        sum += point(i, i).x;
+    }
+    Object.setPrototypeOf(point(0, 0), null);
+"#};
+
+/// Collects the statistics of `code`, run as a script from `/test.js`.
+fn stats(code: &str) -> VmStats {
+    let mut context = Context::default();
+    context.start_vm_stats();
+    context
+        .eval(Source::from_bytes(code).with_path(Path::new("/test.js")))
+        .expect("the code should run");
+    context
+        .stop_vm_stats()
+        .expect("statistics should be collected")
+}
+
+fn function<'a>(stats: &'a VmStats, name: &str) -> Option<&'a FunctionStats> {
+    stats
+        .functions()
+        .iter()
+        .find(|function| function.function_name() == &js_string!(name))
+}
+
+#[test]
+fn counts_opcodes() {
+    let stats = stats(CODE);
+    assert!(!stats.opcodes().is_empty());
+    assert!(
+        stats
+            .opcodes()
+            .windows(2)
+            .all(|pair| pair[0].count() >= pair[1].count())
+    );
+
+    let count = |name| {
+        stats
+            .opcodes()
+            .iter()
+            .find(|opcode| opcode.name() == name)
+            .map_or(0, super::OpcodeStats::count)
+    };
+    assert_eq!(count("StoreEmptyObject"), 11);
+    assert!(count("Call") >= 11);
+}
+
+#[test]
+fn counts_calls() {
+    let stats = stats(CODE);
+
+    let point = function(&stats, "point").expect("`point` should be called");
+    assert_eq!(point.invocations(), 11);
+    assert_eq!(
+        point.path(),
+        &SourcePath::Path(Path::new("/test.js").into())
+    );
+    assert_eq!(
+        point.position().map(boa_ast::Position::line_number),
+        Some(1)
+    );
+    assert_eq!(stats.functions()[0].function_name(), &js_string!("point"));
+    assert!(function(&stats, "unused").is_none());
+}
+
+#[test]
+fn counts_inline_caches_and_shapes() {
+    let stats = stats(CODE);
+
+    // `point(i, i).x` misses once, then hits the shape of the first point.
+    let ic = stats.inline_caches();
+    assert!(ic.hits() >= 9);
+    assert!(ic.misses() >= 1);
+
+    let shapes = stats.shape_transitions();
+    assert!(shapes.insert_property() >= 2);
+    assert!(shapes.change_prototype() >= 1);
+    assert_eq!(
+        shapes.total(),
+        shapes.insert_property()
+            + shapes.change_attributes()
+            + shapes.remove_property()
+            + shapes.change_prototype()
+    );
+}
+
+#[test]
+fn megamorphic_lookups() {
+    let stats = stats(indoc! {r#"
+        const objects = [{ a: 1 }, { b: 1, a: 1 }, { c: 1, a: 1 }, { d: 1, a: 1 }, { e: 1, a: 1 }];
+        for (let i = 0; i < 10; i++) {
+            for (const object of objects) {
+                object.a;
+            }
+        }
+    "#});
+    assert!(stats.inline_caches().megamorphic() > 0);
+}
+
+#[test]
+fn stop_without_start() {
+    let mut context = Context::default();
+    assert!(context.stop_vm_stats().is_none());
+    context.start_vm_stats();
+    assert!(context.stop_vm_stats().is_some());
+    assert!(context.stop_vm_stats().is_none());
+}
+
+#[test]
+fn table_and_json() {
+    let stats = stats(CODE);
+
+    let table = stats.to_table_string();
+    assert!(table.contains("\nOpcodes:\n"));
+    assert!(table.contains("StoreEmptyObject"));
+    assert!(table.contains("point (/test.js:1:"));
+    assert!(table.contains("\nInline caches:\n"));
+    assert!(table.contains("\nShape transitions:\n"));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&stats.to_json_string()).expect("the statistics should be JSON");
+    assert!(json["opcodes"][0]["count"].as_u64().is_some());
+    let point = json["functions"]
+        .as_array()
+        .expect("functions should be an array")
+        .iter()
+        .find(|function| function["functionName"] == "point")
+        .expect("`point` should be called");
+    assert_eq!(point["invocations"], 11);
+    assert_eq!(point["url"], "/test.js");
+    assert!(json["inlineCaches"]["hits"].as_u64().is_some());
+    assert!(
+        json["shapeTransitions"]["insertProperty"]
+            .as_u64()
+            .is_some()
+    );
+}
@@ -0,0 +1,227 @@
+//! Introspection of the garbage collected heap.
+//!
+//! [`walk_heap`] takes a snapshot of every allocation managed by the garbage collector, together
+//! with the references between them. This is the building block for tools that want to inspect
+//! what is keeping values alive, like heap snapshot exporters.
+
+use crate::{BOA_GC, Gc, GcErased, Tracer, pointers::NonTraceable};
+
+/// The kind of allocation represented by a [`HeapNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapNodeKind {
+    /// An allocation created by [`Gc::new`][crate::Gc::new].
+    Gc,
+
+    /// An allocation created by an [`Ephemeron`][crate::Ephemeron], including the ones backing
+    /// [`WeakGc`][crate::WeakGc] pointers.
+    Ephemeron,
+}
+
+/// A reference from one [`HeapNode`] to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapEdge {
+    target: usize,
+    weak: bool,
+}
+
+impl HeapEdge {
+    /// Returns the [`HeapNode::id`] of the referenced allocation.
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Returns `true` if the reference doesn't keep the referenced allocation alive.
+    ///
+    /// This is the case for references to ephemerons and for the key of an ephemeron.
+    #[inline]
+    #[must_use]
+    pub const fn is_weak(&self) -> bool {
+        self.weak
+    }
+}
+
+/// A single allocation in the garbage collected heap.
+#[derive(Debug)]
+pub struct HeapNode {
+    id: usize,
+    kind: HeapNodeKind,
+    type_name: &'static str,
+    size: usize,
+    root: bool,
+    edges: Vec<HeapEdge>,
+    value: Option<GcErased>,
+}
+
+impl HeapNode {
+    /// Returns the unique identifier of this allocation.
+    ///
+    /// This is the address of the allocation, which is the same value returned by
+    /// [`Gc::addr`][crate::Gc::addr] for the pointers to it. Identifiers can be reused after the
+    /// allocation is collected.
+    #[inline]
+    #[must_use]
+    pub const fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the kind of this allocation.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> HeapNodeKind {
+        self.kind
+    }
+
+    /// Returns the name of the Rust type stored in this allocation.
+    ///
+    /// This is the result of [`std::any::type_name`], so it should only be used for diagnostics.
+    #[inline]
+    #[must_use]
+    pub const fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the size in bytes of this allocation.
+    ///
+    /// This doesn't include any memory owned by the value outside of the garbage collected heap.
+    #[inline]
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns `true` if this allocation is referenced from outside the garbage collected heap.
+    #[inline]
+    #[must_use]
+    pub const fn is_root(&self) -> bool {
+        self.root
+    }
+
+    /// Returns the references from this allocation to other allocations.
+    ///
+    /// Strong references come first, in tracing order, followed by the weak references.
+    #[inline]
+    #[must_use]
+    pub fn edges(&self) -> &[HeapEdge] {
+        &self.edges
+    }
+
+    /// Returns a type erased pointer to the value of this allocation.
+    ///
+    /// This is `None` for ephemerons, since they cannot be accessed through a [`Gc`][crate::Gc].
+    #[inline]
+    #[must_use]
+    pub const fn value(&self) -> Option<&GcErased> {
+        self.value.as_ref()
+    }
+}
+
+/// Walks the garbage collected heap, returning all the allocations that are currently managed by
+/// the garbage collector.
+///
+/// This doesn't run a collection, so the result can contain unreachable allocations that weren't
+/// collected yet. Call [`force_collect`][crate::force_collect] beforehand to only get the live
+/// allocations.
+///
+/// The returned [`HeapNode`]s keep their values alive until they are dropped.
+#[must_use]
+pub fn walk_heap() -> Vec<HeapNode> {
+    BOA_GC.with(|current| {
+        let gc = current.borrow();
+
+        // Count the handles located in the GC heap, to find out which nodes are referenced
+        // from somewhere else. This must be undone before returning.
+        for node in &gc.strongs {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            let trace_non_roots_fn = unsafe { node.as_ref() }.trace_non_roots_fn();
+
+            // SAFETY: The function pointer is appropriate for this node type because we extract it from it's VTable.
+            unsafe {
+                trace_non_roots_fn(*node);
+            }
+        }
+        for eph in &gc.weaks {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            unsafe { eph.as_ref() }.trace_non_roots();
+        }
+
+        let mut nodes = Vec::with_capacity(gc.strongs.len() + gc.weaks.len());
+        let mut tracer = Tracer::new_recording();
+
+        for node in &gc.strongs {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            let node_ref = unsafe { node.as_ref() };
+            let trace_fn = node_ref.trace_fn();
+
+            // SAFETY: The function pointer is appropriate for this node type because we extract it from it's VTable.
+            // Tracing outside of a collection only enqueues the referenced nodes.
+            unsafe { trace_fn(*node, &mut tracer) }
+
+            // Must be checked before creating the handle below, which roots the node.
+            let root = node_ref.is_rooted();
+
+            node_ref.inc_ref_count();
+            // SAFETY: The reference count was incremented above, so the new pointer owns it.
+            let value = GcErased::from_inner(unsafe { Gc::<NonTraceable>::from_raw(*node) });
+
+            nodes.push(HeapNode {
+                id: node.as_ptr().addr(),
+                kind: HeapNodeKind::Gc,
+                type_name: node_ref.type_name(),
+                size: node_ref.size(),
+                root,
+                edges: collect_edges(&mut tracer, None),
+                value: Some(value),
+            });
+        }
+
+        for eph in &gc.weaks {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            let eph_ref = unsafe { eph.as_ref() };
+
+            // SAFETY: We are outside of a collection, so tracing only enqueues the referenced nodes.
+            let key = unsafe { eph_ref.trace_edges(&mut tracer) };
+
+            nodes.push(HeapNode {
+                id: eph.as_ptr().addr(),
+                kind: HeapNodeKind::Ephemeron,
+                type_name: eph_ref.type_name(),
+                size: eph_ref.size(),
+                root: eph_ref.header().is_rooted(),
+                edges: collect_edges(&mut tracer, key),
+                value: None,
+            });
+        }
+
+        // Restore the state the collector expects between collections. Tracing ephemerons
+        // marks their boxes, so those marks must be cleared too.
+        for node in &gc.strongs {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            unsafe { node.as_ref() }.reset_non_root_count();
+        }
+        for eph in &gc.weaks {
+            // SAFETY: node must be valid as this phase cannot drop any node.
+            let header = unsafe { eph.as_ref() }.header();
+            header.unmark();
+            header.reset_non_root_count();
+        }
+
+        nodes
+    })
+}
+
+fn collect_edges(tracer: &mut Tracer, weak_key: Option<usize>) -> Vec<HeapEdge> {
+    let (strong, weak) = tracer.drain();
+
+    let strong = strong.into_iter().map(|node| HeapEdge {
+        target: node.as_ptr().addr(),
+        weak: false,
+    });
+    let weak = weak_key
+        .into_iter()
+        .chain(weak)
+        .map(|target| HeapEdge { target, weak: true });
+
+    strong.chain(weak).collect()
+}
@@ -129,6 +129,18 @@ pub(crate) trait ErasedEphemeronBox {
     /// Runs the finalization logic of the `EphemeronBox`'s held value, if the key is still live,
     /// and clears its contents.
     fn finalize_and_clear(&self);
+
+    /// Gets the type name of the `EphemeronBox`.
+    fn type_name(&self) -> &'static str;
+
+    /// Gets the size in bytes of the `EphemeronBox`.
+    fn size(&self) -> usize;
+
+    /// Traces through the `EphemeronBox`'s held value without checking the marks of the box or
+    /// its key, returning the address of the key if there is one.
+    ///
+    /// This is only used to collect the outgoing edges of the ephemeron when walking the heap.
+    unsafe fn trace_edges(&self, tracer: &mut Tracer) -> Option<usize>;
 }
 
 impl<K: Trace + ?Sized, V: Trace> ErasedEphemeronBox for EphemeronBox<K, V> {
@@ -178,4 +190,25 @@ impl<K: Trace + ?Sized, V: Trace> ErasedEphemeronBox for EphemeronBox<K, V> {
         // there are no remaining references to the inner data.
         unsafe { (*self.data.get()).take() };
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self)
+    }
+
+    unsafe fn trace_edges(&self, tracer: &mut Tracer) -> Option<usize> {
+        // SAFETY: the garbage collector ensures the ephemeron doesn't mutate until
+        // finalization.
+        let data = unsafe { &*self.data.get() };
+        let data = data.as_ref()?;
+
+        // SAFETY: The caller must ensure this is only called outside of a collection, where
+        // tracing doesn't have any side effects besides enqueueing the traced nodes.
+        unsafe { data.value.trace(tracer) }
+
+        Some(data.key.as_ptr().addr())
+    }
 }
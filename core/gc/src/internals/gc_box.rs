@@ -82,4 +82,8 @@ impl<T: Trace + ?Sized> GcBox<T> {
     pub(crate) fn size(&self) -> usize {
         self.vtable.size()
     }
+
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.vtable.type_name()
+    }
 }
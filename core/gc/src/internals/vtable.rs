@@ -51,6 +51,7 @@ pub(crate) const fn vtable_of<T: Trace + 'static>() -> &'static VTable {
             run_finalizer_fn: T::run_finalizer_fn,
             drop_fn: T::drop_fn,
             type_id: TypeId::of::<T>(),
+            type_name_fn: std::any::type_name::<T>,
             size: size_of::<GcBox<T>>(),
         };
     }
@@ -70,6 +71,7 @@ pub(crate) struct VTable {
     run_finalizer_fn: RunFinalizerFn,
     drop_fn: DropFn,
     type_id: TypeId,
+    type_name_fn: fn() -> &'static str,
     size: usize,
 }
 
@@ -94,6 +96,10 @@ impl VTable {
         self.type_id
     }
 
+    pub(crate) fn type_name(&self) -> &'static str {
+        (self.type_name_fn)()
+    }
+
     pub(crate) fn size(&self) -> usize {
         self.size
     }
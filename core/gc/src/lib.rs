@@ -21,6 +21,8 @@ mod cell;
 mod pointers;
 mod trace;
 
+pub mod heap;
+
 pub(crate) mod internals;
 
 use internals::{EphemeronBox, ErasedEphemeronBox, ErasedWeakMapBox, WeakMapBox};
@@ -225,6 +227,15 @@ impl Collector {
     fn collect(gc: &mut BoaGc) {
         gc.runtime.collections += 1;
 
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "gc",
+            collection = gc.runtime.collections,
+            bytes_before = gc.runtime.bytes_allocated,
+            bytes_after = tracing::field::Empty,
+        )
+        .entered();
+
         Self::trace_non_roots(gc);
 
         let mut tracer = Tracer::new();
@@ -275,6 +286,9 @@ impl Collector {
         gc.strongs.shrink_to(gc.strongs.len() >> 2);
         gc.weaks.shrink_to(gc.weaks.len() >> 2);
         gc.weak_maps.shrink_to(gc.weak_maps.len() >> 2);
+
+        #[cfg(feature = "tracing")]
+        span.record("bytes_after", gc.runtime.bytes_allocated);
     }
 
     fn trace_non_roots(gc: &BoaGc) {
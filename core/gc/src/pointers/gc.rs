@@ -69,12 +69,25 @@ impl GcErased {
         }
     }
 
+    pub(crate) const fn from_inner(inner: Gc<NonTraceable>) -> Self {
+        Self { inner }
+    }
+
     /// Returns `true` if the two [`GcErased`]s point to the same allocation.
     #[must_use]
     pub fn ptr_eq(this: &Self, other: &Self) -> bool {
         Gc::ptr_eq(&this.inner, &other.inner)
     }
 
+    /// Returns the address of the allocation.
+    ///
+    /// See [`Gc::addr`].
+    #[inline]
+    #[must_use]
+    pub fn addr(&self) -> usize {
+        Gc::addr(&self.inner)
+    }
+
     /// Returns the [`TypeId`] of the inner type.
     #[inline]
     #[must_use]
@@ -221,6 +234,16 @@ impl<T: Trace + ?Sized> Gc<T> {
         std::ptr::addr_eq(this.inner(), other.inner())
     }
 
+    /// Returns the address of the allocation.
+    ///
+    /// This can be used to identify the allocation, for example to match it with a
+    /// [`HeapNode`][crate::heap::HeapNode].
+    #[inline]
+    #[must_use]
+    pub fn addr(this: &Self) -> usize {
+        this.inner_ptr.as_ptr().addr()
+    }
+
     /// Constructs a `Gc<T>` from a raw pointer.
     ///
     /// The raw pointer must have been returned by a previous call to [`Gc<U>::into_raw`][Gc::into_raw]
@@ -0,0 +1,115 @@
+mod miri {
+    use boa_macros::{Finalize, Trace};
+
+    use super::super::{Harness, run_test};
+    use crate::{
+        Gc, GcErased, WeakGc, force_collect,
+        heap::{HeapNodeKind, walk_heap},
+    };
+
+    #[derive(Debug, Finalize, Trace)]
+    struct Node {
+        value: u32,
+        next: Option<Gc<Node>>,
+    }
+
+    #[test]
+    fn walk_heap_edges_and_roots() {
+        run_test(|| {
+            let leaf = Gc::new(Node {
+                value: 1,
+                next: None,
+            });
+            let root = Gc::new(Node {
+                value: 2,
+                next: Some(leaf.clone()),
+            });
+            drop(leaf);
+
+            let nodes = walk_heap();
+            assert_eq!(nodes.len(), 2);
+
+            let root_node = nodes
+                .iter()
+                .find(|node| node.id() == Gc::addr(&root))
+                .expect("root must be in the heap");
+            let leaf_node = nodes
+                .iter()
+                .find(|node| node.id() != Gc::addr(&root))
+                .expect("leaf must be in the heap");
+
+            assert!(root_node.is_root());
+            assert!(!leaf_node.is_root());
+            assert_eq!(root_node.kind(), HeapNodeKind::Gc);
+            assert!(root_node.type_name().ends_with("Node"));
+            assert_eq!(root_node.edges().len(), 1);
+            assert_eq!(root_node.edges()[0].target(), leaf_node.id());
+            assert!(!root_node.edges()[0].is_weak());
+            assert!(leaf_node.edges().is_empty());
+
+            let value = root_node
+                .value()
+                .cloned()
+                .and_then(GcErased::downcast::<Node>)
+                .expect("value must be a `Node`");
+            assert_eq!(value.value, 2);
+        });
+    }
+
+    #[test]
+    fn walk_heap_weak_edges() {
+        run_test(|| {
+            let target = Gc::new(Node {
+                value: 1,
+                next: None,
+            });
+            let holder = Gc::new(WeakGc::new(&target));
+
+            let nodes = walk_heap();
+            assert_eq!(nodes.len(), 3);
+
+            let holder_node = nodes
+                .iter()
+                .find(|node| node.id() == Gc::addr(&holder))
+                .expect("holder must be in the heap");
+            let ephemeron_node = nodes
+                .iter()
+                .find(|node| node.kind() == HeapNodeKind::Ephemeron)
+                .expect("ephemeron must be in the heap");
+
+            assert_eq!(holder_node.edges().len(), 1);
+            assert_eq!(holder_node.edges()[0].target(), ephemeron_node.id());
+            assert!(holder_node.edges()[0].is_weak());
+            assert!(!ephemeron_node.is_root());
+            assert_eq!(ephemeron_node.edges().len(), 1);
+            assert_eq!(ephemeron_node.edges()[0].target(), Gc::addr(&target));
+            assert!(ephemeron_node.edges()[0].is_weak());
+        });
+    }
+
+    #[test]
+    fn walk_heap_preserves_collection() {
+        run_test(|| {
+            let target = Gc::new(Node {
+                value: 1,
+                next: None,
+            });
+            let weak = WeakGc::new(&target);
+            let cycle = Gc::new(Node {
+                value: 2,
+                next: Some(target.clone()),
+            });
+
+            drop(walk_heap());
+            drop(cycle);
+            drop(target);
+
+            force_collect();
+            assert!(weak.upgrade().is_none());
+
+            drop(weak);
+            force_collect();
+            Harness::assert_empty_gc();
+        });
+    }
+}
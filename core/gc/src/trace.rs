@@ -5,6 +5,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
     hash::{BuildHasher, Hash},
     marker::PhantomData,
+    mem,
     num::{
         NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8,
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
@@ -22,12 +23,25 @@ use crate::GcErasedPointer;
 #[allow(missing_debug_implementations)]
 pub struct Tracer {
     queue: VecDeque<GcErasedPointer>,
+    ephemerons: Option<Vec<usize>>,
 }
 
 impl Tracer {
     pub(crate) fn new() -> Self {
         Self {
             queue: VecDeque::default(),
+            ephemerons: None,
+        }
+    }
+
+    /// Creates a tracer that also records the addresses of the traced ephemerons.
+    ///
+    /// This is only used when walking the heap, since the collector doesn't need to know
+    /// which ephemerons are reachable from a node.
+    pub(crate) fn new_recording() -> Self {
+        Self {
+            queue: VecDeque::default(),
+            ephemerons: Some(Vec::new()),
         }
     }
 
@@ -35,6 +49,19 @@ impl Tracer {
         self.queue.push_back(node);
     }
 
+    pub(crate) fn record_ephemeron(&mut self, addr: usize) {
+        if let Some(ephemerons) = &mut self.ephemerons {
+            ephemerons.push(addr);
+        }
+    }
+
+    /// Takes all the enqueued nodes and recorded ephemerons, without tracing through them.
+    pub(crate) fn drain(&mut self) -> (Vec<GcErasedPointer>, Vec<usize>) {
+        let strong = self.queue.drain(..).collect();
+        let weak = self.ephemerons.as_mut().map(mem::take).unwrap_or_default();
+        (strong, weak)
+    }
+
     /// Traces through all the queued nodes until the queue is empty.
     ///
     /// # Safety
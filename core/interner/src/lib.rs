@@ -249,7 +249,10 @@ impl core::fmt::Display for JSInternedStrRef<'_, '_> {
 }
 
 /// The string interner for Boa.
-#[derive(Debug, Default)]
+///
+/// Cloning an interner copies all of its strings, and the clone resolves the same symbols to the
+/// same strings.
+#[derive(Debug, Default, Clone)]
 pub struct Interner {
     utf8_interner: RawInterner<u8>,
     utf16_interner: RawInterner<u16>,
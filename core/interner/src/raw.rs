@@ -38,6 +38,28 @@ impl<Char> Default for RawInterner<Char> {
     }
 }
 
+impl<Char> Clone for RawInterner<Char>
+where
+    Char: Hash + Eq + Clone,
+{
+    fn clone(&self) -> Self {
+        // The spans point into the memory of `self`, so the strings are interned again, in the
+        // same order to keep their indices.
+        let capacity = self.spans.iter().map(|span| {
+            // SAFETY: We always ensure the stored `InternedStr`s always
+            // reference memory inside `head` and `full`
+            unsafe { span.as_ref() }.len()
+        });
+        let mut interner = Self::with_capacity(capacity.sum());
+        for span in &self.spans {
+            // SAFETY: We always ensure the stored `InternedStr`s always
+            // reference memory inside `head` and `full`
+            interner.intern(unsafe { span.as_ref() });
+        }
+        interner
+    }
+}
+
 impl<Char> RawInterner<Char> {
     /// Creates a new `RawInterner` with the specified capacity.
     pub(super) fn with_capacity(capacity: usize) -> Self {
@@ -164,3 +164,26 @@ fn check_is_latin1() {
     let static_sym = interner.get_or_intern_static(STATIC_STR, STATIC_UTF16);
     assert!(interner.is_latin1(static_sym));
 }
+
+#[test]
+fn check_clone() {
+    let unp = &[0xDC15u16, 'h' as u16, 'i' as u16];
+
+    let mut interner = Interner::default();
+    let sym = interner.get_or_intern("abc");
+    let sym2 = interner.get_or_intern(unp);
+    let sym3 = interner.get_or_intern_static("def", utf16!("def"));
+
+    let mut clone = interner.clone();
+    assert_eq!(clone.len(), interner.len());
+    assert_eq!(clone.resolve_expect(sym).utf8(), Some("abc"));
+    assert!(clone.resolve_expect(sym2).utf8().is_none());
+    assert_eq!(clone.resolve_expect(sym2).utf16(), unp);
+    assert_eq!(clone.resolve_expect(sym3).utf8(), Some("def"));
+    assert_eq!(clone.get_or_intern("def"), sym3);
+
+    // The clone and the original can intern new strings independently.
+    let sym4 = clone.get_or_intern("ghi");
+    assert!(interner.resolve(sym4).is_none());
+    assert_eq!(interner.get_or_intern("jkl"), sym4);
+}
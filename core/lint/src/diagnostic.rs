@@ -0,0 +1,261 @@
+//! The problems reported by the rules, and the fixes for them.
+
+use crate::Level;
+use boa_ast::{Position, Span, Spanned};
+use boa_parser::diagnostic::{Diagnostic, Label, Severity};
+
+/// A replacement of a span of the source code with a new text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    span: Span,
+    text: Box<str>,
+}
+
+impl Edit {
+    /// Creates a new edit that replaces `span` with `text`.
+    pub fn new<T>(span: Span, text: T) -> Self
+    where
+        T: Into<Box<str>>,
+    {
+        Self {
+            span,
+            text: text.into(),
+        }
+    }
+
+    /// Gets the text that replaces the span.
+    #[inline]
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Spanned for Edit {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A change of the source code that fixes a [`LintDiagnostic`].
+///
+/// All the edits of a fix are applied together, or not at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    message: Box<str>,
+    edits: Vec<Edit>,
+}
+
+impl Fix {
+    /// Creates a new fix, described by `message`, made of the given edits.
+    pub fn new<M>(message: M, edits: Vec<Edit>) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            message: message.into(),
+            edits,
+        }
+    }
+
+    /// Gets the description of the fix, like "rename to `_a`".
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the edits of the fix.
+    #[inline]
+    #[must_use]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+}
+
+/// A problem found by a [`Rule`](crate::Rule).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    pub(crate) rule: &'static str,
+    pub(crate) level: Level,
+    message: Box<str>,
+    span: Span,
+    labels: Vec<Label>,
+    fix: Option<Fix>,
+}
+
+impl LintDiagnostic {
+    /// Creates a new diagnostic for the problem at `span`.
+    ///
+    /// The rule and level of the diagnostic are set when it's reported with
+    /// [`LintContext::report`](crate::LintContext::report).
+    pub fn new<M>(span: Span, message: M) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            rule: "",
+            level: Level::Warn,
+            message: message.into(),
+            span,
+            labels: Vec::new(),
+            fix: None,
+        }
+    }
+
+    /// Adds a secondary label to the diagnostic.
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Sets the fix of the diagnostic.
+    #[must_use]
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Gets the name of the rule that reported the diagnostic.
+    #[inline]
+    #[must_use]
+    pub const fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    /// Gets the level the diagnostic was reported at.
+    #[inline]
+    #[must_use]
+    pub const fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Gets the message of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the secondary labels of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Gets the fix of the diagnostic, if it has one.
+    #[inline]
+    #[must_use]
+    pub const fn fix(&self) -> Option<&Fix> {
+        self.fix.as_ref()
+    }
+}
+
+impl Spanned for LintDiagnostic {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl From<&LintDiagnostic> for Diagnostic {
+    fn from(diagnostic: &LintDiagnostic) -> Self {
+        let severity = match diagnostic.level {
+            Level::Off | Level::Warn => Severity::Warning,
+            Level::Error => Severity::Error,
+        };
+        let mut result = Self::new(diagnostic.message.clone())
+            .with_severity(severity)
+            .with_code(diagnostic.rule)
+            .with_span(diagnostic.span);
+        for label in &diagnostic.labels {
+            result = result.with_label(label.clone());
+        }
+        result
+    }
+}
+
+/// Applies the fixes of the diagnostics to `source`, the source code they were reported for.
+///
+/// Fixes are applied in the order of the diagnostics, and a fix that overlaps with one that was
+/// already applied is skipped, so linting the fixed code again may find more fixes to apply.
+/// Returns the fixed source code and the number of fixes that were applied.
+#[must_use]
+pub fn apply_fixes(source: &str, diagnostics: &[LintDiagnostic]) -> (String, usize) {
+    let lines = line_starts(source);
+    let mut applied: Vec<(usize, usize, &str)> = Vec::new();
+    let mut count = 0;
+    for fix in diagnostics.iter().filter_map(LintDiagnostic::fix) {
+        let edits: Vec<_> = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                (
+                    offset(source, &lines, edit.span.start()),
+                    offset(source, &lines, edit.span.end()),
+                    &*edit.text,
+                )
+            })
+            .collect();
+        let overlaps = edits.iter().any(|(start, end, _)| {
+            applied
+                .iter()
+                .any(|(other_start, other_end, _)| start < other_end && other_start < end)
+        });
+        if !overlaps {
+            applied.extend(edits);
+            count += 1;
+        }
+    }
+
+    applied.sort_by_key(|(start, end, _)| (*start, *end));
+    let mut fixed = String::with_capacity(source.len());
+    let mut last = 0;
+    for (start, end, text) in applied {
+        if start < last {
+            continue;
+        }
+        fixed.push_str(&source[last..start]);
+        fixed.push_str(text);
+        last = end;
+    }
+    fixed.push_str(&source[last..]);
+    (fixed, count)
+}
+
+/// Gets the byte offsets where the lines of `source` start, using the same line terminators as
+/// the lexer.
+fn line_starts(source: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    let mut chars = source.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            let mut start = index + ch.len_utf8();
+            if ch == '\r'
+                && let Some((_, '\n')) = chars.peek()
+            {
+                chars.next();
+                start += 1;
+            }
+            starts.push(start);
+        }
+    }
+    starts
+}
+
+/// Gets the byte offset of a position of `source`, whose columns count characters.
+fn offset(source: &str, lines: &[usize], position: Position) -> usize {
+    let Some(&start) = (position.line_number() as usize)
+        .checked_sub(1)
+        .and_then(|index| lines.get(index))
+    else {
+        return source.len();
+    };
+    source[start..]
+        .char_indices()
+        .nth(position.column_number() as usize - 1)
+        .map_or(source.len(), |(index, _)| start + index)
+}
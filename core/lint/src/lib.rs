@@ -0,0 +1,387 @@
+//! Boa's **`boa_lint`** crate is a linter for ECMAScript scripts and modules.
+//!
+//! # Crate Overview
+//!
+//! The [`Linter`] runs a set of [`Rule`]s over an AST returned by the parser, and reports the
+//! problems they find as [`LintDiagnostic`]s, which point to the [`Span`] of the source code
+//! that caused them and may come with a [`Fix`] that can be applied to the source code with
+//! [`apply_fixes`].
+//!
+//! Rules walk the AST with a [`Visitor`](boa_ast::visitor::Visitor), and can ask the
+//! [`SemanticModel`] of the code where each binding is declared and referenced. Every rule has a
+//! [`Level`] that can be changed per rule, and can have options of its own, which are set with
+//! [`Linter::configure`].
+//!
+//! The starter set of [`rules`] targets scripts that are embedded in an application, where
+//! dynamic code evaluation and leaking state to the global object are usually mistakes.
+//!
+//! [`Span`]: boa_ast::Span
+//!
+//! ```
+//! # use boa_ast::scope::Scope;
+//! # use boa_interner::Interner;
+//! # use boa_lint::{Level, Linter};
+//! # use boa_parser::{Parser, Source};
+//! let code = "function run(code) { return eval(code); }\nfunction save(value) { saved = value; }";
+//! let interner = &mut Interner::default();
+//! let scope = Scope::new_global();
+//! let script = Parser::new(Source::from_bytes(code))
+//!     .parse_script(&scope, interner)
+//!     .unwrap();
+//!
+//! let mut linter = Linter::default();
+//! linter.set_level("no-eval", Level::Warn).unwrap();
+//!
+//! let diagnostics = linter.lint_script(&script, &scope, interner);
+//! let rules: Vec<_> = diagnostics.iter().map(|d| (d.rule(), d.level())).collect();
+//! assert_eq!(
+//!     rules,
+//!     [("no-eval", Level::Warn), ("no-implicit-globals", Level::Error)]
+//! );
+//! ```
+#![doc = include_str!("../ABOUT.md")]
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/boa-dev/boa/main/assets/logo_black.svg",
+    html_favicon_url = "https://raw.githubusercontent.com/boa-dev/boa/main/assets/logo_black.svg"
+)]
+#![cfg_attr(not(test), forbid(clippy::unwrap_used))]
+#![allow(clippy::module_name_repetitions)]
+
+mod diagnostic;
+pub mod rules;
+
+#[cfg(test)]
+mod tests;
+
+pub use diagnostic::{Edit, Fix, LintDiagnostic, apply_fixes};
+
+use boa_ast::{
+    Module, Script, Spanned,
+    scope::Scope,
+    semantic::SemanticModel,
+    visitor::{VisitWith, Visitor},
+};
+use boa_interner::Interner;
+use std::{error, fmt, ops::ControlFlow, str::FromStr};
+
+/// How the problems found by a rule are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    /// The rule is disabled.
+    Off,
+
+    /// The problems are reported as warnings.
+    Warn,
+
+    /// The problems are reported as errors.
+    Error,
+}
+
+impl Level {
+    /// Gets the name of the level, as accepted by [`Level::from_str`].
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Level {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(ConfigError::InvalidLevel(s.into())),
+        }
+    }
+}
+
+/// An error in the configuration of a [`Linter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// There is no rule with the given name.
+    UnknownRule(Box<str>),
+
+    /// The rule doesn't have an option with the given name.
+    UnknownOption {
+        /// The name of the rule.
+        rule: &'static str,
+        /// The name of the option.
+        option: Box<str>,
+    },
+
+    /// The value of an option is not valid.
+    InvalidValue {
+        /// The name of the rule.
+        rule: &'static str,
+        /// The name of the option.
+        option: Box<str>,
+        /// The invalid value.
+        value: Box<str>,
+        /// A description of the valid values.
+        expected: &'static str,
+    },
+
+    /// The name of a level is not `off`, `warn` or `error`.
+    InvalidLevel(Box<str>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRule(rule) => write!(f, "unknown rule `{rule}`"),
+            Self::UnknownOption { rule, option } => {
+                write!(f, "rule `{rule}` has no option `{option}`")
+            }
+            Self::InvalidValue {
+                rule,
+                option,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value `{value}` for option `{option}` of rule `{rule}`, expected {expected}"
+            ),
+            Self::InvalidLevel(level) => write!(
+                f,
+                "invalid level `{level}`, expected `off`, `warn` or `error`"
+            ),
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+/// A lint rule, which checks a script or module for a kind of problem.
+///
+/// See the [`rules`] module for the rules provided by this crate.
+pub trait Rule: fmt::Debug {
+    /// Gets the name of the rule, in kebab case, like `no-eval`.
+    fn name(&self) -> &'static str;
+
+    /// Gets a short description of the problems the rule reports.
+    fn description(&self) -> &'static str;
+
+    /// Gets the level the rule is reported at unless configured otherwise.
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    /// Sets an option of the rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rule doesn't have the option or the value is not valid for it.
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), ConfigError> {
+        let _ = value;
+        Err(ConfigError::UnknownOption {
+            rule: self.name(),
+            option: option.into(),
+        })
+    }
+
+    /// Checks the code of the context, reporting the problems found with
+    /// [`LintContext::report`].
+    fn check(&self, context: &mut LintContext<'_>);
+}
+
+/// The AST that is linted, either a script or a module.
+#[derive(Debug, Clone, Copy)]
+pub enum Program<'a> {
+    /// A script.
+    Script(&'a Script),
+
+    /// A module.
+    Module(&'a Module),
+}
+
+impl<'a> Program<'a> {
+    /// Walks the AST with a visitor.
+    pub fn visit<V>(self, visitor: &mut V) -> ControlFlow<V::BreakTy>
+    where
+        V: Visitor<'a>,
+    {
+        match self {
+            Self::Script(script) => script.visit_with(visitor),
+            Self::Module(module) => module.visit_with(visitor),
+        }
+    }
+}
+
+/// The code checked by a [`Rule`], and the problems reported for it.
+#[derive(Debug)]
+pub struct LintContext<'a> {
+    program: Program<'a>,
+    semantic: &'a SemanticModel,
+    interner: &'a Interner,
+    rule: &'static str,
+    level: Level,
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl<'a> LintContext<'a> {
+    /// Gets the AST that is linted.
+    #[inline]
+    #[must_use]
+    pub const fn program(&self) -> Program<'a> {
+        self.program
+    }
+
+    /// Gets the semantic model of the AST.
+    #[inline]
+    #[must_use]
+    pub const fn semantic(&self) -> &'a SemanticModel {
+        self.semantic
+    }
+
+    /// Gets the interner the symbols of the AST were interned with.
+    #[inline]
+    #[must_use]
+    pub const fn interner(&self) -> &'a Interner {
+        self.interner
+    }
+
+    /// Reports a problem found by the rule, at the level the rule is configured with.
+    pub fn report(&mut self, mut diagnostic: LintDiagnostic) {
+        diagnostic.rule = self.rule;
+        diagnostic.level = self.level;
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+/// Runs a set of [`Rule`]s over scripts and modules.
+///
+/// [`Linter::default`] has all the [`rules`] of this crate at their default level, and
+/// [`Linter::new`] has no rules.
+#[derive(Debug)]
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Level)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut linter = Self::new();
+        for rule in rules::all() {
+            linter.add_rule(rule);
+        }
+        linter
+    }
+}
+
+impl Linter {
+    /// Creates a new `Linter` without rules.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule at its default level, replacing the rule with the same name if there is one.
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        let level = rule.default_level();
+        if let Some(entry) = self
+            .rules
+            .iter_mut()
+            .find(|(existing, _)| existing.name() == rule.name())
+        {
+            *entry = (rule, level);
+        } else {
+            self.rules.push((rule, level));
+        }
+    }
+
+    /// Gets the rules of the linter with their levels.
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Level)> {
+        self.rules
+            .iter()
+            .map(|(rule, level)| (rule.as_ref(), *level))
+    }
+
+    /// Sets the level of a rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the linter doesn't have the rule.
+    pub fn set_level(&mut self, rule: &str, level: Level) -> Result<(), ConfigError> {
+        self.entry(rule)?.1 = level;
+        Ok(())
+    }
+
+    /// Sets an option of a rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the linter doesn't have the rule, or if the rule rejects the option.
+    pub fn configure(&mut self, rule: &str, option: &str, value: &str) -> Result<(), ConfigError> {
+        self.entry(rule)?.0.configure(option, value)
+    }
+
+    fn entry(&mut self, rule: &str) -> Result<&mut (Box<dyn Rule>, Level), ConfigError> {
+        self.rules
+            .iter_mut()
+            .find(|(existing, _)| existing.name() == rule)
+            .ok_or_else(|| ConfigError::UnknownRule(rule.into()))
+    }
+
+    /// Lints a script that was analyzed in the given global scope, like the scripts returned by
+    /// [`Parser::parse_script`](boa_parser::Parser::parse_script).
+    ///
+    /// The diagnostics are sorted by their position in the source code.
+    #[must_use]
+    pub fn lint_script(
+        &self,
+        script: &Script,
+        scope: &Scope,
+        interner: &Interner,
+    ) -> Vec<LintDiagnostic> {
+        let semantic = SemanticModel::script(script, scope, interner);
+        self.lint(Program::Script(script), &semantic, interner)
+    }
+
+    /// Lints an analyzed module, like the modules returned by
+    /// [`Parser::parse_module`](boa_parser::Parser::parse_module).
+    ///
+    /// The diagnostics are sorted by their position in the source code.
+    #[must_use]
+    pub fn lint_module(&self, module: &Module, interner: &Interner) -> Vec<LintDiagnostic> {
+        let semantic = SemanticModel::module(module, interner);
+        self.lint(Program::Module(module), &semantic, interner)
+    }
+
+    fn lint(
+        &self,
+        program: Program<'_>,
+        semantic: &SemanticModel,
+        interner: &Interner,
+    ) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+        for (rule, level) in &self.rules {
+            if *level == Level::Off {
+                continue;
+            }
+            let mut context = LintContext {
+                program,
+                semantic,
+                interner,
+                rule: rule.name(),
+                level: *level,
+                diagnostics,
+            };
+            rule.check(&mut context);
+            diagnostics = context.diagnostics;
+        }
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span().start());
+        diagnostics
+    }
+}
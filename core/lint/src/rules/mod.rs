@@ -0,0 +1,38 @@
+//! The lint rules provided by this crate.
+//!
+//! | Rule | Default level | Options |
+//! |------|---------------|---------|
+//! | [`no-eval`](NoEval) | `error` | |
+//! | [`no-with`](NoWith) | `error` | |
+//! | [`no-implicit-globals`](NoImplicitGlobals) | `error` | `globals` |
+//! | [`no-unused-vars`](NoUnusedVars) | `warn` | `vars`, `args`, `ignore-prefix` |
+//! | [`no-unreachable`](NoUnreachable) | `warn` | |
+
+mod no_eval;
+mod no_implicit_globals;
+mod no_unreachable;
+mod no_unused_vars;
+mod no_with;
+
+#[cfg(test)]
+mod tests;
+
+pub use no_eval::NoEval;
+pub use no_implicit_globals::NoImplicitGlobals;
+pub use no_unreachable::NoUnreachable;
+pub use no_unused_vars::{ArgsOption, NoUnusedVars, VarsOption};
+pub use no_with::NoWith;
+
+use crate::Rule;
+
+/// Gets all the rules provided by this crate, with their default options.
+#[must_use]
+pub fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(NoEval),
+        Box::new(NoWith),
+        Box::new(NoImplicitGlobals::default()),
+        Box::new(NoUnusedVars::default()),
+        Box::new(NoUnreachable),
+    ]
+}
@@ -0,0 +1,39 @@
+use crate::{Level, LintContext, LintDiagnostic, Rule};
+use boa_ast::Spanned;
+use boa_interner::Sym;
+
+/// Disallows the use of the global `eval` function.
+///
+/// `eval` runs code that is not known ahead of time with the same privileges as the script that
+/// calls it, and prevents the bindings around it from being optimized. Both direct and indirect
+/// calls, and any other use of the global `eval`, are reported. A local binding named `eval` is
+/// allowed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoEval;
+
+impl Rule for NoEval {
+    fn name(&self) -> &'static str {
+        "no-eval"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow the use of `eval`"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Error
+    }
+
+    fn check(&self, context: &mut LintContext<'_>) {
+        let semantic = context.semantic();
+        for reference in semantic.references() {
+            let binding = semantic.binding(reference.binding());
+            if binding.name() == Sym::EVAL && binding.is_global() && !binding.is_declared() {
+                context.report(LintDiagnostic::new(
+                    reference.span(),
+                    "`eval` can be harmful",
+                ));
+            }
+        }
+    }
+}
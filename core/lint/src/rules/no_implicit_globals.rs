@@ -0,0 +1,86 @@
+use crate::{ConfigError, Level, LintContext, LintDiagnostic, Rule};
+use boa_ast::{Spanned, semantic::ReferenceKind};
+
+/// Disallows assignments to undeclared variables.
+///
+/// Outside of strict mode code, assigning to a variable that was never declared silently creates
+/// a property of the global object, which leaks state between the scripts that share a realm and
+/// usually hides a typo. In strict mode code the assignment throws a `ReferenceError` instead.
+///
+/// Assignments inside of a `with` statement or a scope with a direct `eval` are not reported,
+/// since they could resolve to a binding at runtime.
+///
+/// # Options
+///
+/// - `globals`: a comma separated list of the names of the global variables that the host
+///   defines, which can be assigned.
+#[derive(Debug, Clone, Default)]
+pub struct NoImplicitGlobals {
+    globals: Vec<Box<str>>,
+}
+
+impl NoImplicitGlobals {
+    /// Allows assignments to a global variable that the host defines.
+    #[must_use]
+    pub fn with_global<N>(mut self, name: N) -> Self
+    where
+        N: Into<Box<str>>,
+    {
+        self.globals.push(name.into());
+        self
+    }
+}
+
+impl Rule for NoImplicitGlobals {
+    fn name(&self) -> &'static str {
+        "no-implicit-globals"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow assignments to undeclared variables"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Error
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), ConfigError> {
+        match option {
+            "globals" => {
+                self.globals = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(Into::into)
+                    .collect();
+                Ok(())
+            }
+            _ => Err(ConfigError::UnknownOption {
+                rule: self.name(),
+                option: option.into(),
+            }),
+        }
+    }
+
+    fn check(&self, context: &mut LintContext<'_>) {
+        let semantic = context.semantic();
+        let interner = context.interner();
+        for reference in semantic.references() {
+            if reference.kind() == ReferenceKind::Read || reference.is_dynamic() {
+                continue;
+            }
+            let binding = semantic.binding(reference.binding());
+            if binding.is_declared() || !binding.is_global() {
+                continue;
+            }
+            let name = interner.resolve_expect(binding.name()).to_string();
+            if self.globals.iter().any(|global| **global == *name) {
+                continue;
+            }
+            context.report(LintDiagnostic::new(
+                reference.span(),
+                format!("assignment to undeclared variable `{name}`"),
+            ));
+        }
+    }
+}
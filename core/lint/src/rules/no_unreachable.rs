@@ -0,0 +1,178 @@
+use crate::{LintContext, LintDiagnostic, Rule};
+use boa_ast::{
+    Declaration, Expression, ModuleItem, ModuleItemList, Script, Span, Spanned, Statement,
+    StatementList, StatementListItem,
+    expression::Identifier,
+    function::FunctionBody,
+    visitor::{VisitWith, Visitor},
+};
+use std::{convert::Infallible, ops::ControlFlow};
+
+/// Disallows code after a `return`, `throw`, `break` or `continue` statement, which can never
+/// run.
+///
+/// Statements that always end abruptly, like a block that ends with a `return` or an `if`
+/// statement whose branches both `throw`, also make the code after them unreachable. Loops and
+/// labelled statements are assumed to complete normally.
+///
+/// Function declarations and `var` declarations without an initializer are hoisted, so they are
+/// not reported. The problem is reported at the expressions and bindings of the unreachable
+/// statements, since the AST doesn't keep the positions of the statements themselves, which
+/// means that unreachable statements without any of them, like `break;`, are not reported.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoUnreachable;
+
+impl Rule for NoUnreachable {
+    fn name(&self) -> &'static str {
+        "no-unreachable"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow code that can never run"
+    }
+
+    fn check(&self, context: &mut LintContext<'_>) {
+        let mut visitor = UnreachableVisitor(Vec::new());
+        let _ = context.program().visit(&mut visitor);
+        for span in visitor.0 {
+            context.report(LintDiagnostic::new(span, "unreachable code"));
+        }
+    }
+}
+
+/// Collects the spans of the unreachable code of every list of statements.
+struct UnreachableVisitor(Vec<Span>);
+
+impl UnreachableVisitor {
+    fn check<'a, I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = &'a StatementListItem>,
+    {
+        let mut abrupt = false;
+        let mut unreachable: Option<Span> = None;
+        for item in items {
+            if abrupt {
+                if !is_hoisted(item)
+                    && let Some(span) = span_of(item)
+                {
+                    unreachable = Some(unreachable.map_or(span, |other| other.union(span)));
+                }
+            } else {
+                abrupt = item_completes_abruptly(item);
+            }
+        }
+        self.0.extend(unreachable);
+    }
+}
+
+impl<'ast> Visitor<'ast> for UnreachableVisitor {
+    type BreakTy = Infallible;
+
+    fn visit_script(&mut self, node: &'ast Script) -> ControlFlow<Self::BreakTy> {
+        self.check(node.statements().statements());
+        node.visit_with(self)
+    }
+
+    fn visit_module_item_list(&mut self, node: &'ast ModuleItemList) -> ControlFlow<Self::BreakTy> {
+        self.check(node.items().iter().filter_map(|item| match item {
+            ModuleItem::StatementListItem(item) => Some(item),
+            ModuleItem::ImportDeclaration(_) | ModuleItem::ExportDeclaration(_) => None,
+        }));
+        node.visit_with(self)
+    }
+
+    fn visit_function_body(&mut self, node: &'ast FunctionBody) -> ControlFlow<Self::BreakTy> {
+        self.check(node.statements());
+        node.visit_with(self)
+    }
+
+    fn visit_statement_list(&mut self, node: &'ast StatementList) -> ControlFlow<Self::BreakTy> {
+        self.check(node.statements());
+        node.visit_with(self)
+    }
+}
+
+/// Returns `true` if the item is hoisted, so it is not unreachable even after the end of its
+/// list of statements.
+fn is_hoisted(item: &StatementListItem) -> bool {
+    match item {
+        StatementListItem::Declaration(declaration) => matches!(
+            declaration.as_ref(),
+            Declaration::FunctionDeclaration(_)
+                | Declaration::GeneratorDeclaration(_)
+                | Declaration::AsyncFunctionDeclaration(_)
+                | Declaration::AsyncGeneratorDeclaration(_)
+        ),
+        StatementListItem::Statement(statement) => match statement.as_ref() {
+            Statement::Empty => true,
+            Statement::Var(var) => var
+                .0
+                .as_ref()
+                .iter()
+                .all(|variable| variable.init().is_none()),
+            _ => false,
+        },
+    }
+}
+
+/// Returns `true` if the item never completes normally.
+fn item_completes_abruptly(item: &StatementListItem) -> bool {
+    match item {
+        StatementListItem::Statement(statement) => completes_abruptly(statement),
+        StatementListItem::Declaration(_) => false,
+    }
+}
+
+/// Returns `true` if the statement never completes normally.
+fn completes_abruptly(statement: &Statement) -> bool {
+    let list = |list: &StatementList| list.statements().iter().any(item_completes_abruptly);
+    match statement {
+        Statement::Return(_)
+        | Statement::Throw(_)
+        | Statement::Break(_)
+        | Statement::Continue(_) => true,
+        Statement::Block(block) => list(block.statement_list()),
+        Statement::If(node) => {
+            node.else_node().is_some_and(completes_abruptly) && completes_abruptly(node.body())
+        }
+        Statement::Try(node) => {
+            node.finally()
+                .is_some_and(|finally| list(finally.block().statement_list()))
+                || (list(node.block().statement_list())
+                    && node
+                        .catch()
+                        .is_none_or(|catch| list(catch.block().statement_list())))
+        }
+        _ => false,
+    }
+}
+
+/// Gets the span of the expressions and bindings of an item, if it has any.
+fn span_of(item: &StatementListItem) -> Option<Span> {
+    let mut visitor = SpanVisitor(None);
+    let _ = item.visit_with(&mut visitor);
+    visitor.0
+}
+
+/// Computes the union of the spans of the expressions and bindings it visits.
+struct SpanVisitor(Option<Span>);
+
+impl SpanVisitor {
+    fn add(&mut self, span: Span) {
+        self.0 = Some(self.0.map_or(span, |other| other.union(span)));
+    }
+}
+
+impl<'ast> Visitor<'ast> for SpanVisitor {
+    type BreakTy = Infallible;
+
+    fn visit_expression(&mut self, node: &'ast Expression) -> ControlFlow<Self::BreakTy> {
+        self.add(node.span());
+        node.visit_with(self)
+    }
+
+    fn visit_identifier(&mut self, node: &'ast Identifier) -> ControlFlow<Self::BreakTy> {
+        self.add(node.span());
+        ControlFlow::Continue(())
+    }
+}
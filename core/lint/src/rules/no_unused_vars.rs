@@ -0,0 +1,346 @@
+use crate::{ConfigError, Edit, Fix, LintContext, LintDiagnostic, Program, Rule};
+use boa_ast::{
+    Span, Spanned,
+    expression::Identifier,
+    function::{
+        AsyncFunctionExpression, AsyncGeneratorExpression, ClassExpression, FormalParameterList,
+        FunctionExpression, GeneratorExpression,
+    },
+    operations::{bound_identifiers, lexically_declared_identifiers, var_declared_identifiers},
+    pattern::ObjectPatternElement,
+    property::PropertyName,
+    semantic::{BindingId, BindingKind, Reference, ReferenceKind},
+    visitor::{VisitWith, Visitor},
+};
+use rustc_hash::FxHashSet;
+use std::{convert::Infallible, ops::ControlFlow};
+
+/// Which variables are checked by [`NoUnusedVars`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VarsOption {
+    /// All the variables are checked, including the global variables of scripts.
+    All,
+
+    /// The global variables of scripts are not checked, since other scripts or the host can use
+    /// them.
+    #[default]
+    Local,
+}
+
+/// Which parameters of functions are checked by [`NoUnusedVars`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArgsOption {
+    /// Only the parameters after the last used parameter are checked, since the ones before it
+    /// cannot be removed.
+    #[default]
+    AfterUsed,
+
+    /// All the parameters are checked.
+    All,
+
+    /// The parameters are not checked.
+    None,
+}
+
+/// Disallows variables, functions, classes, parameters and imports that are never used.
+///
+/// A binding is used if it is read, so a variable that is only assigned is reported. Bindings
+/// whose name starts with the ignore prefix, the names of function and class expressions, and
+/// the exported bindings of modules are never reported.
+///
+/// When possible, the problem comes with a fix that adds the ignore prefix to the name of the
+/// binding. No fix is offered for imports and bindings declared or assigned with the shorthand
+/// syntax of object patterns, since renaming them would change the property they refer to, nor
+/// in code that uses `with` statements or a direct `eval`.
+///
+/// # Options
+///
+/// - `vars`: `local` (default) to skip the global variables of scripts, or `all`.
+/// - `args`: `after-used` (default) to only check the parameters after the last used one,
+///   `all` to check every parameter, or `none` to skip parameters.
+/// - `ignore-prefix`: the prefix of the names of the bindings that are not checked, `_` by
+///   default. An empty prefix checks all the names and disables the fix.
+#[derive(Debug, Clone)]
+pub struct NoUnusedVars {
+    vars: VarsOption,
+    args: ArgsOption,
+    ignore_prefix: Box<str>,
+}
+
+impl Default for NoUnusedVars {
+    fn default() -> Self {
+        Self {
+            vars: VarsOption::default(),
+            args: ArgsOption::default(),
+            ignore_prefix: "_".into(),
+        }
+    }
+}
+
+impl NoUnusedVars {
+    /// Sets which variables are checked.
+    #[must_use]
+    pub const fn with_vars(mut self, vars: VarsOption) -> Self {
+        self.vars = vars;
+        self
+    }
+
+    /// Sets which parameters are checked.
+    #[must_use]
+    pub const fn with_args(mut self, args: ArgsOption) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Sets the prefix of the names of the bindings that are not checked.
+    #[must_use]
+    pub fn with_ignore_prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<Box<str>>,
+    {
+        self.ignore_prefix = prefix.into();
+        self
+    }
+
+    fn invalid_value(&self, option: &str, value: &str, expected: &'static str) -> ConfigError {
+        ConfigError::InvalidValue {
+            rule: self.name(),
+            option: option.into(),
+            value: value.into(),
+            expected,
+        }
+    }
+}
+
+impl Rule for NoUnusedVars {
+    fn name(&self) -> &'static str {
+        "no-unused-vars"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow bindings that are never used"
+    }
+
+    fn configure(&mut self, option: &str, value: &str) -> Result<(), ConfigError> {
+        match option {
+            "vars" => {
+                self.vars = match value {
+                    "all" => VarsOption::All,
+                    "local" => VarsOption::Local,
+                    _ => return Err(self.invalid_value(option, value, "`all` or `local`")),
+                };
+            }
+            "args" => {
+                self.args = match value {
+                    "after-used" => ArgsOption::AfterUsed,
+                    "all" => ArgsOption::All,
+                    "none" => ArgsOption::None,
+                    _ => {
+                        return Err(self.invalid_value(
+                            option,
+                            value,
+                            "`after-used`, `all` or `none`",
+                        ));
+                    }
+                };
+            }
+            "ignore-prefix" => self.ignore_prefix = value.into(),
+            _ => {
+                return Err(ConfigError::UnknownOption {
+                    rule: self.name(),
+                    option: option.into(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&self, context: &mut LintContext<'_>) {
+        let semantic = context.semantic();
+        let interner = context.interner();
+        let is_used = |id: BindingId| {
+            semantic
+                .references_to(id)
+                .any(|reference| reference.kind() != ReferenceKind::Write)
+        };
+
+        let mut collector = Collector::default();
+        let _ = context.program().visit(&mut collector);
+
+        let mut ignored = collector.expression_names;
+        if let Program::Module(module) = context.program() {
+            let exported = module.items().exported_bindings();
+            ignored.extend(
+                lexically_declared_identifiers(module.items())
+                    .into_iter()
+                    .map(|(identifier, _)| identifier)
+                    .chain(var_declared_identifiers(module.items()))
+                    .filter(|identifier| exported.contains(&identifier.sym()))
+                    .map(|identifier| identifier.span()),
+            );
+        }
+        for parameters in collector.parameters {
+            // The parameters before the first checked one are ignored.
+            let skipped = match self.args {
+                ArgsOption::All => 0,
+                ArgsOption::None => parameters.len(),
+                ArgsOption::AfterUsed => parameters
+                    .iter()
+                    .rposition(|span| semantic.binding_at(*span).is_some_and(is_used))
+                    .map_or(0, |last| last + 1),
+            };
+            ignored.extend(&parameters[..skipped]);
+        }
+        let dynamic = semantic.references().iter().any(Reference::is_dynamic);
+
+        for binding in semantic.bindings() {
+            let Some(first) = binding.declarations().first() else {
+                continue;
+            };
+            if (binding.is_global() && self.vars == VarsOption::Local)
+                || binding
+                    .declarations()
+                    .iter()
+                    .any(|declaration| ignored.contains(&declaration.span()))
+            {
+                continue;
+            }
+            let name = interner.resolve_expect(binding.name()).to_string();
+            if !self.ignore_prefix.is_empty() && name.starts_with(&*self.ignore_prefix) {
+                continue;
+            }
+            let Some(id) = semantic.binding_at(first.span()) else {
+                continue;
+            };
+            if is_used(id) {
+                continue;
+            }
+
+            let assigned = semantic.references_to(id).next().is_some();
+            let message = match first.kind() {
+                BindingKind::Import => format!("`{name}` is imported but never used"),
+                _ if assigned => format!("`{name}` is assigned a value but never used"),
+                _ => format!("`{name}` is declared but never used"),
+            };
+            let mut diagnostic = LintDiagnostic::new(first.span(), message);
+
+            let renamed = format!("{}{name}", self.ignore_prefix);
+            let spans: Vec<_> = binding
+                .declarations()
+                .iter()
+                .map(Spanned::span)
+                .chain(semantic.references_to(id).map(Spanned::span))
+                .collect();
+            if !self.ignore_prefix.is_empty()
+                && !dynamic
+                && first.kind() != BindingKind::Import
+                && !spans.iter().any(|span| collector.shorthands.contains(span))
+                && interner.get(renamed.as_str()).is_none()
+            {
+                diagnostic = diagnostic.with_fix(Fix::new(
+                    format!("rename to `{renamed}`"),
+                    spans
+                        .into_iter()
+                        .map(|span| Edit::new(span, renamed.as_str()))
+                        .collect(),
+                ));
+            }
+            context.report(diagnostic);
+        }
+    }
+}
+
+/// Collects the parts of the AST that decide if a binding is checked and can be renamed.
+#[derive(Default)]
+struct Collector {
+    /// The spans of the names of function and class expressions.
+    expression_names: FxHashSet<Span>,
+
+    /// The spans of the bound identifiers of each formal parameter list, in order.
+    parameters: Vec<Vec<Span>>,
+
+    /// The spans of the identifiers of object patterns that are also the property name.
+    shorthands: FxHashSet<Span>,
+}
+
+impl Collector {
+    fn expression_name(&mut self, name: Option<Identifier>, has_binding: bool) {
+        if let Some(name) = name.filter(|_| has_binding) {
+            self.expression_names.insert(name.span());
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Collector {
+    type BreakTy = Infallible;
+
+    fn visit_function_expression(
+        &mut self,
+        node: &'ast FunctionExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.expression_name(node.name(), node.has_binding_identifier());
+        node.visit_with(self)
+    }
+
+    fn visit_generator_expression(
+        &mut self,
+        node: &'ast GeneratorExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.expression_name(node.name(), node.has_binding_identifier());
+        node.visit_with(self)
+    }
+
+    fn visit_async_function_expression(
+        &mut self,
+        node: &'ast AsyncFunctionExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.expression_name(node.name(), node.has_binding_identifier());
+        node.visit_with(self)
+    }
+
+    fn visit_async_generator_expression(
+        &mut self,
+        node: &'ast AsyncGeneratorExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.expression_name(node.name(), node.has_binding_identifier());
+        node.visit_with(self)
+    }
+
+    fn visit_class_expression(
+        &mut self,
+        node: &'ast ClassExpression,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.expression_name(node.name(), node.name_scope().is_some());
+        node.visit_with(self)
+    }
+
+    fn visit_formal_parameter_list(
+        &mut self,
+        node: &'ast FormalParameterList,
+    ) -> ControlFlow<Self::BreakTy> {
+        self.parameters.push(
+            node.as_ref()
+                .iter()
+                .flat_map(|parameter| bound_identifiers(parameter.variable()))
+                .map(|identifier| identifier.span())
+                .collect(),
+        );
+        node.visit_with(self)
+    }
+
+    fn visit_object_pattern_element(
+        &mut self,
+        node: &'ast ObjectPatternElement,
+    ) -> ControlFlow<Self::BreakTy> {
+        if let ObjectPatternElement::SingleName {
+            name: PropertyName::Literal(name),
+            ident,
+            ..
+        } = node
+            && name.span() == ident.span()
+        {
+            self.shorthands.insert(ident.span());
+        }
+        node.visit_with(self)
+    }
+}
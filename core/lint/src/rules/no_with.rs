@@ -0,0 +1,52 @@
+use crate::{Level, LintContext, LintDiagnostic, Rule};
+use boa_ast::{
+    Span, Spanned,
+    statement::With,
+    visitor::{VisitWith, Visitor},
+};
+use std::{convert::Infallible, ops::ControlFlow};
+
+/// Disallows `with` statements.
+///
+/// The bindings referenced inside of a `with` statement can resolve to the properties of its
+/// object at runtime, which makes the code hard to follow and to optimize. `with` statements
+/// are already a syntax error in strict mode code.
+///
+/// The problem is reported at the object of the statement, since the AST doesn't keep the
+/// position of the `with` keyword.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoWith;
+
+impl Rule for NoWith {
+    fn name(&self) -> &'static str {
+        "no-with"
+    }
+
+    fn description(&self) -> &'static str {
+        "disallow `with` statements"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Error
+    }
+
+    fn check(&self, context: &mut LintContext<'_>) {
+        let mut visitor = WithVisitor(Vec::new());
+        let _ = context.program().visit(&mut visitor);
+        for span in visitor.0 {
+            context.report(LintDiagnostic::new(span, "unexpected `with` statement"));
+        }
+    }
+}
+
+/// Collects the spans of the objects of all the `with` statements.
+struct WithVisitor(Vec<Span>);
+
+impl<'ast> Visitor<'ast> for WithVisitor {
+    type BreakTy = Infallible;
+
+    fn visit_with(&mut self, node: &'ast With) -> ControlFlow<Self::BreakTy> {
+        self.0.push(node.expression().span());
+        node.visit_with(self)
+    }
+}
@@ -0,0 +1,310 @@
+use super::*;
+use crate::{Fix, LintDiagnostic, Linter, apply_fixes};
+use boa_ast::{Span, Spanned, scope::Scope};
+use boa_interner::Interner;
+use boa_parser::{Parser, Source};
+use indoc::indoc;
+
+/// Lints a script or module with a single rule.
+fn lint<R: Rule + 'static>(rule: R, code: &str, module: bool) -> Vec<LintDiagnostic> {
+    let mut linter = Linter::new();
+    linter.add_rule(Box::new(rule));
+    let interner = &mut Interner::default();
+    let scope = Scope::new_global();
+    let mut parser = Parser::new(Source::from_bytes(code));
+    if module {
+        let module = parser.parse_module(&scope, interner).unwrap();
+        linter.lint_module(&module, interner)
+    } else {
+        let script = parser.parse_script(&scope, interner).unwrap();
+        linter.lint_script(&script, &scope, interner)
+    }
+}
+
+/// Gets the messages and spans of the problems found by a rule in a script.
+fn problems<R: Rule + 'static>(rule: R, code: &str) -> Vec<(String, Span)> {
+    lint(rule, code, false)
+        .iter()
+        .map(|diagnostic| (diagnostic.message().to_string(), diagnostic.span()))
+        .collect()
+}
+
+/// Gets the messages of the problems found by a rule in a script.
+fn messages<R: Rule + 'static>(rule: R, code: &str) -> Vec<String> {
+    problems(rule, code)
+        .into_iter()
+        .map(|(message, _)| message)
+        .collect()
+}
+
+#[test]
+fn no_eval() {
+    let code = indoc! {"
+        eval('a');
+        (0, eval)('b');
+        const run = eval;
+        function local(eval) { return eval('c'); }
+        globalThis.eval('d');
+    "};
+    assert_eq!(
+        problems(NoEval, code),
+        [
+            ("`eval` can be harmful".into(), Span::new((1, 1), (1, 5))),
+            ("`eval` can be harmful".into(), Span::new((2, 5), (2, 9))),
+            ("`eval` can be harmful".into(), Span::new((3, 13), (3, 17))),
+        ]
+    );
+}
+
+#[test]
+fn no_with() {
+    let code = "with (Math) { with (obj.inner) { x = PI; } }";
+    assert_eq!(
+        problems(NoWith, code),
+        [
+            (
+                "unexpected `with` statement".into(),
+                Span::new((1, 7), (1, 11))
+            ),
+            (
+                "unexpected `with` statement".into(),
+                Span::new((1, 21), (1, 30))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn no_implicit_globals() {
+    let code = indoc! {"
+        var declared;
+        declared = 1;
+        counter = 0;
+        counter++;
+        [first, ...rest] = list;
+        for (key in object) {}
+        function f(param) { param = 1; let local; local = 2; }
+        host = typeof undeclared;
+        with (obj) { inside = 1; }
+    "};
+    assert_eq!(
+        messages(NoImplicitGlobals::default(), code),
+        [
+            "assignment to undeclared variable `counter`",
+            "assignment to undeclared variable `counter`",
+            "assignment to undeclared variable `first`",
+            "assignment to undeclared variable `rest`",
+            "assignment to undeclared variable `key`",
+            "assignment to undeclared variable `host`",
+        ]
+    );
+
+    let mut rule = NoImplicitGlobals::default();
+    rule.configure("globals", "host, counter").unwrap();
+    assert_eq!(
+        messages(rule, code),
+        [
+            "assignment to undeclared variable `first`",
+            "assignment to undeclared variable `rest`",
+            "assignment to undeclared variable `key`",
+        ]
+    );
+
+    assert!(
+        messages(
+            NoImplicitGlobals::default().with_global("result"),
+            "result = 1;"
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn no_unused_vars() {
+    let code = indoc! {"
+        var global = 1;
+        function main(used, unused) {
+            let a = 1;
+            let b;
+            b = 2;
+            const { c, d: e } = used;
+            const f = function named() {};
+            class C {}
+            try { return c; } catch (error) {}
+            function helper() {}
+            let _ignored;
+        }
+    "};
+    assert_eq!(
+        messages(NoUnusedVars::default(), code),
+        [
+            "`unused` is declared but never used",
+            "`a` is declared but never used",
+            "`b` is assigned a value but never used",
+            "`e` is declared but never used",
+            "`f` is declared but never used",
+            "`C` is declared but never used",
+            "`error` is declared but never used",
+            "`helper` is declared but never used",
+        ]
+    );
+
+    let rule = NoUnusedVars::default()
+        .with_vars(VarsOption::All)
+        .with_ignore_prefix("");
+    assert_eq!(
+        messages(rule, code),
+        [
+            "`global` is declared but never used",
+            "`main` is declared but never used",
+            "`unused` is declared but never used",
+            "`a` is declared but never used",
+            "`b` is assigned a value but never used",
+            "`e` is declared but never used",
+            "`f` is declared but never used",
+            "`C` is declared but never used",
+            "`error` is declared but never used",
+            "`helper` is declared but never used",
+            "`_ignored` is declared but never used",
+        ]
+    );
+}
+
+#[test]
+fn no_unused_vars_args() {
+    let code = "function f(a, b, c) { return b; }\nf();";
+    assert_eq!(
+        messages(NoUnusedVars::default(), code),
+        ["`c` is declared but never used"]
+    );
+    assert_eq!(
+        messages(NoUnusedVars::default().with_args(ArgsOption::All), code),
+        [
+            "`a` is declared but never used",
+            "`c` is declared but never used"
+        ]
+    );
+    assert!(messages(NoUnusedVars::default().with_args(ArgsOption::None), code).is_empty());
+
+    let mut rule = NoUnusedVars::default();
+    assert_eq!(
+        rule.configure("args", "some").unwrap_err().to_string(),
+        "invalid value `some` for option `args` of rule `no-unused-vars`, expected \
+         `after-used`, `all` or `none`"
+    );
+}
+
+#[test]
+fn no_unused_vars_module() {
+    let code = indoc! {"
+        import { used, unused } from 'mod';
+        import * as namespace from 'other';
+        export const exported = used;
+        const local = 1, renamed = 2;
+        export { renamed as name };
+        export default function () {}
+        function internal() {}
+    "};
+    let diagnostics = lint(NoUnusedVars::default(), code, true);
+    let messages: Vec<_> = diagnostics.iter().map(LintDiagnostic::message).collect();
+    assert_eq!(
+        messages,
+        [
+            "`unused` is imported but never used",
+            "`namespace` is imported but never used",
+            "`local` is declared but never used",
+            "`internal` is declared but never used",
+        ]
+    );
+    assert!(diagnostics[0].fix().is_none());
+}
+
+#[test]
+fn no_unused_vars_fix() {
+    let code = indoc! {"
+        function f(a, b) {
+            let { c } = a;
+            let d = 1, e;
+            e = 2;
+            return function () { e = 3; };
+        }
+        f();
+    "};
+    let diagnostics = lint(NoUnusedVars::default(), code, false);
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.fix().map(Fix::message))
+            .collect::<Vec<_>>(),
+        [
+            Some("rename to `_b`"),
+            None,
+            Some("rename to `_d`"),
+            Some("rename to `_e`"),
+        ]
+    );
+    assert_eq!(
+        apply_fixes(code, &diagnostics),
+        (
+            indoc! {"
+                function f(a, _b) {
+                    let { c } = a;
+                    let _d = 1, _e;
+                    _e = 2;
+                    return function () { _e = 3; };
+                }
+                f();
+            "}
+            .to_string(),
+            3
+        )
+    );
+
+    // Code with a direct `eval` could use any binding by name.
+    let diagnostics = lint(
+        NoUnusedVars::default(),
+        "function f(a) { eval(''); }",
+        false,
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].fix().is_none());
+}
+
+#[test]
+fn no_unreachable() {
+    let code = indoc! {"
+        function f(x) {
+            if (x) {
+                return 1;
+                x++;
+            } else {
+                throw new Error();
+            }
+            console.log(x);
+            var hoisted;
+            function declared() {}
+            var assigned = 2;
+        }
+        for (const item of list) {
+            if (item) continue;
+            try { break; } finally { log(item); }
+            log(item);
+        }
+        switch (value) {
+            case 1:
+                break;
+                log('unreachable');
+            case 2:
+                log('reachable');
+        }
+    "};
+    assert_eq!(
+        problems(NoUnreachable, code),
+        [
+            ("unreachable code".into(), Span::new((4, 9), (4, 12))),
+            ("unreachable code".into(), Span::new((8, 5), (11, 21))),
+            ("unreachable code".into(), Span::new((16, 5), (16, 14))),
+            ("unreachable code".into(), Span::new((21, 9), (21, 27))),
+        ]
+    );
+}
@@ -0,0 +1,143 @@
+use super::*;
+use boa_ast::{Span, Spanned};
+use boa_parser::{
+    Parser, Source,
+    diagnostic::{Diagnostic, Label},
+};
+use indoc::indoc;
+
+fn lint_script(linter: &Linter, code: &str) -> Vec<LintDiagnostic> {
+    let interner = &mut Interner::default();
+    let scope = Scope::new_global();
+    let script = Parser::new(Source::from_bytes(code))
+        .parse_script(&scope, interner)
+        .unwrap();
+    linter.lint_script(&script, &scope, interner)
+}
+
+#[test]
+fn default_rules() {
+    let linter = Linter::default();
+    assert_eq!(
+        linter
+            .rules()
+            .map(|(rule, level)| (rule.name(), level))
+            .collect::<Vec<_>>(),
+        [
+            ("no-eval", Level::Error),
+            ("no-with", Level::Error),
+            ("no-implicit-globals", Level::Error),
+            ("no-unused-vars", Level::Warn),
+            ("no-unreachable", Level::Warn),
+        ]
+    );
+    assert!(Linter::new().rules().next().is_none());
+}
+
+#[test]
+fn configuration() {
+    let code = "function f(a) { with (a) { b = eval('c'); } }";
+    let mut linter = Linter::default();
+    assert_eq!(
+        lint_script(&linter, code)
+            .iter()
+            .map(|diagnostic| (diagnostic.rule(), diagnostic.level()))
+            .collect::<Vec<_>>(),
+        [("no-with", Level::Error), ("no-eval", Level::Error)]
+    );
+
+    linter.set_level("no-with", Level::Off).unwrap();
+    linter
+        .set_level("no-eval", "warn".parse().unwrap())
+        .unwrap();
+    assert_eq!(
+        lint_script(&linter, code)
+            .iter()
+            .map(|diagnostic| (diagnostic.rule(), diagnostic.level()))
+            .collect::<Vec<_>>(),
+        [("no-eval", Level::Warn)]
+    );
+
+    assert_eq!(
+        linter.set_level("no-console", Level::Warn),
+        Err(ConfigError::UnknownRule("no-console".into()))
+    );
+    assert_eq!(
+        linter.configure("no-eval", "allow", "indirect"),
+        Err(ConfigError::UnknownOption {
+            rule: "no-eval",
+            option: "allow".into()
+        })
+    );
+    assert_eq!(
+        "warning".parse::<Level>().unwrap_err().to_string(),
+        "invalid level `warning`, expected `off`, `warn` or `error`"
+    );
+}
+
+#[test]
+fn code_frame() {
+    let code = "let unused = 1;\neval(code);\n";
+    let mut linter = Linter::default();
+    linter.configure("no-unused-vars", "vars", "all").unwrap();
+    let diagnostics = lint_script(&linter, code);
+    let frames: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| Diagnostic::from(diagnostic).render(code).to_string())
+        .collect();
+    assert_eq!(
+        frames,
+        [
+            indoc! {"
+                warning[no-unused-vars]: `unused` is declared but never used
+                 --> 1:5
+                  |
+                1 | let unused = 1;
+                  |     ^^^^^^
+            "},
+            indoc! {"
+                error[no-eval]: `eval` can be harmful
+                 --> 2:1
+                  |
+                2 | eval(code);
+                  | ^^^^
+            "},
+        ]
+    );
+}
+
+#[test]
+fn fixes() {
+    let code = "let a = 1;\r\nconst é = 'ü', b = 2;\n";
+    let rename = |span: Span, text: &str| {
+        LintDiagnostic::new(span, "").with_fix(Fix::new("", vec![Edit::new(span, text)]))
+    };
+    let diagnostics = [
+        rename(Span::new((1, 5), (1, 6)), "x"),
+        rename(Span::new((2, 7), (2, 8)), "e"),
+        // Overlaps with the previous fix, so it's skipped.
+        rename(Span::new((2, 7), (2, 13)), "skipped"),
+        LintDiagnostic::new(Span::new((2, 16), (2, 17)), "").with_fix(Fix::new(
+            "",
+            vec![
+                Edit::new(Span::new((2, 21), (2, 21)), "0"),
+                Edit::new(Span::new((2, 16), (2, 17)), "c"),
+            ],
+        )),
+        LintDiagnostic::new(Span::new((1, 1), (1, 2)), "without fix")
+            .with_label(Label::new(Span::new((1, 1), (1, 2)), "label")),
+    ];
+    assert_eq!(diagnostics[4].labels()[0].message(), "label");
+    assert_eq!(
+        diagnostics[3].fix().unwrap().edits()[0]
+            .span()
+            .start()
+            .column_number(),
+        21
+    );
+
+    assert_eq!(
+        apply_fixes(code, &diagnostics),
+        ("let x = 1;\r\nconst e = 'ü', c = 20;\n".to_string(), 3)
+    );
+}
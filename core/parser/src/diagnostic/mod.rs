@@ -0,0 +1,512 @@
+//! Rendering of errors as code frames.
+//!
+//! A [`Diagnostic`] is an error message together with the locations of the source code it
+//! refers to. It can be rendered as a code frame, an excerpt of the source code with the
+//! locations underlined:
+//!
+//! ```text
+//! error: lexical name `a` declared multiple times
+//!  --> script.js:2:5
+//!   |
+//! 1 | let a = 1;
+//!   |     - previous declaration here
+//! 2 | let a = 2;
+//!   |     ^
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use crate::{Error, Source, source::ReadChar};
+use boa_ast::{LinearPosition, Span};
+use std::{fmt, io, path::Path};
+
+/// The number of columns a tab is expanded to in a code frame.
+const TAB_WIDTH: usize = 4;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const YELLOW: &str = "\x1b[1;33m";
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    /// A problem that doesn't prevent the code from running, like the ones reported by a linter.
+    Warning,
+
+    /// A problem that prevents the code from running.
+    #[default]
+    Error,
+}
+
+impl Severity {
+    /// Gets the name of the severity, as shown in a code frame.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A secondary location of the source code that is related to a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    span: Span,
+    message: Box<str>,
+}
+
+impl Label {
+    /// Creates a new label for the given span of the source code.
+    pub fn new<M>(span: Span, message: M) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    /// Gets the span of the source code the label points to.
+    #[inline]
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the message of the label.
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// An error message together with the locations of the source code it refers to.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_interner::Interner;
+/// # use boa_parser::{Parser, Source, diagnostic::Diagnostic};
+/// let code = "let a = 1;\nlet a = 2;";
+/// let error = Parser::new(Source::from_bytes(code))
+///     .parse_script(&boa_ast::scope::Scope::new_global(), &mut Interner::default())
+///     .unwrap_err();
+///
+/// let frame = Diagnostic::from(&error).render(code).to_string();
+/// assert_eq!(
+///     frame.lines().collect::<Vec<_>>(),
+///     [
+///         "error: lexical name `a` declared multiple times",
+///         " --> 2:5",
+///         "  |",
+///         "1 | let a = 1;",
+///         "  |     - previous declaration here",
+///         "2 | let a = 2;",
+///         "  |     ^",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<Box<str>>,
+    message: Box<str>,
+    path: Option<Box<str>>,
+    span: Option<Span>,
+    labels: Vec<Label>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic with the given message.
+    pub fn new<M>(message: M) -> Self
+    where
+        M: Into<Box<str>>,
+    {
+        Self {
+            severity: Severity::Error,
+            code: None,
+            message: message.into(),
+            path: None,
+            span: None,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Sets the severity of the diagnostic, which is an error by default.
+    #[must_use]
+    pub const fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets a code that identifies the kind of the diagnostic, like the name of a lint rule.
+    ///
+    /// The code is shown next to the severity, as in `warning[no-eval]`.
+    #[must_use]
+    pub fn with_code<C>(mut self, code: C) -> Self
+    where
+        C: Into<Box<str>>,
+    {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the span of the source code the diagnostic is about.
+    #[must_use]
+    pub const fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Adds a secondary label to the diagnostic.
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    /// Sets the path of the file that contains the source code, shown before the code frame.
+    #[must_use]
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.display().to_string().into());
+        self
+    }
+
+    /// Gets the severity of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Gets the code that identifies the kind of the diagnostic, if it has one.
+    #[inline]
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Gets the message of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the span of the source code the diagnostic is about, if it is known.
+    #[inline]
+    #[must_use]
+    pub const fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Gets the secondary labels of the diagnostic.
+    #[inline]
+    #[must_use]
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Renders the diagnostic as a code frame of `source`, the source code the spans of the
+    /// diagnostic refer to.
+    ///
+    /// The returned [`CodeFrame`] implements [`Display`](fmt::Display), and doesn't use colors
+    /// unless [`CodeFrame::colored`] is called.
+    #[must_use]
+    pub const fn render<'a>(&'a self, source: &'a str) -> CodeFrame<'a> {
+        CodeFrame {
+            diagnostic: self,
+            source,
+            colored: false,
+        }
+    }
+
+    /// Reads `source` and renders the diagnostic as a code frame of it.
+    ///
+    /// If the diagnostic doesn't have a path, the path of the source is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read.
+    pub fn render_source<R: ReadChar>(
+        &self,
+        source: Source<'_, R>,
+        colored: bool,
+    ) -> io::Result<String> {
+        let mut diagnostic = None;
+        if self.path.is_none()
+            && let Some(path) = source.path()
+        {
+            diagnostic = Some(self.clone().with_path(path));
+        }
+        let diagnostic = diagnostic.as_ref().unwrap_or(self);
+
+        let text = source.read_source_text()?;
+        let text = String::from_utf16_lossy(text.get_code_points_from_pos(LinearPosition::new(0)));
+        Ok(diagnostic.render(&text).colored(colored).to_string())
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        let mut diagnostic = Self::new(error.message());
+        if let Some(span) = error.span() {
+            diagnostic = diagnostic.with_span(span);
+        }
+        if let Error::Redeclaration { previous, .. } = error {
+            diagnostic = diagnostic.with_label(Label::new(*previous, "previous declaration here"));
+        }
+        diagnostic
+    }
+}
+
+/// Renders a parser error as a code frame of the source code that caused it.
+///
+/// This is a shorthand for creating a [`Diagnostic`] from the error and calling
+/// [`Diagnostic::render_source`].
+///
+/// # Errors
+///
+/// Returns an error if the source cannot be read.
+pub fn code_frame<R: ReadChar>(
+    source: Source<'_, R>,
+    error: &Error,
+    colored: bool,
+) -> io::Result<String> {
+    Diagnostic::from(error).render_source(source, colored)
+}
+
+/// A [`Diagnostic`] rendered as a code frame of its source code.
+///
+/// Created by [`Diagnostic::render`].
+#[derive(Debug, Clone, Copy)]
+pub struct CodeFrame<'a> {
+    diagnostic: &'a Diagnostic,
+    source: &'a str,
+    colored: bool,
+}
+
+impl CodeFrame<'_> {
+    /// Sets if the code frame is rendered with ANSI colors, for terminals.
+    #[must_use]
+    pub const fn colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// Writes `text` with the given ANSI style, if colors are enabled.
+    fn paint(&self, f: &mut fmt::Formatter<'_>, style: &str, text: &str) -> fmt::Result {
+        if self.colored {
+            write!(f, "{style}{text}{RESET}")
+        } else {
+            f.write_str(text)
+        }
+    }
+
+    /// Writes a line of the source code, with its line number in the gutter.
+    fn write_line(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        lines: &[&str],
+        line_number: u32,
+        width: usize,
+    ) -> fmt::Result {
+        self.paint(f, BLUE, &format!("{line_number:>width$} |"))?;
+        let line = line(lines, line_number);
+        if !line.is_empty() {
+            write!(f, " ")?;
+            for ch in line.chars() {
+                if ch == '\t' {
+                    write!(f, "{:TAB_WIDTH$}", "")?;
+                } else {
+                    write!(f, "{ch}")?;
+                }
+            }
+        }
+        writeln!(f)
+    }
+}
+
+/// A location of the source code to underline in a code frame.
+struct Annotation<'a> {
+    span: Span,
+    message: &'a str,
+    primary: bool,
+}
+
+impl fmt::Display for CodeFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let diagnostic = self.diagnostic;
+        let primary_style = match diagnostic.severity {
+            Severity::Warning => YELLOW,
+            Severity::Error => RED,
+        };
+        match &diagnostic.code {
+            Some(code) => self.paint(
+                f,
+                primary_style,
+                &format!("{}[{code}]", diagnostic.severity),
+            )?,
+            None => self.paint(f, primary_style, diagnostic.severity.as_str())?,
+        }
+        self.paint(f, BOLD, &format!(": {}", diagnostic.message))?;
+        writeln!(f)?;
+
+        let mut annotations: Vec<_> = diagnostic
+            .span
+            .map(|span| Annotation {
+                span,
+                message: "",
+                primary: true,
+            })
+            .into_iter()
+            .chain(diagnostic.labels.iter().map(|label| Annotation {
+                span: label.span,
+                message: &label.message,
+                primary: false,
+            }))
+            .collect();
+
+        let location = diagnostic.span.map(|span| {
+            format!(
+                "{}:{}",
+                span.start().line_number(),
+                span.start().column_number()
+            )
+        });
+        let location = match (&diagnostic.path, location) {
+            (Some(path), Some(location)) => format!("{path}:{location}"),
+            (Some(path), None) => path.to_string(),
+            (None, Some(location)) => location,
+            (None, None) => return Ok(()),
+        };
+
+        let Some(last_line) = annotations
+            .iter()
+            .map(|annotation| annotation.span.start().line_number())
+            .max()
+        else {
+            self.paint(f, BLUE, " --> ")?;
+            return writeln!(f, "{location}");
+        };
+        let width = last_line.to_string().len();
+        let gutter = " ".repeat(width);
+
+        write!(f, "{gutter}")?;
+        self.paint(f, BLUE, "--> ")?;
+        writeln!(f, "{location}")?;
+        write!(f, "{gutter} ")?;
+        self.paint(f, BLUE, "|")?;
+        writeln!(f)?;
+
+        annotations.sort_by_key(|annotation| annotation.span.start());
+        let lines = lines(self.source);
+        let mut annotated_lines: Vec<_> = annotations
+            .iter()
+            .map(|annotation| annotation.span.start().line_number())
+            .collect();
+        annotated_lines.dedup();
+
+        let mut previous = None;
+        for line_number in annotated_lines {
+            match previous.map(|previous| line_number - previous) {
+                // Show a single line between two annotated lines instead of eliding it.
+                Some(2) => self.write_line(f, &lines, line_number - 1, width)?,
+                Some(gap) if gap > 2 => {
+                    self.paint(f, BLUE, "...")?;
+                    writeln!(f)?;
+                }
+                _ => {}
+            }
+            previous = Some(line_number);
+
+            self.write_line(f, &lines, line_number, width)?;
+            let line = line(&lines, line_number);
+            for annotation in annotations
+                .iter()
+                .filter(|annotation| annotation.span.start().line_number() == line_number)
+            {
+                let start = annotation.span.start().column_number() as usize;
+                let end = if annotation.span.end().line_number() == line_number {
+                    annotation.span.end().column_number() as usize
+                } else {
+                    line.chars().count() + 1
+                };
+                let offset = display_width(line, start - 1);
+                let length = display_width(line, end - 1).saturating_sub(offset).max(1);
+
+                write!(f, "{gutter} ")?;
+                self.paint(f, BLUE, "|")?;
+                write!(f, " {}", " ".repeat(offset))?;
+                let (style, mark) = if annotation.primary {
+                    (primary_style, "^")
+                } else {
+                    (BLUE, "-")
+                };
+                self.paint(f, style, &mark.repeat(length))?;
+                if !annotation.message.is_empty() {
+                    write!(f, " ")?;
+                    self.paint(f, style, annotation.message)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the source code in lines, using the same line terminators as the lexer.
+fn lines(source: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut chars = source.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            lines.push(&source[start..index]);
+            start = index + ch.len_utf8();
+            if ch == '\r'
+                && let Some((_, '\n')) = chars.peek()
+            {
+                chars.next();
+                start += 1;
+            }
+        }
+    }
+    lines.push(&source[start..]);
+    lines
+}
+
+/// Gets the line with the given line number, or an empty line if it is out of the source code.
+fn line<'a>(lines: &[&'a str], line_number: u32) -> &'a str {
+    (line_number as usize)
+        .checked_sub(1)
+        .and_then(|index| lines.get(index))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Gets the number of columns taken by the first `chars` characters of `line` in a code frame.
+fn display_width(line: &str, chars: usize) -> usize {
+    let mut width = 0;
+    let mut iter = line.chars();
+    for _ in 0..chars {
+        width += match iter.next() {
+            Some('\t') => TAB_WIDTH,
+            _ => 1,
+        };
+    }
+    width
+}
@@ -0,0 +1,183 @@
+use super::*;
+use crate::Parser;
+use boa_ast::{Position, scope::Scope};
+use boa_interner::Interner;
+use indoc::indoc;
+
+fn parse_error(code: &str) -> Error {
+    Parser::new(Source::from_bytes(code))
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .expect_err("the code should not parse")
+}
+
+#[test]
+fn redeclaration() {
+    let code = indoc! {"
+        let a = 1;
+        let b = 2;
+        let a = 3;
+    "};
+    let error = parse_error(code);
+    let diagnostic = Diagnostic::from(&error);
+    assert_eq!(
+        diagnostic.labels(),
+        [Label::new(
+            Span::new((1, 5), (1, 6)),
+            "previous declaration here"
+        )]
+    );
+
+    assert_eq!(
+        diagnostic.render(code).to_string(),
+        indoc! {"
+            error: lexical name `a` declared multiple times
+             --> 3:5
+              |
+            1 | let a = 1;
+              |     - previous declaration here
+            2 | let b = 2;
+            3 | let a = 3;
+              |     ^
+        "}
+    );
+}
+
+#[test]
+fn unexpected_token() {
+    let code = "let x = 1;\nif (x) {\n  x +;\n}";
+    let error = parse_error(code);
+
+    assert_eq!(
+        Diagnostic::from(&error).render(code).to_string(),
+        indoc! {"
+            error: unexpected token ';', primary expression
+             --> 3:6
+              |
+            3 |   x +;
+              |      ^
+        "}
+    );
+}
+
+#[test]
+fn elided_lines() {
+    let code = "let a;\n\n\n\n\n\n\n\n\n{ let b; let b; }\nlet a;";
+    let diagnostic = Diagnostic::new("oops")
+        .with_span(Span::new((11, 5), (11, 6)))
+        .with_label(Label::new(Span::new((1, 5), (1, 6)), "first"))
+        .with_label(Label::new(Span::new((10, 7), (10, 8)), "second"))
+        .with_label(Label::new(Span::new((10, 14), (10, 15)), "third"));
+
+    assert_eq!(
+        diagnostic.render(code).to_string(),
+        indoc! {"
+            error: oops
+              --> 11:5
+               |
+             1 | let a;
+               |     - first
+            ...
+            10 | { let b; let b; }
+               |       - second
+               |              - third
+            11 | let a;
+               |     ^
+        "}
+    );
+}
+
+#[test]
+fn multiline_spans_and_tabs() {
+    let code = "\tfoo(\n\t\tbar\r\n)";
+    let diagnostic = Diagnostic::new("oops").with_span(Span::new((1, 2), (3, 2)));
+
+    assert_eq!(
+        diagnostic.render(code).to_string(),
+        indoc! {"
+            error: oops
+             --> 1:2
+              |
+            1 |     foo(
+              |     ^^^^
+        "}
+    );
+
+    let diagnostic = Diagnostic::new("oops").with_span(Span::new((2, 3), (2, 6)));
+    assert!(
+        diagnostic
+            .render(code)
+            .to_string()
+            .ends_with("2 |         bar\n  |         ^^^\n")
+    );
+}
+
+#[test]
+fn without_span() {
+    let diagnostic = Diagnostic::new("abrupt end");
+    assert_eq!(diagnostic.render("").to_string(), "error: abrupt end\n");
+    assert_eq!(
+        diagnostic
+            .with_path(Path::new("script.js"))
+            .render("")
+            .to_string(),
+        "error: abrupt end\n --> script.js\n"
+    );
+}
+
+#[test]
+fn colored() {
+    let diagnostic = Diagnostic::new("oops").with_span(Position::new(1, 1).into());
+    assert_eq!(
+        diagnostic.render("x").colored(true).to_string(),
+        "\x1b[1;31merror\x1b[0m\x1b[1m: oops\x1b[0m\n \
+         \x1b[1;34m--> \x1b[0m1:1\n  \
+         \x1b[1;34m|\x1b[0m\n\
+         \x1b[1;34m1 |\x1b[0m x\n  \
+         \x1b[1;34m|\x1b[0m \x1b[1;31m^\x1b[0m\n"
+    );
+}
+
+#[test]
+fn warning_with_code() {
+    let diagnostic = Diagnostic::new("`eval` can be harmful")
+        .with_severity(Severity::Warning)
+        .with_code("no-eval")
+        .with_span(Span::new((1, 1), (1, 5)));
+    assert_eq!(
+        diagnostic.render("eval(x);").to_string(),
+        indoc! {"
+            warning[no-eval]: `eval` can be harmful
+             --> 1:1
+              |
+            1 | eval(x);
+              | ^^^^
+        "}
+    );
+    assert_eq!(
+        diagnostic.render("eval(x);").colored(true).to_string(),
+        "\x1b[1;33mwarning[no-eval]\x1b[0m\x1b[1m: `eval` can be harmful\x1b[0m\n \
+         \x1b[1;34m--> \x1b[0m1:1\n  \
+         \x1b[1;34m|\x1b[0m\n\
+         \x1b[1;34m1 |\x1b[0m eval(x);\n  \
+         \x1b[1;34m|\x1b[0m \x1b[1;33m^^^^\x1b[0m\n"
+    );
+}
+
+#[test]
+fn source_and_error() {
+    let code = "var a;\ntry {} catch (e) { let e; }";
+    let error = parse_error(code);
+    let source = Source::from_bytes(code).with_path(Path::new("script.js"));
+
+    assert_eq!(
+        code_frame(source, &error, false).unwrap(),
+        indoc! {"
+            error: catch parameter identifier declared in catch body
+             --> script.js:2:24
+              |
+            2 | try {} catch (e) { let e; }
+              |               - previous declaration here
+              |                        ^
+        "}
+    );
+}
@@ -103,6 +103,18 @@ pub enum Error {
         err: &'static str,
     },
 
+    /// When a name is declared more than once where it is not allowed.
+    Redeclaration {
+        /// The error message.
+        message: Box<str>,
+
+        /// Position of the source code where the name was redeclared.
+        span: Span,
+
+        /// Position of the source code where the name was previously declared.
+        previous: Span,
+    },
+
     /// Catch all General Error
     General {
         /// The error message.
@@ -161,6 +173,24 @@ impl Error {
         }
     }
 
+    /// Creates a `Redeclaration` parsing error from the spans of two declarations of the same
+    /// name, in any order.
+    pub(crate) fn redeclaration<S>(message: S, first: Span, second: Span) -> Self
+    where
+        S: Into<Box<str>>,
+    {
+        let (previous, span) = if second.start() < first.start() {
+            (second, first)
+        } else {
+            (first, second)
+        };
+        Self::Redeclaration {
+            message: message.into(),
+            span,
+            previous,
+        }
+    }
+
     /// Creates a "general" parsing error with the specific error message for a misplaced function declaration.
     pub(crate) fn misplaced_function_declaration(position: Position, strict: bool) -> Self {
         Self::General {
@@ -186,16 +216,85 @@ impl Error {
     pub(crate) const fn lex(e: LexError) -> Self {
         Self::Lex { err: e }
     }
+
+    /// Gets the location of the source code where the error occurred, if it is known.
+    #[must_use]
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Expected { span, .. }
+            | Self::Unexpected { span, .. }
+            | Self::Redeclaration { span, .. } => Some(*span),
+            Self::General { position, .. }
+            | Self::Lex {
+                err: LexError::Syntax(_, position),
+            } => Some((*position).into()),
+            Self::AbruptEnd | Self::ScopeAnalysis { .. } | Self::Lex { .. } => None,
+        }
+    }
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// The result of a parse that recovers from syntax errors.
+///
+/// It contains the parsed tree, where the code that could not be parsed is replaced by
+/// [`Statement::Error`][boa_ast::Statement::Error] nodes, and all the errors that were found in
+/// the source, together with their spans.
+#[derive(Debug)]
+pub struct Recovered<T> {
+    ast: T,
+    errors: Vec<(Error, Span)>,
+}
+
+impl<T> Recovered<T> {
+    /// Creates a new `Recovered` parse result.
+    pub(crate) const fn new(ast: T, errors: Vec<(Error, Span)>) -> Self {
+        Self { ast, errors }
+    }
+
+    /// Gets the parsed tree.
+    #[must_use]
+    pub const fn ast(&self) -> &T {
+        &self.ast
+    }
+
+    /// Gets the errors that were found in the source, in the order they were found.
+    #[must_use]
+    pub fn errors(&self) -> &[(Error, Span)] {
+        &self.errors
+    }
+
+    /// Returns `true` if the source doesn't contain any errors.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Splits the result into the parsed tree and the errors.
+    #[must_use]
+    pub fn into_parts(self) -> (T, Vec<(Error, Span)>) {
+        (self.ast, self.errors)
+    }
+}
+
+impl Error {
+    /// Gets the message of the error, without the location of the source code where it
+    /// occurred.
+    #[must_use]
+    pub fn message(&self) -> String {
+        struct Message<'a>(&'a Error);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_message(f)
+            }
+        }
+
+        Message(self).to_string()
+    }
+
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Expected {
-                expected,
-                found,
-                span,
-                ..
+                expected, found, ..
             } => {
                 write!(f, "expected ")?;
                 match &**expected {
@@ -214,41 +313,38 @@ impl fmt::Display for Error {
                         }
                     }
                 }
+                write!(f, ", got '{found}'")?;
                 if let Some(context) = self.context() {
-                    write!(
-                        f,
-                        ", got '{found}' in {context} at line {}, col {}",
-                        span.start().line_number(),
-                        span.start().column_number()
-                    )
-                } else {
-                    write!(
-                        f,
-                        ", got '{found}' at line {}, col {}",
-                        span.start().line_number(),
-                        span.start().column_number()
-                    )
+                    write!(f, " in {context}")?;
                 }
+                Ok(())
             }
-            Self::Unexpected {
-                found,
-                span,
-                message,
-            } => write!(
+            Self::Unexpected { found, message, .. } => {
+                write!(f, "unexpected token '{found}', {message}")
+            }
+            Self::AbruptEnd => f.write_str("abrupt end"),
+            Self::Redeclaration { message, .. }
+            | Self::General { message, .. }
+            | Self::Lex {
+                err: LexError::Syntax(message, _),
+            } => f.write_str(message),
+            Self::Lex { err } => fmt::Display::fmt(err, f),
+            Self::ScopeAnalysis { err } => write!(f, "invalid scope analysis: {err}"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_message(f)?;
+        match self.span() {
+            Some(span) => write!(
                 f,
-                "unexpected token '{found}', {message} at line {}, col {}",
+                " at line {}, col {}",
                 span.start().line_number(),
                 span.start().column_number()
             ),
-            Self::AbruptEnd => f.write_str("abrupt end"),
-            Self::General { message, position } => write!(
-                f,
-                "{message} at line {}, col {}",
-                position.line_number(),
-                position.column_number()
-            ),
-            Self::Lex { err } => err.fmt(f),
-            Self::ScopeAnalysis { err } => write!(f, "invalid scope analysis: {err}"),
+            None => Ok(()),
         }
     }
 }
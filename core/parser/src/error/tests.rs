@@ -164,3 +164,26 @@ fn display() {
 
     assert_eq!(err.to_string(), "testing at line 1, col 1");
 }
+
+#[test]
+fn message() {
+    let err = Error::redeclaration(
+        "lexical name declared multiple times",
+        Span::new(Position::new(2, 5), Position::new(2, 6)),
+        Span::new(Position::new(1, 5), Position::new(1, 6)),
+    );
+    assert_eq!(err.message(), "lexical name declared multiple times");
+    assert_eq!(
+        err.to_string(),
+        "lexical name declared multiple times at line 2, col 5"
+    );
+    assert_eq!(
+        err.span(),
+        Some(Span::new(Position::new(2, 5), Position::new(2, 6)))
+    );
+
+    let err = Error::lex(LexError::syntax("testing", Position::new(1, 1)));
+    assert_eq!(err.message(), "testing");
+
+    assert_eq!(Error::AbruptEnd.message(), "abrupt end");
+}
@@ -1,6 +1,6 @@
 //! Boa's lexing for ECMAScript comments.
 
-use crate::lexer::{Cursor, Error, Token, TokenKind, Tokenizer};
+use crate::lexer::{Cursor, Error, Token, TokenKind, Tokenizer, trivia::TriviaKind};
 use crate::source::ReadChar;
 use boa_ast::PositionGroup;
 use boa_interner::Interner;
@@ -36,6 +36,7 @@ impl<R> Tokenizer<R> for SingleLineComment {
             }
             cursor.next_char().expect("Comment character vanished");
         }
+        cursor.push_trivia(TriviaKind::SingleLineComment, start_pos, cursor.pos_group());
         Ok(Token::new_by_position_group(
             TokenKind::Comment,
             start_pos,
@@ -71,6 +72,7 @@ impl<R> Tokenizer<R> for MultiLineComment {
             let tried_ch = char::try_from(ch);
             match tried_ch {
                 Ok(c) if c == '*' && cursor.next_if(0x2F /* / */)? => {
+                    cursor.push_trivia(TriviaKind::MultiLineComment, start_pos, cursor.pos_group());
                     return Ok(Token::new_by_position_group(
                         if new_line {
                             TokenKind::LineTerminator
@@ -113,14 +115,25 @@ impl<R> Tokenizer<R> for HashbangComment {
     where
         R: ReadChar,
     {
+        // The line terminator is consumed with the comment, so it is recorded separately.
+        let mut end = cursor.pos_group();
+        let mut line_terminator_end = None;
         while let Some(ch) = cursor.next_char()? {
             let tried_ch = char::try_from(ch);
             match tried_ch {
-                Ok(c) if c == '\r' || c == '\n' || c == '\u{2028}' || c == '\u{2029}' => break,
+                Ok(c) if c == '\r' || c == '\n' || c == '\u{2028}' || c == '\u{2029}' => {
+                    line_terminator_end = Some(cursor.pos_group());
+                    break;
+                }
                 _ => {}
             }
+            end = cursor.pos_group();
         }
 
+        cursor.push_trivia(TriviaKind::HashbangComment, start_pos, end);
+        if let Some(line_terminator_end) = line_terminator_end {
+            cursor.push_trivia(TriviaKind::LineTerminator, end, line_terminator_end);
+        }
         Ok(Token::new_by_position_group(
             TokenKind::Comment,
             start_pos,
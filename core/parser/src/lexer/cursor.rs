@@ -1,6 +1,9 @@
 //! Boa's lexer cursor that manages the input byte stream.
 
-use crate::source::{ReadChar, UTF8Input};
+use crate::{
+    lexer::trivia::{Trivia, TriviaKind},
+    source::{ReadChar, UTF8Input},
+};
 use boa_ast::{LinearPosition, Position, PositionGroup, SourceText};
 use std::io::{self, Error, ErrorKind};
 
@@ -13,6 +16,18 @@ pub(super) struct Cursor<R> {
     strict: bool,
     peeked: [Option<u32>; 4],
     source_collector: SourceText,
+    trivia: Option<Vec<Trivia>>,
+    #[cfg(feature = "typescript")]
+    stripped: Option<Stripped>,
+}
+
+/// The JavaScript code that replaces a TypeScript input.
+#[cfg(feature = "typescript")]
+#[derive(Debug)]
+struct Stripped {
+    chars: std::vec::IntoIter<(u32, crate::typescript::Origin)>,
+    /// The origins of the characters that were read but not consumed yet, in order.
+    origins: std::collections::VecDeque<crate::typescript::Origin>,
 }
 
 impl<R> Cursor<R> {
@@ -34,11 +49,41 @@ impl<R> Cursor<R> {
         self.source_collector.cur_linear_position()
     }
 
+    /// Gets the source code read so far.
+    pub(super) const fn source_text(&self) -> &SourceText {
+        &self.source_collector
+    }
+
     pub(super) fn take_source(&mut self) -> SourceText {
         let replace_with = SourceText::with_capacity(0);
         std::mem::replace(&mut self.source_collector, replace_with)
     }
 
+    /// Starts recording the trivia of the source code.
+    pub(super) fn set_collect_trivia(&mut self) {
+        self.trivia.get_or_insert_with(Vec::new);
+    }
+
+    /// Records a piece of trivia, if trivia is being collected.
+    #[inline]
+    pub(super) fn push_trivia(
+        &mut self,
+        kind: TriviaKind,
+        start: PositionGroup,
+        end: PositionGroup,
+    ) {
+        if let Some(trivia) = &mut self.trivia
+            && start.linear_position() != end.linear_position()
+        {
+            trivia.push(Trivia::new(kind, start, end));
+        }
+    }
+
+    /// Takes the trivia recorded so far.
+    pub(super) fn take_trivia(&mut self) -> Vec<Trivia> {
+        self.trivia.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     /// Advances the position to the next column.
     fn next_column(&mut self) {
         let current_line = self.pos.line_number();
@@ -84,7 +129,49 @@ impl<R: ReadChar> Cursor<R> {
             module: false,
             peeked: [None; 4],
             source_collector: SourceText::default(),
+            trivia: None,
+            #[cfg(feature = "typescript")]
+            stripped: None,
+        }
+    }
+
+    /// Reads the next code point from the input.
+    fn read_char(&mut self) -> io::Result<Option<u32>> {
+        #[cfg(feature = "typescript")]
+        if let Some(stripped) = &mut self.stripped {
+            return Ok(stripped.chars.next().map(|(cp, origin)| {
+                stripped.origins.push_back(origin);
+                cp
+            }));
         }
+        self.iter.next_char()
+    }
+
+    /// Replaces the TypeScript input of the cursor with the equivalent JavaScript, lexing it as
+    /// a module or as a script.
+    ///
+    /// The positions and the source text stay those of the TypeScript input: the spaces that
+    /// replace its types collect the erased code, and the inserted code takes no space.
+    ///
+    /// This must be called before reading any character.
+    #[cfg(feature = "typescript")]
+    pub(super) fn strip_typescript(&mut self, module: bool) -> Result<(), crate::Error> {
+        debug_assert!(self.peeked.iter().all(Option::is_none));
+        let mut source = Vec::new();
+        while let Some(cp) = self.read_char().map_err(crate::lexer::Error::from)? {
+            match char::from_u32(cp) {
+                Some(ch) => source.extend_from_slice(ch.encode_utf16(&mut [0; 2])),
+                // Lone surrogates are kept as they are.
+                #[allow(clippy::cast_possible_truncation)]
+                None => source.push(cp as u16),
+            }
+        }
+        let stripped = crate::typescript::strip(&source, module)?;
+        self.stripped = Some(Stripped {
+            chars: stripped.into_iter(),
+            origins: std::collections::VecDeque::new(),
+        });
+        Ok(())
     }
 
     /// Peeks the next n bytes, the maximum number of peeked bytes is 4 (n <= 4).
@@ -93,7 +180,7 @@ impl<R: ReadChar> Cursor<R> {
         let needs_peek = n as usize - peeked;
 
         for i in 0..needs_peek {
-            let next = self.iter.next_char()?;
+            let next = self.read_char()?;
             self.peeked[i + peeked] = next;
         }
 
@@ -106,7 +193,7 @@ impl<R: ReadChar> Cursor<R> {
             return Ok(Some(c));
         }
 
-        let next = self.iter.next_char()?;
+        let next = self.read_char()?;
         self.peeked[0] = next;
         Ok(next)
     }
@@ -182,6 +269,20 @@ impl<R: ReadChar> Cursor<R> {
         }
     }
 
+    /// Continues the source code at `position`, after `prefix`, which was already read.
+    pub(super) fn resume(&mut self, position: Position, prefix: &[u16]) {
+        self.pos = position;
+        for &unit in prefix {
+            self.source_collector.collect_code_point(u32::from(unit));
+        }
+    }
+
+    /// Skips the source code up to the given linear position.
+    pub(super) fn skip_to(&mut self, end: LinearPosition) -> Result<(), Error> {
+        while self.linear_pos() < end && self.next_char()?.is_some() {}
+        Ok(())
+    }
+
     /// Retrieves the next UTF-8 character.
     pub(crate) fn next_char(&mut self) -> Result<Option<u32>, Error> {
         let ch = if let Some(c) = self.peeked[0] {
@@ -189,9 +290,25 @@ impl<R: ReadChar> Cursor<R> {
             self.peeked.rotate_left(1);
             Some(c)
         } else {
-            self.iter.next_char()?
+            self.read_char()?
         };
 
+        #[cfg(feature = "typescript")]
+        if ch.is_some()
+            && let Some(stripped) = &mut self.stripped
+        {
+            use crate::typescript::Origin;
+            match stripped.origins.pop_front() {
+                Some(Origin::Inserted) => return Ok(ch),
+                Some(Origin::Blanked(erased)) => {
+                    self.source_collector.collect_code_point(erased);
+                    self.next_column();
+                    return Ok(ch);
+                }
+                Some(Origin::Source) | None => {}
+            }
+        }
+
         if let Some(ch) = ch {
             self.source_collector.collect_code_point(ch);
         }
@@ -203,6 +320,10 @@ impl<R: ReadChar> Cursor<R> {
                 if self.peek_char()? == Some(0xA) {
                     self.peeked[0] = None;
                     self.peeked.rotate_left(1);
+                    #[cfg(feature = "typescript")]
+                    if let Some(stripped) = &mut self.stripped {
+                        stripped.origins.pop_front();
+                    }
                     self.source_collector.collect_code_point(0xA);
                 }
                 self.next_line();
@@ -44,7 +44,7 @@ impl Identifier {
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-names-and-keywords
-    fn is_identifier_part(ch: u32) -> bool {
+    pub(super) fn is_identifier_part(ch: u32) -> bool {
         const ID_CONTINUE: CodePointSetDataBorrowed<'static> =
             CodePointSetData::new::<IdContinue>();
         matches!(
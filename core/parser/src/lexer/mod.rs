@@ -14,11 +14,14 @@
 
 pub mod error;
 pub mod regex;
+pub mod stream;
 pub mod token;
+pub mod trivia;
 
 mod comment;
 mod cursor;
 mod identifier;
+mod jsx;
 mod number;
 mod operator;
 mod private_identifier;
@@ -33,6 +36,7 @@ use self::{
     comment::{HashbangComment, MultiLineComment, SingleLineComment},
     cursor::Cursor,
     identifier::Identifier,
+    jsx::{JsxIdentifier, JsxString, JsxText},
     number::NumberLiteral,
     operator::Operator,
     private_identifier::PrivateIdentifier,
@@ -42,12 +46,14 @@ use self::{
     template::TemplateLiteral,
 };
 use crate::source::{ReadChar, UTF8Input};
-use boa_ast::{PositionGroup, Punctuator};
+use boa_ast::{LinearPosition, Position, PositionGroup, Punctuator};
 use boa_interner::Interner;
 
 pub use self::{
     error::Error,
+    stream::{Lexeme, LexemeKind, TokenStream},
     token::{Token, TokenKind},
+    trivia::{Trivia, TriviaKind, TriviaTable},
 };
 
 trait Tokenizer<R> {
@@ -71,7 +77,10 @@ pub struct Lexer<R> {
 
 impl<R> Lexer<R> {
     /// Sets the goal symbol for the lexer.
-    pub(crate) fn set_goal(&mut self, elm: InputElement) {
+    ///
+    /// The goal symbol decides if a `/` starts a regular expression or is a division, which the
+    /// lexer can't tell by itself.
+    pub fn set_goal(&mut self, elm: InputElement) {
         self.goal_symbol = elm;
     }
 
@@ -111,6 +120,47 @@ impl<R> Lexer<R> {
         }
     }
 
+    /// Continues lexing a source code whose first `prefix.len()` code units were already parsed,
+    /// ending at `position`.
+    pub(crate) fn resume(&mut self, position: Position, prefix: &[u16])
+    where
+        R: ReadChar,
+    {
+        self.cursor.resume(position, prefix);
+    }
+
+    /// Skips the source code up to the given linear position without lexing it, returning the
+    /// position it ends at.
+    pub(crate) fn skip_to(&mut self, end: LinearPosition) -> Result<Position, Error>
+    where
+        R: ReadChar,
+    {
+        self.cursor.skip_to(end)?;
+        Ok(self.cursor.pos())
+    }
+
+    /// Starts recording the whitespace, line terminators and comments of the source code.
+    ///
+    /// Trivia is not recorded by default. The recorded trivia can be retrieved with
+    /// [`Lexer::take_trivia`].
+    pub fn set_collect_trivia(&mut self) {
+        self.cursor.set_collect_trivia();
+    }
+
+    /// Takes the trivia recorded since the last call, in source order.
+    pub fn take_trivia(&mut self) -> TriviaTable {
+        TriviaTable::new(self.cursor.take_trivia())
+    }
+
+    /// Replaces the TypeScript source of the lexer with the equivalent JavaScript.
+    #[cfg(feature = "typescript")]
+    pub(crate) fn strip_typescript(&mut self, module: bool) -> Result<(), crate::Error>
+    where
+        R: ReadChar,
+    {
+        self.cursor.strip_typescript(module)
+    }
+
     /// Handles lexing of a token starting '/' with the '/' already being consumed.
     /// This could be a divide symbol or the start of a regex.
     ///
@@ -192,17 +242,20 @@ impl<R> Lexer<R> {
             return Ok(());
         }
 
+        let start = self.cursor.pos_group();
         while self.cursor.peek_char()?.is_some_and(is_whitespace) {
             let _next = self.cursor.next_char();
         }
+        self.cursor
+            .push_trivia(TriviaKind::Whitespace, start, self.cursor.pos_group());
 
         // -->
         if self.cursor.peek_n(3)?[..3] == [Some(0x2D), Some(0x2D), Some(0x3E)] {
+            let start = self.cursor.pos_group();
             let _next = self.cursor.next_char();
             let _next = self.cursor.next_char();
             let _next = self.cursor.next_char();
 
-            let start = self.cursor.pos_group();
             SingleLineComment.lex(&mut self.cursor, start, interner)?;
         }
 
@@ -236,9 +289,12 @@ impl<R> Lexer<R> {
 
         // Ignore whitespace
         if is_whitespace(next_ch) {
+            let whitespace_start = start;
             loop {
                 start = self.cursor.pos_group();
                 let Some(next) = self.cursor.next_char()? else {
+                    self.cursor
+                        .push_trivia(TriviaKind::Whitespace, whitespace_start, start);
                     return Ok(None);
                 };
                 if !is_whitespace(next) {
@@ -246,15 +302,22 @@ impl<R> Lexer<R> {
                     break;
                 }
             }
+            self.cursor
+                .push_trivia(TriviaKind::Whitespace, whitespace_start, start);
         }
 
         if let Ok(c) = char::try_from(next_ch) {
             let token = match c {
-                '\r' | '\n' | '\u{2028}' | '\u{2029}' => Ok(Token::new_by_position_group(
-                    TokenKind::LineTerminator,
-                    start,
-                    self.cursor.pos_group(),
-                )),
+                '\r' | '\n' | '\u{2028}' | '\u{2029}' => {
+                    let end = self.cursor.pos_group();
+                    self.cursor
+                        .push_trivia(TriviaKind::LineTerminator, start, end);
+                    Ok(Token::new_by_position_group(
+                        TokenKind::LineTerminator,
+                        start,
+                        end,
+                    ))
+                }
                 '"' | '\'' => StringLiteral::new(c).lex(&mut self.cursor, start, interner),
                 '`' => TemplateLiteral.lex(&mut self.cursor, start, interner),
                 ';' => Ok(Token::new_by_position_group(
@@ -324,7 +387,6 @@ impl<R> Lexer<R> {
                     let _next = self.cursor.next_char();
                     let _next = self.cursor.next_char();
                     let _next = self.cursor.next_char();
-                    let start = self.cursor.pos_group();
                     SingleLineComment.lex(&mut self.cursor, start, interner)
                 }
                 #[allow(clippy::cast_possible_truncation)]
@@ -394,6 +456,93 @@ impl<R> Lexer<R> {
         TemplateLiteral.lex(&mut self.cursor, start, interner)
     }
 
+    /// Lexes the JSX text of the children of an element, up to the next `<` or `{`.
+    pub(crate) fn lex_jsx_text(&mut self, interner: &mut Interner) -> Result<Token, Error>
+    where
+        R: ReadChar,
+    {
+        let start = self.cursor.pos_group();
+        JsxText.lex(&mut self.cursor, start, interner)
+    }
+
+    /// Retrieves the next token inside of a JSX tag, skipping whitespace and comments.
+    ///
+    /// Inside of tags, identifiers can contain dashes and strings can't contain escape
+    /// sequences. Any other character than `<`, `>`, `/`, `{`, `}`, `=`, `:` and `.` is an error.
+    pub(crate) fn lex_jsx_tag(&mut self, interner: &mut Interner) -> Result<Option<Token>, Error>
+    where
+        R: ReadChar,
+    {
+        loop {
+            let start = self.cursor.pos_group();
+            let Some(next_ch) = self.cursor.next_char()? else {
+                return Ok(None);
+            };
+
+            let punctuator = match char::from_u32(next_ch) {
+                Some('\r' | '\n' | '\u{2028}' | '\u{2029}') => {
+                    self.cursor.push_trivia(
+                        TriviaKind::LineTerminator,
+                        start,
+                        self.cursor.pos_group(),
+                    );
+                    continue;
+                }
+                _ if is_whitespace(next_ch) => {
+                    while self.cursor.peek_char()?.is_some_and(is_whitespace) {
+                        self.cursor.next_char()?;
+                    }
+                    self.cursor
+                        .push_trivia(TriviaKind::Whitespace, start, self.cursor.pos_group());
+                    continue;
+                }
+                Some('/') if self.cursor.next_if(0x002F /* / */)? => {
+                    SingleLineComment.lex(&mut self.cursor, start, interner)?;
+                    continue;
+                }
+                Some('/') if self.cursor.next_if(0x002A /* * */)? => {
+                    MultiLineComment.lex(&mut self.cursor, start, interner)?;
+                    continue;
+                }
+                Some(quote @ ('"' | '\'')) => {
+                    return JsxString::new(quote)
+                        .lex(&mut self.cursor, start, interner)
+                        .map(Some);
+                }
+                Some(c) if Identifier::is_identifier_start(next_ch) => {
+                    return JsxIdentifier::new(c)
+                        .lex(&mut self.cursor, start, interner)
+                        .map(Some);
+                }
+                Some('<') => Punctuator::LessThan,
+                Some('>') => Punctuator::GreaterThan,
+                Some('/') => Punctuator::Div,
+                Some('{') => Punctuator::OpenBlock,
+                Some('}') => Punctuator::CloseBlock,
+                Some('=') => Punctuator::Assign,
+                Some(':') => Punctuator::Colon,
+                Some('.') => Punctuator::Dot,
+                Some(c) => {
+                    return Err(Error::syntax(
+                        format!("unexpected '{c}' in JSX tag"),
+                        start.position(),
+                    ));
+                }
+                None => {
+                    return Err(Error::syntax(
+                        format!("unexpected utf-8 char '\\u{next_ch}' in JSX tag"),
+                        start.position(),
+                    ));
+                }
+            };
+            return Ok(Some(Token::new_by_position_group(
+                punctuator.into(),
+                start,
+                self.cursor.pos_group(),
+            )));
+        }
+    }
+
     pub(super) fn take_source(&mut self) -> boa_ast::SourceText {
         self.cursor.take_source()
     }
@@ -409,11 +558,19 @@ impl<'a> From<&'a [u8]> for Lexer<UTF8Input<&'a [u8]>> {
 ///
 /// <https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar>
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum InputElement {
+pub enum InputElement {
+    /// A `/` is a division, like after an expression.
     Div,
+
+    /// A `/` starts a regular expression, like at the start of an expression.
     #[default]
     RegExp,
+
+    /// A `}` continues a template literal after a substitution, and a `/` is a division.
     TemplateTail,
+
+    /// Like [`InputElement::RegExp`], but a hashbang comment is also allowed, at the start of
+    /// the source.
     HashbangOrRegExp,
 }
 
@@ -425,7 +582,7 @@ pub(crate) enum InputElement {
 ///  * ECMAScript standard uses `\{Space_Separator}` + `\u{0009}`, `\u{000B}`, `\u{000C}`, `\u{FEFF}`
 ///
 /// [More information](https://tc39.es/ecma262/#table-32)
-const fn is_whitespace(ch: u32) -> bool {
+pub(crate) const fn is_whitespace(ch: u32) -> bool {
     matches!(
         ch,
         0x0020 | 0x0009 | 0x000B | 0x000C | 0x00A0 | 0xFEFF |
@@ -0,0 +1,305 @@
+//! A stream of the tokens of a source code, for tools that don't need a full parse.
+//!
+//! Unlike the [`Lexer`], which relies on the parser to pick the goal symbol of every token, the
+//! [`TokenStream`] disambiguates regular expressions from divisions and resumes template literals
+//! after their substitutions by itself. It also gives the raw text of every token and,
+//! optionally, the whitespace, line terminators and comments in between.
+
+use super::{InputElement, Lexer, Token, TokenKind, Trivia, TriviaKind};
+use crate::{Source, lexer::Error, source::ReadChar};
+use boa_ast::{Keyword, LinearSpan, Punctuator, Span, Spanned};
+use boa_interner::Interner;
+use std::collections::VecDeque;
+
+/// The kind of a [`Lexeme`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexemeKind {
+    /// A token of the source code.
+    Token(TokenKind),
+
+    /// A piece of whitespace, line terminator or comment.
+    Trivia(TriviaKind),
+}
+
+/// A token or piece of trivia yielded by a [`TokenStream`], with its raw text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexeme {
+    kind: LexemeKind,
+    span: Span,
+    linear_span: LinearSpan,
+    raw: String,
+}
+
+impl Lexeme {
+    /// Gets the kind of the lexeme.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> &LexemeKind {
+        &self.kind
+    }
+
+    /// Gets the kind of the token, if the lexeme is a token.
+    #[inline]
+    #[must_use]
+    pub const fn token_kind(&self) -> Option<&TokenKind> {
+        match &self.kind {
+            LexemeKind::Token(kind) => Some(kind),
+            LexemeKind::Trivia(_) => None,
+        }
+    }
+
+    /// Returns `true` if the lexeme is whitespace, a line terminator or a comment.
+    #[inline]
+    #[must_use]
+    pub const fn is_trivia(&self) -> bool {
+        matches!(self.kind, LexemeKind::Trivia(_))
+    }
+
+    /// Gets the linear span of the lexeme in the source code.
+    #[inline]
+    #[must_use]
+    pub const fn linear_span(&self) -> LinearSpan {
+        self.linear_span
+    }
+
+    /// Gets the text of the lexeme, as written in the source code.
+    #[inline]
+    #[must_use]
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl Spanned for Lexeme {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A stream of the tokens of a source code.
+///
+/// The goal symbol of every token is chosen from the previous token: a `/` is lexed as a
+/// division after something that ends an expression, like an identifier, a literal or a closing
+/// bracket, and as the start of a regular expression otherwise. Since a closing parenthesis or
+/// brace doesn't always end an expression (like in `if (a) /b/.exec(c)`), the goal of the next
+/// token can be forced with [`TokenStream::set_goal`]. For an exact tokenization following the
+/// grammar, use [`Parser::set_collect_tokens`](crate::Parser::set_collect_tokens) instead.
+///
+/// JSX and TypeScript syntax are not supported.
+///
+/// # Examples
+///
+/// ```
+/// # use boa_interner::Interner;
+/// # use boa_parser::{Source, lexer::TokenStream};
+/// let mut stream = TokenStream::new(Source::from_bytes("a / b; /c/g"));
+/// let interner = &mut Interner::default();
+///
+/// let mut raw = Vec::new();
+/// while let Some(lexeme) = stream.next(interner).unwrap() {
+///     raw.push(lexeme.raw().to_owned());
+/// }
+/// assert_eq!(raw, ["a", "/", "b", ";", "/c/g"]);
+/// ```
+#[derive(Debug)]
+pub struct TokenStream<R> {
+    lexer: Lexer<R>,
+    collect_trivia: bool,
+    pending: VecDeque<Lexeme>,
+    previous: Option<TokenKind>,
+    goal: Option<InputElement>,
+    /// For every open brace, if it closes the substitution of a template literal.
+    braces: Vec<bool>,
+    line_start: bool,
+    done: bool,
+}
+
+impl<R> TokenStream<R>
+where
+    R: ReadChar,
+{
+    /// Creates a new token stream for the given source.
+    pub fn new(source: Source<'_, R>) -> Self {
+        Self {
+            lexer: Lexer::new(source.reader),
+            collect_trivia: false,
+            pending: VecDeque::new(),
+            previous: None,
+            goal: None,
+            braces: Vec::new(),
+            line_start: false,
+            done: false,
+        }
+    }
+
+    /// Lexes the source as a module, where HTML-like comments are not allowed.
+    pub fn set_module(&mut self) {
+        self.lexer.set_module(true);
+        self.lexer.set_strict(true);
+    }
+
+    /// Lexes the source as strict mode code, where legacy octal literals are not allowed.
+    pub fn set_strict(&mut self) {
+        self.lexer.set_strict(true);
+    }
+
+    /// Also yields the whitespace, line terminators and comments of the source.
+    ///
+    /// Trivia is not yielded by default.
+    pub fn set_collect_trivia(&mut self) {
+        self.collect_trivia = true;
+        self.lexer.set_collect_trivia();
+    }
+
+    /// Forces the goal symbol of the next token.
+    ///
+    /// With [`InputElement::TemplateTail`], a `}` resumes a template literal after a
+    /// substitution, and a `/` is a division.
+    pub fn set_goal(&mut self, goal: InputElement) {
+        self.goal = Some(goal);
+    }
+
+    /// Retrieves the next token or piece of trivia of the source.
+    ///
+    /// After an error, the stream ends.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on invalid tokens and invalid reads of the bytes being lexed.
+    // We intentionally don't implement Iterator trait as Result<Option> is cleaner to handle.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self, interner: &mut Interner) -> Result<Option<Lexeme>, Error> {
+        loop {
+            if let Some(lexeme) = self.pending.pop_front() {
+                return Ok(Some(lexeme));
+            }
+            if self.done {
+                return Ok(None);
+            }
+
+            let token = self.lex(interner);
+            if self.collect_trivia {
+                for trivia in self.lexer.cursor.take_trivia() {
+                    let lexeme = self.trivia(trivia);
+                    self.pending.push_back(lexeme);
+                }
+            }
+            match token {
+                Ok(Some(token)) => {
+                    let lexeme = self.token(&token);
+                    self.pending.push_back(lexeme);
+                }
+                Ok(None) => self.done = true,
+                Err(error) => {
+                    self.done = true;
+                    self.pending.clear();
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Lexes the next token, skipping line terminators and comments.
+    fn lex(&mut self, interner: &mut Interner) -> Result<Option<Token>, Error> {
+        let hint = self.goal.take();
+        let mut goal = match (hint, &self.previous) {
+            (Some(goal), _) => goal,
+            (None, None) => InputElement::HashbangOrRegExp,
+            (None, Some(previous)) => goal_after(previous),
+        };
+
+        loop {
+            if self.line_start {
+                self.lexer.skip_html_close(interner)?;
+            }
+            self.lexer.set_goal(goal);
+            if goal == InputElement::HashbangOrRegExp {
+                // A hashbang comment is only allowed at the very start of the source.
+                goal = InputElement::RegExp;
+            }
+            let Some(token) = self.lexer.next_no_skip(interner)? else {
+                return Ok(None);
+            };
+
+            let token = match token.kind() {
+                TokenKind::LineTerminator => {
+                    self.line_start = true;
+                    continue;
+                }
+                TokenKind::Comment => continue,
+                TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                    self.braces.push(false);
+                    token
+                }
+                TokenKind::Punctuator(Punctuator::CloseBlock)
+                    if self.braces.pop().unwrap_or_default()
+                        || hint == Some(InputElement::TemplateTail) =>
+                {
+                    self.lexer.lex_template(token.start_group(), interner)?
+                }
+                _ => token,
+            };
+            if let TokenKind::TemplateMiddle(_) = token.kind() {
+                self.braces.push(true);
+            }
+
+            self.line_start = false;
+            self.previous = Some(token.kind().clone());
+            return Ok(Some(token));
+        }
+    }
+
+    fn token(&self, token: &Token) -> Lexeme {
+        Lexeme {
+            span: token.span(),
+            linear_span: token.linear_span(),
+            raw: self.raw(token.linear_span()),
+            kind: LexemeKind::Token(token.kind().clone()),
+        }
+    }
+
+    fn trivia(&self, trivia: Trivia) -> Lexeme {
+        Lexeme {
+            kind: LexemeKind::Trivia(trivia.kind()),
+            span: trivia.span(),
+            linear_span: trivia.linear_span(),
+            raw: self.raw(trivia.linear_span()),
+        }
+    }
+
+    fn raw(&self, span: LinearSpan) -> String {
+        String::from_utf16_lossy(
+            self.lexer
+                .cursor
+                .source_text()
+                .get_code_points_from_span(span),
+        )
+    }
+}
+
+/// Gets the goal symbol of the token after `previous`.
+///
+/// A `/` after something that ends an expression is a division, and the start of a regular
+/// expression everywhere else.
+const fn goal_after(previous: &TokenKind) -> InputElement {
+    match previous {
+        TokenKind::IdentifierName(_)
+        | TokenKind::PrivateIdentifier(_)
+        | TokenKind::BooleanLiteral(_)
+        | TokenKind::NullLiteral(_)
+        | TokenKind::NumericLiteral(_)
+        | TokenKind::StringLiteral(_)
+        | TokenKind::TemplateNoSubstitution(_)
+        | TokenKind::RegularExpressionLiteral(..)
+        | TokenKind::Keyword((Keyword::This | Keyword::Super, _))
+        | TokenKind::Punctuator(
+            Punctuator::CloseParen
+            | Punctuator::CloseBracket
+            | Punctuator::CloseBlock
+            | Punctuator::Inc
+            | Punctuator::Dec,
+        ) => InputElement::Div,
+        _ => InputElement::RegExp,
+    }
+}
@@ -1178,3 +1178,177 @@ mod carriage_return {
         expect_tokens_with_lines(3, "-\r\n\n\r3");
     }
 }
+
+#[test]
+fn trivia_is_not_collected_by_default() {
+    let mut lexer = Lexer::from("/* a */ x // b".as_bytes());
+    let interner = &mut Interner::default();
+
+    while lexer.next(interner).unwrap().is_some() {}
+    assert!(lexer.take_trivia().trivia().is_empty());
+}
+
+#[test]
+fn collect_trivia() {
+    use crate::lexer::{InputElement, TriviaKind};
+
+    let mut lexer = Lexer::from("#!/usr/bin/env boa\n  /* a\n */ x // b\r\n\ty".as_bytes());
+    lexer.set_goal(InputElement::HashbangOrRegExp);
+    lexer.set_collect_trivia();
+    let interner = &mut Interner::default();
+
+    while lexer.next(interner).unwrap().is_some() {}
+    let trivia = lexer
+        .take_trivia()
+        .trivia()
+        .iter()
+        .map(|trivia| (trivia.kind(), trivia.span()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        trivia,
+        [
+            (TriviaKind::HashbangComment, span((1, 1), (1, 19))),
+            (TriviaKind::LineTerminator, span((1, 19), (2, 1))),
+            (TriviaKind::Whitespace, span((2, 1), (2, 3))),
+            (TriviaKind::MultiLineComment, span((2, 3), (3, 4))),
+            (TriviaKind::Whitespace, span((3, 4), (3, 5))),
+            (TriviaKind::Whitespace, span((3, 6), (3, 7))),
+            (TriviaKind::SingleLineComment, span((3, 7), (3, 11))),
+            (TriviaKind::LineTerminator, span((3, 11), (4, 1))),
+            (TriviaKind::Whitespace, span((4, 1), (4, 2))),
+        ]
+    );
+}
+
+/// Gets the raw text of the tokens of a token stream over `source`.
+fn stream_raw(source: &str, trivia: bool) -> Vec<String> {
+    use crate::{Source, lexer::TokenStream};
+
+    let mut stream = TokenStream::new(Source::from_bytes(source));
+    if trivia {
+        stream.set_collect_trivia();
+    }
+    let interner = &mut Interner::default();
+
+    let mut raw = Vec::new();
+    while let Some(lexeme) = stream.next(interner).unwrap() {
+        raw.push(lexeme.raw().to_owned());
+    }
+    raw
+}
+
+#[test]
+fn token_stream_regex_or_division() {
+    assert_eq!(
+        stream_raw("a / b / c; x = /=/g.test(y)[0] /2", false),
+        [
+            "a", "/", "b", "/", "c", ";", "x", "=", "/=/g", ".", "test", "(", "y", ")", "[", "0",
+            "]", "/", "2"
+        ]
+    );
+    assert_eq!(
+        stream_raw("return /a/; this /= 2; typeof /b/", false),
+        [
+            "return", "/a/", ";", "this", "/=", "2", ";", "typeof", "/b/"
+        ]
+    );
+}
+
+#[test]
+fn token_stream_templates() {
+    assert_eq!(
+        stream_raw("`a${ {b: `c${d}e`}.b }f${g / 2}h` / 2", false),
+        [
+            "`a${", "{", "b", ":", "`c${", "d", "}e`", "}", ".", "b", "}f${", "g", "/", "2", "}h`",
+            "/", "2"
+        ]
+    );
+}
+
+#[test]
+fn token_stream_trivia() {
+    use crate::{
+        Source,
+        lexer::{LexemeKind, TokenStream, TriviaKind},
+    };
+
+    let mut stream = TokenStream::new(Source::from_bytes("#!boa\nlet /* a */ x // b\n"));
+    stream.set_collect_trivia();
+    let interner = &mut Interner::default();
+
+    let mut lexemes = Vec::new();
+    while let Some(lexeme) = stream.next(interner).unwrap() {
+        lexemes.push((
+            lexeme.kind().clone(),
+            lexeme.raw().to_owned(),
+            lexeme.span(),
+        ));
+    }
+    let trivia =
+        |kind, raw: &str, start, end| (LexemeKind::Trivia(kind), raw.to_owned(), span(start, end));
+    assert_eq!(
+        lexemes,
+        [
+            trivia(TriviaKind::HashbangComment, "#!boa", (1, 1), (1, 6)),
+            trivia(TriviaKind::LineTerminator, "\n", (1, 6), (2, 1)),
+            (
+                LexemeKind::Token(TokenKind::Keyword((Keyword::Let, false))),
+                "let".to_owned(),
+                span((2, 1), (2, 4))
+            ),
+            trivia(TriviaKind::Whitespace, " ", (2, 4), (2, 5)),
+            trivia(TriviaKind::MultiLineComment, "/* a */", (2, 5), (2, 12)),
+            trivia(TriviaKind::Whitespace, " ", (2, 12), (2, 13)),
+            (
+                LexemeKind::Token(TokenKind::identifier(interner.get("x").unwrap())),
+                "x".to_owned(),
+                span((2, 13), (2, 14))
+            ),
+            trivia(TriviaKind::Whitespace, " ", (2, 14), (2, 15)),
+            trivia(TriviaKind::SingleLineComment, "// b", (2, 15), (2, 19)),
+            trivia(TriviaKind::LineTerminator, "\n", (2, 19), (3, 1)),
+        ]
+    );
+}
+
+#[test]
+fn token_stream_goal_hints() {
+    use crate::{
+        Source,
+        lexer::{InputElement, TokenStream},
+    };
+
+    let interner = &mut Interner::default();
+    let mut stream = TokenStream::new(Source::from_bytes("if (a) /b/.exec(c)"));
+    let mut raw = Vec::new();
+    while let Some(lexeme) = stream.next(interner).unwrap() {
+        if lexeme.raw() == ")" && raw.len() == 3 {
+            stream.set_goal(InputElement::RegExp);
+        }
+        raw.push(lexeme.raw().to_owned());
+    }
+    assert_eq!(
+        raw,
+        ["if", "(", "a", ")", "/b/", ".", "exec", "(", "c", ")"]
+    );
+
+    let mut stream = TokenStream::new(Source::from_bytes("} a`"));
+    stream.set_goal(InputElement::TemplateTail);
+    let lexeme = stream.next(interner).unwrap().unwrap();
+    assert_eq!(lexeme.raw(), "} a`");
+    assert!(matches!(
+        lexeme.token_kind(),
+        Some(TokenKind::TemplateNoSubstitution(_))
+    ));
+}
+
+#[test]
+fn token_stream_ends_after_error() {
+    use crate::{Source, lexer::TokenStream};
+
+    let interner = &mut Interner::default();
+    let mut stream = TokenStream::new(Source::from_bytes("a 'b"));
+    assert_eq!(stream.next(interner).unwrap().unwrap().raw(), "a");
+    assert!(stream.next(interner).is_err());
+    assert!(stream.next(interner).unwrap().is_none());
+}
@@ -172,6 +172,10 @@ pub enum TokenKind {
     /// A regular expression, consisting of body and flags.
     RegularExpressionLiteral(Sym, Sym),
 
+    /// JSX text or a JSX attribute string, consisting of the raw text and its value with the
+    /// character references decoded.
+    JsxText(Sym, Sym),
+
     /// Indicates a [**line terminator (`\n`)**][spec].
     ///
     /// [spec]: https://tc39.es/ecma262/#prod-LineTerminator
@@ -314,6 +318,7 @@ impl TokenKind {
                     interner.resolve_expect(flags),
                 )
             }
+            Self::JsxText(raw, _) => interner.resolve_expect(raw).to_string(),
             Self::LineTerminator => "line terminator".to_owned(),
             Self::Comment => "comment".to_owned(),
         }
@@ -0,0 +1,185 @@
+//! Boa's representation of the trivia of the source code.
+//!
+//! Trivia is the source code that doesn't affect the meaning of a program, like whitespace, line
+//! terminators and comments. The lexer discards it by default, but it can record it with
+//! [`Lexer::set_collect_trivia`](crate::Lexer::set_collect_trivia) for tools like formatters and
+//! documentation extractors.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar
+
+use boa_ast::{LinearSpan, Position, PositionGroup, Span, Spanned};
+
+/// The kind of a piece of [`Trivia`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    /// A run of whitespace characters.
+    Whitespace,
+
+    /// A line terminator, like `\n` or `\r\n`.
+    LineTerminator,
+
+    /// A single line comment, like `// comment`, or an HTML-like comment of Annex B.
+    SingleLineComment,
+
+    /// A multi line comment, like `/* comment */`.
+    MultiLineComment,
+
+    /// A hashbang comment at the start of the source, like `#!/usr/bin/env boa`.
+    HashbangComment,
+}
+
+impl TriviaKind {
+    /// Returns `true` if the trivia is a comment.
+    #[inline]
+    #[must_use]
+    pub const fn is_comment(self) -> bool {
+        matches!(
+            self,
+            Self::SingleLineComment | Self::MultiLineComment | Self::HashbangComment
+        )
+    }
+}
+
+/// A piece of whitespace, line terminator or comment in the source code.
+///
+/// The text of the trivia can be retrieved from the [`SourceText`][boa_ast::SourceText] of the
+/// source with its [`linear_span`](Trivia::linear_span).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trivia {
+    kind: TriviaKind,
+    span: Span,
+    linear_span: LinearSpan,
+}
+
+impl Trivia {
+    /// Creates a new `Trivia` of the given kind, between two positions.
+    #[inline]
+    pub(crate) fn new(kind: TriviaKind, start: PositionGroup, end: PositionGroup) -> Self {
+        Self {
+            kind,
+            span: Span::new(start.position(), end.position()),
+            linear_span: LinearSpan::new(start.linear_position(), end.linear_position()),
+        }
+    }
+
+    /// Gets the kind of the trivia.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    /// Gets the linear span of the trivia in the source code.
+    #[inline]
+    #[must_use]
+    pub const fn linear_span(&self) -> LinearSpan {
+        self.linear_span
+    }
+}
+
+impl Spanned for Trivia {
+    #[inline]
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The trivia of a source, in source order.
+///
+/// Besides listing the trivia, the table finds the comments that belong to a node of the AST,
+/// given the [`Span`] of the node:
+///  - The trailing trivia of a span is the trivia after the end of the span, up to the next token
+///    or the end of the line.
+///  - The leading trivia of a span is the trivia between the previous token and the start of the
+///    span, without the trailing trivia of the previous token.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriviaTable {
+    trivia: Vec<Trivia>,
+}
+
+impl TriviaTable {
+    /// Creates a new `TriviaTable` from trivia in source order.
+    pub(crate) const fn new(trivia: Vec<Trivia>) -> Self {
+        Self { trivia }
+    }
+
+    /// Gets all the trivia of the source.
+    #[inline]
+    #[must_use]
+    pub fn trivia(&self) -> &[Trivia] {
+        &self.trivia
+    }
+
+    /// Returns an iterator over all the comments of the source.
+    pub fn comments(&self) -> impl Iterator<Item = &Trivia> {
+        self.trivia.iter().filter(|trivia| trivia.kind.is_comment())
+    }
+
+    /// Gets the trivia right before `span`, starting on the line after the previous token.
+    #[must_use]
+    pub fn leading(&self, span: Span) -> &[Trivia] {
+        let end = self
+            .trivia
+            .partition_point(|trivia| trivia.span.end() <= span.start());
+        if end == 0 || self.trivia[end - 1].span.end() != span.start() {
+            return &[];
+        }
+
+        let mut start = end - 1;
+        while start > 0 && self.trivia[start - 1].span.end() == self.trivia[start].span.start() {
+            start -= 1;
+        }
+
+        // Unless the trivia is at the start of the source, the trivia up to the first line
+        // terminator is the trailing trivia of the previous token.
+        if self.trivia[start].span.start() != Position::new(1, 1)
+            && let Some(line_terminator) = self.trivia[start..end]
+                .iter()
+                .position(|trivia| trivia.kind == TriviaKind::LineTerminator)
+        {
+            start += line_terminator + 1;
+        }
+        &self.trivia[start..end]
+    }
+
+    /// Gets the trivia right after `span`, up to the end of its line.
+    #[must_use]
+    pub fn trailing(&self, span: Span) -> &[Trivia] {
+        let start = self
+            .trivia
+            .partition_point(|trivia| trivia.span.start() < span.end());
+        if self
+            .trivia
+            .get(start)
+            .is_none_or(|trivia| trivia.span.start() != span.end())
+        {
+            return &[];
+        }
+
+        let mut end = start;
+        while let Some(trivia) = self.trivia.get(end)
+            && trivia.kind != TriviaKind::LineTerminator
+            && (end == start || self.trivia[end - 1].span.end() == trivia.span.start())
+        {
+            end += 1;
+        }
+        &self.trivia[start..end]
+    }
+
+    /// Returns an iterator over the comments right before `span`.
+    pub fn leading_comments(&self, span: Span) -> impl Iterator<Item = &Trivia> {
+        self.leading(span)
+            .iter()
+            .filter(|trivia| trivia.kind.is_comment())
+    }
+
+    /// Returns an iterator over the comments right after `span`, on the same line as its end.
+    pub fn trailing_comments(&self, span: Span) -> impl Iterator<Item = &Trivia> {
+        self.trailing(span)
+            .iter()
+            .filter(|trivia| trivia.kind.is_comment())
+    }
+}
@@ -25,10 +25,13 @@
     clippy::struct_field_names
 )]
 
+pub mod diagnostic;
 pub mod error;
 pub mod lexer;
 pub mod parser;
 pub mod source;
+#[cfg(feature = "typescript")]
+mod typescript;
 
 pub use error::Error;
 pub use lexer::Lexer;
@@ -1,10 +1,10 @@
 use crate::{
     Error,
-    lexer::{InputElement, Lexer, Token, TokenKind},
+    lexer::{InputElement, Lexer, Token, TokenKind, TriviaTable},
     parser::ParseResult,
     source::{ReadChar, UTF8Input},
 };
-use boa_ast::{LinearPosition, PositionGroup};
+use boa_ast::{LinearPosition, LinearSpan, Position, PositionGroup, Span, Spanned};
 use boa_interner::Interner;
 
 #[cfg(test)]
@@ -27,9 +27,12 @@ const PEEK_BUF_SIZE: usize = (MAX_PEEK_SKIP + 1) * 2 + 1;
 pub(super) struct BufferedLexer<R> {
     lexer: Lexer<R>,
     peeked: [Option<Token>; PEEK_BUF_SIZE],
+    goals: [InputElement; PEEK_BUF_SIZE],
     read_index: usize,
     write_index: usize,
     last_linear_pos: LinearPosition,
+    last_position: Position,
+    tokens: Option<Vec<Token>>,
 }
 
 impl<R> From<Lexer<R>> for BufferedLexer<R>
@@ -50,9 +53,12 @@ where
                 None::<Token>,
                 None::<Token>,
             ],
+            goals: [InputElement::default(); PEEK_BUF_SIZE],
             read_index: 0,
             write_index: 0,
             last_linear_pos: LinearPosition::default(),
+            last_position: Position::default(),
+            tokens: None,
         }
     }
 }
@@ -90,9 +96,13 @@ where
         init_with_eq: bool,
     ) -> ParseResult<Token> {
         self.set_goal(InputElement::RegExp);
-        self.lexer
-            .lex_slash_token(start, interner, init_with_eq)
-            .map_err(Into::into)
+        let token = self.lexer.lex_slash_token(start, interner, init_with_eq)?;
+        if let Some(tokens) = &mut self.tokens {
+            // The regex replaces the `/` or `/=` punctuator that was already consumed.
+            tokens.pop();
+            tokens.push(token.clone());
+        }
+        Ok(token)
     }
 
     /// Lexes the next tokens as template middle or template tail assuming that the starting
@@ -102,9 +112,101 @@ where
         start: PositionGroup,
         interner: &mut Interner,
     ) -> ParseResult<Token> {
-        self.lexer
-            .lex_template(start, interner)
-            .map_err(Error::from)
+        let token = self.lexer.lex_template(start, interner)?;
+        if let Some(tokens) = &mut self.tokens
+            && let Some(close) = tokens.pop()
+        {
+            // The template continuation replaces the `}` that was already consumed, and starts
+            // with it.
+            tokens.push(Token::new(
+                token.kind().clone(),
+                Span::new(close.span().start(), token.span().end()),
+                LinearSpan::new(close.linear_span().start(), token.linear_span().end()),
+            ));
+        }
+        Ok(token)
+    }
+
+    /// Lexes JSX text, up to the next `<` or `{`.
+    pub(super) fn lex_jsx_text(&mut self, interner: &mut Interner) -> ParseResult<Token> {
+        self.check_empty()?;
+        let token = self.lexer.lex_jsx_text(interner)?;
+        self.last_linear_pos = token.linear_span().end();
+        self.last_position = token.span().end();
+        self.record(&token);
+        Ok(token)
+    }
+
+    /// Lexes the next token inside of a JSX tag.
+    pub(super) fn lex_jsx_tag(&mut self, interner: &mut Interner) -> ParseResult<Option<Token>> {
+        self.check_empty()?;
+        let token = self.lexer.lex_jsx_tag(interner)?;
+        if let Some(token) = &token {
+            self.last_linear_pos = token.linear_span().end();
+            self.last_position = token.span().end();
+            self.record(token);
+        }
+        Ok(token)
+    }
+
+    /// Continues a source code whose first `prefix.len()` code units were already parsed, ending
+    /// at `start`.
+    pub(super) fn resume(&mut self, start: PositionGroup, prefix: &[u16]) {
+        self.lexer.resume(start.position(), prefix);
+        self.last_linear_pos = start.linear_position();
+        self.last_position = start.position();
+    }
+
+    /// Gets the goal symbol that the next token, skipping line terminators, was lexed with.
+    ///
+    /// The next token must have been peeked.
+    pub(super) fn next_goal(&self) -> InputElement {
+        match &self.peeked[self.read_index] {
+            Some(token) if token.kind() == &TokenKind::LineTerminator => {
+                self.goals[(self.read_index + 1) % PEEK_BUF_SIZE]
+            }
+            _ => self.goals[self.read_index],
+        }
+    }
+
+    /// Consumes the next token and skips the source code after it up to `end`, without lexing it.
+    ///
+    /// Returns `false` without consuming anything if other tokens were peeked after the next one.
+    pub(super) fn skip_to(
+        &mut self,
+        end: LinearPosition,
+        interner: &mut Interner,
+    ) -> ParseResult<bool> {
+        let last = self.write_index.checked_sub(1).unwrap_or(PEEK_BUF_SIZE - 1);
+        if self.read_index == self.write_index
+            || !matches!(&self.peeked[last], Some(token) if token.kind() != &TokenKind::LineTerminator)
+        {
+            return Ok(false);
+        }
+        let mut index = self.read_index;
+        while index != last {
+            if !matches!(&self.peeked[index], Some(token) if token.kind() == &TokenKind::LineTerminator)
+            {
+                return Ok(false);
+            }
+            index = (index + 1) % PEEK_BUF_SIZE;
+        }
+
+        self.next(true, interner)?;
+        self.last_position = self.lexer.skip_to(end)?;
+        self.last_linear_pos = end;
+        Ok(true)
+    }
+
+    /// Checks that no token was peeked, since JSX is lexed directly from the source.
+    fn check_empty(&self) -> ParseResult<()> {
+        if self.read_index == self.write_index {
+            return Ok(());
+        }
+        let position = self.peeked[self.read_index]
+            .as_ref()
+            .map_or(self.last_position, |token| token.span().start());
+        Err(Error::general("unexpected JSX", position))
     }
 
     pub(super) const fn strict(&self) -> bool {
@@ -133,6 +235,7 @@ where
         );
 
         let previous_index = self.write_index.checked_sub(1).unwrap_or(PEEK_BUF_SIZE - 1);
+        self.goals[self.write_index] = self.lexer.get_goal();
 
         if let Some(ref token) = self.peeked[previous_index]
             && token.kind() == &TokenKind::LineTerminator
@@ -201,6 +304,10 @@ where
 
             if let Some(tok) = &tok {
                 self.last_linear_pos = tok.linear_span().end();
+                self.last_position = tok.span().end();
+                if tok.kind() != &TokenKind::LineTerminator {
+                    self.record(tok);
+                }
             }
 
             Ok(tok)
@@ -273,6 +380,41 @@ where
         self.last_linear_pos
     }
 
+    pub(super) fn set_collect_trivia(&mut self) {
+        self.lexer.set_collect_trivia();
+    }
+
+    pub(super) fn take_trivia(&mut self) -> TriviaTable {
+        self.lexer.take_trivia()
+    }
+
+    /// Starts recording the tokens consumed by the parser.
+    pub(super) fn set_collect_tokens(&mut self) {
+        self.tokens.get_or_insert_with(Vec::new);
+    }
+
+    /// Takes the tokens recorded so far.
+    pub(super) fn take_tokens(&mut self) -> Vec<Token> {
+        self.tokens.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn record(&mut self, token: &Token) {
+        if let Some(tokens) = &mut self.tokens {
+            tokens.push(token.clone());
+        }
+    }
+
+    #[cfg(feature = "typescript")]
+    pub(super) fn strip_typescript(&mut self, module: bool) -> ParseResult<()> {
+        self.lexer.strip_typescript(module)
+    }
+
+    /// Gets the position of the end of the last token that was consumed.
+    #[inline]
+    pub(super) const fn position(&self) -> Position {
+        self.last_position
+    }
+
     pub(super) fn take_source(&mut self) -> boa_ast::SourceText {
         self.lexer.take_source()
     }
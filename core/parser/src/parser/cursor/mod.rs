@@ -3,11 +3,14 @@ mod buffered_lexer;
 
 use crate::{
     Error,
-    lexer::{InputElement, Lexer, Token, TokenKind},
-    parser::{OrAbrupt, ParseResult},
-    source::ReadChar,
+    lexer::{InputElement, Lexer, Token, TokenKind, TriviaTable},
+    parser::{OrAbrupt, ParseResult, incremental::Reuse},
+    source::{JsxOptions, ReadChar},
+};
+use boa_ast::{
+    LinearPosition, Position, PositionGroup, Punctuator, Span, Spanned, function::FunctionBody,
+    operations::relocate,
 };
-use boa_ast::{LinearPosition, PositionGroup, Punctuator, Spanned};
 use boa_interner::Interner;
 use buffered_lexer::BufferedLexer;
 
@@ -37,6 +40,15 @@ pub(super) struct Cursor<R> {
 
     /// Tracks the number of tagged templates that are currently being parsed.
     tagged_templates_count: u32,
+
+    /// The errors that the parser recovered from, if error recovery is enabled.
+    recovered_errors: Option<Vec<(Error, Span)>>,
+
+    /// The JSX options of the source, if JSX is enabled.
+    jsx: Option<JsxOptions>,
+
+    /// The state of an incremental parse, if the cursor is used for one.
+    reuse: Option<Box<Reuse>>,
 }
 
 impl<R> Cursor<R>
@@ -51,9 +63,86 @@ where
             json_parse: false,
             identifier: 0,
             tagged_templates_count: 0,
+            recovered_errors: None,
+            jsx: None,
+            reuse: None,
         }
     }
 
+    /// Continues parsing a source code whose first `prefix.len()` code units were already
+    /// parsed, ending at `start`.
+    pub(super) fn resume(&mut self, start: PositionGroup, prefix: &[u16]) {
+        self.buffered_lexer.resume(start, prefix);
+    }
+
+    /// Starts an incremental parse.
+    pub(super) fn set_reuse(&mut self, reuse: Reuse) {
+        self.reuse = Some(Box::new(reuse));
+    }
+
+    /// Takes the state of the incremental parse.
+    pub(super) fn take_reuse(&mut self) -> Option<Reuse> {
+        self.reuse.take().map(|reuse| *reuse)
+    }
+
+    /// Returns `true` if the top-level list of items of an incremental parse is entered, which
+    /// happens only once.
+    pub(super) fn enter_top_level(&mut self) -> bool {
+        self.reuse
+            .as_deref_mut()
+            .is_some_and(Reuse::enter_top_level)
+    }
+
+    /// Returns `true` if the top-level item starting at `start` is reused by the incremental
+    /// parse, which ends the list of items.
+    ///
+    /// No item is reused if `can_stop` is `false`, like in the middle of the directives.
+    pub(super) fn stop_before_item(&mut self, start: PositionGroup, can_stop: bool) -> bool {
+        let strict = self.strict();
+        let goal = self.buffered_lexer.next_goal();
+        self.reuse
+            .as_deref_mut()
+            .is_some_and(|reuse| reuse.stop_before_item(start, goal, strict, can_stop))
+    }
+
+    /// Reuses the function body that starts at the next token from the previous parse, if the
+    /// incremental parse didn't touch it.
+    pub(super) fn reuse_function_body(
+        &mut self,
+        allow_yield: bool,
+        allow_await: bool,
+        interner: &mut Interner,
+    ) -> ParseResult<Option<FunctionBody>> {
+        if self.reuse.is_none() {
+            return Ok(None);
+        }
+        let start = match self.peek(0, interner)? {
+            Some(token) if token.kind() == &TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                PositionGroup::new(token.span().start(), token.linear_span().start())
+            }
+            _ => return Ok(None),
+        };
+        let strict = self.strict();
+        let Some((mut body, relocation, end)) = self
+            .reuse
+            .as_deref_mut()
+            .and_then(|reuse| reuse.function_body(start, allow_yield, allow_await, strict))
+        else {
+            return Ok(None);
+        };
+        if !self
+            .buffered_lexer
+            .skip_to(relocation.linear_position(end), interner)?
+        {
+            return Ok(None);
+        }
+        if let Some(reuse) = self.reuse.as_deref_mut() {
+            reuse.reuse_nested_bodies(&relocation, end);
+        }
+        relocate(&mut body, relocation);
+        Ok(Some(body))
+    }
+
     /// Sets the goal symbol of the cursor to `Module`.
     pub(super) fn set_module(&mut self) {
         self.buffered_lexer.set_module(true);
@@ -87,6 +176,28 @@ where
         self.buffered_lexer.lex_template(start, interner)
     }
 
+    /// Sets the JSX options of the source, enabling JSX if they are `Some`.
+    pub(super) fn set_jsx(&mut self, jsx: Option<JsxOptions>) {
+        self.jsx = jsx;
+    }
+
+    /// Returns the JSX options of the source, if JSX is enabled.
+    pub(super) const fn jsx(&self) -> Option<&JsxOptions> {
+        self.jsx.as_ref()
+    }
+
+    /// Lexes JSX text, assuming that the `>` or `}` before it has already been consumed.
+    pub(super) fn lex_jsx_text(&mut self, interner: &mut Interner) -> ParseResult<Token> {
+        self.buffered_lexer.lex_jsx_text(interner)
+    }
+
+    /// Advances the cursor to the next token inside of a JSX tag.
+    ///
+    /// No token can be peeked while a JSX tag is lexed.
+    pub(super) fn lex_jsx_tag(&mut self, interner: &mut Interner) -> ParseResult<Option<Token>> {
+        self.buffered_lexer.lex_jsx_tag(interner)
+    }
+
     /// Advances the cursor and returns the next token.
     pub(super) fn next(&mut self, interner: &mut Interner) -> ParseResult<Option<Token>> {
         self.buffered_lexer.next(true, interner)
@@ -174,6 +285,47 @@ where
         (count << 32) | identifier
     }
 
+    /// Enables error recovery, so that the parser collects syntax errors instead of stopping at
+    /// the first one.
+    pub(super) fn set_error_recovery(&mut self) {
+        self.recovered_errors = Some(Vec::new());
+    }
+
+    /// Returns `true` if the parser recovers from syntax errors.
+    pub(super) const fn error_recovery(&self) -> bool {
+        self.recovered_errors.is_some()
+    }
+
+    /// Reports an error that does not prevent parsing the rest of the source.
+    ///
+    /// If error recovery is enabled, the error is collected and parsing continues, otherwise the
+    /// error is returned.
+    pub(super) fn report(&mut self, error: Error) -> ParseResult<()> {
+        let span = error.span().unwrap_or_else(|| Position::new(1, 1).into());
+        self.report_in(error, span)
+    }
+
+    /// Reports an error like [`Cursor::report`], using `span` as the location of errors that
+    /// don't have a location of their own.
+    pub(super) fn report_in(&mut self, error: Error, span: Span) -> ParseResult<()> {
+        match &mut self.recovered_errors {
+            Some(errors) => {
+                let span = error.span().unwrap_or(span);
+                errors.push((error, span));
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Takes the errors that the parser recovered from.
+    pub(super) fn take_recovered_errors(&mut self) -> Vec<(Error, Span)> {
+        self.recovered_errors
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Returns an error if the next token is not of kind `kind`.
     pub(super) fn expect<K>(
         &mut self,
@@ -184,11 +336,13 @@ where
     where
         K: Into<TokenKind>,
     {
-        let next_token = self.next(interner).or_abrupt()?;
         let kind = kind.into();
+        let next_token = self.peek(0, interner).or_abrupt()?;
 
+        // The unexpected token is not consumed, so that the parser can recover from the error
+        // knowing the nesting of the tokens that follow.
         if next_token.kind() == &kind {
-            Ok(next_token)
+            self.next(interner).or_abrupt()
         } else {
             Err(Error::expected(
                 [kind.to_string(interner)],
@@ -309,6 +463,38 @@ where
         self.buffered_lexer.linear_pos()
     }
 
+    /// Starts recording the trivia of the source code.
+    pub(super) fn set_collect_trivia(&mut self) {
+        self.buffered_lexer.set_collect_trivia();
+    }
+
+    /// Takes the trivia recorded so far.
+    pub(super) fn take_trivia(&mut self) -> TriviaTable {
+        self.buffered_lexer.take_trivia()
+    }
+
+    /// Starts recording the tokens consumed by the parser.
+    pub(super) fn set_collect_tokens(&mut self) {
+        self.buffered_lexer.set_collect_tokens();
+    }
+
+    /// Takes the tokens recorded so far.
+    pub(super) fn take_tokens(&mut self) -> Vec<Token> {
+        self.buffered_lexer.take_tokens()
+    }
+
+    /// Replaces the TypeScript source of the cursor with the equivalent JavaScript.
+    #[cfg(feature = "typescript")]
+    pub(super) fn strip_typescript(&mut self, module: bool) -> ParseResult<()> {
+        self.buffered_lexer.strip_typescript(module)
+    }
+
+    /// Gets the position of the end of the last token that was consumed.
+    #[inline]
+    pub(super) const fn position(&self) -> Position {
+        self.buffered_lexer.position()
+    }
+
     pub(super) fn take_source(&mut self) -> boa_ast::SourceText {
         self.buffered_lexer.take_source()
     }
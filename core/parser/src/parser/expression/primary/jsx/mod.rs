@@ -0,0 +1,469 @@
+//! JSX parsing.
+//!
+//! JSX is only parsed if it was enabled in the [`Source`][crate::Source], where a `<` at the
+//! start of a primary expression starts an element. The tags and the children of elements are
+//! lexed with the JSX rules, while the expressions in `{}` containers are lexed as usual.
+//!
+//! More information:
+//!  - [JSX specification][spec]
+//!
+//! [spec]: https://facebook.github.io/jsx/
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Error,
+    lexer::{InputElement, Token, TokenKind},
+    parser::{
+        AllowAwait, AllowYield, Cursor, OrAbrupt, ParseResult, TokenParser,
+        expression::AssignmentExpression,
+    },
+    source::{JsxOptions, ReadChar},
+};
+use boa_ast::{
+    self as ast, Position, Punctuator, Span, Spanned,
+    expression::{
+        Identifier, This,
+        access::{PropertyAccess, SimplePropertyAccess},
+        jsx::{
+            Jsx, JsxAttribute, JsxAttributeValue, JsxChild, JsxElement, JsxElementName, JsxRuntime,
+            JsxText,
+        },
+    },
+};
+use boa_interner::{Interner, Sym};
+
+/// The local names of the `jsx`, `jsxs` and `Fragment` exports of the automatic runtime.
+const AUTOMATIC_RUNTIME_BINDINGS: [(&str, &str); 3] = [
+    ("jsx", "_jsx"),
+    ("jsxs", "_jsxs"),
+    ("Fragment", "_Fragment"),
+];
+
+/// Parses a JSX element or fragment used as an expression.
+///
+/// More information:
+///  - [JSX specification][spec]
+///
+/// [spec]: https://facebook.github.io/jsx/#prod-PrimaryExpression
+#[derive(Debug, Clone, Copy)]
+pub(super) struct JsxExpression {
+    allow_yield: AllowYield,
+    allow_await: AllowAwait,
+}
+
+impl JsxExpression {
+    /// Creates a new `JsxExpression` parser.
+    pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+    where
+        Y: Into<AllowYield>,
+        A: Into<AllowAwait>,
+    {
+        Self {
+            allow_yield: allow_yield.into(),
+            allow_await: allow_await.into(),
+        }
+    }
+}
+
+impl<R> TokenParser<R> for JsxExpression
+where
+    R: ReadChar,
+{
+    type Output = Jsx;
+
+    fn parse(self, cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Self::Output> {
+        let open = cursor.expect(Punctuator::LessThan, "JSX element", interner)?;
+        let runtime = runtime(cursor, open.span(), interner)?;
+
+        let first = tag_token(cursor, interner)?;
+        let element = self.element(cursor, open.span().start(), &first, interner)?;
+        cursor.set_goal(InputElement::Div);
+
+        Ok(Jsx::new(element, runtime))
+    }
+}
+
+impl JsxExpression {
+    /// Parses an element whose `<` at `start` has already been consumed, where `first` is the
+    /// token after the `<`.
+    fn element<R: ReadChar>(
+        self,
+        cursor: &mut Cursor<R>,
+        start: Position,
+        first: &Token,
+        interner: &mut Interner,
+    ) -> ParseResult<JsxElement> {
+        // Fragments, like `<>a</>`.
+        if first.kind() == &TokenKind::Punctuator(Punctuator::GreaterThan) {
+            let children = self.children(cursor, None, interner)?;
+            let span = Span::new(start, cursor.position());
+            return Ok(JsxElement::new(None, Box::default(), children, span));
+        }
+
+        let (name, text, mut token) = element_name(cursor, first, interner)?;
+        let mut attributes = Vec::new();
+        loop {
+            match token.kind() {
+                TokenKind::Punctuator(Punctuator::GreaterThan) => break,
+                TokenKind::Punctuator(Punctuator::Div) => {
+                    let close = tag_token(cursor, interner)?;
+                    expect_punctuator(&close, Punctuator::GreaterThan, "JSX element", interner)?;
+                    let span = Span::new(start, close.span().end());
+                    return Ok(JsxElement::new(
+                        Some(name),
+                        attributes.into_boxed_slice(),
+                        Box::default(),
+                        span,
+                    ));
+                }
+                // Spread attributes, like `{...props}`.
+                TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                    cursor.expect(Punctuator::Spread, "JSX spread attribute", interner)?;
+                    let expression = self.expression_container(cursor, interner)?;
+                    attributes.push(JsxAttribute::Spread(expression));
+                    token = tag_token(cursor, interner)?;
+                }
+                TokenKind::IdentifierName(_) => {
+                    let (name, next) = namespaced_name(cursor, &token, interner)?;
+                    if next.kind() == &TokenKind::Punctuator(Punctuator::Assign) {
+                        let value = self.attribute_value(cursor, interner)?;
+                        attributes.push(JsxAttribute::Named {
+                            name,
+                            value: Some(value),
+                        });
+                        token = tag_token(cursor, interner)?;
+                    } else {
+                        attributes.push(JsxAttribute::Named { name, value: None });
+                        token = next;
+                    }
+                }
+                _ => {
+                    return Err(Error::unexpected(
+                        token.to_string(interner),
+                        token.span(),
+                        "JSX attribute",
+                    ));
+                }
+            }
+        }
+
+        let children = self.children(cursor, Some(&text), interner)?;
+        let span = Span::new(start, cursor.position());
+        Ok(JsxElement::new(
+            Some(name),
+            attributes.into_boxed_slice(),
+            children,
+            span,
+        ))
+    }
+
+    /// Parses the children of an element up to its closing tag, which has to match `name`, or
+    /// the closing tag of a fragment if `name` is `None`.
+    fn children<R: ReadChar>(
+        self,
+        cursor: &mut Cursor<R>,
+        name: Option<&str>,
+        interner: &mut Interner,
+    ) -> ParseResult<Box<[JsxChild]>> {
+        let mut children = Vec::new();
+        loop {
+            let text = cursor.lex_jsx_text(interner)?;
+            if let TokenKind::JsxText(raw, value) = *text.kind()
+                && raw != Sym::EMPTY_STRING
+            {
+                children.push(JsxChild::Text(JsxText::new(raw, value, text.span())));
+            }
+
+            let token = tag_token(cursor, interner)?;
+            match token.kind() {
+                TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                    // Containers with only comments, like `{/* a */}`, are ignored.
+                    if cursor.next_if(Punctuator::CloseBlock, interner)?.is_some() {
+                        continue;
+                    }
+                    if cursor.next_if(Punctuator::Spread, interner)?.is_some() {
+                        let expression = self.expression_container(cursor, interner)?;
+                        children.push(JsxChild::Spread(expression));
+                    } else {
+                        let expression = self.expression_container(cursor, interner)?;
+                        children.push(JsxChild::Expression(expression));
+                    }
+                }
+                TokenKind::Punctuator(Punctuator::LessThan) => {
+                    let next = tag_token(cursor, interner)?;
+                    if next.kind() != &TokenKind::Punctuator(Punctuator::Div) {
+                        let element =
+                            self.element(cursor, token.span().start(), &next, interner)?;
+                        children.push(JsxChild::Element(element));
+                        continue;
+                    }
+
+                    // The closing tag.
+                    let mut next = tag_token(cursor, interner)?;
+                    let closing_name =
+                        if next.kind() == &TokenKind::Punctuator(Punctuator::GreaterThan) {
+                            None
+                        } else {
+                            let (_, text, after) = element_name(cursor, &next, interner)?;
+                            next = after;
+                            Some(text)
+                        };
+                    expect_punctuator(&next, Punctuator::GreaterThan, "JSX closing tag", interner)?;
+                    if closing_name.as_deref() != name {
+                        return Err(Error::general(
+                            format!(
+                                "expected corresponding JSX closing tag for `<{}>`",
+                                name.unwrap_or_default()
+                            ),
+                            token.span().start(),
+                        ));
+                    }
+                    return Ok(children.into_boxed_slice());
+                }
+                _ => {
+                    return Err(Error::unexpected(
+                        token.to_string(interner),
+                        token.span(),
+                        "JSX children",
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Parses the value of an attribute after its `=`.
+    fn attribute_value<R: ReadChar>(
+        self,
+        cursor: &mut Cursor<R>,
+        interner: &mut Interner,
+    ) -> ParseResult<JsxAttributeValue> {
+        let token = tag_token(cursor, interner)?;
+        match *token.kind() {
+            TokenKind::JsxText(raw, value) => Ok(JsxAttributeValue::String(JsxText::new(
+                raw,
+                value,
+                token.span(),
+            ))),
+            TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                if let Some(close) = cursor.next_if(Punctuator::CloseBlock, interner)? {
+                    return Err(Error::general(
+                        "JSX attributes must only be assigned a non-empty expression",
+                        close.span().start(),
+                    ));
+                }
+                self.expression_container(cursor, interner)
+                    .map(JsxAttributeValue::Expression)
+            }
+            TokenKind::Punctuator(Punctuator::LessThan) => {
+                let first = tag_token(cursor, interner)?;
+                self.element(cursor, token.span().start(), &first, interner)
+                    .map(JsxAttributeValue::Element)
+            }
+            _ => Err(Error::unexpected(
+                token.to_string(interner),
+                token.span(),
+                "JSX attribute value",
+            )),
+        }
+    }
+
+    /// Parses the expression of a container up to its closing `}`, which is consumed.
+    fn expression_container<R: ReadChar>(
+        self,
+        cursor: &mut Cursor<R>,
+        interner: &mut Interner,
+    ) -> ParseResult<ast::Expression> {
+        let expression = AssignmentExpression::new(true, self.allow_yield, self.allow_await)
+            .parse(cursor, interner)?;
+        cursor.expect(Punctuator::CloseBlock, "JSX expression container", interner)?;
+        Ok(expression)
+    }
+}
+
+/// Lexes the next token inside of a JSX tag, failing at the end of the input.
+fn tag_token<R: ReadChar>(cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Token> {
+    cursor.lex_jsx_tag(interner).or_abrupt()
+}
+
+/// Checks that `token` is the `punctuator`.
+fn expect_punctuator(
+    token: &Token,
+    punctuator: Punctuator,
+    context: &'static str,
+    interner: &Interner,
+) -> ParseResult<()> {
+    if token.kind() == &TokenKind::Punctuator(punctuator) {
+        Ok(())
+    } else {
+        Err(Error::expected(
+            [punctuator.to_string()],
+            token.to_string(interner),
+            token.span(),
+            context,
+        ))
+    }
+}
+
+/// Parses a possibly namespaced name starting at `first`, like `a` or `a:b`, returning it with
+/// the token after it.
+fn namespaced_name<R: ReadChar>(
+    cursor: &mut Cursor<R>,
+    first: &Token,
+    interner: &mut Interner,
+) -> ParseResult<(Sym, Token)> {
+    let TokenKind::IdentifierName((name, _)) = *first.kind() else {
+        return Err(Error::unexpected(
+            first.to_string(interner),
+            first.span(),
+            "JSX identifier",
+        ));
+    };
+    let next = tag_token(cursor, interner)?;
+    if next.kind() != &TokenKind::Punctuator(Punctuator::Colon) {
+        return Ok((name, next));
+    }
+
+    let local = tag_token(cursor, interner)?;
+    let TokenKind::IdentifierName((local_name, _)) = *local.kind() else {
+        return Err(Error::unexpected(
+            local.to_string(interner),
+            local.span(),
+            "JSX namespaced name",
+        ));
+    };
+    let name = format!(
+        "{}:{}",
+        interner.resolve_expect(name),
+        interner.resolve_expect(local_name)
+    );
+    Ok((
+        interner.get_or_intern(name.as_str()),
+        tag_token(cursor, interner)?,
+    ))
+}
+
+/// Parses the name of an element starting at `first`, returning it with its source text and the
+/// token after it.
+///
+/// Names that start with a lowercase letter or contain a dash are intrinsic elements, like
+/// `div`, and other names reference components, like `Button` or `ui.Button`.
+fn element_name<R: ReadChar>(
+    cursor: &mut Cursor<R>,
+    first: &Token,
+    interner: &mut Interner,
+) -> ParseResult<(JsxElementName, String, Token)> {
+    let (name, mut next) = namespaced_name(cursor, first, interner)?;
+    let mut text = interner.resolve_expect(name).to_string();
+    if text.contains(':') {
+        return Ok((JsxElementName::Intrinsic(name), text, next));
+    }
+
+    if next.kind() != &TokenKind::Punctuator(Punctuator::Dot) {
+        let intrinsic = text.starts_with(|c: char| c.is_ascii_lowercase()) || text.contains('-');
+        let name = if intrinsic {
+            JsxElementName::Intrinsic(name)
+        } else {
+            JsxElementName::Component(Identifier::new(name, first.span()).into())
+        };
+        return Ok((name, text, next));
+    }
+
+    // Member expressions, like `a.b.C`.
+    let mut expression = if text == "this" {
+        This::new(first.span()).into()
+    } else {
+        Identifier::new(name, first.span()).into()
+    };
+    while next.kind() == &TokenKind::Punctuator(Punctuator::Dot) {
+        let property = tag_token(cursor, interner)?;
+        let TokenKind::IdentifierName((property_name, _)) = *property.kind() else {
+            return Err(Error::unexpected(
+                property.to_string(interner),
+                property.span(),
+                "JSX member expression",
+            ));
+        };
+        text.push('.');
+        text.push_str(&interner.resolve_expect(property_name).to_string());
+        expression = PropertyAccess::from(SimplePropertyAccess::new(
+            expression,
+            Identifier::new(property_name, property.span()),
+        ))
+        .into();
+        next = tag_token(cursor, interner)?;
+    }
+    Ok((JsxElementName::Component(expression), text, next))
+}
+
+/// Creates the runtime of a JSX expression at `span` from the JSX options of the cursor.
+fn runtime<R: ReadChar>(
+    cursor: &mut Cursor<R>,
+    span: Span,
+    interner: &mut Interner,
+) -> ParseResult<JsxRuntime> {
+    match cursor.jsx() {
+        Some(JsxOptions::Classic { factory, fragment }) => {
+            let factory = reference(factory, span, interner)?;
+            let fragment = reference(fragment, span, interner)?;
+            Ok(JsxRuntime::Classic {
+                factory: Box::new(factory),
+                fragment: Box::new(fragment),
+            })
+        }
+        Some(JsxOptions::Automatic { import_source }) => {
+            if !cursor.module() {
+                return Err(Error::general(
+                    "the automatic JSX runtime can only be used in modules",
+                    span.start(),
+                ));
+            }
+            let module = interner.get_or_intern(format!("{import_source}/jsx-runtime").as_str());
+            let [jsx, jsxs, fragment] = AUTOMATIC_RUNTIME_BINDINGS
+                .map(|(_, local)| Identifier::new(interner.get_or_intern(local), span));
+            let exports =
+                AUTOMATIC_RUNTIME_BINDINGS.map(|(export, _)| interner.get_or_intern(export));
+            Ok(JsxRuntime::Automatic {
+                jsx,
+                jsxs,
+                fragment,
+                module,
+                exports,
+            })
+        }
+        None => Err(Error::general("JSX is not enabled", span.start())),
+    }
+}
+
+/// Creates the expression referenced by a JSX factory option, like `React.createElement`.
+fn reference(path: &str, span: Span, interner: &mut Interner) -> ParseResult<ast::Expression> {
+    let is_name = |name: &str| {
+        name.starts_with(|c: char| c.is_alphabetic() || c == '$' || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '$' || c == '_')
+    };
+    if !path.split('.').all(is_name) {
+        return Err(Error::general(
+            format!("invalid JSX factory `{path}`"),
+            span.start(),
+        ));
+    }
+
+    let mut names = path.split('.');
+    let first = names.next().unwrap_or_default();
+    let mut expression = if first == "this" {
+        This::new(span).into()
+    } else {
+        Identifier::new(interner.get_or_intern(first), span).into()
+    };
+    for name in names {
+        expression = PropertyAccess::from(SimplePropertyAccess::new(
+            expression,
+            Identifier::new(interner.get_or_intern(name), span),
+        ))
+        .into();
+    }
+    Ok(expression)
+}
@@ -0,0 +1,227 @@
+use crate::{Parser, Source, source::JsxOptions};
+use boa_ast::{
+    Expression, Statement, StatementListItem,
+    declaration::ImportName,
+    expression::jsx::{JsxAttribute, JsxAttributeValue, JsxChild, JsxElementName, JsxRuntime},
+    scope::Scope,
+};
+use boa_interner::{Interner, ToInternedString};
+
+/// Parses the JSX expression statement `js` with the default classic runtime.
+#[track_caller]
+fn parse(js: &str, interner: &mut Interner) -> Expression {
+    let script = Parser::new(Source::from_bytes(js).with_jsx(JsxOptions::default()))
+        .parse_script(&Scope::new_global(), interner)
+        .expect("failed to parse JSX");
+    match script.statements().statements() {
+        [StatementListItem::Statement(statement)] => match &**statement {
+            Statement::Expression(expression) => expression.clone(),
+            statement => panic!("unexpected statement: {statement:?}"),
+        },
+        items => panic!("unexpected items: {items:?}"),
+    }
+}
+
+/// Checks that the JSX expression `js` parses and prints back as `expected`.
+#[track_caller]
+fn check(js: &str, expected: &str) {
+    let interner = &mut Interner::default();
+    let expression = parse(js, interner);
+    assert_eq!(expression.to_interned_string(interner), expected);
+}
+
+/// Checks that parsing the JSX script `js` fails with an error containing `message`.
+#[track_caller]
+fn check_invalid(js: &str, message: &str) {
+    let error = Parser::new(Source::from_bytes(js).with_jsx(JsxOptions::default()))
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .expect_err("JSX should not parse");
+    assert!(
+        error.to_string().contains(message),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn elements() {
+    check("<div />", "<div />");
+    check("<div></div>", "<div />");
+    check("<a><b>c</b><d /></a>", "<a><b>c</b><d /></a>");
+    check("<my-element />", "<my-element />");
+    check("<svg:rect />", "<svg:rect />");
+    check("<Foo.Bar.Baz />", "<Foo.Bar.Baz />");
+    check("<this.Component />", "<this.Component />");
+}
+
+#[test]
+fn fragments() {
+    check("<>a<b /></>", "<>a<b /></>");
+    check("<></>", "<></>");
+}
+
+#[test]
+fn attributes() {
+    check(
+        "<a b c=\"d\" e='\"f\"' g={h} {...i} j=<k /> l:m=\"n\" />",
+        "<a b c=\"d\" e='\"f\"' g={h} {...i} j=<k /> l:m=\"n\" />",
+    );
+}
+
+#[test]
+fn children() {
+    check(
+        "<a>\n  b {c} {...d} {/* e */}\n  <f>{g ? <h /> : null}</f>\n</a>",
+        "<a>\n  b {c} {...d} \n  <f>{g ? <h /> : null}</f>\n</a>",
+    );
+}
+
+#[test]
+fn element_names() {
+    let interner = &mut Interner::default();
+    let Expression::Jsx(jsx) = parse("<a><B /><c-d /><E.F /></a>", interner) else {
+        panic!("expected a JSX expression");
+    };
+    assert!(matches!(
+        jsx.element().name(),
+        Some(JsxElementName::Intrinsic(_))
+    ));
+    let names = jsx
+        .element()
+        .children()
+        .iter()
+        .map(|child| match child {
+            JsxChild::Element(element) => element.name().cloned(),
+            child => panic!("unexpected child: {child:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        names[..],
+        [
+            Some(JsxElementName::Component(Expression::Identifier(_))),
+            Some(JsxElementName::Intrinsic(_)),
+            Some(JsxElementName::Component(Expression::PropertyAccess(_))),
+        ]
+    ));
+    assert!(matches!(jsx.runtime(), JsxRuntime::Classic { .. }));
+}
+
+#[test]
+fn entities() {
+    let interner = &mut Interner::default();
+    let Expression::Jsx(jsx) = parse(
+        "<a b=\"&lt;&#65;&#x42;\">&amp;&nbsp;&unknown;</a>",
+        interner,
+    ) else {
+        panic!("expected a JSX expression");
+    };
+    let [
+        JsxAttribute::Named {
+            value: Some(JsxAttributeValue::String(value)),
+            ..
+        },
+    ] = jsx.element().attributes()
+    else {
+        panic!("expected a string attribute");
+    };
+    assert_eq!(interner.resolve_expect(value.value()).to_string(), "<AB");
+    let [JsxChild::Text(text)] = jsx.element().children() else {
+        panic!("expected a text child");
+    };
+    assert_eq!(
+        interner.resolve_expect(text.raw()).to_string(),
+        "&amp;&nbsp;&unknown;"
+    );
+    assert_eq!(
+        interner.resolve_expect(text.value()).to_string(),
+        "&\u{a0}&unknown;"
+    );
+}
+
+#[test]
+fn surrounding_expressions() {
+    check("a = <b /> < c", "a = <b /> < c");
+    check("f(<a />, <b></b>) / 2", "f(<a />, <b />) / 2");
+}
+
+#[test]
+fn invalid_jsx() {
+    check_invalid(
+        "<a></b>",
+        "expected corresponding JSX closing tag for `<a>`",
+    );
+    check_invalid(
+        "<a.b></a.c>",
+        "expected corresponding JSX closing tag for `<a.b>`",
+    );
+    check_invalid("<>a</b>", "expected corresponding JSX closing tag for `<>`");
+    check_invalid("<a>b > c</a>", "unexpected '>' in JSX text");
+    check_invalid("<a b={} />", "non-empty expression");
+    check_invalid("<a>", "abrupt end");
+    check_invalid("<a #b />", "unexpected '#' in JSX tag");
+}
+
+#[test]
+fn disabled_jsx() {
+    Parser::new(Source::from_bytes("<a />"))
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .expect_err("JSX should not parse if it is disabled");
+}
+
+#[test]
+fn invalid_factory() {
+    let error =
+        Parser::new(Source::from_bytes("<a />").with_jsx(JsxOptions::classic("h()", "Fragment")))
+            .parse_script(&Scope::new_global(), &mut Interner::default())
+            .expect_err("invalid factories should not parse");
+    assert!(error.to_string().contains("invalid JSX factory `h()`"));
+}
+
+#[test]
+fn automatic_runtime() {
+    let interner = &mut Interner::default();
+    let source = Source::from_bytes("export default <a />;\nconst b = <></>;")
+        .with_jsx(JsxOptions::automatic("preact"));
+    let module = Parser::new(source)
+        .parse_module(&Scope::new_global(), interner)
+        .expect("failed to parse JSX module");
+
+    // The runtime is imported when the module is compiled, not by an item of the module.
+    assert_eq!(module.items().items().len(), 2);
+    let entries = module.items().jsx_runtime_import_entries();
+    let imports = entries
+        .iter()
+        .map(|entry| {
+            let ImportName::Name(export) = entry.import_name() else {
+                panic!("expected a named import");
+            };
+            format!(
+                "{} as {} from {}",
+                interner.resolve_expect(export),
+                interner.resolve_expect(entry.local_name().sym()),
+                interner.resolve_expect(entry.module_request()),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        imports,
+        [
+            "jsx as _jsx from preact/jsx-runtime",
+            "jsxs as _jsxs from preact/jsx-runtime",
+            "Fragment as _Fragment from preact/jsx-runtime"
+        ]
+    );
+
+    let error = Parser::new(Source::from_bytes("<a />").with_jsx(JsxOptions::automatic("preact")))
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .expect_err("the automatic runtime should not be used in scripts");
+    assert!(error.to_string().contains("can only be used in modules"));
+}
+
+#[test]
+fn modules_without_jsx() {
+    let source = Source::from_bytes("export const a = 1;").with_jsx(JsxOptions::automatic("react"));
+    let module = Parser::new(source)
+        .parse_module(&Scope::new_global(), &mut Interner::default())
+        .expect("failed to parse module");
+    assert!(module.items().jsx_runtime_import_entries().is_empty());
+}
@@ -16,6 +16,7 @@ mod async_generator_expression;
 mod class_expression;
 mod function_expression;
 mod generator_expression;
+mod jsx;
 mod template;
 
 pub(in crate::parser) mod object_initializer;
@@ -24,7 +25,7 @@ use self::{
     array_initializer::ArrayLiteral, async_function_expression::AsyncFunctionExpression,
     async_generator_expression::AsyncGeneratorExpression, class_expression::ClassExpression,
     function_expression::FunctionExpression, generator_expression::GeneratorExpression,
-    object_initializer::ObjectLiteral,
+    jsx::JsxExpression, object_initializer::ObjectLiteral,
 };
 use crate::{
     Error,
@@ -96,6 +97,7 @@ where
     fn parse(self, cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Self::Output> {
         // TODO: tok currently consumes the token instead of peeking, so the token
         // isn't passed and consumed by parsers according to spec (EX: GeneratorExpression)
+        let jsx = cursor.jsx().is_some();
         let tok = cursor.peek(0, interner).or_abrupt()?;
         let tok_position = tok.span().start();
 
@@ -167,6 +169,11 @@ where
                 .parse(cursor, interner)?;
                 Ok(expr)
             }
+            TokenKind::Punctuator(Punctuator::LessThan) if jsx => {
+                JsxExpression::new(self.allow_yield, self.allow_await)
+                    .parse(cursor, interner)
+                    .map(Into::into)
+            }
             TokenKind::Punctuator(Punctuator::OpenBracket) => {
                 ArrayLiteral::new(self.allow_yield, self.allow_await)
                     .parse(cursor, interner)
@@ -469,6 +469,13 @@ where
     type Output = AstFunctionBody;
 
     fn parse(self, cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Self::Output> {
+        if !self.parse_full_input
+            && let Some(body) =
+                cursor.reuse_function_body(self.allow_yield.0, self.allow_await.0, interner)?
+        {
+            return Ok(body);
+        }
+
         let start = if self.parse_full_input {
             cursor
                 .peek(0, interner)?
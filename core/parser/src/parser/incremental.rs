@@ -0,0 +1,779 @@
+//! Incremental reparsing of scripts and modules.
+//!
+//! An editor reparses its source text after every edit, which is wasteful for large sources where
+//! most of the code didn't change. [`Incremental`] keeps the source text next to its AST, and
+//! [`Incremental::reparse`] only parses again the top-level items that are touched by a
+//! [`TextEdit`]:
+//!
+//! - The items before the edit are reused as they are, except for the last two of them, whose
+//!   end can depend on the tokens that follow them.
+//! - The parser stops as soon as it reaches the start of an item after the edit, and the rest of
+//!   the items are reused with their positions moved.
+//! - Inside of the reparsed items, the body of a function that was not touched by the edit is
+//!   reused instead of being parsed again.
+//!
+//! The early errors that depend on the whole source, like duplicate declarations, are checked
+//! again, and the scope analysis is run again over the resulting AST, so that it is identical
+//! to the AST of a parse from scratch. If the edited source has a syntax error, it is parsed
+//! again from scratch to report the same error.
+//!
+//! # Limitations
+//!
+//! The scope analysis is not incremental: it runs over the whole AST after every edit, and its
+//! cost grows with the size of the source, not with the size of the edit. It can't be limited to
+//! the function that encloses the edit, since its result for a function depends on the functions
+//! nested in it and on the ones around it:
+//!
+//! - A binding that is used by a nested function escapes, and has to be stored in an environment
+//!   instead of a register, so an edit of an inner function can change the bindings of all the
+//!   functions around it.
+//! - The `var` and function declarations of a script are added to the global scope, which is
+//!   created again for each parse.
+//! - The reused nodes share their scopes with the AST of the previous parse, which has to be left
+//!   untouched, so their scopes are created again.
+//!
+//! The `incremental` bench of `boa_benches` measures this with the v8 benchmark scripts, by
+//! inserting a line in the middle of each of them. A reparse is 1.5 to 5 times faster than a
+//! parse from scratch, but the scope analysis alone takes from an eighth to a quarter of a parse
+//! from scratch, which is more than half of most reparses.
+//!
+//! JSX and TypeScript sources can't be parsed incrementally.
+
+use crate::{
+    Error,
+    lexer::{InputElement, is_whitespace},
+    parser::{
+        ModuleParser, ParseResult, ScriptBody, ScriptParser, TokenParser,
+        cursor::Cursor,
+        statement::{self, ModuleItemList},
+    },
+    source::{ReadChar, UTF16Input},
+};
+use boa_ast::{
+    LinearPosition, Module, ModuleItem, PositionGroup, Relocation, Script, Spanned, StatementList,
+    StatementListItem,
+    function::FunctionBody,
+    operations::{number_tagged_templates, relocate},
+    scope::Scope,
+    visitor::{VisitWith, Visitor},
+};
+use boa_interner::Interner;
+use rustc_hash::FxHashMap;
+use std::{convert::Infallible, ops::ControlFlow, ops::Range};
+
+/// An edit of a source text, which replaces a range of it with a new text.
+///
+/// Ranges are given in UTF-16 code units, like the positions of the Language Server Protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    range: Range<usize>,
+    text: Vec<u16>,
+}
+
+impl TextEdit {
+    /// Creates a new `TextEdit` that replaces `range` with `text`.
+    #[must_use]
+    pub fn new(range: Range<usize>, text: &str) -> Self {
+        Self {
+            range,
+            text: text.encode_utf16().collect(),
+        }
+    }
+
+    /// Creates a new `TextEdit` that replaces `range` with a UTF-16 encoded `text`.
+    #[must_use]
+    pub fn from_utf16(range: Range<usize>, text: &[u16]) -> Self {
+        Self {
+            range,
+            text: text.to_vec(),
+        }
+    }
+
+    /// Creates the smallest `TextEdit` that changes the `old` text into the `new` one.
+    ///
+    /// This is useful to combine several edits into one, or when only the new text is known.
+    #[must_use]
+    pub fn between(old: &[u16], new: &[u16]) -> Self {
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        Self::from_utf16(prefix..old.len() - suffix, &new[prefix..new.len() - suffix])
+    }
+
+    /// Gets the range of the source text that is replaced, in UTF-16 code units.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Gets the UTF-16 encoded text that replaces the range.
+    #[must_use]
+    pub fn text(&self) -> &[u16] {
+        &self.text
+    }
+
+    /// Applies the edit to a source text.
+    fn apply(&self, source: &[u16]) -> Vec<u16> {
+        let mut text = Vec::with_capacity(source.len() - self.range.len() + self.text.len());
+        text.extend_from_slice(&source[..self.range.start]);
+        text.extend_from_slice(&self.text);
+        text.extend_from_slice(&source[self.range.end..]);
+        text
+    }
+}
+
+/// A [`Script`] or a [`Module`] with its source text, that can be reparsed after an edit.
+///
+/// The same [`Interner`] has to be used for all the parses of a source, since the reused nodes
+/// keep the symbols they were interned with.
+#[derive(Debug, Clone)]
+pub struct Incremental<T> {
+    ast: T,
+    source: Vec<u16>,
+    items: Vec<ItemStart>,
+    bodies: Vec<BodyStart>,
+}
+
+impl<T> Incremental<T> {
+    /// Gets the AST of the source.
+    #[must_use]
+    pub const fn ast(&self) -> &T {
+        &self.ast
+    }
+
+    /// Takes the AST of the source.
+    #[must_use]
+    pub fn into_ast(self) -> T {
+        self.ast
+    }
+
+    /// Gets the UTF-16 encoded source text.
+    #[must_use]
+    pub fn source(&self) -> &[u16] {
+        &self.source
+    }
+}
+
+impl Incremental<Script> {
+    /// Parses a [`Script`] that can be reparsed incrementally.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on any parsing error.
+    pub fn parse_script(source: &str, scope: &Scope, interner: &mut Interner) -> ParseResult<Self> {
+        parse(source.encode_utf16().collect(), None, scope, interner)
+    }
+
+    /// Parses the script again after an edit of its source text, reusing the parts of the AST
+    /// that are not affected by the edit.
+    ///
+    /// The result is the same as parsing the new source text from scratch with
+    /// [`Incremental::parse_script`], and `self` is left untouched. `scope` has to be a new
+    /// global scope, like for a parse from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on any parsing error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of the bounds of the source text.
+    pub fn reparse(
+        &self,
+        edit: &TextEdit,
+        scope: &Scope,
+        interner: &mut Interner,
+    ) -> ParseResult<Self> {
+        parse(
+            edit.apply(&self.source),
+            Some((self, edit)),
+            scope,
+            interner,
+        )
+    }
+}
+
+impl Incremental<Module> {
+    /// Parses a [`Module`] that can be reparsed incrementally.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on any parsing error.
+    pub fn parse_module(source: &str, scope: &Scope, interner: &mut Interner) -> ParseResult<Self> {
+        parse(source.encode_utf16().collect(), None, scope, interner)
+    }
+
+    /// Parses the module again after an edit of its source text, reusing the parts of the AST
+    /// that are not affected by the edit.
+    ///
+    /// The result is the same as parsing the new source text from scratch with
+    /// [`Incremental::parse_module`], and `self` is left untouched. `scope` has to be a new
+    /// global scope, like for a parse from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on any parsing error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of the bounds of the source text.
+    pub fn reparse(
+        &self,
+        edit: &TextEdit,
+        scope: &Scope,
+        interner: &mut Interner,
+    ) -> ParseResult<Self> {
+        parse(
+            edit.apply(&self.source),
+            Some((self, edit)),
+            scope,
+            interner,
+        )
+    }
+}
+
+/// The goal symbols that can be parsed incrementally.
+trait Goal: Sized + VisitWith {
+    /// The type of the top-level items.
+    type Item: Clone + VisitWith;
+
+    /// Gets the top-level items.
+    fn items(&self) -> &[Self::Item];
+
+    /// Moves the positions of a reused top-level item.
+    fn relocate_item(item: &mut Self::Item, relocation: Relocation);
+
+    /// Returns `true` if the goal is strict mode code.
+    fn strict(&self) -> bool;
+
+    /// Gets the number of top-level items that can be directives, which decide the strict mode
+    /// of the rest of the items.
+    fn prologue_len(&self) -> usize;
+
+    /// Parses the top-level items starting at the cursor, returning them along with their strict
+    /// mode and the end of their list.
+    fn parse_items<R: ReadChar>(
+        cursor: &mut Cursor<R>,
+        previous: Option<&Self>,
+        interner: &mut Interner,
+    ) -> ParseResult<(Vec<Self::Item>, bool, LinearPosition)>;
+
+    /// Creates the goal from its top-level items.
+    fn new(items: Vec<Self::Item>, strict: bool, linear_pos_end: LinearPosition) -> Self;
+
+    /// Gets the end of the list of top-level items.
+    fn linear_pos_end(&self) -> LinearPosition;
+
+    /// Checks the early errors that depend on all the top-level items.
+    fn check<R: ReadChar>(&self, cursor: &mut Cursor<R>, interner: &Interner) -> ParseResult<()>;
+
+    /// Analyzes the scopes of the goal.
+    fn analyze_scope(&mut self, scope: &Scope, interner: &Interner) -> Result<(), &'static str>;
+}
+
+impl Goal for Script {
+    type Item = StatementListItem;
+
+    fn items(&self) -> &[Self::Item] {
+        self.statements().statements()
+    }
+
+    fn relocate_item(item: &mut Self::Item, relocation: Relocation) {
+        relocate(item, relocation);
+    }
+
+    fn strict(&self) -> bool {
+        Self::strict(self)
+    }
+
+    fn prologue_len(&self) -> usize {
+        self.statements()
+            .statements()
+            .iter()
+            .take_while(|item| {
+                matches!(
+                    item,
+                    StatementListItem::Statement(statement)
+                        if matches!(
+                            statement.as_ref(),
+                            boa_ast::Statement::Expression(boa_ast::Expression::Literal(literal))
+                                if literal.as_string().is_some()
+                        )
+                )
+            })
+            .count()
+    }
+
+    fn parse_items<R: ReadChar>(
+        cursor: &mut Cursor<R>,
+        previous: Option<&Self>,
+        interner: &mut Interner,
+    ) -> ParseResult<(Vec<Self::Item>, bool, LinearPosition)> {
+        let strict = previous.is_some_and(Self::strict);
+        cursor.set_strict(strict);
+        let (list, _end) =
+            statement::StatementList::new(false, false, false, &[], previous.is_none(), strict)
+                .parse(cursor, interner)?;
+        Ok((
+            list.statements().to_vec(),
+            list.strict(),
+            list.linear_pos_end(),
+        ))
+    }
+
+    fn new(items: Vec<Self::Item>, strict: bool, linear_pos_end: LinearPosition) -> Self {
+        Self::new(StatementList::new(items, linear_pos_end, strict))
+    }
+
+    fn linear_pos_end(&self) -> LinearPosition {
+        self.statements().linear_pos_end()
+    }
+
+    fn check<R: ReadChar>(&self, cursor: &mut Cursor<R>, interner: &Interner) -> ParseResult<()> {
+        ScriptBody::new(true, false, false).check(self.statements(), cursor, interner)?;
+        ScriptParser::check(self, cursor, interner)
+    }
+
+    fn analyze_scope(&mut self, scope: &Scope, interner: &Interner) -> Result<(), &'static str> {
+        Self::analyze_scope(self, scope, interner)
+    }
+}
+
+impl Goal for Module {
+    type Item = ModuleItem;
+
+    fn items(&self) -> &[Self::Item] {
+        Self::items(self).items()
+    }
+
+    fn relocate_item(item: &mut Self::Item, relocation: Relocation) {
+        relocate(item, relocation);
+    }
+
+    fn strict(&self) -> bool {
+        true
+    }
+
+    fn prologue_len(&self) -> usize {
+        0
+    }
+
+    fn parse_items<R: ReadChar>(
+        cursor: &mut Cursor<R>,
+        _previous: Option<&Self>,
+        interner: &mut Interner,
+    ) -> ParseResult<(Vec<Self::Item>, bool, LinearPosition)> {
+        cursor.set_module();
+        let list = ModuleItemList.parse(cursor, interner)?;
+        Ok((list.items().to_vec(), true, LinearPosition::default()))
+    }
+
+    fn new(items: Vec<Self::Item>, _strict: bool, _linear_pos_end: LinearPosition) -> Self {
+        Self::new(items.into())
+    }
+
+    fn linear_pos_end(&self) -> LinearPosition {
+        LinearPosition::default()
+    }
+
+    fn check<R: ReadChar>(&self, cursor: &mut Cursor<R>, interner: &Interner) -> ParseResult<()> {
+        ModuleParser::check(self, cursor, interner)
+    }
+
+    fn analyze_scope(&mut self, scope: &Scope, interner: &Interner) -> Result<(), &'static str> {
+        Self::analyze_scope(self, scope, interner)
+    }
+}
+
+/// The start of a top-level item that was parsed, with the goal symbol its first token was
+/// lexed with.
+#[derive(Debug, Clone, Copy)]
+struct ItemStart {
+    start: PositionGroup,
+    goal: InputElement,
+}
+
+impl ItemStart {
+    /// Moves the start of the item.
+    fn relocate(self, relocation: &Relocation) -> Self {
+        Self {
+            start: relocate_group(relocation, self.start),
+            ..self
+        }
+    }
+}
+
+/// The start of a function body that was parsed, with the context it was parsed in.
+#[derive(Debug, Clone, Copy)]
+struct BodyStart {
+    start: PositionGroup,
+    allow_yield: bool,
+    allow_await: bool,
+    strict: bool,
+}
+
+impl BodyStart {
+    /// Moves the start of the body.
+    fn relocate(self, relocation: &Relocation) -> Self {
+        Self {
+            start: relocate_group(relocation, self.start),
+            ..self
+        }
+    }
+}
+
+/// A function body of the previous parse that can be reused.
+#[derive(Debug)]
+struct Candidate {
+    body: FunctionBody,
+    start: BodyStart,
+    end: LinearPosition,
+}
+
+/// The state of an incremental parse, which is kept by the cursor.
+#[derive(Debug, Default)]
+pub(super) struct Reuse {
+    /// Tracks if the top-level list of items was entered.
+    top_level: bool,
+
+    /// The strict mode of the previous parse, which the top-level items must have to be reused.
+    strict: bool,
+
+    /// The new linear positions of the reusable top-level items, mapped to their index.
+    stops: FxHashMap<usize, usize>,
+
+    /// The index of the first reused top-level item, and its new position.
+    stopped: Option<(usize, PositionGroup)>,
+
+    /// The starts of the top-level items parsed so far.
+    items: Vec<ItemStart>,
+
+    /// The reusable function bodies, by the new linear position of their start.
+    candidates: FxHashMap<usize, Candidate>,
+
+    /// The function bodies of the previous parse.
+    previous_bodies: Vec<BodyStart>,
+
+    /// The function bodies parsed or reused so far.
+    bodies: Vec<BodyStart>,
+}
+
+impl Reuse {
+    /// Returns `true` the first time it's called, when the top-level list of items is entered.
+    pub(super) fn enter_top_level(&mut self) -> bool {
+        !std::mem::replace(&mut self.top_level, true)
+    }
+
+    /// Returns `true` if the top-level item starting at `start` is reused, which ends the parse.
+    ///
+    /// Items can only be reused if the parse has the same strict mode as the previous one, and
+    /// if the directives that decide it were all parsed, which `can_stop` tells.
+    pub(super) fn stop_before_item(
+        &mut self,
+        start: PositionGroup,
+        goal: InputElement,
+        strict: bool,
+        can_stop: bool,
+    ) -> bool {
+        if can_stop
+            && strict == self.strict
+            && let Some(&index) = self.stops.get(&start.linear_position().pos())
+        {
+            self.stopped = Some((index, start));
+            return true;
+        }
+        self.items.push(ItemStart { start, goal });
+        false
+    }
+
+    /// Records the start of a function body, returning the body of the previous parse that can
+    /// be reused in its place, along with the relocation of its positions and its previous end.
+    pub(super) fn function_body(
+        &mut self,
+        start: PositionGroup,
+        allow_yield: bool,
+        allow_await: bool,
+        strict: bool,
+    ) -> Option<(FunctionBody, Relocation, LinearPosition)> {
+        self.bodies.push(BodyStart {
+            start,
+            allow_yield,
+            allow_await,
+            strict,
+        });
+        let candidate = self.candidates.remove(&start.linear_position().pos())?;
+        if (
+            candidate.start.allow_yield,
+            candidate.start.allow_await,
+            candidate.start.strict,
+        ) != (allow_yield, allow_await, strict)
+        {
+            return None;
+        }
+        let relocation = Relocation::new(candidate.start.start, start);
+        Some((candidate.body, relocation, candidate.end))
+    }
+
+    /// Records the function bodies nested in a reused function body, which ends at `end` in the
+    /// previous parse.
+    pub(super) fn reuse_nested_bodies(&mut self, relocation: &Relocation, end: LinearPosition) {
+        let start = relocation.from().linear_position();
+        let first = self
+            .previous_bodies
+            .partition_point(|body| body.start.linear_position() <= start);
+        self.bodies.extend(
+            self.previous_bodies[first..]
+                .iter()
+                .take_while(|body| body.start.linear_position() < end)
+                .map(|body| body.relocate(relocation)),
+        );
+    }
+}
+
+/// Parses a source text, reusing the nodes of a previous parse if given.
+fn parse<T: Goal>(
+    source: Vec<u16>,
+    previous: Option<(&Incremental<T>, &TextEdit)>,
+    scope: &Scope,
+    interner: &mut Interner,
+) -> ParseResult<Incremental<T>> {
+    // The errors of a partial parse can be different from the ones of a parse from scratch,
+    // since the reused nodes are not checked again, so the source is parsed again to report them.
+    let (mut ast, items, bodies) = match previous {
+        Some((previous, edit)) => match parse_edited(&source, previous, edit, interner) {
+            Ok(parsed) => parsed,
+            Err(_) => parse_full(&source, interner)?,
+        },
+        None => parse_full(&source, interner)?,
+    };
+
+    number_tagged_templates(&mut ast);
+    ast.analyze_scope(scope, interner)
+        .map_err(Error::scope_analysis)?;
+
+    Ok(Incremental {
+        ast,
+        source,
+        items,
+        bodies,
+    })
+}
+
+/// Parses a source text from scratch, recording the starts of its items and function bodies.
+fn parse_full<T: Goal>(
+    source: &[u16],
+    interner: &mut Interner,
+) -> ParseResult<(T, Vec<ItemStart>, Vec<BodyStart>)> {
+    let mut cursor = Cursor::new(UTF16Input::new(source));
+    cursor.set_goal(InputElement::HashbangOrRegExp);
+    cursor.set_reuse(Reuse::default());
+    let (items, strict, linear_pos_end) = T::parse_items(&mut cursor, None, interner)?;
+    let reuse = cursor.take_reuse().unwrap_or_default();
+    let ast = T::new(items, strict, linear_pos_end);
+    ast.check(&mut cursor, interner)?;
+    Ok((ast, reuse.items, reuse.bodies))
+}
+
+/// Parses an edited source text, reusing the nodes of the previous parse that are not touched by
+/// the edit.
+fn parse_edited<T: Goal>(
+    source: &[u16],
+    previous: &Incremental<T>,
+    edit: &TextEdit,
+    interner: &mut Interner,
+) -> ParseResult<(T, Vec<ItemStart>, Vec<BodyStart>)> {
+    let old = &previous.ast;
+    let edit_start = edit.range.start;
+    let edit_end = edit.range.end;
+    let new_edit_end = edit_start + edit.text.len();
+
+    // The two items before the edited one are parsed again, since their end can depend on the
+    // tokens after them.
+    let touched = previous
+        .items
+        .partition_point(|item| item.start.linear_position().pos() <= edit_start);
+    let mut first = touched.saturating_sub(3);
+    if first <= old.prologue_len() {
+        first = 0;
+    }
+
+    let shift =
+        |position: LinearPosition| LinearPosition::new(position.pos() - edit_end + new_edit_end);
+
+    let mut reuse = Reuse {
+        strict: old.strict(),
+        ..Reuse::default()
+    };
+    for (index, item) in previous.items.iter().enumerate().skip(first + 1) {
+        let start = item.start.linear_position();
+        if start.pos() >= edit_end {
+            reuse.stops.insert(shift(start).pos(), index);
+        }
+    }
+
+    let mut collector = Candidates {
+        bodies: &previous.bodies,
+        candidates: &mut reuse.candidates,
+        source,
+        edit_start,
+        edit_end,
+        shift: &shift,
+    };
+    for item in &old.items()[first..] {
+        let _ = item.visit_with(&mut collector);
+    }
+    reuse.previous_bodies.clone_from(&previous.bodies);
+
+    let start = previous.items.get(first).copied().filter(|_| first > 0);
+    let mut cursor = if let Some(ItemStart { start, goal }) = start {
+        let linear = start.linear_position().pos();
+        let mut cursor = Cursor::new(UTF16Input::new(&source[linear..]));
+        cursor.resume(start, &source[..linear]);
+        cursor.set_goal(goal);
+        cursor
+    } else {
+        let mut cursor = Cursor::new(UTF16Input::new(source));
+        cursor.set_goal(InputElement::HashbangOrRegExp);
+        cursor
+    };
+    cursor.set_reuse(reuse);
+
+    let (new_items, strict, mut linear_pos_end) =
+        T::parse_items(&mut cursor, start.map(|_| old), interner)?;
+    let reuse = cursor.take_reuse().unwrap_or_default();
+
+    let first_body = start.map_or(0, |start| {
+        previous
+            .bodies
+            .partition_point(|body| body.start.linear_position() < start.start.linear_position())
+    });
+    let mut items = old.items()[..first].to_vec();
+    items.extend(new_items);
+    let mut starts = previous.items[..first].to_vec();
+    starts.extend(reuse.items);
+    let mut bodies = previous.bodies[..first_body].to_vec();
+    bodies.extend(reuse.bodies);
+
+    if let Some((index, to)) = reuse.stopped {
+        let relocation = Relocation::new(previous.items[index].start, to);
+        items.extend(old.items()[index..].iter().map(|item| {
+            let mut item = item.clone();
+            T::relocate_item(&mut item, relocation);
+            item
+        }));
+        starts.extend(
+            previous.items[index..]
+                .iter()
+                .map(|start| start.relocate(&relocation)),
+        );
+        let from = relocation.from().linear_position();
+        let first_body = previous
+            .bodies
+            .partition_point(|body| body.start.linear_position() < from);
+        bodies.extend(
+            previous.bodies[first_body..]
+                .iter()
+                .map(|body| body.relocate(&relocation)),
+        );
+        linear_pos_end = relocation.linear_position(old.linear_pos_end());
+    }
+
+    let ast = T::new(items, strict, linear_pos_end);
+    ast.check(&mut cursor, interner)?;
+    Ok((ast, starts, bodies))
+}
+
+/// Moves a position group.
+fn relocate_group(relocation: &Relocation, group: PositionGroup) -> PositionGroup {
+    PositionGroup::new(
+        relocation.position(group.position()),
+        relocation.linear_position(group.linear_position()),
+    )
+}
+
+/// Collects the function bodies of the previous parse that are not touched by an edit.
+struct Candidates<'a, F> {
+    bodies: &'a [BodyStart],
+    candidates: &'a mut FxHashMap<usize, Candidate>,
+    source: &'a [u16],
+    edit_start: usize,
+    edit_end: usize,
+    shift: &'a F,
+}
+
+impl<'ast, F> Visitor<'ast> for Candidates<'_, F>
+where
+    F: Fn(LinearPosition) -> LinearPosition,
+{
+    type BreakTy = Infallible;
+
+    fn visit_function_body(&mut self, node: &'ast FunctionBody) -> ControlFlow<Self::BreakTy> {
+        if let Ok(index) = self
+            .bodies
+            .binary_search_by_key(&node.span().start(), |body| body.start.position())
+        {
+            let start = self.bodies[index];
+            let linear_start = start.start.linear_position();
+            let end = node.linear_pos_end();
+            let new = if end.pos() <= self.edit_start {
+                Some((linear_start, end))
+            } else if linear_start.pos() >= self.edit_end {
+                Some(((self.shift)(linear_start), (self.shift)(end)))
+            } else {
+                None
+            };
+            if let Some((new_start, new_end)) = new
+                && linear_start < end
+                && !followed_by_slash(self.source, new_end.pos())
+            {
+                self.candidates.insert(
+                    new_start.pos(),
+                    Candidate {
+                        body: node.clone(),
+                        start,
+                        end,
+                    },
+                );
+            }
+        }
+        node.visit_with(self)
+    }
+}
+
+/// Returns `true` if the next token after `index` could start with a `/`, whose meaning depends
+/// on the tokens before it.
+fn followed_by_slash(source: &[u16], mut index: usize) -> bool {
+    loop {
+        match source.get(index).copied().map(u32::from) {
+            Some(0x000A | 0x000D | 0x2028 | 0x2029) => index += 1,
+            Some(ch) if is_whitespace(ch) => index += 1,
+            Some(0x002F) => match source.get(index + 1) {
+                Some(0x002F) => {
+                    while source
+                        .get(index)
+                        .is_some_and(|&ch| !matches!(ch, 0x000A | 0x000D | 0x2028 | 0x2029))
+                    {
+                        index += 1;
+                    }
+                }
+                Some(0x002A) => {
+                    let Some(end) = source[index + 2..]
+                        .windows(2)
+                        .position(|window| window == [0x002A, 0x002F])
+                    else {
+                        return true;
+                    };
+                    index += end + 4;
+                }
+                _ => return true,
+            },
+            // HTML-like comments.
+            Some(0x002D | 0x003C) => return true,
+            _ => return false,
+        }
+    }
+}
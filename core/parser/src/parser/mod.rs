@@ -5,14 +5,15 @@ mod expression;
 mod statement;
 
 pub(crate) mod function;
+pub mod incremental;
 
 #[cfg(test)]
 mod tests;
 
 use crate::{
     Error, Source,
-    error::ParseResult,
-    lexer::{Error as LexError, InputElement},
+    error::{ParseResult, Recovered},
+    lexer::{Error as LexError, InputElement, Token, TriviaTable},
     parser::{
         cursor::Cursor,
         function::{FormalParameters, FunctionStatementList},
@@ -20,16 +21,17 @@ use crate::{
     source::ReadChar,
 };
 use boa_ast::{
-    Position, StatementList,
+    Position, Span, Spanned, StatementList,
+    expression::Identifier,
     function::{FormalParameterList, FunctionBody},
     operations::{
         ContainsSymbol, all_private_identifiers_valid, check_labels, contains,
-        contains_invalid_object_literal, lexically_declared_names, var_declared_names,
+        contains_invalid_object_literal, lexically_declared_identifiers, var_declared_identifiers,
     },
     scope::Scope,
 };
 use boa_interner::{Interner, Sym};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::path::Path;
 
 use self::statement::ModuleItemList;
@@ -123,17 +125,33 @@ pub struct Parser<'a, R> {
     path: Option<&'a Path>,
     /// Cursor of the parser, pointing to the lexer and used to get tokens for the parser.
     cursor: Cursor<R>,
+    /// If the source is TypeScript code that has not been stripped yet.
+    #[cfg(feature = "typescript")]
+    typescript: bool,
 }
 
 impl<'a, R: ReadChar> Parser<'a, R> {
     /// Create a new `Parser` with a `Source` as the input to parse.
     pub fn new(source: Source<'a, R>) -> Self {
+        let mut cursor = Cursor::new(source.reader);
+        cursor.set_jsx(source.jsx);
         Self {
             path: source.path,
-            cursor: Cursor::new(source.reader),
+            cursor,
+            #[cfg(feature = "typescript")]
+            typescript: source.typescript,
         }
     }
 
+    /// Erases the TypeScript syntax of the source before parsing it, if it is TypeScript code.
+    #[cfg(feature = "typescript")]
+    fn strip_typescript(&mut self, module: bool) -> ParseResult<()> {
+        if std::mem::take(&mut self.typescript) {
+            self.cursor.strip_typescript(module)?;
+        }
+        Ok(())
+    }
+
     /// Parse the full input as a [ECMAScript Script][spec] into the boa AST representation without source text.
     /// The resulting `Script` can be compiled into boa bytecode and executed in the boa vm.
     ///
@@ -163,6 +181,8 @@ impl<'a, R: ReadChar> Parser<'a, R> {
         scope: &Scope,
         interner: &mut Interner,
     ) -> ParseResult<ScriptParseOutput> {
+        #[cfg(feature = "typescript")]
+        self.strip_typescript(false)?;
         self.cursor.set_goal(InputElement::HashbangOrRegExp);
         let (mut ast, source) = ScriptParser::new(false).parse(&mut self.cursor, interner)?;
         if let Err(reason) = ast.analyze_scope(scope, interner) {
@@ -206,6 +226,8 @@ impl<'a, R: ReadChar> Parser<'a, R> {
     where
         R: ReadChar,
     {
+        #[cfg(feature = "typescript")]
+        self.strip_typescript(true)?;
         self.cursor.set_goal(InputElement::HashbangOrRegExp);
         let (mut module, source) = ModuleParser.parse(&mut self.cursor, interner)?;
         if let Err(reason) = module.analyze_scope(scope, interner) {
@@ -214,6 +236,78 @@ impl<'a, R: ReadChar> Parser<'a, R> {
         Ok((module, source))
     }
 
+    /// Parse the full input as a [ECMAScript Script][spec], recovering from syntax errors.
+    ///
+    /// Instead of stopping at the first syntax error, the parser skips the statement or
+    /// declaration that contains it and continues after the next `;`, closing `}` or keyword that
+    /// starts a statement on a new line. The skipped code is replaced by
+    /// [`Statement::Error`][boa_ast::Statement::Error] nodes in the resulting `Script`, which
+    /// should not be compiled if any error was found.
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-Script
+    pub fn parse_script_recovering(
+        &mut self,
+        scope: &Scope,
+        interner: &mut Interner,
+    ) -> Recovered<boa_ast::Script> {
+        self.cursor.set_error_recovery();
+        #[cfg(feature = "typescript")]
+        if let Err(error) = self.strip_typescript(false) {
+            return self.recover(Err(error), |_| Ok(()));
+        }
+        self.cursor.set_goal(InputElement::HashbangOrRegExp);
+        let script = ScriptParser::new(false)
+            .parse(&mut self.cursor, interner)
+            .map(|(script, _)| script);
+        self.recover(script, |script| script.analyze_scope(scope, interner))
+    }
+
+    /// Parse the full input as an [ECMAScript Module][spec], recovering from syntax errors.
+    ///
+    /// See [`Parser::parse_script_recovering`] for how the parser recovers from the errors.
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-Module
+    pub fn parse_module_recovering(
+        &mut self,
+        scope: &Scope,
+        interner: &mut Interner,
+    ) -> Recovered<boa_ast::Module> {
+        self.cursor.set_error_recovery();
+        #[cfg(feature = "typescript")]
+        if let Err(error) = self.strip_typescript(true) {
+            return self.recover(Err(error), |_| Ok(()));
+        }
+        self.cursor.set_goal(InputElement::HashbangOrRegExp);
+        let module = ModuleParser
+            .parse(&mut self.cursor, interner)
+            .map(|(module, _)| module);
+        self.recover(module, |module| module.analyze_scope(scope, interner))
+    }
+
+    /// Collects the errors of a parse with error recovery, falling back to an empty tree if the
+    /// parser could not recover from an error.
+    fn recover<T: Default>(
+        &mut self,
+        result: ParseResult<T>,
+        analyze_scope: impl FnOnce(&mut T) -> Result<(), &'static str>,
+    ) -> Recovered<T> {
+        let mut ast = result.unwrap_or_else(|error| {
+            self.report_recovered(error);
+            T::default()
+        });
+        if let Err(reason) = analyze_scope(&mut ast) {
+            self.report_recovered(Error::scope_analysis(reason));
+        }
+        Recovered::new(ast, self.cursor.take_recovered_errors())
+    }
+
+    /// Reports an error to the cursor while error recovery is enabled.
+    fn report_recovered(&mut self, error: Error) {
+        self.cursor
+            .report(error)
+            .expect("error recovery should be enabled");
+    }
+
     /// [`19.2.1.1 PerformEval ( x, strictCaller, direct )`][spec]
     ///
     /// Parses the source text input of an `eval` call.
@@ -284,6 +378,50 @@ impl<R> Parser<'_, R> {
         self.cursor.set_json_parse(true);
     }
 
+    /// Records the whitespace, line terminators and comments of the source while parsing.
+    ///
+    /// This is disabled by default. The recorded trivia can be retrieved with
+    /// [`Parser::take_trivia`] after parsing, and the comments that belong to a node of the AST
+    /// can be found with its span.
+    pub fn set_collect_trivia(&mut self)
+    where
+        R: ReadChar,
+    {
+        self.cursor.set_collect_trivia();
+    }
+
+    /// Takes the trivia recorded while parsing.
+    pub fn take_trivia(&mut self) -> TriviaTable
+    where
+        R: ReadChar,
+    {
+        self.cursor.take_trivia()
+    }
+
+    /// Records the tokens of the source while parsing, with the goal symbols chosen by the
+    /// parser.
+    ///
+    /// This is disabled by default. Unlike a [`TokenStream`](crate::lexer::TokenStream), the
+    /// recorded tokens always tell regular expressions and divisions apart, and include JSX.
+    /// The recorded tokens can be retrieved with [`Parser::take_tokens`] after parsing.
+    pub fn set_collect_tokens(&mut self)
+    where
+        R: ReadChar,
+    {
+        self.cursor.set_collect_tokens();
+    }
+
+    /// Takes the tokens recorded while parsing, in source order.
+    ///
+    /// A template literal with substitutions is split into a token for every part, where the
+    /// parts after the first start with the `}` closing the previous substitution.
+    pub fn take_tokens(&mut self) -> Vec<Token>
+    where
+        R: ReadChar,
+    {
+        self.cursor.take_tokens()
+    }
+
     /// Set the unique identifier for the parser.
     pub fn set_identifier(&mut self, identifier: u32)
     where
@@ -322,30 +460,41 @@ where
         let stmts =
             ScriptBody::new(true, cursor.strict(), self.direct_eval).parse(cursor, interner)?;
         let script = boa_ast::Script::new(stmts);
+        Self::check(&script, cursor, interner)?;
 
+        let source = cursor.take_source();
+        Ok((script, source))
+    }
+}
+
+impl ScriptParser {
+    /// Checks the early errors of the declarations of a script.
+    fn check<R: ReadChar>(
+        script: &boa_ast::Script,
+        cursor: &mut Cursor<R>,
+        interner: &Interner,
+    ) -> ParseResult<()> {
         // It is a Syntax Error if the LexicallyDeclaredNames of ScriptBody contains any duplicate entries.
-        let mut lexical_names = FxHashSet::default();
-        for name in lexically_declared_names(&script) {
-            if !lexical_names.insert(name) {
-                return Err(Error::general(
-                    "lexical name declared multiple times",
-                    Position::new(1, 1),
-                ));
+        let mut lexical_names = FxHashMap::default();
+        for (ident, _) in lexically_declared_identifiers(script) {
+            if let Some(&previous) = lexical_names.get(&ident.sym()) {
+                cursor.report(redeclaration(ident, previous, interner))?;
+            } else {
+                lexical_names.insert(ident.sym(), ident.span());
             }
         }
 
         // It is a Syntax Error if any element of the LexicallyDeclaredNames of ScriptBody also occurs in the VarDeclaredNames of ScriptBody.
-        for name in var_declared_names(&script) {
-            if lexical_names.contains(&name) {
-                return Err(Error::general(
-                    "lexical name declared multiple times",
-                    Position::new(1, 1),
-                ));
+        let mut var_names = FxHashSet::default();
+        for ident in var_declared_identifiers(script) {
+            if let Some(&previous) = lexical_names.get(&ident.sym())
+                && var_names.insert(ident.sym())
+            {
+                cursor.report(redeclaration(ident, previous, interner))?;
             }
         }
 
-        let source = cursor.take_source();
-        Ok((script, source))
+        Ok(())
     }
 }
 
@@ -390,50 +539,62 @@ where
             self.strict,
         )
         .parse(cursor, interner)?;
+        self.check(&body, cursor, interner)?;
+        Ok(body)
+    }
+}
 
+impl ScriptBody {
+    /// Checks the early errors of the statements of a script.
+    fn check<R: ReadChar>(
+        self,
+        body: &StatementList,
+        cursor: &mut Cursor<R>,
+        interner: &Interner,
+    ) -> ParseResult<()> {
         if !self.direct_eval {
             // It is a Syntax Error if StatementList Contains super unless the source text containing super is eval
             // code that is being processed by a direct eval.
             // Additional early error rules for super within direct eval are defined in 19.2.1.1.
-            if contains(&body, ContainsSymbol::Super) {
-                return Err(Error::general("invalid super usage", Position::new(1, 1)));
+            if contains(body, ContainsSymbol::Super) {
+                cursor.report(Error::general("invalid super usage", Position::new(1, 1)))?;
             }
             // It is a Syntax Error if StatementList Contains NewTarget unless the source text containing NewTarget
             // is eval code that is being processed by a direct eval.
             // Additional early error rules for NewTarget in direct eval are defined in 19.2.1.1.
-            if contains(&body, ContainsSymbol::NewTarget) {
-                return Err(Error::general(
+            if contains(body, ContainsSymbol::NewTarget) {
+                cursor.report(Error::general(
                     "invalid new.target usage",
                     Position::new(1, 1),
-                ));
+                ))?;
             }
 
             // It is a Syntax Error if AllPrivateIdentifiersValid of StatementList with
             // argument « » is false unless the source text containing ScriptBody is
             // eval code that is being processed by a direct eval.
-            if !all_private_identifiers_valid(&body, Vec::new()) {
-                return Err(Error::general(
+            if !all_private_identifiers_valid(body, Vec::new()) {
+                cursor.report(Error::general(
                     "invalid private identifier usage",
                     Position::new(1, 1),
-                ));
+                ))?;
             }
         }
 
-        if let Err(error) = check_labels(&body) {
-            return Err(Error::lex(LexError::Syntax(
+        if let Err(error) = check_labels(body) {
+            cursor.report(Error::lex(LexError::Syntax(
                 error.message(interner).into(),
                 Position::new(1, 1),
-            )));
+            )))?;
         }
 
-        if contains_invalid_object_literal(&body) {
-            return Err(Error::lex(LexError::Syntax(
+        if contains_invalid_object_literal(body) {
+            cursor.report(Error::lex(LexError::Syntax(
                 "invalid object literal in script statement list".into(),
                 Position::new(1, 1),
-            )));
+            )))?;
         }
 
-        Ok(body)
+        Ok(())
     }
 }
 
@@ -456,32 +617,41 @@ where
         cursor.set_module();
 
         let module = boa_ast::Module::new(ModuleItemList.parse(cursor, interner)?);
+        Self::check(&module, cursor, interner)?;
+
+        let source = cursor.take_source();
+        Ok((module, source))
+    }
+}
 
+impl ModuleParser {
+    /// Checks the early errors of the items of a module.
+    fn check<R: ReadChar>(
+        module: &boa_ast::Module,
+        cursor: &mut Cursor<R>,
+        interner: &Interner,
+    ) -> ParseResult<()> {
         // It is a Syntax Error if the LexicallyDeclaredNames of ModuleItemList contains any duplicate entries.
-        let mut bindings = FxHashSet::default();
-        for name in lexically_declared_names(&module) {
-            if !bindings.insert(name) {
-                return Err(Error::general(
-                    format!(
-                        "lexical name `{}` declared multiple times",
-                        interner.resolve_expect(name)
-                    ),
-                    Position::new(1, 1),
-                ));
+        let mut bindings = FxHashMap::default();
+        for (ident, _) in lexically_declared_identifiers(module) {
+            if let Some(&previous) = bindings.get(&ident.sym()) {
+                cursor.report(redeclaration(ident, previous, interner))?;
+            } else {
+                bindings.insert(ident.sym(), ident.span());
             }
         }
 
         // It is a Syntax Error if any element of the LexicallyDeclaredNames of ModuleItemList also occurs in the
         // VarDeclaredNames of ModuleItemList.
-        for name in var_declared_names(&module) {
-            if !bindings.insert(name) {
-                return Err(Error::general(
-                    format!(
-                        "lexical name `{}` declared multiple times",
-                        interner.resolve_expect(name)
-                    ),
-                    Position::new(1, 1),
-                ));
+        let mut var_names = FxHashSet::default();
+        for ident in var_declared_identifiers(module) {
+            if !var_names.insert(ident.sym()) {
+                continue;
+            }
+            if let Some(&previous) = bindings.get(&ident.sym()) {
+                cursor.report(redeclaration(ident, previous, interner))?;
+            } else {
+                bindings.insert(ident.sym(), ident.span());
             }
         }
 
@@ -490,13 +660,13 @@ where
             let mut exported_names = FxHashSet::default();
             for name in module.items().exported_names() {
                 if !exported_names.insert(name) {
-                    return Err(Error::general(
+                    cursor.report(Error::general(
                         format!(
                             "exported name `{}` declared multiple times",
                             interner.resolve_expect(name)
                         ),
                         Position::new(1, 1),
-                    ));
+                    ))?;
                 }
             }
         }
@@ -504,56 +674,67 @@ where
         // It is a Syntax Error if any element of the ExportedBindings of ModuleItemList does not also occur in either
         // the VarDeclaredNames of ModuleItemList, or the LexicallyDeclaredNames of ModuleItemList.
         for name in module.items().exported_bindings() {
-            if !bindings.contains(&name) {
-                return Err(Error::general(
+            if !bindings.contains_key(&name) {
+                cursor.report(Error::general(
                     format!(
                         "could not find the exported binding `{}` in the declared names of the module",
                         interner.resolve_expect(name)
                     ),
                     Position::new(1, 1),
-                ));
+                ))?;
             }
         }
 
         // It is a Syntax Error if ModuleItemList Contains super.
-        if contains(&module, ContainsSymbol::Super) {
-            return Err(Error::general(
+        if contains(module, ContainsSymbol::Super) {
+            cursor.report(Error::general(
                 "module cannot contain `super` on the top-level",
                 Position::new(1, 1),
-            ));
+            ))?;
         }
 
         // It is a Syntax Error if ModuleItemList Contains NewTarget.
-        if contains(&module, ContainsSymbol::NewTarget) {
-            return Err(Error::general(
+        if contains(module, ContainsSymbol::NewTarget) {
+            cursor.report(Error::general(
                 "module cannot contain `new.target` on the top-level",
                 Position::new(1, 1),
-            ));
+            ))?;
         }
 
         // It is a Syntax Error if ContainsDuplicateLabels of ModuleItemList with argument « » is true.
         // It is a Syntax Error if ContainsUndefinedBreakTarget of ModuleItemList with argument « » is true.
         // It is a Syntax Error if ContainsUndefinedContinueTarget of ModuleItemList with arguments « » and « » is true.
-        check_labels(&module).map_err(|error| {
-            Error::lex(LexError::Syntax(
+        if let Err(error) = check_labels(module) {
+            cursor.report(Error::lex(LexError::Syntax(
                 error.message(interner).into(),
                 Position::new(1, 1),
-            ))
-        })?;
+            )))?;
+        }
 
         // It is a Syntax Error if AllPrivateIdentifiersValid of ModuleItemList with argument « » is false.
-        if !all_private_identifiers_valid(&module, Vec::new()) {
-            return Err(Error::general(
+        if !all_private_identifiers_valid(module, Vec::new()) {
+            cursor.report(Error::general(
                 "invalid private identifier usage",
                 Position::new(1, 1),
-            ));
+            ))?;
         }
 
-        let source = cursor.take_source();
-        Ok((module, source))
+        Ok(())
     }
 }
 
+/// Creates the error for a lexical name that was declared more than once.
+fn redeclaration(ident: Identifier, previous: Span, interner: &Interner) -> Error {
+    Error::redeclaration(
+        format!(
+            "lexical name `{}` declared multiple times",
+            interner.resolve_expect(ident.sym())
+        ),
+        previous,
+        ident.span(),
+    )
+}
+
 /// Helper to check if any parameter names are declared in the given list.
 fn name_in_lexically_declared_names(
     bound_names: &[Sym],
@@ -14,14 +14,14 @@ use crate::{
     Error,
     lexer::TokenKind,
     parser::{
-        AllowAwait, AllowReturn, AllowYield, Cursor, OrAbrupt, ParseResult, TokenParser,
+        AllowAwait, AllowReturn, AllowYield, Cursor, ParseResult, TokenParser,
         statement::StatementList,
     },
     source::ReadChar,
 };
 use boa_ast::{
     Punctuator, Spanned,
-    operations::{lexically_declared_names_legacy, var_declared_names},
+    operations::{lexically_declared_identifiers, var_declared_identifiers},
     statement,
 };
 use boa_interner::Interner;
@@ -83,7 +83,6 @@ where
             cursor.advance(interner);
             return Ok(statement::Block::from((vec![], cursor.linear_pos())));
         }
-        let position = cursor.peek(0, interner).or_abrupt()?.span().start();
         let (statement_list, _end) = StatementList::new(
             self.allow_yield,
             self.allow_await,
@@ -100,14 +99,17 @@ where
         // entries, unless the source text matched by this production is not strict mode code and the
         // duplicate entries are only bound by FunctionDeclarations.
         let mut lexical_names = FxHashMap::default();
-        for (name, is_fn) in lexically_declared_names_legacy(&statement_list) {
-            if let Some(is_fn_previous) = lexical_names.insert(name, is_fn) {
+        for (ident, is_fn) in lexically_declared_identifiers(&statement_list) {
+            if let Some((previous, is_fn_previous)) =
+                lexical_names.insert(ident.sym(), (ident.span(), is_fn))
+            {
                 match (cursor.strict(), is_fn, is_fn_previous) {
                     (false, true, true) => {}
                     _ => {
-                        return Err(Error::general(
+                        return Err(Error::redeclaration(
                             "lexical name declared multiple times",
-                            position,
+                            previous,
+                            ident.span(),
                         ));
                     }
                 }
@@ -116,11 +118,12 @@ where
 
         // It is a Syntax Error if any element of the LexicallyDeclaredNames of StatementList also
         // occurs in the VarDeclaredNames of StatementList.
-        for name in var_declared_names(&statement_list) {
-            if lexical_names.contains_key(&name) {
-                return Err(Error::general(
+        for ident in var_declared_identifiers(&statement_list) {
+            if let Some(&(previous, _)) = lexical_names.get(&ident.sym()) {
+                return Err(Error::redeclaration(
                     "lexical name declared in var names",
-                    position,
+                    previous,
+                    ident.span(),
                 ));
             }
         }
@@ -19,7 +19,8 @@ use ast::{
     function::FunctionBody as AstFunctionBody,
     function::PrivateName,
     operations::{
-        check_labels, contains_invalid_object_literal, lexically_declared_names, var_declared_names,
+        check_labels, contains_invalid_object_literal, lexically_declared_identifiers,
+        var_declared_identifiers,
     },
     property::MethodDefinitionKind,
 };
@@ -35,7 +36,7 @@ use boa_ast::{
 };
 use boa_interner::{Interner, Sym};
 use boa_macros::utf16;
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxHashMap;
 
 /// Class declaration parsing.
 ///
@@ -620,20 +621,20 @@ where
                 } else {
                     let strict = cursor.strict();
                     cursor.set_strict(true);
-                    let position = cursor.peek(0, interner).or_abrupt()?.span().start();
                     let (statement_list, _end) =
                         StatementList::new(false, true, false, &FUNCTION_BREAK_TOKENS, false, true)
                             .parse(cursor, interner)?;
 
-                    let mut lexical_names = FxHashSet::default();
+                    let mut lexical_names = FxHashMap::default();
 
                     // It is a Syntax Error if the LexicallyDeclaredNames of
                     // ClassStaticBlockStatementList contains any duplicate entries.
-                    for name in &lexically_declared_names(&statement_list) {
-                        if !lexical_names.insert(*name) {
-                            return Err(Error::general(
+                    for (ident, _) in lexically_declared_identifiers(&statement_list) {
+                        if let Some(previous) = lexical_names.insert(ident.sym(), ident.span()) {
+                            return Err(Error::redeclaration(
                                 "lexical name declared multiple times",
-                                position,
+                                previous,
+                                ident.span(),
                             ));
                         }
                     }
@@ -641,11 +642,12 @@ where
                     // It is a Syntax Error if any element of the LexicallyDeclaredNames of
                     // ClassStaticBlockStatementList also occurs in the VarDeclaredNames of
                     // ClassStaticBlockStatementList.
-                    for name in var_declared_names(&statement_list) {
-                        if lexical_names.contains(&name) {
-                            return Err(Error::general(
+                    for ident in var_declared_identifiers(&statement_list) {
+                        if let Some(&previous) = lexical_names.get(&ident.sym()) {
+                            return Err(Error::redeclaration(
                                 "lexical name declared in var names",
-                                position,
+                                previous,
+                                ident.span(),
                             ));
                         }
                     }
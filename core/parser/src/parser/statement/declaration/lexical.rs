@@ -18,10 +18,10 @@ use crate::{
     },
     source::ReadChar,
 };
-use ast::operations::bound_names;
+use ast::operations::{bound_identifiers, bound_names};
 use boa_ast::{self as ast, Keyword, Punctuator, Spanned, declaration::Variable};
 use boa_interner::{Interner, Sym};
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
 
 /// The type of lexical declaration being parsed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -157,19 +157,19 @@ where
 
         // It is a Syntax Error if the BoundNames of BindingList contains "let".
         // It is a Syntax Error if the BoundNames of BindingList contains any duplicate entries.
-        let bound_names = bound_names(&lexical_declaration);
-        let mut names = FxHashSet::default();
-        for name in bound_names {
-            if name == Sym::LET {
+        let mut names = FxHashMap::default();
+        for ident in bound_identifiers(&lexical_declaration) {
+            if ident.sym() == Sym::LET {
                 return Err(Error::general(
                     "'let' is disallowed as a lexically bound name",
                     tok.span().start(),
                 ));
             }
-            if !names.insert(name) {
-                return Err(Error::general(
+            if let Some(previous) = names.insert(ident.sym(), ident.span()) {
+                return Err(Error::redeclaration(
                     "lexical name declared multiple times",
-                    tok.span().start(),
+                    previous,
+                    ident.span(),
                 ));
             }
         }
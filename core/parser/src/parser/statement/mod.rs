@@ -48,7 +48,7 @@ use crate::{
     source::ReadChar,
 };
 use ast::{
-    Position,
+    Position, PositionGroup,
     operations::{all_private_identifiers_valid, check_labels, contains_invalid_object_literal},
 };
 use boa_ast::{
@@ -218,9 +218,10 @@ where
                 ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor, interner)
             }
             TokenKind::Keyword((Keyword::Debugger, _)) => {
+                let span = tok.span();
                 cursor.advance(interner);
                 cursor.expect_semicolon("debugger statement", interner)?;
-                Ok(ast::Statement::Debugger)
+                Ok(ast::Statement::Debugger(span))
             }
             _ => {
                 ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor, interner)
@@ -296,6 +297,7 @@ where
         let mut directives_stack = Vec::new();
         let mut linear_pos_end = cursor.linear_pos();
         let mut end_position = None;
+        let top_level = cursor.enter_top_level();
 
         loop {
             let peek_token = cursor.peek(0, interner)?;
@@ -304,20 +306,42 @@ where
                 end_position = Some(peek_token.span().end());
             }
 
-            match peek_token {
+            let (item_start, item_linear_start) = match peek_token {
                 Some(token) if self.break_nodes.contains(token.kind()) => break,
-                Some(token) if directive_prologues => {
-                    if let TokenKind::StringLiteral((_, escape)) = token.kind() {
+                Some(token) => {
+                    if directive_prologues
+                        && let TokenKind::StringLiteral((_, escape)) = token.kind()
+                    {
                         directives_stack.push((token.span().start(), *escape));
                     }
+                    (token.span(), token.linear_span().start())
                 }
                 None => break,
-                _ => {}
+            };
+
+            // An incremental parse stops before the items that it reuses, once the directives
+            // are known.
+            if top_level
+                && cursor.stop_before_item(
+                    PositionGroup::new(item_start.start(), item_linear_start),
+                    !directive_prologues,
+                )
+            {
+                break;
             }
 
             let item =
-                StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor, interner)?;
+                match StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
+                    .parse(cursor, interner)
+                {
+                    Ok(item) => item,
+                    Err(error) if cursor.error_recovery() => {
+                        let span = synchronize(cursor, item_start, self.break_nodes, interner)?;
+                        cursor.report_in(error, span)?;
+                        ast::Statement::Error(span).into()
+                    }
+                    Err(error) => return Err(error),
+                };
 
             if directive_prologues {
                 if let ast::StatementListItem::Statement(statement) = &item {
@@ -341,17 +365,17 @@ where
 
                                 for (position, escape) in std::mem::take(&mut directives_stack) {
                                     if escape.contains(EscapeSequence::LEGACY_OCTAL) {
-                                        return Err(Error::general(
+                                        cursor.report(Error::general(
                                             "legacy octal escape sequences are not allowed in strict mode",
                                             position,
-                                        ));
+                                        ))?;
                                     }
 
                                     if escape.contains(EscapeSequence::NON_OCTAL_DECIMAL) {
-                                        return Err(Error::general(
+                                        cursor.report(Error::general(
                                             "decimal escape sequences are not allowed in strict mode",
                                             position,
-                                        ));
+                                        ))?;
                                     }
                                 }
                             }
@@ -381,6 +405,115 @@ where
     }
 }
 
+/// Skips the tokens of a statement list item that could not be parsed, up to the next point
+/// where the parser can continue, returning the span of the skipped source code.
+///
+/// The parser continues after a `;` or a block that closes at the nesting level of the item, and
+/// before a token of `break_nodes`, the end of the input or a keyword that starts a new statement
+/// or declaration on a new line. At least one token is skipped, so that the parser doesn't get
+/// stuck on an item that cannot start with its first token.
+fn synchronize<R>(
+    cursor: &mut Cursor<R>,
+    item_start: Span,
+    break_nodes: &[TokenKind],
+    interner: &mut Interner,
+) -> ParseResult<Span>
+where
+    R: ReadChar,
+{
+    let mut skipped = cursor
+        .peek(0, interner)?
+        .is_some_and(|token| token.span() != item_start);
+    let mut depth = 0usize;
+
+    loop {
+        let token = match cursor.peek_no_skip_line_term(0, interner) {
+            Ok(token) => token.cloned(),
+            Err(
+                error @ Error::Lex {
+                    err: LexError::IO(_),
+                },
+            ) => return Err(error),
+            Err(error) => {
+                // The lexer always moves forward on syntax errors, so the invalid source code is
+                // skipped like any other token.
+                cursor.report(error)?;
+                skipped = true;
+                continue;
+            }
+        };
+        let Some(mut token) = token else {
+            break;
+        };
+
+        let new_line = token.kind() == &TokenKind::LineTerminator;
+        if new_line {
+            match cursor.peek(0, interner)? {
+                Some(next) => token = next.clone(),
+                None => break,
+            }
+        }
+
+        if skipped && depth == 0 {
+            let starts_item = matches!(
+                token.kind(),
+                TokenKind::Keyword((
+                    Keyword::Break
+                        | Keyword::Class
+                        | Keyword::Const
+                        | Keyword::Continue
+                        | Keyword::Debugger
+                        | Keyword::Do
+                        | Keyword::Export
+                        | Keyword::For
+                        | Keyword::Function
+                        | Keyword::If
+                        | Keyword::Import
+                        | Keyword::Let
+                        | Keyword::Return
+                        | Keyword::Switch
+                        | Keyword::Throw
+                        | Keyword::Try
+                        | Keyword::Var
+                        | Keyword::While
+                        | Keyword::With,
+                    false
+                ))
+            );
+            if break_nodes.contains(token.kind()) || (new_line && starts_item) {
+                break;
+            }
+        }
+
+        cursor.advance(interner);
+        skipped = true;
+
+        match token.kind() {
+            TokenKind::Punctuator(
+                Punctuator::OpenBlock | Punctuator::OpenParen | Punctuator::OpenBracket,
+            ) => depth += 1,
+            TokenKind::Punctuator(Punctuator::CloseBlock) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    break;
+                }
+            }
+            TokenKind::Punctuator(Punctuator::CloseParen | Punctuator::CloseBracket) => {
+                depth = depth.saturating_sub(1);
+            }
+            TokenKind::Punctuator(Punctuator::Semicolon) if depth == 0 => break,
+            _ => {}
+        }
+    }
+
+    let end = cursor.position();
+    if end > item_start.start() {
+        Ok(Span::new(item_start.start(), end))
+    } else {
+        Ok(item_start)
+    }
+}
+
 /// Statement list item parsing
 ///
 /// A statement list item can either be an statement or a declaration.
@@ -959,21 +1092,43 @@ where
 
     fn parse(self, cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Self::Output> {
         let mut list = Vec::new();
-        while cursor.peek(0, interner)?.is_some() {
-            let item = ModuleItem.parse(cursor, interner)?;
+        let top_level = cursor.enter_top_level();
+        while let Some(token) = cursor.peek(0, interner)? {
+            let item_start = token.span();
+            let item_linear_start = token.linear_span().start();
+
+            // An incremental parse stops before the items that it reuses.
+            if top_level
+                && cursor.stop_before_item(
+                    PositionGroup::new(item_start.start(), item_linear_start),
+                    true,
+                )
+            {
+                break;
+            }
+
+            let item = match ModuleItem.parse(cursor, interner) {
+                Ok(item) => item,
+                Err(error) if cursor.error_recovery() => {
+                    let span = synchronize(cursor, item_start, &[], interner)?;
+                    cursor.report_in(error, span)?;
+                    boa_ast::ModuleItem::StatementListItem(ast::Statement::Error(span).into())
+                }
+                Err(error) => return Err(error),
+            };
 
             if let Err(error) = check_labels(&item) {
-                return Err(Error::lex(LexError::Syntax(
+                cursor.report(Error::lex(LexError::Syntax(
                     error.message(interner).into(),
                     Position::new(1, 1),
-                )));
+                )))?;
             }
 
             if contains_invalid_object_literal(&item) {
-                return Err(Error::lex(LexError::Syntax(
+                cursor.report(Error::lex(LexError::Syntax(
                     "invalid object literal in module item list".into(),
                     Position::new(1, 1),
-                )));
+                )))?;
             }
 
             list.push(item);
@@ -983,10 +1138,10 @@ where
 
         // It is a Syntax Error if AllPrivateIdentifiersValid of ModuleItemList with argument « » is false.
         if !all_private_identifiers_valid(&list, Vec::new()) {
-            return Err(Error::general(
+            cursor.report(Error::general(
                 "invalid private identifier usage",
                 Position::new(1, 1),
-            ));
+            ))?;
         }
 
         Ok(list)
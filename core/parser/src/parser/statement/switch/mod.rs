@@ -10,7 +10,7 @@ use crate::{
     },
     source::ReadChar,
 };
-use ast::operations::{lexically_declared_names_legacy, var_declared_names};
+use ast::operations::{lexically_declared_identifiers, var_declared_identifiers};
 use boa_ast::{self as ast, Keyword, Punctuator, Spanned, statement, statement::Switch};
 use boa_interner::Interner;
 use rustc_hash::FxHashMap;
@@ -68,8 +68,6 @@ where
 
         cursor.expect(Punctuator::CloseParen, "switch statement", interner)?;
 
-        let position = cursor.peek(0, interner).or_abrupt()?.span().start();
-
         let cases = CaseBlock::new(self.allow_yield, self.allow_await, self.allow_return)
             .parse(cursor, interner)?;
 
@@ -79,14 +77,17 @@ where
         // entries, unless the source text matched by this production is not strict mode code and the
         // duplicate entries are only bound by FunctionDeclarations.
         let mut lexical_names = FxHashMap::default();
-        for (name, is_fn) in lexically_declared_names_legacy(&switch) {
-            if let Some(is_fn_previous) = lexical_names.insert(name, is_fn) {
+        for (ident, is_fn) in lexically_declared_identifiers(&switch) {
+            if let Some((previous, is_fn_previous)) =
+                lexical_names.insert(ident.sym(), (ident.span(), is_fn))
+            {
                 match (cursor.strict(), is_fn, is_fn_previous) {
                     (false, true, true) => {}
                     _ => {
-                        return Err(Error::general(
+                        return Err(Error::redeclaration(
                             "lexical name declared multiple times",
-                            position,
+                            previous,
+                            ident.span(),
                         ));
                     }
                 }
@@ -95,11 +96,12 @@ where
 
         // It is a Syntax Error if any element of the LexicallyDeclaredNames of CaseBlock also occurs
         // in the VarDeclaredNames of CaseBlock.
-        for name in var_declared_names(&switch) {
-            if lexical_names.contains_key(&name) {
-                return Err(Error::general(
+        for ident in var_declared_identifiers(&switch) {
+            if let Some(&(previous, _)) = lexical_names.get(&ident.sym()) {
+                return Err(Error::redeclaration(
                     "lexical name declared in var declared names",
-                    position,
+                    previous,
+                    ident.span(),
                 ));
             }
         }
@@ -10,11 +10,11 @@ use crate::{
 use boa_ast::{
     Keyword, Punctuator, Spanned,
     declaration::Binding,
-    operations::{bound_names, lexically_declared_names, var_declared_names},
+    operations::{bound_identifiers, lexically_declared_identifiers, var_declared_identifiers},
     statement,
 };
 use boa_interner::Interner;
-use rustc_hash::FxHashSet;
+use rustc_hash::FxHashMap;
 
 /// Catch parsing
 ///
@@ -55,7 +55,6 @@ where
 
     fn parse(self, cursor: &mut Cursor<R>, interner: &mut Interner) -> ParseResult<Self::Output> {
         cursor.expect((Keyword::Catch, false), "try statement", interner)?;
-        let position = cursor.peek(0, interner).or_abrupt()?.span().start();
         let catch_param = if cursor.next_if(Punctuator::OpenParen, interner)?.is_some() {
             let catch_param =
                 CatchParameter::new(self.allow_yield, self.allow_await).parse(cursor, interner)?;
@@ -68,23 +67,23 @@ where
 
         // It is a Syntax Error if BoundNames of CatchParameter contains any duplicate elements.
         // https://tc39.es/ecma262/#sec-try-statement-static-semantics-early-errors
-        let bound_names: Option<FxHashSet<_>> = catch_param
+        let bound_names: Option<FxHashMap<_, _>> = catch_param
             .as_ref()
             .map(|binding| {
-                let mut set = FxHashSet::default();
-                for ident in bound_names(binding) {
-                    if !set.insert(ident) {
-                        return Err(Error::general(
+                let mut map = FxHashMap::default();
+                for ident in bound_identifiers(binding) {
+                    if let Some(previous) = map.insert(ident.sym(), ident.span()) {
+                        return Err(Error::redeclaration(
                             "duplicate catch parameter identifier",
-                            position,
+                            previous,
+                            ident.span(),
                         ));
                     }
                 }
-                Ok(set)
+                Ok(map)
             })
             .transpose()?;
 
-        let position = cursor.peek(0, interner).or_abrupt()?.span().start();
         let catch_block = Block::new(self.allow_yield, self.allow_await, self.allow_return)
             .parse(cursor, interner)?;
 
@@ -93,20 +92,22 @@ where
         // https://tc39.es/ecma262/#sec-try-statement-static-semantics-early-errors
         // https://tc39.es/ecma262/#sec-variablestatements-in-catch-blocks
         if let Some(bound_names) = bound_names {
-            for name in lexically_declared_names(&catch_block) {
-                if bound_names.contains(&name) {
-                    return Err(Error::general(
+            for (ident, _) in lexically_declared_identifiers(&catch_block) {
+                if let Some(&previous) = bound_names.get(&ident.sym()) {
+                    return Err(Error::redeclaration(
                         "catch parameter identifier declared in catch body",
-                        position,
+                        previous,
+                        ident.span(),
                     ));
                 }
             }
             if !matches!(&catch_param, Some(Binding::Identifier(_))) {
-                for name in var_declared_names(&catch_block) {
-                    if bound_names.contains(&name) {
-                        return Err(Error::general(
+                for ident in var_declared_identifiers(&catch_block) {
+                    if let Some(&previous) = bound_names.get(&ident.sym()) {
+                        return Err(Error::redeclaration(
                             "catch parameter identifier declared in catch body",
-                            position,
+                            previous,
+                            ident.span(),
                         ));
                     }
                 }
@@ -0,0 +1,422 @@
+//! Tests for generating code from parsed sources, which must parse back to the same code.
+
+use crate::{Parser, Source};
+use boa_ast::{
+    Expression, Module, Script,
+    codegen::{Generated, Generator},
+    scope::Scope,
+    visitor::{VisitWith, VisitorMut},
+};
+use boa_interner::Interner;
+use indoc::indoc;
+use std::{convert::Infallible, ops::ControlFlow};
+
+/// Removes the parentheses kept from the source, so the generator has to add the ones that are
+/// needed.
+struct StripParentheses;
+
+impl<'ast> VisitorMut<'ast> for StripParentheses {
+    type BreakTy = Infallible;
+
+    fn visit_expression_mut(&mut self, node: &'ast mut Expression) -> ControlFlow<Infallible> {
+        while let Expression::Parenthesized(parenthesized) = node {
+            *node = parenthesized.expression().clone();
+        }
+        node.visit_with_mut(self)
+    }
+}
+
+fn parse_script(js: &str, interner: &mut Interner) -> Script {
+    Parser::new(Source::from_bytes(js))
+        .parse_script(&Scope::new_global(), interner)
+        .unwrap_or_else(|error| panic!("failed to parse `{js}`: {error}"))
+}
+
+fn parse_module(js: &str, interner: &mut Interner) -> Module {
+    Parser::new(Source::from_bytes(js))
+        .parse_module(&Scope::new_global(), interner)
+        .unwrap_or_else(|error| panic!("failed to parse `{js}`: {error}"))
+}
+
+/// Generates the code of a script without its original parentheses, and checks that the
+/// generated code parses back to a script that generates the same code.
+#[track_caller]
+fn generate_script(js: &str, minify: bool) -> String {
+    let interner = &mut Interner::default();
+    let mut script = parse_script(js, interner);
+    let _ = script.visit_with_mut(&mut StripParentheses);
+    let code = Generator::new(interner)
+        .minify(minify)
+        .script(&script)
+        .into_parts()
+        .0;
+
+    let mut reparsed = parse_script(&code, interner);
+    let _ = reparsed.visit_with_mut(&mut StripParentheses);
+    let regenerated = Generator::new(interner).minify(minify).script(&reparsed);
+    assert_eq!(
+        regenerated.code(),
+        code,
+        "generated code doesn't round-trip"
+    );
+    code
+}
+
+/// Generates the code of a module, like [`generate_script`].
+#[track_caller]
+fn generate_module(js: &str, minify: bool) -> String {
+    let interner = &mut Interner::default();
+    let mut module = parse_module(js, interner);
+    let _ = module.visit_with_mut(&mut StripParentheses);
+    let code = Generator::new(interner)
+        .minify(minify)
+        .module(&module)
+        .into_parts()
+        .0;
+
+    let mut reparsed = parse_module(&code, interner);
+    let _ = reparsed.visit_with_mut(&mut StripParentheses);
+    let regenerated = Generator::new(interner).minify(minify).module(&reparsed);
+    assert_eq!(
+        regenerated.code(),
+        code,
+        "generated code doesn't round-trip"
+    );
+    code
+}
+
+/// Checks the minified code of each script.
+#[track_caller]
+fn check_minified(cases: &[(&str, &str)]) {
+    for (js, expected) in cases {
+        assert_eq!(generate_script(js, true), *expected, "minifying `{js}`");
+    }
+}
+
+#[test]
+fn pretty() {
+    let js = indoc! {r#"
+        "use strict";
+        function f(a, [b, , c] = [], ...{ d }) {
+            if (a) {
+                return b;
+            } else if (c) throw new Error("c");
+            for (let i = 0; i < 10; i++) {
+                label: for (const x of y) continue label;
+            }
+            return { a, b: c, [d]: 1, get e() { return 2; } };
+        }
+        class A extends B {
+            #x = 1;
+            static y;
+            constructor() {
+                super();
+            }
+            *[Symbol.iterator]() {
+                yield* this.#x;
+            }
+            static {
+                A.y = async () => await 1;
+            }
+        }
+        switch (x) {
+            case 1:
+                f(1);
+                break;
+            default:
+        }
+        try {
+            a?.b.c?.(d);
+        } catch {
+        } finally {
+            `a${b}c`;
+        }
+    "#};
+    let expected = indoc! {r#"
+        "use strict";
+        function f(a, [b, , c] = [], ...{
+            d
+        }) {
+            if (a) {
+                return b;
+            } else if (c) throw new Error("c");
+            for (let i = 0; i < 10; i++) {
+                label: for (const x of y) continue label;
+            }
+            return {
+                a,
+                b: c,
+                [d]: 1,
+                get e() {
+                    return 2;
+                }
+            };
+        }
+        class A extends B {
+            constructor() {
+                super();
+            }
+            #x = 1;
+            static y;
+            *[Symbol.iterator]() {
+                yield* this.#x;
+            }
+            static {
+                A.y = async () => await 1;
+            }
+        }
+        switch (x) {
+            case 1:
+                f(1);
+                break;
+            default:
+        }
+        try {
+            a?.b.c?.(d);
+        } catch {} finally {
+            `a${b}c`;
+        }"#};
+    assert_eq!(generate_script(js, false), expected);
+}
+
+#[test]
+fn minified() {
+    check_minified(&[
+        (
+            "function f(a, b) { return a + b; }",
+            "function f(a,b){return a+b;}",
+        ),
+        ("let x = (y) => y * 2;", "let x=y=>y*2;"),
+        ("var a = 1, b = 'c';", r#"var a=1,b="c";"#),
+        ("if (a) b(); else { c(); }", "if(a)b();else{c();}"),
+        ("x = typeof y; z = void 0;", "x=typeof y;z=void 0;"),
+        (
+            "a = b + +c; d = e - -f; g = h - --i;",
+            "a=b+ +c;d=e- -f;g=h- --i;",
+        ),
+        ("a = b / /re/g;", "a=b/ /re/g;"),
+        ("x = a < !--b;", "x=a<! --b;"),
+    ]);
+}
+
+#[test]
+fn precedence() {
+    check_minified(&[
+        ("(a + b) * c;", "(a+b)*c;"),
+        ("a + b * c;", "a+b*c;"),
+        ("a - (b - c);", "a-(b-c);"),
+        ("(a - b) - c;", "a-b-c;"),
+        ("(-a) ** b;", "(-a)**b;"),
+        ("a ** b ** c;", "a**b**c;"),
+        ("(a ** b) ** c;", "(a**b)**c;"),
+        ("(a || b) ?? c;", "(a||b)??c;"),
+        ("a ?? b ?? c;", "a??b??c;"),
+        ("(a, b) ? c : d;", "(a,b)?c:d;"),
+        ("a = (b, c);", "a=(b,c);"),
+        ("f((a, b), c);", "f((a,b),c);"),
+        ("(a ? b : c)();", "(a?b:c)();"),
+        ("new (f())();", "new(f())();"),
+        ("new (a.b().c);", "new(a.b().c)();"),
+        ("new a.b;", "new a.b();"),
+        ("(a?.b).c;", "(a?.b).c;"),
+        ("(1).toString();", "(1).toString();"),
+        ("(async () => {})();", "(async()=>{})();"),
+        ("x = () => ({});", "x=()=>({});"),
+        ("for ((a in b);;);", "for((a in b);;);"),
+        ("for (var x = (a in b);;);", "for(var x=(a in b);;);"),
+        ("for (x of [a in b]);", "for(x of[a in b]);"),
+        ("a = (b = c);", "a=b=c;"),
+        ("(a || b) && c;", "(a||b)&&c;"),
+        ("-(-a);", "- -a;"),
+        ("(a + b).c;", "(a+b).c;"),
+        ("((a) => a)(1);", "(a=>a)(1);"),
+        ("(a ? b : c) ? d : e;", "(a?b:c)?d:e;"),
+        ("a ? (b, c) : d;", "a?(b,c):d;"),
+        ("(typeof a)();", "(typeof a)();"),
+        ("(a++).b;", "(a++).b;"),
+        ("(a.b)`c`;", "a.b`c`;"),
+        ("(a?.b)`c`;", "(a?.b)`c`;"),
+        ("(a?.b)();", "(a?.b)();"),
+    ]);
+}
+
+#[test]
+fn parenthesized_nodes_are_kept() {
+    let interner = &mut Interner::default();
+    let script = parse_script("((a)) + (b * c);", interner);
+    let generated = Generator::new(interner).minify(true).script(&script);
+    assert_eq!(generated.code(), "((a))+(b*c);");
+}
+
+#[test]
+fn statement_starts() {
+    check_minified(&[
+        ("(function () {})();", "(function(){})();"),
+        ("(function () {}).call(a);", "(function(){}).call(a);"),
+        ("(class {});", "(class{});"),
+        ("({}).toString();", "({}).toString();"),
+        ("({ a } = b);", "({a}=b);"),
+        ("(async function () {});", "(async function(){});"),
+        ("(let)[0] = 1;", "(let)[0]=1;"),
+        ("for ((let).x of y);", "for((let).x of y);"),
+        ("for ((async) of y);", "for((async) of y);"),
+    ]);
+    assert_eq!(
+        generate_module("export default (function () {});", true),
+        "export default(function(){});"
+    );
+    assert_eq!(
+        generate_module("export default (class {}).name;", true),
+        "export default(class{}).name;"
+    );
+}
+
+#[test]
+fn automatic_semicolon_insertion() {
+    check_minified(&[
+        ("a\n++b", "a;++b;"),
+        ("function f() { return\na }", "function f(){return;a;}"),
+        ("a = b\n(c)", "a=b(c);"),
+        ("do x(); while (y) z()", "do x();while(y);z();"),
+        ("let a = 1\nlet b = 2", "let a=1;let b=2;"),
+    ]);
+}
+
+#[test]
+fn dangling_else() {
+    check_minified(&[
+        (
+            "if (a) { if (b) c(); } else d();",
+            "if(a){if(b)c();}else d();",
+        ),
+        (
+            "if (a) { for (;;) if (b) c(); } else d();",
+            "if(a){for(;;)if(b)c();}else d();",
+        ),
+        ("if (a) if (b) c(); else d();", "if(a)if(b)c();else d();"),
+    ]);
+}
+
+#[test]
+fn escaping() {
+    check_minified(&[
+        (r#"a = "it's";"#, r#"a="it's";"#),
+        (r#"a = 'say "hi"';"#, r#"a='say "hi"';"#),
+        ("a = '\\n\\t\\\\\u{2028}\\x01';", r#"a="\n\t\\\u2028\x01";"#),
+        (r"a = '\u{1F600}\uD800';", r#"a="😀\ud800";"#),
+        (r"a = `\`${b}\${c}`;", r"a=`\`${b}\${c}`;"),
+        (r"a = tag`\unicode ${b}`;", r"a=tag`\unicode ${b}`;"),
+        (r"a = /[/]\//gu;", r"a=/[/]\//gu;"),
+    ]);
+}
+
+#[test]
+fn escaped_directives() {
+    check_minified(&[
+        (
+            r#""use\x20strict"; with (a) b;"#,
+            r#"("use strict");with(a)b;"#,
+        ),
+        (r#""use strict"; a;"#, r#""use strict";a;"#),
+        (
+            r#"function f() { 'use\x20strict'; with (a) b; }"#,
+            r#"function f(){("use strict");with(a)b;}"#,
+        ),
+    ]);
+}
+
+#[test]
+fn numbers() {
+    check_minified(&[
+        ("a = 0.5;", "a=0.5;"),
+        ("a = 1e21;", "a=1e21;"),
+        ("a = 1000;", "a=1000;"),
+        ("a = 123456789012;", "a=123456789012;"),
+        ("a = 1.5e-7;", "a=1.5e-7;"),
+        ("a = 0x10;", "a=16;"),
+        ("a = 1e999;", "a=1/0;"),
+        ("a = 1e999 ** 2;", "a=(1/0)**2;"),
+        ("a = 10n;", "a=10n;"),
+    ]);
+}
+
+#[test]
+fn modules() {
+    let js = indoc! {r#"
+        import a, { b as c, "d e" as f } from "m";
+        import * as ns from "n" with { type: "json" };
+        import "side-effect";
+        export { a, c as "g h" };
+        export * as other from "o";
+        export { q as r } from "p";
+        export const x = 1;
+        export default async function () {}
+    "#};
+    assert_eq!(
+        generate_module(js, true),
+        concat!(
+            r#"import a,{b as c,"d e"as f}from"m";"#,
+            r#"import*as ns from"n"with{type:"json"};"#,
+            r#"import"side-effect";"#,
+            r#"export{a,c as"g h"};"#,
+            r#"export*as other from"o";"#,
+            r#"export{q as r}from"p";"#,
+            "export const x=1;",
+            "export default async function(){}",
+        )
+    );
+}
+
+#[test]
+fn round_trips() {
+    let js = indoc! {r#"
+        var { a = 1, b: { c }, ...rest } = obj, [d, [e] = [], ...f] = arr;
+        [a.b, { c: this.d }] = [1, 2];
+        label: {
+            break label;
+        }
+        while (i--) if (!i) continue;
+        for (var k in o) delete o[k];
+        async function* outer() {
+            for await (const [k, v] of entries) ;
+            x = a ? b => b : async function* g() { yield; yield a, b; };
+            z = new.target ?? import("m") ?? (yield* tag`a${b}`) ?? await c;
+        }
+        y = { async *[a]() {}, set b(v) {}, "c-d": 1, 2: 3, __proto__: null, ...e };
+        class C { static async #m() {} get [x]() {} "quoted"() {} static #f; }
+        o = { a: (1, 2), b: [, , ...c,], ["d"]: x in y };
+        i = a++ + ++b - -c - (-d) ** 2 + !(e instanceof f) + typeof typeof g;
+        h = a &&= b ||= c ??= d >>>= e;
+        debugger;
+    "#};
+    generate_script(js, false);
+    generate_script(js, true);
+}
+
+#[test]
+fn source_map() {
+    let js = "let answer = 42;\nconsole.log(answer);\n";
+    let interner = &mut Interner::default();
+    let script = parse_script(js, interner);
+    let generated = Generator::new(interner)
+        .minify(true)
+        .source_map("input.js")
+        .source_map_file("output.js")
+        .script(&script);
+
+    assert_eq!(generated.code(), "let answer=42;console.log(answer);");
+    let map = generated.source_map().expect("source map is enabled");
+    assert_eq!(map.file(), Some("output.js"));
+    assert_eq!(map.source(), "input.js");
+    assert_eq!(map.names(), ["answer", "console", "log"]);
+    // `answer`, `42`, `console`, `log` and `answer`.
+    assert_eq!(map.mappings(), "IAAIA,OAAS,GACbC,QAAQC,IAAIF");
+}
+
+#[test]
+fn no_source_map() {
+    let interner = &mut Interner::default();
+    let script = parse_script("a;", interner);
+    let generated: Generated = Generator::new(interner).script(&script);
+    assert_eq!(generated.source_map(), None);
+}
@@ -0,0 +1,247 @@
+//! Tests for the incremental parser.
+
+use crate::{
+    Parser, Source,
+    parser::incremental::{Incremental, TextEdit},
+};
+use boa_ast::{Module, Script, scope::Scope};
+use boa_interner::Interner;
+use indoc::indoc;
+
+const SCRIPT: &str = indoc! {r#"
+    #!/usr/bin/env boa
+    "use strict";
+    // A comment.
+    const tag = (strings, ...values) => strings.raw.join("|") + values;
+    let a = tag`a${1}b`, b = 2;
+
+    function outer(x, y = function () { return 1; }) {
+        const inner = () => { return tag`${x}`; };
+        function* gen() { yield x; }
+        async function later() { await inner(); }
+        return { inner, get value() { return y; }, async *items() { yield* gen(); } };
+    }
+
+    class Point {
+        #x = 0;
+        static count = 0;
+        static { Point.count = tag`static`.length; }
+        constructor(x) { this.#x = x; }
+        get x() { return this.#x; }
+        *[Symbol.iterator]() { yield this.#x; }
+        async move(dx) { this.#x += await dx; }
+    }
+
+    label: for (const value of [1, 2, 3]) {
+        if (value > a) { break label; }
+        b = value / 2 / b;
+    }
+
+    var re = /ab+c/g, division = b / 2;
+    b
+    / 2 /g.exec(re);
+    const fn = function () {}
+    /re/g.test(fn);
+    outer(a, b)
+    (function () { return tag`last`; })();
+"#};
+
+const MODULE: &str = indoc! {r#"
+    import { x as y } from "mod";
+    export const a = 1;
+    export function f() { return tag`${a}`; }
+    function tag(strings) { return strings; }
+    export default class { method() { return y; } }
+    let b = a
+    /2/ f();
+"#};
+
+/// Parses `source` from scratch, with the formatted AST or error as the result.
+fn parse_fresh(source: &[u16], module: bool, interner: &mut Interner) -> String {
+    let mut parser = Parser::new(Source::from_utf16(source));
+    let scope = Scope::new_global();
+    if module {
+        parser
+            .parse_module(&scope, interner)
+            .map_or_else(|error| format!("{error}"), |ast| format!("{ast:#?}"))
+    } else {
+        parser
+            .parse_script(&scope, interner)
+            .map_or_else(|error| format!("{error}"), |ast| format!("{ast:#?}"))
+    }
+}
+
+/// An incrementally parsed script or module, and its current source text.
+#[derive(Clone)]
+struct Document {
+    text: Vec<u16>,
+    parsed: Parsed,
+}
+
+/// The last successful parse of a document.
+#[derive(Clone)]
+enum Parsed {
+    Script(Incremental<Script>),
+    Module(Incremental<Module>),
+}
+
+impl Document {
+    fn new(source: &str, module: bool, interner: &mut Interner) -> Self {
+        let scope = Scope::new_global();
+        let parsed = if module {
+            Parsed::Module(Incremental::parse_module(source, &scope, interner).unwrap())
+        } else {
+            Parsed::Script(Incremental::parse_script(source, &scope, interner).unwrap())
+        };
+        let document = Self {
+            text: source.encode_utf16().collect(),
+            parsed,
+        };
+        assert_eq!(
+            document.ast(),
+            parse_fresh(&document.text, module, interner)
+        );
+        document
+    }
+
+    fn ast(&self) -> String {
+        match &self.parsed {
+            Parsed::Script(script) => format!("{:#?}", script.ast()),
+            Parsed::Module(module) => format!("{:#?}", module.ast()),
+        }
+    }
+
+    /// Edits the document and reparses it from its last successful parse, checking that the
+    /// result is the same as a parse from scratch.
+    fn edit(&mut self, range: std::ops::Range<usize>, text: &str, interner: &mut Interner) {
+        self.text.splice(range, text.encode_utf16());
+
+        let scope = Scope::new_global();
+        let (module, result) = match &self.parsed {
+            Parsed::Script(script) => (
+                false,
+                script
+                    .reparse(
+                        &TextEdit::between(script.source(), &self.text),
+                        &scope,
+                        interner,
+                    )
+                    .map(Parsed::Script),
+            ),
+            Parsed::Module(module) => (
+                true,
+                module
+                    .reparse(
+                        &TextEdit::between(module.source(), &self.text),
+                        &scope,
+                        interner,
+                    )
+                    .map(Parsed::Module),
+            ),
+        };
+        let expected = parse_fresh(&self.text, module, interner);
+
+        let text = String::from_utf16_lossy(&self.text);
+        match result {
+            Ok(parsed) => {
+                self.parsed = parsed;
+                assert_eq!(self.ast(), expected, "different AST for:\n{text}");
+            }
+            Err(error) => {
+                assert_eq!(format!("{error}"), expected, "different error for:\n{text}");
+            }
+        }
+    }
+}
+
+/// Applies every kind of small edit at every position of the source.
+fn edit_everywhere(source: &str, module: bool) {
+    let interner = &mut Interner::default();
+    let original = Document::new(source, module, interner);
+
+    let len = original.text.len();
+    for start in 0..=len {
+        for (end, text) in [
+            (start, " "),
+            (start, "\n"),
+            (start, ";"),
+            (start, "x"),
+            (start, "/"),
+            (start, "}"),
+            (start, "'use strict';"),
+            ((start + 1).min(len), ""),
+            ((start + 5).min(len), "f() {}"),
+        ] {
+            original.clone().edit(start..end, text, interner);
+        }
+    }
+}
+
+#[test]
+fn script_edits() {
+    edit_everywhere(SCRIPT, false);
+}
+
+#[test]
+fn module_edits() {
+    edit_everywhere(MODULE, true);
+}
+
+#[test]
+fn typing_session() {
+    let interner = &mut Interner::default();
+    let source = "function f(a) {\n  return a;\n}\n\nf(1);\n";
+    let mut document = Document::new(source, false, interner);
+
+    let typed = "let g = (b) => {\n  const c = tag`${b}`;\n  return c / 2;\n};\n";
+    let mut position = source.len();
+    for ch in typed.chars() {
+        document.edit(position..position, &ch.to_string(), interner);
+        position += ch.len_utf16();
+    }
+
+    // Turn the whole script into strict mode code, and back.
+    document.edit(0..0, "'use strict';\n", interner);
+    document.edit(0..14, "", interner);
+}
+
+#[test]
+fn edit_between() {
+    let old = "let a = 1;\nlet b = 2;\n"
+        .encode_utf16()
+        .collect::<Vec<_>>();
+    let new = "let a = 1;\nlet bc = 2;\n"
+        .encode_utf16()
+        .collect::<Vec<_>>();
+    let edit = TextEdit::between(&old, &new);
+    assert_eq!(edit.range(), 16..16);
+    assert_eq!(edit.text(), [u16::from(b'c')]);
+
+    let edit = TextEdit::between(&new, &new);
+    assert!(edit.range().is_empty());
+    assert!(edit.text().is_empty());
+}
+
+#[test]
+fn redeclaration_across_reused_items() {
+    let interner = &mut Interner::default();
+    let source = "let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;\nlet e = 5;\n";
+    let mut document = Document::new(source, false, interner);
+
+    // Renaming the last declaration to the first one is an early error, even if the first item
+    // is reused.
+    let start = source.rfind('e').unwrap();
+    document.edit(start..start + 1, "a", interner);
+    let Parsed::Script(script) = &document.parsed else {
+        unreachable!()
+    };
+    assert!(
+        script
+            .reparse(
+                &TextEdit::between(script.source(), &document.text),
+                &Scope::new_global(),
+                interner
+            )
+            .is_err()
+    );
+}
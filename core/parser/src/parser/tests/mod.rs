@@ -1,6 +1,11 @@
 //! Tests for the parser.
 
+mod codegen;
 mod format;
+mod incremental;
+mod recovery;
+mod semantic;
+mod tokens;
 
 use std::convert::TryInto;
 
@@ -808,13 +813,13 @@ fn stress_test_operations() {
 fn debugger_statement() {
     check_script_parser(
         "debugger;",
-        vec![Statement::Debugger.into()],
+        vec![Statement::Debugger(Span::new((1, 1), (1, 9))).into()],
         &mut Interner::default(),
     );
 
     check_script_parser(
         "debugger",
-        vec![Statement::Debugger.into()],
+        vec![Statement::Debugger(Span::new((1, 1), (1, 9))).into()],
         &mut Interner::default(),
     );
 
@@ -830,3 +835,64 @@ fn invalid_arrow_function() {
     check_invalid_script(r#"(!()=>"#);
     check_invalid_script(r#"!()=>{}"#);
 }
+
+#[test]
+fn leading_and_trailing_comments() {
+    use crate::lexer::TriviaKind;
+    use boa_ast::Spanned;
+
+    let js = indoc! {"
+        // License header.
+
+        /** Docs of `f`. */
+        f() // Calls `f`.
+        g(/* none */);
+    "};
+    let interner = &mut Interner::default();
+    let mut parser = Parser::new(Source::from_bytes(js));
+    parser.set_collect_trivia();
+    let (script, source) = parser
+        .parse_script_with_source(&Scope::new_global(), interner)
+        .expect("failed to parse");
+    let trivia = parser.take_trivia();
+
+    let text = |trivia: &crate::lexer::Trivia| {
+        String::from_utf16_lossy(source.get_code_points_from_span(trivia.linear_span()))
+    };
+    let call = |index: usize| match &script.statements().statements()[index] {
+        StatementListItem::Statement(statement) => match statement.as_ref() {
+            Statement::Expression(Expression::Call(call)) => call,
+            _ => unreachable!("the statement should be a call"),
+        },
+        StatementListItem::Declaration(_) => unreachable!("the item should be a statement"),
+    };
+
+    assert_eq!(trivia.comments().count(), 4);
+    assert_eq!(
+        trivia
+            .leading_comments(call(0).function().span())
+            .map(text)
+            .collect::<Vec<_>>(),
+        ["// License header.", "/** Docs of `f`. */"]
+    );
+    assert_eq!(
+        trivia
+            .leading(call(0).function().span())
+            .first()
+            .map(crate::lexer::Trivia::kind),
+        Some(TriviaKind::SingleLineComment)
+    );
+    assert_eq!(
+        trivia
+            .trailing_comments(call(0).span())
+            .map(text)
+            .collect::<Vec<_>>(),
+        ["// Calls `f`."]
+    );
+    assert!(
+        trivia
+            .leading_comments(call(1).function().span())
+            .next()
+            .is_none()
+    );
+}
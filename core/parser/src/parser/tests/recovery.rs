@@ -0,0 +1,192 @@
+//! Tests for the error-recovering mode of the parser.
+
+use crate::{Error, Parser, Source, error::Recovered};
+use boa_ast::{
+    Declaration, ModuleItem, Position, Script, Span, Statement, StatementListItem, scope::Scope,
+};
+use boa_interner::Interner;
+use indoc::indoc;
+
+fn parse_script(js: &str) -> Recovered<Script> {
+    Parser::new(Source::from_bytes(js))
+        .parse_script_recovering(&Scope::new_global(), &mut Interner::default())
+}
+
+/// Gets the spans of the `Statement::Error` nodes at the top level of `script`.
+fn error_nodes(script: &Script) -> Vec<Span> {
+    script
+        .statements()
+        .statements()
+        .iter()
+        .filter_map(|item| match item {
+            StatementListItem::Statement(statement) => match statement.as_ref() {
+                Statement::Error(span) => Some(*span),
+                _ => None,
+            },
+            StatementListItem::Declaration(_) => None,
+        })
+        .collect()
+}
+
+/// Gets the lines of the errors of a parse.
+fn error_lines<T>(recovered: &Recovered<T>) -> Vec<u32> {
+    recovered
+        .errors()
+        .iter()
+        .map(|(_, span)| span.start().line_number())
+        .collect()
+}
+
+#[test]
+fn valid_script() {
+    let js = "let a = 1;\nfunction f() { return a; }\nf();";
+    let recovered = parse_script(js);
+    assert!(recovered.is_ok());
+    assert_eq!(
+        recovered.ast(),
+        &Parser::new(Source::from_bytes(js))
+            .parse_script(&Scope::new_global(), &mut Interner::default())
+            .expect("the script should parse")
+    );
+}
+
+#[test]
+fn multiple_errors() {
+    let recovered = parse_script(indoc! {"
+        let a = ;
+        let b = 1;
+        foo(;
+        bar();
+        if (b {
+            baz();
+        }
+        let c = 2;
+    "});
+    assert_eq!(error_lines(&recovered), [1, 3, 5]);
+
+    let statements = recovered.ast().statements().statements();
+    assert_eq!(statements.len(), 6);
+    assert!(matches!(
+        statements[1],
+        StatementListItem::Declaration(ref declaration)
+            if matches!(declaration.as_ref(), Declaration::Lexical(_))
+    ));
+    assert_eq!(
+        error_nodes(recovered.ast()),
+        [
+            Span::new((1, 1), (1, 10)),
+            Span::new((3, 1), (3, 6)),
+            Span::new((5, 1), (7, 2)),
+        ]
+    );
+}
+
+#[test]
+fn statement_keyword_on_new_line() {
+    let recovered = parse_script(indoc! {"
+        let a = 1 +
+        const b = 2;
+        let c = b +;
+        var d = c
+    "});
+    assert_eq!(error_lines(&recovered), [2, 3]);
+    assert_eq!(
+        error_nodes(recovered.ast()),
+        [Span::new((1, 1), (1, 12)), Span::new((3, 1), (3, 13))]
+    );
+    assert_eq!(recovered.ast().statements().statements().len(), 4);
+}
+
+#[test]
+fn errors_in_function_body() {
+    let recovered = parse_script(indoc! {"
+        function f() {
+            let x = ;
+            return 1;
+        }
+        let y = 2;
+    "});
+    assert_eq!(error_lines(&recovered), [2]);
+    assert!(error_nodes(recovered.ast()).is_empty());
+    assert_eq!(recovered.ast().statements().statements().len(), 2);
+}
+
+#[test]
+fn stray_closing_brace() {
+    let recovered = parse_script("}\nlet a = 1;");
+    assert_eq!(error_lines(&recovered), [1]);
+    assert_eq!(error_nodes(recovered.ast()), [Span::new((1, 1), (1, 2))]);
+    assert_eq!(recovered.ast().statements().statements().len(), 2);
+}
+
+#[test]
+fn abrupt_end() {
+    let recovered = parse_script("let a = 1;\nfunction f() {");
+    assert!(matches!(recovered.errors(), [(Error::AbruptEnd, _)]));
+    assert_eq!(recovered.ast().statements().statements().len(), 2);
+}
+
+#[test]
+fn early_errors() {
+    let recovered = parse_script(indoc! {"
+        let a;
+        let a;
+        break;
+        new.target;
+    "});
+    let starts: Vec<_> = recovered
+        .errors()
+        .iter()
+        .map(|(_, span)| span.start())
+        .collect();
+    assert_eq!(
+        starts,
+        [
+            Position::new(1, 1),
+            Position::new(1, 1),
+            Position::new(2, 5)
+        ]
+    );
+    assert!(matches!(
+        recovered.errors()[2].0,
+        Error::Redeclaration { previous, .. } if previous.start() == Position::new(1, 5)
+    ));
+}
+
+#[test]
+fn module() {
+    let recovered = Parser::new(Source::from_bytes(indoc! {"
+        import { from 'x';
+        export const a = 1;
+        export let = 2;
+        export const b = a;
+    "}))
+    .parse_module_recovering(&Scope::new_global(), &mut Interner::default());
+    assert_eq!(error_lines(&recovered), [1, 3]);
+
+    let items = recovered.ast().items().items();
+    assert_eq!(items.len(), 4);
+    assert!(matches!(
+        &items[0],
+        ModuleItem::StatementListItem(StatementListItem::Statement(statement))
+            if matches!(statement.as_ref(), Statement::Error(_))
+    ));
+    assert!(matches!(items[3], ModuleItem::ExportDeclaration(_)));
+}
+
+#[test]
+fn lexer_errors() {
+    let recovered = parse_script(indoc! {"
+        let a = 1 @ 2;
+        foo(# 1, @);
+        let b = 1;
+    "});
+    assert!(
+        recovered
+            .errors()
+            .iter()
+            .all(|(error, _)| matches!(error, Error::Lex { .. }))
+    );
+    assert_eq!(error_lines(&recovered), [1, 2, 2]);
+    assert_eq!(recovered.ast().statements().statements().len(), 3);
+}
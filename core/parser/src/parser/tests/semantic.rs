@@ -0,0 +1,227 @@
+//! Tests for the semantic model of parsed sources.
+
+use crate::{Parser, Source};
+use boa_ast::{
+    Declaration, Span, Spanned, StatementListItem,
+    scope::Scope,
+    semantic::{BindingId, BindingKind, Reference, ReferenceKind, SemanticModel},
+};
+use boa_interner::Interner;
+
+fn script_model(js: &str, interner: &mut Interner) -> SemanticModel {
+    let scope = Scope::new_global();
+    let script = Parser::new(Source::from_bytes(js))
+        .parse_script(&scope, interner)
+        .unwrap_or_else(|error| panic!("failed to parse `{js}`: {error}"));
+    SemanticModel::script(&script, &scope, interner)
+}
+
+/// Gets the spans of the occurrences of the identifier `name` in `js`.
+fn spans(js: &str, name: &str) -> Vec<Span> {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut spans = Vec::new();
+    for (line, text) in (1..).zip(js.lines()) {
+        for (index, _) in text.match_indices(name) {
+            let before = text[..index].chars().next_back();
+            let after = text[index + name.len()..].chars().next();
+            if before.is_some_and(is_identifier) || after.is_some_and(is_identifier) {
+                continue;
+            }
+            let column = u32::try_from(index).unwrap() + 1;
+            let end = column + u32::try_from(name.len()).unwrap();
+            spans.push(Span::new((line, column), (line, end)));
+        }
+    }
+    spans
+}
+
+/// Gets the bindings of the occurrences of the identifier `name` in `js`.
+fn bindings(model: &SemanticModel, js: &str, name: &str) -> Vec<BindingId> {
+    spans(js, name)
+        .into_iter()
+        .map(|span| {
+            model
+                .binding_at(span)
+                .unwrap_or_else(|| panic!("no binding for `{name}` at {span:?}"))
+        })
+        .collect()
+}
+
+#[test]
+fn declarations_and_references() {
+    let js = "let a = 1; function f(b) { return a + b; } a = f(a); a += 1; a++;";
+    let interner = &mut Interner::default();
+    let model = script_model(js, interner);
+
+    let a = bindings(&model, js, "a");
+    assert!(a.iter().all(|id| *id == a[0]));
+    let binding = model.binding(a[0]);
+    assert_eq!(interner.resolve_expect(binding.name()).to_string(), "a");
+    assert_eq!(binding.declarations().len(), 1);
+    assert_eq!(binding.declarations()[0].kind(), BindingKind::Let);
+    assert_eq!(
+        model
+            .references_to(a[0])
+            .map(Reference::kind)
+            .collect::<Vec<_>>(),
+        [
+            ReferenceKind::Read,
+            ReferenceKind::Write,
+            ReferenceKind::Read,
+            ReferenceKind::ReadWrite,
+            ReferenceKind::ReadWrite,
+        ]
+    );
+
+    let b = bindings(&model, js, "b");
+    assert_eq!(b[0], b[1]);
+    assert_eq!(
+        model.binding(b[0]).declarations()[0].kind(),
+        BindingKind::Parameter
+    );
+    let f = bindings(&model, js, "f");
+    assert_eq!(f[0], f[1]);
+    assert_eq!(
+        model.binding(f[0]).declarations()[0].kind(),
+        BindingKind::Function
+    );
+}
+
+#[test]
+fn shadowing() {
+    let js = "let a; { let a; a; } a; try {} catch (a) { a; }";
+    let interner = &mut Interner::default();
+    let model = script_model(js, interner);
+
+    let a = bindings(&model, js, "a");
+    assert_eq!(a[0], a[3]);
+    assert_eq!(a[1], a[2]);
+    assert_eq!(a[4], a[5]);
+    assert_ne!(a[0], a[1]);
+    assert_ne!(a[0], a[4]);
+    assert_ne!(a[1], a[4]);
+    assert_eq!(
+        model.binding(a[4]).declarations()[0].kind(),
+        BindingKind::CatchParameter
+    );
+}
+
+#[test]
+fn globals() {
+    let js = "var v; let l; console.log(v, l); function f() { let x; return x; }";
+    let interner = &mut Interner::default();
+    let model = script_model(js, interner);
+
+    for name in ["console", "v", "l", "f"] {
+        for span in spans(js, name) {
+            assert!(model.is_global(span), "`{name}` is not global");
+        }
+    }
+    for span in spans(js, "x") {
+        assert!(!model.is_global(span));
+    }
+
+    let console = model.binding(bindings(&model, js, "console")[0]);
+    assert!(!console.is_declared());
+    assert!(model.binding(bindings(&model, js, "v")[0]).is_declared());
+    assert!(!model.is_global(Span::new((1, 1), (1, 2))));
+}
+
+#[test]
+fn closures() {
+    let js = "function outer(a) { let b; function inner() { return a + b + c; } return () => b; } var c;";
+    let interner = &mut Interner::default();
+    let scope = Scope::new_global();
+    let script = Parser::new(Source::from_bytes(js))
+        .parse_script(&scope, interner)
+        .unwrap();
+    let model = SemanticModel::script(&script, &scope, interner);
+
+    let a = bindings(&model, js, "a")[0];
+    let b = bindings(&model, js, "b")[0];
+    let [outer, inner, arrow] = model.closures() else {
+        panic!("expected three closures");
+    };
+    assert!(outer.captures().is_empty());
+    assert_eq!(inner.captures(), [a, b]);
+    assert_eq!(arrow.captures(), [b]);
+
+    let StatementListItem::Declaration(declaration) = &script.statements().statements()[0] else {
+        panic!("expected a declaration");
+    };
+    let Declaration::FunctionDeclaration(function) = declaration.as_ref() else {
+        panic!("expected a function declaration");
+    };
+    assert_eq!(model.closure(function.body()), Some(outer));
+}
+
+#[test]
+fn classes_and_patterns() {
+    let js = "class A { m() { return A; } } new A(); const {x, y: [z = x], ...r} = o; [z2] = [r];";
+    let interner = &mut Interner::default();
+    let model = script_model(js, interner);
+
+    let class = bindings(&model, js, "A");
+    assert!(class.iter().all(|id| *id == class[0]));
+    assert_eq!(model.references_to(class[0]).count(), 2);
+
+    for name in ["x", "z", "r"] {
+        let binding = model.binding(bindings(&model, js, name)[0]);
+        assert_eq!(binding.declarations()[0].kind(), BindingKind::Const);
+    }
+    assert_eq!(model.references_to(bindings(&model, js, "x")[0]).count(), 1);
+    assert!(
+        spans(js, "y")
+            .iter()
+            .all(|span| model.binding_at(*span).is_none())
+    );
+
+    let z2 = bindings(&model, js, "z2")[0];
+    assert!(!model.binding(z2).is_declared());
+    assert_eq!(
+        model.references_to(z2).next().map(Reference::kind),
+        Some(ReferenceKind::Write)
+    );
+}
+
+#[test]
+fn module_imports() {
+    let js = "import d, {a as b} from 'm'; import * as ns from 'n'; export { b }; d(ns, b);";
+    let interner = &mut Interner::default();
+    let module = Parser::new(Source::from_bytes(js))
+        .parse_module(&Scope::new_global(), interner)
+        .unwrap();
+    let model = SemanticModel::module(&module, interner);
+
+    for name in ["d", "b", "ns"] {
+        // Export specifiers have no span, so they are not in the model.
+        let ids = spans(js, name)
+            .into_iter()
+            .filter_map(|span| model.binding_at(span))
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], ids[1]);
+        let binding = model.binding(ids[0]);
+        assert_eq!(binding.declarations()[0].kind(), BindingKind::Import);
+        assert!(!binding.is_global());
+    }
+}
+
+#[test]
+fn dynamic_references() {
+    let js = "with (o) { a; } a; function f() { eval(''); b; }";
+    let interner = &mut Interner::default();
+    let model = script_model(js, interner);
+
+    let dynamic = model
+        .references()
+        .iter()
+        .map(|reference| (reference.span(), reference.is_dynamic()))
+        .collect::<Vec<_>>();
+    let [first, second] = spans(js, "a")[..] else {
+        panic!("expected two references to `a`");
+    };
+    assert!(dynamic.contains(&(first, true)));
+    assert!(dynamic.contains(&(second, false)));
+    assert!(dynamic.contains(&(spans(js, "b")[0], true)));
+}
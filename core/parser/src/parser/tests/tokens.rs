@@ -0,0 +1,66 @@
+//! Tests for the tokens recorded by the parser.
+
+use crate::{Parser, Source, lexer::TokenKind};
+use boa_ast::scope::Scope;
+use boa_interner::Interner;
+
+/// Gets the text of the tokens recorded while parsing `js` as a script.
+fn tokens(js: &str) -> Vec<String> {
+    let mut parser = Parser::new(Source::from_bytes(js));
+    parser.set_collect_tokens();
+    parser
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .unwrap();
+
+    let code: Vec<u16> = js.encode_utf16().collect();
+    parser
+        .take_tokens()
+        .iter()
+        .map(|token| {
+            let span = token.linear_span();
+            String::from_utf16_lossy(&code[span.start().pos()..span.end().pos()])
+        })
+        .collect()
+}
+
+#[test]
+fn tokens_are_not_collected_by_default() {
+    let mut parser = Parser::new(Source::from_bytes("a + b"));
+    parser
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .unwrap();
+    assert!(parser.take_tokens().is_empty());
+}
+
+#[test]
+fn regular_expressions() {
+    assert_eq!(
+        tokens("if (a) /b/g.exec(c) / 2\nx /= /=/"),
+        [
+            "if", "(", "a", ")", "/b/g", ".", "exec", "(", "c", ")", "/", "2", "x", "/=", "/=/"
+        ]
+    );
+}
+
+#[test]
+fn templates() {
+    assert_eq!(
+        tokens("tag`a${b}c${ `d${e}` }f`;"),
+        ["tag", "`a${", "b", "}c${", "`d${", "e", "}`", "}f`", ";"]
+    );
+}
+
+#[test]
+fn template_kinds() {
+    let mut parser = Parser::new(Source::from_bytes("`a${b}c`"));
+    parser.set_collect_tokens();
+    parser
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .unwrap();
+    let tokens = parser.take_tokens();
+    assert!(matches!(tokens[0].kind(), TokenKind::TemplateMiddle(_)));
+    assert!(matches!(
+        tokens[2].kind(),
+        TokenKind::TemplateNoSubstitution(_)
+    ));
+}
@@ -1,5 +1,6 @@
 //! Boa parser input source types.
 
+use boa_ast::SourceText;
 use std::{
     fs::File,
     io::{self, BufReader, Read},
@@ -20,6 +21,9 @@ mod utf8;
 pub struct Source<'path, R> {
     pub(crate) reader: R,
     pub(crate) path: Option<&'path Path>,
+    pub(crate) jsx: Option<JsxOptions>,
+    #[cfg(feature = "typescript")]
+    pub(crate) typescript: bool,
 }
 
 impl<'bytes> Source<'static, UTF8Input<&'bytes [u8]>> {
@@ -40,6 +44,9 @@ impl<'bytes> Source<'static, UTF8Input<&'bytes [u8]>> {
         Self {
             reader: UTF8Input::new(source.as_ref()),
             path: None,
+            jsx: None,
+            #[cfg(feature = "typescript")]
+            typescript: false,
         }
     }
 }
@@ -62,6 +69,9 @@ impl<'input> Source<'static, UTF16Input<'input>> {
         Self {
             reader: UTF16Input::new(input),
             path: None,
+            jsx: None,
+            #[cfg(feature = "typescript")]
+            typescript: false,
         }
     }
 }
@@ -89,6 +99,9 @@ impl<'path> Source<'path, UTF8Input<BufReader<File>>> {
         Ok(Self {
             reader: UTF8Input::new(BufReader::new(reader)),
             path: Some(source),
+            jsx: None,
+            #[cfg(feature = "typescript")]
+            typescript: false,
         })
     }
 }
@@ -116,6 +129,9 @@ impl<'path, R: Read> Source<'path, UTF8Input<R>> {
         Self {
             reader: UTF8Input::new(reader),
             path,
+            jsx: None,
+            #[cfg(feature = "typescript")]
+            typescript: false,
         }
     }
 }
@@ -126,6 +142,9 @@ impl<'path, R> Source<'path, R> {
         Source {
             reader: self.reader,
             path: Some(new_path),
+            jsx: self.jsx,
+            #[cfg(feature = "typescript")]
+            typescript: self.typescript,
         }
     }
 
@@ -133,6 +152,140 @@ impl<'path, R> Source<'path, R> {
     pub fn path(&self) -> Option<&'path Path> {
         self.path
     }
+
+    /// Enables JSX in this [`Source`], creating its elements with the runtime of `options`.
+    ///
+    /// JSX elements, fragments, attributes, spread attributes and children, and expression
+    /// containers are parsed as primary expressions, and they are compiled into calls to the
+    /// element factory of the runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boa_parser::{Source, source::JsxOptions};
+    /// let code = r#"const link = <a href="/">Home</a>;"#;
+    /// let source = Source::from_bytes(code).with_jsx(JsxOptions::classic("h", "Fragment"));
+    /// ```
+    #[must_use]
+    pub fn with_jsx(mut self, options: JsxOptions) -> Self {
+        self.jsx = Some(options);
+        self
+    }
+
+    /// Returns the JSX options of this source, if JSX is enabled.
+    pub const fn jsx(&self) -> Option<&JsxOptions> {
+        self.jsx.as_ref()
+    }
+
+    /// Sets if this [`Source`] is TypeScript code.
+    ///
+    /// The types of a TypeScript source, like annotations, interfaces, type aliases, generics,
+    /// `as` and `satisfies` expressions, non-null assertions and `declare` declarations, are
+    /// erased before parsing it. Enums, namespaces and parameter properties are rewritten into the
+    /// equivalent JavaScript. Types are never checked, and type-only imports must be marked with
+    /// `import type` or inline `type` modifiers.
+    ///
+    /// The positions of the parsed nodes and their source text are those of the TypeScript
+    /// source, so errors and stack traces point to the TypeScript code, and
+    /// `Function.prototype.toString` returns it with its types. The code inserted for an enum, a
+    /// namespace or a parameter property has the position of the construct it replaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use boa_parser::Source;
+    /// let code = "let answer: number = 42;";
+    /// let source = Source::from_bytes(code).with_typescript(true);
+    /// ```
+    #[cfg(feature = "typescript")]
+    #[must_use]
+    pub fn with_typescript(mut self, typescript: bool) -> Self {
+        self.typescript = typescript;
+        self
+    }
+
+    /// Returns `true` if this source is TypeScript code.
+    #[cfg(feature = "typescript")]
+    pub const fn is_typescript(&self) -> bool {
+        self.typescript
+    }
+}
+
+impl<R: ReadChar> Source<'_, R> {
+    /// Reads the whole input of this [`Source`] without parsing it.
+    ///
+    /// The returned [`SourceText`] contains the same code units that the parser would collect
+    /// while parsing the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be read or contains an invalid code point.
+    pub fn read_source_text(mut self) -> io::Result<SourceText> {
+        let mut source_text = SourceText::default();
+        while let Some(cp) = self.reader.next_char()? {
+            if cp > u32::from(char::MAX) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid unicode code point",
+                ));
+            }
+            source_text.collect_code_point(cp);
+        }
+        Ok(source_text)
+    }
+}
+
+/// The runtime that creates the JSX elements of a [`Source`], see [`Source::with_jsx`].
+///
+/// The default runtime is the classic React runtime, which uses `React.createElement` and
+/// `React.Fragment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsxOptions {
+    /// Elements are created with `factory(type, props, ...children)`.
+    ///
+    /// The factory and the fragment type are identifiers or member expressions, like
+    /// `React.createElement` and `React.Fragment`, which must be in scope where JSX is used.
+    Classic {
+        /// The function that creates an element.
+        factory: String,
+        /// The type of fragments.
+        fragment: String,
+    },
+    /// Elements are created with `jsx(type, props, key)` and `jsxs(type, props, key)`, imported
+    /// with `Fragment` from the `jsx-runtime` module of `import_source`, like `react/jsx-runtime`.
+    ///
+    /// The automatic runtime can only be used in modules.
+    Automatic {
+        /// The module that provides the `jsx-runtime` module.
+        import_source: String,
+    },
+}
+
+impl JsxOptions {
+    /// Creates the options of the classic runtime with the given element factory and fragment
+    /// type.
+    #[must_use]
+    pub fn classic(factory: impl Into<String>, fragment: impl Into<String>) -> Self {
+        Self::Classic {
+            factory: factory.into(),
+            fragment: fragment.into(),
+        }
+    }
+
+    /// Creates the options of the automatic runtime, importing it from
+    /// `{import_source}/jsx-runtime`.
+    #[must_use]
+    pub fn automatic(import_source: impl Into<String>) -> Self {
+        Self::Automatic {
+            import_source: import_source.into(),
+        }
+    }
+}
+
+impl Default for JsxOptions {
+    fn default() -> Self {
+        Self::classic("React.createElement", "React.Fragment")
+    }
 }
 
 /// This trait is used to abstract over the different types of input readers.
@@ -0,0 +1,404 @@
+//! Erasure of the TypeScript syntax of functions and classes.
+
+use super::{Kind, Stripper};
+use crate::error::ParseResult;
+use boa_ast::{Keyword, Punctuator};
+use std::fmt::Write;
+
+/// A modifier of a class member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+    /// A JavaScript modifier, like `static` or `get`.
+    JavaScript,
+    /// A TypeScript modifier that is erased, like `public` or `readonly`.
+    TypeScript,
+    /// A TypeScript modifier that erases the whole member, like `abstract` or `declare`.
+    Erasing,
+}
+
+impl Stripper<'_> {
+    /// Erases the TypeScript syntax of the function starting with the `function` keyword at
+    /// `index`, returning the index of its body.
+    ///
+    /// Overload signatures, which don't have a body, are erased entirely.
+    pub(super) fn function(&mut self, index: usize) -> ParseResult<usize> {
+        let mut next = index + 1;
+        if self.is(next, Punctuator::Mul) {
+            next += 1;
+        }
+        if self.is_name(next) {
+            next += 1;
+        }
+        if self.is(next, Punctuator::LessThan) {
+            let end = self
+                .skip_type_parameters(next)
+                .ok_or_else(|| self.unexpected(next))?;
+            self.blank(next, end);
+            next = end;
+        }
+        if !self.is(next, Punctuator::OpenParen) {
+            return Ok(next);
+        }
+        self.parameters(next, false)?;
+        next = self.return_type(self.matching[next] + 1)?;
+        if self.is(next, Punctuator::OpenBlock) {
+            return Ok(next);
+        }
+
+        let mut start = index;
+        while start > 0
+            && matches!(
+                self.tokens[start - 1].kind,
+                Kind::Keyword(Keyword::Async | Keyword::Default | Keyword::Export)
+            )
+        {
+            start -= 1;
+        }
+        if self.is(next, Punctuator::Semicolon) {
+            next += 1;
+        }
+        self.blank_statement(start, next);
+        Ok(next)
+    }
+
+    /// Erases the TypeScript syntax of the parameters in the parentheses at `open`, returning the
+    /// names of the parameter properties if the parameters are the ones of a constructor.
+    pub(super) fn parameters(
+        &mut self,
+        open: usize,
+        constructor: bool,
+    ) -> ParseResult<Vec<String>> {
+        let close = self.matching[open];
+        let mut properties = Vec::new();
+        let mut index = open + 1;
+        while index < close {
+            let (end, property) = self.parameter(index, close)?;
+            if let Some(property) = property
+                && constructor
+            {
+                properties.push(property);
+            }
+            if end == close {
+                break;
+            }
+            if !self.is(end, Punctuator::Comma) {
+                return Err(self.unexpected(end));
+            }
+            index = end + 1;
+        }
+        Ok(properties)
+    }
+
+    /// Erases the TypeScript syntax of the parameter at `index`, returning the index after it and
+    /// its name if it is a parameter property, like `private readonly a: number`.
+    fn parameter(
+        &mut self,
+        mut index: usize,
+        close: usize,
+    ) -> ParseResult<(usize, Option<String>)> {
+        let mut property = false;
+        while ["public", "private", "protected", "readonly", "override"]
+            .iter()
+            .any(|modifier| self.is_word(index, modifier))
+            && matches!(
+                self.kind(index + 1),
+                Some(
+                    Kind::Identifier
+                        | Kind::Keyword(_)
+                        | Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket)
+                )
+            )
+        {
+            self.blank(index, index + 1);
+            property = true;
+            index += 1;
+        }
+
+        // The type of `this`, like `function (this: Window) {}`.
+        if self.is_keyword(index, Keyword::This)
+            && (index + 1 == close
+                || self.is(index + 1, Punctuator::Colon)
+                || self.is(index + 1, Punctuator::Comma))
+        {
+            let mut end = index + 1;
+            if self.is(end, Punctuator::Colon) {
+                end = self
+                    .skip_type(end + 1)
+                    .ok_or_else(|| self.expected_type(end + 1))?;
+            }
+            let erased_end = if self.is(end, Punctuator::Comma) {
+                end + 1
+            } else {
+                end
+            };
+            self.blank(index, erased_end);
+            return Ok((end, None));
+        }
+
+        if self.is(index, Punctuator::Spread) {
+            index += 1;
+        }
+        let name = (self.kind(index) == Some(Kind::Identifier)).then(|| self.text(index));
+        index = self.binding(index)?;
+        if self.is(index, Punctuator::Question) {
+            self.blank(index, index + 1);
+            index += 1;
+        }
+        index = self.annotation(index)?;
+        if self.is(index, Punctuator::Assign) {
+            let end = self.expression_end(index + 1, close);
+            self.scan(index + 1, end, None)?;
+            index = end;
+        }
+        Ok((index, name.filter(|_| property)))
+    }
+
+    /// Erases the TypeScript syntax of the class starting with the `class` keyword at `index`,
+    /// returning the index after the class.
+    pub(super) fn class(&mut self, index: usize, end: usize) -> ParseResult<usize> {
+        let mut next = index + 1;
+        if self.kind(next) == Some(Kind::Identifier) && !self.is_word(next, "implements") {
+            next += 1;
+        }
+        if self.is(next, Punctuator::LessThan) {
+            let type_parameters_end = self
+                .skip_type_parameters(next)
+                .ok_or_else(|| self.unexpected(next))?;
+            self.blank(next, type_parameters_end);
+            next = type_parameters_end;
+        }
+
+        let extends = self.is_keyword(next, Keyword::Extends);
+        if extends {
+            next += 1;
+            while next < end
+                && !self.is(next, Punctuator::OpenBlock)
+                && !self.is_word(next, "implements")
+            {
+                next = match self.tokens[next].kind {
+                    Kind::Punctuator(Punctuator::LessThan)
+                        if self.tokens[next - 1].ends_expression =>
+                    {
+                        match self.skip_type_arguments(next) {
+                            Some(type_arguments_end) => {
+                                self.blank(next, type_arguments_end);
+                                type_arguments_end
+                            }
+                            None => next + 1,
+                        }
+                    }
+                    Kind::Punctuator(Punctuator::OpenParen | Punctuator::OpenBracket) => {
+                        let close = self.matching[next];
+                        self.scan(next + 1, close, None)?;
+                        close + 1
+                    }
+                    _ => next + 1,
+                };
+            }
+        }
+
+        if self.is_word(next, "implements") {
+            let mut implements_end = next;
+            loop {
+                implements_end = self
+                    .skip_type(implements_end + 1)
+                    .ok_or_else(|| self.expected_type(implements_end + 1))?;
+                if !self.is(implements_end, Punctuator::Comma) {
+                    break;
+                }
+            }
+            self.blank(next, implements_end);
+            next = implements_end;
+        }
+
+        // Not a class, like in `a.class`.
+        if !self.is(next, Punctuator::OpenBlock) {
+            return Ok(index + 1);
+        }
+
+        let close = self.matching[next];
+        let mut member = next + 1;
+        while member < close {
+            member = self.member(member, close, extends)?;
+        }
+        Ok(close + 1)
+    }
+
+    /// Gets the kind of the modifier at `index` of a class member, if it is a modifier.
+    fn modifier(&self, index: usize) -> Option<Modifier> {
+        let modifier = [
+            ("public", Modifier::TypeScript),
+            ("private", Modifier::TypeScript),
+            ("protected", Modifier::TypeScript),
+            ("readonly", Modifier::TypeScript),
+            ("override", Modifier::TypeScript),
+            ("abstract", Modifier::Erasing),
+            ("declare", Modifier::Erasing),
+            ("static", Modifier::JavaScript),
+            ("get", Modifier::JavaScript),
+            ("set", Modifier::JavaScript),
+            ("accessor", Modifier::JavaScript),
+        ]
+        .into_iter()
+        .find(|(word, _)| self.is_word(index, word))
+        .map(|(_, modifier)| modifier)
+        .or_else(|| {
+            self.is_keyword(index, Keyword::Async)
+                .then_some(Modifier::JavaScript)
+        })?;
+
+        // A modifier is followed by the name of the member, otherwise it is the name.
+        let followed_by_name = matches!(
+            self.kind(index + 1),
+            Some(
+                Kind::Identifier
+                    | Kind::PrivateIdentifier
+                    | Kind::Keyword(_)
+                    | Kind::String
+                    | Kind::Literal
+                    | Kind::Punctuator(Punctuator::OpenBracket | Punctuator::Mul)
+            )
+        );
+        (followed_by_name
+            && (!self.is_keyword(index, Keyword::Async) || self.on_same_line(index + 1)))
+        .then_some(modifier)
+    }
+
+    /// Erases the TypeScript syntax of the class member at `start`, returning the index after
+    /// the member.
+    fn member(&mut self, start: usize, close: usize, extends: bool) -> ParseResult<usize> {
+        if self.is(start, Punctuator::Semicolon) {
+            return Ok(start + 1);
+        }
+        if self.is_word(start, "static") && self.is(start + 1, Punctuator::OpenBlock) {
+            let block_close = self.matching[start + 1];
+            self.scan(start + 2, block_close, None)?;
+            return Ok(block_close + 1);
+        }
+
+        let mut index = start;
+        let mut erased = false;
+        while let Some(modifier) = self.modifier(index) {
+            match modifier {
+                Modifier::JavaScript => {}
+                Modifier::TypeScript => self.blank(index, index + 1),
+                Modifier::Erasing => erased = true,
+            }
+            index += 1;
+        }
+
+        // Index signatures, like `[key: string]: number`.
+        if self.is(index, Punctuator::OpenBracket)
+            && self.kind(index + 1) == Some(Kind::Identifier)
+            && self.is(index + 2, Punctuator::Colon)
+        {
+            let mut end = self.matching[index] + 1;
+            if self.is(end, Punctuator::Colon) {
+                end = self
+                    .skip_type(end + 1)
+                    .ok_or_else(|| self.expected_type(end + 1))?;
+            }
+            if self.is(end, Punctuator::Semicolon) {
+                end += 1;
+            }
+            self.blank(start, end);
+            return Ok(end);
+        }
+
+        if self.is(index, Punctuator::Mul) {
+            index += 1;
+        }
+        let name = index;
+        match self.kind(index) {
+            Some(Kind::Punctuator(Punctuator::OpenBracket)) => {
+                let computed_close = self.matching[index];
+                self.scan(index + 1, computed_close, None)?;
+                index = computed_close + 1;
+            }
+            Some(
+                Kind::Identifier
+                | Kind::PrivateIdentifier
+                | Kind::Keyword(_)
+                | Kind::String
+                | Kind::Literal,
+            ) => index += 1,
+            _ => return Err(self.unexpected(index)),
+        }
+        if self.is(index, Punctuator::Question) || self.is(index, Punctuator::Not) {
+            self.blank(index, index + 1);
+            index += 1;
+        }
+        if self.is(index, Punctuator::LessThan) {
+            let end = self
+                .skip_type_parameters(index)
+                .ok_or_else(|| self.unexpected(index))?;
+            self.blank(index, end);
+            index = end;
+        }
+
+        if self.is(index, Punctuator::OpenParen) {
+            let properties = self.parameters(index, self.is_word(name, "constructor"))?;
+            index = self.return_type(self.matching[index] + 1)?;
+            if erased || !self.is(index, Punctuator::OpenBlock) {
+                // Abstract methods and overload signatures.
+                if self.is(index, Punctuator::Semicolon) {
+                    index += 1;
+                }
+                self.blank(start, index);
+                return Ok(index);
+            }
+            if !properties.is_empty() {
+                self.assign_properties(index, extends, &properties);
+            }
+            let body_close = self.matching[index];
+            self.scan(index + 1, body_close, None)?;
+            return Ok(body_close + 1);
+        }
+
+        index = self.annotation(index)?;
+        if self.is(index, Punctuator::Assign) {
+            let end = self.expression_end(index + 1, close);
+            self.scan(index + 1, end, None)?;
+            index = end;
+        }
+        if self.is(index, Punctuator::Semicolon) {
+            index += 1;
+        }
+        if erased {
+            // Abstract properties and `declare` fields.
+            self.blank(start, index);
+        }
+        Ok(index)
+    }
+
+    /// Inserts the assignments of the parameter properties of a constructor at the start of
+    /// its body at `open`, or after the `super` call of a derived class.
+    fn assign_properties(&mut self, open: usize, extends: bool, properties: &[String]) {
+        let mut assignments = String::new();
+        for property in properties {
+            let _ = write!(assignments, " this.{property} = {property};");
+        }
+
+        let close = self.matching[open];
+        let mut index = open + 1;
+        while extends && index < close {
+            if self.is_keyword(index, Keyword::Super) && self.is(index + 1, Punctuator::OpenParen) {
+                let call_close = self.matching[index + 1];
+                if self.is(call_close + 1, Punctuator::Semicolon) {
+                    self.insert(self.tokens[call_close + 1].end, assignments);
+                } else {
+                    self.insert(self.tokens[call_close].end, format!(";{assignments}"));
+                }
+                return;
+            }
+            index = match self.tokens[index].kind {
+                Kind::Punctuator(
+                    Punctuator::OpenParen | Punctuator::OpenBracket | Punctuator::OpenBlock,
+                )
+                | Kind::TemplateHead => self.matching[index] + 1,
+                _ => index + 1,
+            };
+        }
+        self.insert(self.tokens[open].end, assignments);
+    }
+}
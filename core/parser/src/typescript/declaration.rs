@@ -0,0 +1,459 @@
+//! Erasure of the TypeScript declarations, imports and exports, and rewriting of enums and
+//! namespaces.
+
+use super::{Kind, Stripper};
+use crate::{Error, error::ParseResult};
+use boa_ast::{Keyword, Punctuator};
+use std::fmt::Write;
+
+impl Stripper<'_> {
+    /// Erases or rewrites the TypeScript declaration starting at `index`, returning the index
+    /// after it, or `None` if the statement at `index` is not a TypeScript declaration.
+    ///
+    /// If the declaration is in the body of a namespace, the names it exports are added to
+    /// `exports`.
+    pub(super) fn declaration(
+        &mut self,
+        index: usize,
+        end: usize,
+        exports: Option<&mut Vec<String>>,
+    ) -> ParseResult<Option<usize>> {
+        match self.tokens[index].kind {
+            Kind::Keyword(Keyword::Export) => self.export(index, end, exports),
+            Kind::Keyword(Keyword::Import) => self.import(index),
+            Kind::Keyword(Keyword::Enum) => self.enumeration(index, index).map(Some),
+            Kind::Keyword(Keyword::Const) if self.is_keyword(index + 1, Keyword::Enum) => {
+                self.enumeration(index, index + 1).map(Some)
+            }
+            Kind::Identifier => {
+                if let Some(declaration_end) = self.type_only_declaration(index)? {
+                    self.blank_statement(index, declaration_end);
+                    return Ok(Some(declaration_end));
+                }
+                if self.is_word(index, "abstract")
+                    && self.is_keyword(index + 1, Keyword::Class)
+                    && self.on_same_line(index + 1)
+                {
+                    self.blank(index, index + 1);
+                    return Ok(Some(index + 1));
+                }
+                if self.is_namespace(index) {
+                    return self.namespace(index).map(Some);
+                }
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Finds the end of the interface, type alias or ambient declaration at `index`, if the
+    /// statement at `index` is one of them.
+    fn type_only_declaration(&self, index: usize) -> ParseResult<Option<usize>> {
+        let named = self.kind(index + 1) == Some(Kind::Identifier) && self.on_same_line(index + 1);
+        if self.is_word(index, "interface") && named {
+            return self.skip_body_declaration(index + 2).map(Some);
+        }
+        if self.is_word(index, "type")
+            && named
+            && (self.is(index + 2, Punctuator::Assign) || self.is(index + 2, Punctuator::LessThan))
+        {
+            let mut next = index + 2;
+            if self.is(next, Punctuator::LessThan) {
+                next = self
+                    .skip_type_parameters(next)
+                    .ok_or_else(|| self.unexpected(next))?;
+            }
+            if !self.is(next, Punctuator::Assign) {
+                return Err(self.unexpected(next));
+            }
+            next = self
+                .skip_type(next + 1)
+                .ok_or_else(|| self.expected_type(next + 1))?;
+            if self.is(next, Punctuator::Semicolon) {
+                next += 1;
+            }
+            return Ok(Some(next));
+        }
+        if self.is_word(index, "declare") && self.is_name(index + 1) && self.on_same_line(index + 1)
+        {
+            return self.skip_ambient_declaration(index + 1).map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Finds the end of the declaration after a `declare` keyword at `index`.
+    fn skip_ambient_declaration(&self, index: usize) -> ParseResult<usize> {
+        match self.tokens[index].kind {
+            Kind::Keyword(Keyword::Var | Keyword::Let | Keyword::Const)
+                if !self.is_keyword(index + 1, Keyword::Enum) =>
+            {
+                let mut next = index + 1;
+                loop {
+                    next = match self.kind(next) {
+                        Some(Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket)) => {
+                            self.matching[next] + 1
+                        }
+                        Some(Kind::Identifier | Kind::Keyword(_)) => next + 1,
+                        _ => return Err(self.unexpected(next)),
+                    };
+                    if self.is(next, Punctuator::Colon) {
+                        next = self
+                            .skip_type(next + 1)
+                            .ok_or_else(|| self.expected_type(next + 1))?;
+                    }
+                    if self.is(next, Punctuator::Assign) {
+                        next = self.expression_end(next + 1, self.tokens.len());
+                    }
+                    if !self.is(next, Punctuator::Comma) {
+                        break;
+                    }
+                    next += 1;
+                }
+                if self.is(next, Punctuator::Semicolon) {
+                    next += 1;
+                }
+                Ok(next)
+            }
+            Kind::Keyword(Keyword::Async | Keyword::Function) => {
+                let mut next = index + 1;
+                if self.is_keyword(index, Keyword::Async) {
+                    next += 1;
+                }
+                if self.is(next, Punctuator::Mul) {
+                    next += 1;
+                }
+                next += 1;
+                if self.is(next, Punctuator::LessThan) {
+                    next = self
+                        .skip_type_parameters(next)
+                        .ok_or_else(|| self.unexpected(next))?;
+                }
+                if !self.is(next, Punctuator::OpenParen) {
+                    return Err(self.unexpected(next));
+                }
+                next = self.matching[next] + 1;
+                if self.is(next, Punctuator::Colon) {
+                    next = self
+                        .skip_return_type(next + 1)
+                        .ok_or_else(|| self.expected_type(next + 1))?;
+                }
+                if self.is(next, Punctuator::Semicolon) {
+                    next += 1;
+                }
+                Ok(next)
+            }
+            _ => match self.type_only_declaration(index)? {
+                Some(end) => Ok(end),
+                None => self.skip_body_declaration(index),
+            },
+        }
+    }
+
+    /// Finds the end of a declaration with a body, like an interface, an ambient class or an
+    /// ambient namespace, starting the search at `index`.
+    fn skip_body_declaration(&self, mut index: usize) -> ParseResult<usize> {
+        while let Some(kind) = self.kind(index) {
+            index = match kind {
+                Kind::Punctuator(Punctuator::OpenBlock) => return Ok(self.matching[index] + 1),
+                Kind::Punctuator(Punctuator::Semicolon) => return Ok(index + 1),
+                Kind::Punctuator(Punctuator::OpenParen | Punctuator::OpenBracket) => {
+                    self.matching[index] + 1
+                }
+                Kind::Punctuator(Punctuator::LessThan) => self
+                    .skip_type_parameters(index)
+                    .or_else(|| self.skip_type_arguments(index))
+                    .ok_or_else(|| self.unexpected(index))?,
+                _ => index + 1,
+            };
+        }
+        Err(Error::AbruptEnd)
+    }
+
+    /// Erases the TypeScript syntax of the `export` declaration at `index`.
+    fn export(
+        &mut self,
+        index: usize,
+        end: usize,
+        exports: Option<&mut Vec<String>>,
+    ) -> ParseResult<Option<usize>> {
+        let next = index + 1;
+        if self.is_word(next, "type")
+            && (self.is(next + 1, Punctuator::OpenBlock) || self.is(next + 1, Punctuator::Mul))
+        {
+            let statement_end = self.statement_end(next);
+            self.blank_statement(index, statement_end);
+            return Ok(Some(statement_end));
+        }
+        if self.is_word(next, "as") && self.is_word(next + 1, "namespace") {
+            let statement_end = self.statement_end(next);
+            self.blank_statement(index, statement_end);
+            return Ok(Some(statement_end));
+        }
+        if self.is(next, Punctuator::Assign) {
+            return Err(Error::general(
+                "`export =` is not supported in TypeScript modules, use `export default` instead",
+                self.tokens[next].position,
+            ));
+        }
+        if self.is_keyword(next, Keyword::Import) {
+            return Err(self.import_alias(next));
+        }
+
+        let declaration = if self.is_keyword(next, Keyword::Default) {
+            next + 1
+        } else {
+            next
+        };
+        if let Some(declaration_end) = self.type_only_declaration(declaration)? {
+            self.blank_statement(index, declaration_end);
+            return Ok(Some(declaration_end));
+        }
+
+        match exports {
+            None if self.is(next, Punctuator::OpenBlock) => {
+                let close = self.matching[next];
+                self.type_specifiers(next);
+                Ok(Some(close + 1))
+            }
+            None => Ok(None),
+            Some(exports) => {
+                // Exports of namespaces are assigned as properties of the namespace.
+                if declaration != next || self.is(next, Punctuator::OpenBlock) {
+                    return Err(Error::general(
+                        "namespaces can only export declarations",
+                        self.tokens[next].position,
+                    ));
+                }
+                self.blank(index, next);
+                for name in self.declared_names(next, end)? {
+                    if !exports.contains(&name) {
+                        exports.push(name);
+                    }
+                }
+                Ok(Some(next))
+            }
+        }
+    }
+
+    /// Erases the TypeScript syntax of the `import` declaration at `index`.
+    fn import(&mut self, index: usize) -> ParseResult<Option<usize>> {
+        let mut next = index + 1;
+        let default_import_named_type =
+            self.is_word(next + 1, "from") && self.kind(next + 2) == Some(Kind::String);
+        if self.is_word(next, "type")
+            && !default_import_named_type
+            && matches!(
+                self.kind(next + 1),
+                Some(
+                    Kind::Identifier
+                        | Kind::Keyword(_)
+                        | Kind::Punctuator(Punctuator::OpenBlock | Punctuator::Mul)
+                )
+            )
+        {
+            let statement_end = self.statement_end(next);
+            self.blank_statement(index, statement_end);
+            return Ok(Some(statement_end));
+        }
+        if self.kind(next) == Some(Kind::Identifier) && self.is(next + 1, Punctuator::Assign) {
+            return Err(self.import_alias(index));
+        }
+
+        if self.kind(next) == Some(Kind::Identifier) && self.is(next + 1, Punctuator::Comma) {
+            next += 2;
+        }
+        if self.is(next, Punctuator::OpenBlock) {
+            self.type_specifiers(next);
+            return Ok(Some(self.matching[next] + 1));
+        }
+        Ok(None)
+    }
+
+    /// Creates the error for an import alias declaration, like `import a = require("a")`.
+    fn import_alias(&self, index: usize) -> Error {
+        Error::general(
+            "import alias declarations are not supported in TypeScript code, use `import` \
+            declarations instead",
+            self.tokens[index].position,
+        )
+    }
+
+    /// Erases the type-only specifiers of the import or export specifiers in the braces at
+    /// `open`, like `type A` in `import { type A, B } from "a"`.
+    fn type_specifiers(&mut self, open: usize) {
+        let close = self.matching[open];
+        let mut index = open + 1;
+        while index < close {
+            let mut end = index;
+            while end < close && !self.is(end, Punctuator::Comma) {
+                end += 1;
+            }
+            let type_only = self.is_word(index, "type")
+                && index + 1 < end
+                && !(self.is_word(index + 1, "as") && index + 2 == end);
+            if type_only {
+                let erased_end = if end < close { end + 1 } else { end };
+                self.blank(index, erased_end);
+            }
+            index = end + 1;
+        }
+    }
+
+    /// Gets the names declared by the declaration at `index`, in the body of a namespace.
+    fn declared_names(&self, index: usize, end: usize) -> ParseResult<Vec<String>> {
+        let mut next = index;
+        if self.is_keyword(next, Keyword::Async) || self.is_word(next, "abstract") {
+            next += 1;
+        }
+        match self.kind(next) {
+            Some(Kind::Keyword(Keyword::Function)) => {
+                next += 1;
+                if self.is(next, Punctuator::Mul) {
+                    next += 1;
+                }
+            }
+            Some(Kind::Keyword(Keyword::Class | Keyword::Enum)) => next += 1,
+            Some(Kind::Keyword(Keyword::Const)) if self.is_keyword(next + 1, Keyword::Enum) => {
+                next += 2;
+            }
+            Some(Kind::Identifier) if self.is_namespace(next) => next += 1,
+            Some(Kind::Keyword(Keyword::Var | Keyword::Let | Keyword::Const)) => {
+                return self.declared_variables(next + 1, end);
+            }
+            _ => return Err(self.unexpected(next)),
+        }
+        if !self.is_name(next) {
+            return Err(self.unexpected(next));
+        }
+        Ok(vec![self.text(next)])
+    }
+
+    /// Gets the names declared by the list of variable declarations at `index`.
+    fn declared_variables(&self, mut index: usize, end: usize) -> ParseResult<Vec<String>> {
+        let mut names = Vec::new();
+        loop {
+            if !self.is_name(index) {
+                return Err(Error::general(
+                    "namespaces can only export variables declared with identifiers",
+                    self.tokens
+                        .get(index)
+                        .map_or(self.tokens[index - 1].position, |token| token.position),
+                ));
+            }
+            names.push(self.text(index));
+            index = self.expression_end(index + 1, end);
+            if !self.is(index, Punctuator::Comma) {
+                return Ok(names);
+            }
+            index += 1;
+        }
+    }
+
+    /// Returns `true` if the statement at `index` is a namespace declaration, like
+    /// `namespace A {}` or `module A.B {}`.
+    fn is_namespace(&self, index: usize) -> bool {
+        (self.is_word(index, "namespace") || self.is_word(index, "module"))
+            && self.kind(index + 1) == Some(Kind::Identifier)
+            && self.on_same_line(index + 1)
+            && (self.is(index + 2, Punctuator::OpenBlock) || self.is(index + 2, Punctuator::Dot))
+    }
+
+    /// Rewrites the namespace declaration at `index` into a function that assigns the exports
+    /// of the namespace to an object.
+    fn namespace(&mut self, index: usize) -> ParseResult<usize> {
+        if self.is(index + 2, Punctuator::Dot) {
+            return Err(Error::general(
+                "namespaces with qualified names are not supported, nest the namespaces instead",
+                self.tokens[index + 2].position,
+            ));
+        }
+        let name = self.text(index + 1);
+        let open = index + 2;
+        let close = self.matching[open];
+
+        self.blank(index, open + 1);
+        self.insert(
+            self.tokens[index].start,
+            format!("var {name}; (function ({name}) {{"),
+        );
+        let mut exports = Vec::new();
+        self.scan(open + 1, close, Some(&mut exports))?;
+
+        let mut closing = String::new();
+        for export in exports {
+            let _ = write!(closing, " {name}.{export} = {export};");
+        }
+        let _ = write!(closing, " }})({name} || ({name} = {{}}));");
+        self.blank(close, close + 1);
+        self.insert(self.tokens[close].start, closing);
+        Ok(close + 1)
+    }
+
+    /// Rewrites the enum declaration at `start` into a function that assigns the members of the
+    /// enum to an object, like `tsc` does.
+    ///
+    /// Numeric members are also mapped from their values to their names. The members can be used
+    /// by name in the initializers of the next members.
+    fn enumeration(&mut self, start: usize, keyword: usize) -> ParseResult<usize> {
+        if self.kind(keyword + 1) != Some(Kind::Identifier) {
+            return Err(self.unexpected(keyword + 1));
+        }
+        let name = self.text(keyword + 1);
+        let open = keyword + 2;
+        if !self.is(open, Punctuator::OpenBlock) {
+            return Err(self.unexpected(open));
+        }
+        let close = self.matching[open];
+
+        let mut code = format!("var {name}; (function ({name}) {{");
+        let mut previous: Option<String> = None;
+        let mut index = open + 1;
+        while index < close {
+            let (key, local) = match self.tokens[index].kind {
+                Kind::Identifier => {
+                    let member = self.text(index);
+                    let local = (member != name).then(|| member.clone());
+                    (format!("\"{member}\""), local)
+                }
+                Kind::Keyword(_) => (format!("\"{}\"", self.text(index)), None),
+                Kind::String => (self.text(index), None),
+                _ => return Err(self.unexpected(index)),
+            };
+            index += 1;
+
+            let (mut value, string) = if self.is(index, Punctuator::Assign) {
+                let value_end = self.expression_end(index + 1, close);
+                let value = self.code(index + 1, value_end);
+                let string = value_end == index + 2
+                    && matches!(self.kind(index + 1), Some(Kind::String | Kind::Template));
+                index = value_end;
+                (value, string)
+            } else {
+                let value =
+                    previous.map_or_else(|| "0".to_owned(), |previous| format!("{previous} + 1"));
+                (value, false)
+            };
+            if let Some(local) = &local {
+                let _ = write!(code, " const {local} = {value};");
+                value.clone_from(local);
+            }
+            if string {
+                let _ = write!(code, " {name}[{key}] = {value};");
+            } else {
+                let _ = write!(code, " {name}[{name}[{key}] = {value}] = {key};");
+            }
+            previous = Some(local.unwrap_or_else(|| format!("{name}[{key}]")));
+
+            if self.is(index, Punctuator::Comma) {
+                index += 1;
+            } else if index != close {
+                return Err(self.unexpected(index));
+            }
+        }
+        let _ = write!(code, " }})({name} || ({name} = {{}}));");
+
+        self.blank(start, close + 1);
+        self.insert(self.tokens[start].start, code);
+        Ok(close + 1)
+    }
+}
@@ -0,0 +1,787 @@
+//! Boa's TypeScript front end.
+//!
+//! TypeScript sources are turned into JavaScript before being parsed, by replacing type
+//! annotations, interfaces, type aliases and the rest of the type-only syntax with whitespace, and
+//! by rewriting the few TypeScript constructs that have a runtime meaning, like enums, namespaces
+//! and parameter properties, into the equivalent JavaScript.
+//!
+//! Each code point of the JavaScript source records where it comes from, so the lexer keeps the
+//! positions and the source text of the TypeScript source: the whitespace that replaces a type
+//! is collected as the erased code, and the inserted code takes no space, having the position of
+//! the code that follows it.
+//!
+//! The types are never checked, and constructs that need type information to be compiled are
+//! compiled like their plain counterparts (e.g. a `const enum` is not inlined). Imports that only
+//! bring types into scope must be marked with `import type` or with inline `type` modifiers, as
+//! with the `verbatimModuleSyntax` option of `tsc`.
+
+mod class;
+mod declaration;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    Error,
+    error::ParseResult,
+    lexer::{InputElement, Lexer, TokenKind},
+    source::UTF16Input,
+};
+use boa_ast::{Keyword, Position, Punctuator, Spanned};
+use boa_interner::Interner;
+
+/// Where a code point of the JavaScript source produced by [`strip`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Origin {
+    /// The code point of the TypeScript source, kept as it is.
+    Source,
+    /// A space, which replaces the given code point of the TypeScript source.
+    Blanked(u32),
+    /// A code point of the JavaScript code that replaces a TypeScript construct, which is not
+    /// part of the TypeScript source.
+    Inserted,
+}
+
+/// Erases the TypeScript syntax of a UTF-16 encoded source, returning the code points of the
+/// JavaScript source with their origin.
+pub(crate) fn strip(source: &[u16], module: bool) -> ParseResult<Vec<(u32, Origin)>> {
+    let mut stripper = Stripper::new(source, module)?;
+    stripper.scan(0, stripper.tokens.len(), None)?;
+    Ok(stripper.finish())
+}
+
+/// The kind of a token of a TypeScript source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Identifier,
+    PrivateIdentifier,
+    Keyword(Keyword),
+    Punctuator(Punctuator),
+    String,
+    /// A numeric, boolean, `null` or regular expression literal.
+    Literal,
+    /// A template literal without substitutions.
+    Template,
+    /// The part of a template literal up to its first substitution.
+    TemplateHead,
+    /// The part of a template literal between two substitutions.
+    TemplateMiddle,
+    /// The part of a template literal after its last substitution.
+    TemplateTail,
+}
+
+/// A token of a TypeScript source.
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    /// The index of the first code unit of the token in the source.
+    start: usize,
+    /// The index after the last code unit of the token in the source.
+    end: usize,
+    position: Position,
+    newline_before: bool,
+    /// If the token can be the last token of an expression, meaning that a `/` after it is a
+    /// division and a `<` after it is a comparison or the start of type arguments.
+    ends_expression: bool,
+}
+
+/// Records the edits that turn a TypeScript source into JavaScript.
+#[derive(Debug)]
+struct Stripper<'a> {
+    source: &'a [u16],
+    tokens: Vec<Token>,
+    /// The index of the token matching each parenthesis, bracket, brace or template part.
+    matching: Vec<usize>,
+    /// The code units of the source replaced by whitespace.
+    blanked: Vec<bool>,
+    /// The code inserted before a code unit of the source, in insertion order.
+    insertions: Vec<(usize, String)>,
+}
+
+impl<'a> Stripper<'a> {
+    /// Tokenizes a TypeScript source.
+    fn new(source: &'a [u16], module: bool) -> ParseResult<Self> {
+        let mut interner = Interner::default();
+        let mut lexer = Lexer::new(UTF16Input::new(source));
+        lexer.set_module(module);
+        lexer.set_goal(InputElement::HashbangOrRegExp);
+
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut matching = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        let mut newline_before = false;
+        loop {
+            if let Some(last) = tokens.last() {
+                lexer.set_goal(if last.ends_expression {
+                    InputElement::Div
+                } else {
+                    InputElement::RegExp
+                });
+            }
+            let Some(token) = lexer.next_no_skip(&mut interner)? else {
+                break;
+            };
+            let start = token.linear_span().start().pos();
+            let mut end = token.linear_span().end().pos();
+            let position = token.span().start();
+            let kind = match token.kind() {
+                TokenKind::LineTerminator => {
+                    newline_before = true;
+                    continue;
+                }
+                TokenKind::Comment => {
+                    newline_before |= position.line_number() != token.span().end().line_number();
+                    continue;
+                }
+                TokenKind::EOF => break,
+                TokenKind::IdentifierName(_) => Kind::Identifier,
+                TokenKind::PrivateIdentifier(_) => Kind::PrivateIdentifier,
+                TokenKind::Keyword((keyword, _)) => Kind::Keyword(*keyword),
+                TokenKind::StringLiteral(_) => Kind::String,
+                TokenKind::BooleanLiteral(_)
+                | TokenKind::NullLiteral(_)
+                | TokenKind::NumericLiteral(_)
+                | TokenKind::RegularExpressionLiteral(..)
+                | TokenKind::JsxText(..) => Kind::Literal,
+                TokenKind::TemplateNoSubstitution(_) => Kind::Template,
+                TokenKind::TemplateMiddle(_) => Kind::TemplateHead,
+                TokenKind::Punctuator(Punctuator::CloseBlock)
+                    if open.last().is_some_and(|&index| {
+                        matches!(
+                            tokens[index].kind,
+                            Kind::TemplateHead | Kind::TemplateMiddle
+                        )
+                    }) =>
+                {
+                    let template = lexer.lex_template(token.start_group(), &mut interner)?;
+                    end = template.linear_span().end().pos();
+                    if matches!(template.kind(), TokenKind::TemplateMiddle(_)) {
+                        Kind::TemplateMiddle
+                    } else {
+                        Kind::TemplateTail
+                    }
+                }
+                TokenKind::Punctuator(punctuator) => {
+                    // `>>` and similar tokens are split, since they can close nested type
+                    // arguments.
+                    let pieces: &[Punctuator] = match punctuator {
+                        Punctuator::RightSh => &[Punctuator::GreaterThan; 2],
+                        Punctuator::URightSh => &[Punctuator::GreaterThan; 3],
+                        Punctuator::GreaterThanOrEq => {
+                            &[Punctuator::GreaterThan, Punctuator::Assign]
+                        }
+                        Punctuator::AssignRightSh => &[
+                            Punctuator::GreaterThan,
+                            Punctuator::GreaterThan,
+                            Punctuator::Assign,
+                        ],
+                        Punctuator::AssignURightSh => &[
+                            Punctuator::GreaterThan,
+                            Punctuator::GreaterThan,
+                            Punctuator::GreaterThan,
+                            Punctuator::Assign,
+                        ],
+                        _ => &[],
+                    };
+                    if !pieces.is_empty() {
+                        for (offset, piece) in pieces.iter().enumerate() {
+                            let offset_column = u32::try_from(offset).unwrap_or(u32::MAX);
+                            tokens.push(Token {
+                                kind: Kind::Punctuator(*piece),
+                                start: start + offset,
+                                end: start + offset + 1,
+                                position: Position::new(
+                                    position.line_number(),
+                                    position.column_number() + offset_column,
+                                ),
+                                newline_before: newline_before && offset == 0,
+                                ends_expression: false,
+                            });
+                            matching.push(0);
+                        }
+                        newline_before = false;
+                        continue;
+                    }
+                    Kind::Punctuator(*punctuator)
+                }
+            };
+
+            let index = tokens.len();
+            let mut ends_expression = match kind {
+                Kind::Identifier
+                | Kind::PrivateIdentifier
+                | Kind::String
+                | Kind::Literal
+                | Kind::Template
+                | Kind::TemplateTail => true,
+                Kind::Keyword(keyword) => matches!(
+                    keyword,
+                    Keyword::This
+                        | Keyword::Super
+                        | Keyword::Let
+                        | Keyword::Async
+                        | Keyword::Of
+                        | Keyword::Using
+                ),
+                Kind::Punctuator(punctuator) => matches!(
+                    punctuator,
+                    Punctuator::CloseParen | Punctuator::CloseBracket
+                ),
+                Kind::TemplateHead | Kind::TemplateMiddle => false,
+            };
+            matching.push(0);
+
+            match kind {
+                Kind::Punctuator(
+                    Punctuator::CloseParen | Punctuator::CloseBracket | Punctuator::CloseBlock,
+                )
+                | Kind::TemplateMiddle
+                | Kind::TemplateTail => {
+                    let Some(opener) = open.pop() else {
+                        return Err(Error::general(
+                            format!("unexpected '{}'", token.to_string(&interner)),
+                            position,
+                        ));
+                    };
+                    matching[opener] = index;
+                    matching[index] = opener;
+
+                    // The parenthesis closing the condition of a statement, like in
+                    // `if (a) /b/.exec(c)`, is not the end of an expression.
+                    if kind == Kind::Punctuator(Punctuator::CloseParen)
+                        && opener > 0
+                        && matches!(
+                            tokens[opener - 1].kind,
+                            Kind::Keyword(
+                                Keyword::If | Keyword::While | Keyword::For | Keyword::With
+                            )
+                        )
+                    {
+                        ends_expression = false;
+                    }
+                }
+                _ => {}
+            }
+            if matches!(
+                kind,
+                Kind::Punctuator(
+                    Punctuator::OpenParen | Punctuator::OpenBracket | Punctuator::OpenBlock
+                ) | Kind::TemplateHead
+                    | Kind::TemplateMiddle
+            ) {
+                open.push(index);
+            }
+
+            tokens.push(Token {
+                kind,
+                start,
+                end,
+                position,
+                newline_before,
+                ends_expression,
+            });
+            newline_before = false;
+        }
+
+        if !open.is_empty() {
+            return Err(Error::AbruptEnd);
+        }
+
+        Ok(Self {
+            source,
+            tokens,
+            matching,
+            blanked: vec![false; source.len()],
+            insertions: Vec::new(),
+        })
+    }
+
+    /// Builds the JavaScript source from the recorded edits.
+    fn finish(mut self) -> Vec<(u32, Origin)> {
+        self.insertions.sort_by_key(|(at, _)| *at);
+        let mut insertions = self.insertions.into_iter().peekable();
+        let mut output = Vec::with_capacity(self.source.len());
+        let mut index = 0;
+        while index < self.source.len() {
+            while let Some((_, code)) = insertions.next_if(|(at, _)| *at == index) {
+                output.extend(code.chars().map(|ch| (u32::from(ch), Origin::Inserted)));
+            }
+            let unit = self.source[index];
+            let blanked = self.blanked[index] && !is_line_terminator(unit);
+            index += 1;
+            let mut cp = u32::from(unit);
+            if (0xD800..0xDC00).contains(&unit)
+                && let Some(&next) = self.source.get(index)
+                && (0xDC00..0xE000).contains(&next)
+            {
+                cp = 0x10000 + ((cp - 0xD800) << 10) + (u32::from(next) - 0xDC00);
+                index += 1;
+            }
+            output.push(if blanked {
+                (u32::from(b' '), Origin::Blanked(cp))
+            } else {
+                (cp, Origin::Source)
+            });
+        }
+        for (_, code) in insertions {
+            output.extend(code.chars().map(|ch| (u32::from(ch), Origin::Inserted)));
+        }
+        output
+    }
+
+    /// Gets the kind of the token at `index`, if any.
+    fn kind(&self, index: usize) -> Option<Kind> {
+        self.tokens.get(index).map(|token| token.kind)
+    }
+
+    /// Returns `true` if the token at `index` is the given punctuator.
+    fn is(&self, index: usize, punctuator: Punctuator) -> bool {
+        self.kind(index) == Some(Kind::Punctuator(punctuator))
+    }
+
+    /// Returns `true` if the token at `index` is the given keyword.
+    fn is_keyword(&self, index: usize, keyword: Keyword) -> bool {
+        self.kind(index) == Some(Kind::Keyword(keyword))
+    }
+
+    /// Returns `true` if the token at `index` is the given identifier, like `type` or `as`.
+    fn is_word(&self, index: usize, word: &str) -> bool {
+        self.tokens.get(index).is_some_and(|token| {
+            token.kind == Kind::Identifier
+                && word
+                    .encode_utf16()
+                    .eq(self.source[token.start..token.end].iter().copied())
+        })
+    }
+
+    /// Returns `true` if the token at `index` is an identifier or a keyword.
+    fn is_name(&self, index: usize) -> bool {
+        matches!(self.kind(index), Some(Kind::Identifier | Kind::Keyword(_)))
+    }
+
+    /// Returns `true` if the token at `index` exists and is on the same line as the token before.
+    fn on_same_line(&self, index: usize) -> bool {
+        self.tokens
+            .get(index)
+            .is_some_and(|token| !token.newline_before)
+    }
+
+    /// Gets the source text of the token at `index`.
+    fn text(&self, index: usize) -> String {
+        let token = &self.tokens[index];
+        String::from_utf16_lossy(&self.source[token.start..token.end])
+    }
+
+    /// Gets the source text of the tokens in `start..end`, without comments or line terminators.
+    fn code(&self, start: usize, end: usize) -> String {
+        let mut code = String::new();
+        for index in start..end {
+            if index > start && self.tokens[index - 1].end != self.tokens[index].start {
+                code.push(' ');
+            }
+            code.push_str(&self.text(index));
+        }
+        code
+    }
+
+    /// Creates an error for an unexpected token at `index`.
+    fn unexpected(&self, index: usize) -> Error {
+        match self.tokens.get(index) {
+            Some(token) => Error::general(
+                format!("unexpected '{}' in TypeScript code", self.text(index)),
+                token.position,
+            ),
+            None => Error::AbruptEnd,
+        }
+    }
+
+    /// Creates an error for a type that could not be parsed at `index`.
+    fn expected_type(&self, index: usize) -> Error {
+        match self.tokens.get(index) {
+            Some(token) => Error::general("expected a type", token.position),
+            None => Error::AbruptEnd,
+        }
+    }
+
+    /// Replaces the tokens in `start..end` and the code between them with whitespace.
+    fn blank(&mut self, start: usize, end: usize) {
+        if start < end {
+            let (start, end) = (self.tokens[start].start, self.tokens[end - 1].end);
+            self.blanked[start..end].fill(true);
+        }
+    }
+
+    /// Replaces a whole statement in `start..end` with whitespace.
+    ///
+    /// A semicolon is left if the next statement could otherwise continue the previous one.
+    fn blank_statement(&mut self, start: usize, end: usize) {
+        self.blank(start, end);
+        let continues = matches!(
+            self.kind(end),
+            Some(
+                Kind::Punctuator(
+                    Punctuator::OpenParen
+                        | Punctuator::OpenBracket
+                        | Punctuator::Add
+                        | Punctuator::Sub
+                ) | Kind::Template
+                    | Kind::TemplateHead
+            )
+        );
+        let terminated = start == 0
+            || matches!(
+                self.tokens[start - 1].kind,
+                Kind::Punctuator(
+                    Punctuator::Semicolon | Punctuator::OpenBlock | Punctuator::CloseBlock
+                )
+            );
+        if continues && !terminated {
+            self.insert(self.tokens[start].start, ";".to_owned());
+        }
+    }
+
+    /// Inserts code before the code unit at `at`.
+    fn insert(&mut self, at: usize, code: String) {
+        self.insertions.push((at, code));
+    }
+
+    /// Returns `true` if the token at `index` can start a statement.
+    fn starts_statement(&self, index: usize, start: usize) -> bool {
+        index == start
+            || self.tokens[index].newline_before
+            || matches!(
+                self.tokens[index - 1].kind,
+                Kind::Punctuator(
+                    Punctuator::Semicolon | Punctuator::OpenBlock | Punctuator::CloseBlock
+                ) | Kind::Keyword(Keyword::Export | Keyword::Default)
+            )
+    }
+
+    /// Finds the end of the expression starting at `start`, which is the first `,`, `;` or
+    /// unmatched closing token, the start of the next statement on a new line, or `limit`.
+    fn expression_end(&self, start: usize, limit: usize) -> usize {
+        let mut index = start;
+        while index < limit {
+            let token = &self.tokens[index];
+            if index > start && token.newline_before && self.inserts_semicolon(index) {
+                return index;
+            }
+            match token.kind {
+                Kind::Punctuator(
+                    Punctuator::Comma
+                    | Punctuator::Semicolon
+                    | Punctuator::CloseParen
+                    | Punctuator::CloseBracket
+                    | Punctuator::CloseBlock,
+                )
+                | Kind::TemplateMiddle
+                | Kind::TemplateTail => return index,
+                Kind::Punctuator(
+                    Punctuator::OpenParen | Punctuator::OpenBracket | Punctuator::OpenBlock,
+                )
+                | Kind::TemplateHead => index = self.matching[index] + 1,
+                Kind::Punctuator(Punctuator::LessThan) => {
+                    index = self.skip_angle_brackets(index).unwrap_or(index + 1);
+                }
+                _ => index += 1,
+            }
+        }
+        limit
+    }
+
+    /// Returns `true` if a semicolon is automatically inserted before the token at `index`,
+    /// assuming it is on a new line.
+    fn inserts_semicolon(&self, index: usize) -> bool {
+        let previous = &self.tokens[index - 1];
+        if !previous.ends_expression && previous.kind != Kind::Punctuator(Punctuator::CloseBlock) {
+            return false;
+        }
+        match self.tokens[index].kind {
+            Kind::Identifier
+            | Kind::PrivateIdentifier
+            | Kind::String
+            | Kind::Literal
+            | Kind::Punctuator(Punctuator::OpenBlock | Punctuator::Inc | Punctuator::Dec) => true,
+            Kind::Keyword(keyword) => !matches!(keyword, Keyword::In | Keyword::InstanceOf),
+            _ => false,
+        }
+    }
+
+    /// Finds the end of the statement starting at `start`.
+    fn statement_end(&self, start: usize) -> usize {
+        let mut index = start;
+        while let Some(token) = self.tokens.get(index) {
+            if index > start && token.newline_before && self.inserts_semicolon(index) {
+                return index;
+            }
+            match token.kind {
+                Kind::Punctuator(Punctuator::Semicolon) => return index + 1,
+                Kind::Punctuator(
+                    Punctuator::CloseParen | Punctuator::CloseBracket | Punctuator::CloseBlock,
+                ) => {
+                    return index;
+                }
+                Kind::Punctuator(
+                    Punctuator::OpenParen | Punctuator::OpenBracket | Punctuator::OpenBlock,
+                )
+                | Kind::TemplateHead => index = self.matching[index] + 1,
+                _ => index += 1,
+            }
+        }
+        index
+    }
+
+    /// Erases the TypeScript syntax of the tokens in `start..end`.
+    ///
+    /// If the tokens are the body of a namespace, the names exported by its declarations are
+    /// added to `exports`.
+    fn scan(
+        &mut self,
+        start: usize,
+        end: usize,
+        mut exports: Option<&mut Vec<String>>,
+    ) -> ParseResult<()> {
+        let mut depth = 0usize;
+        let mut index = start;
+        while index < end {
+            if self.starts_statement(index, start)
+                && let Some(next) =
+                    self.declaration(index, end, exports.as_deref_mut().filter(|_| depth == 0))?
+            {
+                index = next;
+                continue;
+            }
+
+            // Keywords used as property names, like in `a.class`.
+            let property = index > start
+                && matches!(
+                    self.tokens[index - 1].kind,
+                    Kind::Punctuator(Punctuator::Dot | Punctuator::Optional)
+                );
+            index =
+                match self.tokens[index].kind {
+                    Kind::Keyword(_) if property => index + 1,
+                    Kind::Keyword(Keyword::Function) => self.function(index)?,
+                    Kind::Keyword(Keyword::Class) => self.class(index, end)?,
+                    Kind::Keyword(
+                        Keyword::Var | Keyword::Let | Keyword::Const | Keyword::Using,
+                    ) if self.starts_binding(index) => self.variables(index + 1, end)?,
+                    Kind::Punctuator(Punctuator::OpenParen) => self.parenthesized(index)?,
+                    Kind::Punctuator(Punctuator::LessThan) => self.angle_brackets(index),
+                    Kind::Punctuator(Punctuator::Not)
+                        if index > start
+                            && self.tokens[index - 1].ends_expression
+                            && !self.tokens[index].newline_before =>
+                    {
+                        // A non-null assertion, like `a!.b`.
+                        self.blank(index, index + 1);
+                        index + 1
+                    }
+                    Kind::Identifier
+                        if (self.is_word(index, "as") || self.is_word(index, "satisfies"))
+                            && index > start
+                            && !self.tokens[index].newline_before
+                            && (self.tokens[index - 1].ends_expression
+                                || self.is(index - 1, Punctuator::CloseBlock)) =>
+                    {
+                        match self.skip_type(index + 1) {
+                            Some(type_end) => {
+                                self.blank(index, type_end);
+                                type_end
+                            }
+                            None => index + 1,
+                        }
+                    }
+                    Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket)
+                    | Kind::TemplateHead => {
+                        depth += 1;
+                        index + 1
+                    }
+                    Kind::Punctuator(Punctuator::CloseBlock | Punctuator::CloseBracket)
+                    | Kind::TemplateTail => {
+                        depth = depth.saturating_sub(1);
+                        index + 1
+                    }
+                    _ => index + 1,
+                };
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the `var`, `let`, `const` or `using` keyword at `index` starts a
+    /// declaration.
+    fn starts_binding(&self, index: usize) -> bool {
+        if self.is_keyword(index, Keyword::Using) {
+            return self.kind(index + 1) == Some(Kind::Identifier) && self.on_same_line(index + 1);
+        }
+        matches!(
+            self.kind(index + 1),
+            Some(
+                Kind::Identifier
+                    | Kind::Keyword(
+                        Keyword::Let
+                            | Keyword::Async
+                            | Keyword::Of
+                            | Keyword::Yield
+                            | Keyword::Await
+                            | Keyword::Using
+                    )
+                    | Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket)
+            )
+        )
+    }
+
+    /// Erases the types of a list of variable declarations starting at `index`, like
+    /// `a: number = 1, b!: string`, returning the index after the list.
+    fn variables(&mut self, mut index: usize, end: usize) -> ParseResult<usize> {
+        loop {
+            index = self.binding(index)?;
+            if self.is(index, Punctuator::Not) {
+                self.blank(index, index + 1);
+                index += 1;
+            }
+            index = self.annotation(index)?;
+            if self.is(index, Punctuator::Assign) {
+                let initializer_end = self.expression_end(index + 1, end);
+                self.scan(index + 1, initializer_end, None)?;
+                index = initializer_end;
+            }
+            if index < end && self.is(index, Punctuator::Comma) {
+                index += 1;
+            } else {
+                return Ok(index);
+            }
+        }
+    }
+
+    /// Skips the binding identifier or pattern at `index`, erasing the TypeScript syntax of the
+    /// default values of a pattern.
+    fn binding(&mut self, index: usize) -> ParseResult<usize> {
+        match self.kind(index) {
+            Some(Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket)) => {
+                let close = self.matching[index];
+                self.scan(index + 1, close, None)?;
+                Ok(close + 1)
+            }
+            Some(Kind::Identifier | Kind::Keyword(_)) => Ok(index + 1),
+            _ => Err(self.unexpected(index)),
+        }
+    }
+
+    /// Erases the type annotation at `index`, like `: number`, if there is one.
+    fn annotation(&mut self, index: usize) -> ParseResult<usize> {
+        if !self.is(index, Punctuator::Colon) {
+            return Ok(index);
+        }
+        let end = self
+            .skip_type(index + 1)
+            .ok_or_else(|| self.expected_type(index + 1))?;
+        self.blank(index, end);
+        Ok(end)
+    }
+
+    /// Erases the return type at `index`, like `: number`, if there is one.
+    fn return_type(&mut self, index: usize) -> ParseResult<usize> {
+        if !self.is(index, Punctuator::Colon) {
+            return Ok(index);
+        }
+        let end = self
+            .skip_return_type(index + 1)
+            .ok_or_else(|| self.expected_type(index + 1))?;
+        self.blank(index, end);
+        Ok(end)
+    }
+
+    /// Erases the TypeScript syntax of the parenthesized code at `open`, which can be an
+    /// expression or the parameters of an arrow function or a method.
+    fn parenthesized(&mut self, open: usize) -> ParseResult<usize> {
+        let close = self.matching[open];
+        let before = open.checked_sub(1).map(|index| self.tokens[index].kind);
+        match before {
+            Some(Kind::Keyword(
+                Keyword::If | Keyword::While | Keyword::For | Keyword::Switch | Keyword::With,
+            )) => {}
+            Some(Kind::Keyword(Keyword::Await))
+                if open > 1 && self.is_keyword(open - 2, Keyword::For) => {}
+            Some(Kind::Keyword(Keyword::Catch)) => {
+                self.parameters(open, false)?;
+                return Ok(close + 1);
+            }
+            _ if self.is_parameter_list(open) => {
+                self.parameters(open, false)?;
+                return self.return_type(close + 1);
+            }
+            _ => {}
+        }
+        self.scan(open + 1, close, None)?;
+        Ok(close + 1)
+    }
+
+    /// Returns `true` if the parenthesized code at `open` is a list of parameters.
+    fn is_parameter_list(&self, open: usize) -> bool {
+        let close = self.matching[open];
+        // The parameters of methods are preceded by their name or their type parameters.
+        let named = open > 0
+            && match self.tokens[open - 1].kind {
+                Kind::Identifier
+                | Kind::PrivateIdentifier
+                | Kind::String
+                | Kind::Literal
+                | Kind::Punctuator(Punctuator::CloseBracket | Punctuator::GreaterThan) => true,
+                Kind::Keyword(keyword) => keyword != Keyword::Case,
+                _ => false,
+            };
+        match self.kind(close + 1) {
+            Some(Kind::Punctuator(Punctuator::Arrow)) => true,
+            Some(Kind::Punctuator(Punctuator::Colon)) => {
+                self.skip_return_type(close + 2).is_some_and(|end| {
+                    self.is(end, Punctuator::Arrow) || named && self.is(end, Punctuator::OpenBlock)
+                })
+            }
+            Some(Kind::Punctuator(Punctuator::OpenBlock)) => named,
+            _ => false,
+        }
+    }
+
+    /// Erases the TypeScript syntax of the `<` at `index`, which can start type arguments, type
+    /// parameters, a type assertion or be a comparison.
+    fn angle_brackets(&mut self, index: usize) -> usize {
+        match self.skip_angle_brackets(index) {
+            Some(end) => {
+                self.blank(index, end);
+                end
+            }
+            None => index + 1,
+        }
+    }
+
+    /// Skips the type arguments or type parameters starting with the `<` at `index`, if the `<`
+    /// is not a comparison.
+    fn skip_angle_brackets(&self, index: usize) -> Option<usize> {
+        if index > 0 && self.tokens[index - 1].ends_expression {
+            return self.type_arguments_of_call(index);
+        }
+
+        // A `<` can't start an expression in JavaScript, so this is either a generic arrow
+        // function like `<T>(a: T) => a`, or a type assertion like `<T>a`.
+        self.skip_type_parameters(index)
+            .or_else(|| self.skip_type_arguments(index))
+    }
+
+    /// Skips the type arguments at `index` if they are followed by the arguments of a call or
+    /// a tagged template, like in `a<T>(b)`.
+    fn type_arguments_of_call(&self, index: usize) -> Option<usize> {
+        let end = self.skip_type_arguments(index)?;
+        matches!(
+            self.kind(end),
+            Some(Kind::Punctuator(Punctuator::OpenParen) | Kind::Template | Kind::TemplateHead)
+        )
+        .then_some(end)
+    }
+}
+
+/// Returns `true` if the UTF-16 code unit is a line terminator.
+const fn is_line_terminator(unit: u16) -> bool {
+    matches!(unit, 0x000A | 0x000D | 0x2028 | 0x2029)
+}
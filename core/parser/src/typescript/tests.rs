@@ -0,0 +1,266 @@
+use super::strip;
+use crate::{Parser, Source};
+use boa_ast::scope::Scope;
+use boa_interner::Interner;
+
+/// Strips the TypeScript module `typescript`, and checks that the result is `javascript`,
+/// ignoring whitespace, that it keeps the lines of the source and that it can be parsed.
+///
+/// Blanked code is replaced by spaces, so the comparison removes whitespace entirely.
+#[track_caller]
+fn check(typescript: &str, javascript: &str) {
+    let source: Vec<u16> = typescript.encode_utf16().collect();
+    let stripped: String = strip(&source, true)
+        .expect("stripping failed")
+        .into_iter()
+        .map(|(cp, _)| char::from_u32(cp).expect("invalid code point"))
+        .collect();
+
+    assert_eq!(
+        stripped.split_whitespace().collect::<String>(),
+        javascript.split_whitespace().collect::<String>(),
+    );
+    assert_eq!(stripped.lines().count(), typescript.lines().count());
+    Parser::new(Source::from_bytes(&stripped))
+        .parse_module(&Scope::new_global(), &mut Interner::default())
+        .expect("stripped code should be valid JavaScript");
+}
+
+/// Checks that stripping the TypeScript module `typescript` fails with `message`.
+#[track_caller]
+fn check_error(typescript: &str, message: &str) {
+    let source: Vec<u16> = typescript.encode_utf16().collect();
+    let error = strip(&source, true).expect_err("stripping should fail");
+    assert!(
+        error.to_string().contains(message),
+        "unexpected error: {error}"
+    );
+}
+
+#[test]
+fn type_annotations() {
+    check(
+        "let a: number = 1, b!: string[];\n\
+         const { c, d }: { c: number; d?: string } = e;\n\
+         function f(g: number, h?: string, ...i: Array<number>): void {}\n\
+         const j = (k: string, l = 1): k is string => true;\n\
+         try {} catch (m: unknown) {}",
+        "let a = 1, b;\n\
+         const { c, d } = e;\n\
+         function f(g, h, ...i) {}\n\
+         const j = (k, l = 1) => true;\n\
+         try {} catch (m) {}",
+    );
+}
+
+#[test]
+fn complex_types() {
+    check(
+        "let a: (b: number) => void;\n\
+         let c: new () => Foo;\n\
+         let d: keyof typeof e | readonly string[] & { [K in T]?: K };\n\
+         let f: T extends U ? [infer V] : `g${T}`;\n\
+         let h: import(\"i\").J<K<L>>= m;\n\
+         function n(o: unknown): asserts o is string {}",
+        "let a;\n\
+         let c;\n\
+         let d;\n\
+         let f;\n\
+         let h = m;\n\
+         function n(o) {}",
+    );
+}
+
+#[test]
+fn interfaces_and_type_aliases() {
+    check(
+        "interface A<T> extends B<{ c: T }> {\n  d: T;\n}\n\
+         type E<F = string> =\n  | F\n  | number;\n\
+         export interface G {}\n\
+         export type H = G;\n\
+         let type = 1;\n\
+         type = 2;",
+        "let type = 1;\n\
+         type = 2;",
+    );
+}
+
+#[test]
+fn generics() {
+    check(
+        "function a<T extends object = {}>(b: T): T { return b; }\n\
+         const c = <T,>(d: T) => d;\n\
+         const e = new Map<string, Array<number>>();\n\
+         f<number>(1);\n\
+         g<number>`h`;\n\
+         const i = j < k, l = m > (n);",
+        "function a(b) { return b; }\n\
+         const c = (d) => d;\n\
+         const e = new Map();\n\
+         f(1);\n\
+         g`h`;\n\
+         const i = j < k, l = m > (n);",
+    );
+}
+
+#[test]
+fn assertions() {
+    check(
+        "const a = b as unknown as string;\n\
+         const c = { d: 1 } satisfies E;\n\
+         const f = g!.h![i!];\n\
+         const j = [1, 2] as const;\n\
+         if (k) !l && m();\n\
+         const n = `${o as string}`;",
+        "const a = b;\n\
+         const c = { d: 1 };\n\
+         const f = g.h[i];\n\
+         const j = [1, 2];\n\
+         if (k) !l && m();\n\
+         const n = `${o}`;",
+    );
+}
+
+#[test]
+fn ambient_declarations() {
+    check(
+        "declare const a: number;\n\
+         declare function b(c: string): void;\n\
+         declare class D { e(): void; }\n\
+         declare module \"f\" {\n  export const g: number;\n}\n\
+         declare global { interface Window {} }\n\
+         declare enum H { I }\n\
+         export declare let j: string;",
+        "",
+    );
+}
+
+#[test]
+fn imports_and_exports() {
+    check(
+        "import type { A } from \"a\";\n\
+         import type B from \"b\";\n\
+         import type from \"c\";\n\
+         import { type D, E, type F as G } from \"d\";\n\
+         import H, { type I } from \"h\";\n\
+         export type { A };\n\
+         export type * from \"j\";\n\
+         export { type D, E as K };",
+        "import type from \"c\";\n\
+         import { E, } from \"d\";\n\
+         import H, { } from \"h\";\n\
+         export { E as K };",
+    );
+}
+
+#[test]
+fn classes() {
+    check(
+        "export abstract class A<T> extends B<T> implements C, D<T> {\n\
+         \x20 private readonly e: number = 1;\n\
+         \x20 static f?: string;\n\
+         \x20 g!: T;\n\
+         \x20 declare h: number;\n\
+         \x20 abstract i(): void;\n\
+         \x20 [key: string]: unknown;\n\
+         \x20 public get j(): number { return this.e; }\n\
+         \x20 k<U>(u: U): U;\n\
+         \x20 k(u: any) { return u; }\n\
+         \x20 override l = (m: number): void => {};\n\
+         }",
+        "export class A extends B {\n\
+         \x20 e = 1;\n\
+         \x20 static f;\n\
+         \x20 g;\n\
+         \x20 get j() { return this.e; }\n\
+         \x20 k(u) { return u; }\n\
+         \x20 l = (m) => {};\n\
+         }",
+    );
+}
+
+#[test]
+fn overloads() {
+    check(
+        "function a(b: string): string;\n\
+         export function a(b: number): number;\n\
+         export function a(b: any) { return b; }",
+        "export function a(b) { return b; }",
+    );
+}
+
+#[test]
+fn parameter_properties() {
+    check(
+        "class A {\n  constructor(public b: number, private readonly c = 2, d: string) {}\n}\n\
+         class E extends A {\n  constructor(protected f: number) {\n    super(f, 2, \"\");\n    g();\n  }\n}",
+        "class A {\n  constructor(b, c = 2, d) { this.b = b; this.c = c;}\n}\n\
+         class E extends A {\n  constructor(f) {\n    super(f, 2, \"\"); this.f = f;\n    g();\n  }\n}",
+    );
+}
+
+#[test]
+fn enums() {
+    check(
+        "enum A { B, C = 5, D, E = \"e\" }\nexport const enum F {\n  G = 1 << 2,\n  H = G | 1,\n}",
+        "var A; (function (A) { const B = 0; A[A[\"B\"] = B] = \"B\"; \
+         const C = 5; A[A[\"C\"] = C] = \"C\"; const D = C + 1; A[A[\"D\"] = D] = \"D\"; \
+         const E = \"e\"; A[\"E\"] = E; })(A || (A = {}));\n\
+         export var F; (function (F) { const G = 1 << 2; F[F[\"G\"] = G] = \"G\"; \
+         const H = G | 1; F[F[\"H\"] = H] = \"H\"; })(F || (F = {}));",
+    );
+}
+
+#[test]
+fn namespaces() {
+    check(
+        "namespace A {\n  export const b: number = 1;\n  const c = 2;\n  export function d() {}\n  \
+         export namespace E { export enum F { G } }\n  export interface H {}\n}",
+        "var A; (function (A) {\n  const b = 1;\n  const c = 2;\n  function d() {}\n  \
+         var E; (function (E) { var F; (function (F) { const G = 0; F[F[\"G\"] = G] = \"G\"; })(F || (F = {})); \
+         E.F = F; })(E || (E = {}));\n \
+         A.b = b; A.d = d; A.E = E; })(A || (A = {}));",
+    );
+}
+
+#[test]
+fn statements_are_not_merged() {
+    check(
+        "let a = b\ninterface C {}\n(d);\nlet e = f\ntype G = H\n[1].map(i);",
+        "let a = b\n;(d);\nlet e = f\n;[1].map(i);",
+    );
+}
+
+#[test]
+fn unsupported_syntax() {
+    check_error("import a = require(\"a\");", "import alias declarations");
+    check_error("export = a;", "`export =`");
+    check_error("namespace A.B {}", "qualified names");
+    check_error(
+        "namespace A { export { b }; }",
+        "can only export declarations",
+    );
+    check_error("let a: = 1;", "expected a type");
+}
+
+#[test]
+fn parse_typescript_source() {
+    let source = Source::from_bytes(
+        "interface Point { x: number; y: number }\n\
+         export const origin: Point = { x: 0, y: 0 };\n\
+         export enum Color { Red, Green }",
+    )
+    .with_typescript(true);
+    let module = Parser::new(source)
+        .parse_module(&Scope::new_global(), &mut Interner::default())
+        .expect("failed to parse TypeScript module");
+    assert_eq!(module.items().items().len(), 3);
+
+    let error = Parser::new(Source::from_bytes("let a: = 1;").with_typescript(true))
+        .parse_script(&Scope::new_global(), &mut Interner::default())
+        .expect_err("invalid TypeScript should not parse");
+    assert_eq!(
+        error.span().map(|span| span.start().column_number()),
+        Some(8)
+    );
+}
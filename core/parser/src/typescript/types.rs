@@ -0,0 +1,250 @@
+//! Skipping of TypeScript types.
+//!
+//! Types are never parsed into a tree, they are only skipped to find the code that has to be
+//! erased. The methods return the index of the token after the type, or `None` if the tokens
+//! don't form a type.
+
+use super::{Kind, Stripper};
+use boa_ast::{Keyword, Punctuator};
+
+impl Stripper<'_> {
+    /// Skips a type, like `string | Array<number>` or `T extends U ? X : Y`.
+    pub(super) fn skip_type(&self, index: usize) -> Option<usize> {
+        let mut index = self.skip_union_type(index)?;
+
+        // Conditional types.
+        if self.is_keyword(index, Keyword::Extends) && self.on_same_line(index) {
+            let extends_end = self.skip_union_type(index + 1)?;
+            if self.is(extends_end, Punctuator::Question) {
+                let true_end = self.skip_type(extends_end + 1)?;
+                if !self.is(true_end, Punctuator::Colon) {
+                    return None;
+                }
+                index = self.skip_type(true_end + 1)?;
+            }
+        }
+        Some(index)
+    }
+
+    /// Skips a union or intersection of types, like `A | B & C`.
+    fn skip_union_type(&self, mut index: usize) -> Option<usize> {
+        if self.is(index, Punctuator::Or) || self.is(index, Punctuator::And) {
+            index += 1;
+        }
+        loop {
+            index = self.skip_type_operand(index)?;
+            if self.is(index, Punctuator::Or) || self.is(index, Punctuator::And) {
+                index += 1;
+            } else {
+                return Some(index);
+            }
+        }
+    }
+
+    /// Skips a type with its prefix operators and array suffixes, like `keyof T[]`.
+    fn skip_type_operand(&self, mut index: usize) -> Option<usize> {
+        while ["keyof", "unique", "readonly", "infer"]
+            .iter()
+            .any(|operator| self.is_word(index, operator))
+            && self.starts_type(index + 1)
+        {
+            index += 1;
+        }
+
+        index = self.skip_primary_type(index)?;
+
+        // Array types and indexed access types.
+        while self.is(index, Punctuator::OpenBracket) && self.on_same_line(index) {
+            index = self.matching[index] + 1;
+        }
+        Some(index)
+    }
+
+    /// Returns `true` if the token at `index` can start a type.
+    fn starts_type(&self, index: usize) -> bool {
+        matches!(
+            self.kind(index),
+            Some(
+                Kind::Identifier
+                    | Kind::Keyword(_)
+                    | Kind::String
+                    | Kind::Literal
+                    | Kind::Template
+                    | Kind::TemplateHead
+                    | Kind::Punctuator(
+                        Punctuator::OpenParen
+                            | Punctuator::OpenBracket
+                            | Punctuator::OpenBlock
+                            | Punctuator::LessThan
+                            | Punctuator::Sub
+                    )
+            )
+        )
+    }
+
+    /// Skips a type without operators.
+    fn skip_primary_type(&self, index: usize) -> Option<usize> {
+        match self.kind(index)? {
+            // Parenthesized types and function types, like `(a: T) => U`.
+            Kind::Punctuator(Punctuator::OpenParen) => {
+                let close = self.matching[index];
+                if self.is(close + 1, Punctuator::Arrow) {
+                    self.skip_return_type(close + 2)
+                } else {
+                    Some(close + 1)
+                }
+            }
+            // Generic function types, like `<T>(a: T) => T`.
+            Kind::Punctuator(Punctuator::LessThan) => {
+                self.skip_function_type(self.skip_type_parameters(index)?)
+            }
+            // Object types and tuple types.
+            Kind::Punctuator(Punctuator::OpenBlock | Punctuator::OpenBracket) => {
+                Some(self.matching[index] + 1)
+            }
+            Kind::Punctuator(Punctuator::Sub) if self.kind(index + 1) == Some(Kind::Literal) => {
+                Some(index + 2)
+            }
+            Kind::String | Kind::Literal | Kind::Template => Some(index + 1),
+            // Template literal types, like `` `a${T}` ``.
+            Kind::TemplateHead => {
+                let mut index = index;
+                while self.kind(index) != Some(Kind::TemplateTail) {
+                    index = self.matching[index];
+                }
+                Some(index + 1)
+            }
+            // Constructor types, like `new (a: T) => U`.
+            Kind::Keyword(Keyword::New) => {
+                let mut index = index + 1;
+                if self.is(index, Punctuator::LessThan) {
+                    index = self.skip_type_parameters(index)?;
+                }
+                self.skip_function_type(index)
+            }
+            Kind::Identifier
+                if self.is_word(index, "abstract") && self.is_keyword(index + 1, Keyword::New) =>
+            {
+                self.skip_primary_type(index + 1)
+            }
+            // Type queries, like `typeof a.b`, and import types, like `import("a").B`.
+            Kind::Keyword(Keyword::TypeOf) => self.skip_type_reference(index + 1),
+            Kind::Keyword(Keyword::Import) if self.is(index + 1, Punctuator::OpenParen) => {
+                let close = self.matching[index + 1];
+                if self.is(close + 1, Punctuator::Dot) {
+                    self.skip_type_reference(close + 2)
+                } else {
+                    Some(close + 1)
+                }
+            }
+            Kind::Identifier | Kind::Keyword(_) => self.skip_type_reference(index),
+            _ => None,
+        }
+    }
+
+    /// Skips the parameters and return type of a function type at `index`, like `(a) => b`.
+    fn skip_function_type(&self, index: usize) -> Option<usize> {
+        if !self.is(index, Punctuator::OpenParen) {
+            return None;
+        }
+        let close = self.matching[index];
+        if !self.is(close + 1, Punctuator::Arrow) {
+            return None;
+        }
+        self.skip_return_type(close + 2)
+    }
+
+    /// Skips a possibly qualified type name with its type arguments, like `a.B<C>`.
+    fn skip_type_reference(&self, mut index: usize) -> Option<usize> {
+        if !self.is_name(index) {
+            return None;
+        }
+        index += 1;
+        while self.is(index, Punctuator::Dot) && self.is_name(index + 1) {
+            index += 2;
+        }
+        if self.is(index, Punctuator::LessThan)
+            && self.on_same_line(index)
+            && let Some(end) = self.skip_type_arguments(index)
+        {
+            index = end;
+        }
+        Some(index)
+    }
+
+    /// Skips a return type, which can also be a type predicate like `a is T` or
+    /// `asserts a is T`.
+    pub(super) fn skip_return_type(&self, index: usize) -> Option<usize> {
+        let is_subject = |index: usize| {
+            matches!(
+                self.kind(index),
+                Some(Kind::Identifier | Kind::Keyword(Keyword::This))
+            ) && self.on_same_line(index)
+        };
+        if self.is_word(index, "asserts") && is_subject(index + 1) {
+            if self.is_word(index + 2, "is") && self.on_same_line(index + 2) {
+                return self.skip_type(index + 3);
+            }
+            return Some(index + 2);
+        }
+        if is_subject(index) && self.is_word(index + 1, "is") && self.on_same_line(index + 1) {
+            return self.skip_type(index + 2);
+        }
+        self.skip_type(index)
+    }
+
+    /// Skips a list of type arguments, like `<string, T[]>`.
+    pub(super) fn skip_type_arguments(&self, index: usize) -> Option<usize> {
+        if !self.is(index, Punctuator::LessThan) {
+            return None;
+        }
+        let mut index = index + 1;
+        loop {
+            index = self.skip_type(index)?;
+            if self.is(index, Punctuator::Comma) {
+                index += 1;
+            } else if self.is(index, Punctuator::GreaterThan) {
+                return Some(index + 1);
+            } else {
+                return None;
+            }
+        }
+    }
+
+    /// Skips a list of type parameters, like `<const T extends U = V, in out W>`.
+    pub(super) fn skip_type_parameters(&self, index: usize) -> Option<usize> {
+        if !self.is(index, Punctuator::LessThan) {
+            return None;
+        }
+        let mut index = index + 1;
+        loop {
+            while (self.is_keyword(index, Keyword::Const)
+                || self.is_keyword(index, Keyword::In)
+                || self.is_word(index, "out"))
+                && self.kind(index + 1) == Some(Kind::Identifier)
+            {
+                index += 1;
+            }
+            if !self.is_name(index) {
+                return None;
+            }
+            index += 1;
+            if self.is_keyword(index, Keyword::Extends) {
+                index = self.skip_type(index + 1)?;
+            }
+            if self.is(index, Punctuator::Assign) {
+                index = self.skip_type(index + 1)?;
+            }
+            if self.is(index, Punctuator::Comma) {
+                index += 1;
+                if self.is(index, Punctuator::GreaterThan) {
+                    return Some(index + 1);
+                }
+            } else if self.is(index, Punctuator::GreaterThan) {
+                return Some(index + 1);
+            } else {
+                return None;
+            }
+        }
+    }
+}
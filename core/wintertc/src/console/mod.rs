@@ -64,7 +64,7 @@ pub trait Logger: Trace {
         self.log(msg, state, context)?;
 
         let stack_trace_dump = context
-            .stack_trace()
+            .call_frames()
             .map(|frame| frame.code_block().name())
             .map(JsString::to_std_string_escaped)
             .collect::<Vec<_>>();